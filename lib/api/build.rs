@@ -29,6 +29,13 @@ fn main() -> std::io::Result<()> {
             &["src/grpc/proto"], // specify the root location to search proto dependencies
         )?;
 
+    // Server streaming code of tonic-build refers to a re-export, which our tonic doesn't have
+    replace_in_file(
+        "src/grpc/qdrant.rs",
+        "tonic::codegen::tokio_stream::Stream",
+        "tonic::codegen::futures_core::Stream",
+    );
+
     // Append trait extension imports to generated gRPC output
     append_to_file("src/grpc/qdrant.rs", "use super::validate::ValidateExt;");
 
@@ -122,6 +129,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.ttl_config", ""),
+            ("CreateCollection.strict_mode_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -129,13 +138,23 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.strict_mode_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
             ("CollectionConfig.hnsw_config", ""),
             ("CollectionConfig.optimizers_config", ""),
             ("CollectionConfig.quantization_config", ""),
+            ("CollectionConfig.strict_mode_config", ""),
             ("CollectionParams.vectors_config", ""),
+            ("CollectionParamsDiff.ttl_config", ""),
+            ("TtlConfig.default_ttl_sec", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_query_limit", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("StrictModeConfig.max_timeout", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("StrictModeConfig.search_max_batch_size", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("StrictModeConfig.upsert_max_batch_size", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
+            ("StrictModeConfig.upsert_max_payload_bytes", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_filter_conditions", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
@@ -153,12 +172,12 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParamsDiffMap.map", ""),
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
+            ("TtlConfigDiff.ttl", ""),
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
             ("UpdateCollectionClusterSetupRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
         ], &[
             "ListCollectionsRequest",
-            "CollectionParamsDiff",
             "ListAliasesRequest",
             "CollectionClusterInfoRequest",
             "UpdateCollectionClusterSetupRequest",
@@ -190,6 +209,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpdatePayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpdatePayloadPoints.updates", "length(min = 1)"),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
@@ -201,6 +222,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPoints.limit", "range(min = 1)"),
             ("SearchPoints.params", ""),
             ("SearchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchPoints.mmr", ""),
+            ("Mmr.lambda", "custom = \"crate::grpc::validate::validate_f32_range_1\""),
+            ("Mmr.candidates_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
@@ -220,6 +244,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("RecommendPoints.filter", ""),
             ("RecommendPoints.params", ""),
             ("RecommendPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("RecommendPoints.mmr", ""),
             ("RecommendPoints.positive_vectors", ""),
             ("RecommendPoints.negative_vectors", ""),
             ("RecommendBatchPoints.collection_name", "length(min = 1, max = 255)"),
@@ -242,8 +267,28 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("DiscoverBatchPoints.discover_points", ""),
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("PrefetchQuery.prefetch", ""),
+            ("PrefetchQuery.filter", ""),
+            ("PrefetchQuery.params", ""),
+            ("PrefetchQuery.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPoints.prefetch", ""),
+            ("QueryPoints.filter", ""),
+            ("QueryPoints.params", ""),
+            ("QueryPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchMatrixPoints.filter", ""),
+            ("SearchMatrixPoints.sample", "custom = \"crate::grpc::validate::validate_u64_range_min_2\""),
+            ("SearchMatrixPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StreamChangesRequest.collection_name", "length(min = 1, max = 255)"),
+            ("StreamChangesRequest.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
@@ -275,6 +320,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPointsInternal.set_payload_points", ""),
             ("DeletePayloadPointsInternal.delete_payload_points", ""),
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
+            ("UpdatePayloadPointsInternal.update_payload_points", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
             ("SearchPointsInternal.search_points", ""),
@@ -290,6 +336,14 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("FacetCountsInternal.facet_counts", ""),
+            ("SparseStatisticsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("SamplePointsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("SamplePointsInternal.filter", ""),
+            ("SamplePointsInternal.limit", "range(min = 1)"),
+            ("ReadChangesInternal.collection_name", "length(min = 1, max = 255)"),
+            ("ReadChangesInternal.limit", "range(min = 1)"),
+            ("QueryPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
         ], &[])
@@ -328,3 +382,8 @@ fn append_to_file(path: &str, line: &str) {
     )
     .unwrap()
 }
+
+fn replace_in_file(path: &str, from: &str, to: &str) {
+    let content = std::fs::read_to_string(path).unwrap();
+    std::fs::write(path, content.replace(from, to)).unwrap()
+}
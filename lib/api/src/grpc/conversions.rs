@@ -3,37 +3,46 @@ use std::time::Instant;
 
 use chrono::{NaiveDateTime, TimeZone as _, Timelike};
 use segment::data_types::integer_index::IntegerIndexType;
+use segment::data_types::order_by::OrderingValue;
 use segment::data_types::text_index::TextIndexType;
 use segment::data_types::vectors::DenseVector;
+use segment::index::query_optimization::rescore_formula::parsed_formula::{
+    DecayKind, ParsedExpression, ParsedFormula, VariableId,
+};
 use segment::json_path::JsonPath;
 use segment::types::{default_quantization_ignore_value, DateTimePayloadType, FloatPayloadType};
 use tonic::Status;
 use uuid::Uuid;
 
 use super::qdrant::{
-    start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction, GeoLineString,
-    GroupId, OrderBy, Range, SparseIndices, StartFrom,
+    facet_value, order_value, start_from, BinaryQuantization, CompressionRatio, DatetimeRange,
+    Direction, FacetHit, FacetValue, GeoLineString, GroupId, OrderBy, OrderValue, Range,
+    SparseIndices, StartFrom,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::stemming_algorithm::StemmingParams;
 use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius,
+    Condition, Datatype, DecayParamsExpression, Distance, DivExpression, Expression,
+    FieldCondition, Filter, Formula, GeoBoundingBox, GeoDistance, GeoPoint, GeoPolygon, GeoRadius,
     HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams, IsEmptyCondition,
-    IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould, NamedVectors,
-    NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
-    PayloadSchemaInfo, PayloadSchemaType, PointId, PointsOperationResponse,
-    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
-    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
-    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, Struct, TextIndexParams,
-    TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector, Vectors,
-    VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould, MultExpression,
+    MultiVectorComparator, MultiVectorConfig, NamedVectors, NestedCondition,
+    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo,
+    PayloadSchemaType, PointId, PointsOperationResponse, PointsOperationResponseInternal,
+    PowExpression, ProductQuantization, QuantizationConfig, QuantizationSearchParams,
+    QuantizationType, RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint,
+    SearchParams, ShardKey, SnowballParams, SparseVectorStatistics, StemmingAlgorithm,
+    StopwordsSet, Struct, SumExpression, TextIndexParams, TokenizerType, UpdateResult,
+    UpdateResultInternal, Value, ValuesCount, Vector, Vectors, VectorsSelector,
+    WithPayloadSelector, WithVectorsSelector,
 };
 
 pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Value> {
@@ -43,7 +52,7 @@ pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Val
         .collect()
 }
 
-fn json_to_proto(json_value: serde_json::Value) -> Value {
+pub fn json_to_proto(json_value: serde_json::Value) -> Value {
     match json_value {
         serde_json::Value::Null => Value {
             kind: Some(Kind::NullValue(0)),
@@ -88,7 +97,7 @@ pub fn proto_to_payloads(proto: HashMap<String, Value>) -> Result<segment::types
     Ok(map.into())
 }
 
-fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
+pub fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
     match proto.kind {
         None => Ok(serde_json::Value::default()),
         Some(kind) => match kind {
@@ -206,6 +215,9 @@ impl From<segment::data_types::text_index::TextIndexParams> for PayloadIndexPara
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                ascii_folding: params.ascii_folding,
+                stopwords: params.stopwords.map(StopwordsSet::from),
+                stemmer: params.stemmer.map(StemmingAlgorithm::from),
             })),
         }
     }
@@ -233,6 +245,7 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
                 segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
                 segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+                segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
             }
             .into(),
             params: schema.params.map(|params| match params {
@@ -276,10 +289,91 @@ impl TryFrom<TextIndexParams> for segment::data_types::text_index::TextIndexPara
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            ascii_folding: params.ascii_folding,
+            stopwords: params.stopwords.map(TryInto::try_into).transpose()?,
+            stemmer: params.stemmer.map(TryInto::try_into).transpose()?,
         })
     }
 }
 
+/// Languages are passed by their names in gRPC, same as in REST
+fn language_from_name<T: serde::de::DeserializeOwned>(name: String) -> Result<T, Status> {
+    serde_json::from_value(serde_json::Value::String(name.clone()))
+        .map_err(|_| Status::invalid_argument(format!("unknown language: {name}")))
+}
+
+fn language_name<T: serde::Serialize>(language: T) -> String {
+    match serde_json::to_value(language) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => unreachable!("languages are serialized as strings"),
+    }
+}
+
+impl From<segment::data_types::text_index::StopwordsInterface> for StopwordsSet {
+    fn from(stopwords: segment::data_types::text_index::StopwordsInterface) -> Self {
+        let (languages, custom) = match stopwords {
+            segment::data_types::text_index::StopwordsInterface::Language(language) => {
+                (vec![language], vec![])
+            }
+            segment::data_types::text_index::StopwordsInterface::Set(set) => {
+                (set.languages, set.custom)
+            }
+        };
+        StopwordsSet {
+            languages: languages.into_iter().map(language_name).collect(),
+            custom,
+        }
+    }
+}
+
+impl TryFrom<StopwordsSet> for segment::data_types::text_index::StopwordsInterface {
+    type Error = Status;
+    fn try_from(stopwords: StopwordsSet) -> Result<Self, Self::Error> {
+        let StopwordsSet { languages, custom } = stopwords;
+        Ok(segment::data_types::text_index::StopwordsInterface::Set(
+            segment::data_types::text_index::StopwordsSet {
+                languages: languages
+                    .into_iter()
+                    .map(language_from_name)
+                    .collect::<Result<_, _>>()?,
+                custom,
+            },
+        ))
+    }
+}
+
+impl From<segment::data_types::text_index::StemmingAlgorithm> for StemmingAlgorithm {
+    fn from(stemmer: segment::data_types::text_index::StemmingAlgorithm) -> Self {
+        let stemming_params = match stemmer {
+            segment::data_types::text_index::StemmingAlgorithm::Snowball(params) => {
+                StemmingParams::Snowball(SnowballParams {
+                    language: language_name(params.language),
+                })
+            }
+        };
+        StemmingAlgorithm {
+            stemming_params: Some(stemming_params),
+        }
+    }
+}
+
+impl TryFrom<StemmingAlgorithm> for segment::data_types::text_index::StemmingAlgorithm {
+    type Error = Status;
+    fn try_from(stemmer: StemmingAlgorithm) -> Result<Self, Self::Error> {
+        match stemmer.stemming_params {
+            Some(StemmingParams::Snowball(SnowballParams { language })) => Ok(
+                segment::data_types::text_index::StemmingAlgorithm::Snowball(
+                    segment::data_types::text_index::SnowballParams {
+                        r#type: segment::data_types::text_index::Snowball::Snowball,
+                        language: language_from_name(language)?,
+                    },
+                ),
+            ),
+            None => Err(Status::invalid_argument("Malformed StemmingAlgorithm")),
+        }
+    }
+}
+
 impl TryFrom<IntegerIndexParams> for segment::data_types::integer_index::IntegerIndexParams {
     type Error = Status;
     fn try_from(params: IntegerIndexParams) -> Result<Self, Self::Error> {
@@ -324,6 +418,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
                 PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+                PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -480,12 +575,19 @@ impl From<segment::data_types::vectors::Vector> for Vector {
             segment::data_types::vectors::Vector::Dense(vector) => Self {
                 data: vector,
                 indices: None,
+                vectors_count: None,
             },
             segment::data_types::vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
                 indices: Some(SparseIndices {
                     data: vector.indices,
                 }),
+                vectors_count: None,
+            },
+            segment::data_types::vectors::Vector::MultiDense(vector) => Self {
+                vectors_count: Some(vector.vectors_count() as u32),
+                data: vector.flattened_vectors,
+                indices: None,
             },
         }
     }
@@ -495,9 +597,9 @@ impl TryFrom<Vector> for segment::data_types::vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
-        Ok(match vector.indices {
-            None => segment::data_types::vectors::Vector::Dense(vector.data),
-            Some(indices) => segment::data_types::vectors::Vector::Sparse(
+        Ok(match (vector.indices, vector.vectors_count) {
+            (None, None) => segment::data_types::vectors::Vector::Dense(vector.data),
+            (Some(indices), None) => segment::data_types::vectors::Vector::Sparse(
                 sparse::common::sparse_vector::SparseVector::new(indices.data, vector.data)
                     .map_err(|_| {
                         Status::invalid_argument(
@@ -505,10 +607,42 @@ impl TryFrom<Vector> for segment::data_types::vectors::Vector {
                         )
                     })?,
             ),
+            (None, Some(vectors_count)) => segment::data_types::vectors::Vector::MultiDense(
+                into_multi_dense_vector(vector.data, vectors_count)?,
+            ),
+            (Some(_), Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "Vector can not be both sparse and multi-dense",
+                ))
+            }
         })
     }
 }
 
+/// Restore a multi dense vector from its flattened representation
+pub fn into_multi_dense_vector(
+    flattened_vectors: DenseVector,
+    vectors_count: u32,
+) -> Result<segment::data_types::vectors::MultiDenseVector, Status> {
+    let vectors_count = vectors_count as usize;
+    if vectors_count == 0 || flattened_vectors.is_empty() {
+        return Err(Status::invalid_argument(
+            "Multi-dense vector must contain at least one vector",
+        ));
+    }
+    if flattened_vectors.len() % vectors_count != 0 {
+        return Err(Status::invalid_argument(format!(
+            "Multi-dense vector data length {} is not divisible by the vectors count {vectors_count}",
+            flattened_vectors.len(),
+        )));
+    }
+    let dim = flattened_vectors.len() / vectors_count;
+    Ok(segment::data_types::vectors::MultiDenseVector::new(
+        flattened_vectors,
+        dim,
+    ))
+}
+
 impl From<HashMap<String, segment::data_types::vectors::Vector>> for NamedVectors {
     fn from(vectors: HashMap<String, segment::data_types::vectors::Vector>) -> Self {
         Self {
@@ -747,6 +881,32 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
     }
 }
 
+impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
+    fn from(value: segment::types::MultiVectorConfig) -> Self {
+        MultiVectorConfig {
+            comparator: match value.comparator {
+                segment::types::MultiVectorComparator::MaxSim => MultiVectorComparator::MaxSim,
+            }
+            .into(),
+        }
+    }
+}
+
+impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
+    type Error = Status;
+
+    fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
+        Ok(segment::types::MultiVectorConfig {
+            comparator: match MultiVectorComparator::from_i32(value.comparator) {
+                Some(MultiVectorComparator::MaxSim) => {
+                    segment::types::MultiVectorComparator::MaxSim
+                }
+                None => return Err(Status::invalid_argument("Unknown multi vector comparator")),
+            },
+        })
+    }
+}
+
 impl From<segment::types::QuantizationConfig> for QuantizationConfig {
     fn from(value: segment::types::QuantizationConfig) -> Self {
         match value {
@@ -909,6 +1069,10 @@ impl From<segment::types::Condition> for Condition {
             segment::types::Condition::Nested(nested) => {
                 ConditionOneOf::Nested(nested.nested.into())
             }
+            segment::types::Condition::CustomIdChecker(_) => {
+                // Custom checkers are only constructed for local shards and never sent over gRPC
+                unreachable!("CustomIdChecker condition can not be converted into gRPC")
+            }
         };
 
         Self {
@@ -1200,6 +1364,59 @@ impl From<segment::types::Range<FloatPayloadType>> for Range {
     }
 }
 
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        use segment::data_types::facets::FacetValue as SegmentFacetValue;
+
+        let variant = match value {
+            SegmentFacetValue::Keyword(keyword) => facet_value::Variant::StringValue(keyword),
+            SegmentFacetValue::Int(integer) => facet_value::Variant::IntegerValue(integer),
+            SegmentFacetValue::Bool(flag) => facet_value::Variant::BoolValue(flag),
+        };
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        match value.variant {
+            Some(facet_value::Variant::StringValue(keyword)) => Ok(Self::Keyword(keyword)),
+            Some(facet_value::Variant::IntegerValue(integer)) => Ok(Self::Int(integer)),
+            Some(facet_value::Variant::BoolValue(flag)) => Ok(Self::Bool(flag)),
+            None => Err(Status::invalid_argument(
+                "Malformed FacetValue: missing variant",
+            )),
+        }
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(value: segment::data_types::facets::FacetValueHit) -> Self {
+        Self {
+            value: Some(value.value.into()),
+            count: value.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(value: FacetHit) -> Result<Self, Self::Error> {
+        let facet_value = value
+            .value
+            .ok_or_else(|| Status::invalid_argument("Malformed FacetHit: missing value"))?;
+        Ok(Self {
+            value: facet_value.try_into()?,
+            count: value.count as usize,
+        })
+    }
+}
+
 impl From<Range> for segment::types::RangeInterface {
     fn from(value: Range) -> Self {
         Self::Float(value.into())
@@ -1262,6 +1479,7 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::Integer(int) => int.into(),
                 MatchValue::Boolean(flag) => flag.into(),
                 MatchValue::Text(text) => segment::types::Match::Text(text.into()),
+                MatchValue::Phrase(phrase) => segment::types::Match::new_phrase(&phrase),
                 MatchValue::Keywords(kwds) => kwds.strings.into(),
                 MatchValue::Integers(ints) => ints.integers.into(),
                 MatchValue::ExceptIntegers(kwds) => {
@@ -1287,6 +1505,9 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::Text(segment::types::MatchText { text }) => {
                 MatchValue::Text(text)
             }
+            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase }) => {
+                MatchValue::Phrase(phrase)
+            }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Keywords(strings) => {
                     let strings = strings.into_iter().collect();
@@ -1342,6 +1563,28 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
     }
 }
 
+impl From<Option<OrderingValue>> for OrderValue {
+    fn from(value: Option<OrderingValue>) -> Self {
+        Self {
+            variant: value.map(|value| match value {
+                OrderingValue::Int(int) => order_value::Variant::Int(int),
+                OrderingValue::Float(float) => order_value::Variant::Float(float),
+                OrderingValue::Keyword(keyword) => order_value::Variant::Keyword(keyword),
+            }),
+        }
+    }
+}
+
+impl From<OrderValue> for Option<OrderingValue> {
+    fn from(value: OrderValue) -> Self {
+        value.variant.map(|variant| match variant {
+            order_value::Variant::Int(int) => OrderingValue::Int(int),
+            order_value::Variant::Float(float) => OrderingValue::Float(float),
+            order_value::Variant::Keyword(keyword) => OrderingValue::Keyword(keyword),
+        })
+    }
+}
+
 impl From<segment::data_types::order_by::StartFrom> for StartFrom {
     fn from(value: segment::data_types::order_by::StartFrom) -> Self {
         Self {
@@ -1369,6 +1612,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            appendable: hnsw_config.appendable,
         }
     }
 }
@@ -1422,22 +1666,88 @@ pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
     }
 }
 
+impl From<segment::data_types::sparse_statistics::SparseVectorStatistics>
+    for SparseVectorStatistics
+{
+    fn from(value: segment::data_types::sparse_statistics::SparseVectorStatistics) -> Self {
+        Self {
+            indexed_vector_count: value.indexed_vector_count as u64,
+            document_frequencies: value
+                .document_frequencies
+                .into_iter()
+                .map(|(dim, frequency)| (dim, frequency as u64))
+                .collect(),
+        }
+    }
+}
+
+impl From<SparseVectorStatistics>
+    for segment::data_types::sparse_statistics::SparseVectorStatistics
+{
+    fn from(value: SparseVectorStatistics) -> Self {
+        Self {
+            indexed_vector_count: value.indexed_vector_count as usize,
+            document_frequencies: value
+                .document_frequencies
+                .into_iter()
+                .map(|(dim, frequency)| (dim, frequency as usize))
+                .collect(),
+        }
+    }
+}
+
+pub fn from_grpc_datatype(
+    datatype: i32,
+) -> Result<Option<segment::types::VectorStorageDatatype>, Status> {
+    match Datatype::from_i32(datatype) {
+        None => Err(Status::invalid_argument(format!(
+            "Malformed datatype parameter, unexpected value: {datatype}"
+        ))),
+        Some(Datatype::Default) => Ok(None),
+        Some(Datatype::Float32) => Ok(Some(segment::types::VectorStorageDatatype::Float32)),
+        Some(Datatype::Float16) => Ok(Some(segment::types::VectorStorageDatatype::Float16)),
+        Some(Datatype::Uint8) => Ok(Some(segment::types::VectorStorageDatatype::Uint8)),
+    }
+}
+
+impl From<segment::types::VectorStorageDatatype> for Datatype {
+    fn from(value: segment::types::VectorStorageDatatype) -> Self {
+        match value {
+            segment::types::VectorStorageDatatype::Float32 => Datatype::Float32,
+            segment::types::VectorStorageDatatype::Float16 => Datatype::Float16,
+            segment::types::VectorStorageDatatype::Uint8 => Datatype::Uint8,
+        }
+    }
+}
+
 pub fn into_named_vector_struct(
     vector_name: Option<String>,
     vector: DenseVector,
     indices: Option<SparseIndices>,
+    vectors_count: Option<u32>,
 ) -> Result<segment::data_types::vectors::NamedVectorStruct, Status> {
-    use segment::data_types::vectors::{NamedSparseVector, NamedVector, NamedVectorStruct};
+    use segment::data_types::vectors::{
+        NamedMultiDenseVector, NamedSparseVector, NamedVector, NamedVectorStruct,
+    };
     use sparse::common::sparse_vector::SparseVector;
-    Ok(match indices {
-        Some(indices) => NamedVectorStruct::Sparse(NamedSparseVector {
+    Ok(match (indices, vectors_count) {
+        (Some(_), Some(_)) => {
+            return Err(Status::invalid_argument(
+                "Vector can not be both sparse and multi-dense",
+            ))
+        }
+        (Some(indices), None) => NamedVectorStruct::Sparse(NamedSparseVector {
             name: vector_name
                 .ok_or_else(|| Status::invalid_argument("Sparse vector must have a name"))?,
             vector: SparseVector::new(indices.data, vector).map_err(|_| {
                 Status::invalid_argument("Sparse indices does not match sparse vector conditions")
             })?,
         }),
-        None => {
+        (None, Some(vectors_count)) => NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+            name: vector_name.unwrap_or_default(),
+            vector: into_multi_dense_vector(vector, vectors_count)?,
+        }),
+        (None, None) => {
             if let Some(vector_name) = vector_name {
                 NamedVectorStruct::Dense(NamedVector {
                     name: vector_name,
@@ -1474,6 +1784,7 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            failed_preconditions: res.failed_preconditions,
         }
     }
 }
@@ -1485,6 +1796,95 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            failed_preconditions: res.failed_preconditions,
+        }
+    }
+}
+
+fn expression_to_proto(
+    expression: ParsedExpression,
+    conditions: &[segment::types::Condition],
+) -> Expression {
+    use crate::grpc::qdrant::expression::Variant;
+
+    let to_proto = |expression| expression_to_proto(expression, conditions);
+    let boxed = |expression: Box<ParsedExpression>| Box::new(to_proto(*expression));
+
+    let variant = match expression {
+        ParsedExpression::Constant(constant) => Variant::Constant(constant),
+        ParsedExpression::Variable(VariableId::Condition(index)) => {
+            Variant::Condition(conditions[index].clone().into())
+        }
+        ParsedExpression::Variable(variable) => Variant::Variable(variable.to_string()),
+        ParsedExpression::GeoDistance { origin, key } => Variant::GeoDistance(GeoDistance {
+            origin: Some(origin.into()),
+            to: key.to_string(),
+        }),
+        ParsedExpression::DatetimeKey(key) => Variant::DatetimeKey(key.to_string()),
+        ParsedExpression::Mult(expressions) => Variant::Mult(MultExpression {
+            mult: expressions.into_iter().map(to_proto).collect(),
+        }),
+        ParsedExpression::Sum(expressions) => Variant::Sum(SumExpression {
+            sum: expressions.into_iter().map(to_proto).collect(),
+        }),
+        ParsedExpression::Div {
+            left,
+            right,
+            by_zero_default,
+        } => Variant::Div(Box::new(DivExpression {
+            left: Some(boxed(left)),
+            right: Some(boxed(right)),
+            by_zero_default,
+        })),
+        ParsedExpression::Neg(expression) => Variant::Neg(boxed(expression)),
+        ParsedExpression::Abs(expression) => Variant::Abs(boxed(expression)),
+        ParsedExpression::Sqrt(expression) => Variant::Sqrt(boxed(expression)),
+        ParsedExpression::Pow { base, exponent } => Variant::Pow(Box::new(PowExpression {
+            base: Some(boxed(base)),
+            exponent: Some(boxed(exponent)),
+        })),
+        ParsedExpression::Exp(expression) => Variant::Exp(boxed(expression)),
+        ParsedExpression::Log10(expression) => Variant::Log10(boxed(expression)),
+        ParsedExpression::Ln(expression) => Variant::Ln(boxed(expression)),
+        ParsedExpression::Decay {
+            kind,
+            x,
+            target,
+            scale,
+            midpoint,
+        } => {
+            let params = Box::new(DecayParamsExpression {
+                x: Some(boxed(x)),
+                target: target.map(boxed),
+                scale: Some(scale),
+                midpoint: Some(midpoint),
+            });
+            match kind {
+                DecayKind::Lin => Variant::LinDecay(params),
+                DecayKind::Exp => Variant::ExpDecay(params),
+                DecayKind::Gauss => Variant::GaussDecay(params),
+            }
+        }
+    };
+    Expression {
+        variant: Some(variant),
+    }
+}
+
+impl From<ParsedFormula> for Formula {
+    fn from(value: ParsedFormula) -> Self {
+        let ParsedFormula {
+            payload_vars: _,
+            conditions,
+            defaults,
+            formula,
+        } = value;
+        Self {
+            expression: Some(expression_to_proto(formula, &conditions)),
+            defaults: defaults
+                .into_iter()
+                .map(|(variable, value)| (variable.to_string(), json_to_proto(value)))
+                .collect(),
         }
     }
 }
@@ -21,6 +21,12 @@ pub struct VectorParams {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Configuration for multi-vector points. If omitted - each point holds a single vector
+    #[prost(message, optional, tag = "6")]
+    pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Data type of the vectors. If omitted - float32 is used
+    #[prost(enumeration = "Datatype", optional, tag = "7")]
+    pub datatype: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -109,6 +115,9 @@ pub struct SparseVectorParams {
     /// Configuration of sparse index
     #[prost(message, optional, tag = "1")]
     pub index: ::core::option::Option<SparseIndexConfig>,
+    /// If set - apply modifier to the vector values
+    #[prost(enumeration = "Modifier", optional, tag = "2")]
+    pub modifier: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -234,6 +243,12 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    ///
+    /// Update HNSW index in place on inserts and deletes, instead of re-indexing new points in the background.
+    /// Indexed segments stay appendable, if their vectors are stored in RAM and not quantized, and the index is not on disk.
+    /// Default: false
+    #[prost(bool, optional, tag = "7")]
+    pub appendable: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -248,6 +263,22 @@ pub struct SparseIndexConfig {
     /// Store inverted index on disk. If set to false, the index will be stored in RAM.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    ///
+    /// Datatype used to store weights in the index. Weights of reduced precision are scaled into the range of each posting list.
+    #[prost(enumeration = "Datatype", optional, tag = "3")]
+    pub datatype: ::core::option::Option<i32>,
+    ///
+    /// Keep full-precision weights on disk, to rescore results of the index with reduced precision weights.
+    #[prost(bool, optional, tag = "4")]
+    pub full_precision_on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultiVectorConfig {
+    /// Comparator for multi-vector search
+    #[prost(enumeration = "MultiVectorComparator", tag = "1")]
+    pub comparator: i32,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -426,6 +457,88 @@ pub mod quantization_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TtlConfig {
+    /// Time to live of points inserted without expiration time, in seconds. If not set - such points never expire
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub default_ttl_sec: ::core::option::Option<u64>,
+    /// Top-level payload field, which holds the expiration time of the point. Default: `expire_at`
+    #[prost(string, optional, tag = "2")]
+    pub expire_at_field: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TtlConfigDiff {
+    #[prost(oneof = "ttl_config_diff::Ttl", tags = "1, 2")]
+    #[validate]
+    pub ttl: ::core::option::Option<ttl_config_diff::Ttl>,
+}
+/// Nested message and enum types in `TtlConfigDiff`.
+pub mod ttl_config_diff {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Ttl {
+        #[prost(message, tag = "1")]
+        Enabled(super::TtlConfig),
+        #[prost(message, tag = "2")]
+        Disabled(super::Disabled),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Max allowed `limit` parameter
+    #[prost(uint32, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub max_query_limit: ::core::option::Option<u32>,
+    /// Max allowed `offset` parameter
+    #[prost(uint32, optional, tag = "3")]
+    pub max_query_offset: ::core::option::Option<u32>,
+    /// Max allowed `timeout` parameter, in seconds
+    #[prost(uint32, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub max_timeout: ::core::option::Option<u32>,
+    /// Allow usage of unindexed fields in retrieval based filters
+    #[prost(bool, optional, tag = "5")]
+    pub unindexed_filtering_retrieve: ::core::option::Option<bool>,
+    /// Allow usage of unindexed fields in filtered updates
+    #[prost(bool, optional, tag = "6")]
+    pub unindexed_filtering_update: ::core::option::Option<bool>,
+    /// Max HNSW `ef` value allowed in search parameters
+    #[prost(uint32, optional, tag = "7")]
+    pub search_max_hnsw_ef: ::core::option::Option<u32>,
+    /// Whether exact search is allowed
+    #[prost(bool, optional, tag = "8")]
+    pub search_allow_exact: ::core::option::Option<bool>,
+    /// Max number of requests in a single batch search request
+    #[prost(uint32, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub search_max_batch_size: ::core::option::Option<u32>,
+    /// Max number of points in a single upsert or vector update request
+    #[prost(uint32, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub upsert_max_batch_size: ::core::option::Option<u32>,
+    /// Max size of a payload of a single point, in bytes
+    #[prost(uint64, optional, tag = "11")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub upsert_max_payload_bytes: ::core::option::Option<u64>,
+    /// Max number of conditions in a filter, including nested ones
+    #[prost(uint32, optional, tag = "12")]
+    #[validate(custom = "crate::grpc::validate::validate_u32_range_min_1")]
+    pub max_filter_conditions: ::core::option::Option<u32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateCollection {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -478,6 +591,14 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Expiration of points
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
+    /// Strict mode limits of requests
+    #[prost(message, optional, tag = "18")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -515,6 +636,10 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New strict mode limits of requests
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -571,6 +696,9 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Expiration of points
+    #[prost(message, optional, tag = "11")]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -589,6 +717,10 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// Expiration of points. Use `disabled` to turn expiration off
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub ttl_config: ::core::option::Option<TtlConfigDiff>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -613,6 +745,10 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Strict mode limits of requests
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -630,6 +766,52 @@ pub struct TextIndexParams {
     /// Maximal token length
     #[prost(uint64, optional, tag = "4")]
     pub max_token_len: ::core::option::Option<u64>,
+    /// If true - remove diacritics from tokens
+    #[prost(bool, optional, tag = "5")]
+    pub ascii_folding: ::core::option::Option<bool>,
+    /// Stopwords to exclude from the index and queries
+    #[prost(message, optional, tag = "6")]
+    pub stopwords: ::core::option::Option<StopwordsSet>,
+    /// Algorithm to reduce tokens to their stems
+    #[prost(message, optional, tag = "7")]
+    pub stemmer: ::core::option::Option<StemmingAlgorithm>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopwordsSet {
+    /// Languages of predefined stopwords
+    #[prost(string, repeated, tag = "1")]
+    pub languages: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Custom stopwords
+    #[prost(string, repeated, tag = "2")]
+    pub custom: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StemmingAlgorithm {
+    #[prost(oneof = "stemming_algorithm::StemmingParams", tags = "1")]
+    pub stemming_params: ::core::option::Option<stemming_algorithm::StemmingParams>,
+}
+/// Nested message and enum types in `StemmingAlgorithm`.
+pub mod stemming_algorithm {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum StemmingParams {
+        /// Snowball stemmer
+        #[prost(message, tag = "1")]
+        Snowball(super::SnowballParams),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnowballParams {
+    /// Language of the stemmer
+    #[prost(string, tag = "1")]
+    pub language: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -887,6 +1069,25 @@ pub struct ShardTransferInfo {
     /// If `true` transfer is a synchronization of a replicas; If `false` transfer is a moving of a shard from one peer to another
     #[prost(bool, tag = "4")]
     pub sync: bool,
+    /// Target shard id, if points are migrated into another shard by resharding
+    #[prost(uint32, optional, tag = "5")]
+    pub to_shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReshardingInfo {
+    /// Shard which is added or removed
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Peer which drives the resharding operation
+    #[prost(uint64, tag = "2")]
+    pub peer_id: u64,
+    /// User-defined shard key of the resharded shards
+    #[prost(message, optional, tag = "3")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    #[prost(enumeration = "ReshardingDirection", tag = "4")]
+    pub direction: i32,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -907,6 +1108,9 @@ pub struct CollectionClusterInfoResponse {
     /// Shard transfers
     #[prost(message, repeated, tag = "5")]
     pub shard_transfers: ::prost::alloc::vec::Vec<ShardTransferInfo>,
+    /// Resharding operations
+    #[prost(message, repeated, tag = "6")]
+    pub resharding_operations: ::prost::alloc::vec::Vec<ReshardingInfo>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -936,6 +1140,23 @@ pub struct RestartTransfer {
     #[prost(enumeration = "ShardTransferMethod", tag = "4")]
     pub method: i32,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartResharding {
+    #[prost(enumeration = "ReshardingDirection", tag = "1")]
+    pub direction: i32,
+    /// Peer to drive resharding and to place the new shard on. If not specified - selected automatically
+    #[prost(uint64, optional, tag = "2")]
+    pub peer_id: ::core::option::Option<u64>,
+    /// User-defined shard key to reshard the shards of
+    #[prost(message, optional, tag = "3")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbortResharding {}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -985,7 +1206,7 @@ pub struct UpdateCollectionClusterSetupRequest {
     pub timeout: ::core::option::Option<u64>,
     #[prost(
         oneof = "update_collection_cluster_setup_request::Operation",
-        tags = "2, 3, 4, 5, 7, 8, 9"
+        tags = "2, 3, 4, 5, 7, 8, 9, 10, 11"
     )]
     #[validate]
     pub operation: ::core::option::Option<
@@ -1012,6 +1233,10 @@ pub mod update_collection_cluster_setup_request {
         DeleteShardKey(super::DeleteShardKey),
         #[prost(message, tag = "9")]
         RestartTransfer(super::RestartTransfer),
+        #[prost(message, tag = "10")]
+        StartResharding(super::StartResharding),
+        #[prost(message, tag = "11")]
+        AbortResharding(super::AbortResharding),
     }
 }
 #[derive(serde::Serialize)]
@@ -1102,6 +1327,91 @@ impl Distance {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum Datatype {
+    Default = 0,
+    Float32 = 1,
+    Uint8 = 2,
+    Float16 = 3,
+}
+impl Datatype {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Datatype::Default => "Default",
+            Datatype::Float32 => "Float32",
+            Datatype::Uint8 => "Uint8",
+            Datatype::Float16 => "Float16",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Default" => Some(Self::Default),
+            "Float32" => Some(Self::Float32),
+            "Uint8" => Some(Self::Uint8),
+            "Float16" => Some(Self::Float16),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Modifier {
+    None = 0,
+    /// Apply Inverse Document Frequency
+    Idf = 1,
+}
+impl Modifier {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Modifier::None => "None",
+            Modifier::Idf => "Idf",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "None" => Some(Self::None),
+            "Idf" => Some(Self::Idf),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MultiVectorComparator {
+    MaxSim = 0,
+}
+impl MultiVectorComparator {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorComparator::MaxSim => "MaxSim",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MaxSim" => Some(Self::MaxSim),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum CollectionStatus {
     UnknownCollectionStatus = 0,
     /// All segments are ready
@@ -1147,6 +1457,7 @@ pub enum PayloadSchemaType {
     Text = 5,
     Bool = 6,
     Datetime = 7,
+    Uuid = 8,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1163,6 +1474,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::Uuid => "Uuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1176,6 +1488,7 @@ impl PayloadSchemaType {
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
+            "Uuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -1360,6 +1673,35 @@ impl ReplicaState {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ReshardingDirection {
+    /// Add a new shard and move a part of the points into it
+    Up = 0,
+    /// Remove the last shard and move its points into the remaining shards
+    Down = 1,
+}
+impl ReshardingDirection {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReshardingDirection::Up => "Up",
+            ReshardingDirection::Down => "Down",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Up" => Some(Self::Up),
+            "Down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ShardTransferMethod {
     /// Stream shard records in batches
     StreamRecords = 0,
@@ -3594,6 +3936,9 @@ pub struct Vector {
     pub data: ::prost::alloc::vec::Vec<f32>,
     #[prost(message, optional, tag = "2")]
     pub indices: ::core::option::Option<SparseIndices>,
+    /// Number of vectors in a multi-vector, `data` holds all of them flattened
+    #[prost(uint32, optional, tag = "3")]
+    pub vectors_count: ::core::option::Option<u32>,
 }
 /// ---------------------------------------------
 /// ----------------- ShardKeySelector ----------
@@ -3606,6 +3951,23 @@ pub struct ShardKeySelector {
     #[prost(message, repeated, tag = "1")]
     pub shard_keys: ::prost::alloc::vec::Vec<ShardKey>,
 }
+/// ---------------------------------------------
+/// ---------------- Preconditions --------------
+/// ---------------------------------------------
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointPrecondition {
+    /// Id of the point to check the precondition for
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Only update the point, if its current version is exactly this one
+    #[prost(uint64, optional, tag = "2")]
+    pub if_version: ::core::option::Option<u64>,
+    /// Only update the point, if it does not exist yet
+    #[prost(bool, optional, tag = "3")]
+    pub if_not_exists: ::core::option::Option<bool>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3626,6 +3988,9 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "6")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3648,6 +4013,9 @@ pub struct DeletePoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "6")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3759,6 +4127,9 @@ pub struct SetPayloadPoints {
     /// Option for indicate property of payload
     #[prost(string, optional, tag = "8")]
     pub key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "9")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3784,6 +4155,80 @@ pub struct DeletePayloadPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "7")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "8")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadUpdate {
+    /// Path to the updated value, e.g. `a.b`. Array elements can't be addressed
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(oneof = "payload_update::Operator", tags = "2, 3, 4, 5, 6, 7, 8")]
+    pub operator: ::core::option::Option<payload_update::Operator>,
+}
+/// Nested message and enum types in `PayloadUpdate`.
+pub mod payload_update {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operator {
+        /// Add the number to the current value. Missing value is treated as `0`
+        #[prost(message, tag = "2")]
+        Increment(super::Value),
+        /// Replace the current value with the number, if the number is smaller or the value is missing
+        #[prost(message, tag = "3")]
+        Min(super::Value),
+        /// Replace the current value with the number, if the number is larger or the value is missing
+        #[prost(message, tag = "4")]
+        Max(super::Value),
+        /// Append the value to the array. Missing array is created
+        #[prost(message, tag = "5")]
+        Push(super::Value),
+        /// Remove all elements, equal to the value, from the array
+        #[prost(message, tag = "6")]
+        Pull(super::Value),
+        /// Append the value to the array, unless it already contains an equal one. Missing array is created
+        #[prost(message, tag = "7")]
+        AddToSet(super::Value),
+        /// Replace the current value. Together with a `condition` works as compare-and-set
+        #[prost(message, tag = "8")]
+        Set(super::Value),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Updates to apply to the payload of each point, in order
+    #[prost(message, repeated, tag = "3")]
+    #[validate(length(min = 1))]
+    pub updates: ::prost::alloc::vec::Vec<PayloadUpdate>,
+    /// Affected points
+    #[prost(message, optional, tag = "4")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Only update points, which payload matches this condition at the moment of the update
+    #[prost(message, optional, tag = "5")]
+    pub condition: ::core::option::Option<Filter>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "6")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "8")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3806,6 +4251,9 @@ pub struct ClearPayloadPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions
+    #[prost(message, repeated, tag = "6")]
+    pub preconditions: ::prost::alloc::vec::Vec<PointPrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3979,6 +4427,20 @@ pub struct QuantizationSearchParams {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Mmr {
+    /// Balance between relevance and diversity of results, from 0 to 1. `1` only takes relevance into account, `0` only diversity. Default is 0.5.
+    #[prost(float, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_f32_range_1")]
+    pub lambda: ::core::option::Option<f32>,
+    /// Number of best candidates to pick results from. Default is 100. At least `limit + offset` candidates are always considered.
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub candidates_limit: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchParams {
     ///
     /// Params relevant to HNSW index. Size of the beam in a beam-search.
@@ -4052,6 +4514,13 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Number of vectors in a multi-vector query, `vector` holds all of them flattened
+    #[prost(uint32, optional, tag = "16")]
+    pub vectors_count: ::core::option::Option<u32>,
+    /// Rerank results to make them diverse, using Maximal Marginal Relevance
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub mmr: ::core::option::Option<Mmr>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4218,6 +4687,12 @@ pub struct ScrollPoints {
     /// Order the records by a payload field
     #[prost(message, optional, tag = "10")]
     pub order_by: ::core::option::Option<OrderBy>,
+    /// Order the records by these payload fields, when values of the previous fields are equal
+    #[prost(message, repeated, tag = "11")]
+    pub then_by: ::prost::alloc::vec::Vec<OrderBy>,
+    /// Continue ordered scrolling after the last point of a previous page, use `next_page_cursor` of the previous response
+    #[prost(string, optional, tag = "12")]
+    pub cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4297,6 +4772,10 @@ pub struct RecommendPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Rerank results to make them diverse, using Maximal Marginal Relevance
+    #[prost(message, optional, tag = "21")]
+    #[validate]
+    pub mmr: ::core::option::Option<Mmr>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4511,29 +4990,354 @@ pub struct DiscoverBatchPoints {
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
 }
-#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CountPoints {
-    /// name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions
-    #[prost(message, optional, tag = "2")]
-    #[validate]
-    pub filter: ::core::option::Option<Filter>,
-    /// If `true` - return exact count, if `false` - return approximate count
-    #[prost(bool, optional, tag = "3")]
-    pub exact: ::core::option::Option<bool>,
-    /// Options for specifying read consistency guarantees
+pub struct SampleParams {
+    /// Method of sampling points
+    #[prost(enumeration = "Sample", tag = "1")]
+    pub method: i32,
+    /// Seed of the sampling. The same seed gives the same points while data is unchanged. If missing, a random seed is used.
+    #[prost(uint64, optional, tag = "2")]
+    pub seed: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Query {
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4")]
+    pub variant: ::core::option::Option<query::Variant>,
+}
+/// Nested message and enum types in `Query`.
+pub mod query {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Find the nearest neighbors to this vector
+        #[prost(message, tag = "1")]
+        Nearest(super::Vector),
+        /// Fuse results of prefetches
+        #[prost(enumeration = "super::Fusion", tag = "2")]
+        Fusion(i32),
+        /// Sample points instead of scoring them. If there are prefetches, samples their results.
+        #[prost(message, tag = "3")]
+        Sample(super::SampleParams),
+        /// Score results of prefetches with an expression, which may use their scores and payload values
+        #[prost(message, tag = "4")]
+        Formula(super::Formula),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    /// Expression to score each candidate with
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    /// Values of variables, which are used if a point has no value of its own. Keys are payload keys or score references, e.g. `$score\[1\]`.
+    #[prost(map = "string, message", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(
+        oneof = "expression::Variant",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19"
+    )]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(double, tag = "1")]
+        Constant(f64),
+        /// Payload key, or score of the point in a prefetch: `$score` for the first prefetch, `$score\[<index>\]` for any of them
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// Payload condition, 1 if the point satisfies it, 0 otherwise
+        #[prost(message, tag = "3")]
+        Condition(super::Condition),
+        #[prost(message, tag = "4")]
+        GeoDistance(super::GeoDistance),
+        /// RFC 3339 datetime, evaluated to seconds since UNIX epoch
+        #[prost(string, tag = "5")]
+        Datetime(::prost::alloc::string::String),
+        /// Payload key with a datetime, evaluated to seconds since UNIX epoch
+        #[prost(string, tag = "6")]
+        DatetimeKey(::prost::alloc::string::String),
+        #[prost(message, tag = "7")]
+        Mult(super::MultExpression),
+        #[prost(message, tag = "8")]
+        Sum(super::SumExpression),
+        #[prost(message, tag = "9")]
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        #[prost(message, tag = "10")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "11")]
+        Abs(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "12")]
+        Sqrt(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "13")]
+        Pow(::prost::alloc::boxed::Box<super::PowExpression>),
+        #[prost(message, tag = "14")]
+        Exp(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "15")]
+        Log10(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "16")]
+        Ln(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "17")]
+        ExpDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        #[prost(message, tag = "18")]
+        GaussDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        #[prost(message, tag = "19")]
+        LinDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    /// Point to measure the distance from
+    #[prost(message, optional, tag = "1")]
+    pub origin: ::core::option::Option<GeoPoint>,
+    /// Payload key with the geo point to measure the distance to. Distance is in meters.
+    #[prost(string, tag = "2")]
+    pub to: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Result of the division by zero. If missing, division by zero fails the query.
+    #[prost(double, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PowExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub base: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub exponent: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParamsExpression {
+    /// Value to apply the decay to
+    #[prost(message, optional, boxed, tag = "1")]
+    pub x: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Value, at which the decay is the highest. Default is 0.
+    #[prost(message, optional, boxed, tag = "2")]
+    pub target: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Distance from the target, at which the decay equals to `midpoint`. Must be positive. Default is 1.
+    #[prost(double, optional, tag = "3")]
+    pub scale: ::core::option::Option<f64>,
+    /// Value of the decay at `scale` distance from the target. Must be between 0 and 1 exclusive. Default is 0.5.
+    #[prost(double, optional, tag = "4")]
+    pub midpoint: ::core::option::Option<f64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrefetchQuery {
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[prost(message, repeated, tag = "1")]
+    #[validate]
+    pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
+    /// Query to perform on this stage
+    #[prost(message, optional, tag = "2")]
+    pub query: ::core::option::Option<Query>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "3")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "4")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search params for when there is no prefetch.
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    #[prost(float, optional, tag = "6")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Max number of points. Default is 10.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
+    /// Query to perform. If missing, results of the only prefetch are returned.
+    #[prost(message, optional, tag = "3")]
+    pub query: ::core::option::Option<Query>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "4")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions. Applied to prefetches as well.
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search params for when there is no prefetch.
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    #[prost(float, optional, tag = "7")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Max number of points. Default is 10.
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Offset of the result. Skip this many points. Default is 0.
+    #[prost(uint64, optional, tag = "9")]
+    pub offset: ::core::option::Option<u64>,
+    /// Options for specifying which vectors to include into the response.
+    #[prost(message, optional, tag = "10")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Options for specifying which payload to include or not.
+    #[prost(message, optional, tag = "11")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying read consistency guarantees.
+    #[prost(message, optional, tag = "12")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards.
+    #[prost(message, optional, tag = "13")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "14")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// If `true` - return exact count, if `false` - return approximate count
+    #[prost(bool, optional, tag = "3")]
+    pub exact: ::core::option::Option<bool>,
+    /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "4")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCounts {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of the facet. Requires a keyword, integer, bool or uuid index, or a range index to count `ranges`.
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of values to return. Default is 10.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// If `true` - return exact counts, if `false` - estimate the counts from the payload index. Default is false.
+    #[prost(bool, optional, tag = "5")]
+    pub exact: ::core::option::Option<bool>,
+    /// Count points in these numeric ranges, instead of counting individual values.
+    #[prost(message, repeated, tag = "6")]
+    pub ranges: ::prost::alloc::vec::Vec<Range>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards.
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many points to select and search within. Default is 10.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_2")]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per sample to find. Default is all other points of the sample.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Use these points instead of sampling. Missing points are skipped.
+    #[prost(message, repeated, tag = "9")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -4681,6 +5485,29 @@ pub struct UpdateBatchPoints {
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamChangesRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Position to start reading each of the shards from, as returned in `next_offset` of the previous batch of the shard. If empty, all shards are read from their oldest available position. Positions are valid on every replica of the shard
+    #[prost(map = "uint32, message", tag = "2")]
+    pub offsets: ::std::collections::HashMap<u32, ChangesOffset>,
+    /// Maximal number of operations to read from each shard at once. Default: 100
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Options for specifying which payload to include with the upserted points. Default: all
+    #[prost(message, optional, tag = "4")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include with the upserted points. Default: none
+    #[prost(message, optional, tag = "5")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -4701,6 +5528,20 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Points, which were skipped because their preconditions didn't hold
+    #[prost(message, repeated, tag = "4")]
+    pub failed_preconditions: ::prost::alloc::vec::Vec<FailedPrecondition>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailedPrecondition {
+    /// Point id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Current version of the point, absent if the point doesn't exist
+    #[prost(uint64, optional, tag = "2")]
+    pub version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4830,6 +5671,9 @@ pub struct ScrollResponse {
     /// Time spent to process
     #[prost(double, tag = "3")]
     pub time: f64,
+    /// Use this cursor for the next query with `order_by`
+    #[prost(string, optional, tag = "4")]
+    pub next_page_cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4851,6 +5695,34 @@ pub struct RetrievedPoint {
     /// Shard key
     #[prost(message, optional, tag = "5")]
     pub shard_key: ::core::option::Option<ShardKey>,
+    /// Values of the `order_by` keys, if the points are ordered
+    #[prost(message, repeated, tag = "6")]
+    pub order_values: ::prost::alloc::vec::Vec<OrderValue>,
+}
+/// Value of an `order_by` key, no variant is set if the point has no value for the key
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderValue {
+    #[prost(oneof = "order_value::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<order_value::Variant>,
+}
+/// Nested message and enum types in `OrderValue`.
+pub mod order_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Integer or datetime value
+        #[prost(int64, tag = "1")]
+        Int(i64),
+        /// Float value
+        #[prost(double, tag = "2")]
+        Float(f64),
+        /// Keyword value
+        #[prost(string, tag = "3")]
+        Keyword(::prost::alloc::string::String),
+    }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4915,12 +5787,200 @@ pub struct RecommendGroupsResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct QueryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValue {
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
+}
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// String value from the facet
+        #[prost(string, tag = "1")]
+        StringValue(::prost::alloc::string::String),
+        /// Integer value from the facet
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+        /// Boolean value from the facet
+        #[prost(bool, tag = "3")]
+        BoolValue(bool),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetHit {
+    /// Value from the facet
+    #[prost(message, optional, tag = "1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetRangeHit {
+    /// Range from the request
+    #[prost(message, optional, tag = "1")]
+    pub range: ::core::option::Option<Range>,
+    /// Number of points with at least one value in the range
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponse {
+    /// Values with the most points, ordered by the number of points
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Number of points in each of the requested ranges, in the same order
+    #[prost(message, repeated, tag = "2")]
+    pub ranges: ::prost::alloc::vec::Vec<FacetRangeHit>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPair {
+    /// First element of the pair
+    #[prost(message, optional, tag = "1")]
+    pub a: ::core::option::Option<PointId>,
+    /// Second element of the pair
+    #[prost(message, optional, tag = "2")]
+    pub b: ::core::option::Option<PointId>,
+    /// Score of the pair
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairs {
+    /// List of pairs of points with scores
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsets {
+    /// Row indices of the matrix
+    #[prost(uint64, repeated, tag = "1")]
+    pub offsets_row: ::prost::alloc::vec::Vec<u64>,
+    /// Column indices of the matrix
+    #[prost(uint64, repeated, tag = "2")]
+    pub offsets_col: ::prost::alloc::vec::Vec<u64>,
+    /// Scores associated with matrix coordinates
+    #[prost(float, repeated, tag = "3")]
+    pub scores: ::prost::alloc::vec::Vec<f32>,
+    /// Ids of the points in order
+    #[prost(message, repeated, tag = "4")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixPairs>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsetsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixOffsets>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangesClock {
+    /// Peer, which the clock belongs to
+    #[prost(uint64, tag = "1")]
+    pub peer_id: u64,
+    /// Id of the clock on the peer
+    #[prost(uint32, tag = "2")]
+    pub clock_id: u32,
+    /// Last read tick of the clock
+    #[prost(uint64, tag = "3")]
+    pub clock_tick: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangesOffset {
+    /// Last read tick of each of the clocks, which updated the shard
+    #[prost(message, repeated, tag = "1")]
+    pub clocks: ::prost::alloc::vec::Vec<ChangesClock>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointChange {
+    /// Position of the last operation, which made the change, in the log of the replica which served the batch. Only comparable within the batch
+    #[prost(uint64, tag = "1")]
+    pub position: u64,
+    /// Type of the change
+    #[prost(enumeration = "ChangeType", tag = "2")]
+    pub change_type: i32,
+    /// Id of the changed point
+    #[prost(message, optional, tag = "3")]
+    pub id: ::core::option::Option<PointId>,
+    /// Current payload of the upserted point
+    #[prost(map = "string, message", tag = "4")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Current vectors of the upserted point
+    #[prost(message, optional, tag = "5")]
+    pub vectors: ::core::option::Option<Vectors>,
+    /// Filter of the operation, which changed the points
+    #[prost(message, optional, tag = "6")]
+    pub filter: ::core::option::Option<Filter>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangesBatch {
+    /// Id of the shard, which was changed
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Changes, reduced to their final effect and ordered by their position
+    #[prost(message, repeated, tag = "2")]
+    pub changes: ::prost::alloc::vec::Vec<PointChange>,
+    /// Position to resume reading the shard from
+    #[prost(message, optional, tag = "3")]
+    pub next_offset: ::core::option::Option<ChangesOffset>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5047,7 +6107,7 @@ pub struct FieldCondition {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -5080,6 +6140,9 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match phrase text
+        #[prost(string, tag = "9")]
+        Phrase(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -5311,6 +6374,7 @@ pub enum FieldType {
     Text = 4,
     Bool = 5,
     Datetime = 6,
+    Uuid = 7,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5326,6 +6390,7 @@ impl FieldType {
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::Uuid => "FieldTypeUuid",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5338,6 +6403,7 @@ impl FieldType {
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeUuid" => Some(Self::Uuid),
             _ => None,
         }
     }
@@ -5405,6 +6471,60 @@ impl RecommendStrategy {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum Fusion {
+    /// Reciprocal Rank Fusion
+    Rrf = 0,
+    /// Distribution-Based Score Fusion
+    Dbsf = 1,
+}
+impl Fusion {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Fusion::Rrf => "RRF",
+            Fusion::Dbsf => "DBSF",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RRF" => Some(Self::Rrf),
+            "DBSF" => Some(Self::Dbsf),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Sample {
+    /// Uniformly random points
+    Random = 0,
+}
+impl Sample {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Sample::Random => "Random",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum UpdateStatus {
     UnknownUpdateStatus = 0,
     /// Update is received, but not processed yet
@@ -5438,6 +6558,46 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChangeType {
+    UnknownChangeType = 0,
+    /// Point was inserted or updated, `id` and the current state of the point are set
+    UpsertChange = 1,
+    /// Point was deleted, `id` is set
+    DeleteChange = 2,
+    /// Points matching `filter` at the moment of the operation were updated
+    UpdateByFilterChange = 3,
+    /// Points matching `filter` at the moment of the operation were deleted
+    DeleteByFilterChange = 4,
+}
+impl ChangeType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ChangeType::UnknownChangeType => "UnknownChangeType",
+            ChangeType::UpsertChange => "UpsertChange",
+            ChangeType::DeleteChange => "DeleteChange",
+            ChangeType::UpdateByFilterChange => "UpdateByFilterChange",
+            ChangeType::DeleteByFilterChange => "DeleteByFilterChange",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownChangeType" => Some(Self::UnknownChangeType),
+            "UpsertChange" => Some(Self::UpsertChange),
+            "DeleteChange" => Some(Self::DeleteChange),
+            "UpdateByFilterChange" => Some(Self::UpdateByFilterChange),
+            "DeleteByFilterChange" => Some(Self::DeleteByFilterChange),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -5752,6 +6912,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Update payload values of points with atomic operators
+        pub async fn update_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdatePayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpdatePayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "UpdatePayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Create index for field in collection
         pub async fn create_field_index(
             &mut self,
@@ -6090,6 +7277,130 @@ pub mod points_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Universally query points. Covers nearest neighbors search, re-scoring of prefetched candidates and fusion of several prefetches, e.g. for hybrid search.
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Query");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Query"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Count points for each value of a payload field, among the points which satisfy the filtering conditions
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Facet");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute distance matrix for sampled points with a pair based output format
+        pub async fn search_matrix_pairs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixPairs",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixPairs"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute distance matrix for sampled points with an offset based output format
+        pub async fn search_matrix_offsets(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixOffsets",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Stream changes of the collection, made by update operations.
+        /// Changes are read from an active replica of each of the shards, and streamed as they happen
+        pub async fn stream_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamChangesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ChangesBatch>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/StreamChanges",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "StreamChanges"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -6178,6 +7489,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Update payload values of points with atomic operators
+        async fn update_payload(
+            &self,
+            request: tonic::Request<super::UpdatePayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Create index for field in collection
         async fn create_field_index(
             &self,
@@ -6299,6 +7619,52 @@ pub mod points_server {
             tonic::Response<super::UpdateBatchResponse>,
             tonic::Status,
         >;
+        ///
+        /// Universally query points. Covers nearest neighbors search, re-scoring of prefetched candidates and fusion of several prefetches, e.g. for hybrid search.
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        ///
+        /// Count points for each value of a payload field, among the points which satisfy the filtering conditions
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCounts>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        ///
+        /// Compute distance matrix for sampled points with a pair based output format
+        async fn search_matrix_pairs(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute distance matrix for sampled points with an offset based output format
+        async fn search_matrix_offsets(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamChanges method.
+        type StreamChangesStream: tonic::codegen::futures_core::Stream<
+                Item = std::result::Result<super::ChangesBatch, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        /// Stream changes of the collection, made by update operations.
+        /// Changes are read from an active replica of each of the shards, and streamed as they happen
+        async fn stream_changes(
+            &self,
+            request: tonic::Request<super::StreamChangesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamChangesStream>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -6659,11 +8025,57 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPayloadPoints>,
+                            request: tonic::Request<super::SetPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::overwrite_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = OverwritePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/DeletePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DeletePayloadPoints>
+                    for DeletePayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::overwrite_payload(&inner, request).await
+                                <T as Points>::delete_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6675,7 +8087,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = OverwritePayloadSvc(inner);
+                        let method = DeletePayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6691,13 +8103,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/DeletePayload" => {
+                "/qdrant.Points/ClearPayload" => {
                     #[allow(non_camel_case_types)]
-                    struct DeletePayloadSvc<T: Points>(pub Arc<T>);
+                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::DeletePayloadPoints>
-                    for DeletePayloadSvc<T> {
+                    > tonic::server::UnaryService<super::ClearPayloadPoints>
+                    for ClearPayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6705,11 +8117,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeletePayloadPoints>,
+                            request: tonic::Request<super::ClearPayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::delete_payload(&inner, request).await
+                                <T as Points>::clear_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6721,7 +8133,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeletePayloadSvc(inner);
+                        let method = ClearPayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6737,13 +8149,13 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/ClearPayload" => {
+                "/qdrant.Points/UpdatePayload" => {
                     #[allow(non_camel_case_types)]
-                    struct ClearPayloadSvc<T: Points>(pub Arc<T>);
+                    struct UpdatePayloadSvc<T: Points>(pub Arc<T>);
                     impl<
                         T: Points,
-                    > tonic::server::UnaryService<super::ClearPayloadPoints>
-                    for ClearPayloadSvc<T> {
+                    > tonic::server::UnaryService<super::UpdatePayloadPoints>
+                    for UpdatePayloadSvc<T> {
                         type Response = super::PointsOperationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -6751,11 +8163,11 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ClearPayloadPoints>,
+                            request: tonic::Request<super::UpdatePayloadPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::clear_payload(&inner, request).await
+                                <T as Points>::update_payload(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6767,7 +8179,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ClearPayloadSvc(inner);
+                        let method = UpdatePayloadSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7365,6 +8777,233 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/Query" => {
+                    #[allow(non_camel_case_types)]
+                    struct QuerySvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPoints>
+                    for QuerySvc<T> {
+                        type Response = super::QueryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::query(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QuerySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCounts>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixPairs" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixPairsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixPairsSvc<T> {
+                        type Response = super::SearchMatrixPairsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchMatrixPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_matrix_pairs(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchMatrixPairsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixOffsets" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixOffsetsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixOffsetsSvc<T> {
+                        type Response = super::SearchMatrixOffsetsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchMatrixPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_matrix_offsets(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchMatrixOffsetsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/StreamChanges" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamChangesSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ServerStreamingService<super::StreamChangesRequest>
+                    for StreamChangesSvc<T> {
+                        type Response = super::ChangesBatch;
+                        type ResponseStream = T::StreamChangesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamChangesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::stream_changes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamChangesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -7537,6 +9176,19 @@ pub struct ClearPayloadPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePayloadPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub update_payload_points: ::core::option::Option<UpdatePayloadPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollectionInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -7587,6 +9239,9 @@ pub struct UpdateResultInternal {
     pub status: i32,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Points, which were skipped because their preconditions didn't hold
+    #[prost(message, repeated, tag = "4")]
+    pub failed_preconditions: ::prost::alloc::vec::Vec<FailedPrecondition>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -7732,26 +9387,131 @@ pub struct CoreSearchPoints {
     pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(message, optional, tag = "10")]
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
-    #[prost(message, optional, tag = "11")]
-    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    #[prost(message, optional, tag = "11")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoreSearchBatchPointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub search_points: ::prost::alloc::vec::Vec<CoreSearchPoints>,
+    #[prost(uint32, optional, tag = "3")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(uint64, optional, tag = "4")]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryShardPoints {
+    #[prost(message, repeated, tag = "1")]
+    pub prefetch: ::prost::alloc::vec::Vec<query_shard_points::Prefetch>,
+    #[prost(message, optional, tag = "2")]
+    pub query: ::core::option::Option<query_shard_points::Query>,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(float, optional, tag = "4")]
+    pub score_threshold: ::core::option::Option<f32>,
+    #[prost(uint64, tag = "5")]
+    pub limit: u64,
+    #[prost(uint64, tag = "6")]
+    pub offset: u64,
+    #[prost(message, optional, tag = "7")]
+    pub params: ::core::option::Option<SearchParams>,
+    #[prost(message, optional, tag = "8")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    #[prost(message, optional, tag = "9")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+}
+/// Nested message and enum types in `QueryShardPoints`.
+pub mod query_shard_points {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Query {
+        /// Name of the vector to score against
+        #[prost(string, optional, tag = "3")]
+        pub vector_name: ::core::option::Option<::prost::alloc::string::String>,
+        #[prost(oneof = "query::Score", tags = "1, 2, 4, 5")]
+        pub score: ::core::option::Option<query::Score>,
+    }
+    /// Nested message and enum types in `Query`.
+    pub mod query {
+        #[derive(serde::Serialize)]
+        #[allow(clippy::derive_partial_eq_without_eq)]
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Score {
+            /// Score points against vector
+            #[prost(message, tag = "1")]
+            Vector(super::super::QueryEnum),
+            /// Fuse results of prefetches
+            #[prost(enumeration = "super::super::Fusion", tag = "2")]
+            Fusion(i32),
+            /// Sample points, seed is always set
+            #[prost(message, tag = "4")]
+            Sample(super::super::SampleParams),
+            /// Score candidates from prefetches with the formula
+            #[prost(message, tag = "5")]
+            Formula(super::super::Formula),
+        }
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Prefetch {
+        #[prost(message, repeated, tag = "1")]
+        pub prefetch: ::prost::alloc::vec::Vec<Prefetch>,
+        #[prost(message, optional, tag = "2")]
+        pub query: ::core::option::Option<Query>,
+        #[prost(uint64, tag = "3")]
+        pub limit: u64,
+        #[prost(message, optional, tag = "4")]
+        pub params: ::core::option::Option<super::SearchParams>,
+        #[prost(message, optional, tag = "5")]
+        pub filter: ::core::option::Option<super::Filter>,
+        #[prost(float, optional, tag = "6")]
+        pub score_threshold: ::core::option::Option<f32>,
+    }
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CoreSearchBatchPointsInternal {
+pub struct QueryPointsInternal {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    #[prost(message, repeated, tag = "2")]
-    #[validate]
-    pub search_points: ::prost::alloc::vec::Vec<CoreSearchPoints>,
+    #[prost(message, optional, tag = "2")]
+    pub query_points: ::core::option::Option<QueryShardPoints>,
     #[prost(uint32, optional, tag = "3")]
     pub shard_id: ::core::option::Option<u32>,
     #[prost(uint64, optional, tag = "4")]
     pub timeout: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IntermediateResult {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryResponseInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub intermediate_results: ::prost::alloc::vec::Vec<IntermediateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -7795,6 +9555,135 @@ pub struct CountPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCountsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub facet_counts: ::core::option::Option<FacetCounts>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseStatisticsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Dimensions to collect statistics for, per sparse vector name
+    #[prost(map = "string, message", tag = "2")]
+    pub vectors: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        SparseIndices,
+    >,
+    #[prost(uint32, optional, tag = "3")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseVectorStatistics {
+    /// Number of indexed vectors
+    #[prost(uint64, tag = "1")]
+    pub indexed_vector_count: u64,
+    /// Number of indexed vectors, which contain each dimension
+    #[prost(map = "uint32, uint64", tag = "2")]
+    pub document_frequencies: ::std::collections::HashMap<u32, u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseStatisticsResponse {
+    /// Statistics per sparse vector name
+    #[prost(map = "string, message", tag = "1")]
+    pub statistics: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        SparseVectorStatistics,
+    >,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SamplePointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Sample only points which satisfy this filter
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of points to sample
+    #[prost(uint64, tag = "3")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "4")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "5")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    #[prost(uint32, optional, tag = "6")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SamplePointsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Sampling keys of the points, in the same order
+    #[prost(float, repeated, tag = "2")]
+    pub keys: ::prost::alloc::vec::Vec<f32>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadChangesInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Position to read the changes after, the oldest available position if not set
+    #[prost(message, optional, tag = "3")]
+    pub offset: ::core::option::Option<ChangesOffset>,
+    /// Max number of operations to read
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Options for specifying which payload to include with the upserted points
+    #[prost(message, optional, tag = "5")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include with the upserted points
+    #[prost(message, optional, tag = "6")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadChangesResponseInternal {
+    /// Changes, reduced to their final effect and ordered by their position
+    #[prost(message, repeated, tag = "1")]
+    pub changes: ::prost::alloc::vec::Vec<PointChange>,
+    /// Position to resume reading from
+    #[prost(message, optional, tag = "2")]
+    pub next_offset: ::core::option::Option<ChangesOffset>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -8105,6 +9994,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "ClearPayload"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn update_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdatePayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/UpdatePayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "UpdatePayload"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn create_field_index(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateFieldIndexCollectionInternal>,
@@ -8311,10 +10225,132 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Get",
+                "/qdrant.PointsInternal/Get",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sparse_statistics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SparseStatisticsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::SparseStatisticsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/SparseStatistics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "SparseStatistics"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Query",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Query"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sample(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SamplePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::SamplePointsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Sample",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Sample"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn read_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReadChangesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReadChangesResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/ReadChanges",
             );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "ReadChanges"));
             self.inner.unary(req, path, codec).await
         }
     }
@@ -8389,6 +10425,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponseInternal>,
             tonic::Status,
         >;
+        async fn update_payload(
+            &self,
+            request: tonic::Request<super::UpdatePayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
         async fn create_field_index(
             &self,
             request: tonic::Request<super::CreateFieldIndexCollectionInternal>,
@@ -8440,6 +10483,38 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::GetPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        async fn sparse_statistics(
+            &self,
+            request: tonic::Request<super::SparseStatisticsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::SparseStatisticsResponse>,
+            tonic::Status,
+        >;
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryResponseInternal>,
+            tonic::Status,
+        >;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCountsInternal>,
+        ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        async fn sample(
+            &self,
+            request: tonic::Request<super::SamplePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::SamplePointsResponse>,
+            tonic::Status,
+        >;
+        async fn read_changes(
+            &self,
+            request: tonic::Request<super::ReadChangesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReadChangesResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -8935,6 +11010,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/UpdatePayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdatePayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::UpdatePayloadPointsInternal>
+                    for UpdatePayloadSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdatePayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::update_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdatePayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: PointsInternal>(pub Arc<T>);
@@ -9358,6 +11479,237 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/SparseStatistics" => {
+                    #[allow(non_camel_case_types)]
+                    struct SparseStatisticsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::SparseStatisticsInternal>
+                    for SparseStatisticsSvc<T> {
+                        type Response = super::SparseStatisticsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SparseStatisticsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::sparse_statistics(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SparseStatisticsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Query" => {
+                    #[allow(non_camel_case_types)]
+                    struct QuerySvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::QueryPointsInternal>
+                    for QuerySvc<T> {
+                        type Response = super::QueryResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::query(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QuerySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetCountsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCountsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Sample" => {
+                    #[allow(non_camel_case_types)]
+                    struct SampleSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::SamplePointsInternal>
+                    for SampleSvc<T> {
+                        type Response = super::SamplePointsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SamplePointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::sample(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SampleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/ReadChanges" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReadChangesSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::ReadChangesInternal>
+                    for ReadChangesSvc<T> {
+                        type Response = super::ReadChangesResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReadChangesInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::read_changes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ReadChangesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
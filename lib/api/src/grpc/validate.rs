@@ -109,6 +109,16 @@ impl Validate for grpc::quantization_config_diff::Quantization {
     }
 }
 
+impl Validate for grpc::ttl_config_diff::Ttl {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        use grpc::ttl_config_diff::Ttl;
+        match self {
+            Ttl::Enabled(ttl_config) => ttl_config.validate(),
+            Ttl::Disabled(_) => Ok(()),
+        }
+    }
+}
+
 impl Validate for grpc::update_collection_cluster_setup_request::Operation {
     fn validate(&self) -> Result<(), ValidationErrors> {
         use grpc::update_collection_cluster_setup_request::Operation;
@@ -120,6 +130,8 @@ impl Validate for grpc::update_collection_cluster_setup_request::Operation {
             Operation::CreateShardKey(op) => op.validate(),
             Operation::DeleteShardKey(op) => op.validate(),
             Operation::RestartTransfer(op) => op.validate(),
+            Operation::StartResharding(op) => op.validate(),
+            Operation::AbortResharding(op) => op.validate(),
         }
     }
 }
@@ -166,6 +178,18 @@ impl Validate for grpc::RestartTransfer {
     }
 }
 
+impl Validate for grpc::StartResharding {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
+impl Validate for grpc::AbortResharding {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
 impl Validate for grpc::condition::ConditionOneOf {
     fn validate(&self) -> Result<(), ValidationErrors> {
         use grpc::condition::ConditionOneOf;
@@ -249,6 +273,11 @@ pub fn validate_u64_range_min_1(value: &Option<u64>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[2, ]` or `None`.
+pub fn validate_u64_range_min_2(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(2), None))
+}
+
 /// Validate the value is in `[1, ]` or `None`.
 pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
@@ -279,6 +308,11 @@ pub fn validate_f32_range_min_0_5_max_1(value: &Option<f32>) -> Result<(), Valid
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.5), Some(1.0)))
 }
 
+/// Validate the value is in `[0.0, 1.0]` or `None`.
+pub fn validate_f32_range_1(value: &Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
+}
+
 /// Validate the value is in `[0.0, 1.0]` or `None`.
 pub fn validate_f64_range_1(value: &Option<f64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(0.0), Some(1.0)))
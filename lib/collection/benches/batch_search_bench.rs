@@ -66,6 +66,8 @@ fn batch_search_bench(c: &mut Criterion) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            multivector_config: None,
+            datatype: None,
         }
         .into(),
         ..CollectionParams::empty()
@@ -86,6 +88,7 @@ fn batch_search_bench(c: &mut Criterion) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     };
 
     let shared_config = Arc::new(RwLock::new(collection_config));
@@ -146,6 +149,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            mmr: None,
                         };
                         let result = shard
                             .core_search(
@@ -179,6 +183,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            mmr: None,
                         };
                         searches.push(search_query.into());
                     }
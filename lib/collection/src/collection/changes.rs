@@ -0,0 +1,97 @@
+use itertools::Itertools;
+use segment::types::{WithPayloadInterface, WithVector};
+
+use super::Collection;
+use crate::operations::changes::{
+    ChangesBatch, ChangesOffset, ChangesRequest, PointChange, DEFAULT_CHANGES_LIMIT,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::shard_not_found_error;
+
+impl Collection {
+    /// Read the next batch of changes of each of the requested shards
+    ///
+    /// Changes of each shard are read from one of its active replicas, the local one is
+    /// preferred. Positions are based on clock tags of the operations, so they are valid on every
+    /// replica of the shard.
+    pub async fn read_changes(
+        &self,
+        request: &ChangesRequest,
+    ) -> CollectionResult<Vec<ChangesBatch>> {
+        let limit = request.limit.unwrap_or(DEFAULT_CHANGES_LIMIT);
+        let with_payload = request
+            .with_payload
+            .clone()
+            .unwrap_or(WithPayloadInterface::Bool(true));
+        let with_vector = request
+            .with_vector
+            .clone()
+            .unwrap_or(WithVector::Bool(false));
+
+        let shards_holder = self.shards_holder.read().await;
+
+        let offsets: Vec<_> = if request.offsets.is_empty() {
+            shards_holder
+                .get_shards()
+                .map(|(shard_id, _)| (*shard_id, None))
+                .sorted_by_key(|(shard_id, _)| *shard_id)
+                .collect()
+        } else {
+            request
+                .offsets
+                .iter()
+                .map(|(shard_id, offset)| (*shard_id, Some(offset)))
+                .sorted_by_key(|(shard_id, _)| *shard_id)
+                .collect()
+        };
+
+        let mut batches = Vec::with_capacity(offsets.len());
+        for (shard_id, offset) in offsets {
+            let replica_set = shards_holder
+                .get_shard(&shard_id)
+                .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+            let (changes, next_offset) = replica_set
+                .read_changes(offset, limit, &with_payload, &with_vector)
+                .await
+                .map_err(|err| match err {
+                    CollectionError::PositionTruncated { description } => {
+                        CollectionError::PositionTruncated {
+                            description: format!("shard {shard_id}: {description}"),
+                        }
+                    }
+                    err => err,
+                })?;
+
+            batches.push(ChangesBatch {
+                shard_id,
+                changes,
+                next_offset,
+            });
+        }
+
+        Ok(batches)
+    }
+
+    /// Read changes of the local replica of the shard, see [`LocalShard::read_changes`]
+    ///
+    /// [`LocalShard::read_changes`]: crate::shards::local_shard::LocalShard::read_changes
+    pub async fn read_local_changes(
+        &self,
+        shard_id: ShardId,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        replica_set
+            .read_local_changes(offset, limit, with_payload, with_vector)
+            .await
+    }
+}
@@ -7,6 +7,7 @@ use segment::types::QuantizationConfig;
 use super::Collection;
 use crate::operations::config_diff::*;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::strict_mode::StrictModeConfig;
 use crate::operations::types::*;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
@@ -27,6 +28,7 @@ impl Collection {
             config.params = params_diff.update(&config.params)?;
         }
         self.collection_config.read().await.save(&self.path)?;
+        self.ensure_expiration_index().await?;
         Ok(())
     }
 
@@ -58,6 +60,7 @@ impl Collection {
     ) -> CollectionResult<()> {
         let mut config = self.collection_config.write().await;
         update_vectors_diff.check_vector_names(&config.params)?;
+        update_vectors_diff.check_multivector_params(&config.params)?;
         config
             .params
             .update_vectors_from_diff(update_vectors_diff)?;
@@ -153,6 +156,23 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates strict mode config:
+    /// Merges the given config into the current one and saves it on disk
+    pub async fn update_strict_mode_config(
+        &self,
+        strict_mode_diff: StrictModeConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.strict_mode_config = Some(match &config.strict_mode_config {
+                Some(current) => strict_mode_diff.update(current)?,
+                None => strict_mode_diff,
+            });
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Handle replica changes
     ///
     /// add and remove replicas from replica set
@@ -195,6 +215,23 @@ impl Collection {
 
                     replica_set.remove_peer(peer_id).await?;
                 }
+                Change::MarkReplacement(shard_id, peer_id) => {
+                    let Some(replica_set) = read_shard_holder.get_shard(&shard_id) else {
+                        return Err(CollectionError::BadRequest {
+                            description: format!("Shard {} of {} not found", shard_id, self.name()),
+                        });
+                    };
+
+                    if replica_set.peer_state(&peer_id).is_none() {
+                        return Err(CollectionError::BadRequest {
+                            description: format!(
+                                "Peer {peer_id} has no replica of shard {shard_id}"
+                            ),
+                        });
+                    }
+
+                    replica_set.add_replacement(peer_id)?;
+                }
             }
         }
         Ok(())
@@ -309,6 +346,11 @@ impl Collection {
         }
         let shard_transfers =
             shards_holder.get_shard_transfer_info(&*self.transfer_tasks.lock().await);
+        let resharding_operations = shards_holder
+            .resharding_state()
+            .map(Self::resharding_info)
+            .into_iter()
+            .collect();
 
         // sort by shard_id
         local_shards.sort_by_key(|k| k.shard_id);
@@ -320,6 +362,7 @@ impl Collection {
             local_shards,
             remote_shards,
             shard_transfers,
+            resharding_operations,
         };
         Ok(info)
     }
@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::types::{
+    Condition, Filter, HasIdCondition, SearchParams, WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CollectionSearchMatrixResponse, CoreSearchRequest,
+    CoreSearchRequestBatch, QueryEnum, SampleRequestInternal, SearchMatrixRequestInternal,
+};
+
+impl Collection {
+    /// Sample points and find the nearest neighbours of each of them among the same sample.
+    ///
+    /// Scores are computed with exact search restricted to the sampled ids, so every segment
+    /// scores each sampled vector against the sample with its raw scorer in a single batch.
+    pub async fn search_points_matrix(
+        &self,
+        request: SearchMatrixRequestInternal,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CollectionSearchMatrixResponse> {
+        let using = request.using().to_string();
+        self.collection_config
+            .read()
+            .await
+            .params
+            .get_distance(&using)?;
+
+        let sample_size = request.sample();
+        if sample_size == 0 {
+            return Ok(CollectionSearchMatrixResponse::default());
+        }
+
+        let filter = match &request.ids {
+            None => request.filter.clone(),
+            Some(ids) => {
+                let has_id = Filter::new_must(Condition::HasId(HasIdCondition::from(
+                    ids.iter().copied().collect::<HashSet<_>>(),
+                )));
+                Some(match &request.filter {
+                    None => has_id,
+                    Some(filter) => has_id.merge(filter),
+                })
+            }
+        };
+
+        let sample_request = SampleRequestInternal {
+            filter,
+            limit: sample_size,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Selector(vec![using.clone()]),
+        };
+        let sampled_points = self.sample_points(sample_request, &shard_selection).await?;
+
+        let samples: Vec<_> = sampled_points
+            .into_iter()
+            .filter_map(|point| {
+                let vector = point.record.vector?.get(&using)?.to_owned();
+                Some((point.record.id, vector))
+            })
+            .collect();
+
+        let sample_ids: Vec<_> = samples.iter().map(|(id, _)| *id).collect();
+
+        // A single point has no neighbours
+        if sample_ids.len() < 2 {
+            return Ok(CollectionSearchMatrixResponse {
+                nearests: vec![Vec::new(); sample_ids.len()],
+                sample_ids,
+            });
+        }
+
+        let limit = request
+            .limit
+            .unwrap_or(sample_ids.len() - 1)
+            .min(sample_ids.len() - 1);
+
+        let sample_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            sample_ids.iter().copied().collect::<HashSet<_>>(),
+        )));
+
+        let searches = samples
+            .into_iter()
+            .map(|(_, vector)| CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                    vector,
+                    using.clone(),
+                )),
+                filter: Some(sample_filter.clone()),
+                params: Some(SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+                // Leave room for the point itself, which is part of the sample too
+                limit: limit + 1,
+                offset: 0,
+                with_payload: None,
+                with_vector: None,
+                score_threshold: None,
+                mmr: None,
+            })
+            .collect();
+
+        let results = self
+            .core_search_batch(
+                CoreSearchRequestBatch { searches },
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await?;
+
+        if results.len() != sample_ids.len() {
+            return Err(CollectionError::service_error(format!(
+                "Expected {} search results for the distance matrix, got {}",
+                sample_ids.len(),
+                results.len(),
+            )));
+        }
+
+        let nearests = sample_ids
+            .iter()
+            .zip(results)
+            .map(|(sample_id, scored_points)| {
+                scored_points
+                    .into_iter()
+                    .filter(|scored_point| scored_point.id != *sample_id)
+                    .take(limit)
+                    .collect()
+            })
+            .collect();
+
+        Ok(CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use chrono::Utc;
+use segment::types::{PayloadFieldSchema, PayloadSchemaType};
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use crate::collection::Collection;
+use crate::config::CollectionConfig;
+use crate::operations::types::CollectionResult;
+use crate::shards::shard_holder::LockedShardHolder;
+use crate::shards::CollectionId;
+
+/// How often the collection is checked for expired points
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+impl Collection {
+    /// Index the expiration field with a datetime index, if expiration of points is configured
+    ///
+    /// Expired points are looked up through this index, so the periodic check doesn't scan all
+    /// points. An index of another type on the field is replaced, as it can't answer the datetime
+    /// range lookup. Called on consensus operations, so all peers create the same index.
+    pub async fn ensure_expiration_index(&self) -> CollectionResult<()> {
+        let Some(ttl_config) = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .ttl_config
+            .clone()
+        else {
+            return Ok(());
+        };
+        let expire_at_field = ttl_config.expire_at_field;
+        let expiration_schema = PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime);
+        let current_schema = self
+            .payload_index_schema
+            .read()
+            .schema
+            .get(&expire_at_field)
+            .cloned();
+        match current_schema {
+            Some(schema) if schema == expiration_schema => return Ok(()),
+            Some(schema) => log::warn!(
+                "Replacing {schema:?} index of expiration field {expire_at_field} \
+                 of collection {} with datetime index",
+                self.id,
+            ),
+            None => {}
+        }
+        self.create_payload_index(expire_at_field, expiration_schema)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Spawn a task, which periodically deletes expired points of the collection
+///
+/// Task holds only weak references to the collection internals and stops as soon as
+/// the collection is dropped.
+pub(super) fn spawn_expiry_worker(
+    collection_id: CollectionId,
+    shards_holder: &Arc<LockedShardHolder>,
+    collection_config: &Arc<RwLock<CollectionConfig>>,
+    updates_lock: &Arc<RwLock<()>>,
+    update_runtime: &Handle,
+) {
+    let shards_holder = Arc::downgrade(shards_holder);
+    let collection_config = Arc::downgrade(collection_config);
+    let updates_lock = Arc::downgrade(updates_lock);
+
+    update_runtime.spawn(async move {
+        loop {
+            tokio::time::sleep(EXPIRY_CHECK_INTERVAL).await;
+
+            let Some((shards_holder, collection_config, updates_lock)) =
+                upgrade_all(&shards_holder, &collection_config, &updates_lock)
+            else {
+                break;
+            };
+
+            let Some(ttl_config) = collection_config.read().await.params.ttl_config.clone() else {
+                continue;
+            };
+
+            let _update_lock = updates_lock.read().await;
+            let now = Utc::now();
+
+            let shards_holder = shards_holder.read().await;
+            for replica_set in shards_holder.all_shards() {
+                if let Err(err) = replica_set.delete_expired_points(&ttl_config, now).await {
+                    log::error!(
+                        "Failed to delete expired points of shard {collection_id}:{}: {err}",
+                        replica_set.shard_id,
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[allow(clippy::type_complexity)]
+fn upgrade_all(
+    shards_holder: &Weak<LockedShardHolder>,
+    collection_config: &Weak<RwLock<CollectionConfig>>,
+    updates_lock: &Weak<RwLock<()>>,
+) -> Option<(
+    Arc<LockedShardHolder>,
+    Arc<RwLock<CollectionConfig>>,
+    Arc<RwLock<()>>,
+)> {
+    Some((
+        shards_holder.upgrade()?,
+        collection_config.upgrade()?,
+        updates_lock.upgrade()?,
+    ))
+}
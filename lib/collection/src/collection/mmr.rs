@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use common::types::ScoreType;
+use segment::data_types::vectors::{Vector, VectorRef};
+use segment::types::{Distance, Order, ScoredPoint};
+
+use super::Collection;
+use crate::common::fetch_vectors::retrieve_points;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionResult, CoreSearchRequest, QueryEnum};
+
+/// Pick `limit` candidates with Maximal Marginal Relevance.
+///
+/// Each next pick is the candidate with the best `lambda * relevance - (1 - lambda) * redundancy`,
+/// where redundancy is its highest similarity to the candidates picked before it.
+/// Both `relevance` and `similarity` must be larger for better matches.
+fn mmr_select<T>(
+    candidates: Vec<T>,
+    relevance: impl Fn(&T) -> ScoreType,
+    similarity: impl Fn(&T, &T) -> ScoreType,
+    lambda: ScoreType,
+    limit: usize,
+) -> Vec<T> {
+    let mut remaining: Vec<(T, ScoreType, Option<ScoreType>)> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let relevance = relevance(&candidate);
+            (candidate, relevance, None)
+        })
+        .collect();
+
+    let mut selected = Vec::with_capacity(limit.min(remaining.len()));
+    while selected.len() < limit && !remaining.is_empty() {
+        let marginal_relevance =
+            |(_, relevance, redundancy): &(T, ScoreType, Option<ScoreType>)| {
+                lambda * relevance - (1.0 - lambda) * redundancy.unwrap_or(0.0)
+            };
+        // Ties are resolved in favor of the more relevant candidate, which comes first
+        let best = remaining
+            .iter()
+            .enumerate()
+            .fold(
+                None,
+                |best: Option<(usize, ScoreType)>, (index, candidate)| {
+                    let score = marginal_relevance(candidate);
+                    match best {
+                        Some((_, best_score)) if best_score >= score => best,
+                        _ => Some((index, score)),
+                    }
+                },
+            )
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+
+        let (picked, _, _) = remaining.remove(best);
+        for (candidate, _, redundancy) in &mut remaining {
+            let similarity = similarity(candidate, &picked);
+            *redundancy = Some(redundancy.map_or(similarity, |max| max.max(similarity)));
+        }
+        selected.push(picked);
+    }
+    selected
+}
+
+/// Similarity of two vectors, larger for more similar ones, in the same units as search scores
+fn vectors_similarity(distance: Distance, a: VectorRef, b: VectorRef) -> ScoreType {
+    let dense_similarity = |a: &[_], b: &[_]| {
+        let score = distance.postprocess_score(distance.similarity(a, b));
+        match distance.distance_order() {
+            Order::LargeBetter => score,
+            Order::SmallBetter => -score,
+        }
+    };
+    match (a, b) {
+        (VectorRef::Dense(a), VectorRef::Dense(b)) => dense_similarity(a, b),
+        (VectorRef::Sparse(a), VectorRef::Sparse(b)) => a.score(b).unwrap_or_default(),
+        // Sum of the best matches of inner vectors, as in the max-sim comparator
+        (VectorRef::MultiDense(a), VectorRef::MultiDense(b)) => a
+            .multi_vectors()
+            .map(|a| {
+                b.multi_vectors()
+                    .map(|b| dense_similarity(a, b))
+                    .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+            })
+            .sum(),
+        _ => 0.0,
+    }
+}
+
+impl Collection {
+    /// Rerank merged search results with Maximal Marginal Relevance.
+    ///
+    /// `candidates` must be sorted from the best, vectors they are compared with are
+    /// retrieved from shards. Candidates, deleted in the meantime, are skipped.
+    pub(super) async fn mmr_rerank(
+        &self,
+        candidates: Vec<ScoredPoint>,
+        request: &CoreSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let Some(mmr) = request.mmr else {
+            return Ok(candidates);
+        };
+        if candidates.len() <= 1 {
+            return Ok(candidates);
+        }
+
+        let vector_name = request.query.get_vector_name().to_string();
+        let distance = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .get_distance(&vector_name)?;
+        // Same as in merging results of shards, only nearest search scores are distances
+        let order = match &request.query {
+            QueryEnum::Nearest(_) => distance.distance_order(),
+            QueryEnum::RecommendBestScore(_) | QueryEnum::Discover(_) | QueryEnum::Context(_) => {
+                Order::LargeBetter
+            }
+        };
+
+        let ids = candidates.iter().map(|point| point.id).collect();
+        let mut vectors: HashMap<_, Vector> = retrieve_points(
+            self,
+            ids,
+            vec![vector_name.clone()],
+            read_consistency,
+            shard_selection,
+        )
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            let vector = record.vector?.get(&vector_name)?.to_owned();
+            Some((record.id, vector))
+        })
+        .collect();
+
+        let candidates: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|point| {
+                let vector = vectors.remove(&point.id)?;
+                Some((point, vector))
+            })
+            .collect();
+        let selected = mmr_select(
+            candidates,
+            |(point, _)| match order {
+                Order::LargeBetter => point.score,
+                Order::SmallBetter => -point.score,
+            },
+            |(_, a), (_, b)| vectors_similarity(distance, a.to_vec_ref(), b.to_vec_ref()),
+            mmr.lambda(),
+            request.limit + request.offset,
+        );
+        Ok(selected.into_iter().map(|(point, _)| point).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmr_select() {
+        // Points on a line, relevance decreases with the position, similarity with the distance
+        let positions = [0.0, 0.1, 0.2, 5.0, 10.0];
+        let relevance = |x: &f32| 10.0 - x;
+        let similarity = |a: &f32, b: &f32| -(a - b).abs();
+
+        // Only relevance, same as the original order
+        let selected = mmr_select(positions.to_vec(), relevance, similarity, 1.0, 3);
+        assert_eq!(selected, vec![0.0, 0.1, 0.2]);
+
+        // Mostly diversity, far points are picked before the close ones
+        let selected = mmr_select(positions.to_vec(), relevance, similarity, 0.3, 3);
+        assert_eq!(selected, vec![0.0, 10.0, 5.0]);
+
+        // Can't pick more than there are
+        let selected = mmr_select(positions.to_vec(), relevance, similarity, 0.5, 10);
+        assert_eq!(selected.len(), positions.len());
+        assert!(mmr_select(Vec::<f32>::new(), relevance, similarity, 0.5, 3).is_empty());
+    }
+
+    #[test]
+    fn test_vectors_similarity() {
+        let a = [1.0, 0.0];
+        let b = [0.0, 1.0];
+        let c = [2.0, 0.0];
+
+        // Closer vectors are more similar for distances, where smaller is better
+        let euclid = |x: &[f32], y: &[f32]| {
+            vectors_similarity(Distance::Euclid, VectorRef::Dense(x), VectorRef::Dense(y))
+        };
+        assert!(euclid(&a, &c) > euclid(&b, &c));
+        assert_eq!(euclid(&a, &c), -1.0);
+
+        let dot = |x: &[f32], y: &[f32]| {
+            vectors_similarity(Distance::Dot, VectorRef::Dense(x), VectorRef::Dense(y))
+        };
+        assert_eq!(dot(&a, &c), 2.0);
+        assert_eq!(dot(&b, &c), 0.0);
+    }
+}
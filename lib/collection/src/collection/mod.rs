@@ -1,11 +1,18 @@
+mod changes;
 mod collection_ops;
+mod distance_matrix;
+mod expiry;
+mod mmr;
 pub mod payload_index_schema;
 mod point_ops;
+mod query;
+mod resharding;
 mod search;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
 mod state_management;
+mod strict_mode;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -24,6 +31,7 @@ use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
+use crate::common::stoppable_task_async::CancellableAsyncTaskHandle;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
@@ -70,6 +78,8 @@ pub struct Collection {
     // Search runtime handle.
     search_runtime: Handle,
     optimizer_cpu_budget: CpuBudget,
+    // Task driving the resharding operation, if this peer drives one.
+    resharding_driver: parking_lot::Mutex<Option<CancellableAsyncTaskHandle<bool>>>,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -133,6 +143,17 @@ impl Collection {
 
         let payload_index_schema = Self::load_payload_index_schema(path)?;
 
+        let updates_lock = Arc::new(RwLock::new(()));
+        let update_runtime = update_runtime.unwrap_or_else(Handle::current);
+
+        expiry::spawn_expiry_worker(
+            name.clone(),
+            &locked_shard_holder,
+            &shared_collection_config,
+            &updates_lock,
+            &update_runtime,
+        );
+
         Ok(Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
@@ -149,10 +170,11 @@ impl Collection {
             abort_shard_transfer_cb: abort_shard_transfer,
             init_time: start_time.elapsed(),
             is_initialized: Default::default(),
-            updates_lock: Default::default(),
-            update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            updates_lock,
+            update_runtime,
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            resharding_driver: Default::default(),
         })
     }
 
@@ -230,6 +252,17 @@ impl Collection {
         let payload_index_schema = Self::load_payload_index_schema(path)
             .expect("Can't load or initialize payload index schema");
 
+        let updates_lock = Arc::new(RwLock::new(()));
+        let update_runtime = update_runtime.unwrap_or_else(Handle::current);
+
+        expiry::spawn_expiry_worker(
+            collection_id.clone(),
+            &locked_shard_holder,
+            &shared_collection_config,
+            &updates_lock,
+            &update_runtime,
+        );
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -246,10 +279,11 @@ impl Collection {
             abort_shard_transfer_cb: abort_shard_transfer,
             init_time: start_time.elapsed(),
             is_initialized: Default::default(),
-            updates_lock: Default::default(),
-            update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            updates_lock,
+            update_runtime,
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            resharding_driver: Default::default(),
         }
     }
 
@@ -443,6 +477,7 @@ impl Collection {
                 .map(|(shard_id, replicas)| {
                     let shard_info = ShardInfo {
                         replicas: replicas.peers(),
+                        replacements: replicas.replacements(),
                     };
                     (*shard_id, shard_info)
                 })
@@ -450,6 +485,7 @@ impl Collection {
             transfers,
             shards_key_mapping: shards_holder.get_shard_key_to_ids_mapping(),
             payload_index_schema: self.payload_index_schema.read().clone(),
+            resharding: shards_holder.resharding_state(),
         }
     }
 
@@ -569,6 +605,7 @@ impl Collection {
                     from: replica_id,
                     to: *this_peer_id,
                     shard_id,
+                    to_shard_id: None,
                     sync: true,
                     // For automatic shard transfers, always select some default method from this point on
                     method: Some(
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use chrono::Utc;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::facets::FacetCounts;
+use segment::data_types::order_by::{MultiOrderBy, OrderBy, OrderedPoint};
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
@@ -13,6 +15,7 @@ use crate::operations::point_ops::WriteOrdering;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::shards::resharding::ReshardStage;
 use crate::shards::shard::ShardId;
 
 impl Collection {
@@ -128,19 +131,52 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
 
+        {
+            let collection_config = self.collection_config.read().await;
+            operation.check_vector_datatypes(&collection_config.params.vectors)?;
+
+            // Expiration time is resolved once here, so all replicas store the same value
+            if let Some(ttl_config) = &collection_config.params.ttl_config {
+                ttl_config.set_default_expiration(&mut operation, Utc::now());
+            }
+        }
+
+        // Preconditions are checked once by the leader replica of each shard, which replicates
+        // the operation without the failed points. Weak ordering has no single leader.
+        let ordering = match ordering {
+            WriteOrdering::Weak if !operation.preconditions().is_empty() => WriteOrdering::Medium,
+            ordering => ordering,
+        };
+
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
         let mut results = tokio::task::spawn(async move {
             let _update_lock = update_lock;
 
+            // While resharding, points are also written into the shards they are moved to
+            let resharding_updates: FuturesUnordered<_> = shard_holder
+                .split_by_resharding(operation.clone(), &shard_keys_selection)
+                .into_iter()
+                .map(move |(shard, operation)| {
+                    shard.update_with_consistency(operation, wait, ordering)
+                })
+                .collect();
+
+            // Until reads switch to the new hash ring, moved points may not exist in their new
+            // shards yet, so updates of them are allowed to fail
+            let is_resharding_update_required =
+                shard_holder.resharding_state().map_or(false, |state| {
+                    state.stage >= ReshardStage::ReadHashRingCommitted
+                });
+
             let updates: FuturesUnordered<_> = shard_holder
                 .split_by_shard(operation, &shard_keys_selection)?
                 .into_iter()
@@ -149,7 +185,16 @@ impl Collection {
                 })
                 .collect();
 
-            let results: Vec<_> = updates.collect().await;
+            let (mut results, resharding_results): (Vec<_>, Vec<_>) =
+                future::join(updates.collect(), resharding_updates.collect()).await;
+
+            for result in resharding_results {
+                match result {
+                    Ok(_) => {}
+                    Err(err) if is_resharding_update_required => results.push(Err(err)),
+                    Err(err) => log::debug!("Failed to update points moved by resharding: {err}"),
+                }
+            }
 
             CollectionResult::Ok(results)
         })
@@ -184,8 +229,16 @@ impl Collection {
                 first_err
             }
         } else {
+            // Each shard reports failed preconditions of its own points
+            let mut failed_preconditions = Vec::new();
+            for result in results.iter_mut().flatten() {
+                failed_preconditions.append(&mut result.failed_preconditions);
+            }
+
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            result.failed_preconditions = failed_preconditions;
+            Ok(result)
         }
     }
 
@@ -220,28 +273,65 @@ impl Collection {
             .unwrap_or_else(|| default_request.with_payload.clone().unwrap());
         let with_vector = request.with_vector;
 
-        let order_by = request.order_by.map(OrderBy::from);
-
-        // Handle case of order_by
-        if let Some(order_by) = &order_by {
-            // Validate we have a range index for the order_by key
-            let has_range_index_for_order_by_field = self
-                .payload_index_schema
-                .read()
-                .schema
-                .get(&order_by.key)
-                .is_some_and(|field| field.has_range_index());
-
-            if !has_range_index_for_order_by_field {
-                return Err(CollectionError::bad_request(format!(
-                    "No range index for `order_by` key: {}. Please create one to use `order_by`. Integer, float, and datetime payloads can have range indexes, see https://qdrant.tech/documentation/concepts/indexing/#payload-index.",
-                    &order_by.key
-                )));
+        let order_by = match request.order_by {
+            None => {
+                if request.cursor.is_some() {
+                    return Err(CollectionError::bad_input(
+                        "`cursor` can only be used together with `order_by`".to_string(),
+                    ));
+                }
+                None
             }
+            Some(order_by) => {
+                let keys = Vec::<OrderBy>::from(order_by);
+                if keys.is_empty() {
+                    return Err(CollectionError::bad_input(
+                        "`order_by` must contain at least one key".to_string(),
+                    ));
+                }
+
+                // Validate we have a range or keyword index for each of the order_by keys
+                {
+                    let payload_index_schema = self.payload_index_schema.read();
+                    for key in &keys {
+                        let field = payload_index_schema.schema.get(&key.key);
+
+                        if !field.is_some_and(|field| field.is_orderable()) {
+                            return Err(CollectionError::bad_request(format!(
+                                "No range or keyword index for `order_by` key: {}. Please create one to use `order_by`. Integer, float, and datetime payloads can have range indexes, keyword payloads can have keyword indexes, see https://qdrant.tech/documentation/concepts/indexing/#payload-index.",
+                                &key.key
+                            )));
+                        }
+
+                        if key.start_from.is_some()
+                            && !field.is_some_and(|field| field.has_range_index())
+                        {
+                            return Err(CollectionError::bad_input(format!(
+                                "`start_from` is only supported for keys with a range index, but `{}` has none. Use `cursor` to continue scrolling instead",
+                                &key.key
+                            )));
+                        }
+                    }
+                }
 
-            // Validate user did not try to use an id offset with order_by
-            if id_offset.is_some() {
-                return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `order_by.start_from` and a filter to exclude the IDs that you've already seen for the `order_by.start_from` value".to_string()));
+                // Validate user did not try to use an id offset with order_by
+                if id_offset.is_some() {
+                    return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `cursor` with the `next_page_cursor` of the previous page".to_string()));
+                }
+
+                let cursor = request
+                    .cursor
+                    .as_deref()
+                    .map(|cursor| {
+                        OrderedPoint::from_cursor(cursor)
+                            .filter(|cursor| cursor.values.len() == keys.len())
+                            .ok_or_else(|| {
+                                CollectionError::bad_input("Invalid `cursor`, it must be the `next_page_cursor` of a previous page with the same `order_by`".to_string())
+                            })
+                    })
+                    .transpose()?;
+
+                Some(MultiOrderBy { keys, cursor })
             }
         };
 
@@ -251,11 +341,8 @@ impl Collection {
             });
         }
 
-        // `order_by` does not support offset
-        if order_by.is_none() {
-            // Needed to return next page offset.
-            limit += 1;
-        };
+        // Needed to return next page offset or cursor.
+        limit += 1;
         let retrieved_points: Vec<_> = {
             let shards_holder = self.shards_holder.read().await;
             let target_shards = shards_holder.select_shards(shard_selection)?;
@@ -294,37 +381,45 @@ impl Collection {
                 .sorted_unstable_by_key(|point| point.id)
                 .take(limit)
                 .collect_vec(),
-            Some(order_by) => {
-                retrieved_iter
-                    // Extract and remove order value from payload
-                    .map(|records| {
-                        records.into_iter().map(|mut record| {
-                            let value =
-                                order_by.remove_order_value_from_payload(record.payload.as_mut());
-                            (value, record)
-                        })
-                    })
-                    // Get top results
-                    .kmerge_by(|(value_a, _), (value_b, _)| match order_by.direction() {
-                        Direction::Asc => value_a <= value_b,
-                        Direction::Desc => value_a >= value_b,
-                    })
-                    .map(|(_, record)| record)
-                    .take(limit)
-                    .collect_vec()
-            }
+            Some(order_by) => retrieved_iter
+                .kmerge_by(|a, b| {
+                    order_by
+                        .compare_values(
+                            a.order_values.as_deref().unwrap_or_default(),
+                            b.order_values.as_deref().unwrap_or_default(),
+                        )
+                        .then_with(|| a.id.cmp(&b.id))
+                        .is_lt()
+                })
+                .take(limit)
+                .collect_vec(),
         };
 
-        let next_page_offset = if points.len() < limit || order_by.is_some() {
+        let (next_page_offset, next_page_cursor) = if points.len() < limit {
             // This was the last page
-            None
+            (None, None)
         } else {
             // remove extra point, it would be a first point of the next page
-            Some(points.pop().unwrap().id)
+            let next_point = points.pop().unwrap();
+            match &order_by {
+                None => (Some(next_point.id), None),
+                // Continue right after the last point of this page
+                Some(_) => {
+                    let cursor = points.last().map(|last_point| {
+                        OrderedPoint {
+                            values: last_point.order_values.clone().unwrap_or_default(),
+                            id: last_point.id,
+                        }
+                        .to_cursor()
+                    });
+                    (None, cursor)
+                }
+            }
         };
         Ok(ScrollResult {
             points,
             next_page_offset,
+            next_page_cursor,
         })
     }
 
@@ -359,6 +454,71 @@ impl Collection {
         Ok(CountResult { count })
     }
 
+    /// Count points per value of the facet key, summed over all selected shards
+    pub async fn facet(
+        &self,
+        request: FacetRequestInternal,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<FacetResponse> {
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            // `facet` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| shard.facet(request.clone(), shard_selection.is_shard_id()))
+            .collect();
+
+        let mut counts = FacetCounts::default();
+
+        while let Some(response) = requests.try_next().await? {
+            counts.merge(response.into_counts());
+        }
+
+        // Internal requests are merged with other shards by the requesting peer,
+        // so top values can only be picked there
+        if shard_selection.is_shard_id() {
+            Ok(FacetResponse::with_all_values(&counts, &request))
+        } else {
+            Ok(FacetResponse::from_counts(&counts, &request))
+        }
+    }
+
+    /// Pick up to `request.limit` random points among all selected shards
+    ///
+    /// Samples of the shards are merged by their sampling keys, so the result is a uniform
+    /// sample of all points, regardless of how they are distributed between the shards.
+    pub async fn sample_points(
+        &self,
+        request: SampleRequestInternal,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let limit = request.limit;
+        let request = Arc::new(request);
+        let all_shard_samples = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.select_shards(shard_selection)?;
+            let sample_futures = target_shards.into_iter().map(|(shard, _shard_key)| {
+                // `sample` requests received through internal gRPC *always* have `shard_selection`
+                shard.sample(request.clone(), shard_selection.is_shard_id())
+            });
+            future::try_join_all(sample_futures).await?
+        };
+        let points = all_shard_samples
+            .into_iter()
+            .flatten()
+            .sorted_by(|a, b| {
+                a.key
+                    .total_cmp(&b.key)
+                    .then_with(|| a.record.id.cmp(&b.record.id))
+            })
+            .unique_by(|point| point.record.id)
+            .take(limit)
+            .collect();
+        Ok(points)
+    }
+
     pub async fn retrieve(
         &self,
         request: PointRequestInternal,
@@ -0,0 +1,206 @@
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::types::ScoreType;
+use futures::{future, TryFutureExt};
+use segment::common::score_fusion::{dbsf_scoring, rrf_scoring};
+use segment::spaces::tools;
+use segment::types::{Order, ScoredPoint, WithPayloadInterface, WithVector};
+
+use super::search::apply_sparse_statistics;
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::shard_query::{
+    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+};
+
+/// Best `limit` points of the list, according to the order of scores
+fn top_by_order(points: Vec<ScoredPoint>, limit: usize, order: Order) -> Vec<ScoredPoint> {
+    match order {
+        Order::LargeBetter => tools::peek_top_largest_iterable(points, limit),
+        Order::SmallBetter => tools::peek_top_smallest_iterable(points, limit),
+    }
+}
+
+fn passes_threshold(score: ScoreType, threshold: ScoreType, order: Order) -> bool {
+    match order {
+        Order::LargeBetter => score >= threshold,
+        Order::SmallBetter => score <= threshold,
+    }
+}
+
+impl Collection {
+    /// Perform the universal query: resolve prefetches and score or fuse their results.
+    ///
+    /// Stages of the query are resolved within each shard, results of shards are merged and,
+    /// if the root query is a fusion, fused here.
+    pub async fn query(
+        &self,
+        request: ShardQueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        if request.limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let request = self.apply_query_modifiers(request, shard_selection).await?;
+
+        let is_intermediate = request.has_intermediate_results();
+        // Offset can only be applied to merged results, payload is retrieved after fusion
+        let shard_request = ShardQueryRequest {
+            limit: request.limit + request.offset,
+            offset: 0,
+            with_payload: if is_intermediate {
+                WithPayloadInterface::Bool(false)
+            } else {
+                request.with_payload.clone()
+            },
+            with_vector: if is_intermediate {
+                WithVector::Bool(false)
+            } else {
+                request.with_vector.clone()
+            },
+            ..request.clone()
+        };
+
+        let mut responses = self
+            .query_shards(
+                Arc::new(shard_request),
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await?;
+
+        let expected_results = if is_intermediate {
+            request.prefetches.len()
+        } else {
+            1
+        };
+        if responses
+            .iter()
+            .any(|response| response.len() != expected_results)
+        {
+            return Err(CollectionError::service_error(format!(
+                "Shard query response must contain {expected_results} results"
+            )));
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        if !is_intermediate {
+            let order = request
+                .query
+                .as_ref()
+                .map_or(Ok(Order::LargeBetter), |query| {
+                    query.order(&collection_params)
+                })?;
+            let merged = responses
+                .iter_mut()
+                .flat_map(|response| mem::take(&mut response[0]))
+                .collect();
+            let top = top_by_order(merged, request.limit + request.offset, order);
+            return Ok(top.into_iter().skip(request.offset).collect());
+        }
+
+        // Merge results of each root prefetch across shards
+        let mut prefetch_results = Vec::with_capacity(request.prefetches.len());
+        for (index, prefetch) in request.prefetches.iter().enumerate() {
+            let order = prefetch.query.order(&collection_params)?;
+            let merged = responses
+                .iter_mut()
+                .flat_map(|response| mem::take(&mut response[index]))
+                .collect();
+            prefetch_results.push((top_by_order(merged, prefetch.limit, order), order));
+        }
+
+        let (combined, order) = match &request.query {
+            Some(ScoringQuery::Fusion(Fusion::Rrf)) => (
+                rrf_scoring(prefetch_results.into_iter().map(|(points, _)| points)),
+                Order::LargeBetter,
+            ),
+            Some(ScoringQuery::Fusion(Fusion::Dbsf)) => {
+                (dbsf_scoring(prefetch_results), Order::LargeBetter)
+            }
+            // Results of the only prefetch are returned as is
+            None => prefetch_results.pop().ok_or_else(|| {
+                CollectionError::bad_request("Query without prefetch is empty".to_string())
+            })?,
+            Some(ScoringQuery::Vector(_) | ScoringQuery::Sample(_) | ScoringQuery::Formula(_)) => {
+                unreachable!("vector query, sample and formula have no intermediate results")
+            }
+        };
+
+        let result = combined
+            .into_iter()
+            .filter(|point| {
+                request.score_threshold.map_or(true, |threshold| {
+                    passes_threshold(point.score, threshold, order)
+                })
+            })
+            .skip(request.offset)
+            .take(request.limit)
+            .collect();
+
+        self.fill_search_result_with_payload(
+            result,
+            Some(request.with_payload),
+            request.with_vector,
+            read_consistency,
+            shard_selection,
+        )
+        .await
+    }
+
+    /// Send the query to all selected shards, returning unmerged responses of each shard
+    pub async fn query_shards(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(shard_selection)?;
+        let all_queries = target_shards.iter().map(|(shard, shard_key)| {
+            let shard_key = shard_key.cloned();
+            shard
+                .query(
+                    Arc::clone(&request),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+                .and_then(move |mut response| async move {
+                    if shard_key.is_none() {
+                        return Ok(response);
+                    }
+                    for point in response.iter_mut().flatten() {
+                        point.shard_key = shard_key.clone();
+                    }
+                    Ok(response)
+                })
+        });
+        future::try_join_all(all_queries).await
+    }
+
+    /// Apply modifiers of sparse vectors, e.g. IDF, to vector queries of all stages
+    async fn apply_query_modifiers(
+        &self,
+        request: ShardQueryRequest,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<ShardQueryRequest> {
+        let statistics = self
+            .sparse_modifier_statistics(request.vector_queries(), shard_selection)
+            .await?;
+        if statistics.is_empty() {
+            return Ok(request);
+        }
+        request.transform_vector_queries(|query| apply_sparse_statistics(query, &statistics))
+    }
+}
@@ -0,0 +1,619 @@
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU32;
+
+use segment::types::{
+    Condition, CustomIdCheckerCondition, Filter, ShardKey, WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::config::ShardingMethod;
+use crate::operations::point_ops::PointOperations;
+use crate::operations::types::{CollectionError, CollectionResult, ReshardingInfo};
+use crate::operations::{
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
+};
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::driver::spawn_resharding_driver;
+use crate::shards::resharding::{
+    ReshardKey, ReshardMigration, ReshardStage, ReshardState, ReshardingDirection,
+};
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::ShardHolder;
+use crate::shards::transfer::ShardTransferConsensus;
+
+/// Number of points removed at once, when cleaning up after an aborted resharding down
+const ABORT_CLEANUP_BATCH_SIZE: usize = 100;
+
+impl Collection {
+    pub async fn resharding_state(&self) -> Option<ReshardState> {
+        self.shards_holder.read().await.resharding_state()
+    }
+
+    pub(super) fn resharding_info(state: ReshardState) -> ReshardingInfo {
+        let migrations_done = state.migrations_done();
+        let migrations_total = state.migrations.len();
+        ReshardingInfo {
+            direction: state.direction,
+            shard_id: state.shard_id,
+            peer_id: state.peer_id,
+            shard_key: state.shard_key,
+            stage: state.stage,
+            comment: Some(format!(
+                "Migrated points of {migrations_done}/{migrations_total} shards"
+            )),
+        }
+    }
+
+    /// Build the key of a new resharding operation, validating it can be started
+    ///
+    /// Resharding up adds a new shard on the given peer, or on the peer with the least shard
+    /// replicas. Resharding down removes the last shard of the shard key. The peer drives the
+    /// operation and defaults to one having a replica of the removed shard.
+    pub async fn reshard_key(
+        &self,
+        direction: ReshardingDirection,
+        peer_id: Option<PeerId>,
+        shard_key: Option<ShardKey>,
+    ) -> CollectionResult<ReshardKey> {
+        let state = self.state().await;
+
+        if let Some(resharding) = &state.resharding {
+            return Err(CollectionError::bad_request(format!(
+                "Resharding {:?} of shard {} is already in progress",
+                resharding.direction, resharding.shard_id,
+            )));
+        }
+
+        if !state.transfers.is_empty() {
+            return Err(CollectionError::bad_request(
+                "Resharding can not be started while shard transfers are in progress".to_string(),
+            ));
+        }
+
+        match (
+            state.config.params.sharding_method.unwrap_or_default(),
+            &shard_key,
+        ) {
+            (ShardingMethod::Auto, Some(shard_key)) => {
+                return Err(CollectionError::bad_request(format!(
+                    "Shard key {shard_key} can not be resharded with Auto sharding method"
+                )));
+            }
+            (ShardingMethod::Custom, None) => {
+                return Err(CollectionError::bad_request(
+                    "Shard key must be specified with Custom sharding method".to_string(),
+                ));
+            }
+            (ShardingMethod::Custom, Some(shard_key))
+                if !state.shards_key_mapping.contains_key(shard_key) =>
+            {
+                return Err(CollectionError::bad_request(format!(
+                    "Shard key {shard_key} not found"
+                )));
+            }
+            _ => {}
+        }
+
+        let all_peers: HashSet<PeerId> = self
+            .channel_service
+            .id_to_address
+            .read()
+            .keys()
+            .copied()
+            .chain([self.this_peer_id])
+            .collect();
+
+        if let Some(peer_id) = peer_id {
+            if !all_peers.contains(&peer_id) {
+                return Err(CollectionError::bad_request(format!(
+                    "Peer {peer_id} does not exist"
+                )));
+            }
+        }
+
+        let shard_ids = self
+            .shards_holder
+            .read()
+            .await
+            .shard_ids_by_key(shard_key.as_ref());
+
+        let (shard_id, peer_id) = match direction {
+            ReshardingDirection::Up => {
+                let shard_id = state.shards.keys().max().map_or(0, |max| max + 1);
+                let peer_id = match peer_id {
+                    Some(peer_id) => peer_id,
+                    None => {
+                        let mut replica_count: HashMap<PeerId, usize> =
+                            all_peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+                        for shard_info in state.shards.values() {
+                            for peer_id in shard_info.replicas.keys() {
+                                *replica_count.entry(*peer_id).or_default() += 1;
+                            }
+                        }
+                        replica_count
+                            .into_iter()
+                            .min_by_key(|(peer_id, count)| (*count, *peer_id))
+                            .map(|(peer_id, _)| peer_id)
+                            .unwrap_or(self.this_peer_id)
+                    }
+                };
+                (shard_id, peer_id)
+            }
+            ReshardingDirection::Down => {
+                if shard_ids.len() < 2 {
+                    return Err(CollectionError::bad_request(
+                        "Resharding down requires at least 2 shards".to_string(),
+                    ));
+                }
+                let shard_id = *shard_ids.last().expect("shard ids are not empty");
+                let peer_id = match peer_id {
+                    Some(peer_id) => peer_id,
+                    None => state
+                        .shards
+                        .get(&shard_id)
+                        .and_then(|shard_info| {
+                            shard_info
+                                .replicas
+                                .iter()
+                                .filter(|(_, state)| **state == ReplicaState::Active)
+                                .map(|(peer_id, _)| *peer_id)
+                                .min()
+                        })
+                        .ok_or_else(|| {
+                            CollectionError::bad_request(format!(
+                                "Shard {shard_id} has no active replica"
+                            ))
+                        })?,
+                };
+                (shard_id, peer_id)
+            }
+        };
+
+        Ok(ReshardKey {
+            peer_id,
+            shard_id,
+            shard_key,
+            direction,
+        })
+    }
+
+    /// Start the resharding operation, as applied by consensus
+    ///
+    /// Resharding up creates the new shard with replicas on the driving peer and the peers with
+    /// the least shard replicas, up to the replication factor. If this peer drives the operation,
+    /// the driver task is started.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn start_resharding(
+        &self,
+        reshard_key: ReshardKey,
+        consensus: Box<dyn ShardTransferConsensus>,
+    ) -> CollectionResult<()> {
+        {
+            let mut shard_holder = self.shards_holder.write().await;
+
+            match shard_holder.resharding_state() {
+                // Operation is applied again, e.g. on restart
+                Some(state) if state.matches(&reshard_key) => {}
+                Some(state) => {
+                    return Err(CollectionError::bad_request(format!(
+                        "Resharding {:?} of shard {} is already in progress",
+                        state.direction, state.shard_id,
+                    )));
+                }
+                None => {
+                    let migrations = match reshard_key.direction {
+                        ReshardingDirection::Up => {
+                            self.add_resharded_shard(&mut shard_holder, &reshard_key)
+                                .await?
+                        }
+                        ReshardingDirection::Down => {
+                            Self::resharding_down_migrations(&shard_holder, &reshard_key)?
+                        }
+                    };
+
+                    shard_holder.set_resharding_state(Some(ReshardState::new(
+                        reshard_key.clone(),
+                        migrations,
+                    )))?;
+                }
+            }
+        }
+
+        if reshard_key.peer_id == self.this_peer_id {
+            self.resume_resharding_driver(consensus).await;
+        }
+
+        Ok(())
+    }
+
+    /// Create the shard added by resharding up, returns migrations of points into it
+    async fn add_resharded_shard(
+        &self,
+        shard_holder: &mut ShardHolder,
+        reshard_key: &ReshardKey,
+    ) -> CollectionResult<Vec<ReshardMigration>> {
+        let shard_id = reshard_key.shard_id;
+        let old_shard_ids = shard_holder.shard_ids_by_key(reshard_key.shard_key.as_ref());
+
+        if old_shard_ids.is_empty() {
+            return Err(CollectionError::bad_request(
+                "Resharding up requires at least 1 shard".to_string(),
+            ));
+        }
+
+        let replication_factor = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .replication_factor
+            .get() as usize;
+        let replicas =
+            Self::resharded_shard_replicas(shard_holder, reshard_key, replication_factor);
+
+        if !shard_holder.contains_shard(&shard_id) {
+            let replica_set = self.create_replica_set(shard_id, &replicas).await?;
+
+            let payload_schema = self.payload_index_schema.read().schema.clone();
+            for (field_name, field_schema) in payload_schema {
+                let create_index_op = CollectionUpdateOperations::FieldIndexOperation(
+                    FieldIndexOperations::CreateIndex(CreateIndex {
+                        field_name,
+                        field_schema: Some(field_schema),
+                    }),
+                );
+
+                replica_set
+                    .update_local(OperationWithClockTag::from(create_index_op), true)
+                    .await?;
+            }
+
+            shard_holder.add_shard(shard_id, replica_set, reshard_key.shard_key.clone())?;
+        }
+
+        if reshard_key.shard_key.is_none() {
+            self.set_shard_number(shard_id + 1).await?;
+        }
+
+        // Every replica of the new shard must receive its points before reads switch to it
+        Ok(old_shard_ids
+            .into_iter()
+            .filter(|old_shard_id| *old_shard_id != shard_id)
+            .flat_map(|from_shard_id| {
+                replicas.iter().map(move |to_peer_id| ReshardMigration {
+                    from_shard_id,
+                    to_shard_id: shard_id,
+                    to_peer_id: *to_peer_id,
+                    done: false,
+                })
+            })
+            .collect())
+    }
+
+    /// Peers to place replicas of the shard added by resharding up on
+    ///
+    /// The driving peer always gets a replica, the other replicas go to the peers with the least
+    /// shard replicas. Peers are picked from the shard holder state only, so every peer applying
+    /// the operation picks the same ones.
+    fn resharded_shard_replicas(
+        shard_holder: &ShardHolder,
+        reshard_key: &ReshardKey,
+        replication_factor: usize,
+    ) -> Vec<PeerId> {
+        // Shard may already be created, if the operation is applied again
+        if let Some(replica_set) = shard_holder.get_shard(&reshard_key.shard_id) {
+            let mut peers: Vec<_> = replica_set.peers().into_keys().collect();
+            peers.sort_unstable();
+            return peers;
+        }
+
+        let mut replica_count: HashMap<PeerId, usize> = HashMap::new();
+        for replica_set in shard_holder.all_shards() {
+            for peer_id in replica_set.peers().into_keys() {
+                *replica_count.entry(peer_id).or_default() += 1;
+            }
+        }
+        replica_count.remove(&reshard_key.peer_id);
+
+        let mut other_peers: Vec<_> = replica_count.into_iter().collect();
+        other_peers.sort_unstable_by_key(|(peer_id, count)| (*count, *peer_id));
+
+        std::iter::once(reshard_key.peer_id)
+            .chain(other_peers.into_iter().map(|(peer_id, _)| peer_id))
+            .take(replication_factor.max(1))
+            .collect()
+    }
+
+    /// Migrations of points from the removed shard into every replica of the remaining shards
+    fn resharding_down_migrations(
+        shard_holder: &ShardHolder,
+        reshard_key: &ReshardKey,
+    ) -> CollectionResult<Vec<ReshardMigration>> {
+        let shard_ids = shard_holder.shard_ids_by_key(reshard_key.shard_key.as_ref());
+
+        if !shard_ids.contains(&reshard_key.shard_id) {
+            return Err(CollectionError::bad_request(format!(
+                "Shard {} to remove by resharding does not exist",
+                reshard_key.shard_id,
+            )));
+        }
+
+        let mut migrations = Vec::new();
+        for to_shard_id in shard_ids {
+            if to_shard_id == reshard_key.shard_id {
+                continue;
+            }
+            let Some(replica_set) = shard_holder.get_shard(&to_shard_id) else {
+                continue;
+            };
+            let mut peers: Vec<_> = replica_set
+                .peers()
+                .into_iter()
+                .filter(|(_, state)| *state == ReplicaState::Active)
+                .map(|(peer_id, _)| peer_id)
+                .collect();
+            peers.sort_unstable();
+            migrations.extend(peers.into_iter().map(|to_peer_id| ReshardMigration {
+                from_shard_id: reshard_key.shard_id,
+                to_shard_id,
+                to_peer_id,
+                done: false,
+            }));
+        }
+
+        if migrations.is_empty() {
+            return Err(CollectionError::bad_request(
+                "Resharding down requires an active replica of another shard".to_string(),
+            ));
+        }
+
+        Ok(migrations)
+    }
+
+    /// Start the resharding driver on this peer, unless it's already running
+    pub async fn resume_resharding_driver(&self, consensus: Box<dyn ShardTransferConsensus>) {
+        let Some(state) = self.resharding_state().await else {
+            return;
+        };
+        if state.peer_id != self.this_peer_id {
+            return;
+        }
+
+        let mut driver = self.resharding_driver.lock();
+        if driver
+            .as_ref()
+            .map_or(false, |driver| !driver.is_finished())
+        {
+            return;
+        }
+
+        driver.replace(spawn_resharding_driver(
+            state.key(),
+            self.shards_holder.clone(),
+            consensus,
+            self.name(),
+            self.channel_service.clone(),
+            self.this_peer_id,
+        ));
+    }
+
+    fn stop_resharding_driver(&self) {
+        if let Some(driver) = self.resharding_driver.lock().take() {
+            driver.ask_to_cancel();
+        }
+    }
+
+    /// Switch reads to the new hash ring, once all points are migrated
+    pub async fn commit_read_hashring(&self, reshard_key: &ReshardKey) -> CollectionResult<()> {
+        let mut shard_holder = self.shards_holder.write().await;
+        let mut state = Self::matching_resharding_state(&shard_holder, reshard_key)?;
+
+        if state.stage >= ReshardStage::ReadHashRingCommitted {
+            return Ok(());
+        }
+        if state.next_migration().is_some() {
+            return Err(CollectionError::bad_request(format!(
+                "Can not commit read hash ring, {} of {} shard migrations are done",
+                state.migrations_done(),
+                state.migrations.len(),
+            )));
+        }
+
+        state.stage = ReshardStage::ReadHashRingCommitted;
+        shard_holder.set_resharding_state(Some(state))
+    }
+
+    /// Switch writes to the new hash ring, once reads use it on all peers
+    pub async fn commit_write_hashring(&self, reshard_key: &ReshardKey) -> CollectionResult<()> {
+        let mut shard_holder = self.shards_holder.write().await;
+        let mut state = Self::matching_resharding_state(&shard_holder, reshard_key)?;
+
+        if state.stage >= ReshardStage::WriteHashRingCommitted {
+            return Ok(());
+        }
+        if state.stage < ReshardStage::ReadHashRingCommitted {
+            return Err(CollectionError::bad_request(
+                "Can not commit write hash ring before read hash ring".to_string(),
+            ));
+        }
+
+        state.stage = ReshardStage::WriteHashRingCommitted;
+        shard_holder.set_resharding_state(Some(state))
+    }
+
+    /// Finish the resharding operation, removing the shard if resharding down
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn finish_resharding(&self, reshard_key: &ReshardKey) -> CollectionResult<()> {
+        let mut shard_holder = self.shards_holder.write().await;
+        let state = Self::matching_resharding_state(&shard_holder, reshard_key)?;
+
+        if state.stage < ReshardStage::WriteHashRingCommitted {
+            return Err(CollectionError::bad_request(
+                "Can not finish resharding before write hash ring is committed".to_string(),
+            ));
+        }
+
+        if state.direction == ReshardingDirection::Down {
+            shard_holder.remove_shard(state.shard_id).await?;
+            if state.shard_key.is_none() {
+                self.set_shard_number(state.shard_id).await?;
+            }
+        }
+
+        shard_holder.set_resharding_state(None)?;
+        drop(shard_holder);
+
+        self.stop_resharding_driver();
+        Ok(())
+    }
+
+    /// Abort the resharding operation, reverting all of its changes
+    ///
+    /// Resharding can only be aborted until writes switch to the new hash ring.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn abort_resharding(
+        &self,
+        reshard_key: &ReshardKey,
+        reason: &str,
+    ) -> CollectionResult<()> {
+        let mut shard_holder = self.shards_holder.write().await;
+        let state = Self::matching_resharding_state(&shard_holder, reshard_key)?;
+
+        if state.stage >= ReshardStage::WriteHashRingCommitted {
+            return Err(CollectionError::bad_request(
+                "Can not abort resharding after write hash ring is committed".to_string(),
+            ));
+        }
+
+        log::warn!(
+            "Aborting resharding {:?} of shard {} in collection {}: {reason}",
+            state.direction,
+            state.shard_id,
+            self.id,
+        );
+
+        for transfer in shard_holder.get_transfers(|transfer| transfer.is_resharding()) {
+            self._abort_shard_transfer(transfer.key(), &shard_holder)
+                .await?;
+        }
+
+        // Points migrated out of the removed shard are not routed to their new shards anymore
+        let moved_points_filters: Vec<_> = match state.direction {
+            ReshardingDirection::Up => Vec::new(),
+            ReshardingDirection::Down => shard_holder
+                .shard_ids_by_key(state.shard_key.as_ref())
+                .into_iter()
+                .filter(|shard_id| *shard_id != state.shard_id)
+                .filter_map(|shard_id| {
+                    let filter = shard_holder.resharding_old_filter(state.shard_id)?;
+                    Some((shard_id, filter))
+                })
+                .collect(),
+        };
+
+        shard_holder.set_resharding_state(None)?;
+
+        match state.direction {
+            ReshardingDirection::Up => {
+                shard_holder.remove_shard(state.shard_id).await?;
+                if state.shard_key.is_none() {
+                    self.set_shard_number(state.shard_id).await?;
+                }
+            }
+            ReshardingDirection::Down => {
+                for (shard_id, filter) in moved_points_filters {
+                    let filter = Filter::new_must(Condition::CustomIdChecker(
+                        CustomIdCheckerCondition::new(filter),
+                    ));
+                    Self::remove_local_points(&shard_holder, shard_id, &filter).await?;
+                }
+            }
+        }
+        drop(shard_holder);
+
+        self.stop_resharding_driver();
+        Ok(())
+    }
+
+    /// Remove points matching the filter from the local replica of the shard, if there is one
+    async fn remove_local_points(
+        shard_holder: &ShardHolder,
+        shard_id: ShardId,
+        filter: &Filter,
+    ) -> CollectionResult<()> {
+        let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+            return Ok(());
+        };
+        if !replica_set.has_local_shard().await {
+            return Ok(());
+        }
+
+        loop {
+            let ids: Vec<_> = replica_set
+                .scroll_by(
+                    None,
+                    ABORT_CLEANUP_BATCH_SIZE,
+                    &WithPayloadInterface::Bool(false),
+                    &WithVector::Bool(false),
+                    Some(filter),
+                    None,
+                    true,
+                    None,
+                )
+                .await?
+                .into_iter()
+                .map(|point| point.id)
+                .collect();
+
+            if ids.is_empty() {
+                return Ok(());
+            }
+
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                    ids,
+                    preconditions: Vec::new(),
+                });
+            replica_set
+                .update_local(OperationWithClockTag::from(operation), true)
+                .await?;
+        }
+    }
+
+    fn matching_resharding_state(
+        shard_holder: &ShardHolder,
+        reshard_key: &ReshardKey,
+    ) -> CollectionResult<ReshardState> {
+        shard_holder
+            .resharding_state()
+            .filter(|state| state.matches(reshard_key))
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Resharding {:?} of shard {} is not in progress",
+                    reshard_key.direction, reshard_key.shard_id,
+                ))
+            })
+    }
+
+    /// Persist the number of shards of the collection with Auto sharding method
+    async fn set_shard_number(&self, shard_number: ShardId) -> CollectionResult<()> {
+        let Some(shard_number) = NonZeroU32::new(shard_number) else {
+            return Err(CollectionError::service_error(
+                "Collection must have at least 1 shard".to_string(),
+            ));
+        };
+        let mut config = self.collection_config.write().await;
+        config.params.shard_number = shard_number;
+        config.save(&self.path)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -23,12 +23,17 @@ impl Collection {
             return Ok(vec![]);
         }
         // search is a special case of search_batch with a single batch
+        let is_mmr = request.mmr.is_some();
         let request_batch = CoreSearchRequestBatch {
             searches: vec![request],
         };
-        let results = self
-            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
-            .await?;
+        let results = if is_mmr {
+            self.core_search_batch_mmr(request_batch, read_consistency, shard_selection, timeout)
+                .await?
+        } else {
+            self.do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+                .await?
+        };
         Ok(results.into_iter().next().unwrap())
     }
 
@@ -43,6 +48,11 @@ impl Collection {
         if request.searches.iter().all(|s| s.limit == 0) {
             return Ok(vec![]);
         }
+        if request.searches.iter().any(|s| s.mmr.is_some()) {
+            return self
+                .core_search_batch_mmr(request, read_consistency, &shard_selection, timeout)
+                .await;
+        }
         // A factor which determines if we need to use the 2-step search or not
         // Should be adjusted based on usage statistics.
         const PAYLOAD_TRANSFERS_FACTOR_THRESHOLD: usize = 10;
@@ -117,6 +127,66 @@ impl Collection {
         }
     }
 
+    /// Search for candidates first, then rerank them for diversity.
+    ///
+    /// Payload and vectors are only retrieved for the final results, as there might be many
+    /// more candidates.
+    async fn core_search_batch_mmr(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let candidate_searches = request
+            .searches
+            .iter()
+            .map(|search| {
+                let mut candidate_search = search.clone();
+                candidate_search.with_payload = None;
+                candidate_search.with_vector = None;
+                if let Some(mmr) = candidate_search.mmr.take() {
+                    candidate_search.limit = mmr.candidates_limit(search.limit + search.offset);
+                    candidate_search.offset = 0;
+                }
+                candidate_search
+            })
+            .collect();
+        let candidates = self
+            .do_core_search_batch(
+                CoreSearchRequestBatch {
+                    searches: candidate_searches,
+                },
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await?;
+
+        let results =
+            candidates
+                .into_iter()
+                .zip(request.searches)
+                .map(|(candidates, search)| async move {
+                    let mut result = self
+                        .mmr_rerank(candidates, &search, read_consistency, shard_selection)
+                        .await?;
+                    // Offset of searches without MMR is already applied
+                    if search.mmr.is_some() {
+                        result.drain(..search.offset.min(result.len()));
+                    }
+                    self.fill_search_result_with_payload(
+                        result,
+                        search.with_payload,
+                        search.with_vector.unwrap_or_default(),
+                        read_consistency,
+                        shard_selection,
+                    )
+                    .await
+                });
+        future::try_join_all(results).await
+    }
+
     async fn do_core_search_batch(
         &self,
         request: CoreSearchRequestBatch,
@@ -124,6 +194,14 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Modifiers are applied once by the node receiving the client request,
+        // requests to specific shards already carry modified queries
+        let request = if shard_selection.is_shard_id() {
+            request
+        } else {
+            self.apply_sparse_modifiers(request, shard_selection)
+                .await?
+        };
         let request = Arc::new(request);
 
         // query all shards concurrently
@@ -158,6 +236,110 @@ impl Collection {
             .await
     }
 
+    /// Rescale weights of sparse queries with IDF, if the sparse vector is configured with
+    /// the IDF modifier.
+    async fn apply_sparse_modifiers(
+        &self,
+        request: CoreSearchRequestBatch,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<CoreSearchRequestBatch> {
+        let statistics = self
+            .sparse_modifier_statistics(
+                request.searches.iter().map(|search| &search.query),
+                shard_selection,
+            )
+            .await?;
+        if statistics.is_empty() {
+            return Ok(request);
+        }
+
+        let searches = request
+            .searches
+            .into_iter()
+            .map(|mut search| {
+                search.query = apply_sparse_statistics(search.query, &statistics)?;
+                Ok(search)
+            })
+            .collect::<CollectionResult<_>>()?;
+        Ok(CoreSearchRequestBatch { searches })
+    }
+
+    /// Statistics, required to apply modifiers of sparse vectors used in the given queries.
+    ///
+    /// Statistics are aggregated across all selected shards, so scores are comparable between
+    /// shards. Empty, if no query uses a sparse vector with the IDF modifier.
+    pub(super) async fn sparse_modifier_statistics<'a>(
+        &self,
+        queries: impl IntoIterator<Item = &'a QueryEnum>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<SparseStatistics> {
+        let idf_vectors: HashSet<String> = {
+            let collection_config = self.collection_config.read().await;
+            collection_config
+                .params
+                .sparse_vectors
+                .iter()
+                .flatten()
+                .filter(|(_, params)| params.is_idf())
+                .map(|(vector_name, _)| vector_name.clone())
+                .collect()
+        };
+        if idf_vectors.is_empty() {
+            return Ok(SparseStatistics::new());
+        }
+
+        let mut statistics_request = SparseStatisticsRequest::default();
+        for query in queries {
+            let vector_name = query.get_vector_name();
+            if !idf_vectors.contains(vector_name) {
+                continue;
+            }
+            let dims = statistics_request
+                .vectors
+                .entry(vector_name.to_string())
+                .or_default();
+            dims.extend(query.sparse_dimensions());
+        }
+        if statistics_request.vectors.is_empty() {
+            return Ok(SparseStatistics::new());
+        }
+        for dims in statistics_request.vectors.values_mut() {
+            dims.sort_unstable();
+            dims.dedup();
+        }
+
+        self.sparse_statistics(statistics_request, shard_selection)
+            .await
+    }
+
+    /// Collect statistics of sparse vectors, aggregated across all selected shards
+    pub async fn sparse_statistics(
+        &self,
+        request: SparseStatisticsRequest,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<SparseStatistics> {
+        let request = Arc::new(request);
+
+        let all_statistics = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.select_shards(shard_selection)?;
+            let all_statistics = target_shards.iter().map(|(shard, _shard_key)| {
+                shard.sparse_statistics(Arc::clone(&request), shard_selection.is_shard_id())
+            });
+            future::try_join_all(all_statistics).await?
+        };
+
+        let mut statistics = request.empty_statistics();
+        for shard_statistics in all_statistics {
+            for (vector_name, vector_statistics) in statistics.iter_mut() {
+                if let Some(shard_vector_statistics) = shard_statistics.get(vector_name) {
+                    vector_statistics.merge(shard_vector_statistics);
+                }
+            }
+        }
+        Ok(statistics)
+    }
+
     pub(crate) async fn fill_search_result_with_payload(
         &self,
         search_result: Vec<ScoredPoint>,
@@ -265,3 +447,16 @@ impl Collection {
         Ok(top_results)
     }
 }
+
+/// Rescale weights of the sparse query with IDF, if statistics of its vector are collected
+pub(super) fn apply_sparse_statistics(
+    query: QueryEnum,
+    statistics: &SparseStatistics,
+) -> CollectionResult<QueryEnum> {
+    match statistics.get(query.get_vector_name()) {
+        Some(vector_statistics) => {
+            Ok(query.transform_sparse(|vector| vector_statistics.apply_idf(vector))?)
+        }
+        None => Ok(query),
+    }
+}
@@ -79,33 +79,38 @@ impl Collection {
             let is_receiver = replica_set.this_peer_id() == shard_transfer.to;
             let is_sender = replica_set.this_peer_id() == shard_transfer.from;
 
-            let initial_state = match shard_transfer.method.unwrap_or_default() {
-                ShardTransferMethod::StreamRecords => ReplicaState::Partial,
-                // TODO(1.9): switch into recovery state instead
-                ShardTransferMethod::Snapshot | ShardTransferMethod::WalDelta => {
-                    ReplicaState::PartialSnapshot
-                }
-            };
-
-            // Create local shard if it does not exist on receiver, or simply set replica state otherwise
-            // (on all peers, regardless if shard is local or remote on that peer).
-            //
-            // This should disable queries to receiver replica even if it was active before.
-            if !is_local && is_receiver {
-                let shard = LocalShard::build(
-                    shard_id,
-                    self.name(),
-                    &replica_set.shard_path,
-                    self.collection_config.clone(),
-                    self.shared_storage_config.clone(),
-                    self.update_runtime.clone(),
-                    self.optimizer_cpu_budget.clone(),
-                )
-                .await?;
+            // Resharding transfers move points into an existing replica of another shard,
+            // replica states are not changed
+            if !shard_transfer.is_resharding() {
+                let initial_state = match shard_transfer.method.unwrap_or_default() {
+                    ShardTransferMethod::StreamRecords
+                    | ShardTransferMethod::ReshardingStreamRecords => ReplicaState::Partial,
+                    // TODO(1.9): switch into recovery state instead
+                    ShardTransferMethod::Snapshot | ShardTransferMethod::WalDelta => {
+                        ReplicaState::PartialSnapshot
+                    }
+                };
+
+                // Create local shard if it does not exist on receiver, or simply set replica state otherwise
+                // (on all peers, regardless if shard is local or remote on that peer).
+                //
+                // This should disable queries to receiver replica even if it was active before.
+                if !is_local && is_receiver {
+                    let shard = LocalShard::build(
+                        shard_id,
+                        self.name(),
+                        &replica_set.shard_path,
+                        self.collection_config.clone(),
+                        self.shared_storage_config.clone(),
+                        self.update_runtime.clone(),
+                        self.optimizer_cpu_budget.clone(),
+                    )
+                    .await?;
 
-                replica_set.set_local(shard, Some(initial_state)).await?;
-            } else {
-                replica_set.set_replica_state(&shard_transfer.to, initial_state)?;
+                    replica_set.set_local(shard, Some(initial_state)).await?;
+                } else {
+                    replica_set.set_replica_state(&shard_transfer.to, initial_state)?;
+                }
             }
 
             is_local && is_sender
@@ -182,6 +187,23 @@ impl Collection {
 
         let shards_holder_guard = self.shards_holder.read().await;
 
+        // Resharding transfer only moved points between existing replicas
+        if transfer.is_resharding() {
+            if self.this_peer_id == transfer.from {
+                transfer::driver::revert_proxy_shard_to_local(
+                    &shards_holder_guard,
+                    transfer.shard_id,
+                )
+                .await?;
+            }
+            let migration_done = shards_holder_guard.finish_resharding_migration(&transfer)?;
+            log::debug!("migration_done: {migration_done}");
+            let finish_was_registered =
+                shards_holder_guard.register_finish_transfer(&transfer.key())?;
+            log::debug!("finish_was_registered: {finish_was_registered}");
+            return Ok(());
+        }
+
         // Should happen on transfer side
         // Unwrap forward proxy into local shard, or replace it with remote shard
         // depending on the `sync` flag.
@@ -270,7 +292,9 @@ impl Collection {
 
         let transfer = shard_holder_guard.get_transfer(&transfer_key);
 
-        if transfer.map(|x| x.sync).unwrap_or(false) {
+        if transfer_key.to_shard_id.is_some() {
+            // Resharding transfer targets an existing replica of another shard, which is kept
+        } else if transfer.map(|x| x.sync).unwrap_or(false) {
             replica_set.set_replica_state(&transfer_key.to, ReplicaState::Dead)?;
         } else {
             replica_set.remove_peer(transfer_key.to).await?;
@@ -6,6 +6,7 @@ use crate::collection_state::{ShardInfo, State};
 use crate::config::CollectionConfig;
 use crate::operations::types::CollectionResult;
 use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::resharding::ReshardState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::ShardKeyMapping;
 use crate::shards::transfer::ShardTransfer;
@@ -24,9 +25,21 @@ impl Collection {
             .await?;
         self.apply_payload_index_schema(state.payload_index_schema)
             .await?;
+        self.apply_resharding_state(state.resharding).await?;
         Ok(())
     }
 
+    async fn apply_resharding_state(
+        &self,
+        resharding: Option<ReshardState>,
+    ) -> CollectionResult<()> {
+        let mut shard_holder = self.shards_holder.write().await;
+        if shard_holder.resharding_state() == resharding {
+            return Ok(());
+        }
+        shard_holder.set_resharding_state(resharding)
+    }
+
     async fn apply_shard_transfers(
         &self,
         shard_transfers: HashSet<ShardTransfer>,
@@ -65,6 +78,8 @@ impl Collection {
             let mut config = self.collection_config.write().await;
             config.params.replication_factor = new_config.params.replication_factor;
             config.params.write_consistency_factor = new_config.params.write_consistency_factor;
+            // Changed by resharding
+            config.params.shard_number = new_config.params.shard_number;
         }
 
         self.recreate_optimizers_blocking().await?;
@@ -89,11 +104,17 @@ impl Collection {
 
         for (shard_id, shard_info) in shards {
             match self.shards_holder.read().await.get_shard(&shard_id) {
-                Some(replica_set) => replica_set.apply_state(shard_info.replicas).await?,
+                Some(replica_set) => {
+                    replica_set
+                        .apply_state(shard_info.replicas, shard_info.replacements)
+                        .await?
+                }
                 None => {
                     let shard_replicas: Vec<_> = shard_info.replicas.keys().copied().collect();
                     let replica_set = self.create_replica_set(shard_id, &shard_replicas).await?;
-                    replica_set.apply_state(shard_info.replicas).await?;
+                    replica_set
+                        .apply_state(shard_info.replicas, shard_info.replacements)
+                        .await?;
                     extra_shards.insert(shard_id, replica_set);
                 }
             }
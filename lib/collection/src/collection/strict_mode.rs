@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use super::Collection;
+use crate::operations::strict_mode::StrictModeVerification;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl Collection {
+    /// Check the request against the strict mode config of the collection, if it is enabled
+    pub async fn check_strict_mode(
+        &self,
+        request: &impl StrictModeVerification,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<()> {
+        self.check_strict_mode_batch(std::iter::once(request), timeout)
+            .await
+    }
+
+    /// Check the batch of requests against the strict mode config of the collection,
+    /// if it is enabled
+    pub async fn check_strict_mode_batch<'a, R: StrictModeVerification + 'a>(
+        &self,
+        requests: impl ExactSizeIterator<Item = &'a R>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+        let Some(strict_mode_config) = config
+            .strict_mode_config
+            .as_ref()
+            .filter(|config| config.is_enabled())
+        else {
+            return Ok(());
+        };
+
+        if let (Some(timeout), Some(max_timeout)) = (timeout, strict_mode_config.max_timeout) {
+            if timeout.as_secs_f64() > max_timeout as f64 {
+                return Err(CollectionError::strict_mode(format!(
+                    "Timeout of {} seconds exceeds the limit of {max_timeout} seconds",
+                    timeout.as_secs_f64(),
+                )));
+            }
+        }
+
+        if let Some(max_batch_size) = strict_mode_config.search_max_batch_size {
+            if requests.len() > max_batch_size {
+                return Err(CollectionError::strict_mode(format!(
+                    "Batch of {} requests exceeds the limit of {max_batch_size} requests",
+                    requests.len(),
+                )));
+            }
+        }
+
+        let schema = self.payload_index_schema.read();
+        for request in requests {
+            request.check_strict_mode(strict_mode_config, &schema)?;
+        }
+        Ok(())
+    }
+}
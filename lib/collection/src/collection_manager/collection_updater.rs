@@ -42,6 +42,10 @@ impl CollectionUpdater {
         }
     }
 
+    /// Apply the operation to the segments
+    ///
+    /// Preconditions of points are not checked here. They are resolved once by the leader
+    /// replica, which excludes failed points before the operation is replicated.
     pub fn update(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
@@ -73,7 +77,7 @@ impl CollectionUpdater {
 #[cfg(test)]
 mod tests {
     use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Condition, FieldCondition, Filter, Payload, WithPayload};
     use serde_json::json;
     use tempfile::Builder;
 
@@ -81,8 +85,14 @@ mod tests {
     use crate::collection_manager::fixtures::build_test_holder;
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
-    use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
-    use crate::operations::point_ops::{PointOperations, PointStruct};
+    use crate::operations::payload_ops::{
+        DeletePayloadOp, PayloadOps, PayloadUpdate, PayloadUpdateOperator, SetPayloadOp,
+        UpdatePayloadOp,
+    };
+    use crate::operations::point_ops::{
+        ConditionalInsertOperationInternal, PointOperations, PointPrecondition, PointStruct,
+    };
+    use crate::operations::types::FailedPrecondition;
 
     #[test]
     fn test_sync_ops() {
@@ -180,6 +190,7 @@ mod tests {
             101,
             PointOperations::DeletePoints {
                 ids: vec![500.into()],
+                preconditions: vec![],
             },
         )
         .unwrap();
@@ -215,6 +226,7 @@ mod tests {
                 points: Some(points.clone()),
                 filter: None,
                 key: None,
+                preconditions: vec![],
             }),
         )
         .unwrap();
@@ -243,6 +255,7 @@ mod tests {
                 points: Some(vec![3.into()]),
                 keys: vec!["color".parse().unwrap(), "empty".parse().unwrap()],
                 filter: None,
+                preconditions: vec![],
             }),
         )
         .unwrap();
@@ -274,6 +287,7 @@ mod tests {
             102,
             PayloadOps::ClearPayload {
                 points: vec![2.into()],
+                preconditions: vec![],
             },
         )
         .unwrap();
@@ -287,4 +301,224 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
     }
+
+    #[test]
+    fn test_update_payload_ops() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let payload: Payload = serde_json::from_str(r#"{"count":1,"tags":["a"]}"#).unwrap();
+        let points = vec![1.into(), 2.into()];
+
+        process_payload_operation(
+            &segments,
+            100,
+            PayloadOps::SetPayload(SetPayloadOp {
+                payload,
+                points: Some(points.clone()),
+                filter: None,
+                key: None,
+                preconditions: vec![],
+            }),
+        )
+        .unwrap();
+
+        let update = |key: &str, operator| PayloadUpdate {
+            key: key.parse().unwrap(),
+            operator,
+        };
+        let get_payload = |id: u64| {
+            let res = SegmentsSearcher::retrieve(
+                &segments,
+                &[id.into()],
+                &WithPayload::from(true),
+                &false.into(),
+            )
+            .unwrap();
+            serde_json::to_value(res[0].payload.as_ref().unwrap()).unwrap()
+        };
+
+        process_payload_operation(
+            &segments,
+            101,
+            PayloadOps::UpdatePayload(UpdatePayloadOp {
+                updates: vec![
+                    update("count", PayloadUpdateOperator::Increment(2.into())),
+                    update("tags", PayloadUpdateOperator::AddToSet("b".into())),
+                    update("tags", PayloadUpdateOperator::AddToSet("a".into())),
+                ],
+                points: Some(points.clone()),
+                filter: None,
+                condition: None,
+                preconditions: vec![],
+            }),
+        )
+        .unwrap();
+        assert_eq!(get_payload(1), json!({"count": 3, "tags": ["a", "b"]}),);
+
+        // Compare-and-set, only point 2 matches the condition
+        process_payload_operation(
+            &segments,
+            102,
+            PayloadOps::UpdatePayload(UpdatePayloadOp {
+                updates: vec![update("count", PayloadUpdateOperator::Set(10.into()))],
+                points: Some(vec![2.into()]),
+                filter: None,
+                condition: Some(Filter::new_must(Condition::Field(
+                    FieldCondition::new_match("count".parse().unwrap(), 3.into()),
+                ))),
+                preconditions: vec![],
+            }),
+        )
+        .unwrap();
+        process_payload_operation(
+            &segments,
+            103,
+            PayloadOps::UpdatePayload(UpdatePayloadOp {
+                updates: vec![update("count", PayloadUpdateOperator::Set(20.into()))],
+                points: Some(points.clone()),
+                filter: None,
+                condition: Some(Filter::new_must(Condition::Field(
+                    FieldCondition::new_match("count".parse().unwrap(), 10.into()),
+                ))),
+                preconditions: vec![],
+            }),
+        )
+        .unwrap();
+        assert_eq!(get_payload(1)["count"], 3);
+        assert_eq!(get_payload(2)["count"], 20);
+
+        // Operator can't be applied to one of the points, nothing is changed
+        process_payload_operation(
+            &segments,
+            104,
+            PayloadOps::SetPayload(SetPayloadOp {
+                payload: serde_json::from_str(r#"{"count":"many"}"#).unwrap(),
+                points: Some(vec![2.into()]),
+                filter: None,
+                key: None,
+                preconditions: vec![],
+            }),
+        )
+        .unwrap();
+        let result = process_payload_operation(
+            &segments,
+            105,
+            PayloadOps::UpdatePayload(UpdatePayloadOp {
+                updates: vec![update("count", PayloadUpdateOperator::Increment(1.into()))],
+                points: Some(points),
+                filter: None,
+                condition: None,
+                preconditions: vec![],
+            }),
+        );
+        assert!(result.is_err());
+        assert_eq!(get_payload(1)["count"], 3);
+    }
+
+    #[test]
+    fn test_point_preconditions() {
+        let leader_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let follower_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        // Two replicas of the same shard
+        let leader = build_test_holder(leader_dir.path());
+        let follower = build_test_holder(follower_dir.path());
+
+        let point = |id: u64| PointStruct {
+            id: id.into(),
+            vector: vec![0.5, 0.5, 0.5, 0.5].into(),
+            payload: None,
+        };
+
+        // Versions of point 1 diverge: the follower has a newer one
+        CollectionUpdater::update(
+            &follower,
+            50,
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                vec![point(1)].into(),
+            )),
+        )
+        .unwrap();
+
+        // On the leader, point 1 was last changed by operation 6, point 4 by operation 7
+        let preconditions = vec![
+            PointPrecondition {
+                id: 1.into(),
+                if_version: Some(6),
+                if_not_exists: false,
+            },
+            PointPrecondition {
+                id: 4.into(),
+                if_version: Some(6),
+                if_not_exists: false,
+            },
+            PointPrecondition {
+                id: 100.into(),
+                if_version: None,
+                if_not_exists: true,
+            },
+        ];
+        let mut operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: vec![point(1), point(4), point(100)].into(),
+                preconditions,
+            }),
+        );
+
+        // Preconditions are checked once, on the leader
+        let failed = check_preconditions(&leader.read(), operation.preconditions()).unwrap();
+        assert_eq!(
+            failed,
+            vec![FailedPrecondition {
+                id: 4.into(),
+                version: Some(7),
+            }],
+        );
+        operation.resolve_preconditions(&failed);
+        assert!(operation.preconditions().is_empty());
+
+        // Both replicas apply the same points, regardless of their own versions
+        for segments in [&leader, &follower] {
+            CollectionUpdater::update(segments, 100, operation.clone()).unwrap();
+            let segments = segments.read();
+            let versions = point_versions(&segments, &[1.into(), 4.into(), 100.into()]).unwrap();
+            assert_eq!(versions[&1.into()], 100);
+            assert_eq!(versions[&4.into()], 7);
+            assert_eq!(versions[&100.into()], 100);
+        }
+
+        // Point 100 exists now, so it is not deleted
+        let mut operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                ids: vec![1.into(), 100.into()],
+                preconditions: vec![PointPrecondition {
+                    id: 100.into(),
+                    if_version: None,
+                    if_not_exists: true,
+                }],
+            });
+        let failed = check_preconditions(&leader.read(), operation.preconditions()).unwrap();
+        assert_eq!(
+            failed,
+            vec![FailedPrecondition {
+                id: 100.into(),
+                version: Some(100),
+            }],
+        );
+        operation.resolve_preconditions(&failed);
+
+        for segments in [&leader, &follower] {
+            CollectionUpdater::update(segments, 101, operation.clone()).unwrap();
+            let records = SegmentsSearcher::retrieve(
+                segments,
+                &[1.into(), 100.into()],
+                &WithPayload::from(false),
+                &false.into(),
+            )
+            .unwrap();
+            let ids: Vec<_> = records.iter().map(|record| record.id).collect();
+            assert_eq!(ids, vec![100.into()]);
+        }
+    }
 }
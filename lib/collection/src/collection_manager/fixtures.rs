@@ -234,6 +234,8 @@ pub(crate) fn get_merge_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         },
@@ -263,6 +265,8 @@ pub(crate) fn get_indexing_optimizer(
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         },
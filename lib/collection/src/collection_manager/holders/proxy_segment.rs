@@ -4,14 +4,18 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
-use common::types::TelemetryDetail;
-use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use common::types::{ScoreType, TelemetryDetail};
+use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::data_types::facets::{FacetCounts, FacetParams};
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::order_by::OrderingValue;
+use segment::data_types::order_by::{MultiOrderBy, OrderedPoint};
+use segment::data_types::sample::{merge_samples, SampledPoint};
+use segment::data_types::sparse_statistics::SparseVectorStatistics;
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
@@ -40,6 +44,18 @@ pub struct ProxySegment {
     created_indexes: LockedFieldsMap,
     last_flushed_version: Arc<RwLock<Option<SeqNumberType>>>,
     wrapped_config: SegmentConfig,
+    /// Sparse vectors of deleted points, which are still counted by the wrapped segment,
+    /// per vector name
+    deleted_sparse_statistics: Mutex<HashMap<String, DeletedSparseStatistics>>,
+}
+
+/// Statistics of sparse vectors of points deleted through the proxy
+#[derive(Default)]
+struct DeletedSparseStatistics {
+    /// Deleted points, which are already accounted in `statistics`
+    accounted_points: HashSet<PointIdType>,
+    /// Number of deleted vectors and document frequencies of all their dimensions
+    statistics: SparseVectorStatistics,
 }
 
 impl ProxySegment {
@@ -59,6 +75,7 @@ impl ProxySegment {
             deleted_indexes,
             last_flushed_version: Arc::new(RwLock::new(None)),
             wrapped_config,
+            deleted_sparse_statistics: Mutex::new(HashMap::new()),
         }
     }
 
@@ -516,8 +533,8 @@ impl SegmentEntry for ProxySegment {
         &'a self,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-        order_by: &'a segment::data_types::order_by::OrderBy,
-    ) -> OperationResult<Vec<(OrderingValue, PointIdType)>> {
+        order_by: &'a MultiOrderBy,
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let deleted_points = self.deleted_points.read();
         let mut read_points = if deleted_points.is_empty() {
             self.wrapped_segment
@@ -539,10 +556,91 @@ impl SegmentEntry for ProxySegment {
             .read()
             .read_ordered_filtered(limit, filter, order_by)?;
         read_points.append(&mut write_segment_points);
-        read_points.sort_unstable();
+        read_points.sort_unstable_by(|a, b| order_by.compare(a, b));
+        read_points.dedup();
+        if let Some(limit) = limit {
+            read_points.truncate(limit);
+        }
         Ok(read_points)
     }
 
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        seed: u64,
+    ) -> Vec<SampledPoint> {
+        let deleted_points = self.deleted_points.read();
+        let read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter, seed)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_random_filtered(
+                limit,
+                Some(&wrapped_filter),
+                seed,
+            )
+        };
+        let write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_random_filtered(limit, filter, seed);
+        merge_samples(read_points.into_iter().chain(write_segment_points), limit)
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_scores: &[HashMap<PointIdType, ScoreType>],
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let mut scored_points = self.wrapped_segment.get().read().rescore_with_formula(
+            formula,
+            prefetches_scores,
+            is_stopped,
+        )?;
+        {
+            let deleted_points = self.deleted_points.read();
+            scored_points.retain(|point| !deleted_points.contains(&point.id));
+        }
+        scored_points.extend(self.write_segment.get().read().rescore_with_formula(
+            formula,
+            prefetches_scores,
+            is_stopped,
+        )?);
+        Ok(scored_points)
+    }
+
+    fn facet(&self, params: &FacetParams, is_stopped: &AtomicBool) -> OperationResult<FacetCounts> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(params, is_stopped)?
+        } else {
+            let wrapped_params = FacetParams {
+                filter: Some(self.add_deleted_points_condition_to_filter(
+                    params.filter.as_ref(),
+                    &deleted_points,
+                )),
+                ..params.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(&wrapped_params, is_stopped)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(params, is_stopped)?;
+        counts.merge(write_segment_counts);
+        Ok(counts)
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -800,6 +898,60 @@ impl SegmentEntry for ProxySegment {
         self.write_segment.get().read().vector_dims()
     }
 
+    fn fill_sparse_vector_statistics(
+        &self,
+        vector_name: &str,
+        statistics: &mut SparseVectorStatistics,
+    ) -> OperationResult<()> {
+        // Write segment is shared between all proxies of the optimization,
+        // so it is accounted once by the shard, see `LocalShard::collect_sparse_statistics`
+        let wrapped_segment = self.wrapped_segment.get();
+        let wrapped_segment = wrapped_segment.read();
+        wrapped_segment.fill_sparse_vector_statistics(vector_name, statistics)?;
+
+        // Points deleted through the proxy are still live in the wrapped segment.
+        // The set of deleted points only grows, so only newly deleted points are read.
+        let mut deleted_sparse_statistics = self.deleted_sparse_statistics.lock();
+        let deleted = deleted_sparse_statistics
+            .entry(vector_name.to_string())
+            .or_default();
+        let deleted_points = self.deleted_points.read();
+        if deleted.accounted_points.len() != deleted_points.len() {
+            for &point_id in deleted_points.iter() {
+                if deleted.accounted_points.contains(&point_id) {
+                    continue;
+                }
+                let vector = if wrapped_segment.has_point(point_id) {
+                    wrapped_segment.vector(vector_name, point_id)?
+                } else {
+                    None
+                };
+                deleted.accounted_points.insert(point_id);
+                let Some(Vector::Sparse(vector)) = vector else {
+                    continue;
+                };
+                deleted.statistics.indexed_vector_count += 1;
+                for dim in &vector.indices {
+                    *deleted
+                        .statistics
+                        .document_frequencies
+                        .entry(*dim)
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        statistics.indexed_vector_count = statistics
+            .indexed_vector_count
+            .saturating_sub(deleted.statistics.indexed_vector_count);
+        for (dim, frequency) in statistics.document_frequencies.iter_mut() {
+            if let Some(deleted_frequency) = deleted.statistics.document_frequencies.get(dim) {
+                *frequency = frequency.saturating_sub(*deleted_frequency);
+            }
+        }
+        Ok(())
+    }
+
     fn take_snapshot(
         &self,
         temp_path: &Path,
@@ -1377,6 +1529,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -1387,6 +1541,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
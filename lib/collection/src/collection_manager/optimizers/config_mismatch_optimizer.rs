@@ -14,6 +14,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
 };
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
+use crate::operations::types::SparseIndexParams;
 
 /// Looks for segments having a mismatch between configured and actual parameters
 ///
@@ -60,12 +61,16 @@ impl ConfigMismatchOptimizer {
 
     /// Check if current configuration requires sparse vectors index to be stored on disk
     #[allow(dead_code)]
-    fn check_if_sparse_vectors_index_on_disk(&self, vector_name: &str) -> Option<bool> {
+    fn get_sparse_index_params(&self, vector_name: &str) -> Option<SparseIndexParams> {
         self.collection_params
             .sparse_vectors
             .as_ref()
             .and_then(|vector_params| vector_params.get(vector_name))
             .and_then(|params| params.index)
+    }
+
+    fn check_if_sparse_vectors_index_on_disk(&self, vector_name: &str) -> Option<bool> {
+        self.get_sparse_index_params(vector_name)
             .and_then(|index| index.on_disk)
     }
 
@@ -192,6 +197,30 @@ impl ConfigMismatchOptimizer {
                         .sparse_vector_data
                         .iter()
                         .any(|(vector_name, vector_data)| {
+                            if vector_data.index.index_type == SparseIndexType::MutableRam {
+                                return false; // Do nothing for mutable RAM
+                            }
+
+                            // Rebuild if datatype of the weights differs
+                            let datatype_mismatch = self
+                                .get_sparse_index_params(vector_name)
+                                .is_some_and(|index_params| {
+                                    index_params.datatype.is_some_and(|datatype| {
+                                        datatype != vector_data.index.effective_datatype()
+                                    }) || index_params.full_precision_on_disk.is_some_and(
+                                        |full_precision_on_disk| {
+                                            full_precision_on_disk
+                                                != vector_data
+                                                    .index
+                                                    .full_precision_on_disk
+                                                    .unwrap_or_default()
+                                        },
+                                    )
+                                });
+                            if datatype_mismatch {
+                                return true;
+                            }
+
                             let Some(is_required_on_disk) =
                                 self.check_if_sparse_vectors_index_on_disk(vector_name)
                             else {
@@ -312,6 +341,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         };
@@ -333,6 +364,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         // Optimizers used in test
@@ -459,6 +491,8 @@ mod tests {
                         hnsw_config: Some(hnsw_config_vector1),
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -469,6 +503,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
@@ -498,6 +534,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config_collection.max_indexing_threads);
@@ -639,6 +676,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: Some(quantization_config_vector1.clone()),
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -649,6 +688,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
@@ -359,6 +359,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 )
             })
@@ -484,6 +486,8 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    multivector_config: None,
+                    datatype: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -780,6 +784,8 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    multivector_config: None,
+                    datatype: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -855,6 +861,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: Some(false),
+                multivector_config: None,
+                datatype: None,
             }),
             ..CollectionParams::empty()
         };
@@ -876,6 +884,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         {
@@ -230,6 +230,13 @@ pub trait SegmentOptimizer {
                         };
 
                         config.index.index_type = index_type;
+
+                        // Weights of reduced precision are used by immutable indexes only
+                        if let Some(index_params) = params.index {
+                            config.index.datatype = index_params.datatype;
+                            config.index.full_precision_on_disk =
+                                index_params.full_precision_on_disk;
+                        }
                     }
                 }
             });
@@ -23,6 +23,9 @@ use crate::config::CollectionParams;
 /// Since the creation of a segment, a lot of points or vectors may have been soft-deleted. This
 /// results in the index slowly breaking apart, and unnecessary storage usage.
 ///
+/// Vectors linked into an appendable HNSW index in place have no links of the payload index
+/// graphs. The index is rebuilt once they make up a large part of it too.
+///
 /// This optimizer will look for the worst segment to rebuilt the index and minimize storage usage.
 pub struct VacuumOptimizer {
     deleted_threshold: f64,
@@ -156,7 +159,23 @@ impl VacuumOptimizer {
 
                 let reached_minimum = deleted_from_index >= self.min_vectors_number;
                 let reached_ratio = deleted_ratio > self.deleted_threshold;
-                (reached_minimum && reached_ratio).then_some(deleted_ratio)
+                let deleted_ratio = (reached_minimum && reached_ratio).then_some(deleted_ratio);
+
+                // Vectors linked in place, without links for filtered search
+                let in_place_linked = vector_index.in_place_linked_count();
+                let in_place_ratio = if indexed_vector_count != 0 {
+                    in_place_linked as f64 / indexed_vector_count as f64
+                } else {
+                    0.0
+                };
+                let in_place_ratio = (in_place_linked >= self.min_vectors_number
+                    && in_place_ratio > self.deleted_threshold)
+                    .then_some(in_place_ratio);
+
+                deleted_ratio
+                    .into_iter()
+                    .chain(in_place_ratio)
+                    .reduce(f64::max)
             })
             .max_by_key(|ratio| OrderedFloat(*ratio))
     }
@@ -306,6 +325,8 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    multivector_config: None,
+                    datatype: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -404,6 +425,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -414,6 +437,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ])),
@@ -451,6 +476,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -10,12 +10,16 @@ use ordered_float::Float;
 use parking_lot::RwLock;
 use segment::common::operation_error::OperationError;
 use segment::common::BYTES_IN_KB;
+use segment::data_types::facets::{FacetCounts, FacetParams};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::QueryVector;
 use segment::entry::entry_point::SegmentEntry;
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
+use segment::spaces::tools::peek_top_largest_iterable;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
-    WithPayload, WithPayloadInterface, WithVector, VECTOR_ELEMENT_SIZE,
+    Condition, Filter, HasIdCondition, Indexes, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
+    VECTOR_ELEMENT_SIZE,
 };
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
@@ -24,7 +28,7 @@ use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentH
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequestBatch, QueryEnum, Record,
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, QueryEnum, Record,
 };
 
 type BatchOffset = usize;
@@ -290,6 +294,119 @@ impl SegmentsSearcher {
         Ok(top_scores)
     }
 
+    /// Re-score given candidate points with exact search
+    ///
+    /// For each request of the batch, only points from the corresponding list of candidates
+    /// are considered. Their stored vectors are scored exactly against the request query,
+    /// bypassing the HNSW index and quantized vectors. This is useful to refine scores of
+    /// candidates found by a cheaper search, e.g. for multi-vectors.
+    pub async fn rescore(
+        segments: Arc<RwLock<SegmentHolder>>,
+        batch_request: &CoreSearchRequestBatch,
+        candidates: Vec<Vec<PointIdType>>,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+        search_optimized_threshold_kb: usize,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        debug_assert_eq!(batch_request.searches.len(), candidates.len());
+
+        let searches = batch_request
+            .searches
+            .iter()
+            .zip(candidates)
+            .map(|(request, candidates)| {
+                let candidates_filter = Filter::new_must(Condition::HasId(HasIdCondition {
+                    has_id: candidates.into_iter().collect(),
+                }));
+                CoreSearchRequest {
+                    filter: Some(match &request.filter {
+                        Some(filter) => filter.merge(&candidates_filter),
+                        None => candidates_filter,
+                    }),
+                    params: Some(SearchParams {
+                        exact: true,
+                        quantization: None,
+                        ..request.params.unwrap_or_default()
+                    }),
+                    ..request.clone()
+                }
+            })
+            .collect();
+
+        Self::search(
+            segments,
+            Arc::new(CoreSearchRequestBatch { searches }),
+            runtime_handle,
+            false,
+            is_stopped,
+            search_optimized_threshold_kb,
+        )
+        .await
+    }
+
+    /// Score candidates, found by prefetches, with the formula.
+    ///
+    /// Each segment scores candidates it contains, the latest version of each point is kept.
+    /// Returns `limit` best points.
+    pub async fn rescore_with_formula(
+        segments: Arc<RwLock<SegmentHolder>>,
+        formula: Arc<ParsedFormula>,
+        prefetches_scores: Arc<Vec<HashMap<PointIdType, ScoreType>>>,
+        limit: usize,
+        runtime_handle: &Handle,
+        is_stopped: Arc<AtomicBool>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let tasks: Vec<_> = segments
+            .read()
+            .iter()
+            .map(|(_id, segment)| {
+                let segment = segment.clone();
+                let formula = formula.clone();
+                let prefetches_scores = prefetches_scores.clone();
+                let is_stopped = is_stopped.clone();
+                runtime_handle.spawn_blocking(move || {
+                    segment.get().read().rescore_with_formula(
+                        &formula,
+                        &prefetches_scores,
+                        &is_stopped,
+                    )
+                })
+            })
+            .collect();
+
+        let mut latest_points: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+        for segment_points in try_join_all(tasks).await? {
+            for point in segment_points? {
+                match latest_points.get(&point.id) {
+                    Some(existing) if existing.version >= point.version => {}
+                    _ => {
+                        latest_points.insert(point.id, point);
+                    }
+                }
+            }
+        }
+
+        Ok(peek_top_largest_iterable(
+            latest_points.into_values(),
+            limit,
+        ))
+    }
+
+    /// Count points per value of the facet key, summed over all segments
+    pub fn facet(
+        segments: &RwLock<SegmentHolder>,
+        params: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> CollectionResult<FacetCounts> {
+        let mut counts = FacetCounts::default();
+        for (_id, segment) in segments.read().iter() {
+            let segment = segment.get();
+            let segment_counts = segment.read().facet(params, is_stopped)?;
+            counts.merge(segment_counts);
+        }
+        Ok(counts)
+    }
+
     pub fn retrieve(
         segments: &RwLock<SegmentHolder>,
         points: &[PointIdType],
@@ -332,6 +449,7 @@ impl SegmentsSearcher {
                             }
                         },
                         shard_key: None,
+                        order_values: None,
                     },
                 );
                 point_version.insert(id, version);
@@ -635,7 +753,7 @@ mod tests {
     use crate::collection_manager::fixtures::{
         build_test_holder, optimize_segment, random_segment,
     };
-    use crate::operations::types::{CoreSearchRequest, SearchRequestInternal};
+    use crate::operations::types::SearchRequestInternal;
     use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
 
     #[test]
@@ -684,6 +802,7 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            mmr: None,
         };
 
         let batch_request = CoreSearchRequestBatch {
@@ -712,6 +831,50 @@ mod tests {
         assert!(result[1].id == 3.into() || result[1].id == 11.into());
     }
 
+    #[tokio::test]
+    async fn test_segments_rescore() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment_holder = Arc::new(build_test_holder(dir.path()));
+
+        let req = CoreSearchRequest {
+            query: vec![1.0, 1.0, 1.0, 1.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit: 5,
+            score_threshold: None,
+            offset: 0,
+            mmr: None,
+        };
+
+        let batch_request = CoreSearchRequestBatch {
+            searches: vec![req],
+        };
+
+        let candidates: Vec<PointIdType> = vec![1.into(), 2.into(), 11.into()];
+
+        let result = SegmentsSearcher::rescore(
+            segment_holder,
+            &batch_request,
+            vec![candidates.clone()],
+            &Handle::current(),
+            Arc::new(AtomicBool::new(false)),
+            DEFAULT_INDEXING_THRESHOLD_KB,
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+
+        // Only candidates are returned, best one first
+        assert_eq!(result.len(), candidates.len());
+        assert!(result.iter().all(|point| candidates.contains(&point.id)));
+        assert_eq!(result[0].id, 11.into());
+    }
+
     #[tokio::test]
     async fn test_segments_search_sampling() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -738,6 +901,7 @@ mod tests {
                 filter: None,
                 params: None,
                 score_threshold: None,
+                mmr: None,
             };
             let req2 = SearchRequestInternal {
                 vector: random_vector(&mut rnd, 4).into(),
@@ -748,6 +912,7 @@ mod tests {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             };
 
             let batch_request = CoreSearchRequestBatch {
@@ -9,14 +9,16 @@ use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    Condition, Filter, HasIdCondition, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PointIdType, SeqNumberType,
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::payload_ops::{PayloadOps, PayloadUpdate, UpdatePayloadOp};
+use crate::operations::point_ops::{
+    ConditionalInsertOperationInternal, PointOperations, PointPrecondition, PointStruct,
+};
+use crate::operations::types::{CollectionError, CollectionResult, FailedPrecondition};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
 
@@ -32,6 +34,48 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Current versions of the given points, missing points are not included
+pub(crate) fn point_versions(
+    segments: &SegmentHolder,
+    ids: &[PointIdType],
+) -> CollectionResult<HashMap<PointIdType, SeqNumberType>> {
+    // A point might temporarily be present in multiple segments, the latest version is used
+    let mut versions: HashMap<PointIdType, SeqNumberType> = HashMap::with_capacity(ids.len());
+    segments.read_points(ids, |id, segment| {
+        let version = segment.point_version(id).unwrap_or_default();
+        let latest = versions.entry(id).or_insert(version);
+        *latest = version.max(*latest);
+        Ok(true)
+    })?;
+    Ok(versions)
+}
+
+/// Check preconditions of the points against their current versions
+///
+/// Returns preconditions, which don't hold, along with the current version of the point.
+pub(crate) fn check_preconditions(
+    segments: &SegmentHolder,
+    preconditions: &[PointPrecondition],
+) -> CollectionResult<Vec<FailedPrecondition>> {
+    let ids: Vec<_> = preconditions
+        .iter()
+        .map(|precondition| precondition.id)
+        .collect();
+    let versions = point_versions(segments, &ids)?;
+
+    let failed = preconditions
+        .iter()
+        .filter_map(|precondition| {
+            let version = versions.get(&precondition.id).copied();
+            (!precondition.check(version)).then_some(FailedPrecondition {
+                id: precondition.id,
+                version,
+            })
+        })
+        .collect();
+    Ok(failed)
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -184,6 +228,119 @@ pub(crate) fn set_payload_by_filter(
     set_payload(segments, op_num, payload, &affected_points, key)
 }
 
+/// Update payload of the points with atomic operators
+///
+/// New payloads of all points are computed before any of them is written, so an operator that
+/// can't be applied to one of the points fails the whole operation without side effects.
+pub(crate) fn update_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    updates: &[PayloadUpdate],
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    // A point might temporarily be present in multiple segments, the latest version is used
+    let mut new_payloads: HashMap<PointIdType, (SeqNumberType, Option<Payload>)> =
+        HashMap::with_capacity(points.len());
+    segments.read_points(points, |id, segment| {
+        let version = segment.point_version(id).unwrap_or_default();
+        if matches!(new_payloads.get(&id), Some((latest, _)) if *latest > version) {
+            return Ok(false);
+        }
+        // Already updated by this operation, e.g. on WAL replay
+        if version >= op_num {
+            new_payloads.insert(id, (version, None));
+            return Ok(true);
+        }
+        let mut payload = segment.payload(id)?;
+        let mut is_changed = false;
+        for update in updates {
+            is_changed |=
+                update
+                    .apply(&mut payload)
+                    .map_err(|err| OperationError::ValidationError {
+                        description: format!(
+                            "Can't update payload key `{}` of point {id}: {err}",
+                            update.key,
+                        ),
+                    })?;
+        }
+        new_payloads.insert(id, (version, is_changed.then_some(payload)));
+        Ok(true)
+    })?;
+    let found_points: HashSet<_> = new_payloads.keys().copied().collect();
+    check_unprocessed_points(points, &found_points)?;
+
+    let changed_points: Vec<_> = new_payloads
+        .iter()
+        .filter(|(_, (_, payload))| payload.is_some())
+        .map(|(id, _)| *id)
+        .collect();
+    segments.apply_points_with_conditional_move(
+        op_num,
+        &changed_points,
+        |id, write_segment| match &new_payloads[&id] {
+            (_, Some(payload)) => write_segment.set_full_payload(op_num, id, payload),
+            (_, None) => Ok(false),
+        },
+        |segment| {
+            iproduct!(segment.get_indexed_fields().keys(), updates).all(|(indexed_path, update)| {
+                !indexed_path.is_affected_by_value_remove(&update.key)
+            })
+        },
+    )?;
+    Ok(found_points.len())
+}
+
+pub(crate) fn update_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    updates: &[PayloadUpdate],
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    update_payload(segments, op_num, updates, &affected_points)
+}
+
+fn process_update_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    operation: UpdatePayloadOp,
+) -> CollectionResult<usize> {
+    let UpdatePayloadOp {
+        updates,
+        points,
+        filter,
+        condition,
+        preconditions: _,
+    } = operation;
+
+    let Some(condition) = condition else {
+        return if let Some(points) = points {
+            update_payload(segments, op_num, &updates, &points)
+        } else if let Some(filter) = filter {
+            update_payload_by_filter(segments, op_num, &updates, &filter)
+        } else {
+            Err(CollectionError::BadRequest {
+                description: "No points or filter specified".to_string(),
+            })
+        };
+    };
+
+    // Points, which don't match the condition, are skipped, not reported as missing
+    let filter = if let Some(points) = points {
+        Filter::new_must(Condition::HasId(HasIdCondition::from(
+            points.into_iter().collect::<HashSet<_>>(),
+        )))
+    } else if let Some(filter) = filter {
+        filter
+    } else {
+        return Err(CollectionError::BadRequest {
+            description: "No points or filter specified".to_string(),
+        });
+    };
+    update_payload_by_filter(segments, op_num, &updates, &filter.merge(&condition))
+}
+
 pub(crate) fn delete_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -445,31 +602,13 @@ pub(crate) fn process_point_operation(
 ) -> CollectionResult<usize> {
     match point_operation {
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
-        PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let all_vectors = batch.vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: vectors.into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: vectors.into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+        PointOperations::UpsertPoints(operation)
+        | PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+            points_op: operation,
+            ..
+        }) => {
+            // Preconditions are resolved by the leader replica before the operation is replicated
+            let points = operation.into_point_vec();
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
             Ok(res)
         }
@@ -554,6 +693,9 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::UpdatePayload(operation) => {
+            process_update_payload(&segments.read(), op_num, operation)
+        }
     }
 }
 
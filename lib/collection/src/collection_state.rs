@@ -6,6 +6,7 @@ use validator::Validate;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::config::CollectionConfig;
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::ReshardState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::ShardKeyMapping;
 use crate::shards::transfer::ShardTransfer;
@@ -13,6 +14,9 @@ use crate::shards::transfer::ShardTransfer;
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ShardInfo {
     pub replicas: HashMap<PeerId, ReplicaState>,
+    /// Replicas created by automatic replication in place of a dead replica
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub replacements: HashSet<PeerId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone, PartialEq)]
@@ -26,6 +30,8 @@ pub struct State {
     pub shards_key_mapping: ShardKeyMapping,
     #[serde(default)]
     pub payload_index_schema: PayloadIndexSchema,
+    #[serde(default)]
+    pub resharding: Option<ReshardState>,
 }
 
 impl State {
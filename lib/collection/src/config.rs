@@ -19,6 +19,8 @@ use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
+use crate::operations::strict_mode::StrictModeConfig;
+use crate::operations::ttl::TtlConfig;
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
     VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
@@ -104,6 +106,10 @@ pub struct CollectionParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Expiration of points. If not set, points never expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub ttl_config: Option<TtlConfig>,
 }
 
 impl Anonymize for CollectionParams {
@@ -117,6 +123,7 @@ impl Anonymize for CollectionParams {
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            ttl_config: self.ttl_config.clone(),
         }
     }
 }
@@ -149,6 +156,9 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 impl CollectionConfig {
@@ -194,6 +204,7 @@ impl CollectionParams {
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            ttl_config: None,
         }
     }
 
@@ -300,7 +311,7 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index } = update_params.clone();
+            let SparseVectorParams { index, modifier } = update_params.clone();
 
             if let Some(index) = index {
                 if let Some(existing_index) = &mut sparse_vector_params.index {
@@ -309,6 +320,11 @@ impl CollectionParams {
                     sparse_vector_params.index = Some(index);
                 }
             }
+
+            // Modifier is applied at query time, so it can be changed without reindexing
+            if let Some(modifier) = modifier {
+                sparse_vector_params.modifier = Some(modifier);
+            }
         }
         Ok(())
     }
@@ -337,6 +353,8 @@ impl CollectionParams {
                         } else {
                             VectorStorageType::Memory
                         },
+                        multivector_config: params.multivector_config,
+                        datatype: params.datatype,
                     },
                 )
             })
@@ -362,6 +380,10 @@ impl CollectionParams {
                                     .index
                                     .and_then(|index| index.full_scan_threshold),
                                 index_type: SparseIndexType::MutableRam,
+                                datatype: params.index.and_then(|index| index.datatype),
+                                full_precision_on_disk: params
+                                    .index
+                                    .and_then(|index| index.full_precision_on_disk),
                             },
                         },
                     )
@@ -107,6 +107,7 @@ fn discovery_into_core_search(
         with_payload: request.with_payload,
         with_vector: request.with_vector,
         score_threshold: None,
+        mmr: None,
     };
 
     Ok(core_search)
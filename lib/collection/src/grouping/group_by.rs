@@ -165,6 +165,7 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             with_payload,
             with_vector,
             score_threshold,
+            mmr: None,
         };
 
         GroupRequest {
@@ -212,6 +213,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             score_threshold,
             using,
             lookup_from,
+            mmr: None,
         };
 
         GroupRequest {
@@ -1,5 +1,10 @@
+use std::fmt;
 use std::hash::Hash;
 
+use segment::types::{CustomIdChecker, PointIdType};
+
+use crate::shards::shard::ShardId;
+
 pub enum HashRing<T: Hash + Copy> {
     Raw(hashring::HashRing<T>),
     Fair {
@@ -71,6 +76,84 @@ impl<T: Hash + Copy> HashRing<T> {
     }
 }
 
+/// Hash rings used to route points, two of them while resharding is in progress
+pub enum HashRingRouter<T: Hash + Copy = ShardId> {
+    /// Regular state, a single hash ring is used for all operations
+    Single(HashRing<T>),
+    /// Resharding is in progress, points are routed by both the old and the new hash ring
+    Resharding { old: HashRing<T>, new: HashRing<T> },
+}
+
+impl<T: Hash + Copy> HashRingRouter<T> {
+    /// Add shard to all hash rings of the router
+    pub fn add(&mut self, shard: T) {
+        match self {
+            HashRingRouter::Single(ring) => ring.add(shard),
+            HashRingRouter::Resharding { old, new } => {
+                old.add(shard);
+                new.add(shard);
+            }
+        }
+    }
+
+    /// Hash ring, which routes points to the shards currently owning them
+    pub fn owner_ring(&self) -> &HashRing<T> {
+        match self {
+            HashRingRouter::Single(ring) => ring,
+            HashRingRouter::Resharding { old, .. } => old,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            HashRingRouter::Single(ring) => ring.is_empty(),
+            HashRingRouter::Resharding { old, new } => old.is_empty() && new.is_empty(),
+        }
+    }
+}
+
+/// Point id checker, which only accepts points routed to the given shard by the hash ring
+pub struct HashRingFilter {
+    ring: HashRing<ShardId>,
+    expected_shard_id: ShardId,
+}
+
+impl HashRingFilter {
+    pub fn new(ring: HashRing<ShardId>, expected_shard_id: ShardId) -> Self {
+        Self {
+            ring,
+            expected_shard_id,
+        }
+    }
+
+    pub fn ring(&self) -> &HashRing<ShardId> {
+        &self.ring
+    }
+
+    pub fn expected_shard_id(&self) -> ShardId {
+        self.expected_shard_id
+    }
+
+    pub fn check(&self, point_id: PointIdType) -> bool {
+        self.ring.get(&point_id) == Some(&self.expected_shard_id)
+    }
+}
+
+impl fmt::Debug for HashRingFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashRingFilter")
+            .field("ring_size", &self.ring.len())
+            .field("expected_shard_id", &self.expected_shard_id)
+            .finish()
+    }
+}
+
+impl CustomIdChecker for HashRingFilter {
+    fn check(&self, point_id: PointIdType) -> bool {
+        HashRingFilter::check(self, point_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +173,30 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_points_keep_owner_or_move_to_new_shard() {
+        let mut old = HashRing::fair(100);
+        let mut new = HashRing::fair(100);
+        for shard_id in 0..6 {
+            old.add(shard_id);
+            new.add(shard_id);
+        }
+        new.add(6);
+
+        let filter = HashRingFilter::new(new, 6);
+
+        let mut moved = 0;
+        for point_id in 0..10_000u64 {
+            let point_id = PointIdType::NumId(point_id);
+            let old_owner = *old.get(&point_id).unwrap();
+            let new_owner = *filter.ring.get(&point_id).unwrap();
+            if old_owner != new_owner {
+                assert_eq!(new_owner, 6, "Point must only move to the new shard");
+                moved += 1;
+            }
+            assert_eq!(filter.check(point_id), new_owner == 6);
+        }
+        assert!(moved > 0, "Some points must move to the new shard");
+    }
 }
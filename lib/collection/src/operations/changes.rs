@@ -0,0 +1,745 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use schemars::JsonSchema;
+use segment::data_types::vectors::VectorStruct;
+use segment::types::{
+    Filter, Payload, PointIdType, SeqNumberType, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
+use super::point_ops::PointOperations;
+use super::types::{CollectionError, CollectionResult};
+use super::{ClockTag, CollectionUpdateOperations};
+use crate::shards::local_shard::clock_map::RecoveryPoint;
+use crate::shards::shard::{PeerId, ShardId};
+
+/// Default maximal number of operations, read from a shard at once
+pub const DEFAULT_CHANGES_LIMIT: usize = 100;
+
+/// Read changes of the collection, made by update operations
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesRequest {
+    /// Position to start reading each of the shards from, as returned in `next_offset` of the
+    /// previous batch of the shard. If empty, all shards are read from their oldest available
+    /// position.
+    /// Positions are valid on every replica of the shard, so reading can be resumed on any peer.
+    #[serde(default)]
+    pub offsets: HashMap<ShardId, ChangesOffset>,
+    /// Maximal number of operations to read from each shard at once. Default: 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Select which payload to return with the upserted points. Default: all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Select which vectors to return with the upserted points. Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_vector: Option<WithVector>,
+}
+
+/// Changes of a shard, reduced to their final effect
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesBatch {
+    /// Id of the shard, which was changed
+    pub shard_id: ShardId,
+    /// Changes, ordered by their position
+    pub changes: Vec<PointChange>,
+    /// Position to resume reading the shard from
+    pub next_offset: ChangesOffset,
+}
+
+/// Position in the changes of a shard
+///
+/// Update operations are identified by their clock tags, which are the same on every replica of
+/// the shard. The position holds the last read tick of each of the clocks.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesOffset {
+    /// Last read tick of each of the clocks, which updated the shard
+    #[serde(default)]
+    pub clocks: Vec<ChangesClock>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangesClock {
+    /// Peer, which the clock belongs to
+    pub peer_id: PeerId,
+    /// Id of the clock on the peer
+    pub clock_id: u32,
+    /// Last read tick of the clock
+    pub clock_tick: u64,
+}
+
+impl ChangesOffset {
+    /// Last read tick of the clock, zero if no operation of the clock was read
+    pub fn clock_tick(&self, peer_id: PeerId, clock_id: u32) -> u64 {
+        self.clocks
+            .iter()
+            .find(|clock| clock.peer_id == peer_id && clock.clock_id == clock_id)
+            .map_or(0, |clock| clock.clock_tick)
+    }
+
+    /// Whether the operation with the given clock tag is read at this position
+    pub fn is_read(&self, clock_tag: &ClockTag) -> bool {
+        clock_tag.clock_tick <= self.clock_tick(clock_tag.peer_id, clock_tag.clock_id)
+    }
+
+    /// Move the position past the given clock tick
+    pub fn advance(&mut self, peer_id: PeerId, clock_id: u32, clock_tick: u64) {
+        match self
+            .clocks
+            .iter_mut()
+            .find(|clock| clock.peer_id == peer_id && clock.clock_id == clock_id)
+        {
+            Some(clock) => clock.clock_tick = clock.clock_tick.max(clock_tick),
+            None => {
+                self.clocks.push(ChangesClock {
+                    peer_id,
+                    clock_id,
+                    clock_tick,
+                });
+                self.clocks
+                    .sort_unstable_by_key(|clock| (clock.peer_id, clock.clock_id));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeType {
+    /// Point was inserted or updated, `id` and the current state of the point are set
+    Upsert,
+    /// Point was deleted, `id` is set
+    Delete,
+    /// Points matching `filter` at the moment of the operation were updated
+    UpdateByFilter,
+    /// Points matching `filter` at the moment of the operation were deleted
+    DeleteByFilter,
+}
+
+/// Change of the shard, made by an update operation
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PointChange {
+    /// Position of the last operation, which made the change, in the log of the replica which
+    /// served the batch. Only comparable within the batch.
+    pub position: u64,
+    #[serde(rename = "type")]
+    pub change_type: ChangeType,
+    /// Id of the changed point
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<PointIdType>,
+    /// Current payload of the upserted point
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Payload>,
+    /// Current vectors of the upserted point
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector: Option<VectorStruct>,
+    /// Filter of the operation, which changed the points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+}
+
+impl PointChange {
+    pub fn delete(position: u64, id: PointIdType) -> Self {
+        Self {
+            position,
+            change_type: ChangeType::Delete,
+            id: Some(id),
+            payload: None,
+            vector: None,
+            filter: None,
+        }
+    }
+}
+
+/// Final effect of the operations on a single point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PointEffect {
+    Upsert,
+    Delete,
+}
+
+/// Range of a sync operation, points of the range missing from the synced ones are deleted
+#[derive(Debug)]
+pub(crate) struct SyncedRange {
+    position: SeqNumberType,
+    from_id: Option<PointIdType>,
+    to_id: Option<PointIdType>,
+    synced: HashSet<PointIdType>,
+}
+
+impl SyncedRange {
+    /// Check if the sync operation deleted the point, if it existed
+    fn deletes(&self, id: &PointIdType) -> bool {
+        self.from_id.map_or(true, |from_id| *id >= from_id)
+            && self.to_id.map_or(true, |to_id| *id < to_id)
+            && !self.synced.contains(id)
+    }
+}
+
+/// Operations of a shard, reduced to the final effect on each of the changed points
+///
+/// Operations, which select points by filter, can't be reduced, as the selected points are not
+/// known anymore. They are kept as is instead.
+#[derive(Debug, Default)]
+pub(crate) struct ReducedChanges {
+    /// Last effect on each of the points, with position of the operation
+    pub points: HashMap<PointIdType, (SeqNumberType, PointEffect)>,
+    /// Changes of the points, selected by filter
+    pub filtered: Vec<PointChange>,
+    /// Ranges of the sync operations, in order of their position
+    pub synced_ranges: Vec<SyncedRange>,
+}
+
+impl ReducedChanges {
+    /// Add the operation at the given position, operations must be added in order
+    pub fn add(&mut self, position: SeqNumberType, operation: &CollectionUpdateOperations) {
+        let is_delete = matches!(
+            operation,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::DeletePoints { .. } | PointOperations::DeletePointsByFilter(_)
+            ),
+        );
+
+        let effect_area = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(sync)) => {
+                let synced: HashSet<_> = sync.points.iter().map(|point| point.id).collect();
+                let range = SyncedRange {
+                    position,
+                    from_id: sync.from_id,
+                    to_id: sync.to_id,
+                    synced,
+                };
+                // Points of the range, upserted before and missing from the synced ones, are
+                // deleted. Points upserted before the reduced operations are resolved by
+                // `add_synced_deletes`.
+                for (id, change) in &mut self.points {
+                    if change.1 == PointEffect::Upsert && range.deletes(id) {
+                        *change = (position, PointEffect::Delete);
+                    }
+                }
+                let effect_area =
+                    OperationEffectArea::Points(range.synced.iter().copied().collect());
+                self.synced_ranges.push(range);
+                effect_area
+            }
+            operation => operation.estimate_effect_area(),
+        };
+
+        match effect_area {
+            OperationEffectArea::Empty => {}
+            OperationEffectArea::Points(ids) => {
+                let effect = if is_delete {
+                    PointEffect::Delete
+                } else {
+                    PointEffect::Upsert
+                };
+                for id in ids {
+                    self.points.insert(id, (position, effect));
+                }
+            }
+            OperationEffectArea::Filter(filter) => {
+                let change_type = if is_delete {
+                    ChangeType::DeleteByFilter
+                } else {
+                    ChangeType::UpdateByFilter
+                };
+                self.filtered.push(PointChange {
+                    position,
+                    change_type,
+                    id: None,
+                    payload: None,
+                    vector: None,
+                    filter: Some(filter),
+                });
+            }
+        }
+    }
+
+    /// Delete points, which were upserted before the reduced operations and deleted by a sync
+    /// operation among them
+    ///
+    /// Points, changed by the reduced operations after the sync, keep their last effect.
+    pub fn add_synced_deletes(&mut self, upserted_before: impl IntoIterator<Item = PointIdType>) {
+        for id in upserted_before {
+            if self.points.contains_key(&id) {
+                continue;
+            }
+            if let Some(range) = self
+                .synced_ranges
+                .iter()
+                .rev()
+                .find(|range| range.deletes(&id))
+            {
+                self.points
+                    .insert(id, (range.position, PointEffect::Delete));
+            }
+        }
+    }
+
+    /// Ids of the points, which are upserted, in order of their last change
+    pub fn upserted_points(&self) -> Vec<PointIdType> {
+        self.points_with_effect(PointEffect::Upsert)
+    }
+
+    /// Ids of the points, which are deleted, in order of their last change
+    pub fn deleted_points(&self) -> Vec<PointIdType> {
+        self.points_with_effect(PointEffect::Delete)
+    }
+
+    fn points_with_effect(&self, effect: PointEffect) -> Vec<PointIdType> {
+        let mut points: Vec<_> = self
+            .points
+            .iter()
+            .filter(|(_, (_, point_effect))| *point_effect == effect)
+            .map(|(id, (position, _))| (*position, *id))
+            .collect();
+        points.sort_unstable();
+        points.into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+/// Effects of the operations in the WAL on each of the points, extended as the WAL grows
+///
+/// Resolves the points, deleted by a sync operation, without reading the operations before the
+/// sync again. Also maps clock tags of the operations to their positions, to find the operations
+/// which are not read yet.
+#[derive(Debug, Default)]
+pub(crate) struct ChangesIndex {
+    /// Position of the oldest operation, which is still indexed
+    first_position: SeqNumberType,
+    /// Position of the next operation to index
+    next_position: SeqNumberType,
+    /// Effects on each of the points, in order of their position
+    points: HashMap<PointIdType, Vec<(SeqNumberType, PointEffect)>>,
+    /// Clock tags of the operations, in order of their position
+    clock_tags: VecDeque<(SeqNumberType, ClockTag)>,
+}
+
+impl ChangesIndex {
+    pub fn next_position(&self) -> SeqNumberType {
+        self.next_position
+    }
+
+    /// Forget the operations, which are not in the WAL range `first_position..end_position`
+    ///
+    /// The index starts over, if the WAL doesn't continue the indexed operations.
+    pub fn truncate(&mut self, first_position: SeqNumberType, end_position: SeqNumberType) {
+        if self.next_position < first_position || self.next_position > end_position {
+            *self = Self {
+                first_position,
+                next_position: first_position,
+                points: HashMap::new(),
+                clock_tags: VecDeque::new(),
+            };
+            return;
+        }
+        if self.first_position >= first_position {
+            return;
+        }
+
+        self.first_position = first_position;
+        self.points.retain(|_, effects| {
+            effects.retain(|(position, _)| *position >= first_position);
+            !effects.is_empty()
+        });
+        while let Some((position, _)) = self.clock_tags.front() {
+            if *position >= first_position {
+                break;
+            }
+            self.clock_tags.pop_front();
+        }
+    }
+
+    /// Add the operation at the given position, operations must be added in order
+    pub fn add(
+        &mut self,
+        position: SeqNumberType,
+        operation: &CollectionUpdateOperations,
+        clock_tag: Option<ClockTag>,
+    ) {
+        let mut reduced = ReducedChanges::default();
+        reduced.add(position, operation);
+
+        for range in &reduced.synced_ranges {
+            for (id, effects) in &mut self.points {
+                let is_upserted =
+                    effects.last().map(|(_, effect)| *effect) == Some(PointEffect::Upsert);
+                if is_upserted && range.deletes(id) {
+                    effects.push((position, PointEffect::Delete));
+                }
+            }
+        }
+        for (id, change) in reduced.points {
+            self.points.entry(id).or_default().push(change);
+        }
+        if let Some(clock_tag) = clock_tag {
+            self.clock_tags.push_back((position, clock_tag));
+        }
+
+        self.next_position = position + 1;
+    }
+
+    /// Position to read all the indexed operations from
+    ///
+    /// Clocks without indexed operations are read up to their newest tick, as their operations
+    /// are not available anymore.
+    pub fn first_offset(&self, newest_clocks: &RecoveryPoint) -> ChangesOffset {
+        let first_ticks = self.first_ticks();
+        let mut offset = ChangesOffset::default();
+        for clock_tag in newest_clocks.iter_as_clock_tags() {
+            let key = (clock_tag.peer_id, clock_tag.clock_id);
+            let clock_tick = first_ticks
+                .get(&key)
+                .map_or(clock_tag.clock_tick, |first_tick| {
+                    first_tick.saturating_sub(1)
+                });
+            offset.advance(key.0, key.1, clock_tick);
+        }
+        offset
+    }
+
+    /// Check that the operations after the `offset` are all indexed
+    ///
+    /// Ticks of a clock are expected to follow each other, the oldest indexed tick of a clock
+    /// has to directly follow the read one.
+    pub fn check_offset(
+        &self,
+        offset: &ChangesOffset,
+        newest_clocks: &RecoveryPoint,
+    ) -> CollectionResult<()> {
+        let newest_ticks: HashMap<_, _> = newest_clocks
+            .iter_as_clock_tags()
+            .map(|clock_tag| {
+                (
+                    (clock_tag.peer_id, clock_tag.clock_id),
+                    clock_tag.clock_tick,
+                )
+            })
+            .collect();
+
+        for clock in &offset.clocks {
+            let newest_tick = newest_ticks.get(&(clock.peer_id, clock.clock_id));
+            if newest_tick.map_or(true, |newest_tick| *newest_tick < clock.clock_tick) {
+                return Err(CollectionError::service_error(format!(
+                    "Replica has not received the operations up to tick {} of clock {}:{} yet",
+                    clock.clock_tick, clock.peer_id, clock.clock_id,
+                )));
+            }
+        }
+
+        let first_ticks = self.first_ticks();
+        for (&(peer_id, clock_id), &newest_tick) in &newest_ticks {
+            let read_tick = offset.clock_tick(peer_id, clock_id);
+            if newest_tick <= read_tick {
+                continue;
+            }
+            let first_tick = first_ticks.get(&(peer_id, clock_id)).copied();
+            if first_tick.map_or(true, |first_tick| first_tick > read_tick + 1) {
+                return Err(CollectionError::position_truncated(
+                    peer_id,
+                    clock_id,
+                    read_tick,
+                    first_tick.unwrap_or(newest_tick + 1),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Positions and clock tags of the indexed operations, which are not read at the `offset`
+    ///
+    /// Operations without a clock tag are not identified on other replicas, they are skipped.
+    pub fn unread<'a>(
+        &'a self,
+        offset: &'a ChangesOffset,
+    ) -> impl Iterator<Item = (SeqNumberType, ClockTag)> + 'a {
+        self.clock_tags
+            .iter()
+            .filter(|(_, clock_tag)| !offset.is_read(clock_tag))
+            .copied()
+    }
+
+    /// Oldest indexed tick of each of the clocks
+    fn first_ticks(&self) -> HashMap<(PeerId, u32), u64> {
+        let mut first_ticks = HashMap::new();
+        for (_, clock_tag) in &self.clock_tags {
+            let first_tick = first_ticks
+                .entry((clock_tag.peer_id, clock_tag.clock_id))
+                .or_insert(clock_tag.clock_tick);
+            *first_tick = (*first_tick).min(clock_tag.clock_tick);
+        }
+        first_ticks
+    }
+
+    /// Ids of the points, which exist after the indexed operations before `position`
+    pub fn upserted_before(
+        &self,
+        position: SeqNumberType,
+    ) -> impl Iterator<Item = PointIdType> + '_ {
+        self.points.iter().filter_map(move |(id, effects)| {
+            let count = effects.partition_point(|(effect_position, _)| *effect_position < position);
+            let (_, effect) = effects[..count].last()?;
+            (*effect == PointEffect::Upsert).then_some(*id)
+        })
+    }
+}
+
+/// Order changes by position, changes of the same operation are ordered by point id
+pub(crate) fn sort_changes(changes: &mut [PointChange]) {
+    changes.sort_by_key(|change| (change.position, change.id));
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{Condition, FieldCondition};
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::payload_ops::{PayloadOps, SetPayloadOp};
+    use crate::operations::point_ops::{
+        PointInsertOperationsInternal, PointStruct, PointSyncOperation,
+    };
+    use crate::operations::{CreateIndex, FieldIndexOperations};
+    use crate::shards::local_shard::clock_map::ClockMap;
+
+    fn upsert(ids: &[u64]) -> CollectionUpdateOperations {
+        let points: Vec<_> = ids
+            .iter()
+            .map(|id| PointStruct {
+                id: (*id).into(),
+                vector: vec![1.0, 2.0].into(),
+                payload: None,
+            })
+            .collect();
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::from(points),
+        ))
+    }
+
+    fn delete(ids: &[u64]) -> CollectionUpdateOperations {
+        CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: ids.iter().map(|id| (*id).into()).collect(),
+            preconditions: vec![],
+        })
+    }
+
+    #[test]
+    fn test_reduce_changes() {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color".parse().unwrap(),
+            "red".to_string().into(),
+        )));
+
+        let set_payload = |points: Option<Vec<PointIdType>>, filter: Option<Filter>| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: json!({ "a": 1 }).into(),
+                points,
+                filter,
+                key: None,
+                preconditions: vec![],
+            }))
+        };
+
+        let mut reduced = ReducedChanges::default();
+        reduced.add(10, &upsert(&[1, 2, 3]));
+        reduced.add(11, &set_payload(Some(vec![1.into()]), None));
+        reduced.add(12, &delete(&[2, 4]));
+        reduced.add(13, &upsert(&[4]));
+        reduced.add(14, &set_payload(None, Some(filter.clone())));
+        reduced.add(
+            15,
+            &CollectionUpdateOperations::FieldIndexOperation(FieldIndexOperations::CreateIndex(
+                CreateIndex {
+                    field_name: "color".parse().unwrap(),
+                    field_schema: None,
+                },
+            )),
+        );
+        reduced.add(
+            16,
+            &CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+                filter.clone(),
+            )),
+        );
+
+        // Point 3 is only upserted, point 1 is upserted and then updated, point 4 is re-inserted
+        assert_eq!(
+            reduced.upserted_points(),
+            vec![3.into(), 1.into(), 4.into()],
+        );
+        assert_eq!(reduced.deleted_points(), vec![2.into()]);
+        assert_eq!(reduced.points[&1.into()], (11, PointEffect::Upsert));
+
+        let filtered: Vec<_> = reduced
+            .filtered
+            .iter()
+            .map(|change| (change.position, change.change_type, change.filter.clone()))
+            .collect();
+        assert_eq!(
+            filtered,
+            vec![
+                (14, ChangeType::UpdateByFilter, Some(filter.clone())),
+                (16, ChangeType::DeleteByFilter, Some(filter)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_reduce_sync_changes() {
+        let sync = |from_id: u64, to_id: u64, ids: &[u64]| {
+            let points = match upsert(ids) {
+                CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                    points,
+                )) => points.into_point_vec(),
+                _ => unreachable!(),
+            };
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(
+                PointSyncOperation {
+                    from_id: Some(from_id.into()),
+                    to_id: Some(to_id.into()),
+                    points,
+                },
+            ))
+        };
+
+        let mut reduced = ReducedChanges::default();
+        reduced.add(10, &upsert(&[1, 2, 3, 12]));
+        // Sync of range [1, 10) keeps points 1 and 4, deletes the other points of the range
+        reduced.add(11, &sync(1, 10, &[1, 4]));
+        reduced.add(12, &upsert(&[3]));
+
+        // Points upserted before the reduced operations, point 20 is out of the range
+        reduced.add_synced_deletes([5.into(), 6.into(), 20.into(), 1.into()]);
+
+        assert_eq!(
+            reduced.upserted_points(),
+            vec![12.into(), 1.into(), 4.into(), 3.into()],
+        );
+        assert_eq!(reduced.deleted_points(), vec![2.into(), 5.into(), 6.into()],);
+
+        let mut reduced = ReducedChanges::default();
+        reduced.add(20, &sync(0, 10, &[1]));
+        reduced.add_synced_deletes([5.into(), 10.into()]);
+        assert_eq!(reduced.upserted_points(), vec![1.into()]);
+        assert_eq!(reduced.deleted_points(), vec![5.into()]);
+
+        let upserted_before = |index: &ChangesIndex, position| {
+            let mut ids: Vec<_> = index.upserted_before(position).collect();
+            ids.sort_unstable();
+            ids
+        };
+
+        let mut index = ChangesIndex::default();
+        index.truncate(10, 10);
+        index.add(10, &upsert(&[1, 2, 3, 12]), None);
+        index.add(11, &sync(1, 10, &[1, 4]), None);
+        index.add(12, &delete(&[12]), None);
+        index.add(13, &upsert(&[3]), None);
+        assert_eq!(index.next_position(), 14);
+
+        assert_eq!(
+            upserted_before(&index, 11),
+            vec![1.into(), 2.into(), 3.into(), 12.into()],
+        );
+        assert_eq!(
+            upserted_before(&index, 12),
+            vec![1.into(), 4.into(), 12.into()],
+        );
+        assert_eq!(
+            upserted_before(&index, 14),
+            vec![1.into(), 3.into(), 4.into()],
+        );
+
+        // Effects of the truncated operations are forgotten
+        index.truncate(12, 14);
+        assert_eq!(upserted_before(&index, 14), vec![3.into()]);
+
+        // Index starts over, if the WAL doesn't continue it
+        index.truncate(20, 30);
+        assert_eq!(index.next_position(), 20);
+        assert_eq!(upserted_before(&index, 30), vec![]);
+    }
+
+    #[test]
+    fn test_changes_index_offsets() {
+        let tag = |peer_id, clock_tick| ClockTag::new_with_token(peer_id, 0, clock_tick, 0);
+        let recovery_point = |tags: &[ClockTag]| {
+            let mut clock_map = ClockMap::default();
+            for tag in tags {
+                clock_map.advance_clock(*tag);
+            }
+            clock_map.to_recovery_point()
+        };
+        let unread = |index: &ChangesIndex, offset: &ChangesOffset| {
+            index
+                .unread(offset)
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>()
+        };
+
+        // Clock of peer 1 is at tick 5, ticks 1 and 2 are not in the WAL anymore
+        let mut index = ChangesIndex::default();
+        index.truncate(10, 10);
+        index.add(10, &upsert(&[1]), Some(tag(1, 3)));
+        index.add(11, &upsert(&[2]), Some(tag(2, 1)));
+        index.add(12, &upsert(&[3]), None);
+        index.add(13, &upsert(&[4]), Some(tag(1, 4)));
+        index.add(14, &upsert(&[5]), Some(tag(2, 2)));
+        index.add(15, &upsert(&[6]), Some(tag(1, 5)));
+
+        // Clock of peer 3 has no operations in the WAL
+        let newest_clocks = recovery_point(&[tag(1, 5), tag(2, 2), tag(3, 7)]);
+
+        let offset = index.first_offset(&newest_clocks);
+        assert_eq!(offset.clock_tick(1, 0), 2);
+        assert_eq!(offset.clock_tick(2, 0), 0);
+        assert_eq!(offset.clock_tick(3, 0), 7);
+        index.check_offset(&offset, &newest_clocks).unwrap();
+
+        // Operations without clock tag are skipped
+        assert_eq!(unread(&index, &offset), vec![10, 11, 13, 14, 15]);
+
+        // Position is advanced by clock, as operations are read on another replica
+        let mut offset = offset;
+        offset.advance(2, 0, 1);
+        offset.advance(1, 0, 4);
+        assert_eq!(unread(&index, &offset), vec![14, 15]);
+        index.check_offset(&offset, &newest_clocks).unwrap();
+
+        // Replica hasn't received the operations yet
+        let mut ahead = offset.clone();
+        ahead.advance(1, 0, 6);
+        assert!(index.check_offset(&ahead, &newest_clocks).is_err());
+        let mut unknown = offset.clone();
+        unknown.advance(4, 0, 1);
+        assert!(index.check_offset(&unknown, &newest_clocks).is_err());
+
+        // Operations after the position are not in the WAL anymore
+        let mut truncated = offset.clone();
+        truncated.clocks.retain(|clock| clock.peer_id != 1);
+        assert!(matches!(
+            index.check_offset(&truncated, &newest_clocks),
+            Err(CollectionError::PositionTruncated { .. }),
+        ));
+
+        index.truncate(14, 16);
+        index.check_offset(&offset, &newest_clocks).unwrap();
+        let mut old_offset = offset.clone();
+        old_offset.clocks.clear();
+        old_offset.advance(1, 0, 2);
+        old_offset.advance(3, 0, 7);
+        assert!(matches!(
+            index.check_offset(&old_offset, &newest_clocks),
+            Err(CollectionError::PositionTruncated { .. }),
+        ));
+    }
+}
@@ -6,6 +6,7 @@ use segment::types::ShardKey;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationErrors};
 
+use crate::shards::resharding::ReshardingDirection;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::ShardTransferMethod;
 
@@ -26,6 +27,10 @@ pub enum ClusterOperations {
     DropShardingKey(DropShardingKeyOperation),
     /// Restart transfer
     RestartTransfer(RestartTransferOperation),
+    /// Start resharding
+    StartResharding(StartReshardingOperation),
+    /// Abort currently running resharding operation
+    AbortResharding(AbortReshardingOperation),
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -46,6 +51,18 @@ pub struct RestartTransferOperation {
     pub restart_transfer: RestartTransfer,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct StartReshardingOperation {
+    pub start_resharding: StartResharding,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct AbortReshardingOperation {
+    pub abort_resharding: AbortResharding,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateShardingKey {
@@ -77,6 +94,23 @@ pub struct RestartTransfer {
     pub method: ShardTransferMethod,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct StartResharding {
+    /// Add a new shard (`up`) or remove the last shard (`down`)
+    pub direction: ReshardingDirection,
+    /// Peer to drive the resharding operation and to place the new shard on
+    /// If not specified, will be selected automatically
+    pub peer_id: Option<PeerId>,
+    /// Shard key to reshard the shards of
+    /// Required for collections with custom sharding
+    pub shard_key: Option<ShardKey>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct AbortResharding {}
+
 impl Validate for ClusterOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -87,6 +121,8 @@ impl Validate for ClusterOperations {
             ClusterOperations::CreateShardingKey(op) => op.validate(),
             ClusterOperations::DropShardingKey(op) => op.validate(),
             ClusterOperations::RestartTransfer(op) => op.validate(),
+            ClusterOperations::StartResharding(op) => op.validate(),
+            ClusterOperations::AbortResharding(op) => op.validate(),
         }
     }
 }
@@ -9,6 +9,7 @@ use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
 use crate::config::{CollectionParams, WalConfig};
+use crate::operations::ttl::TtlConfig;
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -83,6 +84,11 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Update HNSW index in place on inserts and deletes, instead of re-indexing new points in the background.
+    /// Indexed segments stay appendable, if their vectors are stored in RAM and not quantized, and the index is not on disk.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable: Option<bool>,
 }
 
 #[derive(
@@ -96,7 +102,9 @@ pub struct WalConfigDiff {
     pub wal_segments_ahead: Option<usize>,
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge, PartialEq, Eq, Hash,
+)]
 pub struct CollectionParamsDiff {
     /// Number of replicas for each shard
     pub replication_factor: Option<NonZeroU32>,
@@ -110,6 +118,9 @@ pub struct CollectionParamsDiff {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// Expiration of points. Use `Disabled` to turn expiration off.
+    #[validate]
+    pub ttl_config: Option<TtlConfigDiff>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -201,7 +212,19 @@ impl DiffConfig<OptimizersConfig> for OptimizersConfigDiff {}
 
 impl DiffConfig<WalConfig> for WalConfigDiff {}
 
-impl DiffConfig<CollectionParams> for CollectionParamsDiff {}
+impl DiffConfig<CollectionParams> for CollectionParamsDiff {
+    /// Expiration of points is replaced as a whole, or removed if it is disabled
+    fn update(mut self, config: &CollectionParams) -> CollectionResult<CollectionParams> {
+        let ttl_config_diff = self.ttl_config.take();
+        let mut params = update_config(config, self)?;
+        match ttl_config_diff {
+            Some(TtlConfigDiff::Enabled(ttl_config)) => params.ttl_config = Some(ttl_config),
+            Some(TtlConfigDiff::Disabled(_)) => params.ttl_config = None,
+            None => {}
+        }
+        Ok(params)
+    }
+}
 
 impl From<HnswConfig> for HnswConfigDiff {
     fn from(config: HnswConfig) -> Self {
@@ -326,6 +349,28 @@ impl Validate for QuantizationConfigDiff {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum TtlConfigDiff {
+    Enabled(TtlConfig),
+    Disabled(Disabled),
+}
+
+impl TtlConfigDiff {
+    pub fn new_disabled() -> Self {
+        TtlConfigDiff::Disabled(Disabled::Disabled)
+    }
+}
+
+impl Validate for TtlConfigDiff {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            TtlConfigDiff::Enabled(ttl_config) => ttl_config.validate(),
+            TtlConfigDiff::Disabled(_) => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU64;
@@ -345,6 +390,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                multivector_config: None,
+                datatype: None,
             }
             .into(),
             ..CollectionParams::empty()
@@ -355,6 +402,7 @@ mod tests {
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
             on_disk_payload: None,
+            ttl_config: None,
         };
 
         let new_params = diff.update(&params).unwrap();
@@ -364,6 +412,30 @@ mod tests {
         assert!(!new_params.on_disk_payload);
     }
 
+    #[test]
+    fn test_update_ttl_config() {
+        let params = CollectionParams::empty();
+
+        let diff: CollectionParamsDiff =
+            serde_json::from_str(r#"{ "ttl_config": { "default_ttl_sec": 60 } }"#).unwrap();
+        let new_params = diff.update(&params).unwrap();
+        let ttl_config = new_params.ttl_config.clone().unwrap();
+        assert_eq!(ttl_config.default_ttl_sec, Some(60));
+        assert_eq!(ttl_config.expire_at_field.to_string(), "expire_at");
+
+        // Expiration is kept, if it is not in the diff
+        let diff: CollectionParamsDiff =
+            serde_json::from_str(r#"{ "read_fan_out_factor": 1 }"#).unwrap();
+        let new_params = diff.update(&new_params).unwrap();
+        assert_eq!(new_params.ttl_config, Some(ttl_config));
+
+        let diff: CollectionParamsDiff =
+            serde_json::from_str(r#"{ "ttl_config": "Disabled" }"#).unwrap();
+        assert_eq!(diff.ttl_config, Some(TtlConfigDiff::new_disabled()));
+        let new_params = diff.update(&new_params).unwrap();
+        assert_eq!(new_params.ttl_config, None);
+    }
+
     #[test]
     fn test_hnsw_update() {
         let base_config = HnswConfig::default();
@@ -5,18 +5,20 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
-    try_date_time_from_proto,
+    from_grpc_datatype, from_grpc_dist, json_path_from_proto, json_to_proto, payload_to_proto,
+    proto_to_json, proto_to_payloads, try_date_time_from_proto,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
+use api::grpc::qdrant::ttl_config_diff::Ttl;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
     Operation as ClusterOperationsPb, Operation,
 };
 use api::grpc::qdrant::{CreateShardKey, SearchPoints};
 use common::types::ScoreType;
 use itertools::Itertools;
-use segment::data_types::order_by::{OrderBy, StartFrom};
+use segment::data_types::order_by::{OrderBy, OrderingValue, StartFrom};
 use segment::data_types::vectors::{Named, NamedQuery, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{DateTimeWrapper, Distance, QuantizationConfig};
 use segment::vector_storage::query::context_query::{ContextPair, ContextQuery};
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
@@ -24,12 +26,15 @@ use segment::vector_storage::query::reco_query::RecoQuery;
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
 use tonic::Status;
 
+use super::changes::{
+    ChangeType, ChangesBatch, ChangesClock, ChangesOffset, ChangesRequest, PointChange,
+};
 use super::consistency_params::ReadConsistency;
 use super::types::{
     BaseGroupRequest, ContextExamplePair, CoreSearchRequest, DiscoverRequestInternal, GroupsResult,
-    OrderByInterface, PointGroup, QueryEnum, RecommendExample, RecommendGroupsRequestInternal,
-    RecommendStrategy, SearchGroupsRequestInternal, SparseIndexParams, SparseVectorParams,
-    VectorParamsDiff, VectorsConfigDiff,
+    Modifier, OrderByInterface, PointGroup, QueryEnum, RecommendExample,
+    RecommendGroupsRequestInternal, RecommendStrategy, SearchGroupsRequestInternal,
+    SparseIndexParams, SparseVectorParams, VectorParamsDiff, VectorsConfigDiff,
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
@@ -38,29 +43,50 @@ use crate::config::{
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
 use crate::operations::cluster_ops::{
-    AbortTransferOperation, ClusterOperations, CreateShardingKey, CreateShardingKeyOperation,
-    DropReplicaOperation, DropShardingKey, DropShardingKeyOperation, MoveShard, MoveShardOperation,
-    Replica, ReplicateShardOperation, RestartTransfer, RestartTransferOperation,
+    AbortResharding, AbortReshardingOperation, AbortTransferOperation, ClusterOperations,
+    CreateShardingKey, CreateShardingKeyOperation, DropReplicaOperation, DropShardingKey,
+    DropShardingKeyOperation, MoveShard, MoveShardOperation, Replica, ReplicateShardOperation,
+    RestartTransfer, RestartTransferOperation, StartResharding, StartReshardingOperation,
 };
 use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
-    WalConfigDiff,
+    TtlConfigDiff, WalConfigDiff,
 };
+use crate::operations::payload_ops::{PayloadUpdate, PayloadUpdateOperator};
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, FilterSelector, PointIdsList, PointPrecondition, PointStruct, PointsSelector,
+    WriteOrdering,
 };
 use crate::operations::shard_key_selector::ShardKeySelector;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::strict_mode::StrictModeConfig;
+use crate::operations::ttl::{TtlConfig, DEFAULT_EXPIRE_AT_FIELD};
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequestInternal, Record,
-    RemoteShardInfo, SearchRequestInternal, ShardTransferInfo, UpdateResult, UpdateStatus,
-    VectorParams, VectorsConfig,
+    FacetRangeHit, FacetRequestInternal, FacetResponse, FailedPrecondition, LocalShardInfo,
+    LookupLocation, Mmr, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
+    ReshardingInfo, SearchMatrixOffsetsResponse, SearchMatrixPair, SearchMatrixPairsResponse,
+    SearchMatrixRequestInternal, SearchRequestInternal, ShardTransferInfo, UpdateResult,
+    UpdateStatus, VectorParams, VectorsConfig,
+};
+use crate::operations::universal_query::collection_query::{
+    Prefetch, Query as UniversalQuery, QueryInterface, QueryRequestInternal, SampleInterface,
+    SampleParams, DEFAULT_QUERY_LIMIT,
+};
+use crate::operations::universal_query::formula::{
+    AbsExpression, DatetimeExpression, DatetimeKeyExpression, DecayParamsExpression, DivExpression,
+    DivParams, ExpDecayExpression, ExpExpression, Expression, FormulaQuery, GaussDecayExpression,
+    GeoDistanceExpression, GeoDistanceParams, LinDecayExpression, LnExpression, Log10Expression,
+    MultExpression, NegExpression, PowExpression, PowParams, SqrtExpression, SumExpression,
+};
+use crate::operations::universal_query::shard_query::{
+    Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest, ShardSample,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::ReshardingDirection;
 use crate::shards::transfer::ShardTransferMethod;
 
 pub fn sharding_method_to_proto(sharding_method: ShardingMethod) -> i32 {
@@ -137,11 +163,20 @@ pub fn try_record_from_grpc(
         .map(|vectors| vectors.try_into())
         .transpose()?;
 
+    let order_values = (!point.order_values.is_empty()).then(|| {
+        point
+            .order_values
+            .into_iter()
+            .map(Option::<OrderingValue>::from)
+            .collect()
+    });
+
     Ok(Record {
         id,
         payload,
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
+        order_values,
     })
 }
 
@@ -234,6 +269,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            appendable: value.appendable,
         }
     }
 }
@@ -247,6 +283,7 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            appendable: value.appendable,
         }
     }
 }
@@ -260,6 +297,83 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::TtlConfig> for TtlConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::TtlConfig) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::TtlConfig {
+            default_ttl_sec,
+            expire_at_field,
+        } = value;
+        Ok(Self {
+            default_ttl_sec,
+            expire_at_field: match expire_at_field {
+                Some(field) => json_path_from_proto(&field)?,
+                None => DEFAULT_EXPIRE_AT_FIELD.parse().unwrap(),
+            },
+        })
+    }
+}
+
+impl From<TtlConfig> for api::grpc::qdrant::TtlConfig {
+    fn from(value: TtlConfig) -> Self {
+        Self {
+            default_ttl_sec: value.default_ttl_sec,
+            expire_at_field: Some(value.expire_at_field.to_string()),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::TtlConfigDiff> for TtlConfigDiff {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::TtlConfigDiff) -> Result<Self, Self::Error> {
+        match value.ttl {
+            None => Err(Status::invalid_argument("TTL config is not specified")),
+            Some(Ttl::Enabled(ttl_config)) => Ok(Self::Enabled(ttl_config.try_into()?)),
+            Some(Ttl::Disabled(_)) => Ok(Self::new_disabled()),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
+    fn from(value: api::grpc::qdrant::StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|x| x as usize),
+            max_query_offset: value.max_query_offset.map(|x| x as usize),
+            max_timeout: value.max_timeout.map(|x| x as usize),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|x| x as usize),
+            search_allow_exact: value.search_allow_exact,
+            search_max_batch_size: value.search_max_batch_size.map(|x| x as usize),
+            upsert_max_batch_size: value.upsert_max_batch_size.map(|x| x as usize),
+            upsert_max_payload_bytes: value.upsert_max_payload_bytes.map(|x| x as usize),
+            max_filter_conditions: value.max_filter_conditions.map(|x| x as usize),
+        }
+    }
+}
+
+impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
+    fn from(value: StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|x| x as u32),
+            max_query_offset: value.max_query_offset.map(|x| x as u32),
+            max_timeout: value.max_timeout.map(|x| x as u32),
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|x| x as u32),
+            search_allow_exact: value.search_allow_exact,
+            search_max_batch_size: value.search_max_batch_size.map(|x| x as u32),
+            upsert_max_batch_size: value.upsert_max_batch_size.map(|x| x as u32),
+            upsert_max_payload_bytes: value.upsert_max_payload_bytes.map(|x| x as u64),
+            max_filter_conditions: value.max_filter_conditions.map(|x| x as u32),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
     type Error = Status;
 
@@ -282,6 +396,7 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
             on_disk_payload: value.on_disk_payload,
+            ttl_config: value.ttl_config.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -382,6 +497,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
+                    ttl_config: config.params.ttl_config.map(Into::into),
                     sparse_vectors_config: config.params.sparse_vectors.map(|sparse_vectors| {
                         api::grpc::qdrant::SparseVectorConfig {
                             map: sparse_vectors
@@ -400,6 +516,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    appendable: config.hnsw_config.appendable,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -426,6 +543,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                strict_mode_config: config.strict_mode_config.map(Into::into),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -444,6 +562,12 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             payload: record.payload.map(payload_to_proto).unwrap_or_default(),
             vectors,
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
+            order_values: record
+                .order_values
+                .unwrap_or_default()
+                .into_iter()
+                .map(api::grpc::qdrant::OrderValue::from)
+                .collect(),
         }
     }
 }
@@ -546,6 +670,15 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .map(grpc_to_segment_quantization_config)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            multivector_config: vector_params
+                .multivector_config
+                .map(TryInto::try_into)
+                .transpose()?,
+            datatype: vector_params
+                .datatype
+                .map(from_grpc_datatype)
+                .transpose()?
+                .flatten(),
         })
     }
 }
@@ -565,16 +698,41 @@ impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
     }
 }
 
-impl From<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
-    fn from(sparse_vector_params: api::grpc::qdrant::SparseVectorParams) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
+    type Error = Status;
+
+    fn try_from(
+        sparse_vector_params: api::grpc::qdrant::SparseVectorParams,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             index: sparse_vector_params
                 .index
-                .map(|index_config| SparseIndexParams {
-                    full_scan_threshold: index_config.full_scan_threshold.map(|v| v as usize),
-                    on_disk: index_config.on_disk,
-                }),
-        }
+                .map(|index_config| -> Result<_, Status> {
+                    Ok(SparseIndexParams {
+                        full_scan_threshold: index_config.full_scan_threshold.map(|v| v as usize),
+                        on_disk: index_config.on_disk,
+                        datatype: index_config
+                            .datatype
+                            .map(from_grpc_datatype)
+                            .transpose()?
+                            .flatten(),
+                        full_precision_on_disk: index_config.full_precision_on_disk,
+                    })
+                })
+                .transpose()?,
+            modifier: sparse_vector_params
+                .modifier
+                .map(
+                    |modifier| match api::grpc::qdrant::Modifier::from_i32(modifier) {
+                        Some(api::grpc::qdrant::Modifier::None) => Ok(Modifier::None),
+                        Some(api::grpc::qdrant::Modifier::Idf) => Ok(Modifier::Idf),
+                        None => Err(Status::invalid_argument(format!(
+                            "Malformed modifier parameter, unexpected value: {modifier}"
+                        ))),
+                    },
+                )
+                .transpose()?,
+        })
     }
 }
 
@@ -585,8 +743,19 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
                 api::grpc::qdrant::SparseIndexConfig {
                     full_scan_threshold: index_config.full_scan_threshold.map(|v| v as u64),
                     on_disk: index_config.on_disk,
+                    datatype: index_config
+                        .datatype
+                        .map(|datatype| api::grpc::qdrant::Datatype::from(datatype).into()),
+                    full_precision_on_disk: index_config.full_precision_on_disk,
                 }
             }),
+            modifier: sparse_vector_params.modifier.map(|modifier| {
+                match modifier {
+                    Modifier::None => api::grpc::qdrant::Modifier::None,
+                    Modifier::Idf => api::grpc::qdrant::Modifier::Idf,
+                }
+                .into()
+            }),
         }
     }
 }
@@ -644,13 +813,18 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                             ),
                         },
                     },
-                    sparse_vectors: params.sparse_vectors_config.map(|sparse_vectors| {
-                        sparse_vectors
-                            .map
-                            .into_iter()
-                            .map(|(name, sparse_vector_params)| (name, sparse_vector_params.into()))
-                            .collect()
-                    }),
+                    sparse_vectors: params
+                        .sparse_vectors_config
+                        .map(|sparse_vectors| {
+                            sparse_vectors
+                                .map
+                                .into_iter()
+                                .map(|(name, sparse_vector_params)| {
+                                    Ok((name, sparse_vector_params.try_into()?))
+                                })
+                                .collect::<Result<_, Status>>()
+                        })
+                        .transpose()?,
                     shard_number: NonZeroU32::new(params.shard_number)
                         .ok_or_else(|| Status::invalid_argument("`shard_number` cannot be zero"))?,
                     on_disk_payload: params.on_disk_payload,
@@ -676,6 +850,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    ttl_config: params.ttl_config.map(TryInto::try_into).transpose()?,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -697,6 +872,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            strict_mode_config: config.strict_mode_config.map(Into::into),
         })
     }
 }
@@ -833,6 +1009,7 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
 pub fn try_points_selector_from_grpc(
     value: api::grpc::qdrant::PointsSelector,
     shard_key_selector: Option<api::grpc::qdrant::ShardKeySelector>,
+    preconditions: Vec<api::grpc::qdrant::PointPrecondition>,
 ) -> Result<PointsSelector, Status> {
     match value.points_selector_one_of {
         Some(api::grpc::qdrant::points_selector::PointsSelectorOneOf::Points(points)) => {
@@ -843,8 +1020,19 @@ pub fn try_points_selector_from_grpc(
                     .map(|p| p.try_into())
                     .collect::<Result<_, _>>()?,
                 shard_key: shard_key_selector.map(ShardKeySelector::from),
+                preconditions: preconditions
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
             }))
         }
+        Some(api::grpc::qdrant::points_selector::PointsSelectorOneOf::Filter(_))
+            if !preconditions.is_empty() =>
+        {
+            Err(Status::invalid_argument(
+                "Preconditions can only be used with a list of point ids",
+            ))
+        }
         Some(api::grpc::qdrant::points_selector::PointsSelectorOneOf::Filter(f)) => {
             Ok(PointsSelector::FilterSelector(FilterSelector {
                 filter: f.try_into()?,
@@ -855,12 +1043,79 @@ pub fn try_points_selector_from_grpc(
     }
 }
 
+impl TryFrom<api::grpc::qdrant::PointPrecondition> for PointPrecondition {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointPrecondition) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PointPrecondition {
+            id,
+            if_version,
+            if_not_exists,
+        } = value;
+
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
+                .try_into()?,
+            if_version,
+            if_not_exists: if_not_exists.unwrap_or_default(),
+        })
+    }
+}
+
+impl From<PointPrecondition> for api::grpc::qdrant::PointPrecondition {
+    fn from(value: PointPrecondition) -> Self {
+        let PointPrecondition {
+            id,
+            if_version,
+            if_not_exists,
+        } = value;
+
+        Self {
+            id: Some(id.into()),
+            if_version,
+            if_not_exists: if_not_exists.then_some(true),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::FailedPrecondition> for FailedPrecondition {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::FailedPrecondition) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::FailedPrecondition { id, version } = value;
+
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
+                .try_into()?,
+            version,
+        })
+    }
+}
+
+impl From<FailedPrecondition> for api::grpc::qdrant::FailedPrecondition {
+    fn from(value: FailedPrecondition) -> Self {
+        let FailedPrecondition { id, version } = value;
+
+        Self {
+            id: Some(id.into()),
+            version,
+        }
+    }
+}
+
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
     fn from(res: UpdateResult) -> Self {
         Self {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            failed_preconditions: res
+                .failed_preconditions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }
     }
 }
@@ -879,6 +1134,11 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            failed_preconditions: res
+                .failed_preconditions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
         };
 
         Ok(res)
@@ -942,168 +1202,481 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     }
 }
 
-impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
+impl TryFrom<api::grpc::qdrant::FacetCounts> for FacetRequestInternal {
     type Error = Status;
-    fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
-        let SearchPoints {
+
+    fn try_from(value: api::grpc::qdrant::FacetCounts) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::FacetCounts {
             collection_name: _,
-            vector,
+            key,
             filter,
             limit,
-            with_payload,
-            params,
-            score_threshold,
-            offset,
-            vector_name,
-            with_vectors,
-            read_consistency: _,
-            timeout: _,
+            exact,
+            ranges,
             shard_key_selector: _,
-            sparse_indices,
         } = value;
 
-        if let Some(sparse_indices) = &sparse_indices {
-            validate_sparse_vector_impl(&sparse_indices.data, &vector).map_err(|_| {
-                Status::invalid_argument("Sparse indices does not match sparse vector conditions")
-            })?;
-        }
-
-        let vector_struct =
-            api::grpc::conversions::into_named_vector_struct(vector_name, vector, sparse_indices)?;
-
         Ok(Self {
-            query: QueryEnum::Nearest(vector_struct),
+            key: json_path_from_proto(&key)?,
+            limit: limit.map(|limit| limit as usize),
             filter: filter.map(TryInto::try_into).transpose()?,
-            params: params.map(Into::into),
-            limit: limit as usize,
-            offset: offset.map(|v| v as usize).unwrap_or_default(),
-            with_payload: with_payload.map(TryInto::try_into).transpose()?,
-            with_vector: with_vectors.map(Into::into),
-            score_threshold: score_threshold.map(|s| s as ScoreType),
+            exact: exact.unwrap_or_default(),
+            ranges: (!ranges.is_empty()).then(|| ranges.into_iter().map(Into::into).collect()),
         })
     }
 }
 
-// Use wrapper type to bundle CollectionId & SearchRequest
-impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
-    fn from(value: CollectionSearchRequest<'a>) -> Self {
-        let (collection_id, request) = value.0;
-        let (vector, sparse_indices) = match request.vector.get_vector().to_owned() {
-            Vector::Dense(vector) => (vector, None),
-            Vector::Sparse(vector) => (
-                vector.values,
-                Some(api::grpc::qdrant::SparseIndices {
-                    data: vector.indices,
-                }),
-            ),
-        };
+impl From<FacetRangeHit> for api::grpc::qdrant::FacetRangeHit {
+    fn from(value: FacetRangeHit) -> Self {
         Self {
-            collection_name: collection_id,
-            vector,
-            filter: request.filter.clone().map(|f| f.into()),
-            limit: request.limit as u64,
-            with_vectors: request.with_vector.clone().map(|wv| wv.into()),
-            with_payload: request.with_payload.clone().map(|wp| wp.into()),
-            params: request.params.map(|sp| sp.into()),
-            score_threshold: request.score_threshold,
-            offset: request.offset.map(|x| x as u64),
-            vector_name: match request.vector.get_name() {
-                DEFAULT_VECTOR_NAME => None,
-                vector_name => Some(vector_name.to_string()),
-            },
-            read_consistency: None,
-            timeout: None,
-            shard_key_selector: None,
-            sparse_indices,
-        }
-    }
-}
-
-impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
-    fn from(value: QueryEnum) -> Self {
-        match value {
-            QueryEnum::Nearest(vector) => api::grpc::qdrant::QueryEnum {
-                query: Some(api::grpc::qdrant::query_enum::Query::NearestNeighbors(
-                    vector.to_vector().into(),
-                )),
-            },
-            QueryEnum::RecommendBestScore(named) => api::grpc::qdrant::QueryEnum {
-                query: Some(api::grpc::qdrant::query_enum::Query::RecommendBestScore(
-                    api::grpc::qdrant::RecoQuery {
-                        positives: named.query.positives.into_iter().map_into().collect(),
-                        negatives: named.query.negatives.into_iter().map_into().collect(),
-                    },
-                )),
-            },
-            QueryEnum::Discover(named) => api::grpc::qdrant::QueryEnum {
-                query: Some(api::grpc::qdrant::query_enum::Query::Discover(
-                    api::grpc::qdrant::DiscoveryQuery {
-                        target: Some(named.query.target.into()),
-                        context: named
-                            .query
-                            .pairs
-                            .into_iter()
-                            .map(|pair| api::grpc::qdrant::ContextPair {
-                                positive: { Some(pair.positive.into()) },
-                                negative: { Some(pair.negative.into()) },
-                            })
-                            .collect(),
-                    },
-                )),
-            },
-            QueryEnum::Context(named) => api::grpc::qdrant::QueryEnum {
-                query: Some(api::grpc::qdrant::query_enum::Query::Context(
-                    api::grpc::qdrant::ContextQuery {
-                        context: named
-                            .query
-                            .pairs
-                            .into_iter()
-                            .map(|pair| api::grpc::qdrant::ContextPair {
-                                positive: { Some(pair.positive.into()) },
-                                negative: { Some(pair.negative.into()) },
-                            })
-                            .collect(),
-                    },
-                )),
-            },
+            range: Some(value.range.into()),
+            count: value.count as u64,
         }
     }
 }
 
-impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearchPoints {
-    fn from(value: CollectionCoreSearchRequest<'a>) -> Self {
-        let (collection_id, request) = value.0;
+impl TryFrom<api::grpc::qdrant::FacetRangeHit> for FacetRangeHit {
+    type Error = Status;
 
-        Self {
-            collection_name: collection_id,
-            query: Some(request.query.clone().into()),
-            filter: request.filter.clone().map(|f| f.into()),
-            limit: request.limit as u64,
-            with_vectors: request.with_vector.clone().map(|wv| wv.into()),
-            with_payload: request.with_payload.clone().map(|wp| wp.into()),
-            params: request.params.map(|sp| sp.into()),
-            score_threshold: request.score_threshold,
-            offset: Some(request.offset as u64),
-            vector_name: Some(request.query.get_vector_name().to_owned()),
-            read_consistency: None,
-        }
+    fn try_from(value: api::grpc::qdrant::FacetRangeHit) -> Result<Self, Self::Error> {
+        let range = value
+            .range
+            .ok_or_else(|| Status::invalid_argument("Malformed FacetRangeHit: missing range"))?;
+        Ok(Self {
+            range: range.into(),
+            count: value.count as usize,
+        })
     }
 }
 
-impl TryFrom<api::grpc::qdrant::WithLookup> for WithLookup {
+impl TryFrom<api::grpc::qdrant::FacetResponse> for FacetResponse {
     type Error = Status;
 
-    fn try_from(value: api::grpc::qdrant::WithLookup) -> Result<Self, Self::Error> {
-        let with_default_payload = || Some(segment::types::WithPayloadInterface::Bool(true));
-
+    fn try_from(value: api::grpc::qdrant::FacetResponse) -> Result<Self, Self::Error> {
         Ok(Self {
-            collection_name: value.collection,
-            with_payload: value
-                .with_payload
-                .map(|wp| wp.try_into())
-                .transpose()?
-                .or_else(with_default_payload),
-            with_vectors: value.with_vectors.map(|wv| wv.into()),
+            hits: value
+                .hits
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            ranges: value
+                .ranges
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::StreamChangesRequest> for ChangesRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::StreamChangesRequest) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::StreamChangesRequest {
+            collection_name: _,
+            offsets,
+            limit,
+            with_payload,
+            with_vectors,
+        } = value;
+
+        Ok(Self {
+            offsets: offsets
+                .into_iter()
+                .map(|(shard_id, offset)| (shard_id, offset.into()))
+                .collect(),
+            limit: limit.map(|limit| limit as usize),
+            with_payload: with_payload.map(TryInto::try_into).transpose()?,
+            with_vector: with_vectors.map(Into::into),
+        })
+    }
+}
+
+impl From<api::grpc::qdrant::ChangesOffset> for ChangesOffset {
+    fn from(value: api::grpc::qdrant::ChangesOffset) -> Self {
+        let mut offset = Self::default();
+        for clock in value.clocks {
+            offset.advance(clock.peer_id, clock.clock_id, clock.clock_tick);
+        }
+        offset
+    }
+}
+
+impl From<ChangesOffset> for api::grpc::qdrant::ChangesOffset {
+    fn from(value: ChangesOffset) -> Self {
+        Self {
+            clocks: value
+                .clocks
+                .into_iter()
+                .map(|clock| {
+                    let ChangesClock {
+                        peer_id,
+                        clock_id,
+                        clock_tick,
+                    } = clock;
+                    api::grpc::qdrant::ChangesClock {
+                        peer_id,
+                        clock_id,
+                        clock_tick,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<i32> for ChangeType {
+    type Error = Status;
+
+    fn try_from(change_type: i32) -> Result<Self, Self::Error> {
+        let change_type = api::grpc::qdrant::ChangeType::from_i32(change_type)
+            .ok_or_else(|| Status::invalid_argument("Malformed ChangeType type"))?;
+
+        let change_type = match change_type {
+            api::grpc::qdrant::ChangeType::UpsertChange => Self::Upsert,
+            api::grpc::qdrant::ChangeType::DeleteChange => Self::Delete,
+            api::grpc::qdrant::ChangeType::UpdateByFilterChange => Self::UpdateByFilter,
+            api::grpc::qdrant::ChangeType::DeleteByFilterChange => Self::DeleteByFilter,
+
+            api::grpc::qdrant::ChangeType::UnknownChangeType => {
+                return Err(Status::invalid_argument(
+                    "Malformed ChangeType type: change type is unknown",
+                ));
+            }
+        };
+
+        Ok(change_type)
+    }
+}
+
+pub fn try_point_change_from_grpc(
+    change: api::grpc::qdrant::PointChange,
+    with_payload: bool,
+) -> Result<PointChange, Status> {
+    let api::grpc::qdrant::PointChange {
+        position,
+        change_type,
+        id,
+        payload,
+        vectors,
+        filter,
+    } = change;
+
+    let change_type = ChangeType::try_from(change_type)?;
+
+    let payload = if with_payload && change_type == ChangeType::Upsert {
+        Some(api::grpc::conversions::proto_to_payloads(payload)?)
+    } else {
+        debug_assert!(payload.is_empty());
+        None
+    };
+
+    Ok(PointChange {
+        position,
+        change_type,
+        id: id.map(TryInto::try_into).transpose()?,
+        payload,
+        vector: vectors.map(TryInto::try_into).transpose()?,
+        filter: filter.map(TryInto::try_into).transpose()?,
+    })
+}
+
+impl From<ChangeType> for api::grpc::qdrant::ChangeType {
+    fn from(value: ChangeType) -> Self {
+        match value {
+            ChangeType::Upsert => Self::UpsertChange,
+            ChangeType::Delete => Self::DeleteChange,
+            ChangeType::UpdateByFilter => Self::UpdateByFilterChange,
+            ChangeType::DeleteByFilter => Self::DeleteByFilterChange,
+        }
+    }
+}
+
+impl From<PointChange> for api::grpc::qdrant::PointChange {
+    fn from(value: PointChange) -> Self {
+        let PointChange {
+            position,
+            change_type,
+            id,
+            payload,
+            vector,
+            filter,
+        } = value;
+
+        Self {
+            position,
+            change_type: api::grpc::qdrant::ChangeType::from(change_type) as i32,
+            id: id.map(Into::into),
+            payload: payload.map(payload_to_proto).unwrap_or_default(),
+            vectors: vector.map(Into::into),
+            filter: filter.map(Into::into),
+        }
+    }
+}
+
+impl From<ChangesBatch> for api::grpc::qdrant::ChangesBatch {
+    fn from(value: ChangesBatch) -> Self {
+        let ChangesBatch {
+            shard_id,
+            changes,
+            next_offset,
+        } = value;
+
+        Self {
+            shard_id,
+            changes: changes.into_iter().map(Into::into).collect(),
+            next_offset: Some(next_offset.into()),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SearchMatrixPoints> for SearchMatrixRequestInternal {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::SearchMatrixPoints) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::SearchMatrixPoints {
+            collection_name: _,
+            filter,
+            sample,
+            limit,
+            using,
+            read_consistency: _,
+            timeout: _,
+            shard_key_selector: _,
+            ids,
+        } = value;
+
+        let ids = if ids.is_empty() {
+            None
+        } else {
+            Some(
+                ids.into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            )
+        };
+
+        Ok(Self {
+            filter: filter.map(TryInto::try_into).transpose()?,
+            sample: sample.map(|sample| sample as usize),
+            ids,
+            limit: limit.map(|limit| limit as usize),
+            using,
+        })
+    }
+}
+
+impl From<SearchMatrixPair> for api::grpc::qdrant::SearchMatrixPair {
+    fn from(pair: SearchMatrixPair) -> Self {
+        Self {
+            a: Some(pair.a.into()),
+            b: Some(pair.b.into()),
+            score: pair.score,
+        }
+    }
+}
+
+impl From<SearchMatrixPairsResponse> for api::grpc::qdrant::SearchMatrixPairs {
+    fn from(response: SearchMatrixPairsResponse) -> Self {
+        Self {
+            pairs: response.pairs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SearchMatrixOffsetsResponse> for api::grpc::qdrant::SearchMatrixOffsets {
+    fn from(response: SearchMatrixOffsetsResponse) -> Self {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = response;
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: ids.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
+    type Error = Status;
+    fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
+        let SearchPoints {
+            collection_name: _,
+            vector,
+            filter,
+            limit,
+            with_payload,
+            params,
+            score_threshold,
+            offset,
+            vector_name,
+            with_vectors,
+            read_consistency: _,
+            timeout: _,
+            shard_key_selector: _,
+            sparse_indices,
+            vectors_count,
+            mmr,
+        } = value;
+
+        if let Some(sparse_indices) = &sparse_indices {
+            validate_sparse_vector_impl(&sparse_indices.data, &vector).map_err(|_| {
+                Status::invalid_argument("Sparse indices does not match sparse vector conditions")
+            })?;
+        }
+
+        let vector_struct = api::grpc::conversions::into_named_vector_struct(
+            vector_name,
+            vector,
+            sparse_indices,
+            vectors_count,
+        )?;
+
+        Ok(Self {
+            query: QueryEnum::Nearest(vector_struct),
+            filter: filter.map(TryInto::try_into).transpose()?,
+            params: params.map(Into::into),
+            limit: limit as usize,
+            offset: offset.map(|v| v as usize).unwrap_or_default(),
+            with_payload: with_payload.map(TryInto::try_into).transpose()?,
+            with_vector: with_vectors.map(Into::into),
+            score_threshold: score_threshold.map(|s| s as ScoreType),
+            mmr: mmr.map(Into::into),
+        })
+    }
+}
+
+// Use wrapper type to bundle CollectionId & SearchRequest
+impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
+    fn from(value: CollectionSearchRequest<'a>) -> Self {
+        let (collection_id, request) = value.0;
+        let (vector, sparse_indices, vectors_count) = match request.vector.get_vector().to_owned() {
+            Vector::Dense(vector) => (vector, None, None),
+            Vector::Sparse(vector) => (
+                vector.values,
+                Some(api::grpc::qdrant::SparseIndices {
+                    data: vector.indices,
+                }),
+                None,
+            ),
+            Vector::MultiDense(vector) => {
+                let vectors_count = vector.vectors_count() as u32;
+                (vector.flattened_vectors, None, Some(vectors_count))
+            }
+        };
+        Self {
+            collection_name: collection_id,
+            vector,
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: request.limit as u64,
+            with_vectors: request.with_vector.clone().map(|wv| wv.into()),
+            with_payload: request.with_payload.clone().map(|wp| wp.into()),
+            params: request.params.map(|sp| sp.into()),
+            score_threshold: request.score_threshold,
+            offset: request.offset.map(|x| x as u64),
+            vector_name: match request.vector.get_name() {
+                DEFAULT_VECTOR_NAME => None,
+                vector_name => Some(vector_name.to_string()),
+            },
+            read_consistency: None,
+            timeout: None,
+            shard_key_selector: None,
+            sparse_indices,
+            vectors_count,
+            mmr: request.mmr.map(Into::into),
+        }
+    }
+}
+
+impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
+    fn from(value: QueryEnum) -> Self {
+        match value {
+            QueryEnum::Nearest(vector) => api::grpc::qdrant::QueryEnum {
+                query: Some(api::grpc::qdrant::query_enum::Query::NearestNeighbors(
+                    vector.to_vector().into(),
+                )),
+            },
+            QueryEnum::RecommendBestScore(named) => api::grpc::qdrant::QueryEnum {
+                query: Some(api::grpc::qdrant::query_enum::Query::RecommendBestScore(
+                    api::grpc::qdrant::RecoQuery {
+                        positives: named.query.positives.into_iter().map_into().collect(),
+                        negatives: named.query.negatives.into_iter().map_into().collect(),
+                    },
+                )),
+            },
+            QueryEnum::Discover(named) => api::grpc::qdrant::QueryEnum {
+                query: Some(api::grpc::qdrant::query_enum::Query::Discover(
+                    api::grpc::qdrant::DiscoveryQuery {
+                        target: Some(named.query.target.into()),
+                        context: named
+                            .query
+                            .pairs
+                            .into_iter()
+                            .map(|pair| api::grpc::qdrant::ContextPair {
+                                positive: { Some(pair.positive.into()) },
+                                negative: { Some(pair.negative.into()) },
+                            })
+                            .collect(),
+                    },
+                )),
+            },
+            QueryEnum::Context(named) => api::grpc::qdrant::QueryEnum {
+                query: Some(api::grpc::qdrant::query_enum::Query::Context(
+                    api::grpc::qdrant::ContextQuery {
+                        context: named
+                            .query
+                            .pairs
+                            .into_iter()
+                            .map(|pair| api::grpc::qdrant::ContextPair {
+                                positive: { Some(pair.positive.into()) },
+                                negative: { Some(pair.negative.into()) },
+                            })
+                            .collect(),
+                    },
+                )),
+            },
+        }
+    }
+}
+
+impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearchPoints {
+    fn from(value: CollectionCoreSearchRequest<'a>) -> Self {
+        let (collection_id, request) = value.0;
+
+        Self {
+            collection_name: collection_id,
+            query: Some(request.query.clone().into()),
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: request.limit as u64,
+            with_vectors: request.with_vector.clone().map(|wv| wv.into()),
+            with_payload: request.with_payload.clone().map(|wp| wp.into()),
+            params: request.params.map(|sp| sp.into()),
+            score_threshold: request.score_threshold,
+            offset: Some(request.offset as u64),
+            vector_name: Some(request.query.get_vector_name().to_owned()),
+            read_consistency: None,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WithLookup> for WithLookup {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WithLookup) -> Result<Self, Self::Error> {
+        let with_default_payload = || Some(segment::types::WithPayloadInterface::Bool(true));
+
+        Ok(Self {
+            collection_name: value.collection,
+            with_payload: value
+                .with_payload
+                .map(|wp| wp.try_into())
+                .transpose()?
+                .or_else(with_default_payload),
+            with_vectors: value.with_vectors.map(|wv| wv.into()),
         })
     }
 }
@@ -1145,72 +1718,79 @@ fn try_context_pair_from_grpc(
     }
 }
 
+pub fn try_query_enum_from_grpc(
+    query: api::grpc::qdrant::QueryEnum,
+    vector_name: Option<String>,
+) -> Result<QueryEnum, Status> {
+    let query = query
+        .query
+        .ok_or(Status::invalid_argument("Query is not specified"))?;
+
+    Ok(match query {
+        api::grpc::qdrant::query_enum::Query::NearestNeighbors(vector) => {
+            QueryEnum::Nearest(api::grpc::conversions::into_named_vector_struct(
+                vector_name,
+                vector.data,
+                vector.indices.clone(),
+                vector.vectors_count,
+            )?)
+        }
+        api::grpc::qdrant::query_enum::Query::RecommendBestScore(query) => {
+            QueryEnum::RecommendBestScore(NamedQuery {
+                query: RecoQuery::new(
+                    query
+                        .positives
+                        .into_iter()
+                        .map(TryInto::try_into)
+                        .collect::<Result<_, _>>()?,
+                    query
+                        .negatives
+                        .into_iter()
+                        .map(TryInto::try_into)
+                        .collect::<Result<_, _>>()?,
+                ),
+                using: vector_name,
+            })
+        }
+        api::grpc::qdrant::query_enum::Query::Discover(query) => {
+            let target = match query.target {
+                Some(target) => target,
+                None => return Err(Status::invalid_argument("Target is not specified")),
+            };
+
+            let pairs = query
+                .context
+                .into_iter()
+                .map(try_context_pair_from_grpc)
+                .try_collect()?;
+
+            QueryEnum::Discover(NamedQuery {
+                query: DiscoveryQuery::new(target.try_into()?, pairs),
+                using: vector_name,
+            })
+        }
+        api::grpc::qdrant::query_enum::Query::Context(query) => {
+            let pairs = query
+                .context
+                .into_iter()
+                .map(try_context_pair_from_grpc)
+                .try_collect()?;
+
+            QueryEnum::Context(NamedQuery {
+                query: ContextQuery::new(pairs),
+                using: vector_name,
+            })
+        }
+    })
+}
+
 impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
     type Error = Status;
 
     fn try_from(value: api::grpc::qdrant::CoreSearchPoints) -> Result<Self, Self::Error> {
         let query = value
             .query
-            .and_then(|query| query.query)
-            .map(|query| {
-                Ok(match query {
-                    api::grpc::qdrant::query_enum::Query::NearestNeighbors(vector) => {
-                        QueryEnum::Nearest(api::grpc::conversions::into_named_vector_struct(
-                            value.vector_name,
-                            vector.data,
-                            vector.indices.clone(),
-                        )?)
-                    }
-                    api::grpc::qdrant::query_enum::Query::RecommendBestScore(query) => {
-                        QueryEnum::RecommendBestScore(NamedQuery {
-                            query: RecoQuery::new(
-                                query
-                                    .positives
-                                    .into_iter()
-                                    .map(TryInto::try_into)
-                                    .collect::<Result<_, _>>()?,
-                                query
-                                    .negatives
-                                    .into_iter()
-                                    .map(TryInto::try_into)
-                                    .collect::<Result<_, _>>()?,
-                            ),
-                            using: value.vector_name,
-                        })
-                    }
-                    api::grpc::qdrant::query_enum::Query::Discover(query) => {
-                        let target = match query.target {
-                            Some(target) => target,
-                            None => {
-                                return Err(Status::invalid_argument("Target is not specified"))
-                            }
-                        };
-
-                        let pairs = query
-                            .context
-                            .into_iter()
-                            .map(try_context_pair_from_grpc)
-                            .try_collect()?;
-
-                        QueryEnum::Discover(NamedQuery {
-                            query: DiscoveryQuery::new(target.try_into()?, pairs),
-                            using: value.vector_name,
-                        })
-                    }
-                    api::grpc::qdrant::query_enum::Query::Context(query) => {
-                        let pairs = query
-                            .context
-                            .into_iter()
-                            .map(try_context_pair_from_grpc)
-                            .try_collect()?;
-
-                        QueryEnum::Context(NamedQuery {
-                            query: ContextQuery::new(pairs),
-                            using: value.vector_name,
-                        })
-                    }
-                })
-            })
+            .map(|query| try_query_enum_from_grpc(query, value.vector_name))
             .transpose()?
             .ok_or(Status::invalid_argument("Query is not specified"))?;
 
@@ -1228,6 +1808,7 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            mmr: None,
         })
     }
 }
@@ -1241,6 +1822,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequestInternal {
                 value.vector_name,
                 value.vector,
                 value.sparse_indices,
+                value.vectors_count,
             )?,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
             params: value.params.map(|p| p.into()),
@@ -1254,6 +1836,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequestInternal {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            mmr: value.mmr.map(Into::into),
         })
     }
 }
@@ -1277,6 +1860,8 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            vectors_count: None,
+            mmr: None,
         };
 
         if let Some(sparse_indices) = &search_points.sparse_indices {
@@ -1298,6 +1883,7 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
             with_payload,
             with_vector,
             score_threshold,
+            mmr: _,
         } = search_points.try_into()?;
 
         Ok(SearchGroupsRequestInternal {
@@ -1337,6 +1923,81 @@ impl From<api::grpc::qdrant::LookupLocation> for LookupLocation {
     }
 }
 
+fn number_from_proto(value: api::grpc::qdrant::Value) -> Result<serde_json::Number, Status> {
+    match proto_to_json(value)? {
+        serde_json::Value::Number(number) => Ok(number),
+        _ => Err(Status::invalid_argument("Operator value must be a number")),
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PayloadUpdate> for PayloadUpdate {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PayloadUpdate) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::payload_update::Operator;
+
+        let api::grpc::qdrant::PayloadUpdate { key, operator } = value;
+        let operator = match operator
+            .ok_or_else(|| Status::invalid_argument("Payload update operator is missing"))?
+        {
+            Operator::Increment(value) => {
+                PayloadUpdateOperator::Increment(number_from_proto(value)?)
+            }
+            Operator::Min(value) => PayloadUpdateOperator::Min(number_from_proto(value)?),
+            Operator::Max(value) => PayloadUpdateOperator::Max(number_from_proto(value)?),
+            Operator::Push(value) => PayloadUpdateOperator::Push(proto_to_json(value)?),
+            Operator::Pull(value) => PayloadUpdateOperator::Pull(proto_to_json(value)?),
+            Operator::AddToSet(value) => PayloadUpdateOperator::AddToSet(proto_to_json(value)?),
+            Operator::Set(value) => PayloadUpdateOperator::Set(proto_to_json(value)?),
+        };
+        Ok(Self {
+            key: json_path_from_proto(&key)?,
+            operator,
+        })
+    }
+}
+
+impl From<PayloadUpdate> for api::grpc::qdrant::PayloadUpdate {
+    fn from(value: PayloadUpdate) -> Self {
+        use api::grpc::qdrant::payload_update::Operator;
+
+        let PayloadUpdate { key, operator } = value;
+        let operator = match operator {
+            PayloadUpdateOperator::Increment(number) => {
+                Operator::Increment(json_to_proto(number.into()))
+            }
+            PayloadUpdateOperator::Min(number) => Operator::Min(json_to_proto(number.into())),
+            PayloadUpdateOperator::Max(number) => Operator::Max(json_to_proto(number.into())),
+            PayloadUpdateOperator::Push(value) => Operator::Push(json_to_proto(value)),
+            PayloadUpdateOperator::Pull(value) => Operator::Pull(json_to_proto(value)),
+            PayloadUpdateOperator::AddToSet(value) => Operator::AddToSet(json_to_proto(value)),
+            PayloadUpdateOperator::Set(value) => Operator::Set(json_to_proto(value)),
+        };
+        Self {
+            key: key.to_string(),
+            operator: Some(operator),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::Mmr> for Mmr {
+    fn from(value: api::grpc::qdrant::Mmr) -> Self {
+        Self {
+            lambda: value.lambda,
+            candidates_limit: value.candidates_limit.map(|limit| limit as usize),
+        }
+    }
+}
+
+impl From<Mmr> for api::grpc::qdrant::Mmr {
+    fn from(value: Mmr) -> Self {
+        Self {
+            lambda: value.lambda,
+            candidates_limit: value.candidates_limit.map(|limit| limit as u64),
+        }
+    }
+}
+
 impl From<api::grpc::qdrant::RecommendStrategy> for RecommendStrategy {
     fn from(value: api::grpc::qdrant::RecommendStrategy) -> Self {
         match value {
@@ -1411,6 +2072,7 @@ impl TryFrom<api::grpc::qdrant::Vector> for RecommendExample {
         Ok(match vector {
             Vector::Dense(vector) => Self::Dense(vector),
             Vector::Sparse(vector) => Self::Sparse(vector),
+            Vector::MultiDense(vector) => Self::MultiDense(vector),
         })
     }
 }
@@ -1483,6 +2145,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             score_threshold: value.score_threshold,
             using: value.using.map(|name| name.into()),
             lookup_from: value.lookup_from.map(|x| x.into()),
+            mmr: value.mmr.map(Into::into),
         })
     }
 }
@@ -1510,6 +2173,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             negative_vectors: value.negative_vectors,
             timeout: None, // Passed as query param
             shard_key_selector: None,
+            mmr: None,
         };
 
         let RecommendRequestInternal {
@@ -1525,6 +2189,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             score_threshold,
             limit: _,
             offset: _,
+            mmr: _,
         } = recommend_points.try_into()?;
 
         Ok(RecommendGroupsRequestInternal {
@@ -1570,6 +2235,10 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             hnsw_config: value.hnsw_config.map(Into::into),
             quantization_config: value.quantization_config.map(Into::into),
             on_disk: value.on_disk,
+            multivector_config: value.multivector_config.map(Into::into),
+            datatype: value
+                .datatype
+                .map(|datatype| api::grpc::qdrant::Datatype::from(datatype).into()),
         }
     }
 }
@@ -1612,6 +2281,48 @@ impl From<ShardTransferInfo> for api::grpc::qdrant::ShardTransferInfo {
             from: value.from,
             to: value.to,
             sync: value.sync,
+            to_shard_id: value.to_shard_id,
+        }
+    }
+}
+
+impl From<ReshardingInfo> for api::grpc::qdrant::ReshardingInfo {
+    fn from(value: ReshardingInfo) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            peer_id: value.peer_id,
+            shard_key: value.shard_key.map(convert_shard_key_to_grpc),
+            direction: api::grpc::qdrant::ReshardingDirection::from(value.direction) as i32,
+        }
+    }
+}
+
+impl TryFrom<i32> for ReshardingDirection {
+    type Error = Status;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        api::grpc::qdrant::ReshardingDirection::from_i32(value)
+            .map(Into::into)
+            .ok_or_else(|| {
+                Status::invalid_argument(format!("Unknown resharding direction: {value}"))
+            })
+    }
+}
+
+impl From<api::grpc::qdrant::ReshardingDirection> for ReshardingDirection {
+    fn from(value: api::grpc::qdrant::ReshardingDirection) -> Self {
+        match value {
+            api::grpc::qdrant::ReshardingDirection::Up => ReshardingDirection::Up,
+            api::grpc::qdrant::ReshardingDirection::Down => ReshardingDirection::Down,
+        }
+    }
+}
+
+impl From<ReshardingDirection> for api::grpc::qdrant::ReshardingDirection {
+    fn from(value: ReshardingDirection) -> Self {
+        match value {
+            ReshardingDirection::Up => api::grpc::qdrant::ReshardingDirection::Up,
+            ReshardingDirection::Down => api::grpc::qdrant::ReshardingDirection::Down,
         }
     }
 }
@@ -1636,6 +2347,11 @@ impl From<CollectionClusterInfo> for api::grpc::qdrant::CollectionClusterInfoRes
                 .into_iter()
                 .map(|shard| shard.into())
                 .collect(),
+            resharding_operations: value
+                .resharding_operations
+                .into_iter()
+                .map(|resharding| resharding.into())
+                .collect(),
         }
     }
 }
@@ -1772,6 +2488,20 @@ impl TryFrom<ClusterOperationsPb> for ClusterOperations {
                     },
                 })
             }
+            Operation::StartResharding(op) => {
+                ClusterOperations::StartResharding(StartReshardingOperation {
+                    start_resharding: StartResharding {
+                        direction: op.direction.try_into()?,
+                        peer_id: op.peer_id,
+                        shard_key: op.shard_key.and_then(convert_shard_key_from_grpc),
+                    },
+                })
+            }
+            Operation::AbortResharding(api::grpc::qdrant::AbortResharding {}) => {
+                ClusterOperations::AbortResharding(AbortReshardingOperation {
+                    abort_resharding: AbortResharding {},
+                })
+            }
         })
     }
 }
@@ -1847,3 +2577,506 @@ impl TryFrom<api::grpc::qdrant::OrderBy> for OrderByInterface {
         }))
     }
 }
+
+/// Combine the primary `order_by` key and the tie-breaking `then_by` keys of a gRPC request
+pub fn try_order_by_from_grpc(
+    order_by: Option<api::grpc::qdrant::OrderBy>,
+    then_by: Vec<api::grpc::qdrant::OrderBy>,
+) -> Result<Option<OrderByInterface>, Status> {
+    let Some(order_by) = order_by else {
+        if !then_by.is_empty() {
+            return Err(Status::invalid_argument(
+                "`then_by` can only be used together with `order_by`",
+            ));
+        }
+        return Ok(None);
+    };
+
+    let order_by = OrderByInterface::try_from(order_by)?;
+    if then_by.is_empty() {
+        return Ok(Some(order_by));
+    }
+
+    let mut keys = Vec::<OrderBy>::from(order_by);
+    for key in then_by {
+        keys.extend(Vec::<OrderBy>::from(OrderByInterface::try_from(key)?));
+    }
+
+    Ok(Some(OrderByInterface::Multi(keys)))
+}
+
+impl From<Fusion> for api::grpc::qdrant::Fusion {
+    fn from(value: Fusion) -> Self {
+        match value {
+            Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+            Fusion::Dbsf => api::grpc::qdrant::Fusion::Dbsf,
+        }
+    }
+}
+
+pub fn fusion_from_proto(fusion: i32) -> Result<Fusion, Status> {
+    match api::grpc::qdrant::Fusion::from_i32(fusion) {
+        Some(api::grpc::qdrant::Fusion::Rrf) => Ok(Fusion::Rrf),
+        Some(api::grpc::qdrant::Fusion::Dbsf) => Ok(Fusion::Dbsf),
+        None => Err(Status::invalid_argument(format!(
+            "Unknown fusion: {fusion}"
+        ))),
+    }
+}
+
+impl From<Sample> for api::grpc::qdrant::Sample {
+    fn from(value: Sample) -> Self {
+        match value {
+            Sample::Random => api::grpc::qdrant::Sample::Random,
+        }
+    }
+}
+
+pub fn sample_from_proto(sample: i32) -> Result<Sample, Status> {
+    match api::grpc::qdrant::Sample::from_i32(sample) {
+        Some(api::grpc::qdrant::Sample::Random) => Ok(Sample::Random),
+        None => Err(Status::invalid_argument(format!(
+            "Unknown sample method: {sample}"
+        ))),
+    }
+}
+
+impl From<ShardSample> for api::grpc::qdrant::SampleParams {
+    fn from(value: ShardSample) -> Self {
+        let ShardSample { method, seed } = value;
+        Self {
+            method: api::grpc::qdrant::Sample::from(method) as i32,
+            seed: Some(seed),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SampleParams> for ShardSample {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::SampleParams) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::SampleParams { method, seed } = value;
+        Ok(Self {
+            method: sample_from_proto(method)?,
+            seed: seed.ok_or_else(|| Status::invalid_argument("Sample seed is not specified"))?,
+        })
+    }
+}
+
+fn expression_from_proto(
+    expression: Option<Box<api::grpc::qdrant::Expression>>,
+    name: &str,
+) -> Result<Box<Expression>, Status> {
+    let expression = expression
+        .ok_or_else(|| Status::invalid_argument(format!("Expression {name} is not specified")))?;
+    Ok(Box::new((*expression).try_into()?))
+}
+
+fn expressions_from_proto(
+    expressions: Vec<api::grpc::qdrant::Expression>,
+) -> Result<Vec<Expression>, Status> {
+    expressions.into_iter().map(TryInto::try_into).collect()
+}
+
+fn decay_params_from_proto(
+    params: api::grpc::qdrant::DecayParamsExpression,
+) -> Result<DecayParamsExpression, Status> {
+    let api::grpc::qdrant::DecayParamsExpression {
+        x,
+        target,
+        scale,
+        midpoint,
+    } = params;
+    Ok(DecayParamsExpression {
+        x: expression_from_proto(x, "x")?,
+        target: target
+            .map(|target| expression_from_proto(Some(target), "target"))
+            .transpose()?,
+        scale,
+        midpoint,
+    })
+}
+
+impl TryFrom<api::grpc::qdrant::Expression> for Expression {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::Expression) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::expression::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("Expression variant is not specified"))?;
+        let expression = match variant {
+            Variant::Constant(constant) => Expression::Constant(constant),
+            Variant::Variable(variable) => Expression::Variable(variable),
+            Variant::Condition(condition) => Expression::Condition(Box::new(condition.try_into()?)),
+            Variant::GeoDistance(api::grpc::qdrant::GeoDistance { origin, to }) => {
+                Expression::GeoDistance(GeoDistanceExpression {
+                    geo_distance: GeoDistanceParams {
+                        origin: origin
+                            .ok_or_else(|| {
+                                Status::invalid_argument("Geo distance origin is not specified")
+                            })?
+                            .into(),
+                        to: json_path_from_proto(&to)?,
+                    },
+                })
+            }
+            Variant::Datetime(datetime) => Expression::Datetime(DatetimeExpression { datetime }),
+            Variant::DatetimeKey(key) => Expression::DatetimeKey(DatetimeKeyExpression {
+                datetime_key: json_path_from_proto(&key)?,
+            }),
+            Variant::Mult(api::grpc::qdrant::MultExpression { mult }) => {
+                Expression::Mult(MultExpression {
+                    mult: expressions_from_proto(mult)?,
+                })
+            }
+            Variant::Sum(api::grpc::qdrant::SumExpression { sum }) => {
+                Expression::Sum(SumExpression {
+                    sum: expressions_from_proto(sum)?,
+                })
+            }
+            Variant::Div(div) => {
+                let api::grpc::qdrant::DivExpression {
+                    left,
+                    right,
+                    by_zero_default,
+                } = *div;
+                Expression::Div(DivExpression {
+                    div: DivParams {
+                        left: expression_from_proto(left, "left")?,
+                        right: expression_from_proto(right, "right")?,
+                        by_zero_default,
+                    },
+                })
+            }
+            Variant::Neg(neg) => Expression::Neg(NegExpression {
+                neg: expression_from_proto(Some(neg), "neg")?,
+            }),
+            Variant::Abs(abs) => Expression::Abs(AbsExpression {
+                abs: expression_from_proto(Some(abs), "abs")?,
+            }),
+            Variant::Sqrt(sqrt) => Expression::Sqrt(SqrtExpression {
+                sqrt: expression_from_proto(Some(sqrt), "sqrt")?,
+            }),
+            Variant::Pow(pow) => {
+                let api::grpc::qdrant::PowExpression { base, exponent } = *pow;
+                Expression::Pow(PowExpression {
+                    pow: PowParams {
+                        base: expression_from_proto(base, "base")?,
+                        exponent: expression_from_proto(exponent, "exponent")?,
+                    },
+                })
+            }
+            Variant::Exp(exp) => Expression::Exp(ExpExpression {
+                exp: expression_from_proto(Some(exp), "exp")?,
+            }),
+            Variant::Log10(log10) => Expression::Log10(Log10Expression {
+                log10: expression_from_proto(Some(log10), "log10")?,
+            }),
+            Variant::Ln(ln) => Expression::Ln(LnExpression {
+                ln: expression_from_proto(Some(ln), "ln")?,
+            }),
+            Variant::ExpDecay(params) => Expression::ExpDecay(ExpDecayExpression {
+                exp_decay: decay_params_from_proto(*params)?,
+            }),
+            Variant::GaussDecay(params) => Expression::GaussDecay(GaussDecayExpression {
+                gauss_decay: decay_params_from_proto(*params)?,
+            }),
+            Variant::LinDecay(params) => Expression::LinDecay(LinDecayExpression {
+                lin_decay: decay_params_from_proto(*params)?,
+            }),
+        };
+        Ok(expression)
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::Formula> for FormulaQuery {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::Formula) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::Formula {
+            expression,
+            defaults,
+        } = value;
+        Ok(Self {
+            expression: *expression_from_proto(expression.map(Box::new), "of formula")?,
+            defaults: proto_to_payloads(defaults)?.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::Query> for QueryInterface {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::Query) -> Result<Self, Self::Error> {
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("Query variant is not specified"))?;
+        let query = match variant {
+            api::grpc::qdrant::query::Variant::Nearest(vector) => {
+                UniversalQuery::Nearest(vector.try_into()?)
+            }
+            api::grpc::qdrant::query::Variant::Fusion(fusion) => {
+                UniversalQuery::Fusion(fusion_from_proto(fusion)?)
+            }
+            api::grpc::qdrant::query::Variant::Sample(sample) => {
+                let api::grpc::qdrant::SampleParams { method, seed } = sample;
+                UniversalQuery::Sample(SampleInterface::Params(SampleParams {
+                    method: sample_from_proto(method)?,
+                    seed,
+                }))
+            }
+            api::grpc::qdrant::query::Variant::Formula(formula) => {
+                UniversalQuery::Formula(formula.try_into()?)
+            }
+        };
+        Ok(QueryInterface::Query(query))
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PrefetchQuery> for Prefetch {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PrefetchQuery) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PrefetchQuery {
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            limit,
+        } = value;
+
+        Ok(Self {
+            prefetch: prefetch
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            query: query
+                .ok_or_else(|| Status::invalid_argument("Prefetch query is not specified"))?
+                .try_into()?,
+            using,
+            filter: filter.map(TryInto::try_into).transpose()?,
+            params: params.map(Into::into),
+            score_threshold,
+            limit: limit.map_or(DEFAULT_QUERY_LIMIT, |limit| limit as usize),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::QueryPoints> for QueryRequestInternal {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::QueryPoints) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::QueryPoints {
+            collection_name: _,
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            limit,
+            offset,
+            with_vectors,
+            with_payload,
+            read_consistency: _,
+            shard_key_selector: _,
+            timeout: _,
+        } = value;
+
+        Ok(Self {
+            prefetch: prefetch
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            query: query.map(TryInto::try_into).transpose()?,
+            using,
+            filter: filter.map(TryInto::try_into).transpose()?,
+            params: params.map(Into::into),
+            score_threshold,
+            limit: limit.map_or(DEFAULT_QUERY_LIMIT, |limit| limit as usize),
+            offset: offset.unwrap_or_default() as usize,
+            with_vector: with_vectors.map(Into::into),
+            with_payload: with_payload.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<ScoringQuery> for api::grpc::qdrant::query_shard_points::Query {
+    fn from(value: ScoringQuery) -> Self {
+        match value {
+            ScoringQuery::Vector(query) => Self {
+                vector_name: Some(query.get_vector_name().to_string()),
+                score: Some(api::grpc::qdrant::query_shard_points::query::Score::Vector(
+                    query.into(),
+                )),
+            },
+            ScoringQuery::Fusion(fusion) => Self {
+                vector_name: None,
+                score: Some(api::grpc::qdrant::query_shard_points::query::Score::Fusion(
+                    api::grpc::qdrant::Fusion::from(fusion) as i32,
+                )),
+            },
+            ScoringQuery::Sample(sample) => Self {
+                vector_name: None,
+                score: Some(api::grpc::qdrant::query_shard_points::query::Score::Sample(
+                    sample.into(),
+                )),
+            },
+            ScoringQuery::Formula(formula) => Self {
+                vector_name: None,
+                score: Some(
+                    api::grpc::qdrant::query_shard_points::query::Score::Formula(formula.into()),
+                ),
+            },
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::query_shard_points::Query> for ScoringQuery {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::query_shard_points::Query) -> Result<Self, Self::Error> {
+        let score = value
+            .score
+            .ok_or_else(|| Status::invalid_argument("Query score is not specified"))?;
+        match score {
+            api::grpc::qdrant::query_shard_points::query::Score::Vector(query) => Ok(
+                ScoringQuery::Vector(try_query_enum_from_grpc(query, value.vector_name)?),
+            ),
+            api::grpc::qdrant::query_shard_points::query::Score::Fusion(fusion) => {
+                Ok(ScoringQuery::Fusion(fusion_from_proto(fusion)?))
+            }
+            api::grpc::qdrant::query_shard_points::query::Score::Sample(sample) => {
+                Ok(ScoringQuery::Sample(sample.try_into()?))
+            }
+            api::grpc::qdrant::query_shard_points::query::Score::Formula(formula) => {
+                let formula = FormulaQuery::try_from(formula)?;
+                Ok(ScoringQuery::Formula(
+                    ParsedFormula::try_from(formula)
+                        .map_err(|err| Status::invalid_argument(err.to_string()))?,
+                ))
+            }
+        }
+    }
+}
+
+impl From<ShardPrefetch> for api::grpc::qdrant::query_shard_points::Prefetch {
+    fn from(value: ShardPrefetch) -> Self {
+        let ShardPrefetch {
+            prefetches,
+            query,
+            limit,
+            params,
+            filter,
+            score_threshold,
+        } = value;
+
+        Self {
+            prefetch: prefetches.into_iter().map(Into::into).collect(),
+            query: Some(query.into()),
+            limit: limit as u64,
+            params: params.map(Into::into),
+            filter: filter.map(Into::into),
+            score_threshold,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::query_shard_points::Prefetch> for ShardPrefetch {
+    type Error = Status;
+
+    fn try_from(
+        value: api::grpc::qdrant::query_shard_points::Prefetch,
+    ) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::query_shard_points::Prefetch {
+            prefetch,
+            query,
+            limit,
+            params,
+            filter,
+            score_threshold,
+        } = value;
+
+        Ok(Self {
+            prefetches: prefetch
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            query: query
+                .ok_or_else(|| Status::invalid_argument("Prefetch query is not specified"))?
+                .try_into()?,
+            limit: limit as usize,
+            params: params.map(Into::into),
+            filter: filter.map(TryInto::try_into).transpose()?,
+            score_threshold,
+        })
+    }
+}
+
+impl From<ShardQueryRequest> for api::grpc::qdrant::QueryShardPoints {
+    fn from(value: ShardQueryRequest) -> Self {
+        let ShardQueryRequest {
+            prefetches,
+            query,
+            filter,
+            score_threshold,
+            limit,
+            offset,
+            params,
+            with_vector,
+            with_payload,
+        } = value;
+
+        Self {
+            prefetch: prefetches.into_iter().map(Into::into).collect(),
+            query: query.map(Into::into),
+            filter: filter.map(Into::into),
+            score_threshold,
+            limit: limit as u64,
+            offset: offset as u64,
+            params: params.map(Into::into),
+            with_payload: Some(with_payload.into()),
+            with_vectors: Some(with_vector.into()),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::QueryShardPoints> for ShardQueryRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::QueryShardPoints) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::QueryShardPoints {
+            prefetch,
+            query,
+            filter,
+            score_threshold,
+            limit,
+            offset,
+            params,
+            with_payload,
+            with_vectors,
+        } = value;
+
+        Ok(Self {
+            prefetches: prefetch
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            query: query.map(TryInto::try_into).transpose()?,
+            filter: filter.map(TryInto::try_into).transpose()?,
+            score_threshold,
+            limit: limit as usize,
+            offset: offset as usize,
+            params: params.map(Into::into),
+            with_vector: with_vectors.map(Into::into).unwrap_or_default(),
+            with_payload: with_payload
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or(segment::types::WithPayloadInterface::Bool(false)),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+pub mod changes;
 pub mod cluster_ops;
 pub mod config_diff;
 pub mod consistency_params;
@@ -9,18 +10,26 @@ pub mod shard_key_selector;
 pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
+pub mod strict_mode;
+pub mod ttl;
 pub mod types;
+pub mod universal_query;
 pub mod validation;
 pub mod vector_ops;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use segment::common::check_vector_datatype;
+use segment::data_types::vectors::{
+    BatchVectorStruct, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
+};
 use segment::json_path::JsonPath;
-use segment::types::{ExtendedPointId, PayloadFieldSchema};
+use segment::types::{ExtendedPointId, PayloadFieldSchema, PointIdType};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::hash_ring::HashRing;
+use crate::operations::types::{CollectionResult, VectorsConfig};
 use crate::shards::shard::{PeerId, ShardId};
 
 pub type ClockToken = u64;
@@ -265,6 +274,108 @@ impl CollectionUpdateOperations {
             }
         }
     }
+
+    /// Preconditions of the points, which must hold for the operation to update them
+    pub fn preconditions(&self) -> &[point_ops::PointPrecondition] {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => operation.preconditions(),
+            CollectionUpdateOperations::PayloadOperation(operation) => operation.preconditions(),
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => &[],
+        }
+    }
+
+    /// Exclude points from the operation, e.g. the ones with failed preconditions
+    pub fn exclude_points(&mut self, excluded: &HashSet<PointIdType>) {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.exclude_points(excluded)
+            }
+            CollectionUpdateOperations::PayloadOperation(operation) => {
+                operation.exclude_points(excluded)
+            }
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => {}
+        }
+    }
+
+    /// Exclude points with failed preconditions and drop the preconditions
+    ///
+    /// Resolved operation updates the same points on every replica, regardless of the point
+    /// versions the replica has.
+    pub fn resolve_preconditions(&mut self, failed: &[types::FailedPrecondition]) {
+        if !failed.is_empty() {
+            let excluded: HashSet<_> = failed.iter().map(|failed| failed.id).collect();
+            self.exclude_points(&excluded);
+        }
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.clear_preconditions()
+            }
+            CollectionUpdateOperations::PayloadOperation(operation) => {
+                operation.clear_preconditions()
+            }
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => {}
+        }
+    }
+
+    /// Check that inserted vectors can be stored with the datatypes configured for them
+    ///
+    /// Done before the operation is written to the WAL, as segments would fail to apply it.
+    pub fn check_vector_datatypes(&self, vectors_config: &VectorsConfig) -> CollectionResult<()> {
+        let check = |name: &str, vector: VectorRef| -> CollectionResult<()> {
+            match vectors_config
+                .get_params(name)
+                .and_then(|params| params.datatype)
+            {
+                Some(datatype) => Ok(check_vector_datatype(vector, datatype)?),
+                None => Ok(()),
+            }
+        };
+        let check_struct = |vector: &VectorStruct| match vector {
+            VectorStruct::Single(vector) => check(DEFAULT_VECTOR_NAME, vector.into()),
+            VectorStruct::Multi(vectors) => vectors
+                .iter()
+                .try_for_each(|(name, vector)| check(name, vector.into())),
+        };
+
+        match self {
+            CollectionUpdateOperations::PointOperation(
+                point_ops::PointOperations::UpsertPoints(insert)
+                | point_ops::PointOperations::UpsertPointsConditional(
+                    point_ops::ConditionalInsertOperationInternal {
+                        points_op: insert, ..
+                    },
+                ),
+            ) => match insert {
+                point_ops::PointInsertOperationsInternal::PointsBatch(batch) => {
+                    match &batch.vectors {
+                        BatchVectorStruct::Single(vectors) => vectors
+                            .iter()
+                            .try_for_each(|vector| check(DEFAULT_VECTOR_NAME, vector.into())),
+                        BatchVectorStruct::Multi(vectors) => {
+                            vectors.iter().try_for_each(|(name, vectors)| {
+                                vectors
+                                    .iter()
+                                    .try_for_each(|vector| check(name, vector.into()))
+                            })
+                        }
+                    }
+                }
+                point_ops::PointInsertOperationsInternal::PointsList(points) => points
+                    .iter()
+                    .try_for_each(|point| check_struct(&point.vector)),
+            },
+            CollectionUpdateOperations::VectorOperation(
+                vector_ops::VectorOperations::UpdateVectors(update),
+            ) => update
+                .points
+                .iter()
+                .try_for_each(|point| check_struct(&point.vector)),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -357,7 +468,15 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
-            let delete = Self::DeletePoints { ids: Vec::new() };
+            let upsert_conditional =
+                Self::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                    preconditions: Vec::new(),
+                });
+            let delete = Self::DeletePoints {
+                ids: Vec::new(),
+                preconditions: Vec::new(),
+            };
 
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
                 should: None,
@@ -374,6 +493,7 @@ mod tests {
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_conditional),
                 Just(delete),
                 Just(delete_by_filter),
                 Just(sync),
@@ -393,6 +513,7 @@ mod tests {
                 PointIdsList {
                     points: Vec::new(),
                     shard_key: None,
+                    preconditions: Vec::new(),
                 },
                 Vec::new(),
             );
@@ -421,6 +542,7 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                preconditions: Vec::new(),
             });
 
             let overwrite = Self::OverwritePayload(SetPayloadOp {
@@ -428,15 +550,28 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                preconditions: Vec::new(),
             });
 
             let delete = Self::DeletePayload(DeletePayloadOp {
                 keys: Vec::new(),
                 points: None,
                 filter: None,
+                preconditions: Vec::new(),
             });
 
-            let clear = Self::ClearPayload { points: Vec::new() };
+            let clear = Self::ClearPayload {
+                points: Vec::new(),
+                preconditions: Vec::new(),
+            };
+
+            let update = Self::UpdatePayload(UpdatePayloadOp {
+                updates: Vec::new(),
+                points: None,
+                filter: None,
+                condition: None,
+                preconditions: Vec::new(),
+            });
 
             let clear_by_filter = Self::ClearPayloadByFilter(Filter {
                 should: None,
@@ -451,6 +586,7 @@ mod tests {
                 Just(delete),
                 Just(clear),
                 Just(clear_by_filter),
+                Just(update),
             ]
             .boxed()
         }
@@ -471,4 +607,37 @@ mod tests {
             prop_oneof![Just(create), Just(delete),].boxed()
         }
     }
+
+    #[test]
+    fn test_check_vector_datatypes() {
+        let vectors_config = types::VectorsConfig::Single(types::VectorParams {
+            size: std::num::NonZeroU64::new(2).unwrap(),
+            distance: Distance::Dot,
+            hnsw_config: None,
+            quantization_config: None,
+            on_disk: None,
+            multivector_config: None,
+            datatype: Some(VectorStorageDatatype::Uint8),
+        });
+        let upsert = |vector: Vec<f32>| {
+            CollectionUpdateOperations::PointOperation(
+                vec![PointStruct {
+                    id: PointIdType::NumId(1),
+                    vector: vector.into(),
+                    payload: None,
+                }]
+                .into(),
+            )
+        };
+
+        assert!(upsert(vec![0.0, 255.0])
+            .check_vector_datatypes(&vectors_config)
+            .is_ok());
+        assert!(upsert(vec![0.5, 1.0])
+            .check_vector_datatypes(&vectors_config)
+            .is_err());
+        assert!(upsert(vec![1.0, 256.0])
+            .check_vector_datatypes(&vectors_config)
+            .is_err());
+    }
 }
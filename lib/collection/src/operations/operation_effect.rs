@@ -48,7 +48,10 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
             point_ops::PointOperations::UpsertPoints(insert_operations) => {
                 insert_operations.estimate_effect_area()
             }
-            point_ops::PointOperations::DeletePoints { ids } => {
+            point_ops::PointOperations::UpsertPointsConditional(conditional_insert) => {
+                conditional_insert.points_op.estimate_effect_area()
+            }
+            point_ops::PointOperations::DeletePoints { ids, .. } => {
                 OperationEffectArea::Points(ids.clone())
             }
             point_ops::PointOperations::DeletePointsByFilter(filter) => {
@@ -116,7 +119,7 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
-            PayloadOps::ClearPayload { points } => OperationEffectArea::Points(points.clone()),
+            PayloadOps::ClearPayload { points, .. } => OperationEffectArea::Points(points.clone()),
             PayloadOps::ClearPayloadByFilter(filter) => OperationEffectArea::Filter(filter.clone()),
             PayloadOps::OverwritePayload(set_payload) => {
                 if let Some(points) = &set_payload.points {
@@ -127,6 +130,15 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
+            PayloadOps::UpdatePayload(update_payload) => {
+                if let Some(points) = &update_payload.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &update_payload.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
         }
     }
 }
@@ -1,10 +1,18 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
 use schemars::JsonSchema;
-use segment::json_path::JsonPath;
+use segment::json_path::{JsonPath, JsonPathItem};
 use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use serde_json::{Map, Number, Value};
+use validator::{Validate, ValidationError};
 
+use super::point_ops::{
+    preconditions_of_points, preconditions_validation_errors, validate_point_preconditions,
+    validate_preconditions_points, PointPrecondition,
+};
 use super::{split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRing;
 use crate::operations::shard_key_selector::ShardKeySelector;
@@ -23,6 +31,11 @@ pub struct SetPayload {
     pub shard_key: Option<ShardKeySelector>,
     /// Assigns payload to each point that satisfy this path of property
     pub key: Option<JsonPath>,
+    /// Only update points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -31,6 +44,7 @@ pub struct SetPayload {
 /// Unlike `SetPayload` it does not contain `shard_key` field
 /// as individual shard does not need to know about shard key
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+#[validate(schema(function = "validate_set_payload_op_preconditions"))]
 pub struct SetPayloadOp {
     pub payload: Payload,
     /// Assigns payload to each point in this list
@@ -39,6 +53,13 @@ pub struct SetPayloadOp {
     pub filter: Option<Filter>,
     /// Payload selector to indicate property of payload, e.g. `a.b.c`
     pub key: Option<JsonPath>,
+    /// Only update points, which satisfy these preconditions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+fn validate_set_payload_op_preconditions(operation: &SetPayloadOp) -> Result<(), ValidationError> {
+    validate_preconditions_points(&operation.preconditions, operation.points.as_deref())
 }
 
 #[derive(Deserialize)]
@@ -48,6 +69,8 @@ struct SetPayloadShadow {
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
     pub key: Option<JsonPath>,
+    #[serde(default)]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 pub struct PointsSelectorValidationError;
@@ -72,6 +95,7 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 filter: value.filter,
                 shard_key: value.shard_key,
                 key: value.key,
+                preconditions: value.preconditions,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -91,6 +115,11 @@ pub struct DeletePayload {
     pub filter: Option<Filter>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -99,6 +128,7 @@ pub struct DeletePayload {
 /// Unlike `DeletePayload` it does not contain `shard_key` field
 /// as individual shard does not need to know about shard key
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+#[validate(schema(function = "validate_delete_payload_op_preconditions"))]
 pub struct DeletePayloadOp {
     /// List of payload keys to remove from payload
     pub keys: Vec<PayloadKeyType>,
@@ -106,6 +136,15 @@ pub struct DeletePayloadOp {
     pub points: Option<Vec<PointIdType>>,
     /// Deletes values from points that satisfy this filter condition
     pub filter: Option<Filter>,
+    /// Only update points, which satisfy these preconditions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+fn validate_delete_payload_op_preconditions(
+    operation: &DeletePayloadOp,
+) -> Result<(), ValidationError> {
+    validate_preconditions_points(&operation.preconditions, operation.points.as_deref())
 }
 
 #[derive(Deserialize)]
@@ -114,6 +153,8 @@ struct DeletePayloadShadow {
     pub points: Option<Vec<PointIdType>>,
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
+    #[serde(default)]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 impl TryFrom<DeletePayloadShadow> for DeletePayload {
@@ -126,6 +167,270 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
                 points: value.points,
                 filter: value.filter,
                 shard_key: value.shard_key,
+                preconditions: value.preconditions,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+/// Atomic operator, which computes a new payload value from the current one
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadUpdateOperator {
+    /// Add the number to the current value. Missing value is treated as `0`
+    Increment(Number),
+    /// Replace the current value with the number, if the number is smaller or the value is missing
+    Min(Number),
+    /// Replace the current value with the number, if the number is larger or the value is missing
+    Max(Number),
+    /// Append the value to the array. Missing array is created
+    Push(Value),
+    /// Remove all elements, equal to the value, from the array
+    Pull(Value),
+    /// Append the value to the array, unless it already contains an equal one. Missing array is created
+    AddToSet(Value),
+    /// Replace the current value. Together with a `condition` works as compare-and-set
+    Set(Value),
+}
+
+impl PayloadUpdateOperator {
+    /// New value of the field, `None` if it stays unchanged
+    ///
+    /// `null` is treated the same way as a missing value.
+    fn apply(&self, current: Option<&Value>) -> Result<Option<Value>, String> {
+        let current = current.filter(|value| !value.is_null());
+        let new_value = match (self, current) {
+            (PayloadUpdateOperator::Increment(number), None)
+            | (PayloadUpdateOperator::Min(number) | PayloadUpdateOperator::Max(number), None) => {
+                Value::Number(number.clone())
+            }
+            (PayloadUpdateOperator::Increment(number), Some(Value::Number(current))) => {
+                Value::Number(add_numbers(current, number)?)
+            }
+            (PayloadUpdateOperator::Min(number), Some(Value::Number(current))) => {
+                if compare_numbers(number, current) != Ordering::Less {
+                    return Ok(None);
+                }
+                Value::Number(number.clone())
+            }
+            (PayloadUpdateOperator::Max(number), Some(Value::Number(current))) => {
+                if compare_numbers(number, current) != Ordering::Greater {
+                    return Ok(None);
+                }
+                Value::Number(number.clone())
+            }
+            (
+                PayloadUpdateOperator::Increment(_)
+                | PayloadUpdateOperator::Min(_)
+                | PayloadUpdateOperator::Max(_),
+                Some(_),
+            ) => return Err("value is not a number".to_string()),
+            (PayloadUpdateOperator::Push(value) | PayloadUpdateOperator::AddToSet(value), None) => {
+                Value::Array(vec![value.clone()])
+            }
+            (PayloadUpdateOperator::Push(value), Some(Value::Array(current))) => {
+                let mut array = current.clone();
+                array.push(value.clone());
+                Value::Array(array)
+            }
+            (PayloadUpdateOperator::AddToSet(value), Some(Value::Array(current))) => {
+                if current.contains(value) {
+                    return Ok(None);
+                }
+                let mut array = current.clone();
+                array.push(value.clone());
+                Value::Array(array)
+            }
+            (PayloadUpdateOperator::Pull(_), None) => return Ok(None),
+            (PayloadUpdateOperator::Pull(value), Some(Value::Array(current))) => {
+                if !current.contains(value) {
+                    return Ok(None);
+                }
+                Value::Array(current.iter().filter(|x| *x != value).cloned().collect())
+            }
+            (
+                PayloadUpdateOperator::Push(_)
+                | PayloadUpdateOperator::AddToSet(_)
+                | PayloadUpdateOperator::Pull(_),
+                Some(_),
+            ) => return Err("value is not an array".to_string()),
+            (PayloadUpdateOperator::Set(value), current) => {
+                if current == Some(value) {
+                    return Ok(None);
+                }
+                value.clone()
+            }
+        };
+        Ok(Some(new_value))
+    }
+}
+
+/// Integers are added without loss of precision, unless one of the numbers is a float
+fn add_numbers(a: &Number, b: &Number) -> Result<Number, String> {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return a
+            .checked_add(b)
+            .map(Number::from)
+            .ok_or_else(|| "integer overflow".to_string());
+    }
+    let sum = a.as_f64().unwrap_or_default() + b.as_f64().unwrap_or_default();
+    Number::from_f64(sum).ok_or_else(|| format!("result {sum} is not a valid number"))
+}
+
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return a.cmp(&b);
+    }
+    let a = a.as_f64().unwrap_or_default();
+    let b = b.as_f64().unwrap_or_default();
+    a.total_cmp(&b)
+}
+
+/// Update of a single payload value
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct PayloadUpdate {
+    /// Path to the updated value, e.g. `a.b`. Array elements can't be addressed
+    #[validate(custom = "validate_payload_update_key")]
+    pub key: JsonPath,
+    /// How to compute the new value
+    pub operator: PayloadUpdateOperator,
+}
+
+fn validate_payload_update_key(key: &JsonPath) -> Result<(), ValidationError> {
+    if key
+        .rest
+        .iter()
+        .all(|item| matches!(item, JsonPathItem::Key(_)))
+    {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("payload_update_key");
+        error.message = Some("array elements can't be updated by operators".into());
+        Err(error)
+    }
+}
+
+impl PayloadUpdate {
+    fn keys(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.key.first_key.as_str()).chain(self.key.rest.iter().filter_map(
+            |item| match item {
+                JsonPathItem::Key(key) => Some(key.as_str()),
+                JsonPathItem::Index(_) | JsonPathItem::WildcardIndex => None,
+            },
+        ))
+    }
+
+    /// Apply to the payload, returns `false` if it stays unchanged
+    pub fn apply(&self, payload: &mut Payload) -> Result<bool, String> {
+        let mut current = None;
+        let mut map = Some(&payload.0);
+        for key in self.keys() {
+            current = map.and_then(|map| map.get(key));
+            map = current.and_then(Value::as_object);
+        }
+        let Some(new_value) = self.operator.apply(current)? else {
+            return Ok(false);
+        };
+
+        let mut keys = self.keys().peekable();
+        let mut map = &mut payload.0;
+        while let Some(key) = keys.next() {
+            if keys.peek().is_none() {
+                map.insert(key.to_string(), new_value);
+                break;
+            }
+            let value = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+            if value.is_null() {
+                *value = Value::Object(Map::new());
+            }
+            map = value
+                .as_object_mut()
+                .ok_or_else(|| format!("`{key}` is not an object"))?;
+        }
+        Ok(true)
+    }
+}
+
+/// This data structure is used in API interface and applied across multiple shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "UpdatePayloadShadow")]
+pub struct UpdatePayload {
+    /// Updates to apply to the payload of each point, in order
+    #[validate]
+    #[validate(length(min = 1, message = "must specify payload updates"))]
+    pub updates: Vec<PayloadUpdate>,
+    /// Updates payload of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Updates payload of each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+    /// Only update points, which payload matches this condition at the moment of the update.
+    /// Points, which don't match, are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Filter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+/// This data structure is used inside shard operations queue
+/// and supposed to be written into WAL of individual shard.
+///
+/// Unlike `UpdatePayload` it does not contain `shard_key` field
+/// as individual shard does not need to know about shard key
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+#[validate(schema(function = "validate_update_payload_op_preconditions"))]
+pub struct UpdatePayloadOp {
+    /// Updates to apply to the payload of each point, in order
+    #[validate]
+    pub updates: Vec<PayloadUpdate>,
+    /// Updates payload of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Updates payload of each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+    /// Only update points, which payload matches this condition at the moment of the update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Filter>,
+    /// Only update points, which satisfy these preconditions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+fn validate_update_payload_op_preconditions(
+    operation: &UpdatePayloadOp,
+) -> Result<(), ValidationError> {
+    validate_preconditions_points(&operation.preconditions, operation.points.as_deref())
+}
+
+#[derive(Deserialize)]
+struct UpdatePayloadShadow {
+    pub updates: Vec<PayloadUpdate>,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+    #[serde(default)]
+    pub condition: Option<Filter>,
+    pub shard_key: Option<ShardKeySelector>,
+    #[serde(default)]
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+impl TryFrom<UpdatePayloadShadow> for UpdatePayload {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: UpdatePayloadShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(UpdatePayload {
+                updates: value.updates,
+                points: value.points,
+                filter: value.filter,
+                condition: value.condition,
+                shard_key: value.shard_key,
+                preconditions: value.preconditions,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -142,11 +447,17 @@ pub enum PayloadOps {
     /// Deletes specified payload values if they are assigned
     DeletePayload(DeletePayloadOp),
     /// Drops all Payload values associated with given points.
-    ClearPayload { points: Vec<PointIdType> },
+    ClearPayload {
+        points: Vec<PointIdType>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        preconditions: Vec<PointPrecondition>,
+    },
     /// Clear all Payload values by given filter criteria.
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayloadOp),
+    /// Update payload values with atomic operators
+    UpdatePayload(UpdatePayloadOp),
 }
 
 impl PayloadOps {
@@ -157,8 +468,50 @@ impl PayloadOps {
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::UpdatePayload(_) => true,
+        }
+    }
+
+    pub fn preconditions(&self) -> &[PointPrecondition] {
+        match self {
+            PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation) => {
+                &operation.preconditions
+            }
+            PayloadOps::DeletePayload(operation) => &operation.preconditions,
+            PayloadOps::ClearPayload { preconditions, .. } => preconditions,
+            PayloadOps::UpdatePayload(operation) => &operation.preconditions,
+            PayloadOps::ClearPayloadByFilter(_) => &[],
+        }
+    }
+
+    pub fn exclude_points(&mut self, excluded: &HashSet<PointIdType>) {
+        let points = match self {
+            PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation) => {
+                operation.points.as_mut()
+            }
+            PayloadOps::DeletePayload(operation) => operation.points.as_mut(),
+            PayloadOps::ClearPayload { points, .. } => Some(points),
+            PayloadOps::UpdatePayload(operation) => operation.points.as_mut(),
+            PayloadOps::ClearPayloadByFilter(_) => None,
+        };
+        if let Some(points) = points {
+            points.retain(|id| !excluded.contains(id));
         }
     }
+
+    /// Drop preconditions, making the operation unconditional
+    pub fn clear_preconditions(&mut self) {
+        let preconditions = match self {
+            PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation) => {
+                &mut operation.preconditions
+            }
+            PayloadOps::DeletePayload(operation) => &mut operation.preconditions,
+            PayloadOps::ClearPayload { preconditions, .. } => preconditions,
+            PayloadOps::UpdatePayload(operation) => &mut operation.preconditions,
+            PayloadOps::ClearPayloadByFilter(_) => return,
+        };
+        preconditions.clear();
+    }
 }
 
 impl Validate for PayloadOps {
@@ -166,9 +519,14 @@ impl Validate for PayloadOps {
         match self {
             PayloadOps::SetPayload(operation) => operation.validate(),
             PayloadOps::DeletePayload(operation) => operation.validate(),
-            PayloadOps::ClearPayload { .. } => Ok(()),
+            PayloadOps::ClearPayload {
+                points,
+                preconditions,
+            } => validate_preconditions_points(preconditions, Some(points))
+                .map_err(preconditions_validation_errors),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::UpdatePayload(operation) => operation.validate(),
         }
     }
 }
@@ -182,12 +540,23 @@ impl SplitByShard for PayloadOps {
             PayloadOps::DeletePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::DeletePayload),
-            PayloadOps::ClearPayload { points } => split_iter_by_shard(points, |id| *id, ring)
-                .map(|points| PayloadOps::ClearPayload { points }),
+            PayloadOps::ClearPayload {
+                points,
+                preconditions,
+            } => split_iter_by_shard(points, |id| *id, ring).map(|points| {
+                let preconditions = preconditions_of_points(&preconditions, &points);
+                PayloadOps::ClearPayload {
+                    points,
+                    preconditions,
+                }
+            }),
             operation @ PayloadOps::ClearPayloadByFilter(_) => OperationToShard::to_all(operation),
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::UpdatePayload(operation) => operation
+                .split_by_shard(ring)
+                .map(PayloadOps::UpdatePayload),
         }
     }
 }
@@ -198,6 +567,7 @@ impl SplitByShard for DeletePayloadOp {
             (Some(_), _) => {
                 split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
                     DeletePayloadOp {
+                        preconditions: preconditions_of_points(&self.preconditions, &points),
                         points: Some(points),
                         keys: self.keys.clone(),
                         filter: self.filter.clone(),
@@ -216,6 +586,7 @@ impl SplitByShard for SetPayloadOp {
             (Some(_), _) => {
                 split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
                     SetPayloadOp {
+                        preconditions: preconditions_of_points(&self.preconditions, &points),
                         points: Some(points),
                         payload: self.payload.clone(),
                         filter: self.filter.clone(),
@@ -229,13 +600,38 @@ impl SplitByShard for SetPayloadOp {
     }
 }
 
+impl SplitByShard for UpdatePayloadOp {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    UpdatePayloadOp {
+                        updates: self.updates.clone(),
+                        preconditions: preconditions_of_points(&self.preconditions, &points),
+                        points: Some(points),
+                        filter: self.filter.clone(),
+                        condition: self.condition.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use segment::types::{Payload, PayloadContainer};
-    use serde_json::Value;
+    use serde_json::{json, Value};
 
+    use super::PayloadUpdateOperator::*;
     use super::*;
 
+    fn float(value: f64) -> Number {
+        Number::from_f64(value).unwrap()
+    }
+
     #[derive(Debug, Deserialize, Serialize)]
     pub struct TextSelector {
         pub points: Vec<PointIdType>,
@@ -258,6 +654,74 @@ mod tests {
         assert_eq!(obj1.points, obj2.points.unwrap());
     }
 
+    #[test]
+    fn test_payload_update_operators() {
+        let apply = |payload: Value, key: &str, operator: PayloadUpdateOperator| {
+            let mut payload: Payload = serde_json::from_value(payload).unwrap();
+            let update = PayloadUpdate {
+                key: key.parse().unwrap(),
+                operator,
+            };
+            update
+                .apply(&mut payload)
+                .map(|is_changed| (is_changed, serde_json::to_value(payload).unwrap()))
+        };
+
+        // Integers stay integers, missing values are treated as 0
+        let (_, payload) = apply(json!({"a": 1}), "a", Increment(2.into())).unwrap();
+        assert_eq!(payload, json!({"a": 3}));
+        let (_, payload) = apply(json!({}), "a.b", Increment(2.into())).unwrap();
+        assert_eq!(payload, json!({"a": {"b": 2}}));
+        let (_, payload) = apply(json!({"a": 1}), "a", Increment(float(0.5))).unwrap();
+        assert_eq!(payload, json!({"a": 1.5}));
+        assert!(apply(json!({"a": i64::MAX}), "a", Increment(1.into())).is_err());
+        assert!(apply(json!({"a": "1"}), "a", Increment(1.into())).is_err());
+        assert!(apply(json!({"a": 1}), "a.b", Increment(1.into())).is_err());
+
+        let (is_changed, _) = apply(json!({"a": 1}), "a", Min(2.into())).unwrap();
+        assert!(!is_changed);
+        let (_, payload) = apply(json!({"a": 1}), "a", Min(float(0.5))).unwrap();
+        assert_eq!(payload, json!({"a": 0.5}));
+        let (_, payload) = apply(json!({"a": 1}), "a", Max(2.into())).unwrap();
+        assert_eq!(payload, json!({"a": 2}));
+        let (_, payload) = apply(json!({"a": null}), "a", Max(2.into())).unwrap();
+        assert_eq!(payload, json!({"a": 2}));
+
+        let (_, payload) = apply(json!({"a": [1]}), "a", Push(1.into())).unwrap();
+        assert_eq!(payload, json!({"a": [1, 1]}));
+        let (_, payload) = apply(json!({}), "a", Push(1.into())).unwrap();
+        assert_eq!(payload, json!({"a": [1]}));
+        assert!(apply(json!({"a": 1}), "a", Push(1.into())).is_err());
+
+        let (is_changed, _) = apply(json!({"a": [1, 2]}), "a", AddToSet(1.into())).unwrap();
+        assert!(!is_changed);
+        let (_, payload) = apply(json!({"a": [1, 2]}), "a", AddToSet(3.into())).unwrap();
+        assert_eq!(payload, json!({"a": [1, 2, 3]}));
+
+        let (_, payload) = apply(json!({"a": [1, 2, 1]}), "a", Pull(1.into())).unwrap();
+        assert_eq!(payload, json!({"a": [2]}));
+        let (is_changed, payload) = apply(json!({}), "a", Pull(1.into())).unwrap();
+        assert!(!is_changed);
+        assert_eq!(payload, json!({}));
+
+        let (is_changed, _) = apply(json!({"a": "x"}), "a", Set("x".into())).unwrap();
+        assert!(!is_changed);
+        let (_, payload) = apply(json!({"a": "x"}), "a", Set(json!({"b": 1}))).unwrap();
+        assert_eq!(payload, json!({"a": {"b": 1}}));
+    }
+
+    #[test]
+    fn test_payload_update_validation() {
+        let update: PayloadUpdate =
+            serde_json::from_str(r#"{"key": "a.b", "operator": {"increment": 1}}"#).unwrap();
+        assert!(update.validate().is_ok());
+        assert_eq!(update.operator, Increment(1.into()));
+
+        let update: PayloadUpdate =
+            serde_json::from_str(r#"{"key": "a[0].b", "operator": {"increment": 1}}"#).unwrap();
+        assert!(update.validate().is_err());
+    }
+
     #[test]
     fn test_serialization() {
         let query1 = r#"
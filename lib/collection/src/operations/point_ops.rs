@@ -1,14 +1,14 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use itertools::izip;
 use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{BatchVectorStruct, Vector, VectorStruct, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType};
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::{point_to_shard, split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRing;
@@ -56,6 +56,7 @@ impl TryFrom<Record> for PointStruct {
             payload,
             vector,
             shard_key: _,
+            order_values: _,
         } = record;
 
         if vector.is_none() {
@@ -102,6 +103,11 @@ pub struct PointIdsList {
     pub points: Vec<PointIdType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only update points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 impl From<Vec<PointIdType>> for PointIdsList {
@@ -109,10 +115,113 @@ impl From<Vec<PointIdType>> for PointIdsList {
         Self {
             points,
             shard_key: None,
+            preconditions: vec![],
         }
     }
 }
 
+/// Precondition on the current state of a point, which must hold for the point to be updated
+///
+/// Version of a point is the sequential number of the last operation, which changed it.
+/// It is reported as `operation_id` of the update result and as `version` of the scored point.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PointPrecondition {
+    /// Id of the point to check the precondition for
+    pub id: PointIdType,
+    /// Only update the point, if its current version is exactly this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// Only update the point, if it does not exist yet
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub if_not_exists: bool,
+}
+
+impl PointPrecondition {
+    /// Check precondition against the current version of the point, `None` if it doesn't exist
+    pub fn check(&self, version: Option<SeqNumberType>) -> bool {
+        if self.if_not_exists && version.is_some() {
+            return false;
+        }
+        self.if_version
+            .map_or(true, |if_version| version == Some(if_version))
+    }
+}
+
+/// Each precondition must specify exactly one requirement, at most one precondition per point
+pub fn validate_point_preconditions(
+    preconditions: &[PointPrecondition],
+) -> Result<(), ValidationError> {
+    let mut ids = HashSet::with_capacity(preconditions.len());
+    for precondition in preconditions {
+        let message = match (precondition.if_version, precondition.if_not_exists) {
+            (Some(_), true) => Some(format!(
+                "precondition of point {} can't require both `if_version` and `if_not_exists`",
+                precondition.id,
+            )),
+            (None, false) => Some(format!(
+                "precondition of point {} must specify `if_version` or `if_not_exists`",
+                precondition.id,
+            )),
+            _ if !ids.insert(precondition.id) => Some(format!(
+                "multiple preconditions specified for point {}",
+                precondition.id,
+            )),
+            _ => None,
+        };
+        if let Some(message) = message {
+            let mut error = ValidationError::new("preconditions");
+            error.message.replace(Cow::from(message));
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Preconditions can only be specified for the points, explicitly listed in the operation
+pub fn validate_preconditions_points(
+    preconditions: &[PointPrecondition],
+    points: Option<&[PointIdType]>,
+) -> Result<(), ValidationError> {
+    if preconditions.is_empty() {
+        return Ok(());
+    }
+    validate_point_preconditions(preconditions)?;
+
+    let points: HashSet<_> = points.unwrap_or_default().iter().collect();
+    let Some(precondition) = preconditions.iter().find(|p| !points.contains(&p.id)) else {
+        return Ok(());
+    };
+    let mut error = ValidationError::new("preconditions");
+    error.message.replace(Cow::from(format!(
+        "precondition specified for point {}, which is not listed in the operation",
+        precondition.id,
+    )));
+    Err(error)
+}
+
+pub(crate) fn preconditions_validation_errors(error: ValidationError) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("preconditions", error);
+    errors
+}
+
+/// Select preconditions of the given points, used to split conditional operations between shards
+pub fn preconditions_of_points(
+    preconditions: &[PointPrecondition],
+    points: &[PointIdType],
+) -> Vec<PointPrecondition> {
+    if preconditions.is_empty() {
+        return vec![];
+    }
+    let points: HashSet<_> = points.iter().collect();
+    preconditions
+        .iter()
+        .filter(|precondition| points.contains(&precondition.id))
+        .cloned()
+        .collect()
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct FilterSelector {
@@ -154,6 +263,11 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only upsert points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -162,6 +276,11 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only upsert points, which satisfy these preconditions.
+    /// Points with failed preconditions are skipped and reported in the result
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_point_preconditions")]
+    pub preconditions: Vec<PointPrecondition>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
@@ -183,10 +302,20 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
+    pub fn decompose(
+        self,
+    ) -> (
+        Option<ShardKeySelector>,
+        PointInsertOperationsInternal,
+        Vec<PointPrecondition>,
+    ) {
         match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
+            PointInsertOperations::PointsBatch(batch) => {
+                (batch.shard_key, batch.batch.into(), batch.preconditions)
+            }
+            PointInsertOperations::PointsList(list) => {
+                (list.shard_key, list.points.into(), list.preconditions)
+            }
         }
     }
 }
@@ -202,6 +331,44 @@ pub enum PointInsertOperationsInternal {
     PointsList(Vec<PointStruct>),
 }
 
+impl PointInsertOperationsInternal {
+    pub fn point_ids(&self) -> Vec<PointIdType> {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.clone(),
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().map(|point| point.id).collect()
+            }
+        }
+    }
+
+    pub fn into_point_vec(self) -> Vec<PointStruct> {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => {
+                let all_vectors = batch.vectors.into_all_vectors(batch.ids.len());
+                let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+                match batch.payloads {
+                    None => vectors_iter
+                        .map(|(id, vectors)| PointStruct {
+                            id,
+                            vector: vectors.into(),
+                            payload: None,
+                        })
+                        .collect(),
+                    Some(payloads) => vectors_iter
+                        .zip(payloads)
+                        .map(|((id, vectors), payload)| PointStruct {
+                            id,
+                            vector: vectors.into(),
+                            payload,
+                        })
+                        .collect(),
+                }
+            }
+            PointInsertOperationsInternal::PointsList(points) => points,
+        }
+    }
+}
+
 impl Validate for PointInsertOperationsInternal {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -280,6 +447,7 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            preconditions: vec![],
         })
     }
 }
@@ -289,6 +457,7 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            preconditions: vec![],
         })
     }
 }
@@ -305,13 +474,47 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Upsert of points, which is only applied to the points satisfying the preconditions
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ConditionalInsertOperationInternal {
+    pub points_op: PointInsertOperationsInternal,
+    pub preconditions: Vec<PointPrecondition>,
+}
+
+impl Validate for ConditionalInsertOperationInternal {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.points_op.validate()?;
+        validate_preconditions_points(&self.preconditions, Some(&self.points_op.point_ids()))
+            .map_err(preconditions_validation_errors)
+    }
+}
+
+impl SplitByShard for ConditionalInsertOperationInternal {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        let preconditions = self.preconditions;
+        self.points_op.split_by_shard(ring).map(|points_op| {
+            let preconditions = preconditions_of_points(&preconditions, &points_op.point_ids());
+            ConditionalInsertOperationInternal {
+                points_op,
+                preconditions,
+            }
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert or update points, which satisfy the preconditions
+    UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
-    DeletePoints { ids: Vec<PointIdType> },
+    DeletePoints {
+        ids: Vec<PointIdType>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        preconditions: Vec<PointPrecondition>,
+    },
     /// Delete points by given filter criteria
     DeletePointsByFilter(Filter),
     /// Points Sync
@@ -322,18 +525,63 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsConditional(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
         }
     }
+
+    pub fn preconditions(&self) -> &[PointPrecondition] {
+        match self {
+            PointOperations::UpsertPointsConditional(operation) => &operation.preconditions,
+            PointOperations::DeletePoints { preconditions, .. } => preconditions,
+            PointOperations::UpsertPoints(_)
+            | PointOperations::DeletePointsByFilter(_)
+            | PointOperations::SyncPoints(_) => &[],
+        }
+    }
+
+    pub fn exclude_points(&mut self, excluded: &HashSet<PointIdType>) {
+        match self {
+            PointOperations::UpsertPoints(points_op)
+            | PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                ..
+            }) => {
+                let mut points = std::mem::replace(points_op, Vec::new().into()).into_point_vec();
+                points.retain(|point| !excluded.contains(&point.id));
+                *points_op = points.into();
+            }
+            PointOperations::DeletePoints { ids, .. } => ids.retain(|id| !excluded.contains(id)),
+            PointOperations::DeletePointsByFilter(_) | PointOperations::SyncPoints(_) => {}
+        }
+    }
+
+    /// Drop preconditions, making the operation unconditional
+    pub fn clear_preconditions(&mut self) {
+        match self {
+            PointOperations::UpsertPointsConditional(operation) => {
+                let points_op = std::mem::replace(&mut operation.points_op, Vec::new().into());
+                *self = PointOperations::UpsertPoints(points_op);
+            }
+            PointOperations::DeletePoints { preconditions, .. } => preconditions.clear(),
+            PointOperations::UpsertPoints(_)
+            | PointOperations::DeletePointsByFilter(_)
+            | PointOperations::SyncPoints(_) => {}
+        }
+    }
 }
 
 impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
-            PointOperations::DeletePoints { ids: _ } => Ok(()),
+            PointOperations::UpsertPointsConditional(upsert_points) => upsert_points.validate(),
+            PointOperations::DeletePoints { ids, preconditions } => {
+                validate_preconditions_points(preconditions, Some(ids))
+                    .map_err(preconditions_validation_errors)
+            }
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
         }
@@ -458,8 +706,15 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
-            PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
-                .map(|ids| PointOperations::DeletePoints { ids }),
+            PointOperations::UpsertPointsConditional(upsert_points) => upsert_points
+                .split_by_shard(ring)
+                .map(PointOperations::UpsertPointsConditional),
+            PointOperations::DeletePoints { ids, preconditions } => {
+                split_iter_by_shard(ids, |id| *id, ring).map(|ids| {
+                    let preconditions = preconditions_of_points(&preconditions, &ids);
+                    PointOperations::DeletePoints { ids, preconditions }
+                })
+            }
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
                 OperationToShard::to_all(by_filter)
             }
@@ -532,4 +787,51 @@ mod tests {
         .into();
         assert!(batch.validate().is_err());
     }
+
+    #[test]
+    fn check_point_precondition() {
+        let if_version = PointPrecondition {
+            id: 1.into(),
+            if_version: Some(10),
+            if_not_exists: false,
+        };
+        assert!(if_version.check(Some(10)));
+        assert!(!if_version.check(Some(11)));
+        assert!(!if_version.check(None));
+
+        let if_not_exists = PointPrecondition {
+            id: 1.into(),
+            if_version: None,
+            if_not_exists: true,
+        };
+        assert!(if_not_exists.check(None));
+        assert!(!if_not_exists.check(Some(10)));
+    }
+
+    #[test]
+    fn validate_preconditions() {
+        let precondition =
+            |id: u64, if_version: Option<SeqNumberType>, if_not_exists: bool| PointPrecondition {
+                id: id.into(),
+                if_version,
+                if_not_exists,
+            };
+
+        assert!(validate_point_preconditions(&[precondition(1, Some(1), false)]).is_ok());
+        assert!(validate_point_preconditions(&[precondition(1, None, true)]).is_ok());
+        assert!(validate_point_preconditions(&[precondition(1, Some(1), true)]).is_err());
+        assert!(validate_point_preconditions(&[precondition(1, None, false)]).is_err());
+        assert!(validate_point_preconditions(&[
+            precondition(1, Some(1), false),
+            precondition(1, None, true),
+        ])
+        .is_err());
+
+        let points = [1.into(), 2.into()];
+        let preconditions = [precondition(2, Some(1), false)];
+        assert!(validate_preconditions_points(&preconditions, Some(&points)).is_ok());
+        assert!(validate_preconditions_points(&preconditions, Some(&points[..1])).is_err());
+        assert!(validate_preconditions_points(&preconditions, None).is_err());
+        assert!(validate_preconditions_points(&[], None).is_ok());
+    }
 }
@@ -0,0 +1,611 @@
+use merge::Merge;
+use schemars::JsonSchema;
+use segment::json_path::{JsonPath, JsonPathInterface};
+use segment::types::{Condition, Filter, Payload, SearchParams};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::config_diff::DiffConfig;
+use super::payload_ops::PayloadOps;
+use super::point_ops::{
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+};
+use super::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CountRequestInternal,
+    DiscoverRequestInternal, FacetRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
+    SearchMatrixRequestInternal, SearchRequestInternal,
+};
+use super::universal_query::shard_query::{ShardPrefetch, ShardQueryRequest};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::grouping::group_by::{GroupRequest, SourceRequest};
+
+/// Limits of requests to the collection.
+///
+/// Requests, which exceed any of the limits, are rejected before execution.
+/// Limits are only applied if strict mode is enabled.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Merge, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    /// Max allowed `limit` parameter for all APIs that don't have their own max limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_query_limit: Option<usize>,
+
+    /// Max allowed `offset` parameter of paginated requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_query_offset: Option<usize>,
+
+    /// Max allowed `timeout` parameter, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_timeout: Option<usize>,
+
+    /// Allow usage of unindexed fields in retrieval based (e.g. search) filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_retrieve: Option<bool>,
+
+    /// Allow usage of unindexed fields in filtered updates (e.g. delete by payload)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_update: Option<bool>,
+
+    /// Max HNSW `ef` value allowed in search parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_max_hnsw_ef: Option<usize>,
+
+    /// Whether exact search is allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_allow_exact: Option<bool>,
+
+    /// Max number of requests in a single batch search request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub search_max_batch_size: Option<usize>,
+
+    /// Max number of points in a single upsert or vector update request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub upsert_max_batch_size: Option<usize>,
+
+    /// Max size of a payload of a single point in upsert and set payload requests, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub upsert_max_payload_bytes: Option<usize>,
+
+    /// Max number of conditions in a filter, including nested ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_filter_conditions: Option<usize>,
+}
+
+impl DiffConfig<StrictModeConfig> for StrictModeConfig {}
+
+impl StrictModeConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled == Some(true)
+    }
+}
+
+/// Check a value of the request against the limit of the config
+fn check_limit(value: usize, limit: Option<usize>, name: &str) -> CollectionResult<()> {
+    match limit {
+        Some(limit) if value > limit => Err(CollectionError::strict_mode(format!(
+            "Value of {name} ({value}) exceeds the limit of {limit}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Number of conditions in the filter, including conditions of nested filters
+fn count_conditions(filter: &Filter) -> usize {
+    filter_conditions(filter)
+        .map(|condition| match condition {
+            Condition::Nested(nested) => 1 + count_conditions(&nested.nested.filter),
+            Condition::Filter(filter) => 1 + count_conditions(filter),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::CustomIdChecker(_) => 1,
+        })
+        .sum()
+}
+
+fn filter_conditions(filter: &Filter) -> impl Iterator<Item = &Condition> {
+    let min_should = filter
+        .min_should
+        .as_ref()
+        .map(|min_should| &min_should.conditions);
+    filter
+        .should
+        .iter()
+        .chain(min_should)
+        .chain(&filter.must)
+        .chain(&filter.must_not)
+        .flatten()
+}
+
+/// Find a payload key of the filter, which has no payload index
+fn find_unindexed_key(
+    filter: &Filter,
+    nested_prefix: Option<&JsonPath>,
+    schema: &PayloadIndexSchema,
+) -> Option<JsonPath> {
+    filter_conditions(filter).find_map(|condition| {
+        let key = match condition {
+            Condition::Field(field) => &field.key,
+            Condition::IsEmpty(is_empty) => &is_empty.is_empty.key,
+            Condition::IsNull(is_null) => &is_null.is_null.key,
+            Condition::HasId(_) | Condition::CustomIdChecker(_) => return None,
+            Condition::Nested(nested) => {
+                let prefix = JsonPath::extend_or_new(nested_prefix, &nested.nested.key.array_key());
+                return find_unindexed_key(&nested.nested.filter, Some(&prefix), schema);
+            }
+            Condition::Filter(filter) => {
+                return find_unindexed_key(filter, nested_prefix, schema);
+            }
+        };
+        let full_key = JsonPath::extend_or_new(nested_prefix, key);
+        (!schema.schema.contains_key(&full_key)).then_some(full_key)
+    })
+}
+
+fn check_filter(
+    filter: &Filter,
+    allow_unindexed: Option<bool>,
+    config: &StrictModeConfig,
+    schema: &PayloadIndexSchema,
+) -> CollectionResult<()> {
+    check_limit(
+        count_conditions(filter),
+        config.max_filter_conditions,
+        "filter conditions",
+    )?;
+
+    if allow_unindexed == Some(false) {
+        if let Some(key) = find_unindexed_key(filter, None, schema) {
+            return Err(CollectionError::strict_mode(format!(
+                "Index required but not found for \"{key}\". Create a payload index for this field"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_payload_size(payload: &Payload, config: &StrictModeConfig) -> CollectionResult<()> {
+    let Some(max_bytes) = config.upsert_max_payload_bytes else {
+        return Ok(());
+    };
+    let size = serde_json::to_vec(payload)?.len();
+    if size > max_bytes {
+        return Err(CollectionError::strict_mode(format!(
+            "Payload of {size} bytes exceeds the limit of {max_bytes} bytes"
+        )));
+    }
+    Ok(())
+}
+
+/// Request, which can be verified against the strict mode config of the collection
+pub trait StrictModeVerification {
+    /// Limit of the request, if any
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// Offset of the request, if any
+    fn query_offset(&self) -> Option<usize> {
+        None
+    }
+
+    /// Filter of a read request
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    /// Filter of an update request
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    /// Search params of the request
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+
+    /// Additional checks, specific to the request
+    fn check_custom(
+        &self,
+        _config: &StrictModeConfig,
+        _schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    fn check_strict_mode(
+        &self,
+        config: &StrictModeConfig,
+        schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        if let Some(limit) = self.query_limit() {
+            check_limit(limit, config.max_query_limit, "limit")?;
+        }
+        if let Some(offset) = self.query_offset() {
+            check_limit(offset, config.max_query_offset, "offset")?;
+        }
+
+        if let Some(filter) = self.indexed_filter_read() {
+            check_filter(filter, config.unindexed_filtering_retrieve, config, schema)?;
+        }
+        if let Some(filter) = self.indexed_filter_write() {
+            check_filter(filter, config.unindexed_filtering_update, config, schema)?;
+        }
+
+        if let Some(params) = self.request_search_params() {
+            if let Some(hnsw_ef) = params.hnsw_ef {
+                check_limit(hnsw_ef, config.search_max_hnsw_ef, "hnsw_ef")?;
+            }
+            if params.exact && config.search_allow_exact == Some(false) {
+                return Err(CollectionError::strict_mode("Exact search is not allowed"));
+            }
+        }
+
+        self.check_custom(config, schema)
+    }
+}
+
+impl StrictModeVerification for CoreSearchRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+}
+
+impl StrictModeVerification for SearchRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+}
+
+impl StrictModeVerification for RecommendRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+}
+
+impl StrictModeVerification for DiscoverRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+}
+
+impl StrictModeVerification for ShardPrefetch {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+
+    fn check_custom(
+        &self,
+        config: &StrictModeConfig,
+        schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        self.prefetches
+            .iter()
+            .try_for_each(|prefetch| prefetch.check_strict_mode(config, schema))
+    }
+}
+
+impl StrictModeVerification for ShardQueryRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn query_offset(&self) -> Option<usize> {
+        Some(self.offset)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        self.params.as_ref()
+    }
+
+    fn check_custom(
+        &self,
+        config: &StrictModeConfig,
+        schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        self.prefetches
+            .iter()
+            .try_for_each(|prefetch| prefetch.check_strict_mode(config, schema))
+    }
+}
+
+impl StrictModeVerification for CountRequestInternal {
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for FacetRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for SearchMatrixRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.sample())
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for ScrollRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+}
+
+impl StrictModeVerification for GroupRequest {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn check_custom(
+        &self,
+        config: &StrictModeConfig,
+        schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        match &self.source {
+            SourceRequest::Search(search) => search.check_strict_mode(config, schema),
+            SourceRequest::Recommend(recommend) => recommend.check_strict_mode(config, schema),
+        }
+    }
+}
+
+impl StrictModeVerification for CollectionUpdateOperations {
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        match self {
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+                filter,
+            ))
+            | CollectionUpdateOperations::PayloadOperation(PayloadOps::ClearPayloadByFilter(
+                filter,
+            ))
+            | CollectionUpdateOperations::VectorOperation(
+                VectorOperations::DeleteVectorsByFilter(filter, _),
+            ) => Some(filter),
+            CollectionUpdateOperations::PayloadOperation(
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op),
+            ) => op.filter.as_ref(),
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(op)) => {
+                op.filter.as_ref()
+            }
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::UpdatePayload(op)) => {
+                op.filter.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    fn check_custom(
+        &self,
+        config: &StrictModeConfig,
+        _schema: &PayloadIndexSchema,
+    ) -> CollectionResult<()> {
+        match self {
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(batch))
+                | PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsBatch(batch),
+                    ..
+                }),
+            ) => {
+                check_limit(batch.ids.len(), config.upsert_max_batch_size, "batch size")?;
+                batch
+                    .payloads
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .try_for_each(|payload| check_payload_size(payload, config))
+            }
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points))
+                | PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(points),
+                    ..
+                }),
+            ) => {
+                check_limit(points.len(), config.upsert_max_batch_size, "batch size")?;
+                points
+                    .iter()
+                    .filter_map(|point| point.payload.as_ref())
+                    .try_for_each(|payload| check_payload_size(payload, config))
+            }
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(op)) => {
+                check_limit(op.points.len(), config.upsert_max_batch_size, "batch size")
+            }
+            CollectionUpdateOperations::PayloadOperation(
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op),
+            ) => check_payload_size(&op.payload, config),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, PayloadFieldSchema, PayloadSchemaType};
+
+    use super::*;
+
+    fn match_condition(key: &str) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            key.parse().unwrap(),
+            "a".to_string().into(),
+        ))
+    }
+
+    fn strict_config() -> StrictModeConfig {
+        StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: Some(10),
+            unindexed_filtering_retrieve: Some(false),
+            max_filter_conditions: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unindexed_filtering() {
+        let mut schema = PayloadIndexSchema::default();
+        schema.schema.insert(
+            "country.cities[].name".parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        );
+        let config = strict_config();
+
+        let nested = Filter::new_must(Condition::new_nested(
+            "country.cities".parse().unwrap(),
+            Filter::new_must(match_condition("name")),
+        ));
+        let mut request = CountRequestInternal {
+            filter: Some(nested),
+            exact: true,
+        };
+        request.check_strict_mode(&config, &schema).unwrap();
+
+        request.filter = Some(Filter::new_must(match_condition("country.name")));
+        assert!(matches!(
+            request.check_strict_mode(&config, &schema),
+            Err(CollectionError::StrictMode { .. }),
+        ));
+
+        let relaxed = StrictModeConfig {
+            unindexed_filtering_retrieve: None,
+            ..strict_config()
+        };
+        request.check_strict_mode(&relaxed, &schema).unwrap();
+    }
+
+    #[test]
+    fn test_limits() {
+        let schema = PayloadIndexSchema::default();
+        let config = StrictModeConfig {
+            unindexed_filtering_retrieve: None,
+            ..strict_config()
+        };
+
+        let mut request = ScrollRequestInternal {
+            offset: None,
+            limit: Some(10),
+            filter: None,
+            with_payload: None,
+            with_vector: Default::default(),
+            order_by: None,
+            cursor: None,
+        };
+        request.check_strict_mode(&config, &schema).unwrap();
+
+        request.limit = Some(11);
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+
+        request.limit = Some(1);
+        request.filter = Some(Filter {
+            should: Some(vec![match_condition("a"), match_condition("b")]),
+            min_should: None,
+            must: Some(vec![Condition::Filter(Filter::new_must(match_condition(
+                "c",
+            )))]),
+            must_not: None,
+        });
+        assert!(request.check_strict_mode(&config, &schema).is_err());
+    }
+
+    #[test]
+    fn test_update_config() {
+        let config = strict_config();
+        let diff = StrictModeConfig {
+            max_query_limit: Some(100),
+            ..Default::default()
+        };
+        let updated = diff.update(&config).unwrap();
+        assert_eq!(updated.enabled, Some(true));
+        assert_eq!(updated.max_query_limit, Some(100));
+        assert_eq!(updated.max_filter_conditions, Some(3));
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{Condition, FieldCondition, Filter, Payload, Range};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+
+use super::point_ops::{
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+};
+use super::CollectionUpdateOperations;
+
+/// Default payload field, which holds the expiration time of the point
+pub const DEFAULT_EXPIRE_AT_FIELD: &str = "expire_at";
+
+/// Expiration of points.
+///
+/// Point expires once the time in its `expire_at_field` payload field, in RFC 3339 format,
+/// is passed. Expired points are excluded from search, scroll and count right away, and are
+/// deleted in the background afterwards. The expiration field gets a `datetime` payload index,
+/// unless it is indexed already.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct TtlConfig {
+    /// Time to live of points, which are inserted without expiration time, in seconds.
+    /// If not set, such points never expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub default_ttl_sec: Option<u64>,
+    /// Top-level payload field, which holds the expiration time of the point.
+    /// Default: `expire_at`
+    #[serde(default = "default_expire_at_field")]
+    #[validate(custom = "validate_expire_at_field")]
+    pub expire_at_field: JsonPath,
+}
+
+fn default_expire_at_field() -> JsonPath {
+    DEFAULT_EXPIRE_AT_FIELD.parse().unwrap()
+}
+
+fn validate_expire_at_field(field: &JsonPath) -> Result<(), ValidationError> {
+    if field.rest.is_empty() {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("expire_at_field");
+        error.message = Some("expiration time must be stored in a top-level field".into());
+        Err(error)
+    }
+}
+
+impl TtlConfig {
+    /// Condition, which matches points expired at the moment `now`
+    fn expired_condition(&self, now: DateTime<Utc>) -> Condition {
+        Condition::Field(FieldCondition::new_datetime_range(
+            self.expire_at_field.clone(),
+            Range {
+                lt: None,
+                gt: None,
+                gte: None,
+                lte: Some(now.into()),
+            },
+        ))
+    }
+
+    /// Filter, which matches points expired at the moment `now`
+    pub fn expired_filter(&self, now: DateTime<Utc>) -> Filter {
+        Filter::new_must(self.expired_condition(now))
+    }
+
+    /// Extend the filter to exclude points expired at the moment `now`
+    pub fn exclude_expired(&self, filter: Option<&Filter>, now: DateTime<Utc>) -> Filter {
+        let not_expired = Filter::new_must_not(self.expired_condition(now));
+        match filter {
+            Some(filter) => filter.merge(&not_expired),
+            None => not_expired,
+        }
+    }
+
+    /// Set expiration time of upserted points, which don't have it yet, according to the default TTL.
+    ///
+    /// Must be applied once, before the operation is sent to shards, so all replicas store
+    /// the same expiration time.
+    pub fn set_default_expiration(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+        now: DateTime<Utc>,
+    ) {
+        let Some(default_ttl_sec) = self.default_ttl_sec else {
+            return;
+        };
+        let insert = match operation {
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(insert)
+                | PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: insert,
+                    ..
+                }),
+            ) => insert,
+            _ => return,
+        };
+
+        let expire_at = now + Duration::seconds(default_ttl_sec as i64);
+        let expire_at = Value::String(expire_at.to_rfc3339());
+        let expire_at_key = &self.expire_at_field.first_key;
+        let set_expiration = |payload: &mut Option<Payload>| {
+            let payload = payload.get_or_insert_with(|| Payload(Default::default()));
+            if !payload.contains_key(expire_at_key) {
+                payload.0.insert(expire_at_key.clone(), expire_at.clone());
+            }
+        };
+
+        match insert {
+            PointInsertOperationsInternal::PointsBatch(batch) => {
+                let payloads = batch
+                    .payloads
+                    .get_or_insert_with(|| vec![None; batch.ids.len()]);
+                payloads.iter_mut().for_each(set_expiration);
+            }
+            PointInsertOperationsInternal::PointsList(points) => {
+                for point in points {
+                    set_expiration(&mut point.payload);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::PayloadContainer;
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::point_ops::PointStruct;
+
+    #[test]
+    fn test_set_default_expiration() {
+        let now = Utc::now();
+        let explicit_expiration = json!({ "valid_until": "2000-01-01T00:00:00Z" });
+        let points = vec![
+            PointStruct {
+                id: 1.into(),
+                vector: vec![1.0].into(),
+                payload: None,
+            },
+            PointStruct {
+                id: 2.into(),
+                vector: vec![1.0].into(),
+                payload: Some(explicit_expiration.clone().into()),
+            },
+        ];
+        let mut operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+        );
+
+        let config = TtlConfig {
+            default_ttl_sec: Some(60),
+            expire_at_field: "valid_until".parse().unwrap(),
+        };
+        config.set_default_expiration(&mut operation, now);
+
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        )) = operation
+        else {
+            panic!("operation type must not change");
+        };
+
+        let expected = (now + Duration::seconds(60)).to_rfc3339();
+        let default_expiration = points[0].payload.as_ref().unwrap();
+        assert_eq!(
+            default_expiration
+                .get_value(&config.expire_at_field)
+                .to_vec(),
+            vec![&Value::String(expected)],
+        );
+        // Explicit expiration time is kept
+        assert_eq!(points[1].payload, Some(explicit_expiration.into()));
+    }
+}
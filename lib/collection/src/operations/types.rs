@@ -14,25 +14,30 @@ use issues::IssueRecord;
 use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
-use segment::common::operation_error::OperationError;
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::data_types::facets::{FacetCounts, FacetParams, FacetValueHit};
 use segment::data_types::groups::GroupId;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::{OrderBy, OrderValues};
+use segment::data_types::sparse_statistics::SparseVectorStatistics;
 use segment::data_types::vectors::{
-    DenseVector, Named, NamedQuery, NamedVectorStruct, QueryVector, Vector, VectorRef,
-    VectorStruct, DEFAULT_VECTOR_NAME,
+    DenseVector, MultiDenseVector, Named, NamedQuery, NamedVectorStruct, QueryVector, Vector,
+    VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
 };
 use segment::json_path::{JsonPath, JsonPathInterface};
 use segment::types::{
-    Distance, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
-    ScoredPoint, SearchParams, SeqNumberType, ShardKey, WithPayloadInterface, WithVector,
+    Distance, Filter, FloatPayloadType, MultiVectorConfig, Payload, PayloadIndexInfo,
+    PayloadKeyType, PointIdType, QuantizationConfig, Range, ScoredPoint, SearchParams,
+    SeqNumberType, ShardKey, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use segment::vector_storage::query::context_query::ContextQuery;
 use segment::vector_storage::query::discovery_query::DiscoveryQuery;
 use segment::vector_storage::query::reco_query::RecoQuery;
+use segment::vector_storage::query::TransformInto;
 use serde;
 use serde::{Deserialize, Serialize};
 use serde_json::Error as JsonError;
 use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::oneshot::error::RecvError as OneshotRecvError;
@@ -48,6 +53,7 @@ use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
 use crate::operations::shard_key_selector::ShardKeySelector;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::{ReshardStage, ReshardingDirection};
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::ShardTransferMethod;
 use crate::wal::WalError;
@@ -97,6 +103,9 @@ pub struct Record {
     /// Shard Key
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKey>,
+    /// Values of the `order_by` keys, if the points are ordered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_values: Option<OrderValues>,
 }
 
 /// Current statistics and configuration of the collection
@@ -198,12 +207,21 @@ pub struct CollectionClusterInfo {
     pub remote_shards: Vec<RemoteShardInfo>,
     /// Shard transfers
     pub shard_transfers: Vec<ShardTransferInfo>,
+    /// Resharding operations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resharding_operations: Vec<ReshardingInfo>,
 }
 
 #[derive(Debug, Serialize, JsonSchema, Clone)]
 pub struct ShardTransferInfo {
     pub shard_id: ShardId,
 
+    /// Target shard ID if different than source shard ID
+    ///
+    /// Used exclusively with `ReshardingStreamRecords` transfer method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_shard_id: Option<ShardId>,
+
     /// Source peer id
     pub from: PeerId,
 
@@ -222,6 +240,26 @@ pub struct ShardTransferInfo {
     pub comment: Option<String>,
 }
 
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct ReshardingInfo {
+    pub direction: ReshardingDirection,
+
+    /// Shard which is added or removed
+    pub shard_id: ShardId,
+
+    /// Peer which drives the resharding operation
+    pub peer_id: PeerId,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+
+    pub stage: ReshardStage,
+
+    /// A human-readable report of the operation progress
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
 #[derive(Debug, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct LocalShardInfo {
@@ -276,6 +314,21 @@ pub struct UpdateResult {
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
+
+    /// Points, which were skipped because their preconditions didn't hold
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_preconditions: Vec<FailedPrecondition>,
+}
+
+/// Point, which was not updated because its precondition didn't hold
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FailedPrecondition {
+    /// Point id
+    pub id: PointIdType,
+    /// Current version of the point, absent if the point doesn't exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<SeqNumberType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -294,17 +347,20 @@ pub struct ScrollRequest {
 pub enum OrderByInterface {
     Key(JsonPath),
     Struct(OrderBy),
+    /// Order by several keys, each key breaks ties of the previous ones
+    Multi(Vec<OrderBy>),
 }
 
-impl From<OrderByInterface> for OrderBy {
+impl From<OrderByInterface> for Vec<OrderBy> {
     fn from(order_by: OrderByInterface) -> Self {
         match order_by {
-            OrderByInterface::Key(key) => OrderBy {
+            OrderByInterface::Key(key) => vec![OrderBy {
                 key,
                 direction: None,
                 start_from: None,
-            },
-            OrderByInterface::Struct(order_by) => order_by,
+            }],
+            OrderByInterface::Struct(order_by) => vec![order_by],
+            OrderByInterface::Multi(keys) => keys,
         }
     }
 }
@@ -331,8 +387,13 @@ pub struct ScrollRequestInternal {
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
 
-    /// Order the records by a payload field.
+    /// Order the records by a payload field, or by a list of fields.
     pub order_by: Option<OrderByInterface>,
+
+    /// Continue scrolling with `order_by` after the last point of a previous page.
+    /// Use `next_page_cursor` of the previous result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
 }
 
 impl Default for ScrollRequestInternal {
@@ -344,6 +405,7 @@ impl Default for ScrollRequestInternal {
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(false),
             order_by: None,
+            cursor: None,
         }
     }
 }
@@ -356,6 +418,9 @@ pub struct ScrollResult {
     pub points: Vec<Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Cursor which should be used to retrieve a next page result with `order_by`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -402,6 +467,43 @@ pub struct SearchRequestInternal {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// Rerank results to make them diverse, using Maximal Marginal Relevance
+    #[validate]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmr: Option<Mmr>,
+}
+
+pub const DEFAULT_MMR_LAMBDA: f32 = 0.5;
+pub const DEFAULT_MMR_CANDIDATES_LIMIT: usize = 100;
+
+/// Maximal Marginal Relevance reranking.
+///
+/// Results are picked one by one from the best candidates, each next result is the one with
+/// the best balance of its relevance and dissimilarity to the results picked before it.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Mmr {
+    /// Balance between relevance and diversity of results, from 0 to 1.
+    /// `1` only takes relevance into account, `0` only diversity. Default is 0.5.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub lambda: Option<f32>,
+    /// Number of best candidates to pick results from. Default is 100.
+    /// At least `limit + offset` candidates are always considered.
+    #[validate(range(min = 1))]
+    pub candidates_limit: Option<usize>,
+}
+
+impl Mmr {
+    pub fn lambda(&self) -> f32 {
+        self.lambda.unwrap_or(DEFAULT_MMR_LAMBDA)
+    }
+
+    /// Number of candidates to pick `limit` results from
+    pub fn candidates_limit(&self, limit: usize) -> usize {
+        self.candidates_limit
+            .unwrap_or(DEFAULT_MMR_CANDIDATES_LIMIT)
+            .max(limit)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -428,6 +530,55 @@ impl QueryEnum {
             QueryEnum::Context(context_query) => context_query.get_name(),
         }
     }
+
+    /// Dimensions of all sparse vectors used in the query
+    pub fn sparse_dimensions(&self) -> Vec<DimId> {
+        let vectors: Box<dyn Iterator<Item = &Vector>> = match self {
+            QueryEnum::Nearest(NamedVectorStruct::Sparse(named)) => {
+                return named.vector.indices.clone();
+            }
+            QueryEnum::Nearest(_) => return Vec::new(),
+            QueryEnum::RecommendBestScore(named) => Box::new(named.query.flat_iter()),
+            QueryEnum::Discover(named) => Box::new(named.query.flat_iter()),
+            QueryEnum::Context(named) => Box::new(named.query.flat_iter()),
+        };
+        vectors
+            .filter_map(|vector| match vector {
+                Vector::Sparse(sparse) => Some(sparse.indices.iter().copied()),
+                Vector::Dense(_) | Vector::MultiDense(_) => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Apply `f` to every sparse vector used in the query
+    pub fn transform_sparse(self, f: impl Fn(&mut SparseVector)) -> OperationResult<Self> {
+        let transform = |mut vector: Vector| {
+            if let Vector::Sparse(sparse) = &mut vector {
+                f(sparse);
+            }
+            Ok(vector)
+        };
+        Ok(match self {
+            QueryEnum::Nearest(NamedVectorStruct::Sparse(mut named)) => {
+                f(&mut named.vector);
+                QueryEnum::Nearest(NamedVectorStruct::Sparse(named))
+            }
+            QueryEnum::Nearest(vector) => QueryEnum::Nearest(vector),
+            QueryEnum::RecommendBestScore(named) => QueryEnum::RecommendBestScore(NamedQuery {
+                query: named.query.transform(transform)?,
+                using: named.using,
+            }),
+            QueryEnum::Discover(named) => QueryEnum::Discover(NamedQuery {
+                query: named.query.transform(transform)?,
+                using: named.using,
+            }),
+            QueryEnum::Context(named) => QueryEnum::Context(NamedQuery {
+                query: named.query.transform(transform)?,
+                using: named.using,
+            }),
+        })
+    }
 }
 
 impl From<DenseVector> for QueryEnum {
@@ -467,6 +618,9 @@ pub struct CoreSearchRequest {
     /// Whether to return the point vector with the result?
     pub with_vector: Option<WithVector>,
     pub score_threshold: Option<ScoreType>,
+    /// Diversity reranking, applied by the collection to merged results of all shards.
+    /// Shards never receive it.
+    pub mmr: Option<Mmr>,
 }
 
 #[derive(Debug, Clone)]
@@ -544,6 +698,7 @@ pub enum RecommendExample {
     PointId(PointIdType),
     Dense(DenseVector),
     Sparse(SparseVector),
+    MultiDense(MultiDenseVector),
 }
 
 impl RecommendExample {
@@ -561,6 +716,7 @@ impl Validate for RecommendExample {
             RecommendExample::PointId(_) => Ok(()),
             RecommendExample::Dense(_) => Ok(()),
             RecommendExample::Sparse(sparse) => sparse.validate(),
+            RecommendExample::MultiDense(multi) => multi.validate(),
         }
     }
 }
@@ -690,6 +846,11 @@ pub struct RecommendRequestInternal {
     /// Note: the other collection should have the same vector size as the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Rerank results to make them diverse, using Maximal Marginal Relevance
+    #[validate]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmr: Option<Mmr>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -902,6 +1063,274 @@ pub struct CountResult {
     pub count: usize,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub facet_request: FacetRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Facet Request
+/// Counts the number of points for each value of the given payload key, among the points
+/// which satisfy the filter.
+/// If `ranges` are given, counts the number of points in each of the ranges instead.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Validate, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequestInternal {
+    /// Payload key to count values of.
+    /// Requires a keyword, integer, bool or uuid index, or a range index to count `ranges`.
+    pub key: JsonPath,
+    /// Max number of values to return, the values with the most points are returned. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// If true, count exact number of points for each value.
+    /// If false, estimate the counts faster from the payload index. Default: false
+    #[serde(default)]
+    pub exact: bool,
+    /// Numeric ranges to count points in, instead of counting individual values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranges: Option<Vec<Range<FloatPayloadType>>>,
+}
+
+pub const DEFAULT_FACET_LIMIT: usize = 10;
+
+impl FacetRequestInternal {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_FACET_LIMIT)
+    }
+
+    pub fn facet_params(&self, filter: Option<Filter>) -> FacetParams {
+        FacetParams {
+            key: self.key.clone(),
+            filter,
+            exact: self.exact,
+            ranges: self.ranges.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRangeHit {
+    pub range: Range<FloatPayloadType>,
+    /// Number of points with at least one value in the range
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetResponse {
+    /// Values with the most points, ordered by the number of points
+    pub hits: Vec<FacetValueHit>,
+    /// Number of points in each of the requested ranges, in the same order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ranges: Vec<FacetRangeHit>,
+}
+
+impl FacetResponse {
+    /// Build response with `request.limit` values, which have the most points
+    pub fn from_counts(counts: &FacetCounts, request: &FacetRequestInternal) -> Self {
+        Self::with_hits_limit(counts, request, request.limit())
+    }
+
+    /// Build response with counts of all values, so responses of multiple shards can be merged
+    /// before picking the values with the most points
+    pub fn with_all_values(counts: &FacetCounts, request: &FacetRequestInternal) -> Self {
+        Self::with_hits_limit(counts, request, usize::MAX)
+    }
+
+    fn with_hits_limit(counts: &FacetCounts, request: &FacetRequestInternal, limit: usize) -> Self {
+        let ranges = request
+            .ranges
+            .iter()
+            .flatten()
+            .zip(counts.ranges.iter().copied().chain(iter::repeat(0)))
+            .map(|(range, count)| FacetRangeHit {
+                range: range.clone(),
+                count,
+            })
+            .collect();
+        Self {
+            hits: counts.top_hits(limit),
+            ranges,
+        }
+    }
+
+    /// Convert back into counts, to merge responses of multiple shards
+    pub fn into_counts(self) -> FacetCounts {
+        FacetCounts {
+            values: self
+                .hits
+                .into_iter()
+                .map(|hit| (hit.value, hit.count))
+                .collect(),
+            ranges: self.ranges.into_iter().map(|hit| hit.count).collect(),
+        }
+    }
+}
+
+/// Request to pick random points, used internally to build a distance matrix
+#[derive(Debug, Clone)]
+pub struct SampleRequestInternal {
+    /// Only sample points which satisfy this filter
+    pub filter: Option<Filter>,
+    /// Max number of points to sample
+    pub limit: usize,
+    pub with_payload: WithPayloadInterface,
+    pub with_vector: WithVector,
+}
+
+/// Point, picked by random sampling of a shard
+#[derive(Debug, Clone)]
+pub struct SampledRecord {
+    pub record: Record,
+    /// Sampling key, points with the smallest keys among all shards make a uniform sample
+    pub key: ScoreType,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub search_request: SearchMatrixRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Search Matrix Request
+/// Samples points which satisfy the filter, or takes the given ids, and finds the nearest
+/// neighbours of each of them among the same set of points.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequestInternal {
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// How many points to select and search within. Default: 10
+    #[validate(range(min = 2))]
+    pub sample: Option<usize>,
+    /// Use these points instead of sampling. Missing points are skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<PointIdType>>,
+    /// How many neighbours per sample to find.
+    /// Default: all other points of the sample, which gives the full pairwise matrix
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+}
+
+pub const DEFAULT_SEARCH_MATRIX_SAMPLE: usize = 10;
+
+impl SearchMatrixRequestInternal {
+    pub fn sample(&self) -> usize {
+        match &self.ids {
+            Some(ids) => ids.len(),
+            None => self.sample.unwrap_or(DEFAULT_SEARCH_MATRIX_SAMPLE),
+        }
+    }
+
+    pub fn using(&self) -> &str {
+        self.using.as_deref().unwrap_or(DEFAULT_VECTOR_NAME)
+    }
+}
+
+/// Sampled points and the nearest neighbours of each of them, ordered by score
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionSearchMatrixResponse {
+    pub sample_ids: Vec<PointIdType>,
+    pub nearests: Vec<Vec<ScoredPoint>>,
+}
+
+/// Pair of points (a, b) with score
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPair {
+    pub a: PointIdType,
+    pub b: PointIdType,
+    pub score: ScoreType,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPairsResponse {
+    /// List of pairs of points with scores
+    pub pairs: Vec<SearchMatrixPair>,
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let pairs = response
+            .sample_ids
+            .into_iter()
+            .zip(response.nearests)
+            .flat_map(|(a, nearests)| {
+                nearests.into_iter().map(move |nearest| SearchMatrixPair {
+                    a,
+                    b: nearest.id,
+                    score: nearest.score,
+                })
+            })
+            .collect();
+        Self { pairs }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixOffsetsResponse {
+    /// Row indices of the matrix
+    pub offsets_row: Vec<u64>,
+    /// Column indices of the matrix
+    pub offsets_col: Vec<u64>,
+    /// Scores associated with matrix coordinates
+    pub scores: Vec<ScoreType>,
+    /// Ids of the points in order
+    pub ids: Vec<PointIdType>,
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixOffsetsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let offsets: HashMap<_, _> = response
+            .sample_ids
+            .iter()
+            .enumerate()
+            .map(|(offset, id)| (*id, offset as u64))
+            .collect();
+
+        let mut offsets_row = Vec::new();
+        let mut offsets_col = Vec::new();
+        let mut scores = Vec::new();
+        for (row, nearests) in response.nearests.into_iter().enumerate() {
+            for nearest in nearests {
+                // Neighbours are always found among the sampled points
+                let Some(&col) = offsets.get(&nearest.id) else {
+                    continue;
+                };
+                offsets_row.push(row as u64);
+                offsets_col.push(col);
+                scores.push(nearest.score);
+            }
+        }
+
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: response.sample_ids,
+        }
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -936,6 +1365,10 @@ pub enum CollectionError {
     OutOfMemory { description: String, free: u64 },
     #[error("Timeout error: {description}")]
     Timeout { description: String },
+    #[error("Strict mode error: {description}")]
+    StrictMode { description: String },
+    #[error("Position truncated: {description}")]
+    PositionTruncated { description: String },
 }
 
 impl CollectionError {
@@ -967,6 +1400,25 @@ impl CollectionError {
         CollectionError::BadShardSelection { description }
     }
 
+    pub fn strict_mode(description: impl Into<String>) -> CollectionError {
+        CollectionError::StrictMode {
+            description: description.into(),
+        }
+    }
+
+    pub fn position_truncated(
+        peer_id: PeerId,
+        clock_id: u32,
+        read_tick: u64,
+        first_available: u64,
+    ) -> CollectionError {
+        CollectionError::PositionTruncated {
+            description: format!(
+                "operations of clock {peer_id}:{clock_id} after tick {read_tick} are not available anymore, the oldest available tick is {first_available}"
+            ),
+        }
+    }
+
     pub fn forward_proxy_error(peer_id: PeerId, error: impl Into<Self>) -> Self {
         Self::ForwardProxyError {
             peer_id,
@@ -1009,6 +1461,8 @@ impl CollectionError {
             Self::BadShardSelection { .. } => false,
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
+            Self::StrictMode { .. } => false,
+            Self::PositionTruncated { .. } => false,
         }
     }
 }
@@ -1073,7 +1527,15 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongSparse => Self::BadInput {
                 description: "Conversion between sparse and regular vectors failed".to_string(),
             },
+            OperationError::WrongMulti => Self::BadInput {
+                description: "Conversion between multi and regular vectors failed".to_string(),
+            },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
+            OperationError::VariableTypeError { .. } | OperationError::NonFiniteNumber { .. } => {
+                Self::BadInput {
+                    description: format!("{err}"),
+                }
+            }
         }
     }
 }
@@ -1172,6 +1634,12 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::Cancelled => CollectionError::Cancelled {
                 description: format!("{err}"),
             },
+            tonic::Code::OutOfRange => CollectionError::PositionTruncated {
+                description: err
+                    .message()
+                    .trim_start_matches("Position truncated: ")
+                    .to_string(),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1275,6 +1743,7 @@ impl Record {
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_multivector_params"))]
 pub struct VectorParams {
     /// Size of a vectors used
     #[validate(custom = "validate_nonzerou64_range_min_1_max_65536")]
@@ -1297,6 +1766,40 @@ pub struct VectorParams {
     /// Default: false
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// If set, each point holds multiple vectors of `size` dimension under this name,
+    /// e.g. token embeddings for late interaction models. Multi-vectors are always stored in RAM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Element type of stored vectors. Smaller types reduce memory and disk usage at the cost of
+    /// precision. Default: `float32`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+}
+
+/// Multi-vectors can only be stored in RAM, as `float32`, and without quantization.
+fn validate_multivector_params(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.multivector_config.is_none() {
+        return Ok(());
+    }
+    if params
+        .datatype
+        .is_some_and(|datatype| datatype != VectorStorageDatatype::Float32)
+    {
+        return Err(ValidationError::new(
+            "multi-vectors only support the float32 datatype",
+        ));
+    }
+    if params.on_disk == Some(true) {
+        return Err(ValidationError::new(
+            "multi-vectors can't be stored on disk",
+        ));
+    }
+    if params.quantization_config.is_some() {
+        return Err(ValidationError::new(
+            "multi-vectors don't support quantization",
+        ));
+    }
+    Ok(())
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1327,16 +1830,65 @@ pub struct SparseVectorParams {
     /// Custom params for index. If none - values from collection configuration are used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub index: Option<SparseIndexParams>,
+    /// Configures additional value modifications for sparse vectors.
+    /// Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<Modifier>,
+}
+
+impl SparseVectorParams {
+    /// Whether query weights should be rescaled with IDF statistics of the collection
+    pub fn is_idf(&self) -> bool {
+        self.modifier == Some(Modifier::Idf)
+    }
 }
 
 impl Anonymize for SparseVectorParams {
     fn anonymize(&self) -> Self {
         Self {
             index: self.index.anonymize(),
+            modifier: self.modifier,
         }
     }
 }
 
+/// Modifier of sparse vector weights, applied at query time
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Modifier {
+    /// Weights are used as is
+    #[default]
+    None,
+    /// Query weights are multiplied by the inverse document frequency (IDF) of their dimensions.
+    /// IDF is computed from statistics of all vectors in the collection, e.g. for BM25 retrieval.
+    Idf,
+}
+
+/// Request for statistics of sparse vectors, required to compute IDF
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseStatisticsRequest {
+    /// Dimensions to collect statistics for, per sparse vector name
+    pub vectors: HashMap<String, Vec<DimId>>,
+}
+
+impl SparseStatisticsRequest {
+    /// Empty statistics for all requested dimensions
+    pub fn empty_statistics(&self) -> SparseStatistics {
+        self.vectors
+            .iter()
+            .map(|(name, dims)| {
+                (
+                    name.clone(),
+                    SparseVectorStatistics::for_dimensions(dims.iter().copied()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Statistics of sparse vectors, per sparse vector name
+pub type SparseStatistics = HashMap<String, SparseVectorStatistics>;
+
 /// Configuration for sparse inverted index.
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1349,6 +1901,16 @@ pub struct SparseIndexParams {
     /// Store index on disk. If set to false, the index will be stored in RAM. Default: false
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Datatype used to store weights in the index.
+    /// Weights of reduced precision are scaled into the range of each posting list,
+    /// which reduces memory usage at the cost of search precision.
+    /// Applies to optimized segments only. Default: float32
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+    /// Keep full-precision weights on disk, to rescore results of the index with reduced
+    /// precision weights. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_precision_on_disk: Option<bool>,
 }
 
 impl Anonymize for SparseIndexParams {
@@ -1356,6 +1918,8 @@ impl Anonymize for SparseIndexParams {
         SparseIndexParams {
             full_scan_threshold: self.full_scan_threshold,
             on_disk: self.on_disk,
+            datatype: self.datatype,
+            full_precision_on_disk: self.full_precision_on_disk,
         }
     }
 }
@@ -1365,6 +1929,8 @@ impl SparseIndexParams {
         SparseIndexParams {
             full_scan_threshold,
             on_disk,
+            datatype: None,
+            full_precision_on_disk: None,
         }
     }
 
@@ -1375,6 +1941,12 @@ impl SparseIndexParams {
         if let Some(on_disk) = other.on_disk {
             self.on_disk = Some(on_disk);
         }
+        if let Some(datatype) = other.datatype {
+            self.datatype = Some(datatype);
+        }
+        if let Some(full_precision_on_disk) = other.full_precision_on_disk {
+            self.full_precision_on_disk = Some(full_precision_on_disk);
+        }
     }
 }
 
@@ -1674,6 +2246,35 @@ impl VectorsConfigDiff {
         }
         Ok(())
     }
+
+    /// Check that this config doesn't enable on-disk storage or quantization of multi-vectors of
+    /// the given collection.
+    pub fn check_multivector_params(&self, collection: &CollectionParams) -> CollectionResult<()> {
+        for (vector_name, params) in &self.0 {
+            let is_multivector = collection
+                .vectors
+                .get_params(vector_name)
+                .is_some_and(|params| params.multivector_config.is_some());
+            if !is_multivector {
+                continue;
+            }
+            if params.on_disk == Some(true) {
+                return Err(CollectionError::bad_input(format!(
+                    "Multi-vector {vector_name} can't be stored on disk",
+                )));
+            }
+            if params
+                .quantization_config
+                .as_ref()
+                .is_some_and(|config| !matches!(config, QuantizationConfigDiff::Disabled(_)))
+            {
+                return Err(CollectionError::bad_input(format!(
+                    "Multi-vector {vector_name} doesn't support quantization",
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Validate for VectorsConfigDiff {
@@ -1771,6 +2372,7 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
+            mmr: request.mmr,
         }
     }
 }
@@ -0,0 +1,407 @@
+use common::types::ScoreType;
+use schemars::JsonSchema;
+use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
+use segment::types::{Filter, SearchParams, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationErrors};
+
+use super::formula::FormulaQuery;
+use super::shard_query::{
+    Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest, ShardSample,
+};
+use crate::operations::shard_key_selector::ShardKeySelector;
+use crate::operations::types::{CollectionError, CollectionResult, QueryEnum};
+
+pub const DEFAULT_QUERY_LIMIT: usize = 10;
+
+const fn default_query_limit() -> usize {
+    DEFAULT_QUERY_LIMIT
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct QueryRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub internal: QueryRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Query points with optional multi-stage prefetching.
+///
+/// Prefetches are performed first, their results are candidates for the `query`, which either
+/// re-scores them with another vector or fuses them into a single result.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct QueryRequestInternal {
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[validate]
+    #[serde(default)]
+    pub prefetch: Vec<Prefetch>,
+    /// Query to perform. If missing, results of the only prefetch are returned.
+    #[validate]
+    pub query: Option<QueryInterface>,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    /// Applied to prefetches as well.
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Search params for when there is no prefetch
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    pub score_threshold: Option<ScoreType>,
+    /// Max number of points to return. Default is 10.
+    #[validate(range(min = 1))]
+    #[serde(default = "default_query_limit")]
+    pub limit: usize,
+    /// Offset of the result. Skip this many points. Default is 0
+    #[serde(default)]
+    pub offset: usize,
+    /// Options for specifying which vectors to include into the response. Default is false.
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+    /// Options for specifying which payload to include or not. Default is false.
+    pub with_payload: Option<WithPayloadInterface>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct Prefetch {
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[validate]
+    #[serde(default)]
+    pub prefetch: Vec<Prefetch>,
+    /// Query to perform on this stage
+    #[validate]
+    pub query: QueryInterface,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Search params for when there is no prefetch
+    #[validate]
+    pub params: Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    pub score_threshold: Option<ScoreType>,
+    /// Max number of points to return from this stage. Default is 10.
+    #[validate(range(min = 1))]
+    #[serde(default = "default_query_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(untagged)]
+pub enum QueryInterface {
+    /// Shorthand for the nearest neighbors query
+    Nearest(Vector),
+    Query(Query),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Query {
+    /// Find the nearest neighbors to this vector
+    Nearest(Vector),
+    /// Fuse results of prefetches
+    Fusion(Fusion),
+    /// Sample points instead of scoring them. If there are prefetches, samples their results.
+    Sample(SampleInterface),
+    /// Score results of prefetches with an expression, which may use their scores and payload values
+    Formula(FormulaQuery),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(untagged)]
+pub enum SampleInterface {
+    /// Shorthand for the sample with a random seed
+    Method(Sample),
+    Params(SampleParams),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleParams {
+    /// Method of sampling points
+    pub method: Sample,
+    /// Seed of the sampling. The same seed gives the same points while data is unchanged.
+    /// If missing, a random seed is used.
+    pub seed: Option<u64>,
+}
+
+impl From<SampleInterface> for ShardSample {
+    fn from(value: SampleInterface) -> Self {
+        let (method, seed) = match value {
+            SampleInterface::Method(method) => (method, None),
+            SampleInterface::Params(SampleParams { method, seed }) => (method, seed),
+        };
+        // Seed is chosen once, so all shards sample with the same one
+        ShardSample {
+            method,
+            seed: seed.unwrap_or_else(rand::random),
+        }
+    }
+}
+
+impl From<QueryInterface> for Query {
+    fn from(value: QueryInterface) -> Self {
+        match value {
+            QueryInterface::Nearest(vector) => Query::Nearest(vector),
+            QueryInterface::Query(query) => query,
+        }
+    }
+}
+
+impl Validate for QueryInterface {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            QueryInterface::Nearest(vector) | QueryInterface::Query(Query::Nearest(vector)) => {
+                vector.validate()
+            }
+            QueryInterface::Query(Query::Fusion(_) | Query::Sample(_) | Query::Formula(_)) => {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn merge_filters(parent: Option<&Filter>, own: Option<Filter>) -> Option<Filter> {
+    match (parent, own) {
+        (Some(parent), Some(own)) => Some(parent.merge(&own)),
+        (Some(parent), None) => Some(parent.clone()),
+        (None, own) => own,
+    }
+}
+
+/// Convert the query of a stage, checking that it is compatible with its prefetches
+fn scoring_query(
+    query: Query,
+    using: Option<String>,
+    has_prefetches: bool,
+) -> CollectionResult<ScoringQuery> {
+    match query {
+        Query::Nearest(vector) => {
+            let using = using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+            Ok(ScoringQuery::Vector(QueryEnum::Nearest(
+                NamedVectorStruct::new_from_vector(vector, using),
+            )))
+        }
+        Query::Fusion(fusion) => {
+            if !has_prefetches {
+                return Err(CollectionError::bad_request(
+                    "Fusion query requires at least one prefetch".to_string(),
+                ));
+            }
+            Ok(ScoringQuery::Fusion(fusion))
+        }
+        Query::Sample(sample) => Ok(ScoringQuery::Sample(sample.into())),
+        Query::Formula(formula) => {
+            if !has_prefetches {
+                return Err(CollectionError::bad_request(
+                    "Formula query requires at least one prefetch".to_string(),
+                ));
+            }
+            Ok(ScoringQuery::Formula(ParsedFormula::try_from(formula)?))
+        }
+    }
+}
+
+impl Prefetch {
+    fn into_shard_prefetch(
+        self,
+        parent_filter: Option<&Filter>,
+    ) -> CollectionResult<ShardPrefetch> {
+        let Prefetch {
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            limit,
+        } = self;
+
+        let filter = merge_filters(parent_filter, filter);
+        let query = scoring_query(query.into(), using, !prefetch.is_empty())?;
+        let prefetches = prefetch
+            .into_iter()
+            .map(|prefetch| prefetch.into_shard_prefetch(filter.as_ref()))
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(ShardPrefetch {
+            prefetches,
+            query,
+            limit,
+            params,
+            filter,
+            score_threshold,
+        })
+    }
+}
+
+impl TryFrom<QueryRequestInternal> for ShardQueryRequest {
+    type Error = CollectionError;
+
+    fn try_from(request: QueryRequestInternal) -> CollectionResult<Self> {
+        let QueryRequestInternal {
+            prefetch,
+            query,
+            using,
+            filter,
+            params,
+            score_threshold,
+            limit,
+            offset,
+            with_vector,
+            with_payload,
+        } = request;
+
+        let query = match query {
+            Some(query) => Some(scoring_query(query.into(), using, !prefetch.is_empty())?),
+            None if prefetch.len() == 1 => None,
+            None if prefetch.is_empty() => {
+                return Err(CollectionError::bad_request(
+                    "Either `query` or `prefetch` must be specified".to_string(),
+                ))
+            }
+            None => {
+                return Err(CollectionError::bad_request(
+                    "Query is required to combine several prefetches, e.g. fusion".to_string(),
+                ))
+            }
+        };
+
+        let prefetches = prefetch
+            .into_iter()
+            .map(|prefetch| prefetch.into_shard_prefetch(filter.as_ref()))
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(ShardQueryRequest {
+            prefetches,
+            query,
+            filter,
+            score_threshold,
+            limit,
+            offset,
+            params,
+            with_vector: with_vector.unwrap_or_default(),
+            with_payload: with_payload.unwrap_or(WithPayloadInterface::Bool(false)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_request() {
+        let request: QueryRequestInternal = serde_json::from_str(
+            r#"{
+                "prefetch": [
+                    { "query": [0.1, 0.2], "using": "dense", "limit": 20 },
+                    { "query": { "indices": [1, 5], "values": [0.5, 0.3] }, "using": "sparse" }
+                ],
+                "query": { "fusion": "rrf" },
+                "filter": { "must": [{ "key": "city", "match": { "value": "Berlin" } }] }
+            }"#,
+        )
+        .unwrap();
+
+        let request = ShardQueryRequest::try_from(request).unwrap();
+        assert!(request.has_intermediate_results());
+        assert_eq!(request.limit, DEFAULT_QUERY_LIMIT);
+        assert_eq!(request.prefetches.len(), 2);
+        assert_eq!(request.prefetches[0].limit, 20);
+        // Root filter is propagated to prefetches
+        assert!(request
+            .prefetches
+            .iter()
+            .all(|prefetch| prefetch.filter.is_some()));
+
+        let ScoringQuery::Vector(query) = &request.prefetches[1].query else {
+            panic!("prefetch must be a vector query");
+        };
+        assert_eq!(query.get_vector_name(), "sparse");
+    }
+
+    #[test]
+    fn test_parse_sample_query() {
+        let request: QueryRequestInternal = serde_json::from_str(
+            r#"{
+                "query": { "sample": { "method": "random", "seed": 42 } },
+                "filter": { "must": [{ "key": "city", "match": { "value": "Berlin" } }] }
+            }"#,
+        )
+        .unwrap();
+        let request = ShardQueryRequest::try_from(request).unwrap();
+        assert!(!request.has_intermediate_results());
+        let Some(ScoringQuery::Sample(sample)) = request.query else {
+            panic!("query must be a sample");
+        };
+        assert_eq!(
+            sample,
+            ShardSample {
+                method: Sample::Random,
+                seed: 42,
+            }
+        );
+
+        // Sample without prefetches is allowed, unlike fusion
+        let shorthand: QueryRequestInternal =
+            serde_json::from_str(r#"{ "query": { "sample": "random" } }"#).unwrap();
+        let request = ShardQueryRequest::try_from(shorthand).unwrap();
+        assert!(matches!(request.query, Some(ScoringQuery::Sample(_))));
+    }
+
+    #[test]
+    fn test_parse_formula_query() {
+        let request: QueryRequestInternal = serde_json::from_str(
+            r#"{
+                "prefetch": [{ "query": [0.1, 0.2], "limit": 50 }],
+                "query": { "formula": {
+                    "expression": { "sum": ["$score", { "mult": [0.1, "popularity"] }] },
+                    "defaults": { "popularity": 0 }
+                } }
+            }"#,
+        )
+        .unwrap();
+        let request = ShardQueryRequest::try_from(request).unwrap();
+        assert!(!request.has_intermediate_results());
+        let Some(ScoringQuery::Formula(formula)) = request.query else {
+            panic!("query must be a formula");
+        };
+        assert_eq!(formula.payload_vars.len(), 1);
+        assert_eq!(formula.defaults.len(), 1);
+
+        // Formula only rescores candidates of prefetches
+        let formula_without_prefetch: QueryRequestInternal =
+            serde_json::from_str(r#"{ "query": { "formula": { "expression": "$score" } } }"#)
+                .unwrap();
+        assert!(ShardQueryRequest::try_from(formula_without_prefetch).is_err());
+    }
+
+    #[test]
+    fn test_invalid_query_structure() {
+        let fusion_without_prefetch: QueryRequestInternal =
+            serde_json::from_str(r#"{ "query": { "fusion": "dbsf" } }"#).unwrap();
+        assert!(ShardQueryRequest::try_from(fusion_without_prefetch).is_err());
+
+        let empty: QueryRequestInternal = serde_json::from_str("{}").unwrap();
+        assert!(ShardQueryRequest::try_from(empty).is_err());
+
+        let sample_with_unknown_method =
+            serde_json::from_str::<QueryRequestInternal>(r#"{ "query": { "sample": "first" } }"#);
+        assert!(sample_with_unknown_method.is_err());
+
+        let several_prefetches_without_query: QueryRequestInternal =
+            serde_json::from_str(r#"{ "prefetch": [{ "query": [0.1] }, { "query": [0.2] }] }"#)
+                .unwrap();
+        assert!(ShardQueryRequest::try_from(several_prefetches_without_query).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use segment::index::query_optimization::rescore_formula::parsed_formula::{
+    DecayKind, ParsedExpression, ParsedFormula, PreciseScore, VariableId,
+};
+use segment::json_path::JsonPath;
+use segment::types::{Condition, DateTimePayloadType, GeoPoint};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+const MICROS_IN_SECOND: PreciseScore = 1_000_000.0;
+
+/// Score candidates of prefetches with an arbitrary expression
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FormulaQuery {
+    /// Expression to score each candidate with
+    pub expression: Expression,
+    /// Values of variables, which are used if a point has no value of its own.
+    /// Keys are payload keys or score references, e.g. `$score[1]`.
+    #[serde(default)]
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Expression {
+    Constant(PreciseScore),
+    /// Payload key, or score of the point in a prefetch: `$score` for the first prefetch,
+    /// `$score[<index>]` for any of them
+    Variable(String),
+    GeoDistance(GeoDistanceExpression),
+    Datetime(DatetimeExpression),
+    DatetimeKey(DatetimeKeyExpression),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Abs(AbsExpression),
+    Div(DivExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Log10(Log10Expression),
+    Ln(LnExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+    /// Payload condition, `1.0` if the point satisfies it, `0.0` otherwise
+    Condition(Box<Condition>),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct GeoDistanceExpression {
+    pub geo_distance: GeoDistanceParams,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct GeoDistanceParams {
+    /// Point to measure the distance from
+    pub origin: GeoPoint,
+    /// Payload key with the geo point to measure the distance to. Distance is in meters.
+    pub to: JsonPath,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DatetimeExpression {
+    /// RFC 3339 datetime, evaluated to seconds since UNIX epoch
+    pub datetime: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DatetimeKeyExpression {
+    /// Payload key with a datetime, evaluated to seconds since UNIX epoch
+    pub datetime_key: JsonPath,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct MultExpression {
+    pub mult: Vec<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct SumExpression {
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct NegExpression {
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct AbsExpression {
+    pub abs: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Result of the division by zero. If missing, division by zero fails the query.
+    pub by_zero_default: Option<PreciseScore>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct SqrtExpression {
+    pub sqrt: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PowExpression {
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PowParams {
+    pub base: Box<Expression>,
+    pub exponent: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ExpExpression {
+    pub exp: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Log10Expression {
+    pub log10: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LnExpression {
+    pub ln: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LinDecayExpression {
+    pub lin_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ExpDecayExpression {
+    pub exp_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct GaussDecayExpression {
+    pub gauss_decay: DecayParamsExpression,
+}
+
+/// Decay is `1.0` at the `target` and decreases with the distance from it
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct DecayParamsExpression {
+    /// Value to apply the decay to
+    pub x: Box<Expression>,
+    /// Value, at which the decay is the highest. Default is 0.
+    pub target: Option<Box<Expression>>,
+    /// Distance from the target, at which the decay equals to `midpoint`. Must be positive. Default is 1.
+    pub scale: Option<PreciseScore>,
+    /// Value of the decay at `scale` distance from the target. Must be between 0 and 1 exclusive. Default is 0.5.
+    pub midpoint: Option<PreciseScore>,
+}
+
+/// Collects everything, which the formula depends on, while parsing it
+#[derive(Default)]
+struct FormulaParser {
+    payload_vars: HashSet<JsonPath>,
+    conditions: Vec<Condition>,
+}
+
+fn parse_datetime(datetime: &str) -> CollectionResult<PreciseScore> {
+    let datetime = DateTimePayloadType::from_str(datetime).map_err(|_| {
+        CollectionError::bad_request(format!("Invalid datetime in formula: {datetime}"))
+    })?;
+    Ok(datetime.timestamp() as PreciseScore / MICROS_IN_SECOND)
+}
+
+impl FormulaParser {
+    fn parse_variable(&mut self, variable: &str) -> CollectionResult<VariableId> {
+        let variable = VariableId::from_str(variable).map_err(CollectionError::bad_request)?;
+        if let VariableId::Payload(key) = &variable {
+            self.payload_vars.insert(key.clone());
+        }
+        Ok(variable)
+    }
+
+    fn parse_boxed(&mut self, expression: Expression) -> CollectionResult<Box<ParsedExpression>> {
+        self.parse(expression).map(Box::new)
+    }
+
+    fn parse_all(
+        &mut self,
+        expressions: Vec<Expression>,
+    ) -> CollectionResult<Vec<ParsedExpression>> {
+        expressions
+            .into_iter()
+            .map(|expression| self.parse(expression))
+            .collect()
+    }
+
+    fn parse_decay(
+        &mut self,
+        kind: DecayKind,
+        params: DecayParamsExpression,
+    ) -> CollectionResult<ParsedExpression> {
+        let DecayParamsExpression {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = params;
+        let x = self.parse(*x)?;
+        let target = target.map(|target| self.parse(*target)).transpose()?;
+        ParsedExpression::new_decay(kind, x, target, scale, midpoint)
+            .map_err(CollectionError::bad_request)
+    }
+
+    fn parse(&mut self, expression: Expression) -> CollectionResult<ParsedExpression> {
+        let parsed = match expression {
+            Expression::Constant(value) => ParsedExpression::Constant(value),
+            Expression::Variable(variable) => {
+                ParsedExpression::Variable(self.parse_variable(&variable)?)
+            }
+            Expression::GeoDistance(GeoDistanceExpression {
+                geo_distance: GeoDistanceParams { origin, to },
+            }) => {
+                self.payload_vars.insert(to.clone());
+                ParsedExpression::GeoDistance { origin, key: to }
+            }
+            Expression::Datetime(DatetimeExpression { datetime }) => {
+                ParsedExpression::Constant(parse_datetime(&datetime)?)
+            }
+            Expression::DatetimeKey(DatetimeKeyExpression { datetime_key }) => {
+                self.payload_vars.insert(datetime_key.clone());
+                ParsedExpression::DatetimeKey(datetime_key)
+            }
+            Expression::Mult(MultExpression { mult }) => {
+                ParsedExpression::Mult(self.parse_all(mult)?)
+            }
+            Expression::Sum(SumExpression { sum }) => ParsedExpression::Sum(self.parse_all(sum)?),
+            Expression::Neg(NegExpression { neg }) => {
+                ParsedExpression::Neg(self.parse_boxed(*neg)?)
+            }
+            Expression::Abs(AbsExpression { abs }) => {
+                ParsedExpression::Abs(self.parse_boxed(*abs)?)
+            }
+            Expression::Div(DivExpression {
+                div:
+                    DivParams {
+                        left,
+                        right,
+                        by_zero_default,
+                    },
+            }) => ParsedExpression::Div {
+                left: self.parse_boxed(*left)?,
+                right: self.parse_boxed(*right)?,
+                by_zero_default,
+            },
+            Expression::Sqrt(SqrtExpression { sqrt }) => {
+                ParsedExpression::Sqrt(self.parse_boxed(*sqrt)?)
+            }
+            Expression::Pow(PowExpression {
+                pow: PowParams { base, exponent },
+            }) => ParsedExpression::Pow {
+                base: self.parse_boxed(*base)?,
+                exponent: self.parse_boxed(*exponent)?,
+            },
+            Expression::Exp(ExpExpression { exp }) => {
+                ParsedExpression::Exp(self.parse_boxed(*exp)?)
+            }
+            Expression::Log10(Log10Expression { log10 }) => {
+                ParsedExpression::Log10(self.parse_boxed(*log10)?)
+            }
+            Expression::Ln(LnExpression { ln }) => ParsedExpression::Ln(self.parse_boxed(*ln)?),
+            Expression::LinDecay(LinDecayExpression { lin_decay }) => {
+                self.parse_decay(DecayKind::Lin, lin_decay)?
+            }
+            Expression::ExpDecay(ExpDecayExpression { exp_decay }) => {
+                self.parse_decay(DecayKind::Exp, exp_decay)?
+            }
+            Expression::GaussDecay(GaussDecayExpression { gauss_decay }) => {
+                self.parse_decay(DecayKind::Gauss, gauss_decay)?
+            }
+            Expression::Condition(condition) => {
+                self.conditions.push(*condition);
+                ParsedExpression::Variable(VariableId::Condition(self.conditions.len() - 1))
+            }
+        };
+        Ok(parsed)
+    }
+}
+
+impl TryFrom<FormulaQuery> for ParsedFormula {
+    type Error = CollectionError;
+
+    fn try_from(value: FormulaQuery) -> CollectionResult<Self> {
+        let FormulaQuery {
+            expression,
+            defaults,
+        } = value;
+
+        let mut parser = FormulaParser::default();
+        let formula = parser.parse(expression)?;
+        let defaults = defaults
+            .into_iter()
+            .map(|(variable, value)| {
+                let variable =
+                    VariableId::from_str(&variable).map_err(CollectionError::bad_request)?;
+                Ok((variable, value))
+            })
+            .collect::<CollectionResult<_>>()?;
+
+        let FormulaParser {
+            payload_vars,
+            conditions,
+        } = parser;
+        Ok(ParsedFormula {
+            payload_vars,
+            conditions,
+            defaults,
+            formula,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formula() {
+        let formula: FormulaQuery = serde_json::from_str(
+            r#"{
+                "expression": {
+                    "sum": [
+                        { "mult": ["$score", 0.7] },
+                        { "exp_decay": {
+                            "x": { "datetime_key": "published_at" },
+                            "target": { "datetime": "2024-01-01T00:00:00Z" },
+                            "scale": 86400
+                        } },
+                        { "ln": "popularity" },
+                        { "mult": [0.5, { "key": "tag", "match": { "value": "news" } }] },
+                        { "neg": { "geo_distance": { "origin": { "lon": 0, "lat": 0 }, "to": "location" } } }
+                    ]
+                },
+                "defaults": { "popularity": 1, "$score[1]": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let parsed = ParsedFormula::try_from(formula).unwrap();
+        let payload_vars: HashSet<_> = ["published_at", "popularity", "location"]
+            .into_iter()
+            .map(|key| key.parse().unwrap())
+            .collect();
+        assert_eq!(parsed.payload_vars, payload_vars);
+        assert_eq!(parsed.conditions.len(), 1);
+        assert_eq!(parsed.defaults.len(), 2);
+        assert!(parsed.defaults.contains_key(&VariableId::Score(1)));
+
+        let ParsedExpression::Sum(terms) = &parsed.formula else {
+            panic!("formula must be a sum");
+        };
+        let ParsedExpression::Decay {
+            kind,
+            target,
+            scale,
+            ..
+        } = &terms[1]
+        else {
+            panic!("second term must be a decay");
+        };
+        assert_eq!(*kind, DecayKind::Exp);
+        assert_eq!(*scale, 86400.0);
+        assert_eq!(
+            target.as_deref(),
+            Some(&ParsedExpression::Constant(1704067200.0))
+        );
+        assert_eq!(
+            terms[3],
+            ParsedExpression::Mult(vec![
+                ParsedExpression::Constant(0.5),
+                ParsedExpression::Variable(VariableId::Condition(0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_formula() {
+        let invalid = [
+            r#"{ "expression": "$score[first]" }"#,
+            r#"{ "expression": { "datetime": "yesterday" } }"#,
+            r#"{ "expression": { "lin_decay": { "x": "$score", "scale": -1 } } }"#,
+            r#"{ "expression": { "gauss_decay": { "x": "$score", "midpoint": 1.5 } } }"#,
+            r#"{ "expression": "$score", "defaults": { "$score[]": 1 } }"#,
+        ];
+        for formula in invalid {
+            let formula: FormulaQuery = serde_json::from_str(formula).unwrap();
+            assert!(ParsedFormula::try_from(formula).is_err());
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod collection_query;
+pub mod formula;
+pub mod shard_query;
@@ -0,0 +1,175 @@
+use common::types::ScoreType;
+use schemars::JsonSchema;
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
+use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+
+use crate::config::CollectionParams;
+use crate::operations::types::{CollectionResult, QueryEnum};
+
+/// Fusion algorithm, which combines results of several prefetches into one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Fusion {
+    /// Reciprocal Rank Fusion, scores points by their ranks in each prefetch
+    Rrf,
+    /// Distribution-Based Score Fusion, sums scores normalized by their distribution in each prefetch
+    Dbsf,
+}
+
+/// Method of sampling points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    /// Uniformly random points
+    Random,
+}
+
+/// Sampling of points, the same seed gives the same points while data is unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardSample {
+    pub method: Sample,
+    pub seed: u64,
+}
+
+/// Way to score points at a stage of the query
+#[derive(Debug, Clone)]
+pub enum ScoringQuery {
+    /// Score points by similarity to the vector query
+    Vector(QueryEnum),
+    /// Combine results of prefetches
+    Fusion(Fusion),
+    /// Sample points of the shard, or candidates from prefetches if there are any.
+    /// Scores are sampling keys, points with smaller keys are picked first.
+    Sample(ShardSample),
+    /// Score candidates from prefetches with the formula
+    Formula(ParsedFormula),
+}
+
+impl ScoringQuery {
+    /// Which scores are better in results of this query
+    pub fn order(&self, collection_params: &CollectionParams) -> CollectionResult<Order> {
+        match self {
+            ScoringQuery::Vector(query @ QueryEnum::Nearest(_)) => Ok(collection_params
+                .get_distance(query.get_vector_name())?
+                .distance_order()),
+            ScoringQuery::Vector(
+                QueryEnum::RecommendBestScore(_) | QueryEnum::Discover(_) | QueryEnum::Context(_),
+            ) => Ok(Order::LargeBetter),
+            ScoringQuery::Fusion(_) => Ok(Order::LargeBetter),
+            ScoringQuery::Sample(_) => Ok(Order::SmallBetter),
+            ScoringQuery::Formula(_) => Ok(Order::LargeBetter),
+        }
+    }
+
+    fn transform_vector(
+        self,
+        f: &impl Fn(QueryEnum) -> CollectionResult<QueryEnum>,
+    ) -> CollectionResult<Self> {
+        match self {
+            ScoringQuery::Vector(query) => Ok(ScoringQuery::Vector(f(query)?)),
+            ScoringQuery::Fusion(fusion) => Ok(ScoringQuery::Fusion(fusion)),
+            ScoringQuery::Sample(sample) => Ok(ScoringQuery::Sample(sample)),
+            ScoringQuery::Formula(formula) => Ok(ScoringQuery::Formula(formula)),
+        }
+    }
+}
+
+/// Intermediate stage of the query, its results are candidates for the parent stage
+#[derive(Debug, Clone)]
+pub struct ShardPrefetch {
+    pub prefetches: Vec<ShardPrefetch>,
+    pub query: ScoringQuery,
+    pub limit: usize,
+    pub params: Option<SearchParams>,
+    pub filter: Option<Filter>,
+    pub score_threshold: Option<ScoreType>,
+}
+
+impl ShardPrefetch {
+    fn collect_vector_queries<'a>(&'a self, queries: &mut Vec<&'a QueryEnum>) {
+        if let ScoringQuery::Vector(query) = &self.query {
+            queries.push(query);
+        }
+        for prefetch in &self.prefetches {
+            prefetch.collect_vector_queries(queries);
+        }
+    }
+
+    fn transform_vector_queries(
+        self,
+        f: &impl Fn(QueryEnum) -> CollectionResult<QueryEnum>,
+    ) -> CollectionResult<Self> {
+        Ok(Self {
+            prefetches: self
+                .prefetches
+                .into_iter()
+                .map(|prefetch| prefetch.transform_vector_queries(f))
+                .collect::<CollectionResult<_>>()?,
+            query: self.query.transform_vector(f)?,
+            ..self
+        })
+    }
+}
+
+/// Query, executed on a single shard.
+///
+/// Stages form a tree: prefetches without children search the shard, other stages only
+/// score results of their prefetches. If the root query is a fusion or missing, shard returns
+/// results of each root prefetch separately, so they are combined once results of all shards
+/// are merged.
+#[derive(Debug, Clone)]
+pub struct ShardQueryRequest {
+    pub prefetches: Vec<ShardPrefetch>,
+    /// If `None`, results of the only prefetch are returned as is
+    pub query: Option<ScoringQuery>,
+    pub filter: Option<Filter>,
+    pub score_threshold: Option<ScoreType>,
+    pub limit: usize,
+    pub offset: usize,
+    pub params: Option<SearchParams>,
+    pub with_vector: WithVector,
+    pub with_payload: WithPayloadInterface,
+}
+
+impl ShardQueryRequest {
+    /// Whether shard responds with unmerged results of root prefetches
+    pub fn has_intermediate_results(&self) -> bool {
+        matches!(self.query, None | Some(ScoringQuery::Fusion(_)))
+    }
+
+    /// Vector queries of all stages
+    pub fn vector_queries(&self) -> Vec<&QueryEnum> {
+        let mut queries = Vec::new();
+        if let Some(ScoringQuery::Vector(query)) = &self.query {
+            queries.push(query);
+        }
+        for prefetch in &self.prefetches {
+            prefetch.collect_vector_queries(&mut queries);
+        }
+        queries
+    }
+
+    /// Apply `f` to vector queries of all stages
+    pub fn transform_vector_queries(
+        self,
+        f: impl Fn(QueryEnum) -> CollectionResult<QueryEnum>,
+    ) -> CollectionResult<Self> {
+        Ok(Self {
+            prefetches: self
+                .prefetches
+                .into_iter()
+                .map(|prefetch| prefetch.transform_vector_queries(&f))
+                .collect::<CollectionResult<_>>()?,
+            query: self
+                .query
+                .map(|query| query.transform_vector(&f))
+                .transpose()?,
+            ..self
+        })
+    }
+}
+
+/// Results of the query on a single shard: one list per root prefetch if the request has
+/// intermediate results, a single list otherwise
+pub type ShardQueryResponse = Vec<Vec<ScoredPoint>>;
@@ -48,6 +48,11 @@ fn avg_vectors<'a>(vectors: impl Iterator<Item = VectorRef<'a>>) -> CollectionRe
                 sparse_count += 1;
                 avg_sparse = vector.combine_aggregate(&avg_sparse, |v1, v2| v1 + v2);
             }
+            VectorRef::MultiDense(_) => {
+                return Err(CollectionError::bad_input(
+                    "Multi-vectors are not supported with `average_vector` strategy, use `best_score` instead".to_owned(),
+                ));
+            }
         }
     }
 
@@ -279,6 +284,7 @@ fn recommend_by_avg_vector(
         positive,
         negative,
         lookup_from,
+        mmr,
         ..
     } = request;
 
@@ -332,6 +338,7 @@ fn recommend_by_avg_vector(
         limit,
         score_threshold,
         offset: offset.unwrap_or_default(),
+        mmr,
     })
 }
 
@@ -355,6 +362,7 @@ fn recommend_by_best_score(
         score_threshold,
         using,
         lookup_from,
+        mmr,
     } = request;
 
     let lookup_collection_name = lookup_from.as_ref().map(|x| &x.collection);
@@ -396,6 +404,7 @@ fn recommend_by_best_score(
         with_payload,
         with_vector,
         score_threshold,
+        mmr,
     }
 }
 
@@ -6,17 +6,17 @@ use api::grpc::qdrant::{
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
     PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, VectorsSelector,
+    SyncPointsInternal, UpdatePayloadPoints, UpdatePayloadPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, VectorsSelector,
 };
 use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp, UpdatePayloadOp};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    PointInsertOperationsInternal, PointPrecondition, PointSyncOperation, WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
@@ -54,6 +54,7 @@ pub fn internal_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_insert_operations: PointInsertOperationsInternal,
+    preconditions: Vec<PointPrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
@@ -72,6 +73,7 @@ pub fn internal_upsert_points(
             },
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: preconditions.into_iter().map(Into::into).collect(),
         }),
     })
 }
@@ -81,6 +83,7 @@ pub fn internal_delete_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     ids: Vec<PointIdType>,
+    preconditions: Vec<PointPrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> DeletePointsInternal {
@@ -97,6 +100,7 @@ pub fn internal_delete_points(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: preconditions.into_iter().map(Into::into).collect(),
         }),
     }
 }
@@ -120,6 +124,7 @@ pub fn internal_delete_points_by_filter(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: vec![],
         }),
     }
 }
@@ -239,6 +244,11 @@ pub fn internal_set_payload(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             key: set_payload.key.map(|key| key.to_string()),
+            preconditions: set_payload
+                .preconditions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }),
     }
 }
@@ -277,6 +287,51 @@ pub fn internal_delete_payload(
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: delete_payload
+                .preconditions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }),
+    }
+}
+
+pub fn internal_update_payload(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    update_payload: UpdatePayloadOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> UpdatePayloadPointsInternal {
+    let points_selector = if let Some(points) = update_payload.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        update_payload.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    UpdatePayloadPointsInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        update_payload_points: Some(UpdatePayloadPoints {
+            collection_name,
+            wait: Some(wait),
+            updates: update_payload.updates.into_iter().map(Into::into).collect(),
+            points_selector,
+            condition: update_payload.condition.map(Into::into),
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+            preconditions: update_payload
+                .preconditions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }),
     }
 }
@@ -286,6 +341,7 @@ pub fn internal_clear_payload(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     points: Vec<PointIdType>,
+    preconditions: Vec<PointPrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> ClearPayloadPointsInternal {
@@ -302,6 +358,7 @@ pub fn internal_clear_payload(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: preconditions.into_iter().map(Into::into).collect(),
         }),
     }
 }
@@ -325,6 +382,7 @@ pub fn internal_clear_payload_by_filter(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            preconditions: vec![],
         }),
     }
 }
@@ -363,6 +421,9 @@ pub fn internal_create_index(
                     segment::types::PayloadSchemaType::Datetime => {
                         api::grpc::qdrant::FieldType::Datetime as i32
                     }
+                    segment::types::PayloadSchemaType::Uuid => {
+                        api::grpc::qdrant::FieldType::Uuid as i32
+                    }
                 },
                 None,
             ),
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -11,8 +11,11 @@ use tokio::runtime::Handle;
 
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SampleRequestInternal, SampledRecord, SparseStatistics, SparseStatisticsRequest,
+    UpdateResult,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -70,7 +73,7 @@ impl ShardOperation for DummyShard {
         _: &WithVector,
         _: Option<&Filter>,
         _: &Handle,
-        _: Option<&OrderBy>,
+        _: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         self.dummy()
     }
@@ -92,6 +95,30 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn query(
+        &self,
+        _: Arc<ShardQueryRequest>,
+        _: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        self.dummy()
+    }
+
+    async fn facet(&self, _: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        self.dummy()
+    }
+
+    async fn sample(&self, _: Arc<SampleRequestInternal>) -> CollectionResult<Vec<SampledRecord>> {
+        self.dummy()
+    }
+
+    async fn sparse_statistics(
+        &self,
+        _: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        self.dummy()
+    }
+
     async fn retrieve(
         &self,
         _: Arc<PointRequestInternal>,
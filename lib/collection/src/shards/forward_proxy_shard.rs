@@ -4,22 +4,29 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    Condition, CustomIdCheckerCondition, ExtendedPointId, Filter, PointIdType, ScoredPoint,
+    WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
 use super::update_tracker::UpdateTracker;
-use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
+use crate::hash_ring::HashRingFilter;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation,
+};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SampleRequestInternal, SampledRecord, SparseStatistics, SparseStatisticsRequest,
+    UpdateResult, UpdateStatus,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
-    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationToShard,
+    OperationWithClockTag, SplitByShard,
 };
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
@@ -32,23 +39,61 @@ use crate::shards::telemetry::LocalShardTelemetry;
 ///
 /// It can be used to provide all read and write operations while the wrapped shard is being transferred to another node.
 /// Proxy forwards all operations to remote shards.
+///
+/// While resharding, only the points which the new hash ring routes to the remote shard are
+/// transferred and forwarded.
 pub struct ForwardProxyShard {
     pub(crate) wrapped_shard: LocalShard,
     pub(crate) remote_shard: RemoteShard,
+    resharding_filter: Option<Arc<HashRingFilter>>,
     /// Lock required to protect transfer-in-progress updates.
     /// It should block data updating operations while the batch is being transferred.
     update_lock: Mutex<()>,
 }
 
 impl ForwardProxyShard {
-    pub fn new(wrapped_shard: LocalShard, remote_shard: RemoteShard) -> Self {
+    pub fn new(
+        wrapped_shard: LocalShard,
+        remote_shard: RemoteShard,
+        resharding_filter: Option<HashRingFilter>,
+    ) -> Self {
         Self {
             wrapped_shard,
             remote_shard,
+            resharding_filter: resharding_filter.map(Arc::new),
             update_lock: Mutex::new(()),
         }
     }
 
+    /// Filter of the points to transfer, if resharding
+    fn resharding_points_filter(&self) -> Option<Filter> {
+        let resharding_filter = self.resharding_filter.clone()?;
+        Some(Filter::new_must(Condition::CustomIdChecker(
+            CustomIdCheckerCondition(resharding_filter),
+        )))
+    }
+
+    /// Part of the operation to forward to the remote shard
+    ///
+    /// While resharding, only updates of the points routed to the remote shard are forwarded.
+    /// The clock tag of the operation belongs to the local shard, so it's not forwarded.
+    fn forwarded_operation(
+        &self,
+        operation: OperationWithClockTag,
+    ) -> Option<OperationWithClockTag> {
+        let Some(resharding_filter) = &self.resharding_filter else {
+            return Some(operation);
+        };
+
+        match operation.operation.split_by_shard(resharding_filter.ring()) {
+            OperationToShard::ToAll(operation) => Some(OperationWithClockTag::from(operation)),
+            OperationToShard::ByShard(by_shard) => by_shard
+                .into_iter()
+                .find(|(shard_id, _)| *shard_id == resharding_filter.expected_shard_id())
+                .map(|(_, operation)| OperationWithClockTag::from(operation)),
+        }
+    }
+
     /// Create payload indexes in the remote shard same as in the wrapped shard.
     ///
     /// # Cancel safety
@@ -89,6 +134,7 @@ impl ForwardProxyShard {
         debug_assert!(batch_size > 0);
         let limit = batch_size + 1;
         let _update_lock = self.update_lock.lock().await;
+        let filter = self.resharding_points_filter();
         let mut batch = self
             .wrapped_shard
             .scroll_by(
@@ -96,7 +142,7 @@ impl ForwardProxyShard {
                 limit,
                 &WithPayloadInterface::Bool(true),
                 &true.into(),
-                None,
+                filter.as_ref(),
                 runtime_handle,
                 None,
             )
@@ -114,8 +160,13 @@ impl ForwardProxyShard {
 
         let points = points?;
 
-        // Use sync API to leverage potentially existing points
-        let insert_points_operation = {
+        let insert_points_operation = if self.resharding_filter.is_some() {
+            // Remote shard stores its own points too, they must not be removed by syncing
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            ))
+        } else {
+            // Use sync API to leverage potentially existing points
             CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(
                 PointSyncOperation {
                     from_id: offset,
@@ -187,6 +238,10 @@ impl ShardOperation for ForwardProxyShard {
         // during the transfer restart and finalization.
         let mut result = self.wrapped_shard.update(operation.clone(), wait).await?;
 
+        let Some(operation) = self.forwarded_operation(operation) else {
+            return Ok(result);
+        };
+
         let remote_result = self
             .remote_shard
             .update(operation, false)
@@ -220,7 +275,7 @@ impl ShardOperation for ForwardProxyShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
@@ -257,6 +312,43 @@ impl ShardOperation for ForwardProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `query` to `wrapped_shard`
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `sample` to `wrapped_shard`
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sample(request).await
+    }
+
+    /// Forward read-only `sparse_statistics` to `wrapped_shard`
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sparse_statistics(request).await
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
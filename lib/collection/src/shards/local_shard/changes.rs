@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use parking_lot::MutexGuard;
+use segment::types::{PointIdType, WithPayload, WithPayloadInterface, WithVector};
+use tokio::sync::oneshot;
+
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::collection_manager::segments_updater::point_versions;
+use crate::operations::changes::{
+    sort_changes, ChangeType, ChangesIndex, ChangesOffset, PointChange, ReducedChanges,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::UpdateSignal;
+use crate::wal::SerdeWal;
+
+/// Number of operations read from the WAL at once, when extending the changes index
+const CHANGES_INDEX_BATCH_SIZE: usize = 1000;
+
+impl LocalShard {
+    /// Read changes, made by the operations in the WAL, after the `offset` position
+    ///
+    /// Changes are reduced to their final effect, upserted points are returned in their current
+    /// state. If `offset` is not specified, reads from the oldest available position.
+    ///
+    /// Operations are selected by their clock tags, so the position is valid on every replica
+    /// of the shard. Operations without a clock tag, which each peer applies on its own, are not
+    /// reported.
+    ///
+    /// Points deleted by a sync operation are reported, if they were upserted by an operation
+    /// still available in the WAL.
+    ///
+    /// Returns the changes and the position to continue reading from.
+    pub async fn read_changes(
+        &self,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        let newest_clocks = self.wal.recovery_point().await;
+
+        let (offset, selected) = {
+            let index = self.index_changes();
+            let offset = match offset {
+                Some(offset) => {
+                    index.check_offset(offset, &newest_clocks)?;
+                    offset.clone()
+                }
+                None => index.first_offset(&newest_clocks),
+            };
+            let selected: Vec<_> = index.unread(&offset).take(limit).collect();
+            (offset, selected)
+        };
+
+        let (Some(&(first_position, _)), Some(&(last_position, _))) =
+            (selected.first(), selected.last())
+        else {
+            return Ok((vec![], offset));
+        };
+
+        let update_sender = self.update_sender.load();
+        let channel_permit = update_sender.reserve().await?;
+        let (applied_sender, applied_receiver) = oneshot::channel();
+
+        // Only copy the records while holding the WAL, they are deserialized after
+        let records: Vec<_> = {
+            let wal = self.wal.wal.lock();
+
+            if first_position < wal.first_closed_index() {
+                return Err(CollectionError::service_error(format!(
+                    "Operation at position {first_position} was removed from the shard log \
+                     while reading changes",
+                )));
+            }
+
+            let selected_positions: HashSet<_> =
+                selected.iter().map(|(position, _)| *position).collect();
+            let records = wal
+                .read_raw(first_position)
+                .take_while(|(position, _)| *position <= last_position)
+                .filter(|(position, _)| selected_positions.contains(position))
+                .collect();
+
+            // It is *critical* to hold the WAL lock while sending the plunger, so all the read
+            // operations are already submitted to the update handler
+            channel_permit.send(UpdateSignal::Plunger(applied_sender));
+
+            records
+        };
+
+        let mut reduced = ReducedChanges::default();
+        for (position, record) in records {
+            let operation = SerdeWal::<OperationWithClockTag>::deserialize_record(&record);
+            reduced.add(position, &operation.operation);
+        }
+
+        // Sync operations delete points of their range, which are missing from the synced
+        // ones. Points upserted before the read operations are found in the changes index.
+        if !reduced.synced_ranges.is_empty() {
+            let upserted_before: Vec<_> = self
+                .changes_index
+                .lock()
+                .upserted_before(first_position)
+                .collect();
+            reduced.add_synced_deletes(upserted_before);
+        }
+
+        let mut next_offset = offset;
+        for (_, clock_tag) in &selected {
+            next_offset.advance(clock_tag.peer_id, clock_tag.clock_id, clock_tag.clock_tick);
+        }
+
+        // Wait for the read operations to be applied, so their effect is visible in segments
+        applied_receiver.await?;
+
+        let upserted = reduced.upserted_points();
+        let deleted = reduced.deleted_points();
+        let ReducedChanges {
+            points, filtered, ..
+        } = reduced;
+
+        let segments = self.segments();
+        let ids: Vec<_> = upserted.iter().chain(&deleted).copied().collect();
+        let versions = point_versions(&segments.read(), &ids)?;
+
+        // Operation didn't change the point, if the point has an older version.
+        // It happens, if the operation condition or precondition didn't hold.
+        let is_changed =
+            |id: &PointIdType| versions.get(id).map(|version| *version >= points[id].0);
+
+        // Upserted points, which don't exist anymore, are deleted by the later operations
+        let upserted: Vec<_> = upserted
+            .into_iter()
+            .filter(|id| is_changed(id) == Some(true))
+            .collect();
+
+        let with_payload = WithPayload::from(with_payload);
+        let records = SegmentsSearcher::retrieve(segments, &upserted, &with_payload, with_vector)?;
+
+        let mut changes: Vec<_> = records
+            .into_iter()
+            .map(|record| PointChange {
+                position: points[&record.id].0,
+                change_type: ChangeType::Upsert,
+                id: Some(record.id),
+                payload: record.payload,
+                vector: record.vector,
+                filter: None,
+            })
+            .collect();
+
+        changes.extend(
+            deleted
+                .into_iter()
+                .filter(|id| is_changed(id) != Some(false))
+                .map(|id| PointChange::delete(points[&id].0, id)),
+        );
+
+        changes.extend(filtered);
+        sort_changes(&mut changes);
+
+        Ok((changes, next_offset))
+    }
+
+    /// Extend the changes index up to the end of the WAL
+    ///
+    /// Operations are only read from the WAL once, when they are indexed for the first time.
+    fn index_changes(&self) -> MutexGuard<'_, ChangesIndex> {
+        let mut index = self.changes_index.lock();
+
+        loop {
+            let records: Vec<_> = {
+                let wal = self.wal.wal.lock();
+                let first_offset = wal.first_closed_index();
+                let end_offset = wal.first_index() + wal.len(false);
+
+                index.truncate(first_offset, end_offset);
+
+                wal.read_raw(index.next_position())
+                    .take(CHANGES_INDEX_BATCH_SIZE)
+                    .collect()
+            };
+
+            if records.is_empty() {
+                break;
+            }
+
+            for (position, record) in records {
+                let operation = SerdeWal::<OperationWithClockTag>::deserialize_record(&record);
+                index.add(position, &operation.operation, operation.clock_tag);
+            }
+        }
+
+        index
+    }
+}
@@ -1,4 +1,6 @@
+mod changes;
 pub mod clock_map;
+mod query;
 mod shard_ops;
 
 use std::collections::{BTreeSet, HashMap};
@@ -17,6 +19,7 @@ use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::data_types::sample::{merge_samples, SampledPoint};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
@@ -40,10 +43,12 @@ use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentH
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::common::file_utils::{move_dir, move_file};
 use crate::config::CollectionConfig;
+use crate::operations::changes::ChangesIndex;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, OptimizersStatus, SparseStatistics,
+    SparseStatisticsRequest,
 };
 use crate::operations::OperationWithClockTag;
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments};
@@ -74,6 +79,7 @@ pub struct LocalShard {
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
+    pub(super) changes_index: ParkingMutex<ChangesIndex>,
     update_runtime: Handle,
 }
 
@@ -172,6 +178,7 @@ impl LocalShard {
             update_runtime,
             optimizers,
             optimizers_log,
+            changes_index: Default::default(),
         }
     }
 
@@ -756,6 +763,41 @@ impl LocalShard {
         Ok(cardinality)
     }
 
+    /// Accumulate statistics of the requested sparse vectors over all segments of the shard
+    pub fn collect_sparse_statistics(
+        &self,
+        request: &SparseStatisticsRequest,
+    ) -> CollectionResult<SparseStatistics> {
+        let mut statistics = request.empty_statistics();
+        let segments = self.segments().read();
+        // Write segments of the running optimizations, shared by all of their proxies
+        let mut proxy_write_segments = Vec::new();
+        for (_id, segment) in segments.iter() {
+            if let LockedSegment::Proxy(proxy) = segment {
+                let write_segment = proxy.read().write_segment.clone();
+                let is_known = proxy_write_segments
+                    .iter()
+                    .any(|known: &LockedSegment| Arc::ptr_eq(&known.get(), &write_segment.get()));
+                if !is_known {
+                    proxy_write_segments.push(write_segment);
+                }
+            }
+            let segment = segment.get();
+            let segment = segment.read();
+            for (vector_name, vector_statistics) in statistics.iter_mut() {
+                segment.fill_sparse_vector_statistics(vector_name, vector_statistics)?;
+            }
+        }
+        for write_segment in proxy_write_segments {
+            let write_segment = write_segment.get();
+            let write_segment = write_segment.read();
+            for (vector_name, vector_statistics) in statistics.iter_mut() {
+                write_segment.fill_sparse_vector_statistics(vector_name, vector_statistics)?;
+            }
+        }
+        Ok(statistics)
+    }
+
     pub fn read_filtered<'a>(
         &'a self,
         filter: Option<&'a Filter>,
@@ -773,6 +815,25 @@ impl LocalShard {
         Ok(all_points)
     }
 
+    /// Pick up to `limit` uniformly random points which satisfy the filter, across all segments
+    ///
+    /// Points are ordered by their sampling keys, so samples of several shards can be merged.
+    pub fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        seed: u64,
+    ) -> CollectionResult<Vec<SampledPoint>> {
+        let segments = self.segments().read();
+        let points = segments.iter().flat_map(|(_id, segment)| {
+            segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter, seed)
+        });
+        Ok(merge_samples(points, limit))
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
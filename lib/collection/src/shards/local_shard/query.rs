@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::vec;
+
+use common::types::ScoreType;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use itertools::Itertools;
+use segment::common::score_fusion::{dbsf_scoring, rrf_scoring};
+use segment::data_types::sample::point_sampling_key;
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
+use segment::types::{Filter, PointIdType, ScoredPoint, WithPayload};
+use tokio::runtime::Handle;
+
+use super::shard_ops::postprocess_scores;
+use super::LocalShard;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionParams;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, QueryEnum,
+};
+use crate::operations::universal_query::shard_query::{
+    Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest, ShardQueryResponse, ShardSample,
+};
+use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
+
+/// Search request of a stage, which has no prefetches of its own
+fn prefetch_search(prefetch: &ShardPrefetch, query: &QueryEnum) -> CoreSearchRequest {
+    CoreSearchRequest {
+        query: query.clone(),
+        filter: prefetch.filter.clone(),
+        params: prefetch.params,
+        limit: prefetch.limit,
+        offset: 0,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: prefetch.score_threshold,
+        mmr: None,
+    }
+}
+
+/// Collect searches of leaf prefetches, in the same depth-first order they are resolved in
+fn collect_leaf_searches(
+    prefetch: &ShardPrefetch,
+    searches: &mut Vec<CoreSearchRequest>,
+) -> CollectionResult<()> {
+    if !prefetch.prefetches.is_empty() {
+        for child in &prefetch.prefetches {
+            collect_leaf_searches(child, searches)?;
+        }
+        return Ok(());
+    }
+
+    match &prefetch.query {
+        ScoringQuery::Vector(query) => {
+            searches.push(prefetch_search(prefetch, query));
+            Ok(())
+        }
+        ScoringQuery::Fusion(_) => Err(CollectionError::bad_request(
+            "Fusion query requires at least one prefetch".to_string(),
+        )),
+        ScoringQuery::Formula(_) => Err(CollectionError::bad_request(
+            "Formula query requires at least one prefetch".to_string(),
+        )),
+        // Sampled separately, as it doesn't search vectors
+        ScoringQuery::Sample(_) => Ok(()),
+    }
+}
+
+/// Sample of candidates, found by prefetches.
+///
+/// Keys of candidates only depend on their ids, so samples of several shards can be merged.
+fn sample_candidates(
+    sample: &ShardSample,
+    sources: Vec<Vec<ScoredPoint>>,
+    limit: usize,
+) -> Vec<ScoredPoint> {
+    sources
+        .into_iter()
+        .flatten()
+        .unique_by(|point| point.id)
+        .map(|point| ScoredPoint {
+            score: point_sampling_key(sample.seed, point.id),
+            ..point
+        })
+        .sorted_by(|a, b| a.score.total_cmp(&b.score))
+        .take(limit)
+        .collect()
+}
+
+impl LocalShard {
+    /// Execute the universal query on this shard.
+    ///
+    /// Searches of all leaf prefetches are performed in a single batch, then the stages are
+    /// resolved bottom-up: vector queries re-score candidates from their prefetches, fusions
+    /// combine them.
+    pub(super) async fn do_query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let query = self.resolve_query(&request, search_runtime_handle, timeout);
+
+        tokio::time::timeout(timeout, query).await.map_err(|_| {
+            log::debug!("Query timeout reached: {} seconds", timeout.as_secs());
+            CollectionError::timeout(timeout.as_secs() as usize, "Query")
+        })?
+    }
+
+    async fn resolve_query(
+        &self,
+        request: &ShardQueryRequest,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let root_search = |query: &QueryEnum| CoreSearchRequest {
+            query: query.clone(),
+            filter: request.filter.clone(),
+            params: request.params,
+            limit: request.limit,
+            offset: request.offset,
+            with_payload: Some(request.with_payload.clone()),
+            with_vector: Some(request.with_vector.clone()),
+            score_threshold: request.score_threshold,
+            mmr: None,
+        };
+
+        if request.prefetches.is_empty() {
+            let query = match &request.query {
+                Some(ScoringQuery::Vector(query)) => query,
+                Some(ScoringQuery::Sample(sample)) => {
+                    let points = self
+                        .sample_points(
+                            sample,
+                            request.filter.as_ref(),
+                            request.limit + request.offset,
+                        )
+                        .await?
+                        .into_iter()
+                        .skip(request.offset)
+                        .collect();
+                    let result = self.fill_with_payload(points, request)?;
+                    return Ok(vec![result]);
+                }
+                _ => {
+                    return Err(CollectionError::bad_request(
+                        "Query without prefetches must be a vector query or a sample".to_string(),
+                    ))
+                }
+            };
+            let batch = CoreSearchRequestBatch {
+                searches: vec![root_search(query)],
+            };
+            let result = self
+                .do_search(Arc::new(batch), search_runtime_handle, Some(timeout))
+                .await?;
+            return Ok(result);
+        }
+
+        let mut leaf_searches = Vec::new();
+        for prefetch in &request.prefetches {
+            collect_leaf_searches(prefetch, &mut leaf_searches)?;
+        }
+        let leaf_results = self
+            .do_search(
+                Arc::new(CoreSearchRequestBatch {
+                    searches: leaf_searches,
+                }),
+                search_runtime_handle,
+                Some(timeout),
+            )
+            .await?;
+
+        let collection_params = self.collection_config.read().await.params.clone();
+        let mut leaf_results = leaf_results.into_iter();
+        let mut prefetch_results = Vec::with_capacity(request.prefetches.len());
+        for prefetch in &request.prefetches {
+            let result = self
+                .resolve_prefetch(
+                    prefetch,
+                    &mut leaf_results,
+                    &collection_params,
+                    search_runtime_handle,
+                )
+                .await?;
+            prefetch_results.push(result);
+        }
+
+        match &request.query {
+            Some(ScoringQuery::Vector(query)) => {
+                let result = self
+                    .rescore(root_search(query), prefetch_results, search_runtime_handle)
+                    .await?;
+                Ok(vec![result])
+            }
+            Some(ScoringQuery::Sample(sample)) => {
+                let points =
+                    sample_candidates(sample, prefetch_results, request.limit + request.offset)
+                        .into_iter()
+                        .skip(request.offset)
+                        .collect();
+                Ok(vec![self.fill_with_payload(points, request)?])
+            }
+            Some(ScoringQuery::Formula(formula)) => {
+                let points = self
+                    .rescore_with_formula(
+                        formula,
+                        prefetch_results,
+                        request.limit + request.offset,
+                        request.score_threshold,
+                        search_runtime_handle,
+                    )
+                    .await?
+                    .into_iter()
+                    .skip(request.offset)
+                    .collect();
+                Ok(vec![self.fill_with_payload(points, request)?])
+            }
+            // Results of root prefetches are combined once results of all shards are merged
+            Some(ScoringQuery::Fusion(_)) | None => Ok(prefetch_results),
+        }
+    }
+
+    /// Resolve the stage, taking results of its leaf prefetches from `leaf_results`
+    fn resolve_prefetch<'a>(
+        &'a self,
+        prefetch: &'a ShardPrefetch,
+        leaf_results: &'a mut vec::IntoIter<Vec<ScoredPoint>>,
+        collection_params: &'a CollectionParams,
+        search_runtime_handle: &'a Handle,
+    ) -> BoxFuture<'a, CollectionResult<Vec<ScoredPoint>>> {
+        async move {
+            if prefetch.prefetches.is_empty() {
+                if let ScoringQuery::Sample(sample) = &prefetch.query {
+                    return self
+                        .sample_points(sample, prefetch.filter.as_ref(), prefetch.limit)
+                        .await;
+                }
+                return leaf_results.next().ok_or_else(|| {
+                    CollectionError::service_error("Missing result of prefetch search".to_string())
+                });
+            }
+
+            let mut sources = Vec::with_capacity(prefetch.prefetches.len());
+            for child in &prefetch.prefetches {
+                let result = self
+                    .resolve_prefetch(
+                        child,
+                        leaf_results,
+                        collection_params,
+                        search_runtime_handle,
+                    )
+                    .await?;
+                sources.push(result);
+            }
+
+            match &prefetch.query {
+                ScoringQuery::Vector(query) => {
+                    self.rescore(
+                        prefetch_search(prefetch, query),
+                        sources,
+                        search_runtime_handle,
+                    )
+                    .await
+                }
+                ScoringQuery::Fusion(fusion) => {
+                    let fused = match fusion {
+                        Fusion::Rrf => rrf_scoring(sources),
+                        Fusion::Dbsf => {
+                            let orders: Vec<_> = prefetch
+                                .prefetches
+                                .iter()
+                                .map(|child| child.query.order(collection_params))
+                                .collect::<CollectionResult<_>>()?;
+                            dbsf_scoring(sources.into_iter().zip(orders))
+                        }
+                    };
+                    Ok(fused
+                        .into_iter()
+                        .take_while(|point| {
+                            prefetch
+                                .score_threshold
+                                .map_or(true, |threshold| point.score >= threshold)
+                        })
+                        .take(prefetch.limit)
+                        .collect())
+                }
+                ScoringQuery::Sample(sample) => {
+                    Ok(sample_candidates(sample, sources, prefetch.limit))
+                }
+                ScoringQuery::Formula(formula) => {
+                    self.rescore_with_formula(
+                        formula,
+                        sources,
+                        prefetch.limit,
+                        prefetch.score_threshold,
+                        search_runtime_handle,
+                    )
+                    .await
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Uniformly random points of the shard, which satisfy the filter.
+    ///
+    /// Scores are sampling keys, which can be merged with samples of other shards.
+    async fn sample_points(
+        &self,
+        sample: &ShardSample,
+        filter: Option<&Filter>,
+        limit: usize,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let filter = self.exclude_expired(filter).await;
+        let sampled = match sample.method {
+            Sample::Random => self.read_random_filtered(limit, filter.as_ref(), sample.seed)?,
+        };
+        Ok(sampled
+            .into_iter()
+            .map(|point| ScoredPoint {
+                id: point.id,
+                version: point.version,
+                score: point.key,
+                payload: None,
+                vector: None,
+                shard_key: None,
+            })
+            .collect())
+    }
+
+    /// Retrieve payload and vectors of points, requested by the `request`
+    fn fill_with_payload(
+        &self,
+        points: Vec<ScoredPoint>,
+        request: &ShardQueryRequest,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let with_payload = WithPayload::from(&request.with_payload);
+        if !with_payload.enable && !request.with_vector.is_enabled() {
+            return Ok(points);
+        }
+
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let mut records: HashMap<_, _> =
+            SegmentsSearcher::retrieve(self.segments(), &ids, &with_payload, &request.with_vector)?
+                .into_iter()
+                .map(|record| (record.id, record))
+                .collect();
+
+        // Points deleted in the meantime are skipped
+        Ok(points
+            .into_iter()
+            .filter_map(|point| {
+                let record = records.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    ..point
+                })
+            })
+            .collect())
+    }
+
+    /// Score candidates, found by prefetches, with the formula.
+    ///
+    /// Scores of candidates in each of the `sources` are available to the formula by the index
+    /// of the source.
+    async fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        sources: Vec<Vec<ScoredPoint>>,
+        limit: usize,
+        score_threshold: Option<ScoreType>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let prefetches_scores: Vec<HashMap<_, _>> = sources
+            .into_iter()
+            .map(|points| {
+                points
+                    .into_iter()
+                    .map(|point| (point.id, point.score))
+                    .collect()
+            })
+            .collect();
+        if prefetches_scores.iter().all(HashMap::is_empty) {
+            return Ok(vec![]);
+        }
+
+        let is_stopped = StoppingGuard::new();
+        let points = SegmentsSearcher::rescore_with_formula(
+            Arc::clone(&self.segments),
+            Arc::new(formula.clone()),
+            Arc::new(prefetches_scores),
+            limit,
+            search_runtime_handle,
+            is_stopped.get_is_stopped(),
+        )
+        .await?;
+
+        Ok(points
+            .into_iter()
+            .take_while(|point| score_threshold.map_or(true, |threshold| point.score >= threshold))
+            .collect())
+    }
+
+    /// Score candidates, found by prefetches, with the query of the `request`
+    async fn rescore(
+        &self,
+        request: CoreSearchRequest,
+        sources: Vec<Vec<ScoredPoint>>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let candidates: Vec<PointIdType> = sources
+            .into_iter()
+            .flatten()
+            .map(|point| point.id)
+            .unique()
+            .collect();
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (collection_params, indexing_threshold_kb, full_scan_threshold_kb) = {
+            let collection_config = self.collection_config.read().await;
+            (
+                collection_config.params.clone(),
+                collection_config
+                    .optimizer_config
+                    .indexing_threshold
+                    .unwrap_or(DEFAULT_INDEXING_THRESHOLD_KB),
+                collection_config.hnsw_config.full_scan_threshold,
+            )
+        };
+        collection_params.get_distance(request.query.get_vector_name())?;
+
+        let is_stopped = StoppingGuard::new();
+        let batch = CoreSearchRequestBatch {
+            searches: vec![request],
+        };
+        let mut result = SegmentsSearcher::rescore(
+            Arc::clone(&self.segments),
+            &batch,
+            vec![candidates],
+            search_runtime_handle,
+            is_stopped.get_is_stopped(),
+            indexing_threshold_kb.max(full_scan_threshold_kb),
+        )
+        .await?;
+
+        Ok(postprocess_scores(
+            result.pop().unwrap_or_default(),
+            &batch.searches[0],
+            &collection_params,
+        ))
+    }
+}
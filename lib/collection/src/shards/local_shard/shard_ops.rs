@@ -1,10 +1,13 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::future::try_join_all;
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::MultiOrderBy;
+use segment::entry::entry_point::SegmentEntry as _;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -12,12 +15,18 @@ use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::collection_manager::segments_updater::check_preconditions;
 use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionParams;
+use crate::operations::point_ops::PointPrecondition;
+use crate::operations::ttl::TtlConfig;
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, QueryEnum, Record, UpdateResult,
-    UpdateStatus,
+    CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, FailedPrecondition,
+    PointRequestInternal, QueryEnum, Record, SampleRequestInternal, SampledRecord,
+    SparseStatistics, SparseStatisticsRequest, UpdateResult, UpdateStatus,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
 use crate::shards::local_shard::LocalShard;
@@ -25,7 +34,7 @@ use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
 
 impl LocalShard {
-    async fn do_search(
+    pub(super) async fn do_search(
         &self,
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
@@ -48,6 +57,21 @@ impl LocalShard {
             collection_params.get_distance(req.query.get_vector_name())?;
         }
 
+        let core_request = if let Some(ttl_config) = &collection_params.ttl_config {
+            let now = Utc::now();
+            let searches = core_request
+                .searches
+                .iter()
+                .map(|req| CoreSearchRequest {
+                    filter: Some(ttl_config.exclude_expired(req.filter.as_ref(), now)),
+                    ..req.clone()
+                })
+                .collect();
+            Arc::new(CoreSearchRequestBatch { searches })
+        } else {
+            core_request
+        };
+
         let is_stopped = StoppingGuard::new();
 
         let search_request = SegmentsSearcher::search(
@@ -72,36 +96,62 @@ impl LocalShard {
         let top_results = res
             .into_iter()
             .zip(core_request.searches.iter())
-            .map(|(vector_res, req)| {
-                let vector_name = req.query.get_vector_name();
-                let distance = collection_params.get_distance(vector_name).unwrap();
-                let processed_res = vector_res.into_iter().map(|mut scored_point| {
-                    match req.query {
-                        QueryEnum::Nearest(_) => {
-                            scored_point.score = distance.postprocess_score(scored_point.score);
-                        }
-                        // Don't post-process if we are dealing with custom scoring
-                        QueryEnum::RecommendBestScore(_)
-                        | QueryEnum::Discover(_)
-                        | QueryEnum::Context(_) => {}
-                    };
-                    scored_point
-                });
-
-                if let Some(threshold) = req.score_threshold {
-                    processed_res
-                        .take_while(|scored_point| {
-                            distance.check_threshold(scored_point.score, threshold)
-                        })
-                        .collect()
-                } else {
-                    processed_res.collect()
-                }
-            })
+            .map(|(vector_res, req)| postprocess_scores(vector_res, req, &collection_params))
             .collect();
         Ok(top_results)
     }
 
+    /// Extend the filter to exclude expired points, if expiration of points is configured
+    pub(super) async fn exclude_expired(&self, filter: Option<&Filter>) -> Option<Filter> {
+        match &self.collection_config.read().await.params.ttl_config {
+            Some(ttl_config) => Some(ttl_config.exclude_expired(filter, Utc::now())),
+            None => filter.cloned(),
+        }
+    }
+
+    /// Check if the shard has any point, which is expired at the moment `now`
+    ///
+    /// Expiration field is indexed, see `Collection::ensure_expiration_index`, so the check
+    /// is answered by the index cardinality and doesn't scan points of the segments.
+    pub fn has_expired_points(&self, ttl_config: &TtlConfig, now: DateTime<Utc>) -> bool {
+        let expired = ttl_config.expired_filter(now);
+        self.segments.read().iter().any(|(_, segment)| {
+            let segment = segment.get();
+            let segment = segment.read();
+            let cardinality = segment.estimate_point_count(Some(&expired));
+            if cardinality.max == 0 {
+                return false;
+            }
+            if cardinality.min > 0 {
+                return true;
+            }
+            // Estimation is not exact, look up a single expired point through the index
+            !segment
+                .read_filtered(None, Some(1), Some(&expired))
+                .is_empty()
+        })
+    }
+
+    /// Check preconditions of the points against their versions in this shard
+    ///
+    /// Waits until all updates submitted so far are applied, so the versions reflect them.
+    pub async fn check_preconditions(
+        &self,
+        preconditions: &[PointPrecondition],
+    ) -> CollectionResult<Vec<FailedPrecondition>> {
+        let (applied_sender, applied_receiver) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(applied_sender))
+            .await?;
+        applied_receiver.await?;
+
+        let segments = self.segments.clone();
+        let preconditions = preconditions.to_vec();
+        tokio::task::spawn_blocking(move || check_preconditions(&segments.read(), &preconditions))
+            .await?
+    }
+
     async fn scroll_by_id(
         &self,
         offset: Option<ExtendedPointId>,
@@ -157,7 +207,7 @@ impl LocalShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: &OrderBy,
+        order_by: &MultiOrderBy,
     ) -> CollectionResult<Vec<Record>> {
         let segments = self.segments();
         let read_handles: Vec<_> = {
@@ -186,34 +236,34 @@ impl LocalShard {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()?;
 
-        let top_records = all_reads
+        let top_points = all_reads
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
-            })
+            .kmerge_by(|a, b| order_by.compare(a, b).is_lt())
             .dedup()
             .take(limit)
             .collect_vec();
 
         let with_payload = WithPayload::from(with_payload_interface);
 
-        let point_ids = top_records.iter().map(|(_, id)| *id).collect_vec();
+        let point_ids = top_points.iter().map(|point| point.id).collect_vec();
 
         // Fetch with the requested vector and payload
-        let mut records =
-            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
-
-        // Add order_by value to the payload. It will be removed in the next step, after crossing the shard boundary.
-        records
-            .iter_mut()
-            .zip(top_records)
-            .for_each(|(record, (value, _))| {
-                let new_payload =
-                    OrderBy::insert_order_value_in_payload(record.payload.take(), value);
-
-                record.payload = Some(new_payload);
-            });
+        let records: HashMap<_, _> =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?
+                .into_iter()
+                .map(|record| (record.id, record))
+                .collect();
+
+        // A point is repeated for each of its values of a multi-valued key, so attach order values
+        // to each occurrence separately. They are used to merge results after crossing the shard boundary.
+        let records = top_points
+            .into_iter()
+            .filter_map(|point| {
+                let mut record = records.get(&point.id)?.clone();
+                record.order_values = Some(point.values);
+                Some(record)
+            })
+            .collect();
 
         Ok(records)
     }
@@ -259,6 +309,7 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        failed_preconditions: vec![],
                     });
                 }
 
@@ -281,12 +332,14 @@ impl ShardOperation for LocalShard {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
+                failed_preconditions: vec![],
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                failed_preconditions: vec![],
             })
         }
     }
@@ -299,8 +352,10 @@ impl ShardOperation for LocalShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        let filter = self.exclude_expired(filter).await;
+        let filter = filter.as_ref();
         match order_by {
             None => {
                 self.scroll_by_id(
@@ -342,16 +397,70 @@ impl ShardOperation for LocalShard {
             .await
     }
 
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        self.do_query(request, search_runtime_handle, timeout).await
+    }
+
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
+        let filter = self.exclude_expired(request.filter.as_ref()).await;
         let total_count = if request.exact {
-            let all_points = self.read_filtered(request.filter.as_ref())?;
+            let all_points = self.read_filtered(filter.as_ref())?;
             all_points.len()
         } else {
-            self.estimate_cardinality(request.filter.as_ref())?.exp
+            self.estimate_cardinality(filter.as_ref())?.exp
         };
         Ok(CountResult { count: total_count })
     }
 
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let filter = self.exclude_expired(request.filter.as_ref()).await;
+        let params = request.facet_params(filter);
+        let is_stopped = StoppingGuard::new();
+        let counts =
+            SegmentsSearcher::facet(self.segments(), &params, &is_stopped.get_is_stopped())?;
+        // Counts are truncated to the limit only after merging responses of all shards
+        Ok(FacetResponse::with_all_values(&counts, &request))
+    }
+
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let filter = self.exclude_expired(request.filter.as_ref()).await;
+        let keys: HashMap<_, _> = self
+            .read_random_filtered(request.limit, filter.as_ref(), rand::random())?
+            .into_iter()
+            .map(|point| (point.id, point.key))
+            .collect();
+        let ids: Vec<_> = keys.keys().copied().collect();
+        let with_payload = WithPayload::from(&request.with_payload);
+        let records =
+            SegmentsSearcher::retrieve(self.segments(), &ids, &with_payload, &request.with_vector)?;
+
+        // Keys are kept, so samples of several shards can be merged into a uniform one
+        let mut sampled: Vec<_> = records
+            .into_iter()
+            .filter_map(|record| {
+                let key = *keys.get(&record.id)?;
+                Some(SampledRecord { record, key })
+            })
+            .collect();
+        sampled.sort_by(|a, b| a.key.total_cmp(&b.key));
+        Ok(sampled)
+    }
+
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        self.collect_sparse_statistics(&request)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -361,3 +470,132 @@ impl ShardOperation for LocalShard {
         SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)
     }
 }
+
+/// Convert raw scores of the search into user-facing ones and cut off points below the threshold
+pub(super) fn postprocess_scores(
+    points: Vec<ScoredPoint>,
+    request: &CoreSearchRequest,
+    collection_params: &CollectionParams,
+) -> Vec<ScoredPoint> {
+    let vector_name = request.query.get_vector_name();
+    let distance = collection_params.get_distance(vector_name).unwrap();
+    // Max-sim scores are sums of already post-processed similarities
+    let is_multivector = collection_params
+        .vectors
+        .get_params(vector_name)
+        .is_some_and(|params| params.multivector_config.is_some());
+    let processed_res = points.into_iter().map(|mut scored_point| {
+        match request.query {
+            QueryEnum::Nearest(_) if is_multivector => {
+                scored_point.score = distance.postprocess_max_sim_score(scored_point.score);
+            }
+            QueryEnum::Nearest(_) => {
+                scored_point.score = distance.postprocess_score(scored_point.score);
+            }
+            // Don't post-process if we are dealing with custom scoring
+            QueryEnum::RecommendBestScore(_) | QueryEnum::Discover(_) | QueryEnum::Context(_) => {}
+        };
+        scored_point
+    });
+
+    if let Some(threshold) = request.score_threshold {
+        processed_res
+            .take_while(|scored_point| distance.check_threshold(scored_point.score, threshold))
+            .collect()
+    } else {
+        processed_res.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use common::types::ScoreType;
+    use segment::data_types::vectors::{
+        MultiDenseVector, NamedMultiDenseVector, NamedVectorStruct, DEFAULT_VECTOR_NAME,
+    };
+    use segment::types::{Distance, MultiVectorConfig};
+
+    use super::*;
+    use crate::operations::types::{VectorParams, VectorsConfig};
+
+    fn multivector_params(distance: Distance) -> CollectionParams {
+        CollectionParams {
+            vectors: VectorsConfig::Single(VectorParams {
+                size: NonZeroU64::new(2).unwrap(),
+                distance,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                multivector_config: Some(MultiVectorConfig::default()),
+                datatype: None,
+            }),
+            ..CollectionParams::empty()
+        }
+    }
+
+    fn search_request(score_threshold: ScoreType) -> CoreSearchRequest {
+        let query =
+            MultiDenseVector::try_from_matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedVectorStruct::MultiDense(NamedMultiDenseVector {
+                name: DEFAULT_VECTOR_NAME.to_string(),
+                vector: query,
+            })),
+            filter: None,
+            params: None,
+            limit: 10,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: Some(score_threshold),
+            mmr: None,
+        }
+    }
+
+    fn scored_points(scores: &[ScoreType]) -> Vec<ScoredPoint> {
+        scores
+            .iter()
+            .enumerate()
+            .map(|(idx, &score)| ScoredPoint {
+                id: (idx as u64).into(),
+                version: 0,
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multivector_distance_threshold() {
+        // Raw maximal similarities of two query vectors for each point, best point first
+        let euclid_similarities = [[-1.0, 0.0], [-1.0, -2.25], [-4.0, -4.0]];
+        let manhattan_similarities = [[-1.0, 0.0], [-1.0, -1.5], [-2.0, -2.0]];
+
+        for (distance, similarities) in [
+            (Distance::Euclid, euclid_similarities),
+            (Distance::Manhattan, manhattan_similarities),
+        ] {
+            let raw_scores: Vec<_> = similarities
+                .iter()
+                .map(|point_similarities| {
+                    distance.max_sim_score(point_similarities.iter().copied())
+                })
+                .collect();
+
+            let result = postprocess_scores(
+                scored_points(&raw_scores),
+                &search_request(3.0),
+                &multivector_params(distance),
+            );
+
+            // Scores are sums of distances, and the threshold is the maximal sum. The root of
+            // summed squared Euclid distances of the last point would pass the threshold.
+            let scores: Vec<_> = result.iter().map(|point| point.score).collect();
+            assert_eq!(scores, vec![1.0, 2.5], "{distance:?}");
+        }
+    }
+}
@@ -9,6 +9,7 @@ pub mod queue_proxy_shard;
 pub mod remote_shard;
 #[allow(dead_code)]
 pub mod replica_set;
+pub mod resharding;
 pub mod resolve;
 pub mod shard;
 pub mod shard_config;
@@ -6,7 +6,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -21,8 +21,11 @@ use crate::operations::operation_effect::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SampleRequestInternal, SampledRecord, SparseStatistics, SparseStatisticsRequest,
+    UpdateResult,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
@@ -192,7 +195,7 @@ impl ShardOperation for ProxyShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
@@ -233,6 +236,43 @@ impl ShardOperation for ProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `query` to `wrapped_shard`
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `sample` to `wrapped_shard`
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sample(request).await
+    }
+
+    /// Forward read-only `sparse_statistics` to `wrapped_shard`
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sparse_statistics(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
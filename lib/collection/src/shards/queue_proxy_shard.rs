@@ -6,7 +6,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -17,11 +17,14 @@ use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{PointPrecondition, WriteOrdering};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, FailedPrecondition,
+    PointRequestInternal, Record, SampleRequestInternal, SampledRecord, SparseStatistics,
+    SparseStatisticsRequest, UpdateResult,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
@@ -155,6 +158,18 @@ impl QueueProxyShard {
             .await
     }
 
+    pub async fn check_preconditions(
+        &self,
+        preconditions: &[PointPrecondition],
+    ) -> CollectionResult<Vec<FailedPrecondition>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .check_preconditions(preconditions)
+            .await
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         self.inner
             .as_ref()
@@ -230,7 +245,7 @@ impl ShardOperation for QueueProxyShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         self.inner
             .as_ref()
@@ -277,6 +292,53 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `query` to `wrapped_shard`
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .facet(request)
+            .await
+    }
+
+    /// Forward read-only `sample` to `wrapped_shard`
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .sample(request)
+            .await
+    }
+
+    /// Forward read-only `sparse_statistics` to `wrapped_shard`
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .sparse_statistics(request)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -509,7 +571,7 @@ impl ShardOperation for Inner {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
@@ -550,6 +612,43 @@ impl ShardOperation for Inner {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `query` to `wrapped_shard`
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `sample` to `wrapped_shard`
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sample(request).await
+    }
+
+    /// Forward read-only `sparse_statistics` to `wrapped_shard`
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.sparse_statistics(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -10,10 +10,12 @@ use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
-    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
-    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    FacetCounts, FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
+    GetPoints, GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
+    InitiateShardTransferRequest, QueryPointsInternal, ReadChangesInternal,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, SamplePointsInternal, ScrollPoints,
+    ScrollPointsInternal, ShardSnapshotLocation, SparseIndices, SparseStatisticsInternal,
+    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -22,7 +24,7 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -37,15 +39,18 @@ use super::conversions::{
 };
 use super::local_shard::clock_map::RecoveryPoint;
 use super::replica_set::ReplicaState;
-use crate::operations::conversions::try_record_from_grpc;
+use crate::operations::changes::{ChangesOffset, PointChange};
+use crate::operations::conversions::{try_point_change_from_grpc, try_record_from_grpc};
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, SearchRequestInternal,
-    UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SampleRequestInternal, SampledRecord, SearchRequestInternal, SparseStatistics,
+    SparseStatisticsRequest, UpdateResult,
 };
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations, OperationWithClockTag};
 use crate::shards::channel_service::ChannelService;
@@ -53,7 +58,7 @@ use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
     internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_update_payload, internal_upsert_points, try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -256,6 +261,7 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         point_insert_operations,
+                        vec![],
                         wait,
                         ordering,
                     )?;
@@ -265,12 +271,29 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
-                PointOperations::DeletePoints { ids } => {
+                PointOperations::UpsertPointsConditional(conditional_insert) => {
+                    let request = &internal_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_insert.points_op,
+                        conditional_insert.preconditions,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::DeletePoints { ids, preconditions } => {
                     let request = &internal_delete_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
                         ids,
+                        preconditions,
                         wait,
                         ordering,
                     );
@@ -401,12 +424,16 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
-                PayloadOps::ClearPayload { points } => {
+                PayloadOps::ClearPayload {
+                    points,
+                    preconditions,
+                } => {
                     let request = &internal_clear_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
                         points,
+                        preconditions,
                         wait,
                         ordering,
                     );
@@ -452,6 +479,23 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PayloadOps::UpdatePayload(update_payload) => {
+                    let request = &internal_update_payload(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        update_payload,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .update_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
@@ -611,6 +655,48 @@ impl RemoteShard {
         Ok(())
     }
 
+    /// Read changes of the remote replica, see [`LocalShard::read_changes`]
+    ///
+    /// [`LocalShard::read_changes`]: super::local_shard::LocalShard::read_changes
+    pub async fn read_changes(
+        &self,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        let is_payload_required = with_payload.is_required();
+        let request = &ReadChangesInternal {
+            collection_name: self.collection_id.clone(),
+            shard_id: self.id,
+            offset: offset.cloned().map(Into::into),
+            limit: limit as u64,
+            with_payload: Some(with_payload.clone().into()),
+            with_vectors: Some(with_vector.clone().into()),
+        };
+
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .read_changes(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        let changes = response
+            .changes
+            .into_iter()
+            .map(|change| try_point_change_from_grpc(change, is_payload_required))
+            .collect::<Result<_, Status>>()?;
+        let next_offset = response
+            .next_offset
+            .map(Into::into)
+            .ok_or_else(|| CollectionError::service_error("Missing next offset of changes"))?;
+
+        Ok((changes, next_offset))
+    }
+
     pub async fn health_check(&self) -> CollectionResult<()> {
         let _ = self
             .with_qdrant_client(|mut client| async move {
@@ -654,8 +740,20 @@ impl ShardOperation for RemoteShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        let (order_by, then_by, cursor) = match order_by {
+            None => (None, Vec::new(), None),
+            Some(order_by) => (
+                Some(order_by.primary().clone().into()),
+                order_by
+                    .secondary()
+                    .iter()
+                    .map(|key| key.clone().into())
+                    .collect(),
+                order_by.cursor.as_ref().map(|cursor| cursor.to_cursor()),
+            ),
+        };
         let scroll_points = ScrollPoints {
             collection_name: self.collection_id.clone(),
             filter: filter.map(|f| f.clone().into()),
@@ -665,7 +763,9 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
-            order_by: order_by.map(|o| o.clone().into()),
+            order_by,
+            then_by,
+            cursor,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -764,6 +864,47 @@ impl ShardOperation for RemoteShard {
         result
     }
 
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let is_payload_required = request.with_payload.is_required();
+
+        let request = &QueryPointsInternal {
+            collection_name: self.collection_id.clone(),
+            query_points: Some(request.as_ref().clone().into()),
+            shard_id: Some(self.id),
+            timeout: timeout.map(|t| t.as_secs()),
+        };
+        let query_response = self
+            .with_points_client(|mut client| async move {
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.query(request).await
+            })
+            .await?
+            .into_inner();
+
+        let result: Result<ShardQueryResponse, Status> = query_response
+            .intermediate_results
+            .into_iter()
+            .map(|intermediate| {
+                intermediate
+                    .result
+                    .into_iter()
+                    .map(|point| try_scored_point_from_grpc(point, is_payload_required))
+                    .collect()
+            })
+            .collect();
+        result.map_err(|e| e.into())
+    }
+
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
         let count_points = CountPoints {
             collection_name: self.collection_id.clone(),
@@ -793,6 +934,108 @@ impl ShardOperation for RemoteShard {
         )
     }
 
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let facet_counts = FacetCounts {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: request.limit.map(|limit| limit as u64),
+            exact: Some(request.exact),
+            ranges: request
+                .ranges
+                .iter()
+                .flatten()
+                .cloned()
+                .map(Into::into)
+                .collect(),
+            shard_key_selector: None,
+        };
+
+        let request = &FacetCountsInternal {
+            facet_counts: Some(facet_counts),
+            shard_id: Some(self.id),
+        };
+        let facet_response = self
+            .with_points_client(|mut client| async move {
+                client.facet(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+        Ok(facet_response.try_into()?)
+    }
+
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let with_payload = WithPayload::from(&request.with_payload);
+        let request = &SamplePointsInternal {
+            collection_name: self.collection_id.clone(),
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: request.limit as u64,
+            with_payload: Some(request.with_payload.clone().into()),
+            with_vectors: Some(request.with_vector.clone().into()),
+            shard_id: Some(self.id),
+        };
+
+        let sample_response = self
+            .with_points_client(|mut client| async move {
+                client.sample(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+
+        if sample_response.result.len() != sample_response.keys.len() {
+            return Err(CollectionError::service_error(format!(
+                "Expected a sampling key for each of {} sampled points, got {}",
+                sample_response.result.len(),
+                sample_response.keys.len(),
+            )));
+        }
+
+        let result: Result<Vec<SampledRecord>, Status> = sample_response
+            .result
+            .into_iter()
+            .zip(sample_response.keys)
+            .map(|(point, key)| {
+                let record = try_record_from_grpc(point, with_payload.enable)?;
+                Ok(SampledRecord { record, key })
+            })
+            .collect();
+
+        result.map_err(|e| e.into())
+    }
+
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        let request = &SparseStatisticsInternal {
+            collection_name: self.collection_id.clone(),
+            vectors: request
+                .vectors
+                .iter()
+                .map(|(vector_name, dims)| {
+                    (vector_name.clone(), SparseIndices { data: dims.clone() })
+                })
+                .collect(),
+            shard_id: Some(self.id),
+        };
+        let statistics_response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .sparse_statistics(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(statistics_response
+            .statistics
+            .into_iter()
+            .map(|(vector_name, statistics)| (vector_name, statistics.into()))
+            .collect())
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
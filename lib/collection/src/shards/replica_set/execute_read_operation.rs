@@ -10,6 +10,7 @@ use super::ShardReplicaSet;
 use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::remote_shard::RemoteShard;
+use crate::shards::resharding::filtered_shard::FilteredShard;
 use crate::shards::resolve::{Resolve, ResolveCondition};
 use crate::shards::shard::Shard;
 use crate::shards::shard_trait::ShardOperation;
@@ -26,7 +27,9 @@ impl ShardReplicaSet {
         local_only: bool,
     ) -> CollectionResult<Res>
     where
-        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+        F: for<'a> Fn(
+            &'a (dyn ShardOperation + Send + Sync + 'a),
+        ) -> BoxFuture<'a, CollectionResult<Res>>,
     {
         if local_only {
             return self.execute_local_read_operation(read_operation).await;
@@ -46,7 +49,9 @@ impl ShardReplicaSet {
         local_only: bool,
     ) -> CollectionResult<Res>
     where
-        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+        F: for<'a> Fn(
+            &'a (dyn ShardOperation + Send + Sync + 'a),
+        ) -> BoxFuture<'a, CollectionResult<Res>>,
         Res: Resolve,
     {
         if local_only {
@@ -111,7 +116,9 @@ impl ShardReplicaSet {
 
     async fn execute_local_read_operation<Res, F>(&self, read_operation: F) -> CollectionResult<Res>
     where
-        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+        F: for<'a> Fn(
+            &'a (dyn ShardOperation + Send + Sync + 'a),
+        ) -> BoxFuture<'a, CollectionResult<Res>>,
     {
         let local = self.local.read().await;
 
@@ -122,7 +129,25 @@ impl ShardReplicaSet {
             )));
         };
 
-        read_operation(local.get()).await
+        self.read_local(local.get(), &read_operation).await
+    }
+
+    /// Execute read op. on the local shard, applying the resharding read filter if any
+    async fn read_local<'s, Res, F>(
+        &self,
+        local: &'s (dyn ShardOperation + Send + Sync + 's),
+        read_operation: &F,
+    ) -> CollectionResult<Res>
+    where
+        F: for<'a> Fn(
+            &'a (dyn ShardOperation + Send + Sync + 'a),
+        ) -> BoxFuture<'a, CollectionResult<Res>>,
+    {
+        let read_filter = self.read_filter.read().clone();
+        match read_filter {
+            Some(checker) => read_operation(&FilteredShard::new(local, checker)).await,
+            None => read_operation(local).await,
+        }
     }
 
     async fn execute_cluster_read_operation<Res, F>(
@@ -132,7 +157,9 @@ impl ShardReplicaSet {
         remotes: Option<tokio::sync::RwLockReadGuard<'_, Vec<RemoteShard>>>,
     ) -> CollectionResult<Vec<Res>>
     where
-        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+        F: for<'a> Fn(
+            &'a (dyn ShardOperation + Send + Sync + 'a),
+        ) -> BoxFuture<'a, CollectionResult<Res>>,
     {
         let remotes = match remotes {
             Some(remotes) => remotes,
@@ -171,7 +198,7 @@ impl ShardReplicaSet {
                     )));
                 };
 
-                read_operation(local.get()).await
+                self.read_local(local.get(), &read_operation).await
             };
 
             Some(local_operation.map(|result| (result, true)).left_future())
@@ -15,6 +15,7 @@ use std::time::Duration;
 use common::cpu::CpuBudget;
 use common::types::TelemetryDetail;
 use schemars::JsonSchema;
+use segment::types::{CustomIdCheckerCondition, WithPayloadInterface, WithVector};
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
@@ -26,6 +27,7 @@ use super::transfer::ShardTransfer;
 use super::CollectionId;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::config::CollectionConfig;
+use crate::operations::changes::{ChangesOffset, PointChange};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::save_on_disk::SaveOnDisk;
@@ -101,6 +103,9 @@ pub struct ShardReplicaSet {
     write_ordering_lock: Mutex<()>,
     /// Local clock set, used to tag new operations on this shard.
     clock_set: Mutex<ClockSet>,
+    /// Filter of the local reads, set while the shard stores points of other shards during
+    /// resharding.
+    read_filter: parking_lot::RwLock<Option<CustomIdCheckerCondition>>,
 }
 
 pub type AbortShardTransfer = Arc<dyn Fn(ShardTransfer, &str) + Send + Sync>;
@@ -188,6 +193,7 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
+            read_filter: parking_lot::RwLock::new(None),
             clock_set: Default::default(),
         })
     }
@@ -299,6 +305,7 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
+            read_filter: parking_lot::RwLock::new(None),
             clock_set: Default::default(),
         };
 
@@ -312,6 +319,11 @@ impl ShardReplicaSet {
         replica_set
     }
 
+    /// Set filter of the points exposed by local reads, used while resharding
+    pub fn set_read_filter(&self, read_filter: Option<CustomIdCheckerCondition>) {
+        *self.read_filter.write() = read_filter;
+    }
+
     pub fn this_peer_id(&self) -> PeerId {
         self.replica_state.read().this_peer_id
     }
@@ -343,6 +355,18 @@ impl ShardReplicaSet {
         self.replica_state.read().get_peer_state(peer_id).copied()
     }
 
+    /// Replicas created by automatic replication in place of a dead replica
+    pub fn replacements(&self) -> HashSet<PeerId> {
+        self.replica_state.read().replacements()
+    }
+
+    /// Mark the replica as created in place of a dead replica, as applied by consensus
+    pub fn add_replacement(&self, peer_id: PeerId) -> CollectionResult<()> {
+        self.replica_state
+            .write(|state| state.add_replacement(peer_id))?;
+        Ok(())
+    }
+
     pub async fn active_remote_shards(&self) -> Vec<PeerId> {
         let replica_state = self.replica_state.read();
         let this_peer_id = replica_state.this_peer_id;
@@ -596,11 +620,13 @@ impl ShardReplicaSet {
     pub async fn apply_state(
         &self,
         replicas: HashMap<PeerId, ReplicaState>,
+        replacements: HashSet<PeerId>,
     ) -> CollectionResult<()> {
         let old_peers = self.replica_state.read().peers();
 
         self.replica_state.write(|state| {
             state.set_peers(replicas.clone());
+            state.set_replacements(replacements);
         })?;
 
         self.locally_disabled_peers.write().clear();
@@ -831,6 +857,86 @@ impl ShardReplicaSet {
         local_shard.shard_recovery_point().await
     }
 
+    /// Read changes of the local replica, see [`LocalShard::read_changes`]
+    pub(crate) async fn read_local_changes(
+        &self,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: format!("Local replica of shard {}", self.shard_id),
+            });
+        };
+
+        local_shard
+            .read_changes(offset, limit, with_payload, with_vector)
+            .await
+    }
+
+    /// Read changes of the shard from one of its active replicas, see
+    /// [`LocalShard::read_changes`]
+    ///
+    /// The local replica is preferred. Positions are valid on every replica, so the next replica
+    /// is tried, if one fails to serve the changes.
+    pub(crate) async fn read_changes(
+        &self,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        let mut last_error = None;
+
+        if self.peer_is_active(&self.this_peer_id()) {
+            match self
+                .read_local_changes(offset, limit, with_payload, with_vector)
+                .await
+            {
+                Ok(changes) => return Ok(changes),
+                Err(err) => {
+                    log::debug!(
+                        "Failed to read changes of shard {} from local replica: {err}",
+                        self.shard_id,
+                    );
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        let active_peers = self.active_remote_shards().await;
+        let remotes = self.remotes.read().await;
+        for remote in remotes
+            .iter()
+            .filter(|remote| active_peers.contains(&remote.peer_id))
+        {
+            match remote
+                .read_changes(offset, limit, with_payload, with_vector)
+                .await
+            {
+                Ok(changes) => return Ok(changes),
+                Err(err) => {
+                    log::debug!(
+                        "Failed to read changes of shard {} from peer {}: {err}",
+                        self.shard_id,
+                        remote.peer_id,
+                    );
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            CollectionError::service_error(format!(
+                "No active replica of shard {} to read changes from",
+                self.shard_id,
+            ))
+        }))
+    }
+
     /// Update the cutoff point for the local shard.
     pub(crate) async fn update_shard_cutoff_point(
         &self,
@@ -857,6 +963,9 @@ pub struct ReplicaSetState {
     pub is_local: bool,
     pub this_peer_id: PeerId,
     peers: HashMap<PeerId, ReplicaState>,
+    /// Replicas created by automatic replication in place of a dead replica
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    replacements: HashSet<PeerId>,
 }
 
 impl ReplicaSetState {
@@ -869,6 +978,7 @@ impl ReplicaSetState {
     }
 
     pub fn remove_peer_state(&mut self, peer_id: &PeerId) -> Option<ReplicaState> {
+        self.replacements.remove(peer_id);
         self.peers.remove(peer_id)
     }
 
@@ -890,8 +1000,27 @@ impl ReplicaSetState {
     }
 
     pub fn set_peers(&mut self, peers: HashMap<PeerId, ReplicaState>) {
+        self.replacements
+            .retain(|peer_id| peers.contains_key(peer_id));
         self.peers = peers;
     }
+
+    pub fn replacements(&self) -> HashSet<PeerId> {
+        self.replacements.clone()
+    }
+
+    pub fn set_replacements(&mut self, replacements: HashSet<PeerId>) {
+        self.replacements = replacements
+            .into_iter()
+            .filter(|peer_id| self.peers.contains_key(peer_id))
+            .collect();
+    }
+
+    pub fn add_replacement(&mut self, peer_id: PeerId) {
+        if self.peers.contains_key(&peer_id) {
+            self.replacements.insert(peer_id);
+        }
+    }
 }
 
 /// State of the single shard within a replica set.
@@ -951,4 +1080,6 @@ impl ReplicaState {
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 pub enum Change {
     Remove(ShardId, PeerId),
+    /// Mark the replica as created in place of a dead replica by automatic replication
+    MarkReplacement(ShardId, PeerId),
 }
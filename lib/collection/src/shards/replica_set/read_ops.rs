@@ -2,12 +2,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::FutureExt as _;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::*;
 
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::*;
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -20,7 +21,7 @@ impl ShardReplicaSet {
         filter: Option<&Filter>,
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload_interface = Arc::new(with_payload_interface.clone());
         let with_vector = Arc::new(with_vector.clone());
@@ -76,6 +77,26 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = Arc::clone(&request);
+                let search_runtime = self.search_runtime.clone();
+
+                async move { shard.query(request, &search_runtime, timeout).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
     pub async fn count(
         &self,
         request: Arc<CountRequestInternal>,
@@ -93,6 +114,51 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        local_only: bool,
+    ) -> CollectionResult<FacetResponse> {
+        self.execute_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.facet(request).await }.boxed()
+            },
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+        local_only: bool,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        self.execute_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.sample(request).await }.boxed()
+            },
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+        local_only: bool,
+    ) -> CollectionResult<SparseStatistics> {
+        self.execute_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.sparse_statistics(request).await }.boxed()
+            },
+            local_only,
+        )
+        .await
+    }
+
     pub async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -5,6 +5,7 @@ use parking_lot::Mutex;
 use segment::types::PointIdType;
 
 use super::ShardReplicaSet;
+use crate::hash_ring::HashRingFilter;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::clock_map::RecoveryPoint;
@@ -16,10 +17,17 @@ use crate::shards::transfer::transfer_tasks_pool::TransferTaskProgress;
 impl ShardReplicaSet {
     /// Convert `Local` shard into `ForwardProxy`.
     ///
+    /// With a `resharding_filter`, only the points accepted by the filter are transferred and
+    /// forwarded to the remote shard.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
-    pub async fn proxify_local(&self, remote_shard: RemoteShard) -> CollectionResult<()> {
+    pub async fn proxify_local(
+        &self,
+        remote_shard: RemoteShard,
+        resharding_filter: Option<HashRingFilter>,
+    ) -> CollectionResult<()> {
         let mut local = self.local.write().await;
 
         match local.deref() {
@@ -74,7 +82,7 @@ impl ShardReplicaSet {
             _ => unreachable!(),
         };
 
-        let proxy_shard = ForwardProxyShard::new(local_shard, remote_shard);
+        let proxy_shard = ForwardProxyShard::new(local_shard, remote_shard, resharding_filter);
         let _ = local.insert(Shard::ForwardProxy(proxy_shard));
 
         Ok(())
@@ -450,7 +458,7 @@ impl ShardReplicaSet {
         };
 
         let (local_shard, remote_shard) = queue_proxy.forget_updates_and_finalize();
-        let forward_proxy = ForwardProxyShard::new(local_shard, remote_shard);
+        let forward_proxy = ForwardProxyShard::new(local_shard, remote_shard, None);
         let _ = local.insert(Shard::ForwardProxy(forward_proxy));
 
         Ok(())
@@ -1,15 +1,19 @@
 use std::ops::Deref as _;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use itertools::Itertools as _;
 
 use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
-use crate::operations::point_ops::WriteOrdering;
-use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::ttl::TtlConfig;
+use crate::operations::types::{
+    CollectionError, CollectionResult, FailedPrecondition, UpdateResult, UpdateStatus,
+};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation as _;
 
 /// Maximum number of attempts for applying an update with a new clock.
@@ -72,6 +76,13 @@ impl ShardReplicaSet {
     ) -> CollectionResult<UpdateResult> {
         // `ShardReplicaSet::update` is not cancel safe, so this method is not cancel safe.
 
+        // Preconditions must be checked and applied by a single replica at a time, otherwise
+        // concurrent updates could all pass the same precondition
+        let ordering = match ordering {
+            WriteOrdering::Weak if !operation.preconditions().is_empty() => WriteOrdering::Medium,
+            ordering => ordering,
+        };
+
         let Some(leader_peer) = self.leader_peer_for_update(ordering) else {
             return Err(CollectionError::service_error(format!(
                 "Cannot update shard {}:{} with {ordering:?} ordering because no leader could be selected",
@@ -89,7 +100,13 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
-            self.update(operation, wait).await
+            // Conditional updates are applied before the lock is released, so the next one
+            // checks its preconditions against the updated points
+            let wait = wait || !operation.preconditions().is_empty();
+            let (operation, failed_preconditions) = self.resolve_preconditions(operation).await?;
+            let mut result = self.update(operation, wait).await?;
+            result.failed_preconditions = failed_preconditions;
+            Ok(result)
         } else {
             // Forward the update to the designated leader
             self.forward_update(leader_peer, operation, wait, ordering)
@@ -110,6 +127,73 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Check preconditions of the operation against the local replica of the leader
+    ///
+    /// Points with failed preconditions are excluded and the preconditions are dropped, so all
+    /// replicas update the same points, even if their point versions differ.
+    async fn resolve_preconditions(
+        &self,
+        mut operation: CollectionUpdateOperations,
+    ) -> CollectionResult<(CollectionUpdateOperations, Vec<FailedPrecondition>)> {
+        if operation.preconditions().is_empty() {
+            return Ok((operation, vec![]));
+        }
+
+        let local = self.local.read().await;
+        let local = match local.deref() {
+            Some(local) if self.peer_is_active(&self.this_peer_id()) => local,
+            _ => {
+                return Err(CollectionError::service_error(format!(
+                    "Cannot check preconditions of points in shard {}:{}, \
+                     because the leader peer {} has no active replica",
+                    self.collection_id,
+                    self.shard_id,
+                    self.this_peer_id(),
+                )));
+            }
+        };
+
+        let failed_preconditions = local.check_preconditions(operation.preconditions()).await?;
+        operation.resolve_preconditions(&failed_preconditions);
+        Ok((operation, failed_preconditions))
+    }
+
+    /// Delete points of this shard, which are expired at the moment `now`
+    ///
+    /// Deletion is sent only by the replica, which leads updates with medium ordering, and is
+    /// replicated to other replicas like any other update. Returns `None` on other replicas,
+    /// or if there is nothing to delete.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn delete_expired_points(
+        &self,
+        ttl_config: &TtlConfig,
+        now: DateTime<Utc>,
+    ) -> CollectionResult<Option<UpdateResult>> {
+        let ordering = WriteOrdering::Medium;
+        if self.leader_peer_for_update(ordering) != Some(self.this_peer_id()) {
+            return Ok(None);
+        }
+
+        let has_expired_points = match self.local.read().await.deref() {
+            Some(Shard::Local(local_shard)) => local_shard.has_expired_points(ttl_config, now),
+            _ => false,
+        };
+        if !has_expired_points {
+            return Ok(None);
+        }
+
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(ttl_config.expired_filter(now)),
+        );
+        let result = self
+            .update_with_consistency(operation, false, ordering)
+            .await?;
+        Ok(Some(result))
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -548,6 +632,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                multivector_config: None,
+                datatype: None,
             }),
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
@@ -561,6 +647,7 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            strict_mode_config: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
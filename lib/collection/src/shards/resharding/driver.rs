@@ -0,0 +1,354 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::defaults;
+use segment::types::{WithPayloadInterface, WithVector};
+use tokio::time::{sleep, Instant};
+
+use super::{ReshardKey, ReshardMigration, ReshardStage, ReshardState, ReshardingDirection};
+use crate::common::stoppable_task_async::{spawn_async_cancellable, CancellableAsyncTaskHandle};
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::channel_service::ChannelService;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::{LockedShardHolder, ShardHolder};
+use crate::shards::transfer::{
+    await_consensus_sync, ShardTransfer, ShardTransferConsensus, ShardTransferMethod,
+};
+use crate::shards::CollectionId;
+
+/// Interval between checks of the resharding state
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Max interval between attempts to finish the resharding operation
+const MAX_FINISH_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Number of points removed from the old shards at once, when cleaning up after resharding up
+const CLEANUP_BATCH_SIZE: usize = 100;
+
+/// Spawn the task driving the resharding operation on this peer
+///
+/// The driver proposes every next step of the resharding operation to consensus, and waits for
+/// consensus to apply it. It stops as soon as the resharding operation is finished or aborted.
+///
+/// Returns `true` in the task handle if the resharding operation was driven to its end.
+pub fn spawn_resharding_driver(
+    reshard_key: ReshardKey,
+    shard_holder: Arc<LockedShardHolder>,
+    consensus: Box<dyn ShardTransferConsensus>,
+    collection_id: CollectionId,
+    channel_service: ChannelService,
+    this_peer_id: PeerId,
+) -> CancellableAsyncTaskHandle<bool> {
+    spawn_async_cancellable(move |cancel| async move {
+        let future = drive_resharding(
+            &reshard_key,
+            &shard_holder,
+            consensus.as_ref(),
+            &collection_id,
+            &channel_service,
+            this_peer_id,
+        );
+
+        match cancel::future::cancel_on_token(cancel, future).await {
+            Ok(Ok(())) => true,
+            Ok(Err(err)) => {
+                log::error!(
+                    "Failed to drive resharding {reshard_key:?} of collection {collection_id}: {err}"
+                );
+
+                let can_abort = current_state(&shard_holder, &reshard_key)
+                    .await
+                    .map_or(false, |state| {
+                        state.stage < ReshardStage::WriteHashRingCommitted
+                    });
+                if can_abort {
+                    if let Err(err) = consensus.abort_resharding(
+                        reshard_key.clone(),
+                        collection_id.clone(),
+                        &err.to_string(),
+                    ) {
+                        log::error!("Failed to propose resharding abort: {err}");
+                    }
+                }
+                false
+            }
+            Err(err) => {
+                log::debug!(
+                    "Resharding {reshard_key:?} of collection {collection_id} stopped: {err}"
+                );
+                false
+            }
+        }
+    })
+}
+
+/// Drive the resharding operation through all of its stages
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+async fn drive_resharding(
+    reshard_key: &ReshardKey,
+    shard_holder: &Arc<LockedShardHolder>,
+    consensus: &dyn ShardTransferConsensus,
+    collection_id: &CollectionId,
+    channel_service: &ChannelService,
+    this_peer_id: PeerId,
+) -> CollectionResult<()> {
+    let mut finish_retry_interval = POLL_INTERVAL;
+
+    loop {
+        let Some(state) = current_state(shard_holder, reshard_key).await else {
+            log::debug!("Resharding {reshard_key:?} of collection {collection_id} is over");
+            return Ok(());
+        };
+
+        match state.stage {
+            ReshardStage::MigratingPoints => {
+                let has_active_migration = !shard_holder
+                    .read()
+                    .await
+                    .get_transfers(|transfer| transfer.is_resharding())
+                    .is_empty();
+
+                if has_active_migration {
+                    sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+
+                match state.next_migration().copied() {
+                    Some(migration) => {
+                        let transfer = {
+                            let shard_holder = shard_holder.read().await;
+                            migration_transfer(&shard_holder, &migration, this_peer_id)?
+                        };
+                        log::debug!(
+                            "Migrating points of shard {} into shard {} on peer {}",
+                            migration.from_shard_id,
+                            migration.to_shard_id,
+                            migration.to_peer_id,
+                        );
+                        let transfer_key = transfer.key();
+                        consensus.start_shard_transfer(transfer, collection_id.clone())?;
+                        await_state_change(shard_holder, reshard_key, |shard_holder, state| {
+                            shard_holder.check_transfer_exists(&transfer_key)
+                                || state.next_migration() != Some(&migration)
+                        })
+                        .await;
+                    }
+                    None => {
+                        consensus
+                            .commit_read_hashring(reshard_key.clone(), collection_id.clone())?;
+                        await_state_change(shard_holder, reshard_key, |_, state| {
+                            state.stage >= ReshardStage::ReadHashRingCommitted
+                        })
+                        .await;
+                    }
+                }
+            }
+
+            ReshardStage::ReadHashRingCommitted => {
+                // All peers must read by the new hash ring before writes stop going to the old one
+                await_consensus_sync(consensus, channel_service, this_peer_id).await;
+
+                consensus.commit_write_hashring(reshard_key.clone(), collection_id.clone())?;
+                await_state_change(shard_holder, reshard_key, |_, state| {
+                    state.stage >= ReshardStage::WriteHashRingCommitted
+                })
+                .await;
+            }
+
+            ReshardStage::WriteHashRingCommitted => {
+                // All peers must write by the new hash ring before outdated points are removed
+                await_consensus_sync(consensus, channel_service, this_peer_id).await;
+
+                // Resharding can't be aborted anymore, so keep retrying until it's finished
+                let result =
+                    finish_resharding(shard_holder, consensus, collection_id, &state).await;
+                if let Err(err) = result {
+                    log::warn!(
+                        "Failed to finish resharding {reshard_key:?} of collection {collection_id}, \
+                         retrying in {finish_retry_interval:?}: {err}"
+                    );
+                    sleep(finish_retry_interval).await;
+                    finish_retry_interval =
+                        (finish_retry_interval * 2).min(MAX_FINISH_RETRY_INTERVAL);
+                    continue;
+                }
+
+                await_state_change(shard_holder, reshard_key, |_, _| false).await;
+            }
+        }
+    }
+}
+
+/// Clean up points moved out of the old shards, and propose to finish the resharding operation
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+async fn finish_resharding(
+    shard_holder: &LockedShardHolder,
+    consensus: &dyn ShardTransferConsensus,
+    collection_id: &CollectionId,
+    state: &ReshardState,
+) -> CollectionResult<()> {
+    if state.direction == ReshardingDirection::Up {
+        cleanup_moved_points(shard_holder, state).await?;
+    }
+
+    consensus.finish_resharding(state.key(), collection_id.clone())
+}
+
+/// Current state of the resharding operation, if it's still the given one
+async fn current_state(
+    shard_holder: &LockedShardHolder,
+    reshard_key: &ReshardKey,
+) -> Option<ReshardState> {
+    shard_holder
+        .read()
+        .await
+        .resharding_state()
+        .filter(|state| state.matches(reshard_key))
+}
+
+/// Wait until consensus applies the proposed change of the resharding operation
+///
+/// Returns early if the resharding operation is over. Gives up after the consensus timeout, in
+/// which case the driver re-evaluates its state and proposes the change again if needed.
+async fn await_state_change(
+    shard_holder: &LockedShardHolder,
+    reshard_key: &ReshardKey,
+    is_changed: impl Fn(&ShardHolder, &ReshardState) -> bool,
+) {
+    let wait_until = Instant::now() + defaults::CONSENSUS_META_OP_WAIT;
+    while Instant::now() < wait_until {
+        {
+            let shard_holder = shard_holder.read().await;
+            match shard_holder.resharding_state() {
+                Some(state) if state.matches(reshard_key) => {
+                    if is_changed(&shard_holder, &state) {
+                        return;
+                    }
+                }
+                _ => return,
+            }
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Build the transfer moving points of the migration
+///
+/// The points are sent from an active replica of the source shard, preferably the local one.
+fn migration_transfer(
+    shard_holder: &ShardHolder,
+    migration: &ReshardMigration,
+    this_peer_id: PeerId,
+) -> CollectionResult<ShardTransfer> {
+    let Some(replica_set) = shard_holder.get_shard(&migration.from_shard_id) else {
+        return Err(CollectionError::service_error(format!(
+            "Shard {} to migrate points from does not exist",
+            migration.from_shard_id,
+        )));
+    };
+
+    let mut active_peers: Vec<_> = replica_set
+        .peers()
+        .into_iter()
+        .filter(|(_, state)| *state == ReplicaState::Active)
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    active_peers.sort_unstable();
+
+    let from = if active_peers.contains(&this_peer_id) {
+        this_peer_id
+    } else {
+        active_peers.first().copied().ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Shard {} to migrate points from has no active replica",
+                migration.from_shard_id,
+            ))
+        })?
+    };
+
+    Ok(ShardTransfer {
+        shard_id: migration.from_shard_id,
+        to_shard_id: Some(migration.to_shard_id),
+        from,
+        to: migration.to_peer_id,
+        sync: true,
+        method: Some(ShardTransferMethod::ReshardingStreamRecords),
+    })
+}
+
+/// Remove points, which were moved into the new shard, from the shards they were moved from
+///
+/// # Cancel safety
+///
+/// This function is cancel safe. Removed points are not read anymore, so the cleanup can
+/// safely be repeated.
+async fn cleanup_moved_points(
+    shard_holder: &LockedShardHolder,
+    state: &ReshardState,
+) -> CollectionResult<()> {
+    let old_shard_ids: Vec<ShardId> = state
+        .migrations
+        .iter()
+        .map(|migration| migration.from_shard_id)
+        .collect();
+
+    let mut offset = None;
+    loop {
+        let shard_holder = shard_holder.read().await;
+        let Some(new_shard) = shard_holder.get_shard(&state.shard_id) else {
+            return Err(CollectionError::service_error(format!(
+                "Shard {} added by resharding does not exist",
+                state.shard_id,
+            )));
+        };
+
+        let mut batch = new_shard
+            .scroll_by(
+                offset,
+                CLEANUP_BATCH_SIZE + 1,
+                &WithPayloadInterface::Bool(false),
+                &WithVector::Bool(false),
+                None,
+                None,
+                // Driver peer does not necessarily hold a replica of the new shard
+                false,
+                None,
+            )
+            .await?;
+
+        offset = if batch.len() > CLEANUP_BATCH_SIZE {
+            batch.pop().map(|point| point.id)
+        } else {
+            None
+        };
+
+        let ids: Vec<_> = batch.into_iter().map(|point| point.id).collect();
+        if !ids.is_empty() {
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                    ids,
+                    preconditions: Vec::new(),
+                });
+            for shard_id in &old_shard_ids {
+                if let Some(replica_set) = shard_holder.get_shard(shard_id) {
+                    replica_set
+                        .update_with_consistency(operation.clone(), true, WriteOrdering::Weak)
+                        .await?;
+                }
+            }
+        }
+
+        if offset.is_none() {
+            return Ok(());
+        }
+    }
+}
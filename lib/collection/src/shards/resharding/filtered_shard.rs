@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use segment::data_types::order_by::MultiOrderBy;
+use segment::types::{
+    Condition, CustomIdCheckerCondition, ExtendedPointId, Filter, ScoredPoint, WithPayload,
+    WithPayloadInterface, WithVector,
+};
+use tokio::runtime::Handle;
+
+use crate::operations::types::{
+    CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResponse, PointRequestInternal,
+    Record, SampleRequestInternal, SampledRecord, SparseStatistics, SparseStatisticsRequest,
+    UpdateResult,
+};
+use crate::operations::universal_query::shard_query::{
+    ShardPrefetch, ShardQueryRequest, ShardQueryResponse,
+};
+use crate::operations::OperationWithClockTag;
+use crate::shards::shard_trait::ShardOperation;
+
+/// FilteredShard
+///
+/// Wrapper of a local shard, which exposes only the points accepted by the checker to reads.
+///
+/// During resharding a shard temporarily stores points, which already (or still) belong to
+/// another shard. Reads of such shard are wrapped, so that every point is returned exactly once.
+pub struct FilteredShard<'a> {
+    wrapped_shard: &'a (dyn ShardOperation + Send + Sync + 'a),
+    checker: CustomIdCheckerCondition,
+    filter: Filter,
+}
+
+impl<'a> FilteredShard<'a> {
+    pub fn new(
+        wrapped_shard: &'a (dyn ShardOperation + Send + Sync + 'a),
+        checker: CustomIdCheckerCondition,
+    ) -> Self {
+        let filter = Filter::new_must(Condition::CustomIdChecker(checker.clone()));
+        Self {
+            wrapped_shard,
+            checker,
+            filter,
+        }
+    }
+
+    fn merge_filter(&self, filter: Option<&Filter>) -> Filter {
+        match filter {
+            Some(filter) => filter.merge(&self.filter),
+            None => self.filter.clone(),
+        }
+    }
+
+    fn filter_prefetch(&self, prefetch: &ShardPrefetch) -> ShardPrefetch {
+        ShardPrefetch {
+            prefetches: prefetch
+                .prefetches
+                .iter()
+                .map(|prefetch| self.filter_prefetch(prefetch))
+                .collect(),
+            filter: Some(self.merge_filter(prefetch.filter.as_ref())),
+            ..prefetch.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl<'a> ShardOperation for FilteredShard<'a> {
+    /// Forward `update` to `wrapped_shard`, writes are never filtered
+    async fn update(
+        &self,
+        operation: OperationWithClockTag,
+        wait: bool,
+    ) -> CollectionResult<UpdateResult> {
+        self.wrapped_shard.update(operation, wait).await
+    }
+
+    async fn scroll_by(
+        &self,
+        offset: Option<ExtendedPointId>,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        order_by: Option<&MultiOrderBy>,
+    ) -> CollectionResult<Vec<Record>> {
+        let filter = self.merge_filter(filter);
+        self.wrapped_shard
+            .scroll_by(
+                offset,
+                limit,
+                with_payload_interface,
+                with_vector,
+                Some(&filter),
+                search_runtime_handle,
+                order_by,
+            )
+            .await
+    }
+
+    async fn info(&self) -> CollectionResult<CollectionInfo> {
+        self.wrapped_shard.info().await
+    }
+
+    async fn core_search(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let request = CoreSearchRequestBatch {
+            searches: request
+                .searches
+                .iter()
+                .map(|search| CoreSearchRequest {
+                    filter: Some(self.merge_filter(search.filter.as_ref())),
+                    ..search.clone()
+                })
+                .collect(),
+        };
+        self.wrapped_shard
+            .core_search(Arc::new(request), search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse> {
+        let request = ShardQueryRequest {
+            prefetches: request
+                .prefetches
+                .iter()
+                .map(|prefetch| self.filter_prefetch(prefetch))
+                .collect(),
+            filter: Some(self.merge_filter(request.filter.as_ref())),
+            ..request.as_ref().clone()
+        };
+        self.wrapped_shard
+            .query(Arc::new(request), search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
+        let request = CountRequestInternal {
+            filter: Some(self.merge_filter(request.filter.as_ref())),
+            exact: request.exact,
+        };
+        self.wrapped_shard.count(Arc::new(request)).await
+    }
+
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse> {
+        let request = FacetRequestInternal {
+            filter: Some(self.merge_filter(request.filter.as_ref())),
+            ..request.as_ref().clone()
+        };
+        self.wrapped_shard.facet(Arc::new(request)).await
+    }
+
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>> {
+        let request = SampleRequestInternal {
+            filter: Some(self.merge_filter(request.filter.as_ref())),
+            ..request.as_ref().clone()
+        };
+        self.wrapped_shard.sample(Arc::new(request)).await
+    }
+
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics> {
+        self.wrapped_shard.sparse_statistics(request).await
+    }
+
+    async fn retrieve(
+        &self,
+        request: Arc<PointRequestInternal>,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<Vec<Record>> {
+        let request = PointRequestInternal {
+            ids: request
+                .ids
+                .iter()
+                .copied()
+                .filter(|point_id| self.checker.check(*point_id))
+                .collect(),
+            with_payload: request.with_payload.clone(),
+            with_vector: request.with_vector.clone(),
+        };
+        self.wrapped_shard
+            .retrieve(Arc::new(request), with_payload, with_vector)
+            .await
+    }
+}
@@ -0,0 +1,216 @@
+pub mod driver;
+pub mod filtered_shard;
+
+use schemars::JsonSchema;
+use segment::types::ShardKey;
+use serde::{Deserialize, Serialize};
+
+use super::shard::{PeerId, ShardId};
+
+/// Direction of the resharding operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReshardingDirection {
+    /// Add a new shard and move a part of the points into it
+    Up,
+    /// Remove a shard and move its points into the remaining shards
+    Down,
+}
+
+/// Stage of the resharding operation
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ReshardStage {
+    /// Points are moved between shards, reads use the old hash ring
+    MigratingPoints,
+    /// Reads use the new hash ring, writes still go to both hash rings
+    ReadHashRingCommitted,
+    /// Reads and writes use the new hash ring, outdated points are being cleaned up
+    WriteHashRingCommitted,
+}
+
+/// Unique identifier of a resharding operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReshardKey {
+    /// Peer which drives the resharding operation
+    pub peer_id: PeerId,
+    /// Shard which is added or removed
+    pub shard_id: ShardId,
+    /// Shard key the resharded shard belongs to
+    pub shard_key: Option<ShardKey>,
+    pub direction: ReshardingDirection,
+}
+
+/// Migration of points from one shard into a replica of another shard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReshardMigration {
+    pub from_shard_id: ShardId,
+    pub to_shard_id: ShardId,
+    pub to_peer_id: PeerId,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Persisted state of the ongoing resharding operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReshardState {
+    pub peer_id: PeerId,
+    pub shard_id: ShardId,
+    pub shard_key: Option<ShardKey>,
+    pub direction: ReshardingDirection,
+    pub stage: ReshardStage,
+    /// Point migrations, which must be done before reads switch to the new hash ring
+    pub migrations: Vec<ReshardMigration>,
+}
+
+impl ReshardState {
+    pub fn new(key: ReshardKey, migrations: Vec<ReshardMigration>) -> Self {
+        Self {
+            peer_id: key.peer_id,
+            shard_id: key.shard_id,
+            shard_key: key.shard_key,
+            direction: key.direction,
+            stage: ReshardStage::MigratingPoints,
+            migrations,
+        }
+    }
+
+    pub fn key(&self) -> ReshardKey {
+        ReshardKey {
+            peer_id: self.peer_id,
+            shard_id: self.shard_id,
+            shard_key: self.shard_key.clone(),
+            direction: self.direction,
+        }
+    }
+
+    pub fn matches(&self, key: &ReshardKey) -> bool {
+        self.peer_id == key.peer_id
+            && self.shard_id == key.shard_id
+            && self.shard_key == key.shard_key
+            && self.direction == key.direction
+    }
+
+    /// First migration which is not done yet
+    pub fn next_migration(&self) -> Option<&ReshardMigration> {
+        self.migrations.iter().find(|migration| !migration.done)
+    }
+
+    pub fn migrations_done(&self) -> usize {
+        self.migrations
+            .iter()
+            .filter(|migration| migration.done)
+            .count()
+    }
+
+    /// Whether the resharded shard must be excluded from reads at the current stage
+    ///
+    /// A new shard is only readable once it has all of its points, a removed shard stops being
+    /// readable once all of its points are moved out.
+    pub fn is_hidden_for_reads(&self, shard_id: ShardId) -> bool {
+        if shard_id != self.shard_id {
+            return false;
+        }
+        match self.direction {
+            ReshardingDirection::Up => self.stage < ReshardStage::ReadHashRingCommitted,
+            ReshardingDirection::Down => self.stage >= ReshardStage::ReadHashRingCommitted,
+        }
+    }
+
+    /// Whether reads of the given shard must be filtered by the new hash ring
+    ///
+    /// Otherwise reads of the shard must be filtered by the old hash ring, if
+    /// [`ReshardState::filter_reads_by_old_ring`] says so.
+    pub fn filter_reads_by_new_ring(&self, shard_id: ShardId) -> bool {
+        shard_id != self.shard_id
+            && self.direction == ReshardingDirection::Up
+            && self.stage >= ReshardStage::ReadHashRingCommitted
+    }
+
+    /// Whether reads of the given shard must be filtered by the old hash ring
+    pub fn filter_reads_by_old_ring(&self, shard_id: ShardId) -> bool {
+        shard_id != self.shard_id
+            && self.direction == ReshardingDirection::Down
+            && self.stage < ReshardStage::ReadHashRingCommitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(direction: ReshardingDirection, stage: ReshardStage) -> ReshardState {
+        let key = ReshardKey {
+            peer_id: 1,
+            shard_id: 2,
+            shard_key: None,
+            direction,
+        };
+        let mut state = ReshardState::new(key, Vec::new());
+        state.stage = stage;
+        state
+    }
+
+    #[test]
+    fn test_read_filtering_up() {
+        let migrating = state(ReshardingDirection::Up, ReshardStage::MigratingPoints);
+        assert!(migrating.is_hidden_for_reads(2));
+        assert!(!migrating.is_hidden_for_reads(0));
+        assert!(!migrating.filter_reads_by_new_ring(0));
+        assert!(!migrating.filter_reads_by_old_ring(0));
+
+        for stage in [
+            ReshardStage::ReadHashRingCommitted,
+            ReshardStage::WriteHashRingCommitted,
+        ] {
+            let committed = state(ReshardingDirection::Up, stage);
+            assert!(!committed.is_hidden_for_reads(2));
+            // Old shards still hold the moved points, until they are cleaned up
+            assert!(committed.filter_reads_by_new_ring(0));
+            assert!(!committed.filter_reads_by_new_ring(2));
+            assert!(!committed.filter_reads_by_old_ring(0));
+        }
+    }
+
+    #[test]
+    fn test_read_filtering_down() {
+        let migrating = state(ReshardingDirection::Down, ReshardStage::MigratingPoints);
+        assert!(!migrating.is_hidden_for_reads(2));
+        // Remaining shards already receive the points of the removed shard
+        assert!(migrating.filter_reads_by_old_ring(0));
+        assert!(!migrating.filter_reads_by_old_ring(2));
+        assert!(!migrating.filter_reads_by_new_ring(0));
+
+        for stage in [
+            ReshardStage::ReadHashRingCommitted,
+            ReshardStage::WriteHashRingCommitted,
+        ] {
+            let committed = state(ReshardingDirection::Down, stage);
+            assert!(committed.is_hidden_for_reads(2));
+            assert!(!committed.is_hidden_for_reads(0));
+            assert!(!committed.filter_reads_by_old_ring(0));
+            assert!(!committed.filter_reads_by_new_ring(0));
+        }
+    }
+
+    #[test]
+    fn test_next_migration() {
+        let migration = |from_shard_id, done| ReshardMigration {
+            from_shard_id,
+            to_shard_id: 2,
+            to_peer_id: 1,
+            done,
+        };
+        let mut state = state(ReshardingDirection::Up, ReshardStage::MigratingPoints);
+        state.migrations = vec![migration(0, true), migration(1, false)];
+
+        assert_eq!(state.next_migration(), Some(&migration(1, false)));
+        assert_eq!(state.migrations_done(), 1);
+
+        state.migrations[1].done = true;
+        assert_eq!(state.next_migration(), None);
+        assert_eq!(state.migrations_done(), 2);
+    }
+}
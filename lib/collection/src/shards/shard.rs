@@ -3,10 +3,13 @@ use std::future::{self, Future};
 use std::path::Path;
 
 use common::types::TelemetryDetail;
+use segment::types::{WithPayloadInterface, WithVector};
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::changes::{ChangesOffset, PointChange};
+use crate::operations::point_ops::PointPrecondition;
+use crate::operations::types::{CollectionError, CollectionResult, FailedPrecondition};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
@@ -164,6 +167,56 @@ impl Shard {
         }
     }
 
+    pub async fn read_changes(
+        &self,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> CollectionResult<(Vec<PointChange>, ChangesOffset)> {
+        match self {
+            Self::Local(local_shard) => {
+                local_shard
+                    .read_changes(offset, limit, with_payload, with_vector)
+                    .await
+            }
+            Self::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .wrapped_shard
+                    .read_changes(offset, limit, with_payload, with_vector)
+                    .await
+            }
+
+            Self::Proxy(_) | Self::QueueProxy(_) | Self::Dummy(_) => {
+                Err(CollectionError::service_error(format!(
+                    "Reading changes not supported on {}",
+                    self.variant_name(),
+                )))
+            }
+        }
+    }
+
+    pub async fn check_preconditions(
+        &self,
+        preconditions: &[PointPrecondition],
+    ) -> CollectionResult<Vec<FailedPrecondition>> {
+        match self {
+            Self::Local(local_shard) => local_shard.check_preconditions(preconditions).await,
+            Self::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .wrapped_shard
+                    .check_preconditions(preconditions)
+                    .await
+            }
+            Self::QueueProxy(proxy_shard) => proxy_shard.check_preconditions(preconditions).await,
+
+            Self::Proxy(_) | Self::Dummy(_) => Err(CollectionError::service_error(format!(
+                "Checking preconditions not supported on {}",
+                self.variant_name(),
+            ))),
+        }
+    }
+
     pub async fn update_cutoff(&self, cutoff: &RecoveryPoint) -> CollectionResult<()> {
         match self {
             Self::Local(local_shard) => local_shard.update_cutoff(cutoff).await,
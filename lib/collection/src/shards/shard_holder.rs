@@ -5,7 +5,7 @@ use std::sync::Arc;
 use common::cpu::CpuBudget;
 use itertools::Itertools;
 // TODO rename ReplicaShard to ReplicaSetShard
-use segment::types::ShardKey;
+use segment::types::{CustomIdCheckerCondition, ShardKey};
 use tar::Builder as TarBuilder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -13,7 +13,7 @@ use tokio::sync::RwLock;
 use super::replica_set::AbortShardTransfer;
 use super::transfer::transfer_tasks_pool::TransferTasksPool;
 use crate::config::{CollectionConfig, ShardingMethod};
-use crate::hash_ring::HashRing;
+use crate::hash_ring::{HashRing, HashRingFilter, HashRingRouter};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::snapshot_ops::SnapshotDescription;
@@ -23,6 +23,7 @@ use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::{ChangePeerState, ReplicaState, ShardReplicaSet}; // TODO rename ReplicaShard to ReplicaSetShard
+use crate::shards::resharding::{ReshardStage, ReshardState, ReshardingDirection};
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{ShardConfig, ShardType};
 use crate::shards::shard_versioning::latest_shard_paths;
@@ -32,6 +33,7 @@ use crate::shards::CollectionId;
 const HASH_RING_SHARD_SCALE: u32 = 100;
 
 const SHARD_TRANSFERS_FILE: &str = "shard_transfers";
+const RESHARDING_STATE_FILE: &str = "resharding_state.json";
 pub const SHARD_KEY_MAPPING_FILE: &str = "shard_key_mapping.json";
 
 pub type ShardKeyMapping = HashMap<ShardKey, HashSet<ShardId>>;
@@ -39,7 +41,8 @@ pub type ShardKeyMapping = HashMap<ShardKey, HashSet<ShardId>>;
 pub struct ShardHolder {
     shards: HashMap<ShardId, ShardReplicaSet>,
     pub(crate) shard_transfers: SaveOnDisk<HashSet<ShardTransfer>>,
-    rings: HashMap<Option<ShardKey>, HashRing<ShardId>>,
+    pub(crate) resharding_state: SaveOnDisk<Option<ReshardState>>,
+    rings: HashMap<Option<ShardKey>, HashRingRouter>,
     key_mapping: SaveOnDisk<ShardKeyMapping>,
     // Duplicates the information from `key_mapping` for faster access
     // Do not require locking
@@ -51,8 +54,13 @@ pub type LockedShardHolder = RwLock<ShardHolder>;
 impl ShardHolder {
     pub fn new(collection_path: &Path) -> CollectionResult<Self> {
         let mut rings = HashMap::new();
-        rings.insert(None, HashRing::fair(HASH_RING_SHARD_SCALE));
+        rings.insert(
+            None,
+            HashRingRouter::Single(HashRing::fair(HASH_RING_SHARD_SCALE)),
+        );
         let shard_transfers = SaveOnDisk::load_or_init(collection_path.join(SHARD_TRANSFERS_FILE))?;
+        let resharding_state =
+            SaveOnDisk::load_or_init(collection_path.join(RESHARDING_STATE_FILE))?;
         let key_mapping: SaveOnDisk<ShardKeyMapping> =
             SaveOnDisk::load_or_init(collection_path.join(SHARD_KEY_MAPPING_FILE))?;
         let mut shard_id_to_key_mapping = HashMap::new();
@@ -66,6 +74,7 @@ impl ShardHolder {
         Ok(Self {
             shards: HashMap::new(),
             shard_transfers,
+            resharding_state,
             rings,
             key_mapping,
             shard_id_to_key_mapping,
@@ -104,7 +113,7 @@ impl ShardHolder {
         self.shards.insert(shard_id, shard);
         self.rings
             .entry(shard_key.clone())
-            .or_insert_with(|| HashRing::fair(HASH_RING_SHARD_SCALE))
+            .or_insert_with(|| HashRingRouter::Single(HashRing::fair(HASH_RING_SHARD_SCALE)))
             .add(shard_id);
 
         if let Some(shard_key) = shard_key {
@@ -127,6 +136,20 @@ impl ShardHolder {
         Ok(())
     }
 
+    /// Drop the shard and remove it from the shard key mapping
+    pub async fn remove_shard(&mut self, shard_id: ShardId) -> Result<(), CollectionError> {
+        if let Some(shard_key) = self.shard_id_to_key_mapping.remove(&shard_id) {
+            self.key_mapping.write_optional(|key_mapping| {
+                let mut copy_of_mapping = key_mapping.clone();
+                let shard_ids = copy_of_mapping.get_mut(&shard_key)?;
+                shard_ids.remove(&shard_id).then_some(copy_of_mapping)
+            })?;
+        }
+        self.drop_and_remove_shard(shard_id).await?;
+        self.rebuild_rings();
+        Ok(())
+    }
+
     pub async fn remove_shard_key(&mut self, shard_key: &ShardKey) -> Result<(), CollectionError> {
         let mut remove_shard_ids = Vec::new();
 
@@ -152,19 +175,161 @@ impl ShardHolder {
         Ok(())
     }
 
+    /// Build hash rings of all shard keys, and read filters of shards affected by resharding
     fn rebuild_rings(&mut self) {
-        let mut rings = HashMap::new();
-        rings.insert(None, HashRing::fair(HASH_RING_SHARD_SCALE));
+        let mut shard_ids_by_key: HashMap<Option<ShardKey>, Vec<ShardId>> = HashMap::new();
+        shard_ids_by_key.insert(None, Vec::new());
         let ids_to_key = self.get_shard_id_to_key_mapping();
-        for shard_id in self.shards.keys() {
+        for shard_id in self.shards.keys().sorted() {
             let shard_key = ids_to_key.get(shard_id).cloned();
-            rings
+            shard_ids_by_key
                 .entry(shard_key)
-                .or_insert_with(|| HashRing::fair(HASH_RING_SHARD_SCALE))
-                .add(*shard_id);
+                .or_default()
+                .push(*shard_id);
+        }
+
+        let resharding_state = self.resharding_state.read().clone();
+
+        let mut rings = HashMap::new();
+        for (shard_key, shard_ids) in shard_ids_by_key {
+            let ring = match &resharding_state {
+                Some(state)
+                    if state.shard_key == shard_key
+                        && state.stage < ReshardStage::WriteHashRingCommitted =>
+                {
+                    HashRingRouter::Resharding {
+                        old: Self::resharding_ring(&shard_ids, state, false),
+                        new: Self::resharding_ring(&shard_ids, state, true),
+                    }
+                }
+                Some(state) if state.shard_key == shard_key => {
+                    HashRingRouter::Single(Self::resharding_ring(&shard_ids, state, true))
+                }
+                _ => HashRingRouter::Single(Self::hash_ring(shard_ids.iter().copied())),
+            };
+            rings.insert(shard_key, ring);
         }
 
         self.rings = rings;
+
+        for (shard_id, replica_set) in &self.shards {
+            replica_set.set_read_filter(self.read_filter(*shard_id));
+        }
+    }
+
+    fn hash_ring(shard_ids: impl IntoIterator<Item = ShardId>) -> HashRing<ShardId> {
+        let mut ring = HashRing::fair(HASH_RING_SHARD_SCALE);
+        for shard_id in shard_ids {
+            ring.add(shard_id);
+        }
+        ring
+    }
+
+    /// Hash ring of the shard key before (`is_new == false`) or after resharding
+    ///
+    /// Only one of the rings contains the resharded shard: the new one when adding a shard,
+    /// the old one when removing it.
+    fn resharding_ring(
+        shard_ids: &[ShardId],
+        state: &ReshardState,
+        is_new: bool,
+    ) -> HashRing<ShardId> {
+        let with_resharded_shard = match state.direction {
+            ReshardingDirection::Up => is_new,
+            ReshardingDirection::Down => !is_new,
+        };
+        Self::hash_ring(
+            shard_ids
+                .iter()
+                .copied()
+                .filter(|shard_id| with_resharded_shard || *shard_id != state.shard_id),
+        )
+    }
+
+    /// Sorted ids of the shards with the given shard key, or without a shard key
+    pub fn shard_ids_by_key(&self, shard_key: Option<&ShardKey>) -> Vec<ShardId> {
+        self.shards
+            .keys()
+            .copied()
+            .filter(|shard_id| self.shard_id_to_key_mapping.get(shard_id) == shard_key)
+            .sorted()
+            .collect()
+    }
+
+    /// Filter of the points, which are routed to the given shard by the new hash ring of the
+    /// ongoing resharding
+    pub fn resharding_filter(&self, shard_id: ShardId) -> Option<HashRingFilter> {
+        let state = self.resharding_state.read().clone()?;
+        let shard_ids = self.shard_ids_by_key(state.shard_key.as_ref());
+        if !shard_ids.contains(&shard_id) {
+            return None;
+        }
+        let ring = Self::resharding_ring(&shard_ids, &state, true);
+        Some(HashRingFilter::new(ring, shard_id))
+    }
+
+    /// Filter of the points, which are routed to the given shard by the old hash ring of the
+    /// ongoing resharding
+    pub fn resharding_old_filter(&self, shard_id: ShardId) -> Option<HashRingFilter> {
+        let state = self.resharding_state.read().clone()?;
+        let shard_ids = self.shard_ids_by_key(state.shard_key.as_ref());
+        if !shard_ids.contains(&shard_id) {
+            return None;
+        }
+        let ring = Self::resharding_ring(&shard_ids, &state, false);
+        Some(HashRingFilter::new(ring, shard_id))
+    }
+
+    /// Filter to apply to the local reads of the shard, if it stores points of other shards
+    fn read_filter(&self, shard_id: ShardId) -> Option<CustomIdCheckerCondition> {
+        let state = self.resharding_state.read().clone()?;
+        let filter = if state.filter_reads_by_new_ring(shard_id) {
+            self.resharding_filter(shard_id)?
+        } else if state.filter_reads_by_old_ring(shard_id) {
+            self.resharding_old_filter(shard_id)?
+        } else {
+            return None;
+        };
+        Some(CustomIdCheckerCondition::new(filter))
+    }
+
+    pub fn resharding_state(&self) -> Option<ReshardState> {
+        self.resharding_state.read().clone()
+    }
+
+    /// Mark the migration of the finished resharding transfer as done
+    ///
+    /// Returns `true` if a migration was marked.
+    pub fn finish_resharding_migration(&self, transfer: &ShardTransfer) -> CollectionResult<bool> {
+        let Some(to_shard_id) = transfer.to_shard_id else {
+            return Ok(false);
+        };
+        Ok(self.resharding_state.write(|state| {
+            let Some(state) = state else {
+                return false;
+            };
+            let migration = state.migrations.iter_mut().find(|migration| {
+                !migration.done
+                    && migration.from_shard_id == transfer.shard_id
+                    && migration.to_shard_id == to_shard_id
+                    && migration.to_peer_id == transfer.to
+            });
+            match migration {
+                Some(migration) => {
+                    migration.done = true;
+                    true
+                }
+                None => false,
+            }
+        })?)
+    }
+
+    /// Persist the new state of the resharding operation and rebuild hash rings accordingly
+    pub fn set_resharding_state(&mut self, state: Option<ReshardState>) -> CollectionResult<()> {
+        self.resharding_state
+            .write(|resharding| *resharding = state)?;
+        self.rebuild_rings();
+        Ok(())
     }
 
     pub async fn apply_shards_state(
@@ -237,7 +402,7 @@ impl ShardHolder {
             ));
         }
 
-        let operation_to_shard = operation.split_by_shard(hashring);
+        let operation_to_shard = operation.split_by_shard(hashring.owner_ring());
         let shard_ops: Vec<_> = match operation_to_shard {
             OperationToShard::ByShard(by_shard) => by_shard
                 .into_iter()
@@ -265,6 +430,53 @@ impl ShardHolder {
         Ok(shard_ops)
     }
 
+    /// Copies of the operation for the points, which change their shard in the ongoing resharding
+    ///
+    /// While resharding, updates are applied to both the current and the future owner of a
+    /// point. [`ShardHolder::split_by_shard`] covers the current owners, this covers the future
+    /// ones. Operations not bound to specific points are already sent to all shards.
+    pub fn split_by_resharding<O: SplitByShard + Clone>(
+        &self,
+        operation: O,
+        shard_keys_selection: &Option<ShardKey>,
+    ) -> Vec<(&ShardReplicaSet, O)> {
+        let Some(HashRingRouter::Resharding { old, new }) = self.rings.get(shard_keys_selection)
+        else {
+            return Vec::new();
+        };
+        let Some(state) = self.resharding_state.read().clone() else {
+            return Vec::new();
+        };
+
+        let moved = match state.direction {
+            // Points move from any shard into the new one
+            ReshardingDirection::Up => match operation.split_by_shard(new) {
+                OperationToShard::ByShard(by_shard) => by_shard
+                    .into_iter()
+                    .filter(|(shard_id, _)| *shard_id == state.shard_id)
+                    .collect(),
+                OperationToShard::ToAll(_) => Vec::new(),
+            },
+            // Points move from the removed shard into any other shard
+            ReshardingDirection::Down => match operation.split_by_shard(old) {
+                OperationToShard::ByShard(by_shard) => by_shard
+                    .into_iter()
+                    .filter(|(shard_id, _)| *shard_id == state.shard_id)
+                    .flat_map(|(_, operation)| match operation.split_by_shard(new) {
+                        OperationToShard::ByShard(by_shard) => by_shard,
+                        OperationToShard::ToAll(_) => Vec::new(),
+                    })
+                    .collect(),
+                OperationToShard::ToAll(_) => Vec::new(),
+            },
+        };
+
+        moved
+            .into_iter()
+            .filter_map(|(shard_id, operation)| Some((self.shards.get(&shard_id)?, operation)))
+            .collect()
+    }
+
     pub fn register_start_shard_transfer(&self, transfer: ShardTransfer) -> CollectionResult<bool> {
         Ok(self
             .shard_transfers
@@ -308,6 +520,7 @@ impl ShardHolder {
             let status = tasks_pool.get_task_status(&shard_transfer.key());
             shard_transfers.push(ShardTransferInfo {
                 shard_id,
+                to_shard_id: shard_transfer.to_shard_id,
                 from,
                 to,
                 sync,
@@ -327,7 +540,9 @@ impl ShardHolder {
         self.shard_transfers
             .read()
             .iter()
-            .filter(|transfer| transfer.shard_id == *shard_id)
+            .filter(|transfer| {
+                transfer.shard_id == *shard_id || transfer.to_shard_id == Some(*shard_id)
+            })
             .filter(|transfer| transfer.from == *peer_id || transfer.to == *peer_id)
             .cloned()
             .collect()
@@ -348,18 +563,31 @@ impl ShardHolder {
     ) -> CollectionResult<Vec<(&ShardReplicaSet, Option<&ShardKey>)>> {
         let mut res = Vec::new();
 
+        let resharding_state = self.resharding_state.read().clone();
+        let is_hidden = |shard_id: &ShardId| {
+            resharding_state
+                .as_ref()
+                .map_or(false, |state| state.is_hidden_for_reads(*shard_id))
+        };
+
         match shard_selector {
             ShardSelectorInternal::Empty => {
                 debug_assert!(false, "Do not expect empty shard selector")
             }
             ShardSelectorInternal::All => {
                 for (shard_id, shard) in self.shards.iter() {
+                    if is_hidden(shard_id) {
+                        continue;
+                    }
                     let shard_key = self.shard_id_to_key_mapping.get(shard_id);
                     res.push((shard, shard_key));
                 }
             }
             ShardSelectorInternal::ShardKey(shard_key) => {
                 for shard_id in self.get_shard_ids_by_key(shard_key)? {
+                    if is_hidden(&shard_id) {
+                        continue;
+                    }
                     if let Some(replica_set) = self.shards.get(&shard_id) {
                         res.push((replica_set, Some(shard_key)));
                     } else {
@@ -370,6 +598,9 @@ impl ShardHolder {
             ShardSelectorInternal::ShardKeys(shard_keys) => {
                 for shard_key in shard_keys {
                     for shard_id in self.get_shard_ids_by_key(shard_key)? {
+                        if is_hidden(&shard_id) {
+                            continue;
+                        }
                         if let Some(replica_set) = self.shards.get(&shard_id) {
                             res.push((replica_set, Some(shard_key)));
                         } else {
@@ -552,6 +783,9 @@ impl ShardHolder {
                 self.add_shard(shard_id, replica_set, shard_key).unwrap();
             }
         }
+
+        // Routing depends on the ongoing resharding, if any
+        self.rebuild_rings();
     }
 
     pub async fn assert_shard_exists(&self, shard_id: ShardId) -> CollectionResult<()> {
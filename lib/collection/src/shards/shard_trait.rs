@@ -2,11 +2,12 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::order_by::MultiOrderBy;
 use segment::types::*;
 use tokio::runtime::Handle;
 
 use crate::operations::types::*;
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 
 #[async_trait]
@@ -26,7 +27,7 @@ pub trait ShardOperation {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-        order_by: Option<&OrderBy>,
+        order_by: Option<&MultiOrderBy>,
     ) -> CollectionResult<Vec<Record>>;
 
     async fn info(&self) -> CollectionResult<CollectionInfo>;
@@ -38,8 +39,31 @@ pub trait ShardOperation {
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>;
 
+    /// Perform the universal query: search with leaf prefetches, then score their results
+    async fn query(
+        &self,
+        request: Arc<ShardQueryRequest>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ShardQueryResponse>;
+
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult>;
 
+    /// Count points per value of a payload field
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResponse>;
+
+    /// Pick random points which satisfy the filter
+    async fn sample(
+        &self,
+        request: Arc<SampleRequestInternal>,
+    ) -> CollectionResult<Vec<SampledRecord>>;
+
+    /// Collect statistics of sparse vectors, required to compute IDF
+    async fn sparse_statistics(
+        &self,
+        request: Arc<SparseStatisticsRequest>,
+    ) -> CollectionResult<SparseStatistics>;
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -42,16 +42,19 @@ pub async fn transfer_shard(
 ) -> CollectionResult<()> {
     let shard_id = transfer_config.shard_id;
 
-    // Initiate shard on a remote peer
+    // Initiate shard on a remote peer, resharding transfers target a replica of another shard
     let remote_shard = RemoteShard::new(
-        shard_id,
+        transfer_config.to_shard_id.unwrap_or(shard_id),
         collection_id.clone(),
         transfer_config.to,
         channel_service.clone(),
     );
 
     // Prepare the remote for receiving the shard, waits for the correct state on the remote
-    remote_shard.initiate_transfer().await?;
+    // Target of a resharding transfer is an existing replica, which is not prepared
+    if !transfer_config.is_resharding() {
+        remote_shard.initiate_transfer().await?;
+    }
 
     match transfer_config.method.unwrap_or_default() {
         // Transfer shard record in batches
@@ -60,6 +63,25 @@ pub async fn transfer_shard(
                 shard_holder.clone(),
                 progress,
                 shard_id,
+                None,
+                remote_shard,
+                collection_name,
+            )
+            .await?;
+        }
+
+        // Transfer the points of the shard, which are routed to another shard by resharding
+        ShardTransferMethod::ReshardingStreamRecords => {
+            let Some(to_shard_id) = transfer_config.to_shard_id else {
+                return Err(CollectionError::bad_request(format!(
+                    "Resharding transfer of shard {shard_id} has no target shard"
+                )));
+            };
+            transfer_stream_records(
+                shard_holder.clone(),
+                progress,
+                shard_id,
+                Some(to_shard_id),
                 remote_shard,
                 collection_name,
             )
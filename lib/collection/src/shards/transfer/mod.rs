@@ -9,6 +9,7 @@ use tokio::time::{sleep, sleep_until, timeout_at};
 use super::channel_service::ChannelService;
 use super::remote_shard::RemoteShard;
 use super::replica_set::ReplicaState;
+use super::resharding::ReshardKey;
 use super::shard::{PeerId, ShardId};
 use super::CollectionId;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -40,6 +41,9 @@ pub struct ShardTransfer {
     /// Method to transfer shard with. `None` to choose automatically.
     #[serde(default)]
     pub method: Option<ShardTransferMethod>,
+    /// Target shard of the resharding transfer, points are moved between different shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_shard_id: Option<ShardId>,
 }
 
 impl ShardTransfer {
@@ -48,8 +52,14 @@ impl ShardTransfer {
             shard_id: self.shard_id,
             from: self.from,
             to: self.to,
+            to_shard_id: self.to_shard_id,
         }
     }
+
+    /// Whether this transfer moves points of a resharding operation into another shard
+    pub fn is_resharding(&self) -> bool {
+        self.to_shard_id.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -58,6 +68,8 @@ pub struct ShardTransferRestart {
     pub from: PeerId,
     pub to: PeerId,
     pub method: ShardTransferMethod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_shard_id: Option<ShardId>,
 }
 
 impl ShardTransferRestart {
@@ -66,6 +78,7 @@ impl ShardTransferRestart {
             shard_id: self.shard_id,
             from: self.from,
             to: self.to,
+            to_shard_id: self.to_shard_id,
         }
     }
 }
@@ -76,6 +89,8 @@ pub struct ShardTransferKey {
     pub shard_id: ShardId,
     pub from: PeerId,
     pub to: PeerId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_shard_id: Option<ShardId>,
 }
 
 impl ShardTransferKey {
@@ -96,6 +111,9 @@ pub enum ShardTransferMethod {
     /// Attempt to transfer shard difference by WAL delta.
     #[schemars(skip)]
     WalDelta,
+    /// Stream records, which are moved to another shard by resharding, in batches.
+    #[schemars(skip)]
+    ReshardingStreamRecords,
 }
 
 /// Interface to consensus for shard transfer operations.
@@ -121,6 +139,67 @@ pub trait ShardTransferConsensus: Send + Sync {
         collection_name: CollectionId,
     ) -> CollectionResult<()>;
 
+    /// Propose to start the given shard transfer
+    ///
+    /// # Warning
+    ///
+    /// This only submits a proposal to consensus. Calling this does not guarantee that consensus
+    /// will actually apply the operation across the cluster.
+    fn start_shard_transfer(
+        &self,
+        transfer_config: ShardTransfer,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()>;
+
+    /// Propose to switch reads to the new hash ring of the resharding operation
+    ///
+    /// # Warning
+    ///
+    /// This only submits a proposal to consensus. Calling this does not guarantee that consensus
+    /// will actually apply the operation across the cluster.
+    fn commit_read_hashring(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()>;
+
+    /// Propose to switch writes to the new hash ring of the resharding operation
+    ///
+    /// # Warning
+    ///
+    /// This only submits a proposal to consensus. Calling this does not guarantee that consensus
+    /// will actually apply the operation across the cluster.
+    fn commit_write_hashring(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()>;
+
+    /// Propose to finish the resharding operation
+    ///
+    /// # Warning
+    ///
+    /// This only submits a proposal to consensus. Calling this does not guarantee that consensus
+    /// will actually apply the operation across the cluster.
+    fn finish_resharding(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()>;
+
+    /// Propose to abort the resharding operation
+    ///
+    /// # Warning
+    ///
+    /// This only submits a proposal to consensus. Calling this does not guarantee that consensus
+    /// will actually apply the operation across the cluster.
+    fn abort_resharding(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+        reason: &str,
+    ) -> CollectionResult<()>;
+
     /// After snapshot recovery, propose to switch shard to `Partial` and confirm on remote shard
     ///
     /// This is called after shard snapshot recovery has been completed on the remote. It submits a
@@ -238,7 +317,7 @@ pub trait ShardTransferConsensus: Send + Sync {
 /// # Cancel safety
 ///
 /// This function is cancel safe.
-async fn await_consensus_sync(
+pub(crate) async fn await_consensus_sync(
     consensus: &dyn ShardTransferConsensus,
     channel_service: &ChannelService,
     this_peer_id: PeerId,
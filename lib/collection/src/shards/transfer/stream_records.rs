@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use segment::types::{Condition, CustomIdCheckerCondition, Filter};
 
 use super::transfer_tasks_pool::TransferTaskProgress;
 use crate::operations::types::{CollectionError, CollectionResult, CountRequestInternal};
@@ -18,6 +19,9 @@ const TRANSFER_BATCH_SIZE: usize = 100;
 /// This first transfers configured indices. Then it transfers all point records in batches.
 /// Updates to the local shard are forwarded to the remote concurrently.
 ///
+/// If `to_shard_id` is given, this is a resharding transfer. Only the points routed to that
+/// shard by the new hash ring are transferred into its replica on the remote.
+///
 /// # Cancel safety
 ///
 /// This function is cancel safe.
@@ -25,6 +29,7 @@ pub(super) async fn transfer_stream_records(
     shard_holder: Arc<LockedShardHolder>,
     progress: Arc<Mutex<TransferTaskProgress>>,
     shard_id: ShardId,
+    to_shard_id: Option<ShardId>,
     remote_shard: RemoteShard,
     collection_name: &str,
 ) -> CollectionResult<()> {
@@ -42,11 +47,34 @@ pub(super) async fn transfer_stream_records(
             )));
         };
 
-        replica_set.proxify_local(remote_shard.clone()).await?;
+        let resharding_filter = match to_shard_id {
+            Some(to_shard_id) => {
+                let Some(filter) = shard_holder.resharding_filter(to_shard_id) else {
+                    return Err(CollectionError::service_error(format!(
+                        "Shard {shard_id} cannot be resharded into shard {to_shard_id}, no resharding is in progress"
+                    )));
+                };
+                Some(filter)
+            }
+            None => None,
+        };
+
+        // Count only the points which are going to be transferred
+        let count_filter = to_shard_id
+            .and_then(|to_shard_id| shard_holder.resharding_filter(to_shard_id))
+            .map(|filter| {
+                Filter::new_must(Condition::CustomIdChecker(CustomIdCheckerCondition::new(
+                    filter,
+                )))
+            });
+
+        replica_set
+            .proxify_local(remote_shard.clone(), resharding_filter)
+            .await?;
 
         let Some(count_result) = replica_set
             .count_local(Arc::new(CountRequestInternal {
-                filter: None,
+                filter: count_filter,
                 exact: true,
             }))
             .await?
@@ -95,7 +123,8 @@ pub(super) async fn transfer_stream_records(
     }
 
     // Update cutoff point on remote shard, disallow recovery before our current last seen
-    {
+    // The target of a resharding transfer has its own recovery point, which is kept as is
+    if to_shard_id.is_none() {
         let shard_holder = shard_holder.read().await;
         let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
             // Forward proxy gone?!
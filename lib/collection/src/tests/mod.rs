@@ -1,3 +1,4 @@
+mod resharding_test;
 mod sha_256_test;
 mod snapshot_test;
 mod sparse_vectors_validation_tests;
@@ -0,0 +1,499 @@
+use std::collections::{HashMap, HashSet};
+use std::num::{NonZeroU32, NonZeroU64};
+
+use async_trait::async_trait;
+use common::cpu::CpuBudget;
+use segment::types::{Distance, PointIdType};
+use tempfile::Builder;
+
+use super::snapshot_test::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer,
+    TEST_OPTIMIZERS_CONFIG,
+};
+use crate::collection::Collection;
+use crate::config::{CollectionConfig, CollectionParams, WalConfig};
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CountRequestInternal, VectorParams,
+};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::channel_service::ChannelService;
+use crate::shards::collection_shard_distribution::CollectionShardDistribution;
+use crate::shards::resharding::{ReshardKey, ReshardStage, ReshardingDirection};
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::{ShardTransfer, ShardTransferConsensus, ShardTransferMethod};
+use crate::shards::CollectionId;
+
+const THIS_PEER_ID: PeerId = 1;
+
+const NUM_POINTS: u64 = 200;
+
+/// Consensus, which refuses every proposal
+///
+/// The resharding driver gives up on its first proposal, so tests apply every step of the
+/// resharding operation themselves.
+struct RefusingConsensus;
+
+#[async_trait]
+impl ShardTransferConsensus for RefusingConsensus {
+    fn consensus_commit_term(&self) -> (u64, u64) {
+        (0, 0)
+    }
+
+    fn snapshot_recovered_switch_to_partial(
+        &self,
+        _transfer_config: &ShardTransfer,
+        _collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+
+    fn start_shard_transfer(
+        &self,
+        _transfer_config: ShardTransfer,
+        _collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+
+    fn commit_read_hashring(
+        &self,
+        _reshard_key: ReshardKey,
+        _collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+
+    fn commit_write_hashring(
+        &self,
+        _reshard_key: ReshardKey,
+        _collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+
+    fn finish_resharding(
+        &self,
+        _reshard_key: ReshardKey,
+        _collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+
+    fn abort_resharding(
+        &self,
+        _reshard_key: ReshardKey,
+        _collection_name: CollectionId,
+        _reason: &str,
+    ) -> CollectionResult<()> {
+        Err(no_consensus())
+    }
+}
+
+fn no_consensus() -> CollectionError {
+    CollectionError::service_error("No consensus in test".to_string())
+}
+
+async fn resharding_collection(
+    collection_dir: &std::path::Path,
+    shards: HashMap<ShardId, HashSet<PeerId>>,
+    replication_factor: u32,
+) -> Collection {
+    let collection_params = CollectionParams {
+        vectors: VectorParams {
+            size: NonZeroU64::new(4).unwrap(),
+            distance: Distance::Dot,
+            hnsw_config: None,
+            quantization_config: None,
+            on_disk: None,
+            multivector_config: None,
+            datatype: None,
+        }
+        .into(),
+        shard_number: NonZeroU32::new(shards.len() as u32).unwrap(),
+        replication_factor: NonZeroU32::new(replication_factor).unwrap(),
+        ..CollectionParams::empty()
+    };
+
+    let config = CollectionConfig {
+        params: collection_params,
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+    };
+
+    Collection::new(
+        "test".to_string(),
+        THIS_PEER_ID,
+        collection_dir,
+        &collection_dir.join("snapshots"),
+        &config,
+        Default::default(),
+        CollectionShardDistribution { shards },
+        ChannelService::default(),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+    )
+    .await
+    .unwrap()
+}
+
+fn local_shards(shard_number: ShardId) -> HashMap<ShardId, HashSet<PeerId>> {
+    (0..shard_number)
+        .map(|shard_id| (shard_id, HashSet::from([THIS_PEER_ID])))
+        .collect()
+}
+
+fn upsert_operation() -> CollectionUpdateOperations {
+    let points = (0..NUM_POINTS)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: vec![1.0, 0.0, 1.0, id as f32].into(),
+            payload: None,
+        })
+        .collect();
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+async fn upsert_points(collection: &Collection) {
+    collection
+        .update_from_client_simple(upsert_operation(), true, WriteOrdering::default())
+        .await
+        .unwrap();
+}
+
+async fn count_points(collection: &Collection) -> usize {
+    collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap()
+        .count
+}
+
+/// Pretend all point migrations of the ongoing resharding are done
+async fn finish_migrations(collection: &Collection) {
+    let shard_holder = collection.shards_holder.read().await;
+    let state = shard_holder.resharding_state().unwrap();
+    for migration in state.migrations {
+        let transfer = ShardTransfer {
+            shard_id: migration.from_shard_id,
+            to_shard_id: Some(migration.to_shard_id),
+            from: THIS_PEER_ID,
+            to: migration.to_peer_id,
+            sync: true,
+            method: Some(ShardTransferMethod::ReshardingStreamRecords),
+        };
+        assert!(shard_holder.finish_resharding_migration(&transfer).unwrap());
+    }
+}
+
+/// Point ids of the upsert operation, which are copied to other shards by resharding
+async fn moved_point_ids(collection: &Collection) -> HashMap<ShardId, HashSet<PointIdType>> {
+    let shard_holder = collection.shards_holder.read().await;
+    let mut moved: HashMap<ShardId, HashSet<PointIdType>> = HashMap::new();
+    for (replica_set, operation) in shard_holder.split_by_resharding(upsert_operation(), &None) {
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(points)) =
+            operation
+        else {
+            panic!("unexpected operation: {operation:?}");
+        };
+        moved
+            .entry(replica_set.shard_id)
+            .or_default()
+            .extend(points.point_ids());
+    }
+    moved
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resharding_up_stages() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = resharding_collection(collection_dir.path(), local_shards(2), 1).await;
+    upsert_points(&collection).await;
+
+    let key = collection
+        .reshard_key(ReshardingDirection::Up, None, None)
+        .await
+        .unwrap();
+    assert_eq!(key.shard_id, 2);
+    assert_eq!(key.peer_id, THIS_PEER_ID);
+
+    collection
+        .start_resharding(key.clone(), Box::new(RefusingConsensus))
+        .await
+        .unwrap();
+
+    let state = collection.resharding_state().await.unwrap();
+    assert_eq!(state.stage, ReshardStage::MigratingPoints);
+    assert_eq!(state.migrations.len(), 2);
+    assert!(collection.contains_shard(2).await);
+
+    // Another resharding can't be started in the meantime
+    assert!(collection
+        .reshard_key(ReshardingDirection::Down, None, None)
+        .await
+        .is_err());
+
+    // Updates of the points routed to the new shard are copied into it
+    let moved = moved_point_ids(&collection).await;
+    assert_eq!(moved.keys().copied().collect::<Vec<_>>(), vec![2]);
+    let new_filter = collection
+        .shards_holder
+        .read()
+        .await
+        .resharding_filter(2)
+        .unwrap();
+    let expected: HashSet<_> = (0..NUM_POINTS)
+        .map(PointIdType::from)
+        .filter(|id| new_filter.check(*id))
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(moved[&2], expected);
+
+    upsert_points(&collection).await;
+
+    // New shard is not read before its points are migrated
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+
+    // Stages can't be skipped
+    assert!(collection.commit_write_hashring(&key).await.is_err());
+    assert!(collection.commit_read_hashring(&key).await.is_err());
+    assert!(collection.finish_resharding(&key).await.is_err());
+
+    finish_migrations(&collection).await;
+    collection.commit_read_hashring(&key).await.unwrap();
+    assert_eq!(
+        collection.resharding_state().await.unwrap().stage,
+        ReshardStage::ReadHashRingCommitted,
+    );
+
+    // Old shards only read the points they keep, the new shard reads the moved ones
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+
+    assert!(collection.finish_resharding(&key).await.is_err());
+    collection.commit_write_hashring(&key).await.unwrap();
+    assert_eq!(
+        collection.resharding_state().await.unwrap().stage,
+        ReshardStage::WriteHashRingCommitted,
+    );
+
+    // Resharding can't be aborted once writes use the new hash ring
+    assert!(collection.abort_resharding(&key, "test").await.is_err());
+
+    collection.finish_resharding(&key).await.unwrap();
+    assert!(collection.resharding_state().await.is_none());
+    assert!(collection.contains_shard(2).await);
+    assert_eq!(
+        collection
+            .collection_config
+            .read()
+            .await
+            .params
+            .shard_number
+            .get(),
+        3,
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resharding_up_abort_while_migrating() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = resharding_collection(collection_dir.path(), local_shards(2), 1).await;
+    upsert_points(&collection).await;
+
+    let key = collection
+        .reshard_key(ReshardingDirection::Up, None, None)
+        .await
+        .unwrap();
+    collection
+        .start_resharding(key.clone(), Box::new(RefusingConsensus))
+        .await
+        .unwrap();
+    upsert_points(&collection).await;
+
+    collection.abort_resharding(&key, "test").await.unwrap();
+
+    assert!(collection.resharding_state().await.is_none());
+    assert!(!collection.contains_shard(2).await);
+    assert_eq!(
+        collection
+            .collection_config
+            .read()
+            .await
+            .params
+            .shard_number
+            .get(),
+        2,
+    );
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+
+    // Resharding can be started again
+    let key = collection
+        .reshard_key(ReshardingDirection::Up, None, None)
+        .await
+        .unwrap();
+    assert_eq!(key.shard_id, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resharding_up_replication_factor() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let shards = HashMap::from([
+        (0, HashSet::from([THIS_PEER_ID, 10_000])),
+        (1, HashSet::from([THIS_PEER_ID, 20_000])),
+    ]);
+    let collection = resharding_collection(collection_dir.path(), shards, 2).await;
+
+    let key = ReshardKey {
+        peer_id: THIS_PEER_ID,
+        shard_id: 2,
+        shard_key: None,
+        direction: ReshardingDirection::Up,
+    };
+    collection
+        .start_resharding(key.clone(), Box::new(RefusingConsensus))
+        .await
+        .unwrap();
+
+    // New shard is replicated up to the replication factor, every replica receives all points
+    let replica_peers: HashSet<_> = collection
+        .shards_holder
+        .read()
+        .await
+        .get_shard(&2)
+        .unwrap()
+        .peers()
+        .into_keys()
+        .collect();
+    assert_eq!(replica_peers, HashSet::from([THIS_PEER_ID, 10_000]));
+
+    let migrations: HashSet<_> = collection
+        .resharding_state()
+        .await
+        .unwrap()
+        .migrations
+        .iter()
+        .map(|migration| (migration.from_shard_id, migration.to_peer_id))
+        .collect();
+    assert_eq!(
+        migrations,
+        HashSet::from([
+            (0, THIS_PEER_ID),
+            (0, 10_000),
+            (1, THIS_PEER_ID),
+            (1, 10_000),
+        ]),
+    );
+
+    collection.abort_resharding(&key, "test").await.unwrap();
+    assert!(!collection.contains_shard(2).await);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resharding_down_stages() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = resharding_collection(collection_dir.path(), local_shards(3), 1).await;
+    upsert_points(&collection).await;
+
+    let key = collection
+        .reshard_key(ReshardingDirection::Down, None, None)
+        .await
+        .unwrap();
+    assert_eq!(key.shard_id, 2);
+
+    collection
+        .start_resharding(key.clone(), Box::new(RefusingConsensus))
+        .await
+        .unwrap();
+    assert_eq!(
+        collection
+            .resharding_state()
+            .await
+            .unwrap()
+            .migrations
+            .len(),
+        2
+    );
+
+    // Updates of the points of the removed shard are copied into the remaining shards
+    let moved = moved_point_ids(&collection).await;
+    assert!(!moved.contains_key(&2));
+    let old_filter = collection
+        .shards_holder
+        .read()
+        .await
+        .resharding_old_filter(2)
+        .unwrap();
+    let expected: HashSet<_> = (0..NUM_POINTS)
+        .map(PointIdType::from)
+        .filter(|id| old_filter.check(*id))
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(
+        moved.values().flatten().copied().collect::<HashSet<_>>(),
+        expected,
+    );
+
+    upsert_points(&collection).await;
+
+    // Remaining shards don't read the copied points before reads switch to the new hash ring
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+
+    finish_migrations(&collection).await;
+    collection.commit_read_hashring(&key).await.unwrap();
+
+    // Removed shard is not read anymore, remaining shards read the copied points
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+
+    collection.commit_write_hashring(&key).await.unwrap();
+    collection.finish_resharding(&key).await.unwrap();
+
+    assert!(collection.resharding_state().await.is_none());
+    assert!(!collection.contains_shard(2).await);
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resharding_down_abort_while_migrating() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = resharding_collection(collection_dir.path(), local_shards(3), 1).await;
+    upsert_points(&collection).await;
+
+    let key = collection
+        .reshard_key(ReshardingDirection::Down, None, None)
+        .await
+        .unwrap();
+    collection
+        .start_resharding(key.clone(), Box::new(RefusingConsensus))
+        .await
+        .unwrap();
+    upsert_points(&collection).await;
+
+    collection.abort_resharding(&key, "test").await.unwrap();
+
+    // Points copied into the remaining shards are removed again
+    assert!(collection.resharding_state().await.is_none());
+    assert!(collection.contains_shard(2).await);
+    assert_eq!(count_points(&collection).await, NUM_POINTS as usize);
+}
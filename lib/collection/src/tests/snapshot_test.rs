@@ -55,6 +55,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            multivector_config: None,
+            datatype: None,
         }),
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
@@ -68,6 +70,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
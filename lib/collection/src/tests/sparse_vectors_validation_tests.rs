@@ -63,6 +63,7 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        preconditions: vec![],
     });
 }
 
@@ -71,6 +72,7 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        preconditions: vec![],
     });
 }
 
@@ -85,6 +87,7 @@ fn validate_error_sparse_vector_search_request_internal() {
         with_payload: None,
         with_vector: None,
         score_threshold: None,
+        mmr: None,
     });
 }
 
@@ -126,6 +129,7 @@ fn validate_error_sparse_vector_recommend_request_internal() {
         score_threshold: None,
         using: None,
         lookup_from: None,
+        mmr: None,
     });
 }
 
@@ -28,6 +28,8 @@ fn create_collection_config() -> CollectionConfig {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            multivector_config: None,
+            datatype: None,
         }),
         ..CollectionParams::empty()
     };
@@ -43,6 +45,7 @@ fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     }
 }
 
@@ -102,6 +105,7 @@ fn create_payload_index_operation() -> CollectionUpdateOperations {
 fn delete_point_operation(idx: u64) -> CollectionUpdateOperations {
     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
         ids: vec![idx.into()],
+        preconditions: vec![],
     })
 }
 
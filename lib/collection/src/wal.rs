@@ -133,15 +133,29 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
 
         (from..to).map(move |idx| {
             let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
+            (idx, Self::deserialize_record(&record_bin))
+        })
+    }
 
-            let record: R = serde_cbor::from_slice(&record_bin)
-                .or_else(|_err| rmp_serde::from_slice(&record_bin))
-                .expect("Can't deserialize entry, probably corrupted WAL or version mismatch");
+    /// Read serialized records starting at `from`
+    ///
+    /// Records are copied out of the WAL, so they can be deserialized with
+    /// [`SerdeWal::deserialize_record`] without holding the WAL.
+    pub fn read_raw(&self, from: u64) -> impl DoubleEndedIterator<Item = (u64, Vec<u8>)> + '_ {
+        let to = self.first_index() + self.len(false);
 
-            (idx, record)
+        (from..to).map(move |idx| {
+            let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
+            (idx, record_bin.to_vec())
         })
     }
 
+    pub fn deserialize_record(record_bin: &[u8]) -> R {
+        serde_cbor::from_slice(record_bin)
+            .or_else(|_err| rmp_serde::from_slice(record_bin))
+            .expect("Can't deserialize entry, probably corrupted WAL or version mismatch")
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len(false) == 0
     }
@@ -99,6 +99,7 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: true.into(),
                 order_by: None,
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -175,6 +176,7 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Fields(vec![path("k2")])),
                 with_vector: true.into(),
                 order_by: None,
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -210,6 +212,7 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
                 with_payload: Some(PayloadSelectorExclude::new(vec![path("k1")]).into()),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
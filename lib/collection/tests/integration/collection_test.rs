@@ -2,11 +2,14 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
-use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
+use collection::operations::point_ops::{
+    Batch, ConditionalInsertOperationInternal, PointOperations, PointPrecondition, PointStruct,
+    WriteOrdering,
+};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CountRequestInternal, OrderByInterface, PointRequestInternal, RecommendRequestInternal,
-    ScrollRequestInternal, SearchRequestInternal, UpdateStatus,
+    CountRequestInternal, Mmr, OrderByInterface, PointRequestInternal, RecommendRequestInternal,
+    Record, ScrollRequestInternal, SearchRequestInternal, UpdateStatus,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
@@ -73,6 +76,7 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let search_res = collection
@@ -137,6 +141,7 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let search_res = collection
@@ -180,6 +185,87 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
 }
 
 // FIXME: does not work
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_mmr() {
+    test_collection_search_mmr_with_shards(1).await;
+    test_collection_search_mmr_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_mmr_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    // Point 1 is almost a duplicate of point 0, point 2 is less relevant, but different
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into(), 2.into()],
+            vectors: vec![
+                vec![1.0, 1.0, 0.0, 0.0],
+                vec![0.9, 1.0, 0.0, 0.0],
+                vec![0.8, 0.0, 1.0, 0.0],
+            ]
+            .into(),
+            payloads: serde_json::from_str(r#"[{ "k": "v0" }, { "k": "v1" }, { "k": "v2" }]"#)
+                .unwrap(),
+        }
+        .into(),
+    );
+
+    let insert_result = collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await;
+
+    match insert_result {
+        Ok(res) => {
+            assert_eq!(res.status, UpdateStatus::Completed)
+        }
+        Err(err) => panic!("operation failed: {err:?}"),
+    }
+
+    let search_request = |mmr| SearchRequestInternal {
+        vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: None,
+        filter: None,
+        params: None,
+        limit: 2,
+        offset: None,
+        score_threshold: None,
+        mmr,
+    };
+
+    let collection = &collection;
+    let search_ids = |mmr| async move {
+        let res = collection
+            .search(
+                search_request(mmr).into(),
+                None,
+                &ShardSelectorInternal::All,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(res.iter().all(|point| point.payload.is_some()));
+        res.into_iter().map(|point| point.id).collect_vec()
+    };
+
+    assert_eq!(search_ids(None).await, vec![0.into(), 1.into()]);
+
+    let mmr = Mmr {
+        lambda: Some(0.5),
+        candidates_limit: None,
+    };
+    assert_eq!(search_ids(Some(mmr)).await, vec![0.into(), 2.into()]);
+
+    // Only relevance is taken into account
+    let mmr = Mmr {
+        lambda: Some(1.0),
+        candidates_limit: None,
+    };
+    assert_eq!(search_ids(Some(mmr)).await, vec![0.into(), 1.into()]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_loading() {
     test_collection_loading_with_shards(1).await;
@@ -223,6 +309,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
                 points: Some(vec![2.into(), 3.into()]),
                 filter: None,
                 key: None,
+                preconditions: vec![],
             }));
 
         collection
@@ -412,6 +499,7 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -540,6 +628,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         direction: Some(Direction::Asc),
                         start_from: None,
                     })),
+                    cursor: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -570,6 +659,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         direction: Some(Direction::Desc),
                         start_from: None,
                     })),
+                    cursor: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -609,6 +699,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         direction: Some(Direction::Asc),
                         start_from: None,
                     })),
+                    cursor: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -647,6 +738,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         direction: Some(Direction::Desc),
                         start_from: None,
                     })),
+                    cursor: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -684,6 +776,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
                 order_by: Some(OrderByInterface::Key(MULTI_VALUE_KEY.parse().unwrap())),
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -706,6 +799,181 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .all(|&x| x == 2));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_key_ordered_read_api() {
+    test_multi_key_ordered_scroll_api_with_shards(1).await;
+    test_multi_key_ordered_scroll_api_with_shards(N_SHARDS).await;
+}
+
+async fn test_multi_key_ordered_scroll_api_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const CATEGORY_KEY: &str = "category";
+    const PRICE_KEY: &str = "price";
+
+    let num_points = 30u64;
+    let categories = ["a", "b", "c"];
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: (0..num_points).map(|x| x.into()).collect_vec(),
+            vectors: (0..num_points)
+                .map(|x| vec![x as f32, 0.0, 0.0, 1.0])
+                .collect_vec()
+                .into(),
+            payloads: Some(
+                (0..num_points)
+                    .map(|x| {
+                        let mut payload_map = Map::new();
+                        payload_map.insert(
+                            CATEGORY_KEY.to_string(),
+                            categories[x as usize % categories.len()].into(),
+                        );
+                        // Many duplicated prices, the last points have no price at all
+                        if x < num_points - 3 {
+                            payload_map.insert(PRICE_KEY.to_string(), (x % 4).into());
+                        }
+                        Some(Payload(payload_map))
+                    })
+                    .collect(),
+            ),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            CATEGORY_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            PRICE_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let order_by = OrderByInterface::Multi(vec![
+        OrderBy {
+            key: CATEGORY_KEY.parse().unwrap(),
+            direction: Some(Direction::Desc),
+            start_from: None,
+        },
+        OrderBy {
+            key: PRICE_KEY.parse().unwrap(),
+            direction: Some(Direction::Asc),
+            start_from: None,
+        },
+    ]);
+
+    // Read all points page by page with the cursor
+    let mut cursor = None;
+    let mut pages = Vec::new();
+    loop {
+        let result = collection
+            .scroll_by(
+                ScrollRequestInternal {
+                    offset: None,
+                    limit: Some(7),
+                    filter: None,
+                    with_payload: Some(WithPayloadInterface::Bool(true)),
+                    with_vector: false.into(),
+                    order_by: Some(order_by.clone()),
+                    cursor: cursor.take(),
+                },
+                None,
+                &ShardSelectorInternal::All,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.next_page_offset, None);
+        pages.push(result.points);
+        match result.next_page_cursor {
+            Some(next_page_cursor) => cursor = Some(next_page_cursor),
+            None => break,
+        }
+    }
+
+    let points = pages.into_iter().flatten().collect_vec();
+    assert_eq!(points.len(), num_points as usize);
+
+    // Every point is seen once
+    let ids: HashSet<_> = points.iter().map(|point| point.id).collect();
+    assert_eq!(ids.len(), num_points as usize);
+
+    let sort_key = |point: &Record| {
+        let payload = point.payload.as_ref().unwrap();
+        let category = payload.0.get(CATEGORY_KEY).unwrap().as_str().unwrap();
+        let price = payload.0.get(PRICE_KEY).and_then(|price| price.as_i64());
+        (
+            std::cmp::Reverse(category.to_string()),
+            // Points without a price go last
+            price.unwrap_or(i64::MAX),
+            point.id,
+        )
+    };
+
+    assert!(
+        points
+            .iter()
+            .tuple_windows()
+            .all(|(a, b)| sort_key(a) < sort_key(b)),
+        "got: {:#?}",
+        points.iter().map(sort_key).collect_vec(),
+    );
+    assert!(points.iter().all(|point| point.order_values.is_some()));
+
+    // Cursor is only accepted with the same number of keys
+    let next_page_cursor = collection
+        .scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(3),
+                filter: None,
+                with_payload: None,
+                with_vector: false.into(),
+                order_by: Some(order_by),
+                cursor: None,
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap()
+        .next_page_cursor;
+    assert!(next_page_cursor.is_some());
+
+    let result = collection
+        .scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(3),
+                filter: None,
+                with_payload: None,
+                with_vector: false.into(),
+                order_by: Some(OrderByInterface::Key(CATEGORY_KEY.parse().unwrap())),
+                cursor: next_page_cursor,
+            },
+            None,
+            &ShardSelectorInternal::All,
+        )
+        .await;
+    assert!(result.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -776,6 +1044,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -790,6 +1059,48 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     assert_eq!(result.points.get(2).unwrap().id, 4.into());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_concurrent_conditional_upserts() {
+    test_collection_concurrent_conditional_upserts_with_shards(1).await;
+    test_collection_concurrent_conditional_upserts_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_concurrent_conditional_upserts_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let writers = 16;
+
+    // All writers create the same point, only if it doesn't exist yet
+    let upserts = (0..writers).map(|writer| {
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: Batch {
+                    ids: vec![1.into()],
+                    vectors: vec![vec![writer as f32, 0.0, 1.0, 1.0]].into(),
+                    payloads: None,
+                }
+                .into(),
+                preconditions: vec![PointPrecondition {
+                    id: 1.into(),
+                    if_version: None,
+                    if_not_exists: true,
+                }],
+            }),
+        );
+        collection.update_from_client_simple(operation, true, WriteOrdering::Weak)
+    });
+    let results = futures::future::join_all(upserts).await;
+
+    let winners = results
+        .into_iter()
+        .map(|result| result.unwrap())
+        .filter(|result| result.failed_preconditions.is_empty())
+        .count();
+    assert_eq!(winners, 1);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_local_load_initializing_not_stuck() {
     let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
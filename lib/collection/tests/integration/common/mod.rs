@@ -48,6 +48,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            multivector_config: None,
+            datatype: None,
         }
         .into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
@@ -60,6 +62,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequestInternal,
+};
+use collection::operations::CollectionUpdateOperations;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::types::{Condition, FieldCondition, Filter, PointIdType};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::common::{path, simple_collection_fixture, N_SHARDS};
+
+const NUM_POINTS: u64 = 50;
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_distance_matrix() {
+    test_distance_matrix_with_shards(1).await;
+    test_distance_matrix_with_shards(N_SHARDS).await;
+}
+
+async fn test_distance_matrix_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let vectors: HashMap<PointIdType, Vec<f32>> = (0..NUM_POINTS)
+        .map(|id| (id.into(), (0..4).map(|_| rng.gen_range(0.0..1.0)).collect()))
+        .collect();
+
+    let ids: Vec<_> = (0..NUM_POINTS).map(PointIdType::from).collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: ids.clone(),
+            vectors: ids
+                .iter()
+                .map(|id| vectors[id].clone())
+                .collect::<Vec<_>>()
+                .into(),
+            payloads: Some(
+                (0..NUM_POINTS)
+                    .map(|id| Some(json!({ "even": id % 2 == 0 }).into()))
+                    .collect(),
+            ),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Sparse matrix of the nearest neighbours among sampled even points
+    let request = SearchMatrixRequestInternal {
+        filter: Some(Filter::new_must(Condition::Field(
+            FieldCondition::new_match(path("even"), true.into()),
+        ))),
+        sample: Some(10),
+        ids: None,
+        limit: Some(3),
+        using: None,
+    };
+    let response = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.sample_ids.len(), 10);
+    let sample: HashSet<_> = response.sample_ids.iter().copied().collect();
+    assert_eq!(sample.len(), 10);
+
+    for (sample_id, nearests) in response.sample_ids.iter().zip(&response.nearests) {
+        let PointIdType::NumId(num_id) = sample_id else {
+            panic!("unexpected id {sample_id}");
+        };
+        assert_eq!(num_id % 2, 0);
+        assert_eq!(nearests.len(), 3);
+
+        // Nearest neighbours are the best scored points of the sample, except the point itself
+        let mut expected: Vec<_> = sample
+            .iter()
+            .filter(|id| *id != sample_id)
+            .map(|id| dot(&vectors[sample_id], &vectors[id]))
+            .collect();
+        expected.sort_by(|a, b| b.total_cmp(a));
+        for (nearest, expected_score) in nearests.iter().zip(expected) {
+            assert!(sample.contains(&nearest.id));
+            assert_ne!(nearest.id, *sample_id);
+            assert!((nearest.score - expected_score).abs() < 1e-5);
+        }
+    }
+
+    let pairs = SearchMatrixPairsResponse::from(response.clone());
+    assert_eq!(pairs.pairs.len(), 30);
+
+    let offsets = SearchMatrixOffsetsResponse::from(response);
+    assert_eq!(offsets.ids.len(), 10);
+    assert_eq!(offsets.offsets_row.len(), 30);
+    assert_eq!(offsets.offsets_col.len(), 30);
+    for ((row, col), score) in offsets
+        .offsets_row
+        .iter()
+        .zip(&offsets.offsets_col)
+        .zip(&offsets.scores)
+    {
+        let a = offsets.ids[*row as usize];
+        let b = offsets.ids[*col as usize];
+        assert!((dot(&vectors[&a], &vectors[&b]) - score).abs() < 1e-5);
+    }
+
+    // Full pairwise matrix of the given points, missing ones are skipped
+    let request = SearchMatrixRequestInternal {
+        filter: None,
+        sample: None,
+        ids: Some(vec![1.into(), 2.into(), 3.into(), 4.into(), 1000.into()]),
+        limit: None,
+        using: None,
+    };
+    let response = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await
+        .unwrap();
+
+    let sample: HashSet<_> = response.sample_ids.iter().copied().collect();
+    assert_eq!(
+        sample,
+        HashSet::from([1.into(), 2.into(), 3.into(), 4.into()])
+    );
+    assert!(response.nearests.iter().all(|nearests| nearests.len() == 3));
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{FacetRequestInternal, ScrollRequestInternal};
+use collection::operations::CollectionUpdateOperations;
+use segment::data_types::facets::{FacetValue, FacetValueHit};
+use segment::types::{PayloadFieldSchema, PayloadSchemaType, PointIdType};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::common::{path, simple_collection_fixture, N_SHARDS};
+
+const NUM_POINTS: u64 = 60;
+
+/// Points per shard with a value, which is only present in that shard
+const LOCAL_POINTS: usize = 4;
+
+/// Points per shard with a value, which is present in all shards
+const GLOBAL_POINTS: usize = 3;
+
+fn upsert_batch(
+    ids: Vec<PointIdType>,
+    payloads: Vec<Option<serde_json::Value>>,
+) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(
+        Batch {
+            vectors: ids
+                .iter()
+                .map(|_| vec![1.0, 0.0, 0.0, 0.0])
+                .collect::<Vec<_>>()
+                .into(),
+            payloads: Some(
+                payloads
+                    .into_iter()
+                    .map(|payload| payload.map(|payload| payload.into()))
+                    .collect(),
+            ),
+            ids,
+        }
+        .into(),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_facet_top_values_across_shards() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let ids: Vec<PointIdType> = (0..NUM_POINTS).map(PointIdType::from).collect();
+    collection
+        .update_from_client_simple(
+            upsert_batch(ids.clone(), vec![None; ids.len()]),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    // In each shard, the shard's own value is the most frequent one,
+    // but the value shared by all shards is the most frequent in the collection
+    let mut payloads: HashMap<PointIdType, serde_json::Value> = HashMap::new();
+    for shard_id in 0..N_SHARDS {
+        let shard_points = collection
+            .scroll_by(
+                ScrollRequestInternal {
+                    offset: None,
+                    limit: Some(LOCAL_POINTS + GLOBAL_POINTS),
+                    filter: None,
+                    with_payload: Some(false.into()),
+                    with_vector: false.into(),
+                    order_by: None,
+                    cursor: None,
+                },
+                None,
+                &ShardSelectorInternal::ShardId(shard_id),
+            )
+            .await
+            .unwrap()
+            .points;
+        assert_eq!(shard_points.len(), LOCAL_POINTS + GLOBAL_POINTS);
+
+        for (i, point) in shard_points.into_iter().enumerate() {
+            let kind = if i < LOCAL_POINTS {
+                format!("shard_{shard_id}")
+            } else {
+                "global".to_string()
+            };
+            payloads.insert(point.id, json!({ "kind": kind }));
+        }
+    }
+    collection
+        .update_from_client_simple(
+            upsert_batch(
+                ids.clone(),
+                ids.iter().map(|id| payloads.get(id).cloned()).collect(),
+            ),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            path("kind"),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let request = FacetRequestInternal {
+        key: path("kind"),
+        limit: Some(1),
+        filter: None,
+        exact: false,
+        ranges: None,
+    };
+
+    // Shard responses are not truncated, so they can be merged by the requesting peer
+    for shard_id in 0..N_SHARDS {
+        let response = collection
+            .facet(request.clone(), &ShardSelectorInternal::ShardId(shard_id))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.hits,
+            vec![
+                FacetValueHit {
+                    value: FacetValue::Keyword(format!("shard_{shard_id}")),
+                    count: LOCAL_POINTS,
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("global".to_string()),
+                    count: GLOBAL_POINTS,
+                },
+            ],
+        );
+    }
+
+    let response = collection
+        .facet(request, &ShardSelectorInternal::All)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.hits,
+        vec![FacetValueHit {
+            value: FacetValue::Keyword("global".to_string()),
+            count: GLOBAL_POINTS * N_SHARDS as usize,
+        }],
+    );
+}
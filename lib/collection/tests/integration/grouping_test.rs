@@ -41,6 +41,7 @@ mod group_by {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            mmr: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source, path("docId"), 3);
@@ -139,6 +140,7 @@ mod group_by {
                 negative: Vec::new(),
                 using: None,
                 lookup_from: None,
+                mmr: None,
             }),
             path("docId"),
             2,
@@ -199,6 +201,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             3,
@@ -231,6 +234,7 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             3,
@@ -269,6 +273,7 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                mmr: None,
             }),
             path("other_stuff"),
             3,
@@ -305,6 +310,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             0,
@@ -337,6 +343,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             3,
@@ -369,6 +376,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             3,
@@ -405,6 +413,7 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                mmr: None,
             }),
             path("docId"),
             400,
@@ -459,6 +468,7 @@ mod group_by_builder {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            mmr: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source_request, path("docId"), 3);
@@ -43,6 +43,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        multivector_config: None,
+        datatype: None,
     };
     let vector_params2 = VectorParams {
         size: NonZeroU64::new(4).unwrap(),
@@ -50,6 +52,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        multivector_config: None,
+        datatype: None,
     };
 
     let mut vectors_config = BTreeMap::new();
@@ -69,6 +73,7 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -128,6 +133,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let result = collection
@@ -161,6 +167,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let result = collection
@@ -190,6 +197,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let result = collection
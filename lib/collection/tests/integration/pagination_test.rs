@@ -51,6 +51,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let reference_result = collection
@@ -77,6 +78,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let page_1_result = collection
@@ -104,6 +106,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let page_9_result = collection
@@ -0,0 +1,204 @@
+use collection::collection::Collection;
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::universal_query::collection_query::QueryRequestInternal;
+use collection::operations::universal_query::shard_query::ShardQueryRequest;
+use collection::operations::CollectionUpdateOperations;
+use itertools::Itertools;
+use segment::types::{PointIdType, ScoredPoint};
+use tempfile::Builder;
+
+use crate::common::{simple_collection_fixture, N_SHARDS};
+
+async fn upsert_points(collection: &Collection) {
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..5u64).map(|x| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ]
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+}
+
+async fn query(collection: &Collection, request: &str) -> Vec<ScoredPoint> {
+    let request: QueryRequestInternal = serde_json::from_str(request).unwrap();
+    collection
+        .query(
+            ShardQueryRequest::try_from(request).unwrap(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_rescore_prefetch() {
+    let collection_dir = Builder::new()
+        .prefix("test_query_rescore")
+        .tempdir()
+        .unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+    upsert_points(&collection).await;
+
+    // Prefetch finds points 2, 0 and 3, only they are re-scored by the query
+    let result = query(
+        &collection,
+        r#"{
+            "prefetch": [{ "query": [1.0, 1.0, 1.0, 1.0], "limit": 3 }],
+            "query": [0.0, 1.0, 0.0, 0.0],
+            "limit": 10
+        }"#,
+    )
+    .await;
+
+    assert_eq!(result.len(), 3);
+    let top_ids: Vec<PointIdType> = result[..2].iter().map(|point| point.id).sorted().collect();
+    assert_eq!(top_ids, vec![2.into(), 3.into()]);
+    assert_eq!(result[0].score, 1.0);
+    assert_eq!(result[2].id, 0.into());
+    assert_eq!(result[2].score, 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_rrf_fusion() {
+    let collection_dir = Builder::new()
+        .prefix("test_query_fusion")
+        .tempdir()
+        .unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+    upsert_points(&collection).await;
+
+    let result = query(
+        &collection,
+        r#"{
+            "prefetch": [
+                { "query": [0.0, 0.1, 1.0, 0.5], "limit": 2 },
+                { "query": [0.0, 1.0, 0.1, 0.0], "limit": 2 }
+            ],
+            "query": { "fusion": "rrf" },
+            "with_payload": true
+        }"#,
+    )
+    .await;
+
+    // Point 2 is the best in both prefetches, 0 and 3 are found by only one of them
+    assert_eq!(result.len(), 3);
+    assert_eq!(result[0].id, 2.into());
+    assert!(result[0].score > result[1].score);
+    assert_eq!(result[1].score, result[2].score);
+    assert!(result.iter().all(|point| point.payload.is_some()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_random_sample() {
+    let collection_dir = Builder::new()
+        .prefix("test_query_sample")
+        .tempdir()
+        .unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+    upsert_points(&collection).await;
+
+    let sample_ids = |result: Vec<ScoredPoint>| -> Vec<PointIdType> {
+        assert!(result.windows(2).all(|pair| pair[0].score <= pair[1].score));
+        result.into_iter().map(|point| point.id).collect()
+    };
+
+    // Points from all shards are sampled, same seed gives the same sample
+    let request = r#"{ "query": { "sample": { "method": "random", "seed": 7 } }, "limit": 3 }"#;
+    let sample = sample_ids(query(&collection, request).await);
+    assert_eq!(sample.len(), 3);
+    assert_eq!(sample.iter().unique().count(), 3);
+    assert_eq!(sample_ids(query(&collection, request).await), sample);
+
+    // Limit above the number of points returns all of them
+    let all = sample_ids(query(&collection, r#"{ "query": { "sample": "random" } }"#).await);
+    assert_eq!(
+        all.into_iter().sorted().collect_vec(),
+        (0..5u64).map(PointIdType::from).collect_vec()
+    );
+
+    // Sample of prefetch results only contains candidates
+    let result = query(
+        &collection,
+        r#"{
+            "prefetch": [{ "query": [1.0, 1.0, 1.0, 1.0], "limit": 3 }],
+            "query": { "sample": "random" },
+            "limit": 2,
+            "with_payload": true
+        }"#,
+    )
+    .await;
+    assert_eq!(result.len(), 2);
+    assert!(result.iter().all(|point| point.payload.is_some()));
+    let sample = sample_ids(result);
+    assert!(sample
+        .iter()
+        .all(|id| [0.into(), 2.into(), 3.into()].contains(id)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_formula() {
+    let collection_dir = Builder::new()
+        .prefix("test_query_formula")
+        .tempdir()
+        .unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+    upsert_points(&collection).await;
+
+    // Prefetch scores points 2, 0 and 3 with 4, 3 and 3. Formula doubles them, as points have
+    // no `boost` payload, and adds 10 to point 3.
+    let result = query(
+        &collection,
+        r#"{
+            "prefetch": [{ "query": [1.0, 1.0, 1.0, 1.0], "limit": 3 }],
+            "query": { "formula": {
+                "expression": { "sum": [
+                    { "mult": ["$score", "boost"] },
+                    { "mult": [10, { "has_id": [3] }] }
+                ] },
+                "defaults": { "boost": 2 }
+            } },
+            "score_threshold": 7
+        }"#,
+    )
+    .await;
+
+    let scores = result
+        .iter()
+        .map(|point| (point.id, point.score))
+        .collect_vec();
+    assert_eq!(scores, vec![(3.into(), 16.0), (2.into(), 8.0)]);
+
+    // Variable without a value and without a default fails the query
+    let request: QueryRequestInternal = serde_json::from_str(
+        r#"{
+            "prefetch": [{ "query": [1.0, 1.0, 1.0, 1.0], "limit": 3 }],
+            "query": { "formula": { "expression": { "mult": ["$score", "boost"] } } }
+        }"#,
+    )
+    .unwrap();
+    let result = collection
+        .query(
+            ShardQueryRequest::try_from(request).unwrap(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+}
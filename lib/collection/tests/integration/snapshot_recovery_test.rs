@@ -35,6 +35,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            multivector_config: None,
+            datatype: None,
         }),
         ..CollectionParams::empty()
     };
@@ -45,6 +47,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -154,6 +157,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         with_vector: Some(WithVector::Bool(true)),
         params: None,
         score_threshold: None,
+        mmr: None,
     };
 
     let reference_result = collection
@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{
+    CoreSearchRequest, Modifier, QueryEnum, SparseStatisticsRequest, SparseVectorParams,
+};
+use collection::operations::CollectionUpdateOperations;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{NamedSparseVector, NamedVectorStruct};
+use segment::types::ExtendedPointId;
+use sparse::common::sparse_vector::SparseVector;
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, N_SHARDS, TEST_OPTIMIZERS_CONFIG};
+
+const SPARSE_VECTOR_NAME: &str = "text";
+
+async fn sparse_collection_fixture(collection_path: &Path, modifier: Modifier) -> Collection {
+    let wal_config = WalConfig {
+        wal_capacity_mb: 1,
+        wal_segments_ahead: 0,
+    };
+
+    let collection_params = CollectionParams {
+        sparse_vectors: Some(BTreeMap::from([(
+            SPARSE_VECTOR_NAME.to_string(),
+            SparseVectorParams {
+                index: None,
+                modifier: Some(modifier),
+            },
+        )])),
+        shard_number: NonZeroU32::new(N_SHARDS).unwrap(),
+        ..CollectionParams::empty()
+    };
+
+    let collection_config = CollectionConfig {
+        params: collection_params,
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config,
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+    };
+
+    let snapshot_path = collection_path.join("snapshots");
+
+    new_local_collection(
+        "test".to_string(),
+        collection_path,
+        &snapshot_path,
+        &collection_config,
+    )
+    .await
+    .unwrap()
+}
+
+/// Every point contains dimension `1`, only the first 5 points contain dimension `2`
+async fn upsert_points(collection: &Collection) {
+    let points = (0..100u64)
+        .map(|i| {
+            let vector = if i < 5 {
+                SparseVector::new(vec![1, 2], vec![1.0, 1.0]).unwrap()
+            } else {
+                SparseVector::new(vec![1], vec![1.0]).unwrap()
+            };
+            let mut vectors = NamedVectors::default();
+            vectors.insert(SPARSE_VECTOR_NAME.to_string(), vector.into());
+            PointStruct {
+                id: i.into(),
+                vector: vectors.into(),
+                payload: None,
+            }
+        })
+        .collect();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+}
+
+fn sparse_search_request(indices: Vec<u32>, values: Vec<f32>) -> CoreSearchRequest {
+    CoreSearchRequest {
+        query: QueryEnum::Nearest(NamedVectorStruct::Sparse(NamedSparseVector {
+            name: SPARSE_VECTOR_NAME.to_string(),
+            vector: SparseVector::new(indices, values).unwrap(),
+        })),
+        filter: None,
+        params: None,
+        limit: 10,
+        offset: 0,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+        mmr: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sparse_idf_modifier() {
+    let collection_dir = Builder::new().prefix("test_sparse_idf").tempdir().unwrap();
+    let collection = sparse_collection_fixture(collection_dir.path(), Modifier::Idf).await;
+    upsert_points(&collection).await;
+
+    let statistics = collection
+        .sparse_statistics(
+            SparseStatisticsRequest {
+                vectors: HashMap::from([(SPARSE_VECTOR_NAME.to_string(), vec![1, 2, 3])]),
+            },
+            &ShardSelectorInternal::All,
+        )
+        .await
+        .unwrap();
+    let statistics = &statistics[SPARSE_VECTOR_NAME];
+    assert_eq!(statistics.indexed_vector_count, 100);
+    assert_eq!(statistics.document_frequencies[&1], 100);
+    assert_eq!(statistics.document_frequencies[&2], 5);
+    assert_eq!(statistics.document_frequencies[&3], 0);
+
+    // Common dimension has IDF close to zero, so points with the rare dimension are on top
+    let result = collection
+        .search(
+            sparse_search_request(vec![1, 2], vec![10.0, 1.0]),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), 10);
+    let expected_top_score = 10.0 * statistics.idf(1) + statistics.idf(2);
+    assert!((result[0].score - expected_top_score).abs() < 1e-4);
+    assert!(result[..5]
+        .iter()
+        .all(|point| matches!(point.id, ExtendedPointId::NumId(id) if id < 5)));
+    assert!((result[5].score - 10.0 * statistics.idf(1)).abs() < 1e-4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sparse_without_modifier() {
+    let collection_dir = Builder::new()
+        .prefix("test_sparse_no_modifier")
+        .tempdir()
+        .unwrap();
+    let collection = sparse_collection_fixture(collection_dir.path(), Modifier::None).await;
+    upsert_points(&collection).await;
+
+    let result = collection
+        .search(
+            sparse_search_request(vec![1, 2], vec![10.0, 1.0]),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result[0].score, 11.0);
+    assert_eq!(result[5].score, 10.0);
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use segment::types::Filter;
+use serde::{Deserialize, Serialize};
+
+/// Level of access, each level includes the previous ones
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum AccessLevel {
+    /// Search, scroll, count and retrieve points, read collection info
+    #[serde(rename = "r")]
+    Read,
+    /// Also modify points, their payloads and vectors
+    #[serde(rename = "rw")]
+    ReadWrite,
+    /// Also create, update and delete the collection, its aliases, payload indexes,
+    /// snapshots and shards
+    #[serde(rename = "m")]
+    Manage,
+}
+
+impl fmt::Display for AccessLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessLevel::Read => write!(f, "read"),
+            AccessLevel::ReadWrite => write!(f, "read-write"),
+            AccessLevel::Manage => write!(f, "manage"),
+        }
+    }
+}
+
+/// Access to a single collection, granted by a JWT token
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CollectionAccess {
+    /// Name of the collection
+    pub collection: String,
+
+    /// Access level to the collection
+    pub access: AccessLevel,
+
+    /// Mandatory payload filter. It is added to every search, scroll and count, and every
+    /// deletion is limited to matching points. Operations, which can't be limited by the
+    /// filter, are denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Filter>,
+}
+
+/// Access rights of a request
+#[derive(Clone, PartialEq, Debug)]
+pub enum Access {
+    /// Access to all collections and to cluster-wide operations
+    Global(AccessLevel),
+    /// Access to the listed collections only
+    Collections(Vec<CollectionAccess>),
+}
+
+/// Request is not allowed with the given access rights
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AccessDenied(pub String);
+
+impl fmt::Display for AccessDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AccessDenied {}
+
+impl Access {
+    /// Unrestricted access, used if authentication is disabled and for internal requests
+    pub fn full() -> Self {
+        Access::Global(AccessLevel::Manage)
+    }
+
+    /// Check access to cluster-wide operations, which are not bound to a single collection
+    pub fn check_global_access(&self, required: AccessLevel) -> Result<(), AccessDenied> {
+        match self {
+            Access::Global(level) if *level >= required => Ok(()),
+            Access::Global(_) => Err(AccessDenied(format!("Global {required} access is required"))),
+            Access::Collections(_) => Err(AccessDenied(format!(
+                "Global {required} access is required, but the token is limited to specific collections"
+            ))),
+        }
+    }
+
+    /// Check access to the collection.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(filter))` if access is restricted to the points matching the filter
+    /// - `Ok(None)` if all points of the collection are accessible
+    /// - `Err(AccessDenied)` if the collection is not accessible with the required level
+    pub fn check_collection_access(
+        &self,
+        collection_name: &str,
+        required: AccessLevel,
+    ) -> Result<Option<&Filter>, AccessDenied> {
+        let level = match self {
+            Access::Global(level) => *level,
+            Access::Collections(collections) => {
+                let Some(collection) = collections
+                    .iter()
+                    .find(|access| access.collection == collection_name)
+                else {
+                    return Err(AccessDenied(format!(
+                        "Access to collection {collection_name} is denied"
+                    )));
+                };
+                if collection.access >= required {
+                    return Ok(collection.payload.as_ref());
+                }
+                collection.access
+            }
+        };
+
+        if level >= required {
+            Ok(None)
+        } else {
+            Err(AccessDenied(format!(
+                "{required} access to collection {collection_name} is required, but only {level} is granted"
+            )))
+        }
+    }
+
+    /// Check access to all points of the collection, for operations which can't be limited
+    /// by a payload filter.
+    pub fn check_whole_collection_access(
+        &self,
+        collection_name: &str,
+        required: AccessLevel,
+    ) -> Result<(), AccessDenied> {
+        match self.check_collection_access(collection_name, required)? {
+            None => Ok(()),
+            Some(_) => Err(AccessDenied(format!(
+                "Access to collection {collection_name} is limited by a payload filter, \
+                 operation on the whole collection is denied"
+            ))),
+        }
+    }
+
+    /// Whether the collection is accessible at all, used to hide inaccessible collections
+    /// from listings
+    pub fn is_collection_visible(&self, collection_name: &str) -> bool {
+        self.check_collection_access(collection_name, AccessLevel::Read)
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{Condition, FieldCondition};
+
+    use super::*;
+
+    fn tenant_filter() -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "tenant".parse().unwrap(),
+            "a".to_string().into(),
+        )))
+    }
+
+    #[test]
+    fn test_global_access() {
+        let access = Access::Global(AccessLevel::Read);
+        assert!(access.check_global_access(AccessLevel::Read).is_ok());
+        assert!(access.check_global_access(AccessLevel::ReadWrite).is_err());
+        assert_eq!(
+            access.check_collection_access("any", AccessLevel::Read),
+            Ok(None)
+        );
+        assert!(access
+            .check_collection_access("any", AccessLevel::ReadWrite)
+            .is_err());
+
+        assert!(Access::full()
+            .check_global_access(AccessLevel::Manage)
+            .is_ok());
+        assert!(Access::full()
+            .check_whole_collection_access("any", AccessLevel::Manage)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_collection_access() {
+        let filter = tenant_filter();
+        let access = Access::Collections(vec![
+            CollectionAccess {
+                collection: "managed".to_string(),
+                access: AccessLevel::Manage,
+                payload: None,
+            },
+            CollectionAccess {
+                collection: "tenants".to_string(),
+                access: AccessLevel::ReadWrite,
+                payload: Some(filter.clone()),
+            },
+        ]);
+
+        assert!(access.check_global_access(AccessLevel::Read).is_err());
+
+        assert!(access
+            .check_whole_collection_access("managed", AccessLevel::Manage)
+            .is_ok());
+        assert!(access
+            .check_collection_access("other", AccessLevel::Read)
+            .is_err());
+        assert!(!access.is_collection_visible("other"));
+
+        assert_eq!(
+            access.check_collection_access("tenants", AccessLevel::ReadWrite),
+            Ok(Some(&filter))
+        );
+        assert!(access
+            .check_collection_access("tenants", AccessLevel::Manage)
+            .is_err());
+        assert!(access
+            .check_whole_collection_access("tenants", AccessLevel::Read)
+            .is_err());
+    }
+
+    #[test]
+    fn test_deserialize_access() {
+        let access: Vec<CollectionAccess> = serde_json::from_str(
+            r#"[
+                {"collection": "managed", "access": "m"},
+                {"collection": "tenants", "access": "r", "payload": {"must": [{"key": "tenant", "match": {"value": "a"}}]}}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(access[0].access, AccessLevel::Manage);
+        assert_eq!(access[0].payload, None);
+        assert_eq!(access[1].access, AccessLevel::Read);
+        assert_eq!(access[1].payload, Some(tenant_filter()));
+    }
+}
@@ -1,10 +1,29 @@
 use serde::{Deserialize, Serialize};
 
+use crate::access::{Access, AccessLevel, CollectionAccess};
+
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Claims {
     /// Expiration time (seconds since UNIX epoch)
     pub exp: Option<u64>,
 
-    /// Write access, default is false. Read access is always enabled
+    /// Write access, default is false. Read access is always enabled.
+    /// Ignored if `access` is set.
     pub w: Option<bool>,
+
+    /// Collections the token has access to. If set, other collections and cluster-wide
+    /// operations are not accessible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<Vec<CollectionAccess>>,
+}
+
+impl Claims {
+    /// Access rights granted by the token
+    pub fn to_access(&self) -> Access {
+        match &self.access {
+            Some(collections) => Access::Collections(collections.clone()),
+            None if self.w.unwrap_or(false) => Access::Global(AccessLevel::Manage),
+            None => Access::Global(AccessLevel::Read),
+        }
+    }
 }
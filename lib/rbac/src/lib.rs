@@ -2,6 +2,7 @@ use jsonwebtoken::errors::Error;
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use jwt::Claims;
 
+pub mod access;
 pub mod jwt;
 
 #[derive(Clone)]
@@ -41,6 +42,7 @@ impl JwtParser {
 mod tests {
 
     use super::*;
+    use crate::access::{AccessLevel, CollectionAccess};
 
     pub fn create_token(claims: &Claims) -> String {
         use jsonwebtoken::{encode, EncodingKey, Header};
@@ -59,6 +61,7 @@ mod tests {
         let claims = Claims {
             exp: Some(exp),
             w: Some(true),
+            access: None,
         };
         let token = create_token(&claims);
 
@@ -80,6 +83,7 @@ mod tests {
         let mut claims = Claims {
             exp: Some(exp),
             w: Some(false),
+            access: None,
         };
 
         let token = create_token(&claims);
@@ -96,4 +100,32 @@ mod tests {
 
         assert_eq!(claims, decoded_claims);
     }
+
+    #[test]
+    fn test_collection_access_claims() {
+        let claims = Claims {
+            exp: None,
+            w: Some(true),
+            access: Some(vec![CollectionAccess {
+                collection: "my_collection".to_string(),
+                access: AccessLevel::Read,
+                payload: None,
+            }]),
+        };
+        let token = create_token(&claims);
+
+        let parser = JwtParser::new("secret");
+        let decoded_claims = parser.decode(&token).unwrap();
+        assert_eq!(claims, decoded_claims);
+
+        // Collection list takes precedence over the global write flag
+        let access = decoded_claims.to_access();
+        assert!(access.check_global_access(AccessLevel::Read).is_err());
+        assert!(access
+            .check_collection_access("my_collection", AccessLevel::Read)
+            .is_ok());
+        assert!(access
+            .check_collection_access("my_collection", AccessLevel::ReadWrite)
+            .is_err());
+    }
 }
@@ -6,16 +6,37 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use rand::rngs::StdRng;
 use rand::{thread_rng, SeedableRng};
 use segment::fixtures::index_fixtures::{FakeFilterContext, TestRawScorerProducer};
+use segment::index::hnsw_index::graph_layers::GraphLayers;
 use segment::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::spaces::simple::CosineMetric;
 
 const NUM_VECTORS: usize = 10000;
+/// Number of points, which are already in the old graph
+const NUM_OLD_VECTORS: usize = NUM_VECTORS * 4 / 5;
 const DIM: usize = 32;
 const M: usize = 16;
 const EF_CONSTRUCT: usize = 64;
 const USE_HEURISTIC: bool = true;
 
+fn insert_points(
+    graph_layers_builder: &mut GraphLayersBuilder,
+    vector_holder: &TestRawScorerProducer<CosineMetric>,
+    points: impl Iterator<Item = PointOffsetType>,
+) {
+    let mut rng = thread_rng();
+    let fake_filter_context = FakeFilterContext {};
+    for idx in points {
+        let added_vector = vector_holder.vectors.get(idx).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let level = graph_layers_builder.get_random_layer(&mut rng);
+        graph_layers_builder.set_levels(idx, level);
+        graph_layers_builder.link_new_point(idx, scorer);
+    }
+}
+
 fn hnsw_benchmark(c: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(42);
     let vector_holder = TestRawScorerProducer::<CosineMetric>::new(DIM, NUM_VECTORS, &mut rng);
@@ -23,18 +44,40 @@ fn hnsw_benchmark(c: &mut Criterion) {
     group.sample_size(10);
     group.bench_function("hnsw_index", |b| {
         b.iter(|| {
-            let mut rng = thread_rng();
             let mut graph_layers_builder =
                 GraphLayersBuilder::new(NUM_VECTORS, M, M * 2, EF_CONSTRUCT, 10, USE_HEURISTIC);
-            let fake_filter_context = FakeFilterContext {};
-            for idx in 0..(NUM_VECTORS as PointOffsetType) {
-                let added_vector = vector_holder.vectors.get(idx).to_vec();
-                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
-                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
-                let level = graph_layers_builder.get_random_layer(&mut rng);
-                graph_layers_builder.set_levels(idx, level);
-                graph_layers_builder.link_new_point(idx, scorer);
-            }
+            insert_points(
+                &mut graph_layers_builder,
+                &vector_holder,
+                0..NUM_VECTORS as PointOffsetType,
+            );
+        })
+    });
+
+    // Graph of the biggest segment is reused, only the remaining points are inserted
+    let mut old_graph_builder =
+        GraphLayersBuilder::new(NUM_OLD_VECTORS, M, M * 2, EF_CONSTRUCT, 10, USE_HEURISTIC);
+    insert_points(
+        &mut old_graph_builder,
+        &vector_holder,
+        0..NUM_OLD_VECTORS as PointOffsetType,
+    );
+    let old_graph: GraphLayers<GraphLinksRam> = old_graph_builder.into_graph_layers(None).unwrap();
+
+    group.bench_function("hnsw_index_from_old_graph", |b| {
+        b.iter(|| {
+            let mut graph_layers_builder = GraphLayersBuilder::from_remapped_graph_layers(
+                &old_graph,
+                NUM_VECTORS,
+                10,
+                USE_HEURISTIC,
+                Some,
+            );
+            insert_points(
+                &mut graph_layers_builder,
+                &vector_holder,
+                NUM_OLD_VECTORS as PointOffsetType..NUM_VECTORS as PointOffsetType,
+            );
         })
     });
     group.finish();
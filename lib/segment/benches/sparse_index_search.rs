@@ -18,6 +18,7 @@ use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{Condition, FieldCondition, Filter, Payload};
 use serde_json::json;
 use sparse::common::sparse_vector_fixture::random_positive_sparse_vector;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use tempfile::Builder;
 
@@ -83,10 +84,34 @@ fn sparse_vector_index_search_benchmark(c: &mut Criterion) {
         )
         .unwrap();
     sparse_vector_index_mmap
-        .build_index(permit, &stopped)
+        .build_index(permit.clone(), &stopped)
         .unwrap();
     assert_eq!(sparse_vector_index_mmap.indexed_vector_count(), NUM_VECTORS);
 
+    // compressed mmap inverted index
+    let compressed_mmap_index_dir = Builder::new()
+        .prefix("compressed_mmap_index_dir")
+        .tempdir()
+        .unwrap();
+    let mut sparse_vector_index_compressed_mmap: SparseVectorIndex<
+        InvertedIndexCompressedMmap<f32>,
+    > = SparseVectorIndex::open(
+        sparse_index_config,
+        sparse_vector_index.id_tracker.clone(),
+        sparse_vector_index.vector_storage.clone(),
+        sparse_vector_index.payload_index.clone(),
+        compressed_mmap_index_dir.path(),
+        &stopped,
+    )
+    .unwrap();
+    sparse_vector_index_compressed_mmap
+        .build_index(permit, &stopped)
+        .unwrap();
+    assert_eq!(
+        sparse_vector_index_compressed_mmap.indexed_vector_count(),
+        NUM_VECTORS
+    );
+
     // intent: bench `search` without filter on mmap inverted index
     group.bench_function("mmap-inverted-index-search", |b| {
         b.iter(|| {
@@ -98,6 +123,17 @@ fn sparse_vector_index_search_benchmark(c: &mut Criterion) {
         })
     });
 
+    // intent: bench `search` without filter on compressed mmap inverted index with block-max pruning
+    group.bench_function("compressed-mmap-inverted-index-search", |b| {
+        b.iter(|| {
+            let results = sparse_vector_index_compressed_mmap
+                .search(&[&query_vector], None, TOP, None, &stopped)
+                .unwrap();
+
+            assert_eq!(results[0].len(), TOP);
+        })
+    });
+
     // intent: bench `search` without filter
     group.bench_function("inverted-index-search", |b| {
         b.iter(|| {
@@ -6,6 +6,7 @@ pub mod operation_error;
 pub mod operation_time_statistics;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_wrapper;
+pub mod score_fusion;
 pub mod utils;
 pub mod vector_utils;
 pub mod version;
@@ -14,8 +15,10 @@ use std::sync::atomic::AtomicBool;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::{QueryVector, VectorRef};
-use crate::types::{SegmentConfig, SparseVectorDataConfig, VectorDataConfig};
+use crate::data_types::vectors::{QueryVector, VectorElementType, VectorRef};
+use crate::types::{
+    SegmentConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
+};
 
 pub type Flusher = Box<dyn FnOnce() -> OperationResult<()> + Send>;
 
@@ -131,10 +134,43 @@ pub fn check_named_vectors(
 ) -> OperationResult<()> {
     for (vector_name, vector_data) in vectors.iter() {
         check_vector(vector_name, &vector_data.into(), segment_config)?;
+        if let Ok(vector_config) = get_vector_config_or_error(vector_name, segment_config) {
+            check_vector_datatype(vector_data, vector_config.datatype.unwrap_or_default())?;
+        }
     }
     Ok(())
 }
 
+/// Check that elements of the stored vector can be represented by the given datatype.
+///
+/// Returns an error if some element would be changed by the conversion.
+pub fn check_vector_datatype(
+    vector: VectorRef,
+    datatype: VectorStorageDatatype,
+) -> OperationResult<()> {
+    let elements: &[VectorElementType] = match vector {
+        VectorRef::Dense(vector) => vector,
+        VectorRef::MultiDense(multi_vector) => &multi_vector.flattened_vectors,
+        VectorRef::Sparse(_) => return Ok(()),
+    };
+    match datatype {
+        VectorStorageDatatype::Float32 | VectorStorageDatatype::Float16 => Ok(()),
+        VectorStorageDatatype::Uint8 => {
+            let is_byte = |x: &VectorElementType| {
+                x.fract() == 0.0 && (0.0..=u8::MAX as VectorElementType).contains(x)
+            };
+            match elements.iter().find(|x| !is_byte(x)) {
+                None => Ok(()),
+                Some(invalid) => Err(OperationError::ValidationError {
+                    description: format!(
+                        "Vector element {invalid} is not an integer in range [0, 255], required by uint8 datatype"
+                    ),
+                }),
+            }
+        }
+    }
+}
+
 /// Get the vector config for the given name, or return a name error.
 ///
 /// Returns an error if incompatible.
@@ -185,6 +221,20 @@ fn check_vector_against_config(
             Ok(())
         }
         VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+        VectorRef::MultiDense(multi_vector) => {
+            if vector_config.multivector_config.is_none() {
+                return Err(OperationError::WrongMulti);
+            }
+            // Check dimensionality of each inner vector
+            let dim = vector_config.size;
+            if multi_vector.dim != dim || multi_vector.is_empty() {
+                return Err(OperationError::WrongVector {
+                    expected_dim: dim,
+                    received_dim: multi_vector.dim,
+                });
+            }
+            Ok(())
+        }
     }
 }
 
@@ -195,6 +245,7 @@ fn check_sparse_vector_against_config(
     match vector {
         VectorRef::Dense(_) => Err(OperationError::WrongSparse),
         VectorRef::Sparse(_vector) => Ok(()), // TODO(sparse) check vector by config
+        VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
     }
 }
 
@@ -52,8 +52,17 @@ pub enum OperationError {
     ValidationError { description: String },
     #[error("Wrong usage of sparse vectors")]
     WrongSparse,
+    #[error("Wrong usage of multi vectors")]
+    WrongMulti,
     #[error("Wrong key of payload")]
     WrongPayloadKey { description: String },
+    #[error("Wrong value of formula variable {variable}: {description}")]
+    VariableTypeError {
+        variable: String,
+        description: String,
+    },
+    #[error("Formula evaluated to a non-finite number: {value}")]
+    NonFiniteNumber { value: f64 },
 }
 
 impl OperationError {
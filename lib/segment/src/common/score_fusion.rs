@@ -0,0 +1,155 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use common::types::ScoreType;
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+
+use crate::types::{Order, PointIdType, ScoredPoint};
+
+/// Constant `k` of Reciprocal Rank Fusion, dampens the impact of top ranked points
+pub const DEFAULT_RRF_K: usize = 60;
+
+/// Combine scores of the same points from several result lists.
+///
+/// The first occurrence of each point is kept, with its score replaced by the sum of its
+/// scores in all lists. Result is sorted by the combined score, best first.
+fn sum_scores(scored_lists: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+    let mut fused: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+    for point in scored_lists.into_iter().flatten() {
+        match fused.entry(point.id) {
+            Entry::Occupied(mut entry) => entry.get_mut().score += point.score,
+            Entry::Vacant(entry) => {
+                entry.insert(point);
+            }
+        }
+    }
+    fused
+        .into_values()
+        .sorted_unstable_by_key(|point| (OrderedFloat(-point.score), point.id))
+        .collect()
+}
+
+/// Reciprocal Rank Fusion (RRF) of several result lists.
+///
+/// Each list must be sorted from the best to the worst point. A point at rank `r`
+/// (starting from 1) in a list contributes `1 / (k + r)` to its fused score, so only
+/// positions of points matter, not the scale of original scores.
+pub fn rrf_scoring(responses: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+    let ranked = responses.into_iter().map(|response| {
+        response
+            .into_iter()
+            .enumerate()
+            .map(|(rank, mut point)| {
+                point.score = 1.0 / (DEFAULT_RRF_K + rank + 1) as ScoreType;
+                point
+            })
+            .collect_vec()
+    });
+    sum_scores(ranked)
+}
+
+/// Distribution-Based Score Fusion (DBSF) of several result lists.
+///
+/// Scores of each list are normalized into a common range using their distribution:
+/// `mean - 3 * std_dev` becomes `0` and `mean + 3 * std_dev` becomes `1`. If smaller scores
+/// are better in a list, normalized scores are inverted. Normalized scores of the same point
+/// are summed.
+pub fn dbsf_scoring(
+    responses: impl IntoIterator<Item = (Vec<ScoredPoint>, Order)>,
+) -> Vec<ScoredPoint> {
+    let normalized = responses.into_iter().map(|(mut response, order)| {
+        normalize_scores(&mut response, order);
+        response
+    });
+    sum_scores(normalized)
+}
+
+fn normalize_scores(points: &mut [ScoredPoint], order: Order) {
+    if points.is_empty() {
+        return;
+    }
+
+    let count = points.len() as ScoreType;
+    let mean = points.iter().map(|point| point.score).sum::<ScoreType>() / count;
+    let variance = points
+        .iter()
+        .map(|point| (point.score - mean).powi(2))
+        .sum::<ScoreType>()
+        / count;
+    let std_dev = variance.sqrt();
+
+    let lower = mean - 3.0 * std_dev;
+    let range = 6.0 * std_dev;
+
+    for point in points.iter_mut() {
+        let normalized = if range > 0.0 {
+            (point.score - lower) / range
+        } else {
+            0.5
+        };
+        point.score = match order {
+            Order::LargeBetter => normalized,
+            Order::SmallBetter => 1.0 - normalized,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored_points(ids_and_scores: &[(u64, ScoreType)]) -> Vec<ScoredPoint> {
+        ids_and_scores
+            .iter()
+            .map(|&(id, score)| ScoredPoint {
+                id: id.into(),
+                version: 0,
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+            })
+            .collect()
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<PointIdType> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    #[test]
+    fn test_rrf_scoring() {
+        let dense = scored_points(&[(1, 0.9), (2, 0.8), (3, 0.7)]);
+        let sparse = scored_points(&[(3, 25.0), (4, 20.0), (1, 5.0)]);
+
+        let fused = rrf_scoring([dense, sparse]);
+
+        assert_eq!(ids(&fused), vec![1.into(), 3.into(), 2.into(), 4.into()]);
+        let expected_top =
+            1.0 / (DEFAULT_RRF_K + 1) as ScoreType + 1.0 / (DEFAULT_RRF_K + 3) as ScoreType;
+        assert!((fused[0].score - expected_top).abs() < 1e-6);
+        // Points at the same single rank get the same score
+        assert_eq!(fused[2].score, fused[3].score);
+    }
+
+    #[test]
+    fn test_dbsf_scoring() {
+        let dense = scored_points(&[(1, 0.9), (2, 0.5), (3, 0.1)]);
+        // Distances, smaller is better
+        let euclid = scored_points(&[(3, 1.0), (1, 2.0), (2, 100.0)]);
+
+        let fused = dbsf_scoring([(dense, Order::LargeBetter), (euclid, Order::SmallBetter)]);
+
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].id, 1.into());
+        assert_eq!(fused[2].id, 2.into());
+        assert!(fused.iter().all(|point| point.score > 0.0));
+    }
+
+    #[test]
+    fn test_dbsf_constant_scores() {
+        let points = scored_points(&[(1, 1.0), (2, 1.0)]);
+        let fused = dbsf_scoring([(points, Order::LargeBetter)]);
+        assert!(fused.iter().all(|point| point.score == 0.5));
+    }
+}
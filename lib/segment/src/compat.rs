@@ -52,6 +52,8 @@ impl From<SegmentConfigV5> for SegmentConfig {
                     storage_type: (old_data.on_disk == Some(true))
                         .then_some(VectorStorageType::Mmap)
                         .unwrap_or_else(|| old_segment.storage_type.into()),
+                    multivector_config: None,
+                    datatype: None,
                 };
 
                 (vector_name, new_data)
@@ -150,6 +152,7 @@ mod tests {
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            appendable: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -181,6 +184,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -256,6 +260,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                appendable: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
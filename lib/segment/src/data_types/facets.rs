@@ -0,0 +1,151 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::json_path::JsonPath;
+use crate::types::{
+    Filter, FloatPayloadType, IntPayloadType, Match, Range, UuidIntType, UuidPayloadType,
+};
+
+/// Parameters of a facet request to a single segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetParams {
+    /// Payload key to count values of. Must have a keyword, integer, bool, uuid or numeric index.
+    pub key: JsonPath,
+    /// Only count points which satisfy this filter
+    pub filter: Option<Filter>,
+    /// Count exactly instead of estimating from the index
+    pub exact: bool,
+    /// Numeric ranges to count points in, instead of individual values
+    pub ranges: Vec<Range<FloatPayloadType>>,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+    Bool(bool),
+}
+
+impl FacetValue {
+    /// UUIDs are returned as keywords in their canonical form
+    pub fn from_uuid(uuid: UuidIntType) -> Self {
+        FacetValue::Keyword(UuidPayloadType::from_u128(uuid).to_string())
+    }
+}
+
+impl From<&SmolStr> for FacetValue {
+    fn from(keyword: &SmolStr) -> Self {
+        FacetValue::Keyword(keyword.to_string())
+    }
+}
+
+impl From<FacetValue> for Match {
+    fn from(value: FacetValue) -> Self {
+        match value {
+            FacetValue::Keyword(keyword) => Match::from(keyword),
+            FacetValue::Int(integer) => Match::from(integer),
+            FacetValue::Bool(flag) => Match::from(flag),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetValueHit {
+    pub value: FacetValue,
+    pub count: usize,
+}
+
+/// Number of points per value and per requested range
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FacetCounts {
+    pub values: HashMap<FacetValue, usize>,
+    /// Counts for each of the requested ranges, in the same order
+    pub ranges: Vec<usize>,
+}
+
+impl FacetCounts {
+    /// Sum counts of another segment or shard into this one
+    pub fn merge(&mut self, other: FacetCounts) {
+        for (value, count) in other.values {
+            *self.values.entry(value).or_default() += count;
+        }
+        if self.ranges.len() < other.ranges.len() {
+            self.ranges.resize(other.ranges.len(), 0);
+        }
+        for (total, count) in self.ranges.iter_mut().zip(other.ranges) {
+            *total += count;
+        }
+    }
+
+    /// Values with the highest counts, ties are broken by value
+    pub fn top_hits(&self, limit: usize) -> Vec<FacetValueHit> {
+        let mut hits: Vec<_> = self
+            .values
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(value, count)| FacetValueHit {
+                value: value.clone(),
+                count: *count,
+            })
+            .collect();
+        hits.sort_unstable_by(|a, b| {
+            (Reverse(a.count), &a.value).cmp(&(Reverse(b.count), &b.value))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_and_top_hits() {
+        let mut counts = FacetCounts {
+            values: HashMap::from([
+                (FacetValue::Keyword("a".to_string()), 3),
+                (FacetValue::Keyword("b".to_string()), 1),
+            ]),
+            ranges: vec![2],
+        };
+        counts.merge(FacetCounts {
+            values: HashMap::from([
+                (FacetValue::Keyword("b".to_string()), 2),
+                (FacetValue::Keyword("c".to_string()), 4),
+                (FacetValue::Keyword("d".to_string()), 0),
+            ]),
+            ranges: vec![1, 5],
+        });
+
+        assert_eq!(counts.ranges, vec![3, 5]);
+
+        let hits = counts.top_hits(10);
+        assert_eq!(
+            hits,
+            vec![
+                FacetValueHit {
+                    value: FacetValue::Keyword("c".to_string()),
+                    count: 4
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("a".to_string()),
+                    count: 3
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("b".to_string()),
+                    count: 3
+                },
+            ]
+        );
+        assert_eq!(counts.top_hits(1).len(), 1);
+    }
+}
@@ -1,7 +1,11 @@
+pub mod facets;
 pub mod groups;
 pub mod integer_index;
 pub mod named_vectors;
 pub mod order_by;
+pub mod primitive;
+pub mod sample;
+pub mod sparse_statistics;
 pub mod text_index;
 pub mod tiny_map;
 pub mod vectors;
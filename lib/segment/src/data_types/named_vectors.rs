@@ -4,9 +4,12 @@ use std::collections::HashMap;
 use sparse::common::sparse_vector::SparseVector;
 
 use super::tiny_map;
-use super::vectors::{DenseVector, Vector, VectorElementType, VectorRef};
+use super::vectors::{
+    DenseVector, MultiDenseVector, Vector, VectorElementType, VectorElementTypeByte,
+    VectorElementTypeHalf, VectorRef,
+};
 use crate::common::operation_error::OperationError;
-use crate::types::Distance;
+use crate::types::{Distance, VectorStorageDatatype};
 
 type CowKey<'a> = Cow<'a, str>;
 
@@ -14,6 +17,7 @@ type CowKey<'a> = Cow<'a, str>;
 pub enum CowVector<'a> {
     Dense(Cow<'a, [VectorElementType]>),
     Sparse(Cow<'a, SparseVector>),
+    MultiDense(Cow<'a, MultiDenseVector>),
 }
 
 impl<'a> Default for CowVector<'a> {
@@ -34,6 +38,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => Vector::Dense(v.into_owned()),
             CowVector::Sparse(v) => Vector::Sparse(v.into_owned()),
+            CowVector::MultiDense(v) => Vector::MultiDense(v.into_owned()),
         }
     }
 
@@ -41,6 +46,7 @@ impl<'a> CowVector<'a> {
         match self {
             CowVector::Dense(v) => VectorRef::Dense(v.as_ref()),
             CowVector::Sparse(v) => VectorRef::Sparse(v.as_ref()),
+            CowVector::MultiDense(v) => VectorRef::MultiDense(v.as_ref()),
         }
     }
 }
@@ -50,6 +56,7 @@ impl<'a> From<Vector> for CowVector<'a> {
         match v {
             Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
             Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
+            Vector::MultiDense(v) => CowVector::MultiDense(Cow::Owned(v)),
         }
     }
 }
@@ -66,18 +73,36 @@ impl<'a> From<DenseVector> for CowVector<'a> {
     }
 }
 
+impl<'a> From<MultiDenseVector> for CowVector<'a> {
+    fn from(v: MultiDenseVector) -> Self {
+        CowVector::MultiDense(Cow::Owned(v))
+    }
+}
+
 impl<'a> From<&'a SparseVector> for CowVector<'a> {
     fn from(v: &'a SparseVector) -> Self {
         CowVector::Sparse(Cow::Borrowed(v))
     }
 }
 
+impl<'a> From<&'a MultiDenseVector> for CowVector<'a> {
+    fn from(v: &'a MultiDenseVector) -> Self {
+        CowVector::MultiDense(Cow::Borrowed(v))
+    }
+}
+
 impl<'a> From<&'a [VectorElementType]> for CowVector<'a> {
     fn from(v: &'a [VectorElementType]) -> Self {
         CowVector::Dense(Cow::Owned(v.into()))
     }
 }
 
+impl<'a> From<Cow<'a, [VectorElementType]>> for CowVector<'a> {
+    fn from(v: Cow<'a, [VectorElementType]>) -> Self {
+        CowVector::Dense(v)
+    }
+}
+
 impl<'a> TryFrom<CowVector<'a>> for SparseVector {
     type Error = OperationError;
 
@@ -85,6 +110,7 @@ impl<'a> TryFrom<CowVector<'a>> for SparseVector {
         match value {
             CowVector::Dense(_) => Err(OperationError::WrongSparse),
             CowVector::Sparse(v) => Ok(v.into_owned()),
+            CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -96,6 +122,19 @@ impl<'a> TryFrom<CowVector<'a>> for DenseVector {
         match value {
             CowVector::Dense(v) => Ok(v.into_owned()),
             CowVector::Sparse(_) => Err(OperationError::WrongSparse),
+            CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
+        }
+    }
+}
+
+impl<'a> TryFrom<CowVector<'a>> for MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: CowVector<'a>) -> Result<Self, Self::Error> {
+        match value {
+            CowVector::Dense(_) => Err(OperationError::WrongMulti),
+            CowVector::Sparse(_) => Err(OperationError::WrongSparse),
+            CowVector::MultiDense(v) => Ok(v.into_owned()),
         }
     }
 }
@@ -105,6 +144,7 @@ impl<'a> From<VectorRef<'a>> for CowVector<'a> {
         match v {
             VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
             VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+            VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
         }
     }
 }
@@ -117,6 +157,7 @@ impl<'a> NamedVectors<'a> {
             match value {
                 VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
                 VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+                VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
             },
         );
         Self { map }
@@ -155,6 +196,7 @@ impl<'a> NamedVectors<'a> {
             match vector {
                 Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
                 Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
+                Vector::MultiDense(v) => CowVector::MultiDense(Cow::Owned(v)),
             },
         );
     }
@@ -165,6 +207,7 @@ impl<'a> NamedVectors<'a> {
             match vector {
                 VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
                 VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
+                VectorRef::MultiDense(v) => CowVector::MultiDense(Cow::Borrowed(v)),
             },
         );
     }
@@ -202,19 +245,43 @@ impl<'a> NamedVectors<'a> {
 
     pub fn preprocess<F>(&mut self, distance_map: F)
     where
-        F: Fn(&str) -> Distance,
+        F: Fn(&str) -> (Distance, VectorStorageDatatype),
     {
         for (name, vector) in self.map.iter_mut() {
-            let distance = distance_map(name);
+            let (distance, datatype) = distance_map(name);
             match vector {
                 CowVector::Dense(v) => {
-                    let preprocessed_vector = distance.preprocess_vector(v.to_vec());
+                    let preprocessed_vector = match datatype {
+                        VectorStorageDatatype::Float32 => {
+                            distance.preprocess_vector::<VectorElementType>(v.to_vec())
+                        }
+                        VectorStorageDatatype::Float16 => {
+                            distance.preprocess_vector::<VectorElementTypeHalf>(v.to_vec())
+                        }
+                        VectorStorageDatatype::Uint8 => {
+                            distance.preprocess_vector::<VectorElementTypeByte>(v.to_vec())
+                        }
+                    };
                     *vector = CowVector::Dense(Cow::Owned(preprocessed_vector))
                 }
                 CowVector::Sparse(v) => {
                     // sort by indices to enable faster dot product and overlap checks
                     v.to_mut().sort_by_indices();
                 }
+                CowVector::MultiDense(v) => {
+                    // preprocess each inner vector independently
+                    let dim = v.dim;
+                    let preprocessed_vectors = v
+                        .multi_vectors()
+                        .flat_map(|inner| {
+                            distance.preprocess_vector::<VectorElementType>(inner.to_vec())
+                        })
+                        .collect();
+                    *vector = CowVector::MultiDense(Cow::Owned(MultiDenseVector::new(
+                        preprocessed_vectors,
+                        dim,
+                    )))
+                }
             }
         }
     }
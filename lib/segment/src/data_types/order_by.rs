@@ -1,3 +1,4 @@
+use base64::prelude::{Engine as _, BASE64_URL_SAFE_NO_PAD};
 use num_cmp::NumCmp;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -6,11 +7,9 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Payload, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, IntPayloadType, PointIdType, Range, RangeInterface,
 };
 
-const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
-
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum Direction {
@@ -36,6 +35,15 @@ impl Direction {
             },
         }
     }
+
+    /// Range of numeric values which start from `start` in this direction, including.
+    pub fn as_numeric_range(&self, start: Option<&OrderingValue>) -> RangeInterface {
+        // TODO: When we introduce integer ranges, we'll stop doing lossy conversion to f64 here
+        match start.and_then(OrderingValue::as_float) {
+            Some(start) => RangeInterface::Float(self.as_range_from(start)),
+            None => RangeInterface::Float(Range::default()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -62,76 +70,171 @@ pub struct OrderBy {
 }
 
 impl OrderBy {
-    /// Returns a range representation of OrderBy.
-    pub fn as_range(&self) -> RangeInterface {
-        self.start_from
-            .as_ref()
-            .map(|start_from| match start_from {
-                // TODO: When we introduce integer ranges, we'll stop doing lossy conversion to f64 here
-                // Accepting an integer as start_from simplifies the client generation.
-                StartFrom::Integer(i) => {
-                    RangeInterface::Float(self.direction().as_range_from(*i as f64))
-                }
-                StartFrom::Float(f) => RangeInterface::Float(self.direction().as_range_from(*f)),
-                StartFrom::Datetime(dt) => {
-                    RangeInterface::DateTime(self.direction().as_range_from(*dt))
-                }
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    pub fn start_from(&self) -> Option<OrderingValue> {
+        self.start_from.as_ref().map(|start_from| match start_from {
+            StartFrom::Integer(i) => OrderingValue::Int(*i),
+            StartFrom::Float(f) => OrderingValue::Float(*f),
+            StartFrom::Datetime(dt) => OrderingValue::Int(dt.timestamp()),
+        })
+    }
+
+    /// Compare two values of this key in the direction of ordering.
+    ///
+    /// Missing values always go last, regardless of the direction.
+    pub fn compare_values(
+        &self,
+        a: Option<&OrderingValue>,
+        b: Option<&OrderingValue>,
+    ) -> std::cmp::Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => match self.direction() {
+                Direction::Asc => a.cmp(b),
+                Direction::Desc => b.cmp(a),
+            },
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    }
+
+    /// Select the value of a multi-valued field which defines the position of the point,
+    /// i.e. the smallest one for `asc` and the largest one for `desc`.
+    pub fn select_value(
+        &self,
+        values: impl Iterator<Item = OrderingValue>,
+    ) -> Option<OrderingValue> {
+        match self.direction() {
+            Direction::Asc => values.min(),
+            Direction::Desc => values.max(),
+        }
+    }
+}
+
+/// Values of the `order_by` keys of a point, in the order of the keys.
+/// `None` means the point has no value for the key.
+pub type OrderValues = Vec<Option<OrderingValue>>;
+
+/// Position of a point in the ordering by several keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderedPoint {
+    pub values: OrderValues,
+    pub id: PointIdType,
+}
+
+impl OrderedPoint {
+    /// Encode the position into an opaque cursor, which can be used to continue scrolling after it
+    pub fn to_cursor(&self) -> String {
+        // Serialization of plain values and ids can't fail
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        BASE64_URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn from_cursor(cursor: &str) -> Option<Self> {
+        let json = BASE64_URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+/// Ordering by several payload keys.
+///
+/// Each key breaks ties of the previous ones, and points with equal values of all keys
+/// are ordered by id, so the order is total and stable across segments and shards.
+#[derive(Debug, Clone)]
+pub struct MultiOrderBy {
+    /// Keys to order by, the first one is the primary key
+    pub keys: Vec<OrderBy>,
+
+    /// Return only points which go after this position
+    pub cursor: Option<OrderedPoint>,
+}
+
+impl MultiOrderBy {
+    pub fn primary(&self) -> &OrderBy {
+        &self.keys[0]
+    }
+
+    pub fn secondary(&self) -> &[OrderBy] {
+        &self.keys[1..]
+    }
+
+    /// Value of the primary key to start reading from, including.
+    ///
+    /// The cursor takes precedence over `start_from` of the primary key.
+    pub fn start_from(&self) -> Option<OrderingValue> {
+        match &self.cursor {
+            Some(cursor) => cursor.values.first().cloned().flatten(),
+            None => self.primary().start_from(),
+        }
+    }
+
+    /// Compare values of all keys, without the point id tie-breaker
+    pub fn compare_values(
+        &self,
+        a: &[Option<OrderingValue>],
+        b: &[Option<OrderingValue>],
+    ) -> std::cmp::Ordering {
+        self.keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                key.compare_values(
+                    a.get(i).and_then(Option::as_ref),
+                    b.get(i).and_then(Option::as_ref),
+                )
             })
-            .unwrap_or_else(|| RangeInterface::Float(Range::default()))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
     }
 
-    pub fn direction(&self) -> Direction {
-        self.direction.unwrap_or_default()
+    pub fn compare(&self, a: &OrderedPoint, b: &OrderedPoint) -> std::cmp::Ordering {
+        self.compare_values(&a.values, &b.values)
+            .then_with(|| a.id.cmp(&b.id))
     }
 
-    pub fn start_from(&self) -> OrderingValue {
-        self.start_from
+    /// Whether the point goes strictly after the cursor
+    pub fn is_after_cursor(&self, point: &OrderedPoint) -> bool {
+        self.cursor
             .as_ref()
-            .map(|start_from| match start_from {
-                StartFrom::Integer(i) => OrderingValue::Int(*i),
-                StartFrom::Float(f) => OrderingValue::Float(*f),
-                StartFrom::Datetime(dt) => OrderingValue::Int(dt.timestamp()),
-            })
-            .unwrap_or_else(|| match self.direction() {
-                Direction::Asc => OrderingValue::Int(std::i64::MIN),
-                Direction::Desc => OrderingValue::Int(std::i64::MAX),
-            })
+            .map_or(true, |cursor| self.compare(point, cursor).is_gt())
     }
+}
 
-    pub fn insert_order_value_in_payload(
-        payload: Option<Payload>,
-        value: impl Into<serde_json::Value>,
-    ) -> Payload {
-        let mut new_payload = payload.unwrap_or_default();
-        new_payload
-            .0
-            .insert(INTERNAL_KEY_OF_ORDER_BY_VALUE.to_string(), value.into());
-        new_payload
-    }
-
-    pub fn remove_order_value_from_payload(&self, payload: Option<&mut Payload>) -> f64 {
-        payload
-            .and_then(|payload| payload.0.remove(INTERNAL_KEY_OF_ORDER_BY_VALUE))
-            .and_then(|v| v.as_f64())
-            .unwrap_or_else(|| match self.direction() {
-                Direction::Asc => std::f64::MAX,
-                Direction::Desc => std::f64::MIN,
-            })
+impl From<OrderBy> for MultiOrderBy {
+    fn from(order_by: OrderBy) -> Self {
+        MultiOrderBy {
+            keys: vec![order_by],
+            cursor: None,
+        }
     }
 }
 
+/// Value of a payload field used for ordering
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
 pub enum OrderingValue {
-    Float(FloatPayloadType),
     Int(IntPayloadType),
+    Float(FloatPayloadType),
+    Keyword(String),
 }
 
-impl From<OrderingValue> for serde_json::Value {
-    fn from(value: OrderingValue) -> Self {
-        match value {
-            OrderingValue::Float(value) => serde_json::Number::from_f64(value)
-                .map(serde_json::Value::Number)
-                .unwrap_or(serde_json::Value::Null),
-            OrderingValue::Int(value) => serde_json::Value::Number(serde_json::Number::from(value)),
+impl OrderingValue {
+    pub fn as_float(&self) -> Option<FloatPayloadType> {
+        match self {
+            OrderingValue::Int(value) => Some(*value as FloatPayloadType),
+            OrderingValue::Float(value) => Some(*value),
+            OrderingValue::Keyword(_) => None,
+        }
+    }
+
+    /// Whether the value is at or after `start` in the given direction
+    pub fn starts_from(&self, start: &OrderingValue, direction: Direction) -> bool {
+        match direction {
+            Direction::Asc => self >= start,
+            Direction::Desc => self <= start,
         }
     }
 }
@@ -159,6 +262,8 @@ impl PartialEq for OrderingValue {
             (OrderingValue::Int(a), OrderingValue::Int(b)) => a == b,
             (OrderingValue::Float(a), OrderingValue::Int(b)) => a.num_eq(*b),
             (OrderingValue::Int(a), OrderingValue::Float(b)) => a.num_eq(*b),
+            (OrderingValue::Keyword(a), OrderingValue::Keyword(b)) => a == b,
+            (OrderingValue::Keyword(_), _) | (_, OrderingValue::Keyword(_)) => false,
         }
     }
 }
@@ -185,6 +290,104 @@ impl Ord for OrderingValue {
                 // Ditto, but the NaN is on the right side of the comparison.
                 a.num_cmp(*b).unwrap_or(std::cmp::Ordering::Less)
             }
+            (OrderingValue::Keyword(a), OrderingValue::Keyword(b)) => a.cmp(b),
+            // Numbers go before keywords
+            (OrderingValue::Keyword(_), _) => std::cmp::Ordering::Greater,
+            (_, OrderingValue::Keyword(_)) => std::cmp::Ordering::Less,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_by(key: &str, direction: Direction) -> OrderBy {
+        OrderBy {
+            key: key.parse().unwrap(),
+            direction: Some(direction),
+            start_from: None,
+        }
+    }
+
+    fn point(values: Vec<Option<OrderingValue>>, id: u64) -> OrderedPoint {
+        OrderedPoint {
+            values,
+            id: id.into(),
         }
     }
+
+    #[test]
+    fn test_multi_key_ordering() {
+        let order_by = MultiOrderBy {
+            keys: vec![
+                order_by("category", Direction::Asc),
+                order_by("price", Direction::Desc),
+            ],
+            cursor: None,
+        };
+
+        let mut points = [
+            point(vec![Some(OrderingValue::Keyword("b".into())), None], 1),
+            point(
+                vec![
+                    Some(OrderingValue::Keyword("a".into())),
+                    Some(OrderingValue::Float(1.5)),
+                ],
+                2,
+            ),
+            point(
+                vec![
+                    Some(OrderingValue::Keyword("b".into())),
+                    Some(OrderingValue::Int(3)),
+                ],
+                3,
+            ),
+            point(
+                vec![
+                    Some(OrderingValue::Keyword("a".into())),
+                    Some(OrderingValue::Int(2)),
+                ],
+                4,
+            ),
+            point(
+                vec![
+                    Some(OrderingValue::Keyword("a".into())),
+                    Some(OrderingValue::Float(2.0)),
+                ],
+                0,
+            ),
+        ];
+        points.sort_by(|a, b| order_by.compare(a, b));
+
+        // Ties of both keys are broken by id, missing values go last
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![0.into(), 4.into(), 2.into(), 3.into(), 1.into()]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let cursor = point(
+            vec![
+                Some(OrderingValue::Keyword("a".into())),
+                Some(OrderingValue::Float(0.5)),
+                Some(OrderingValue::Int(-7)),
+                None,
+            ],
+            42,
+        );
+        let encoded = cursor.to_cursor();
+        assert_eq!(OrderedPoint::from_cursor(&encoded), Some(cursor.clone()));
+        assert_eq!(OrderedPoint::from_cursor("not a cursor"), None);
+
+        let order_by = MultiOrderBy {
+            keys: vec![order_by("price", Direction::Desc)],
+            cursor: Some(point(vec![Some(OrderingValue::Int(5))], 3)),
+        };
+        assert!(!order_by.is_after_cursor(&point(vec![Some(OrderingValue::Int(6))], 10)));
+        assert!(!order_by.is_after_cursor(&point(vec![Some(OrderingValue::Int(5))], 3)));
+        assert!(order_by.is_after_cursor(&point(vec![Some(OrderingValue::Int(5))], 4)));
+        assert!(order_by.is_after_cursor(&point(vec![Some(OrderingValue::Float(4.5))], 1)));
+        assert_eq!(order_by.start_from(), Some(OrderingValue::Int(5)));
+    }
 }
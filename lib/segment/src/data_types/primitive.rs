@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::Distance;
+
+/// Element type of dense vectors, which can be stored and scored natively.
+pub trait PrimitiveVectorElement:
+    Copy + Clone + Default + Debug + PartialEq + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]>;
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]>;
+
+    /// Convert stored vector into `f32` representation, suitable for quantization.
+    ///
+    /// Vectors of types, which are not normalized on insertion, are normalized here if required by distance.
+    fn quantization_preprocess(distance: Distance, vector: &[Self]) -> Cow<[VectorElementType]>;
+}
+
+impl PrimitiveVectorElement for VectorElementType {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        vector
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        vector
+    }
+
+    fn quantization_preprocess(_distance: Distance, vector: &[Self]) -> Cow<[VectorElementType]> {
+        Cow::Borrowed(vector)
+    }
+}
+
+impl PrimitiveVectorElement for VectorElementTypeHalf {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| half::f16::from_f32(x)).collect())
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|x| x.to_f32()).collect())
+    }
+
+    fn quantization_preprocess(_distance: Distance, vector: &[Self]) -> Cow<[VectorElementType]> {
+        Self::slice_to_float_cow(Cow::Borrowed(vector))
+    }
+}
+
+impl PrimitiveVectorElement for VectorElementTypeByte {
+    /// Stored vectors are checked to contain bytes only, query vectors are rounded to the nearest one
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(
+            vector
+                .iter()
+                .map(|&x| x.round().clamp(0.0, u8::MAX as VectorElementType) as u8)
+                .collect(),
+        )
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|&x| x as VectorElementType).collect())
+    }
+
+    fn quantization_preprocess(distance: Distance, vector: &[Self]) -> Cow<[VectorElementType]> {
+        let vector = Self::slice_to_float_cow(Cow::Borrowed(vector));
+        match distance {
+            // Byte vectors are not normalized on insertion, cosine is computed on the fly
+            Distance::Cosine => Cow::Owned(
+                <CosineMetric as Metric<VectorElementType>>::preprocess(vector.into_owned()),
+            ),
+            Distance::Euclid | Distance::Dot | Distance::Manhattan => vector,
+        }
+    }
+}
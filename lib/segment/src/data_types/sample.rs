@@ -0,0 +1,146 @@
+//! Uniform random sampling of points.
+//!
+//! Each sampled point gets a random key, as if every point of the source got an independent
+//! uniform key from `[0, 1)` and the ones with the smallest keys were picked. Samples of several
+//! sources, e.g. segments or shards, are merged into a uniform sample of all of them by picking
+//! points with the smallest keys, so no source needs to know the size of the others.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use common::types::ScoreType;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::types::{PointIdType, SeqNumberType};
+
+/// Point, picked by the random sampling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampledPoint {
+    pub id: PointIdType,
+    pub version: SeqNumberType,
+    /// Sampling key, points with smaller keys are picked first
+    pub key: ScoreType,
+}
+
+fn hash_with_seed(seed: u64, value: impl Hash) -> u64 {
+    // `DefaultHasher::new` is not randomized, so the result is reproducible
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Random generator of the sample of a particular source.
+///
+/// Sources must be sampled independently, so the seed is mixed with the `source` identity.
+pub fn source_rng(seed: u64, source: impl Hash) -> StdRng {
+    StdRng::seed_from_u64(hash_with_seed(seed, source))
+}
+
+/// Sampling key of the point, which only depends on the seed and the point id.
+///
+/// Used to sample candidates, which come from several sources and might repeat.
+pub fn point_sampling_key(seed: u64, id: PointIdType) -> ScoreType {
+    // Keep 24 bits, which is the precision of `f32`
+    (hash_with_seed(seed, id) >> 40) as ScoreType / (1u64 << 24) as ScoreType
+}
+
+/// Sampling keys of `count` points, picked from `total` ones, in ascending order.
+///
+/// Keys are the smallest `count` of `total` independent uniform keys, generated one by one
+/// without generating the rest: the next smallest key is the minimum of the remaining keys,
+/// which are uniform above the previous one.
+pub fn sampling_keys(rng: &mut impl Rng, total: usize, count: usize) -> Vec<ScoreType> {
+    let mut key = 0.0f64;
+    (0..count.min(total))
+        .map(|picked| {
+            let remaining = (total - picked) as f64;
+            let min_of_remaining = 1.0 - rng.gen::<f64>().powf(1.0 / remaining);
+            key += (1.0 - key) * min_of_remaining;
+            key as ScoreType
+        })
+        .collect()
+}
+
+/// Merge samples of several sources into the sample of `limit` points of all of them
+pub fn merge_samples(
+    samples: impl IntoIterator<Item = SampledPoint>,
+    limit: usize,
+) -> Vec<SampledPoint> {
+    samples
+        .into_iter()
+        .sorted_by(|a, b| a.key.total_cmp(&b.key).then_with(|| a.id.cmp(&b.id)))
+        .unique_by(|point| point.id)
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_keys() {
+        let mut rng = source_rng(42, "segment");
+        let keys = sampling_keys(&mut rng, 1000, 10);
+        assert_eq!(keys.len(), 10);
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(keys.iter().all(|key| (0.0..1.0).contains(key)));
+
+        // Can't pick more points than there are
+        assert_eq!(sampling_keys(&mut rng, 3, 10).len(), 3);
+
+        // Same seed and source give the same keys
+        let mut same_rng = source_rng(42, "segment");
+        assert_eq!(sampling_keys(&mut same_rng, 1000, 10), keys);
+    }
+
+    #[test]
+    fn test_merged_samples_are_uniform() {
+        // Sources of very different sizes should be represented proportionally to their size
+        let sizes = [10_000usize, 1_000, 100];
+        let limit = 100;
+        let rounds = 200u64;
+
+        let mut picked = [0usize; 3];
+        for round in 0..rounds {
+            let samples = sizes.iter().enumerate().flat_map(|(source, &size)| {
+                let mut rng = source_rng(round, source);
+                let keys = sampling_keys(&mut rng, size, limit);
+                keys.into_iter()
+                    .enumerate()
+                    .map(move |(offset, key)| SampledPoint {
+                        id: PointIdType::NumId((source * 100_000 + offset) as u64),
+                        version: 0,
+                        key,
+                    })
+            });
+            for point in merge_samples(samples, limit) {
+                let PointIdType::NumId(id) = point.id else {
+                    unreachable!()
+                };
+                picked[id as usize / 100_000] += 1;
+            }
+        }
+
+        let total: usize = sizes.iter().sum();
+        for (source, &size) in sizes.iter().enumerate() {
+            let expected = (limit * rounds as usize * size) as f64 / total as f64;
+            let actual = picked[source] as f64;
+            assert!(
+                (actual - expected).abs() < expected * 0.15 + 10.0,
+                "source {source}: expected {expected}, picked {actual}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_point_sampling_key() {
+        let id = PointIdType::NumId(7);
+        assert_eq!(point_sampling_key(1, id), point_sampling_key(1, id));
+        assert_ne!(point_sampling_key(1, id), point_sampling_key(2, id));
+        assert!((0.0..1.0).contains(&point_sampling_key(1, id)));
+    }
+}
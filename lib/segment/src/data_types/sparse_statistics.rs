@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+/// Statistics of a sparse vector index, required to compute inverse document frequency (IDF).
+///
+/// Statistics are collected only for the requested dimensions and can be accumulated
+/// across segments and shards.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseVectorStatistics {
+    /// Number of indexed vectors
+    pub indexed_vector_count: usize,
+    /// Number of indexed vectors, which contain each dimension
+    pub document_frequencies: HashMap<DimId, usize>,
+}
+
+impl SparseVectorStatistics {
+    /// Empty statistics for the given dimensions
+    pub fn for_dimensions(dimensions: impl IntoIterator<Item = DimId>) -> Self {
+        Self {
+            indexed_vector_count: 0,
+            document_frequencies: dimensions.into_iter().map(|dim| (dim, 0)).collect(),
+        }
+    }
+
+    /// Accumulate statistics collected from another segment or shard
+    pub fn merge(&mut self, other: &Self) {
+        self.indexed_vector_count += other.indexed_vector_count;
+        for (dim, frequency) in &other.document_frequencies {
+            *self.document_frequencies.entry(*dim).or_default() += frequency;
+        }
+    }
+
+    /// Inverse document frequency of the dimension, as defined in BM25:
+    ///
+    /// `idf = ln(1 + (N - n + 0.5) / (n + 0.5))`
+    ///
+    /// where `N` is the number of indexed vectors and `n` is the number of vectors
+    /// containing the dimension.
+    pub fn idf(&self, dim: DimId) -> DimWeight {
+        let total = self.indexed_vector_count as f64;
+        let frequency = self
+            .document_frequencies
+            .get(&dim)
+            .copied()
+            .unwrap_or_default() as f64;
+        // Frequency might exceed the vector count, if some vectors were updated
+        let frequency = frequency.min(total);
+        ((total - frequency + 0.5) / (frequency + 0.5)).ln_1p() as DimWeight
+    }
+
+    /// Multiply weights of the sparse vector by IDF of their dimensions
+    pub fn apply_idf(&self, vector: &mut SparseVector) {
+        for (dim, weight) in vector.indices.iter().zip(vector.values.iter_mut()) {
+            *weight *= self.idf(*dim);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idf() {
+        let mut statistics = SparseVectorStatistics::for_dimensions([1, 2, 3]);
+        statistics.merge(&SparseVectorStatistics {
+            indexed_vector_count: 10,
+            document_frequencies: HashMap::from([(1, 1), (2, 5)]),
+        });
+        statistics.merge(&SparseVectorStatistics {
+            indexed_vector_count: 10,
+            document_frequencies: HashMap::from([(1, 1), (2, 15), (3, 0)]),
+        });
+
+        assert_eq!(statistics.indexed_vector_count, 20);
+        assert_eq!(statistics.document_frequencies[&2], 20);
+
+        // Rare dimensions are weighted higher
+        assert!(statistics.idf(3) > statistics.idf(1));
+        assert!(statistics.idf(1) > statistics.idf(2));
+        // Dimension present in all vectors has a small, but positive weight
+        assert!(statistics.idf(2) > 0.0);
+
+        let mut vector = SparseVector::new(vec![1, 2], vec![1.0, 2.0]).unwrap();
+        statistics.apply_idf(&mut vector);
+        assert_eq!(
+            vector.values,
+            vec![statistics.idf(1), 2.0 * statistics.idf(2)]
+        );
+    }
+}
@@ -32,4 +32,93 @@ pub struct TextIndexParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// If true, lowercase all tokens. Default: true
     pub lowercase: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// If true, remove diacritics from tokens, e.g. `café` becomes `cafe`. Default: false
+    pub ascii_folding: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Tokens to exclude from the index and from queries. Default: no stopwords
+    pub stopwords: Option<StopwordsInterface>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Reduce tokens to their stems, so different forms of a word match each other.
+    /// Default: no stemming
+    pub stemmer: Option<StemmingAlgorithm>,
+}
+
+/// Language with a predefined list of stopwords
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopwordsLanguage {
+    Dutch,
+    English,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Russian,
+    Spanish,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StopwordsInterface {
+    /// Predefined stopwords of the language
+    Language(StopwordsLanguage),
+    /// Predefined stopwords of several languages along with custom ones
+    Set(StopwordsSet),
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct StopwordsSet {
+    /// Languages to take predefined stopwords of
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<StopwordsLanguage>,
+    /// Custom stopwords
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<String>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Snowball {
+    #[default]
+    Snowball,
+}
+
+/// Language of the Snowball stemmer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnowballLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SnowballParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: Snowball,
+    pub language: SnowballLanguage,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StemmingAlgorithm {
+    Snowball(SnowballParams),
 }
@@ -6,7 +6,7 @@ use sparse::common::sparse_vector::SparseVector;
 use validator::Validate;
 
 use super::named_vectors::NamedVectors;
-use crate::common::operation_error::OperationError;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::transpose_map_into_named_vector;
 use crate::vector_storage::query::context_query::ContextQuery;
 use crate::vector_storage::query::discovery_query::DiscoveryQuery;
@@ -17,12 +17,14 @@ use crate::vector_storage::query::reco_query::RecoQuery;
 pub enum Vector {
     Dense(DenseVector),
     Sparse(SparseVector),
+    MultiDense(MultiDenseVector),
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum VectorRef<'a> {
     Dense(&'a [VectorElementType]),
     Sparse(&'a SparseVector),
+    MultiDense(&'a MultiDenseVector),
 }
 
 impl Vector {
@@ -30,6 +32,7 @@ impl Vector {
         match self {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
+            Vector::MultiDense(v) => VectorRef::MultiDense(v),
         }
     }
 }
@@ -39,6 +42,7 @@ impl Validate for Vector {
         match self {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
+            Vector::MultiDense(v) => v.validate(),
         }
     }
 }
@@ -48,6 +52,7 @@ impl<'a> VectorRef<'a> {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.clone()),
         }
     }
 }
@@ -59,6 +64,7 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a [VectorElementType] {
         match value {
             VectorRef::Dense(v) => Ok(v),
             VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -70,6 +76,19 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a SparseVector {
         match value {
             VectorRef::Dense(_) => Err(OperationError::WrongSparse),
             VectorRef::Sparse(v) => Ok(v),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
+        }
+    }
+}
+
+impl<'a> TryFrom<VectorRef<'a>> for &'a MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: VectorRef<'a>) -> Result<Self, Self::Error> {
+        match value {
+            VectorRef::Dense(_) => Err(OperationError::WrongMulti),
+            VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -80,6 +99,7 @@ impl From<NamedVectorStruct> for Vector {
             NamedVectorStruct::Default(v) => Vector::Dense(v),
             NamedVectorStruct::Dense(v) => Vector::Dense(v.vector),
             NamedVectorStruct::Sparse(v) => Vector::Sparse(v.vector),
+            NamedVectorStruct::MultiDense(v) => Vector::MultiDense(v.vector),
         }
     }
 }
@@ -91,6 +111,7 @@ impl TryFrom<Vector> for DenseVector {
         match value {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -102,6 +123,23 @@ impl TryFrom<Vector> for SparseVector {
         match value {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
+        }
+    }
+}
+
+impl TryFrom<Vector> for MultiDenseVector {
+    type Error = OperationError;
+
+    /// Dense vectors are accepted as a multi-vector with a single row
+    fn try_from(value: Vector) -> Result<Self, Self::Error> {
+        match value {
+            Vector::Dense(v) => {
+                let dim = v.len();
+                Ok(MultiDenseVector::new(v, dim))
+            }
+            Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(v) => Ok(v),
         }
     }
 }
@@ -130,17 +168,30 @@ impl From<DenseVector> for Vector {
     }
 }
 
+impl<'a> From<&'a MultiDenseVector> for VectorRef<'a> {
+    fn from(val: &'a MultiDenseVector) -> Self {
+        VectorRef::MultiDense(val)
+    }
+}
+
 impl From<SparseVector> for Vector {
     fn from(val: SparseVector) -> Self {
         Vector::Sparse(val)
     }
 }
 
+impl From<MultiDenseVector> for Vector {
+    fn from(val: MultiDenseVector) -> Self {
+        Vector::MultiDense(val)
+    }
+}
+
 impl<'a> From<&'a Vector> for VectorRef<'a> {
     fn from(val: &'a Vector) -> Self {
         match val {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
+            Vector::MultiDense(v) => VectorRef::MultiDense(v),
         }
     }
 }
@@ -148,17 +199,120 @@ impl<'a> From<&'a Vector> for VectorRef<'a> {
 /// Type of vector element.
 pub type VectorElementType = f32;
 
+/// Type of vector element for half-precision storage.
+pub type VectorElementTypeHalf = half::f16;
+
+/// Type of vector element for byte storage.
+pub type VectorElementTypeByte = u8;
+
 pub const DEFAULT_VECTOR_NAME: &str = "";
 
 /// Type for dense vector
 pub type DenseVector = Vec<VectorElementType>;
 
+/// Type for dense vector with a specific element type
+pub type TypedDenseVector<T> = Vec<T>;
+
+/// Type for multi dense vector
+///
+/// Holds a matrix of dense vectors of the same dimension, stored row by row in a single
+/// flattened buffer. Serialized as a list of dense vectors.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<DenseVector>", into = "Vec<DenseVector>")]
+pub struct MultiDenseVector {
+    /// All vectors, concatenated one after another
+    pub flattened_vectors: DenseVector,
+    /// Dimension of each inner vector
+    pub dim: usize,
+}
+
+impl MultiDenseVector {
+    pub fn new(flattened_vectors: DenseVector, dim: usize) -> Self {
+        debug_assert!(dim == 0 || flattened_vectors.len() % dim == 0);
+        Self {
+            flattened_vectors,
+            dim,
+        }
+    }
+
+    /// Build a multi dense vector from a matrix, all rows must have the same dimension
+    pub fn try_from_matrix(matrix: Vec<DenseVector>) -> OperationResult<Self> {
+        let dim = matrix.first().map(|v| v.len()).unwrap_or(0);
+        if let Some(wrong) = matrix.iter().find(|v| v.len() != dim) {
+            return Err(OperationError::WrongVector {
+                expected_dim: dim,
+                received_dim: wrong.len(),
+            });
+        }
+        Ok(Self::new(matrix.into_iter().flatten().collect(), dim))
+    }
+
+    /// Iterate over the inner dense vectors
+    pub fn multi_vectors(&self) -> impl Iterator<Item = &[VectorElementType]> {
+        self.flattened_vectors.chunks_exact(self.dim.max(1))
+    }
+
+    /// Number of inner dense vectors
+    pub fn vectors_count(&self) -> usize {
+        self.flattened_vectors
+            .len()
+            .checked_div(self.dim)
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors_count() == 0
+    }
+
+    pub fn into_matrix(self) -> Vec<DenseVector> {
+        self.multi_vectors().map(|v| v.to_vec()).collect()
+    }
+}
+
+impl TryFrom<Vec<DenseVector>> for MultiDenseVector {
+    type Error = OperationError;
+
+    fn try_from(value: Vec<DenseVector>) -> Result<Self, Self::Error> {
+        Self::try_from_matrix(value)
+    }
+}
+
+impl From<MultiDenseVector> for Vec<DenseVector> {
+    fn from(value: MultiDenseVector) -> Self {
+        value.into_matrix()
+    }
+}
+
+impl JsonSchema for MultiDenseVector {
+    fn schema_name() -> String {
+        "MultiDenseVector".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <Vec<DenseVector>>::json_schema(gen)
+    }
+}
+
+impl Validate for MultiDenseVector {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        if self.is_empty() {
+            let mut errors = validator::ValidationErrors::default();
+            let mut error = validator::ValidationError::new("empty_multi_vector");
+            error.message = Some("multi vector must contain at least one non-empty vector".into());
+            errors.add("data", error);
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
 impl<'a> VectorRef<'a> {
     // Cannot use `ToOwned` trait because of `Borrow` implementation for `Vector`
     pub fn to_owned(self) -> Vector {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
+            VectorRef::MultiDense(v) => Vector::MultiDense(v.clone()),
         }
     }
 
@@ -166,6 +320,7 @@ impl<'a> VectorRef<'a> {
         match self {
             VectorRef::Dense(v) => v.len(),
             VectorRef::Sparse(v) => v.indices.len(),
+            VectorRef::MultiDense(v) => v.flattened_vectors.len(),
         }
     }
 
@@ -181,6 +336,7 @@ impl<'a> TryInto<&'a [VectorElementType]> for &'a Vector {
         match self {
             Vector::Dense(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -192,6 +348,7 @@ impl<'a> TryInto<&'a SparseVector> for &'a Vector {
         match self {
             Vector::Dense(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
     }
 }
@@ -220,6 +377,7 @@ impl VectorStruct {
             VectorStruct::Multi(vectors) => vectors.values().all(|v| match v {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
+                Vector::MultiDense(vector) => vector.is_empty(),
             }),
         }
     }
@@ -322,6 +480,17 @@ pub struct NamedSparseVector {
     pub vector: SparseVector,
 }
 
+/// Multi dense vector data with name
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedMultiDenseVector {
+    /// Name of vector data
+    pub name: String,
+    /// Vector data
+    #[validate]
+    pub vector: MultiDenseVector,
+}
+
 /// Vector data separator for named and unnamed modes
 /// Unnamed mode:
 ///
@@ -344,6 +513,7 @@ pub enum NamedVectorStruct {
     Default(DenseVector),
     Dense(NamedVector),
     Sparse(NamedSparseVector),
+    MultiDense(NamedMultiDenseVector),
 }
 
 impl From<DenseVector> for NamedVectorStruct {
@@ -364,6 +534,12 @@ impl From<NamedSparseVector> for NamedVectorStruct {
     }
 }
 
+impl From<NamedMultiDenseVector> for NamedVectorStruct {
+    fn from(v: NamedMultiDenseVector) -> Self {
+        NamedVectorStruct::MultiDense(v)
+    }
+}
+
 pub trait Named {
     fn get_name(&self) -> &str;
 }
@@ -374,6 +550,7 @@ impl Named for NamedVectorStruct {
             NamedVectorStruct::Default(_) => DEFAULT_VECTOR_NAME,
             NamedVectorStruct::Dense(v) => &v.name,
             NamedVectorStruct::Sparse(v) => &v.name,
+            NamedVectorStruct::MultiDense(v) => &v.name,
         }
     }
 }
@@ -383,6 +560,9 @@ impl NamedVectorStruct {
         match vector {
             Vector::Dense(vector) => NamedVectorStruct::Dense(NamedVector { name, vector }),
             Vector::Sparse(vector) => NamedVectorStruct::Sparse(NamedSparseVector { name, vector }),
+            Vector::MultiDense(vector) => {
+                NamedVectorStruct::MultiDense(NamedMultiDenseVector { name, vector })
+            }
         }
     }
 
@@ -391,6 +571,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.as_slice().into(),
             NamedVectorStruct::Dense(v) => v.vector.as_slice().into(),
             NamedVectorStruct::Sparse(v) => (&v.vector).into(),
+            NamedVectorStruct::MultiDense(v) => (&v.vector).into(),
         }
     }
 
@@ -399,6 +580,7 @@ impl NamedVectorStruct {
             NamedVectorStruct::Default(v) => v.into(),
             NamedVectorStruct::Dense(v) => v.vector.into(),
             NamedVectorStruct::Sparse(v) => v.vector.into(),
+            NamedVectorStruct::MultiDense(v) => v.vector.into(),
         }
     }
 }
@@ -409,6 +591,7 @@ impl Validate for NamedVectorStruct {
             NamedVectorStruct::Default(_) => Ok(()),
             NamedVectorStruct::Dense(_) => Ok(()),
             NamedVectorStruct::Sparse(v) => v.validate(),
+            NamedVectorStruct::MultiDense(v) => v.validate(),
         }
     }
 }
@@ -515,6 +698,12 @@ impl From<SparseVector> for QueryVector {
     }
 }
 
+impl From<MultiDenseVector> for QueryVector {
+    fn from(vec: MultiDenseVector) -> Self {
+        Self::Nearest(Vector::MultiDense(vec))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -685,4 +874,39 @@ mod tests {
             ])),
         );
     }
+
+    #[test]
+    fn multi_dense_vector_serde() {
+        let multi: MultiDenseVector =
+            serde_json::from_str("[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]").unwrap();
+        assert_eq!(multi.dim, 3);
+        assert_eq!(multi.vectors_count(), 2);
+        assert_eq!(multi.flattened_vectors, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(
+            serde_json::to_string(&multi).unwrap(),
+            "[[1.0,2.0,3.0],[4.0,5.0,6.0]]",
+        );
+
+        // Untagged vector resolves to the multi dense variant
+        let vector: Vector = serde_json::from_str("[[1.0, 2.0], [3.0, 4.0]]").unwrap();
+        assert_eq!(
+            vector,
+            Vector::MultiDense(MultiDenseVector::new(vec![1.0, 2.0, 3.0, 4.0], 2)),
+        );
+
+        // Inner vectors of different dimensions are rejected
+        assert!(serde_json::from_str::<MultiDenseVector>("[[1.0, 2.0], [3.0]]").is_err());
+    }
+
+    #[test]
+    fn multi_dense_vector_validation() {
+        let multi = MultiDenseVector::try_from_matrix(vec![vec![1.0, 2.0]]).unwrap();
+        assert!(multi.validate().is_ok());
+
+        let empty = MultiDenseVector::try_from_matrix(vec![]).unwrap();
+        assert!(empty.validate().is_err());
+
+        let empty_inner = MultiDenseVector::try_from_matrix(vec![vec![], vec![]]).unwrap();
+        assert!(empty_inner.validate().is_err());
+    }
 }
@@ -2,13 +2,17 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 
-use common::types::TelemetryDetail;
+use common::types::{ScoreType, TelemetryDetail};
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::data_types::facets::{FacetCounts, FacetParams};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{OrderBy, OrderingValue};
+use crate::data_types::order_by::{MultiOrderBy, OrderedPoint};
+use crate::data_types::sample::SampledPoint;
+use crate::data_types::sparse_statistics::SparseVectorStatistics;
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
@@ -128,16 +132,44 @@ pub trait SegmentEntry {
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType>;
 
-    /// Return points which satisfies filtering condition ordered by the `order_by.key` field,
-    /// starting with `order_by.start_from` value including.
+    /// Return points which satisfies filtering condition ordered by the `order_by.keys` fields,
+    /// starting with the primary key `start_from` value including, or right after `order_by.cursor`.
     ///
-    /// Will fail if there is no index for the order_by key.
+    /// Will fail if there is no index for any of the order_by keys.
     fn read_ordered_filtered<'a>(
         &'a self,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-        order_by: &'a OrderBy,
-    ) -> OperationResult<Vec<(OrderingValue, PointIdType)>>;
+        order_by: &'a MultiOrderBy,
+    ) -> OperationResult<Vec<OrderedPoint>>;
+
+    /// Count points which satisfy `params.filter` for each value of the `params.key` field,
+    /// or for each of `params.ranges` if any are given.
+    ///
+    /// Will fail if there is no suitable index for the key.
+    fn facet(&self, params: &FacetParams, is_stopped: &AtomicBool) -> OperationResult<FacetCounts>;
+
+    /// Return up to `limit` uniformly random points which satisfy the filtering condition.
+    ///
+    /// Points are returned in the ascending order of their sampling keys, so samples of several
+    /// segments can be merged. Same `seed` gives the same points while the segment is unchanged.
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        seed: u64,
+    ) -> Vec<SampledPoint>;
+
+    /// Score points, found by prefetches, with the formula.
+    ///
+    /// `prefetches_scores` holds scores of points in each prefetch. Only points of this segment,
+    /// which are found by at least one of the prefetches, are scored.
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_scores: &[HashMap<PointIdType, ScoreType>],
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
 
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
@@ -152,6 +184,15 @@ pub trait SegmentEntry {
 
     fn vector_dims(&self) -> HashMap<String, usize>;
 
+    /// Accumulate statistics of the sparse vector index, required to compute IDF.
+    ///
+    /// Only dimensions already present in `statistics` are collected.
+    fn fill_sparse_vector_statistics(
+        &self,
+        vector_name: &str,
+        statistics: &mut SparseVectorStatistics,
+    ) -> OperationResult<()>;
+
     /// Number of available points
     ///
     /// - excludes soft deleted points
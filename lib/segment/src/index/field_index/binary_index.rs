@@ -209,6 +209,15 @@ impl BinaryIndex {
     pub fn values_has_false(&self, point_id: PointOffsetType) -> bool {
         self.memory.get(point_id).has_false()
     }
+
+    /// Number of points which have the given value
+    pub fn get_points_with_value_count(&self, value: bool) -> usize {
+        if value {
+            self.memory.trues_count()
+        } else {
+            self.memory.falses_count()
+        }
+    }
 }
 
 impl PayloadFieldIndex for BinaryIndex {
@@ -1,6 +1,7 @@
 use std::fmt::Formatter;
 
 use common::types::PointOffsetType;
+use itertools::Itertools;
 use serde_json::Value;
 use smol_str::SmolStr;
 
@@ -8,6 +9,7 @@ use super::map_index::MapIndex;
 use super::numeric_index::StreamRange;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::facets::FacetValue;
 use crate::data_types::order_by::OrderingValue;
 use crate::index::field_index::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
@@ -16,8 +18,8 @@ use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchPhrase,
+    MatchText, PayloadKeyType, RangeInterface, UuidIntType,
 };
 
 pub trait PayloadFieldIndex {
@@ -107,6 +109,7 @@ pub enum FieldIndex {
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
     BinaryIndex(BinaryIndex),
+    UuidMapIndex(MapIndex<UuidIntType>),
 }
 
 impl std::fmt::Debug for FieldIndex {
@@ -120,6 +123,7 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
             FieldIndex::FullTextIndex(_index) => write!(f, "FullTextIndex"),
+            FieldIndex::UuidMapIndex(_index) => write!(f, "UuidMapIndex"),
         }
     }
 }
@@ -142,6 +146,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
+            FieldIndex::UuidMapIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
@@ -156,6 +161,16 @@ impl FieldIndex {
                     }
                     Some(false)
                 }
+                Some(Match::Phrase(MatchPhrase { phrase })) => {
+                    let query = full_text_index.parse_phrase(phrase);
+                    for value in full_text_index.get_values(payload_value) {
+                        let document = full_text_index.parse_document(&value);
+                        if query.check_match(&document) {
+                            return Some(true);
+                        }
+                    }
+                    Some(false)
+                }
                 _ => None,
             },
         }
@@ -167,6 +182,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
@@ -181,6 +197,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index,
@@ -194,6 +211,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
@@ -207,6 +225,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.clear(),
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
+            FieldIndex::UuidMapIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
@@ -220,6 +239,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.recreate(),
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
+            FieldIndex::UuidMapIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
@@ -273,6 +293,9 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -294,6 +317,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidMapIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
@@ -307,6 +331,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.get_telemetry_data(),
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
+            FieldIndex::UuidMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
@@ -320,6 +345,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_count(point_id),
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
@@ -333,6 +359,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_is_empty(point_id),
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
@@ -347,11 +374,40 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BinaryIndex(_)
             | FieldIndex::FullTextIndex(_) => None,
         }
     }
+
+    pub fn as_orderable(&self) -> Option<OrderableFieldIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(OrderableFieldIndex::KeywordIndex(index)),
+            FieldIndex::IntIndex(_) | FieldIndex::DatetimeIndex(_) | FieldIndex::FloatIndex(_) => {
+                self.as_numeric().map(OrderableFieldIndex::NumericIndex)
+            }
+            FieldIndex::IntMapIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
+
+    pub fn as_facet_index(&self) -> Option<FacetIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(FacetIndex::KeywordIndex(index)),
+            FieldIndex::IntMapIndex(index) => Some(FacetIndex::IntMapIndex(index)),
+            FieldIndex::BinaryIndex(index) => Some(FacetIndex::BinaryIndex(index)),
+            FieldIndex::UuidMapIndex(index) => Some(FacetIndex::UuidMapIndex(index)),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
 }
 
 pub enum NumericFieldIndex<'a> {
@@ -404,3 +460,113 @@ impl<'a> NumericFieldIndex<'a> {
         }
     }
 }
+
+/// Field index which can provide comparable values of points, used to order points by the field
+pub enum OrderableFieldIndex<'a> {
+    NumericIndex(NumericFieldIndex<'a>),
+    KeywordIndex(&'a MapIndex<SmolStr>),
+}
+
+impl<'a> OrderableFieldIndex<'a> {
+    pub fn get_ordering_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Box<dyn Iterator<Item = OrderingValue> + 'a> {
+        match self {
+            OrderableFieldIndex::NumericIndex(index) => index.get_ordering_values(idx),
+            OrderableFieldIndex::KeywordIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .map(|value| OrderingValue::Keyword(value.to_string())),
+            ),
+        }
+    }
+}
+
+/// Field index which can enumerate values of points, used to count points per value
+pub enum FacetIndex<'a> {
+    KeywordIndex(&'a MapIndex<SmolStr>),
+    IntMapIndex(&'a MapIndex<IntPayloadType>),
+    BinaryIndex(&'a BinaryIndex),
+    UuidMapIndex(&'a MapIndex<UuidIntType>),
+}
+
+impl<'a> FacetIndex<'a> {
+    /// Distinct values of the point
+    pub fn get_point_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Box<dyn Iterator<Item = FacetValue> + 'a> {
+        match self {
+            FacetIndex::KeywordIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .unique()
+                    .map(FacetValue::from),
+            ),
+            FacetIndex::IntMapIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .unique()
+                    .copied()
+                    .map(FacetValue::Int),
+            ),
+            FacetIndex::UuidMapIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .unique()
+                    .map(|uuid| FacetValue::from_uuid(*uuid)),
+            ),
+            FacetIndex::BinaryIndex(index) => {
+                let has_true = index.values_has_true(idx);
+                let has_false = index.values_has_false(idx);
+                Box::new(
+                    has_true
+                        .then_some(FacetValue::Bool(true))
+                        .into_iter()
+                        .chain(has_false.then_some(FacetValue::Bool(false))),
+                )
+            }
+        }
+    }
+
+    /// All indexed values with the number of points having each of them
+    pub fn iter_values_counts(&self) -> Box<dyn Iterator<Item = (FacetValue, usize)> + 'a> {
+        match self {
+            FacetIndex::KeywordIndex(index) => Box::new(
+                index
+                    .iter_values_counts()
+                    .map(|(value, count)| (FacetValue::from(value), count)),
+            ),
+            FacetIndex::IntMapIndex(index) => Box::new(
+                index
+                    .iter_values_counts()
+                    .map(|(value, count)| (FacetValue::Int(*value), count)),
+            ),
+            FacetIndex::UuidMapIndex(index) => Box::new(
+                index
+                    .iter_values_counts()
+                    .map(|(uuid, count)| (FacetValue::from_uuid(*uuid), count)),
+            ),
+            FacetIndex::BinaryIndex(index) => Box::new(
+                [true, false]
+                    .into_iter()
+                    .map(|value| {
+                        (
+                            FacetValue::Bool(value),
+                            index.get_points_with_value_count(value),
+                        )
+                    })
+                    .filter(|(_, count)| *count > 0),
+            ),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, HashMap};
 
 use common::types::PointOffsetType;
 use serde::{Deserialize, Serialize};
@@ -16,12 +16,24 @@ pub type TokenId = u32;
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct Document {
     tokens: Vec<TokenId>,
+    /// Word positions of each token in the text, empty if not known
+    #[serde(default)]
+    positions: Vec<Vec<u32>>,
 }
 
 impl Document {
     pub fn new(mut tokens: Vec<TokenId>) -> Self {
         tokens.sort_unstable();
-        Self { tokens }
+        Self {
+            tokens,
+            positions: Vec::new(),
+        }
+    }
+
+    pub fn with_positions(mut tokens: Vec<(TokenId, Vec<u32>)>) -> Self {
+        tokens.sort_unstable_by_key(|(token, _)| *token);
+        let (tokens, positions) = tokens.into_iter().unzip();
+        Self { tokens, positions }
     }
 
     pub fn len(&self) -> usize {
@@ -39,15 +51,33 @@ impl Document {
     pub fn check(&self, token: TokenId) -> bool {
         self.tokens.binary_search(&token).is_ok()
     }
+
+    pub fn positions(&self, token: TokenId) -> Option<&[u32]> {
+        let idx = self.tokens.binary_search(&token).ok()?;
+        self.positions.get(idx).map(Vec::as_slice)
+    }
+
+    /// Take out positions of tokens, leaving the document without them
+    pub fn take_positions(&mut self) -> Vec<Vec<u32>> {
+        std::mem::take(&mut self.positions)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParsedQuery {
     pub tokens: Vec<Option<TokenId>>,
+    /// For phrase queries, word positions of `tokens` relative to the first one.
+    /// Tokens must appear in the document at the same relative positions.
+    pub phrase_positions: Option<Vec<u32>>,
 }
 
 impl ParsedQuery {
     pub fn check_match(&self, document: &Document) -> bool {
+        self.check_tokens(document) && self.check_phrase(|token| document.positions(token))
+    }
+
+    /// Check that all tokens are in the document, regardless of their positions
+    fn check_tokens(&self, document: &Document) -> bool {
         if self.tokens.contains(&None) {
             return false;
         }
@@ -57,6 +87,32 @@ impl ParsedQuery {
             // unwrap crash safety: all tokens exist in the vocabulary if it passes the above check
             .all(|query_token| document.check(query_token.unwrap()))
     }
+
+    /// Check that tokens appear in the document at the positions of the phrase.
+    /// Always true if the query is not a phrase.
+    fn check_phrase<'a>(&self, token_positions: impl Fn(TokenId) -> Option<&'a [u32]>) -> bool {
+        let Some(phrase_positions) = &self.phrase_positions else {
+            return true;
+        };
+        let Some(positions) = self
+            .tokens
+            .iter()
+            .map(|token| token_positions((*token)?))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        let Some((first_token_positions, other_tokens_positions)) = positions.split_first() else {
+            return false;
+        };
+
+        first_token_positions.iter().any(|&start| {
+            other_tokens_positions
+                .iter()
+                .zip(&phrase_positions[1..])
+                .all(|(positions, &offset)| positions.binary_search(&(start + offset)).is_ok())
+        })
+    }
 }
 
 pub enum InvertedIndex {
@@ -73,7 +129,7 @@ impl InvertedIndex {
         }
     }
 
-    pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
+    pub fn document_from_tokens(&mut self, tokens: &BTreeMap<String, Vec<u32>>) -> Document {
         let vocab = match self {
             InvertedIndex::Mutable(index) => &mut index.vocab,
             InvertedIndex::Immutable(index) => &mut index.vocab,
@@ -83,10 +139,10 @@ impl InvertedIndex {
 
     fn document_from_tokens_impl(
         vocab: &mut HashMap<String, TokenId>,
-        tokens: &BTreeSet<String>,
+        tokens: &BTreeMap<String, Vec<u32>>,
     ) -> Document {
         let mut document_tokens = vec![];
-        for token in tokens {
+        for (token, positions) in tokens {
             // check if in vocab
             let vocab_idx = match vocab.get(token) {
                 Some(&idx) => idx,
@@ -96,10 +152,10 @@ impl InvertedIndex {
                     next_token_id
                 }
             };
-            document_tokens.push(vocab_idx);
+            document_tokens.push((vocab_idx, positions.clone()));
         }
 
-        Document::new(document_tokens)
+        Document::with_positions(document_tokens)
     }
 
     pub fn index_document(
@@ -238,7 +294,7 @@ impl InvertedIndex {
 
     pub fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeMap<String, Vec<u32>>)>>,
     ) -> OperationResult<()> {
         let mut index = MutableInvertedIndex::default();
         index.build_index(iter)?;
@@ -302,7 +358,7 @@ pub struct MutableInvertedIndex {
 impl MutableInvertedIndex {
     fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeMap<String, Vec<u32>>)>>,
     ) -> OperationResult<()> {
         self.points_count = 0;
         self.vocab.clear();
@@ -325,8 +381,10 @@ impl MutableInvertedIndex {
 
         // build postings from point_to_docs
         // build in order to increase document id
-        for (idx, doc) in self.point_to_docs.iter().enumerate() {
+        // positions are moved into postings, documents keep tokens only
+        for (idx, doc) in self.point_to_docs.iter_mut().enumerate() {
             if let Some(doc) = doc {
+                let mut positions = doc.take_positions().into_iter();
                 for token_idx in doc.tokens() {
                     if self.postings.len() <= *token_idx as usize {
                         self.postings
@@ -336,10 +394,12 @@ impl MutableInvertedIndex {
                         .postings
                         .get_mut(*token_idx as usize)
                         .expect("posting must exist even if with None");
-                    match posting {
-                        None => *posting = Some(PostingList::new(idx as PointOffsetType)),
-                        Some(vec) => vec.insert(idx as PointOffsetType),
-                    }
+                    posting
+                        .get_or_insert_with(PostingList::default)
+                        .insert_with_positions(
+                            idx as PointOffsetType,
+                            positions.next().unwrap_or_default(),
+                        );
                 }
             }
         }
@@ -347,13 +407,19 @@ impl MutableInvertedIndex {
         Ok(())
     }
 
-    fn index_document(&mut self, idx: PointOffsetType, document: Document) -> OperationResult<()> {
+    fn index_document(
+        &mut self,
+        idx: PointOffsetType,
+        mut document: Document,
+    ) -> OperationResult<()> {
         self.points_count += 1;
         if self.point_to_docs.len() <= idx as usize {
             self.point_to_docs
                 .resize_with(idx as usize + 1, Default::default);
         }
 
+        // positions are moved into postings, document keeps tokens only
+        let mut positions = document.take_positions().into_iter();
         for token_idx in document.tokens() {
             let token_idx_usize = *token_idx as usize;
             if self.postings.len() <= token_idx_usize {
@@ -364,10 +430,9 @@ impl MutableInvertedIndex {
                 .postings
                 .get_mut(token_idx_usize)
                 .expect("posting must exist even if with None");
-            match posting {
-                None => *posting = Some(PostingList::new(idx)),
-                Some(vec) => vec.insert(idx),
-            }
+            posting
+                .get_or_insert_with(PostingList::default)
+                .insert_with_positions(idx, positions.next().unwrap_or_default());
         }
         self.point_to_docs[idx as usize] = Some(document);
         Ok(())
@@ -415,7 +480,15 @@ impl MutableInvertedIndex {
             // Empty request -> no matches
             return Box::new(vec![].into_iter());
         }
-        intersect_postings_iterator(postings)
+        let matches = intersect_postings_iterator(postings);
+        if query.phrase_positions.is_none() {
+            return matches;
+        }
+        let query = query.clone();
+        Box::new(
+            matches
+                .filter(move |&idx| query.check_phrase(|token| self.token_positions(token, idx))),
+        )
     }
 
     fn values_count(&self, point_id: PointOffsetType) -> usize {
@@ -429,7 +502,9 @@ impl MutableInvertedIndex {
 
     fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         if let Some(doc) = self.get_doc(point_id) {
-            parsed_query.check_match(doc)
+            // positions of stored documents are kept in postings
+            parsed_query.check_tokens(doc)
+                && parsed_query.check_phrase(|token| self.token_positions(token, point_id))
         } else {
             false
         }
@@ -439,6 +514,10 @@ impl MutableInvertedIndex {
         self.point_to_docs.get(idx as usize)?.as_ref()
     }
 
+    fn token_positions(&self, token: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        self.postings.get(token as usize)?.as_ref()?.positions(&idx)
+    }
+
     fn vocab_with_positngs_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &posting_idx)| {
             if let Some(Some(postings)) = self.postings.get(posting_idx as usize) {
@@ -492,7 +571,15 @@ impl ImmutableInvertedIndex {
         // in case of immutable index, deleted documents are still in the postings
         let filter =
             move |idx| matches!(self.point_documents_tokens.get(idx as usize), Some(Some(_)));
-        intersect_compressed_postings_iterator(postings, filter)
+        let matches = intersect_compressed_postings_iterator(postings, filter);
+        if query.phrase_positions.is_none() {
+            return matches;
+        }
+        let query = query.clone();
+        Box::new(
+            matches
+                .filter(move |&idx| query.check_phrase(|token| self.token_positions(token, idx))),
+        )
     }
 
     fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
@@ -529,6 +616,11 @@ impl ImmutableInvertedIndex {
                     false
                 }
             })
+            && parsed_query.check_phrase(|token| self.token_positions(token, point_id))
+    }
+
+    fn token_positions(&self, token: TokenId, idx: PointOffsetType) -> Option<&[u32]> {
+        self.postings.get(token as usize)?.as_ref()?.positions(&idx)
     }
 
     fn vocab_with_positngs_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
@@ -1,6 +1,7 @@
 mod inverted_index;
 mod posting_list;
 mod postings_iterator;
+mod stop_words;
 pub mod text_index;
 mod tokenizers;
 
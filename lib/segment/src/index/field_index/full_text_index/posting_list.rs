@@ -6,16 +6,20 @@ type BitPackerImpl = bitpacking::BitPacker4x;
 #[derive(Clone, Debug, Default)]
 pub struct PostingList {
     list: Vec<PointOffsetType>,
+    // word positions of the token in each document of `list`, empty if not known
+    positions: Vec<Vec<u32>>,
 }
 
 impl PostingList {
-    pub fn new(idx: PointOffsetType) -> Self {
-        Self { list: vec![idx] }
+    #[cfg(test)]
+    pub fn insert(&mut self, idx: PointOffsetType) {
+        self.insert_with_positions(idx, Vec::new());
     }
 
-    pub fn insert(&mut self, idx: PointOffsetType) {
+    pub fn insert_with_positions(&mut self, idx: PointOffsetType, positions: Vec<u32>) {
         if self.list.is_empty() || idx > *self.list.last().unwrap() {
             self.list.push(idx);
+            self.positions.push(positions);
         } else if let Err(insertion_idx) = self.list.binary_search(&idx) {
             // Yes, this is O(n) but:
             // 1. That would give us maximal search performance with minimal memory usage
@@ -23,12 +27,14 @@ impl PostingList {
             // 3. Vector indexing is more expensive anyway
             // 4. For loading, insertion is strictly in increasing order
             self.list.insert(insertion_idx, idx);
+            self.positions.insert(insertion_idx, positions);
         }
     }
 
     pub fn remove(&mut self, idx: PointOffsetType) {
         if let Ok(removal_idx) = self.list.binary_search(&idx) {
             self.list.remove(removal_idx);
+            self.positions.remove(removal_idx);
         }
     }
 
@@ -40,6 +46,12 @@ impl PostingList {
         self.list.binary_search(val).is_ok()
     }
 
+    /// Word positions of the token in the document
+    pub fn positions(&self, val: &PointOffsetType) -> Option<&[u32]> {
+        let idx = self.list.binary_search(val).ok()?;
+        Some(&self.positions[idx])
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         self.list.iter().copied()
     }
@@ -52,6 +64,10 @@ pub struct CompressedPostingList {
     chunks: Vec<CompressedPostingChunk>,
     // last postings that are not compressed because they are not aligned with the block size
     reminder_postings: Vec<PointOffsetType>,
+    // word positions of the token in documents, concatenated in the order of documents
+    positions: Vec<u32>,
+    // positions of the i-th document are `positions[positions_offsets[i]..positions_offsets[i + 1]]`
+    positions_offsets: Vec<u32>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -105,31 +121,63 @@ impl CompressedPostingList {
             );
         }
 
+        // flatten positions
+        let mut positions = Vec::new();
+        let mut positions_offsets = Vec::with_capacity(posting_list.positions.len() + 1);
+        positions_offsets.push(0);
+        for document_positions in &posting_list.positions {
+            positions.extend_from_slice(document_positions);
+            positions_offsets.push(positions.len() as u32);
+        }
+
         Self {
             last_doc_id: *posting_list.list.last().unwrap(),
             data,
             chunks,
             reminder_postings: noncompressed_postings,
+            positions,
+            positions_offsets,
         }
     }
 
     pub fn contains(&self, val: &PointOffsetType) -> bool {
+        self.rank(val).is_some()
+    }
+
+    /// Word positions of the token in the document
+    pub fn positions(&self, val: &PointOffsetType) -> Option<&[u32]> {
+        let rank = self.rank(val)?;
+        let start = *self.positions_offsets.get(rank)? as usize;
+        let end = *self.positions_offsets.get(rank + 1)? as usize;
+        Some(&self.positions[start..end])
+    }
+
+    /// Index of the value in the posting list, if present
+    fn rank(&self, val: &PointOffsetType) -> Option<usize> {
         if !self.is_in_postings_range(*val) {
-            return false;
+            return None;
         }
 
         // find the chunk that may contain the value and check if the value is in the chunk
         let chunk_index = self.find_chunk(val, None);
         if let Some(chunk_index) = chunk_index {
+            let chunk_start = chunk_index * BitPackerImpl::BLOCK_LEN;
             if self.chunks[chunk_index].initial == *val {
-                return true;
+                return Some(chunk_start);
             }
 
             let mut decompressed = [0u32; BitPackerImpl::BLOCK_LEN];
             self.decompress_chunk(&BitPackerImpl::new(), chunk_index, &mut decompressed);
-            decompressed.binary_search(val).is_ok()
+            decompressed
+                .binary_search(val)
+                .ok()
+                .map(|idx| chunk_start + idx)
         } else {
-            self.reminder_postings.binary_search(val).is_ok()
+            let reminder_start = self.chunks.len() * BitPackerImpl::BLOCK_LEN;
+            self.reminder_postings
+                .binary_search(val)
+                .ok()
+                .map(|idx| reminder_start + idx)
         }
     }
 
@@ -344,6 +392,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compressed_posting_positions() {
+        let mut posting_list = PostingList::default();
+        for i in 0..999 {
+            posting_list.insert_with_positions(i * 2, vec![i, i + 10]);
+        }
+        let compressed_posting_list = CompressedPostingList::new(posting_list.clone());
+        for i in 0..2000 {
+            let expected = posting_list.positions(&i);
+            assert_eq!(compressed_posting_list.positions(&i), expected);
+            if i % 2 == 0 && i / 2 < 999 {
+                assert_eq!(expected, Some([i / 2, i / 2 + 10].as_slice()));
+            } else {
+                assert_eq!(expected, None);
+            }
+        }
+    }
+
     #[test]
     fn test_compressed_posting_visitor() {
         for build_step in 0..3 {
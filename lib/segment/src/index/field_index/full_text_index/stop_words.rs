@@ -0,0 +1,298 @@
+use crate::data_types::text_index::{StopwordsInterface, StopwordsLanguage};
+
+/// All stopwords of the config, predefined and custom ones
+pub fn stopwords(config: Option<&StopwordsInterface>) -> Vec<&str> {
+    match config {
+        None => Vec::new(),
+        Some(StopwordsInterface::Language(language)) => language_stopwords(*language).to_vec(),
+        Some(StopwordsInterface::Set(set)) => set
+            .languages
+            .iter()
+            .flat_map(|language| language_stopwords(*language).iter().copied())
+            .chain(set.custom.iter().map(String::as_str))
+            .collect(),
+    }
+}
+
+fn language_stopwords(language: StopwordsLanguage) -> &'static [&'static str] {
+    match language {
+        StopwordsLanguage::Dutch => DUTCH,
+        StopwordsLanguage::English => ENGLISH,
+        StopwordsLanguage::French => FRENCH,
+        StopwordsLanguage::German => GERMAN,
+        StopwordsLanguage::Italian => ITALIAN,
+        StopwordsLanguage::Portuguese => PORTUGUESE,
+        StopwordsLanguage::Russian => RUSSIAN,
+        StopwordsLanguage::Spanish => SPANISH,
+    }
+}
+
+const DUTCH: &[&str] = &[
+    "aan", "al", "alles", "als", "altijd", "andere", "ben", "bij", "daar", "dan", "dat", "de",
+    "der", "deze", "die", "dit", "doch", "doen", "door", "dus", "een", "eens", "en", "er", "ge",
+    "geen", "geweest", "haar", "had", "heb", "hebben", "heeft", "hem", "het", "hier", "hij", "hoe",
+    "hun", "iemand", "iets", "ik", "in", "is", "ja", "je", "kan", "kon", "kunnen", "maar", "me",
+    "meer", "men", "met", "mij", "mijn", "moet", "na", "naar", "niet", "niets", "nog", "nu", "of",
+    "om", "omdat", "onder", "ons", "ook", "op", "over", "reeds", "te", "tegen", "toch", "toen",
+    "tot", "u", "uit", "uw", "van", "veel", "voor", "want", "waren", "was", "wat", "werd", "wezen",
+    "wie", "wil", "worden", "wordt", "zal", "ze", "zelf", "zich", "zij", "zijn", "zo", "zonder",
+    "zou",
+];
+
+const ENGLISH: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const FRENCH: &[&str] = &[
+    "ai", "aie", "as", "au", "aux", "avec", "avez", "avons", "c", "ce", "ces", "d", "dans", "de",
+    "des", "du", "elle", "en", "es", "est", "et", "eu", "eux", "il", "ils", "j", "je", "l", "la",
+    "le", "les", "leur", "lui", "m", "ma", "mais", "me", "mes", "moi", "mon", "même", "n", "ne",
+    "nos", "notre", "nous", "on", "ont", "ou", "par", "pas", "pour", "qu", "que", "qui", "s", "sa",
+    "se", "ses", "son", "sont", "sur", "t", "ta", "te", "tes", "toi", "ton", "tu", "un", "une",
+    "vos", "votre", "vous", "y", "à", "été", "étaient", "était", "étant", "être",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "alle", "als", "also", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "bist",
+    "da", "damit", "dann", "das", "dass", "dein", "dem", "den", "der", "des", "dich", "die", "dir",
+    "doch", "du", "durch", "ein", "eine", "einem", "einen", "einer", "eines", "er", "es", "euch",
+    "euer", "für", "hab", "habe", "haben", "hat", "hatte", "ich", "ihm", "ihn", "ihr", "ihre",
+    "im", "in", "ist", "ja", "jede", "kann", "kein", "keine", "man", "mein", "mich", "mir", "mit",
+    "nach", "nicht", "noch", "nun", "nur", "ob", "oder", "sehr", "sein", "seine", "sich", "sie",
+    "sind", "so", "um", "und", "uns", "unser", "unter", "vom", "von", "vor", "war", "waren", "was",
+    "weil", "wenn", "wer", "wie", "wir", "wird", "wo", "zu", "zum", "zur", "über",
+];
+
+const ITALIAN: &[&str] = &[
+    "a", "ad", "agli", "ai", "al", "alla", "alle", "allo", "anche", "avere", "c", "che", "chi",
+    "ci", "come", "con", "cui", "da", "dagli", "dai", "dal", "dalla", "dalle", "degli", "dei",
+    "del", "della", "delle", "dello", "di", "e", "è", "gli", "ha", "hanno", "ho", "i", "il", "in",
+    "io", "l", "la", "le", "lei", "lo", "loro", "lui", "ma", "mi", "mio", "ne", "negli", "nei",
+    "nel", "nella", "nelle", "noi", "non", "o", "per", "più", "quale", "quanto", "quella",
+    "quello", "questa", "questo", "se", "si", "sia", "siamo", "sono", "su", "sua", "sue", "sui",
+    "sul", "sulla", "suo", "ti", "tra", "tu", "tutti", "tutto", "un", "una", "uno", "vi", "voi",
+];
+
+const PORTUGUESE: &[&str] = &[
+    "a", "ao", "aos", "as", "até", "com", "como", "da", "das", "de", "dela", "dele", "do", "dos",
+    "e", "ela", "elas", "ele", "eles", "em", "entre", "era", "essa", "esse", "esta", "este",
+    "está", "eu", "foi", "há", "isso", "isto", "já", "lhe", "mais", "mas", "me", "meu", "minha",
+    "muito", "na", "nas", "nem", "no", "nos", "nós", "num", "numa", "não", "o", "os", "ou", "para",
+    "pela", "pelo", "por", "qual", "quando", "que", "quem", "se", "sem", "ser", "seu", "sua",
+    "são", "só", "também", "te", "tem", "um", "uma", "você", "à", "às", "é",
+];
+
+const RUSSIAN: &[&str] = &[
+    "а",
+    "без",
+    "был",
+    "была",
+    "были",
+    "было",
+    "быть",
+    "в",
+    "вам",
+    "вас",
+    "вот",
+    "все",
+    "всё",
+    "вы",
+    "да",
+    "для",
+    "до",
+    "его",
+    "ее",
+    "её",
+    "если",
+    "есть",
+    "еще",
+    "ещё",
+    "же",
+    "за",
+    "и",
+    "из",
+    "или",
+    "им",
+    "их",
+    "к",
+    "как",
+    "когда",
+    "кто",
+    "ли",
+    "мне",
+    "мы",
+    "на",
+    "над",
+    "не",
+    "него",
+    "нее",
+    "неё",
+    "нет",
+    "ни",
+    "них",
+    "но",
+    "ну",
+    "о",
+    "об",
+    "он",
+    "она",
+    "они",
+    "оно",
+    "от",
+    "по",
+    "под",
+    "при",
+    "с",
+    "так",
+    "там",
+    "тем",
+    "то",
+    "того",
+    "тоже",
+    "только",
+    "том",
+    "ты",
+    "у",
+    "уже",
+    "чем",
+    "что",
+    "чтобы",
+    "эта",
+    "эти",
+    "это",
+    "этот",
+    "я",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "algo", "como", "con", "contra", "cual", "cuando", "de", "del", "desde", "donde",
+    "durante", "e", "el", "ella", "ellas", "ellos", "en", "entre", "era", "es", "esa", "ese",
+    "eso", "esta", "este", "esto", "está", "fue", "ha", "hay", "la", "las", "le", "les", "lo",
+    "los", "me", "mi", "mucho", "muy", "más", "mí", "nada", "ni", "no", "nos", "nosotros", "o",
+    "otra", "otro", "para", "pero", "poco", "por", "porque", "que", "quien", "qué", "se", "sea",
+    "ser", "si", "sin", "sobre", "son", "su", "sus", "sí", "también", "te", "tiene", "todo",
+    "todos", "tu", "tú", "un", "una", "uno", "y", "ya", "yo", "él",
+];
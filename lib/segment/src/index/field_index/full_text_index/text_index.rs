@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -19,12 +19,12 @@ use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, ValueIndexer,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, Match, PayloadKeyType};
+use crate::types::{FieldCondition, Match, MatchPhrase, MatchText, PayloadKeyType};
 
 pub struct FullTextIndex {
     inverted_index: InvertedIndex,
     db_wrapper: DatabaseColumnWrapper,
-    config: TextIndexParams,
+    tokenizer: Tokenizer,
 }
 
 impl FullTextIndex {
@@ -36,27 +36,38 @@ impl FullTextIndex {
         bincode::deserialize(data).unwrap()
     }
 
-    fn serialize_document_tokens(&self, tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
+    fn serialize_document_tokens(
+        &self,
+        tokens: BTreeMap<String, Vec<u32>>,
+    ) -> OperationResult<Vec<u8>> {
         #[derive(Serialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            positions: Vec<Vec<u32>>,
         }
-        let doc = StoredDocument { tokens };
+        let (tokens, positions) = tokens.into_iter().unzip();
+        let doc = StoredDocument { tokens, positions };
         serde_cbor::to_vec(&doc).map_err(|e| {
             OperationError::service_error(format!("Failed to serialize document: {e}"))
         })
     }
 
-    fn deserialize_document(data: &[u8]) -> OperationResult<BTreeSet<String>> {
+    /// Returns `None` for documents stored by older versions, without word positions
+    fn deserialize_document(data: &[u8]) -> OperationResult<Option<BTreeMap<String, Vec<u32>>>> {
         #[derive(Deserialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            #[serde(default)]
+            positions: Option<Vec<Vec<u32>>>,
         }
         serde_cbor::from_slice::<StoredDocument>(data)
             .map_err(|e| {
                 OperationError::service_error(format!("Failed to deserialize document: {e}"))
             })
-            .map(|doc| doc.tokens)
+            .map(|doc| {
+                doc.positions
+                    .map(|positions| doc.tokens.into_iter().zip(positions).collect())
+            })
     }
 
     fn storage_cf_name(field: &str) -> String {
@@ -74,7 +85,7 @@ impl FullTextIndex {
         FullTextIndex {
             inverted_index: InvertedIndex::new(is_appendable),
             db_wrapper,
-            config,
+            tokenizer: Tokenizer::new(config),
         }
     }
 
@@ -93,22 +104,67 @@ impl FullTextIndex {
 
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
-        Tokenizer::tokenize_query(text, &self.config, |token| {
+        self.tokenizer.tokenize_query(text, |_, token| {
             tokens.insert(self.inverted_index.get_token(token));
         });
         ParsedQuery {
             tokens: tokens.into_iter().collect(),
+            phrase_positions: None,
+        }
+    }
+
+    pub fn parse_phrase(&self, phrase: &str) -> ParsedQuery {
+        let mut tokens = vec![];
+        let mut positions = vec![];
+        self.tokenizer.tokenize_query(phrase, |position, token| {
+            tokens.push(self.inverted_index.get_token(token));
+            positions.push(position);
+        });
+        // Only relative positions of words matter
+        if let Some(&first_position) = positions.first() {
+            positions
+                .iter_mut()
+                .for_each(|position| *position -= first_position);
+        }
+        ParsedQuery {
+            tokens,
+            phrase_positions: Some(positions),
         }
     }
 
     pub fn parse_document(&self, text: &str) -> Document {
-        let mut document_tokens = vec![];
-        Tokenizer::tokenize_doc(text, &self.config, |token| {
+        let mut document_tokens: HashMap<_, Vec<_>> = HashMap::new();
+        self.tokenizer.tokenize_doc(text, |position, token| {
             if let Some(token_id) = self.inverted_index.get_token(token) {
-                document_tokens.push(token_id);
+                document_tokens.entry(token_id).or_default().push(position);
             }
         });
-        Document::new(document_tokens)
+        Document::with_positions(document_tokens.into_iter().collect())
+    }
+
+    /// Tokens of all values along with their word positions.
+    /// Positions of values are separated by a gap, so phrases can't span several values.
+    fn tokenize_values(&self, values: &[String]) -> BTreeMap<String, Vec<u32>> {
+        let mut tokens: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        let mut values_offset = 0;
+        for value in values {
+            let mut next_value_offset = values_offset;
+            self.tokenizer.tokenize_doc(value, |position, token| {
+                let position = values_offset + position;
+                tokens.entry(token.to_owned()).or_default().push(position);
+                next_value_offset = position + 2;
+            });
+            values_offset = next_value_offset;
+        }
+        tokens
+    }
+
+    fn parse_condition(&self, condition: &FieldCondition) -> Option<ParsedQuery> {
+        match &condition.r#match {
+            Some(Match::Text(MatchText { text })) => Some(self.parse_query(text)),
+            Some(Match::Phrase(MatchPhrase { phrase })) => Some(self.parse_phrase(phrase)),
+            _ => None,
+        }
     }
 
     #[cfg(test)]
@@ -136,13 +192,7 @@ impl ValueIndexer<String> for FullTextIndex {
             return Ok(());
         }
 
-        let mut tokens: BTreeSet<String> = BTreeSet::new();
-
-        for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
-                tokens.insert(token.to_owned());
-            });
-        }
+        let tokens = self.tokenize_values(&values);
 
         let document = self.inverted_index.document_from_tokens(&tokens);
         self.inverted_index.index_document(idx, document)?;
@@ -181,14 +231,24 @@ impl PayloadFieldIndex for FullTextIndex {
             return Ok(false);
         };
 
+        let mut has_legacy_documents = false;
         let db = self.db_wrapper.lock_db();
         let i = db.iter()?.map(|(key, value)| {
             let idx = Self::restore_key(&key);
-            let tokens = Self::deserialize_document(&value)?;
+            let tokens = Self::deserialize_document(&value)?.unwrap_or_else(|| {
+                has_legacy_documents = true;
+                BTreeMap::new()
+            });
             Ok((idx, tokens))
         });
         self.inverted_index.build_index(i)?;
 
+        // Phrases can't be matched without word positions, so the index is rebuilt from payload
+        if has_legacy_documents {
+            log::info!("Full-text index has documents without word positions, rebuilding it");
+            return Ok(false);
+        }
+
         Ok(true)
     }
 
@@ -204,8 +264,7 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self.inverted_index.filter(&parsed_query));
         }
         Err(OperationError::service_error("failed to filter"))
@@ -215,8 +274,7 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self
                 .inverted_index
                 .estimate_cardinality(&parsed_query, condition));
@@ -242,7 +300,10 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
-    use crate::data_types::text_index::{TextIndexType, TokenizerType};
+    use crate::data_types::text_index::{
+        Snowball, SnowballLanguage, SnowballParams, StemmingAlgorithm, StopwordsInterface,
+        StopwordsLanguage, TextIndexType, TokenizerType,
+    };
     use crate::json_path::path;
 
     fn filter_request(text: &str) -> FieldCondition {
@@ -271,6 +332,9 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
         };
 
         {
@@ -356,4 +420,149 @@ mod tests {
             assert_eq!(index.count_indexed_points(), 2);
         }
     }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_phrase_matching(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The quick brown fox jumps over the lazy dog"),
+            serde_json::json!("The brown quick fox"),
+            serde_json::json!(["Lazy dogs sleep all day", "Quick brown foxes never rest"]),
+            serde_json::json!(["A lazy", "dog"]),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: None,
+            stopwords: Some(StopwordsInterface::Language(StopwordsLanguage::English)),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: Snowball::Snowball,
+                language: SnowballLanguage::English,
+            })),
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text", !immutable);
+        if immutable {
+            assert!(index.load().unwrap());
+        } else {
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+        }
+
+        let phrase_search = |phrase: &str| -> Vec<_> {
+            let condition = FieldCondition::new_match(path("text"), Match::new_phrase(phrase));
+            let res: Vec<_> = index.filter(&condition).unwrap().collect();
+            // Filtering and checking must agree
+            let parsed_query = index.parse_phrase(phrase);
+            for idx in 0..payloads.len() as PointOffsetType {
+                assert_eq!(
+                    index.check_match(&parsed_query, idx),
+                    res.contains(&idx),
+                    "phrase {phrase:?}, point {idx}",
+                );
+            }
+            res
+        };
+
+        // Word order matters, unlike for text match
+        assert_eq!(phrase_search("quick brown fox"), vec![0, 2]);
+        assert_eq!(phrase_search("brown quick"), vec![1]);
+        // Stopwords keep their gaps, stems match different forms of words
+        assert_eq!(phrase_search("jumps over the lazy dogs"), vec![0]);
+        assert_eq!(
+            phrase_search("jumps lazy dog"),
+            Vec::<PointOffsetType>::new()
+        );
+        // Phrases don't span several values
+        assert_eq!(phrase_search("lazy dog"), vec![0, 2]);
+        assert_eq!(
+            phrase_search("unknown phrase"),
+            Vec::<PointOffsetType>::new()
+        );
+
+        let text_condition = filter_request("lazy dog");
+        let res: Vec<_> = index.filter(&text_condition).unwrap().collect();
+        assert_eq!(res, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_legacy_documents_are_rebuilt() {
+        #[derive(Serialize)]
+        struct LegacyDocument {
+            tokens: BTreeSet<String>,
+        }
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            index
+                .add_point(0, &[&serde_json::json!("quick brown fox")])
+                .unwrap();
+            index.flusher()().unwrap();
+        }
+
+        // Index with documents of the current format only is loaded
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", false);
+            assert!(index.load().unwrap());
+        }
+
+        let legacy_document = serde_cbor::to_vec(&LegacyDocument {
+            tokens: ["brown", "fox", "quick"].map(String::from).into(),
+        })
+        .unwrap();
+        assert!(FullTextIndex::deserialize_document(&legacy_document)
+            .unwrap()
+            .is_none());
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let index = FullTextIndex::new(db, config.clone(), "text", true);
+            index
+                .db_wrapper
+                .put(FullTextIndex::store_key(&1), legacy_document)
+                .unwrap();
+            index.flusher()().unwrap();
+        }
+
+        // Documents without word positions can't match phrases, the index must be rebuilt
+        for immutable in [false, true] {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", !immutable);
+            assert!(!index.load().unwrap());
+        }
+    }
 }
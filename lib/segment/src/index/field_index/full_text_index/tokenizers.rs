@@ -1,6 +1,15 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
-use crate::data_types::text_index::{TextIndexParams, TokenizerType};
+use super::stop_words;
+use crate::data_types::text_index::{
+    SnowballLanguage, StemmingAlgorithm, TextIndexParams, TokenizerType,
+};
 
 struct WhiteSpaceTokenizer;
 
@@ -74,70 +83,151 @@ impl MultilingualTokenizer {
     }
 }
 
-pub struct Tokenizer;
+pub struct Tokenizer {
+    config: TextIndexParams,
+    stopwords: HashSet<String>,
+    stemmer: Option<Stemmer>,
+}
 
 impl Tokenizer {
-    fn doc_token_filter<'a, C: FnMut(&str) + 'a>(
-        config: &'a TextIndexParams,
-        mut callback: C,
-    ) -> impl FnMut(&str) + 'a {
-        move |token: &str| {
-            if config
-                .min_token_len
-                .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
-                .unwrap_or(false)
-            {
-                return;
-            }
-            if config
-                .max_token_len
-                .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
-                .unwrap_or(false)
-            {
-                return;
-            }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
-            } else {
-                callback(token);
+    pub fn new(config: TextIndexParams) -> Self {
+        let stemmer = config.stemmer.map(|stemmer| match stemmer {
+            StemmingAlgorithm::Snowball(params) => {
+                Stemmer::create(snowball_algorithm(params.language))
             }
+        });
+        let mut tokenizer = Self {
+            config,
+            stopwords: HashSet::new(),
+            stemmer,
+        };
+        // Stopwords are normalized the same way as tokens, so they are matched after normalization
+        let stopwords = stop_words::stopwords(tokenizer.config.stopwords.as_ref())
+            .into_iter()
+            .map(|stopword| tokenizer.normalize(stopword).into_owned())
+            .collect();
+        tokenizer.stopwords = stopwords;
+        tokenizer
+    }
+
+    /// Lowercase and fold the token according to the config
+    fn normalize<'a>(&self, token: &'a str) -> Cow<'a, str> {
+        let mut token = Cow::Borrowed(token);
+        if self.config.lowercase.unwrap_or(true) {
+            token = Cow::Owned(token.to_lowercase());
+        }
+        if self.config.ascii_folding.unwrap_or(false) {
+            token = Cow::Owned(fold_to_ascii(&token));
         }
+        token
     }
 
-    pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize(
-                text,
-                config.min_token_len.unwrap_or(1),
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+    fn process_token<C: FnMut(u32, &str)>(&self, position: u32, token: &str, callback: &mut C) {
+        if self
+            .config
+            .min_token_len
+            .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        if self
+            .config
+            .max_token_len
+            .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let token = self.normalize(token);
+        if self.stopwords.contains(token.as_ref()) {
+            return;
+        }
+        match &self.stemmer {
+            Some(stemmer) => callback(position, &stemmer.stem(&token)),
+            None => callback(position, &token),
         }
     }
 
-    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize_query(
-                text,
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+    /// Split the text into tokens, passing them along with their word positions in the text.
+    /// Positions of removed words, e.g. stopwords, are skipped, so phrases keep their gaps.
+    fn tokenize<C: FnMut(u32, &str)>(&self, text: &str, is_query: bool, mut callback: C) {
+        let mut position = 0;
+        match self.config.tokenizer {
+            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, |word| {
+                self.process_token(position, word, &mut callback);
+                position += 1;
+            }),
+            TokenizerType::Word => WordTokenizer::tokenize(text, |word| {
+                self.process_token(position, word, &mut callback);
+                position += 1;
+            }),
+            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, |word| {
+                self.process_token(position, word, &mut callback);
+                position += 1;
+            }),
+            TokenizerType::Prefix => {
+                // All prefixes of a word share its position
+                let min_ngram = self.config.min_token_len.unwrap_or(1);
+                let max_ngram = self.config.max_token_len.unwrap_or(usize::MAX);
+                WordTokenizer::tokenize(text, |word| {
+                    let process_prefix =
+                        |prefix: &str| self.process_token(position, prefix, &mut callback);
+                    if is_query {
+                        PrefixTokenizer::tokenize_query(word, max_ngram, process_prefix);
+                    } else {
+                        PrefixTokenizer::tokenize(word, min_ngram, max_ngram, process_prefix);
+                    }
+                    position += 1;
+                })
+            }
         }
     }
+
+    pub fn tokenize_doc<C: FnMut(u32, &str)>(&self, text: &str, callback: C) {
+        self.tokenize(text, false, callback)
+    }
+
+    pub fn tokenize_query<C: FnMut(u32, &str)>(&self, text: &str, callback: C) {
+        self.tokenize(text, true, callback)
+    }
+}
+
+/// Remove diacritics, e.g. `café` becomes `cafe`
+fn fold_to_ascii(token: &str) -> String {
+    token.nfkd().filter(|c| !is_combining_mark(*c)).collect()
+}
+
+fn snowball_algorithm(language: SnowballLanguage) -> Algorithm {
+    match language {
+        SnowballLanguage::Arabic => Algorithm::Arabic,
+        SnowballLanguage::Danish => Algorithm::Danish,
+        SnowballLanguage::Dutch => Algorithm::Dutch,
+        SnowballLanguage::English => Algorithm::English,
+        SnowballLanguage::Finnish => Algorithm::Finnish,
+        SnowballLanguage::French => Algorithm::French,
+        SnowballLanguage::German => Algorithm::German,
+        SnowballLanguage::Greek => Algorithm::Greek,
+        SnowballLanguage::Hungarian => Algorithm::Hungarian,
+        SnowballLanguage::Italian => Algorithm::Italian,
+        SnowballLanguage::Norwegian => Algorithm::Norwegian,
+        SnowballLanguage::Portuguese => Algorithm::Portuguese,
+        SnowballLanguage::Romanian => Algorithm::Romanian,
+        SnowballLanguage::Russian => Algorithm::Russian,
+        SnowballLanguage::Spanish => Algorithm::Spanish,
+        SnowballLanguage::Swedish => Algorithm::Swedish,
+        SnowballLanguage::Tamil => Algorithm::Tamil,
+        SnowballLanguage::Turkish => Algorithm::Turkish,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data_types::text_index::TextIndexType;
+    use crate::data_types::text_index::{
+        Snowball, SnowballParams, StopwordsInterface, StopwordsLanguage, StopwordsSet,
+        TextIndexType,
+    };
 
     #[test]
     fn test_whitespace_tokenizer() {
@@ -246,17 +336,17 @@ mod tests {
     fn test_tokenizer() {
         let text = "Hello, Мир!";
         let mut tokens = Vec::new();
-        Tokenizer::tokenize_doc(
-            text,
-            &TextIndexParams {
-                r#type: TextIndexType::Text,
-                tokenizer: TokenizerType::Prefix,
-                min_token_len: Some(1),
-                max_token_len: Some(4),
-                lowercase: Some(true),
-            },
-            |token| tokens.push(token.to_owned()),
-        );
+        let tokenizer = Tokenizer::new(TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(1),
+            max_token_len: Some(4),
+            lowercase: Some(true),
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+        });
+        tokenizer.tokenize_doc(text, |_, token| tokens.push(token.to_owned()));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 7);
         assert_eq!(tokens.first(), Some(&"h".to_owned()));
@@ -267,4 +357,34 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    #[test]
+    fn test_tokenizer_filters() {
+        let text = "The Cafés, and the BAKERIES of Paris";
+        let mut tokens = Vec::new();
+        let tokenizer = Tokenizer::new(TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: Some(true),
+            stopwords: Some(StopwordsInterface::Set(StopwordsSet {
+                languages: vec![StopwordsLanguage::English],
+                custom: vec!["PARIS".to_string()],
+            })),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: Snowball::Snowball,
+                language: SnowballLanguage::English,
+            })),
+        });
+        tokenizer.tokenize_doc(text, |position, token| {
+            tokens.push((position, token.to_owned()))
+        });
+        // Stopwords are removed, but keep their positions
+        assert_eq!(
+            tokens,
+            vec![(1, "cafe".to_owned()), (4, "bakeri".to_owned())]
+        );
+    }
 }
@@ -12,6 +12,7 @@ use crate::index::field_index::FieldIndex;
 use crate::json_path::JsonPath;
 use crate::types::{
     FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
+    UuidIntType,
 };
 
 /// Selects index types based on field type
@@ -63,6 +64,9 @@ pub fn index_selector(
                     NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
                 )]
             }
+            PayloadSchemaType::Uuid => vec![FieldIndex::UuidMapIndex(
+                MapIndex::<UuidIntType>::new(db, field, is_appendable),
+            )],
         },
         PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
             PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
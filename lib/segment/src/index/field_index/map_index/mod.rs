@@ -1,6 +1,7 @@
 pub mod immutable_map_index;
 pub mod mutable_map_index;
 
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::hash::{BuildHasher, Hash};
 use std::str::FromStr;
@@ -27,7 +28,7 @@ use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    PayloadKeyType, UuidIntType, UuidPayloadType, ValueVariants,
 };
 
 pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
@@ -117,6 +118,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// Iterate over all indexed values with the number of points having each of them
+    pub fn iter_values_counts(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.get_values_iterator().filter_map(|value| {
+            self.get_points_with_value_count(value)
+                .filter(|count| *count > 0)
+                .map(|count| (value, count))
+        })
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
@@ -542,6 +552,155 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 }
 
+impl MapIndex<UuidIntType> {
+    /// Parse keyword of a condition into the index key, UUIDs are matched regardless of their format
+    pub fn parse_uuid(keyword: &str) -> Option<UuidIntType> {
+        UuidPayloadType::parse_str(keyword)
+            .ok()
+            .map(|uuid| uuid.as_u128())
+    }
+
+    pub fn parse_uuids(keywords: &IndexSet<String, impl BuildHasher>) -> HashSet<UuidIntType> {
+        keywords
+            .iter()
+            .filter_map(|keyword| Self::parse_uuid(keyword))
+            .collect()
+    }
+}
+
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.load_from_db()
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.get_db_wrapper().recreate_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        MapIndex::flusher(self)
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match Self::parse_uuid(keyword) {
+                Some(uuid) => Ok(self.get_iterator(&uuid)),
+                None => Ok(Box::new(std::iter::empty())),
+            },
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let uuids = Self::parse_uuids(keywords);
+                    Ok(Box::new(
+                        uuids
+                            .into_iter()
+                            .flat_map(|uuid| self.get_iterator(&uuid))
+                            .unique(),
+                    ))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(Box::new(vec![].into_iter()))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => {
+                let excluded = Self::parse_uuids(keywords);
+                Ok(Box::new(
+                    self.get_values_iterator()
+                        .filter(move |uuid| !excluded.contains(*uuid))
+                        .flat_map(|uuid| self.get_iterator(uuid))
+                        .unique(),
+                ))
+            }
+            _ => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => {
+                let estimation = match Self::parse_uuid(keyword) {
+                    Some(uuid) => self.match_cardinality(&uuid),
+                    None => CardinalityEstimation::exact(0),
+                };
+                Ok(estimation.with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+            }
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let estimations = Self::parse_uuids(keywords)
+                        .iter()
+                        .map(|uuid| self.match_cardinality(uuid))
+                        .collect::<Vec<_>>();
+                    let estimation = if estimations.is_empty() {
+                        CardinalityEstimation::exact(0)
+                    } else {
+                        combine_should_estimations(&estimations, self.get_indexed_points())
+                    };
+                    Ok(estimation
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(CardinalityEstimation::exact(0)
+                            .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => Ok(self.except_cardinality::<UuidIntType, UuidIntType>(
+                Self::parse_uuids(keywords).into_iter(),
+            )),
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.get_values_iterator()
+                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .filter(move |(_value, count)| *count >= threshold)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(
+                        key.clone(),
+                        UuidPayloadType::from_u128(*value).to_string().into(),
+                    ),
+                    cardinality: count,
+                }),
+        )
+    }
+}
+
 impl ValueIndexer<String> for MapIndex<SmolStr> {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         match self {
@@ -596,6 +755,31 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
     }
 }
 
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
+            MapIndex::Immutable(_) => Err(OperationError::service_error(
+                "Can't add values to immutable map index",
+            )),
+        }
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return Self::parse_uuid(keyword);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.remove_point(id),
+            MapIndex::Immutable(index) => index.remove_point(id),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -706,6 +890,58 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_uuid_disk_map_index() {
+        let uuids: Vec<UuidIntType> = (0..5)
+            .map(|_| UuidPayloadType::new_v4().as_u128())
+            .collect();
+        let data = vec![
+            vec![uuids[0], uuids[1]],
+            vec![uuids[1], uuids[2]],
+            vec![uuids[3]],
+            vec![uuids[0], uuids[4]],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        // UUIDs are matched regardless of their format
+        let key: PayloadKeyType = FIELD_NAME.parse().unwrap();
+        let uuid = UuidPayloadType::from_u128(uuids[0]);
+        let condition =
+            FieldCondition::new_match(key.clone(), uuid.simple().to_string().to_uppercase().into());
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([0, 3]));
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.equals_min_exp_max(&CardinalityEstimation::exact(2)));
+
+        let condition = FieldCondition::new_match(
+            key.clone(),
+            Match::from(vec![
+                UuidPayloadType::from_u128(uuids[2]).to_string(),
+                UuidPayloadType::from_u128(uuids[3]).to_string(),
+                "not-a-uuid".to_string(),
+            ]),
+        );
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([1, 2]));
+
+        let condition = FieldCondition::new_match(
+            key,
+            Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(
+                    [uuids[0], uuids[1]]
+                        .iter()
+                        .map(|uuid| UuidPayloadType::from_u128(*uuid).to_string())
+                        .collect(),
+                ),
+            }),
+        );
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([1, 2, 3]));
+    }
+
     #[test]
     fn test_empty_index() {
         let data: Vec<Vec<String>> = vec![];
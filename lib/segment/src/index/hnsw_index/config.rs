@@ -29,6 +29,9 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Number of vectors linked into the appendable graph in place, since it was built
+    #[serde(default)]
+    pub in_place_linked_count: usize,
 }
 
 impl HnswGraphConfig {
@@ -50,6 +53,7 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            in_place_linked_count: 0,
         }
     }
 
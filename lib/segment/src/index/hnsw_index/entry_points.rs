@@ -91,6 +91,41 @@ impl EntryPoints {
         None
     }
 
+    /// Forget the point, once it is removed from the graph
+    ///
+    /// Returns true, if the point was one of the main entry points.
+    pub fn remove_point(&mut self, point_id: PointOffsetType) -> bool {
+        let entry_points_count = self.entry_points.len();
+        self.entry_points.retain(|entry| entry.point_id != point_id);
+        entry_points_count != self.entry_points.len()
+    }
+
+    /// Whether the point is one of the main entry points
+    pub fn is_entry_point(&self, point_id: PointOffsetType) -> bool {
+        self.entry_points
+            .iter()
+            .any(|entry| entry.point_id == point_id)
+    }
+
+    /// Translate point offsets of entry points, points missing in the mapping are dropped
+    pub fn remap<F>(&self, extra_entry_points: usize, old_to_new: F) -> EntryPoints
+    where
+        F: Fn(PointOffsetType) -> Option<PointOffsetType>,
+    {
+        let remap_entry = |entry: &EntryPoint| {
+            old_to_new(entry.point_id).map(|point_id| EntryPoint {
+                point_id,
+                level: entry.level,
+            })
+        };
+        let mut remapped = EntryPoints::new(extra_entry_points);
+        remapped.entry_points = self.entry_points.iter().filter_map(remap_entry).collect();
+        for entry in self.extra_entry_points.iter().filter_map(remap_entry) {
+            remapped.extra_entry_points.push(entry);
+        }
+        remapped
+    }
+
     /// Find the highest `EntryPoint` which satisfies filtering condition of `checker`
     pub fn get_entry_point<F>(&self, checker: F) -> Option<EntryPoint>
     where
@@ -141,4 +176,40 @@ mod tests {
         assert_eq!(points.entry_points.len(), 5);
         assert_eq!(points.extra_entry_points.len(), 10);
     }
+
+    #[test]
+    fn test_remove_entry_point() {
+        let mut points = EntryPoints::new(10);
+
+        points.new_point(0, 1, |_x| true);
+        points.new_point(1, 3, |_x| true);
+        points.new_point(2, 2, |_x| true);
+        assert_eq!(points.get_entry_point(|_x| true).unwrap().point_id, 1);
+
+        assert!(!points.remove_point(2));
+        assert!(points.remove_point(1));
+        assert!(points.entry_points.is_empty());
+
+        // Falls back to extra entry points
+        let entry = points.get_entry_point(|x| x != 1).unwrap();
+        assert_eq!(entry.point_id, 2);
+    }
+
+    #[test]
+    fn test_remap_entry_points() {
+        let mut points = EntryPoints::new(10);
+
+        points.new_point(0, 1, |_x| true);
+        points.new_point(1, 3, |_x| true);
+        points.new_point(2, 2, |_x| true);
+
+        let remapped = points.remap(10, |x| (x != 2).then_some(x + 10));
+        let entry = remapped.get_entry_point(|_x| true).unwrap();
+        assert_eq!(entry.point_id, 11);
+        assert_eq!(entry.level, 3);
+
+        // Dropped points are not used as entry points
+        let entry = remapped.get_entry_point(|x| x != 11).unwrap();
+        assert_eq!(entry.point_id, 10);
+    }
 }
@@ -1,6 +1,6 @@
 use std::cmp::{max, min};
 use std::collections::BinaryHeap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicUsize;
 
 use bitvec::prelude::BitVec;
@@ -10,9 +10,10 @@ use parking_lot::{Mutex, MutexGuard, RwLock};
 use rand::distributions::Uniform;
 use rand::Rng;
 
-use super::graph_links::GraphLinks;
+use super::graph_links::{GraphLinks, GraphLinksRam};
 use crate::common::operation_error::OperationResult;
-use crate::index::hnsw_index::entry_points::EntryPoints;
+use crate::common::Flusher;
+use crate::index::hnsw_index::entry_points::{EntryPoint, EntryPoints};
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
@@ -85,12 +86,7 @@ impl GraphLayersBuilder {
             .map(|l| l.into_iter().map(|l| l.into_inner()).collect())
             .collect();
 
-        let mut links_converter = GraphLinksConverter::new(unlocker_links_layers);
-        if let Some(path) = path {
-            links_converter.save_as(path)?;
-        }
-
-        let links = TGraphLinks::from_converter(links_converter)?;
+        let links = Self::convert_links(unlocker_links_layers, path)?;
         Ok(GraphLayers {
             m: self.m,
             m0: self.m0,
@@ -101,6 +97,126 @@ impl GraphLayersBuilder {
         })
     }
 
+    /// Same as [`Self::into_graph_layers`], but keeps the builder for further updates
+    pub fn to_graph_layers<TGraphLinks: GraphLinks>(
+        &self,
+        path: Option<&Path>,
+    ) -> OperationResult<GraphLayers<TGraphLinks>> {
+        let links_layers = self
+            .links_layers
+            .iter()
+            .map(|l| l.iter().map(|l| l.read().clone()).collect())
+            .collect();
+
+        let links = Self::convert_links(links_layers, path)?;
+        Ok(GraphLayers {
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            links,
+            entry_points: self.entry_points.lock().clone(),
+            visited_pool: VisitedPool::new(),
+        })
+    }
+
+    /// Snapshot the graph and return a flusher, which saves it into the given files
+    ///
+    /// Links to points, which were removed from the graph, are dropped from the snapshot.
+    pub fn flusher(&self, graph_path: PathBuf, links_path: PathBuf) -> Flusher {
+        let ready_list = self.ready_list.read().clone();
+        let links_layers: Vec<Vec<LinkContainer>> = self
+            .links_layers
+            .iter()
+            .map(|layers| {
+                layers
+                    .iter()
+                    .map(|links| {
+                        links
+                            .read()
+                            .iter()
+                            .copied()
+                            .filter(|&link| ready_list.get(link as usize).map_or(false, |r| *r))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let graph = GraphLayers::<GraphLinksRam> {
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            links: GraphLinksRam::default(),
+            entry_points: self.entry_points.lock().clone(),
+            visited_pool: VisitedPool::new(),
+        };
+
+        Box::new(move || {
+            GraphLinksConverter::new(links_layers).save_as(&links_path)?;
+            graph.save(&graph_path)
+        })
+    }
+
+    fn convert_links<TGraphLinks: GraphLinks>(
+        links_layers: Vec<Vec<LinkContainer>>,
+        path: Option<&Path>,
+    ) -> OperationResult<TGraphLinks> {
+        let mut links_converter = GraphLinksConverter::new(links_layers);
+        if let Some(path) = path {
+            links_converter.save_as(path)?;
+        }
+        TGraphLinks::from_converter(links_converter)
+    }
+
+    /// Create a builder, which continues building an existing graph
+    ///
+    /// All points of the graph are considered to be linked already. Other points up to
+    /// `num_vectors` can be added with [`Self::set_levels`] and [`Self::link_new_point`].
+    pub fn from_graph_layers<TGraphLinks: GraphLinks>(
+        graph: &GraphLayers<TGraphLinks>,
+        num_vectors: usize,
+        use_heuristic: bool,
+    ) -> Self {
+        let graph_points = graph.num_points();
+        let num_vectors = max(num_vectors, graph_points);
+
+        let links_layers: Vec<LockedLayersContainer> = (0..num_vectors)
+            .map(|point_id| {
+                if point_id < graph_points {
+                    let point_id = point_id as PointOffsetType;
+                    (0..=graph.point_level(point_id))
+                        .map(|level| RwLock::new(graph.links.links(point_id, level).to_vec()))
+                        .collect()
+                } else {
+                    vec![RwLock::new(Vec::with_capacity(graph.m0))]
+                }
+            })
+            .collect();
+
+        let max_level = links_layers
+            .iter()
+            .map(|layers| layers.len() - 1)
+            .max()
+            .unwrap_or(0);
+
+        let ready_list: BitVec = (0..num_vectors)
+            .map(|point_id| point_id < graph_points)
+            .collect();
+
+        Self {
+            max_level: AtomicUsize::new(max_level),
+            m: graph.m,
+            m0: graph.m0,
+            ef_construct: graph.ef_construct,
+            level_factor: 1.0 / (max(graph.m, 2) as f64).ln(),
+            use_heuristic,
+            links_layers,
+            entry_points: Mutex::new(graph.entry_points.clone()),
+            visited_pool: VisitedPool::new(),
+            ready_list: RwLock::new(ready_list),
+        }
+    }
+
     pub fn new_with_params(
         num_vectors: usize, // Initial number of points in index
         m: usize,           // Expected M for non-first layer
@@ -191,10 +307,103 @@ impl GraphLayersBuilder {
             .merge_from_other(other.entry_points.into_inner());
     }
 
+    /// Create a builder, which reuses the graph of another index
+    ///
+    /// Point offsets of the graph are translated with `old_to_new`, points missing in the mapping
+    /// and links to them are dropped. Points, which lost links and are left with less than half of
+    /// `m` links on some level, are detached from the graph and not marked as linked, their
+    /// remaining links are kept as candidates for re-linking. They are linked together with the
+    /// remaining points up to `num_vectors` with [`Self::set_levels`] and [`Self::link_new_point`].
+    pub fn from_remapped_graph_layers<TGraphLinks, F>(
+        graph: &GraphLayers<TGraphLinks>,
+        num_vectors: usize,
+        entry_points_num: usize,
+        use_heuristic: bool,
+        old_to_new: F,
+    ) -> Self
+    where
+        TGraphLinks: GraphLinks,
+        F: Fn(PointOffsetType) -> Option<PointOffsetType>,
+    {
+        let old_to_new = |old_point_id| {
+            old_to_new(old_point_id).filter(|&new_point_id| (new_point_id as usize) < num_vectors)
+        };
+        let entry_points = graph.entry_points.remap(entry_points_num, old_to_new);
+
+        let mut links_layers: Vec<LockedLayersContainer> = (0..num_vectors)
+            .map(|_| vec![RwLock::new(Vec::with_capacity(graph.m0))])
+            .collect();
+        let mut ready_list = BitVec::repeat(false, num_vectors);
+        let mut needs_relink = BitVec::repeat(false, num_vectors);
+        let mut max_level = 0;
+
+        for old_point_id in 0..graph.num_points() as PointOffsetType {
+            let Some(new_point_id) = old_to_new(old_point_id) else {
+                continue;
+            };
+            let point_level = graph.point_level(old_point_id);
+            let mut lost_links = false;
+            links_layers[new_point_id as usize] = (0..=point_level)
+                .map(|level| {
+                    let old_links = graph.links.links(old_point_id, level);
+                    let links: LinkContainer = old_links
+                        .iter()
+                        .filter_map(|&link| old_to_new(link))
+                        .collect();
+                    let level_m = if level == 0 { graph.m0 } else { graph.m };
+                    lost_links |= links.len() < old_links.len() && links.len() < level_m / 2;
+                    RwLock::new(links)
+                })
+                .collect();
+            // Entry points are kept, linking starts from them
+            if lost_links && !entry_points.is_entry_point(new_point_id) {
+                needs_relink.set(new_point_id as usize, true);
+            } else {
+                ready_list.set(new_point_id as usize, true);
+            }
+            max_level = max(max_level, point_level);
+        }
+
+        // Points to re-link must not be reachable, otherwise they are found as their own neighbours
+        if needs_relink.any() {
+            for point_layers in &mut links_layers {
+                for links in point_layers.iter_mut() {
+                    links.get_mut().retain(|&link| !needs_relink[link as usize]);
+                }
+            }
+        }
+
+        Self {
+            max_level: AtomicUsize::new(max_level),
+            m: graph.m,
+            m0: graph.m0,
+            ef_construct: graph.ef_construct,
+            level_factor: 1.0 / (max(graph.m, 2) as f64).ln(),
+            use_heuristic,
+            links_layers,
+            entry_points: Mutex::new(entry_points),
+            visited_pool: VisitedPool::new(),
+            ready_list: RwLock::new(ready_list),
+        }
+    }
+
     fn num_points(&self) -> usize {
         self.links_layers.len()
     }
 
+    /// Whether the point is linked into the graph
+    pub fn is_linked(&self, point_id: PointOffsetType) -> bool {
+        self.ready_list
+            .read()
+            .get(point_id as usize)
+            .map_or(false, |ready| *ready)
+    }
+
+    /// Number of points linked into the graph
+    pub fn linked_points_count(&self) -> usize {
+        self.ready_list.read().count_ones()
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
@@ -215,6 +424,9 @@ impl GraphLayersBuilder {
             while self.links_layers.len() <= point_id as usize {
                 self.links_layers.push(vec![]);
             }
+            self.ready_list
+                .get_mut()
+                .resize(self.links_layers.len(), false);
         }
         let point_layers = &mut self.links_layers[point_id as usize];
         while point_layers.len() <= level {
@@ -398,14 +610,20 @@ impl GraphLayersBuilder {
                                     idx: point_id,
                                     score: scorer(point_id, other_point),
                                 });
-                                for other_point_link in
-                                    other_point_links.iter().take(level_m).copied()
+                                let ready_list = self.ready_list.read();
+                                // Skip links to points removed from the graph
+                                for other_point_link in other_point_links
+                                    .iter()
+                                    .take(level_m)
+                                    .copied()
+                                    .filter(|&link| ready_list[link as usize])
                                 {
                                     candidates.push(ScoredPointOffset {
                                         idx: other_point_link,
                                         score: scorer(other_point_link, other_point),
                                     });
                                 }
+                                drop(ready_list);
                                 let selected_candidates =
                                     Self::select_candidate_with_heuristic_from_sorted(
                                         candidates.into_sorted_vec().into_iter().rev(),
@@ -452,6 +670,208 @@ impl GraphLayersBuilder {
         self.ready_list.write().set(point_id as usize, true);
     }
 
+    /// Remove the point from the graph, and reconnect its neighbours
+    ///
+    /// Links of every neighbour of the removed point, and of every point linking to it, are
+    /// selected again from their remaining links and neighbours of the removed point, so that the
+    /// graph stays navigable around the gap. Points linking to the removed point are searched
+    /// among its nearest points with `points_scorer`, which must score against the removed vector.
+    /// Remaining links to the removed point, which are not found this way, are skipped on search
+    /// until the point is linked again, and are dropped when the graph is saved with
+    /// [`Self::flusher`].
+    ///
+    /// Returns true, if the point had any links or was an entry point.
+    pub fn remove_point(
+        &self,
+        point_id: PointOffsetType,
+        mut points_scorer: FilteredScorer,
+    ) -> bool {
+        if !self.is_linked(point_id) {
+            return false;
+        }
+        self.ready_list.write().set(point_id as usize, false);
+
+        let incoming_links = self.search_incoming_links(point_id, &mut points_scorer);
+
+        let scorer = |a, b| points_scorer.score_internal(a, b);
+        let mut replacement: Option<EntryPoint> = None;
+        let mut had_links = false;
+
+        for curr_level in (0..=self.get_point_level(point_id)).rev() {
+            let level_m = self.get_m(curr_level);
+            let removed_links =
+                std::mem::take(&mut *self.links_layers[point_id as usize][curr_level].write());
+            had_links |= !removed_links.is_empty();
+            let neighbours: Vec<_> = {
+                let ready_list = self.ready_list.read();
+                removed_links
+                    .into_iter()
+                    .filter(|&link| ready_list[link as usize])
+                    .collect()
+            };
+            let mut repaired: Vec<_> = neighbours
+                .iter()
+                .chain(&incoming_links[curr_level])
+                .copied()
+                .collect();
+            repaired.sort_unstable();
+            repaired.dedup();
+
+            for &neighbour in &repaired {
+                let mut neighbour_links = self.links_layers[neighbour as usize][curr_level].write();
+                let ready_list = self.ready_list.read();
+
+                let mut candidates: Vec<_> = neighbour_links
+                    .iter()
+                    .chain(&neighbours)
+                    .copied()
+                    .filter(|&link| link != neighbour && ready_list[link as usize])
+                    .collect();
+                drop(ready_list);
+                candidates.sort_unstable();
+                candidates.dedup();
+
+                let mut candidates: Vec<_> = candidates
+                    .into_iter()
+                    .map(|link| ScoredPointOffset {
+                        idx: link,
+                        score: scorer(neighbour, link),
+                    })
+                    .collect();
+                candidates.sort_unstable_by(|a, b| b.cmp(a));
+
+                let selected = if self.use_heuristic {
+                    Self::select_candidate_with_heuristic_from_sorted(
+                        candidates.into_iter(),
+                        level_m,
+                        scorer,
+                    )
+                } else {
+                    candidates
+                        .into_iter()
+                        .take(level_m)
+                        .map(|candidate| candidate.idx)
+                        .collect()
+                };
+                neighbour_links.clear(); // this do not free memory, which is good
+                neighbour_links.extend(selected);
+            }
+
+            // Neighbour from the highest level replaces the removed point as an entry point
+            if replacement.is_none() {
+                replacement = neighbours
+                    .iter()
+                    .map(|&neighbour| EntryPoint {
+                        point_id: neighbour,
+                        level: self.get_point_level(neighbour),
+                    })
+                    .max();
+            }
+        }
+
+        let mut entry_points = self.entry_points.lock();
+        let was_entry_point = entry_points.remove_point(point_id);
+        if was_entry_point {
+            if let Some(replacement) = replacement {
+                entry_points.new_point(replacement.point_id, replacement.level, |point_id| {
+                    self.is_linked(point_id)
+                });
+            }
+        }
+
+        had_links || was_entry_point
+    }
+
+    /// Find points, which link to the point on each of its levels
+    ///
+    /// Only the nearest points to the point are checked, found with `points_scorer`, which scores
+    /// against the vector of the point. The point itself must not be marked as linked.
+    fn search_incoming_links(
+        &self,
+        point_id: PointOffsetType,
+        points_scorer: &mut FilteredScorer,
+    ) -> Vec<Vec<PointOffsetType>> {
+        let point_level = self.get_point_level(point_id);
+        let mut incoming_links = vec![Vec::new(); point_level + 1];
+
+        let entry_point = self
+            .entry_points
+            .lock()
+            .get_entry_point(|entry| entry != point_id && self.is_linked(entry));
+        let Some(entry_point) = entry_point else {
+            return incoming_links;
+        };
+
+        let mut level_entry = if entry_point.level > point_level {
+            self.search_entry(
+                entry_point.point_id,
+                entry_point.level,
+                point_level,
+                points_scorer,
+            )
+        } else {
+            ScoredPointOffset {
+                idx: entry_point.point_id,
+                score: points_scorer.score_point(entry_point.point_id),
+            }
+        };
+
+        for level in (0..=min(point_level, entry_point.level)).rev() {
+            let nearest =
+                self.search_on_level(level_entry, level, self.ef_construct, points_scorer);
+            for candidate in nearest.iter() {
+                let links = self.links_layers[candidate.idx as usize][level].read();
+                if links.contains(&point_id) {
+                    incoming_links[level].push(candidate.idx);
+                }
+            }
+            if let Some(nearest) = nearest.iter().max() {
+                level_entry = *nearest;
+            }
+        }
+        incoming_links
+    }
+
+    /// Search for the closest points, same as [`GraphLayers::search`]
+    pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        let entry_point = custom_entry_points
+            .and_then(|custom_entry_points| {
+                custom_entry_points
+                    .iter()
+                    .filter(|&&point_id| {
+                        self.is_linked(point_id) && points_scorer.check_vector(point_id)
+                    })
+                    .map(|&point_id| EntryPoint {
+                        point_id,
+                        level: self.get_point_level(point_id),
+                    })
+                    .max_by_key(|ep| ep.level)
+            })
+            .or_else(|| {
+                self.entry_points.lock().get_entry_point(|point_id| {
+                    self.is_linked(point_id) && points_scorer.check_vector(point_id)
+                })
+            });
+        let Some(entry_point) = entry_point else {
+            return Vec::default();
+        };
+
+        let zero_level_entry = self.search_entry(
+            entry_point.point_id,
+            entry_point.level,
+            0,
+            &mut points_scorer,
+        );
+        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+        nearest.into_iter().take(top).collect()
+    }
+
     /// This function returns average number of links per node in HNSW graph
     /// on specified level.
     ///
@@ -478,17 +898,18 @@ impl GraphLayersBuilder {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use itertools::Itertools;
     use rand::prelude::StdRng;
     use rand::seq::SliceRandom;
     use rand::SeedableRng;
 
     use super::*;
-    use crate::data_types::vectors::DenseVector;
+    use crate::data_types::vectors::{DenseVector, VectorElementType};
     use crate::fixtures::index_fixtures::{
         random_vector, FakeFilterContext, TestRawScorerProducer,
     };
-    use crate::index::hnsw_index::graph_links::GraphLinksRam;
     use crate::index::hnsw_index::tests::create_graph_layer_fixture;
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::{CosineMetric, EuclidMetric};
@@ -632,7 +1053,7 @@ mod tests {
 
         let top = 5;
         let query = random_vector(&mut rng, dim);
-        let processed_query = M::preprocess(query.clone());
+        let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
         let mut reference_top = FixedLengthPriorityQueue::new(top);
         for idx in 0..vector_holder.vectors.len() as PointOffsetType {
             let vec = &vector_holder.vectors.get(idx);
@@ -715,7 +1136,7 @@ mod tests {
 
         let top = 5;
         let query = random_vector(&mut rng, dim);
-        let processed_query = M::preprocess(query.clone());
+        let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
         let mut reference_top = FixedLengthPriorityQueue::new(top);
         for idx in 0..vector_holder.vectors.len() as PointOffsetType {
             let vec = &vector_holder.vectors.get(idx);
@@ -738,6 +1159,212 @@ mod tests {
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[test]
+    fn test_remove_points() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 5;
+        let ef = 64;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, true, &mut rng);
+
+        let removed = |idx: PointOffsetType| idx % 5 == 0;
+
+        let fake_filter_context = FakeFilterContext {};
+        for idx in (0..num_vectors as PointOffsetType).filter(|&idx| removed(idx)) {
+            let vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            assert!(graph_layers_builder.remove_point(idx, scorer));
+        }
+
+        assert_eq!(
+            graph_layers_builder.linked_points_count(),
+            num_vectors - num_vectors / 5,
+        );
+        for idx in (0..num_vectors as PointOffsetType).filter(|&idx| removed(idx)) {
+            assert!(!graph_layers_builder.is_linked(idx));
+            assert!(graph_layers_builder.links_layers[idx as usize]
+                .iter()
+                .all(|links| links.read().is_empty()));
+        }
+
+        let entry_point = graph_layers_builder
+            .entry_points
+            .lock()
+            .get_entry_point(|_x| true)
+            .expect("Expect entry point to exists");
+        assert!(!removed(entry_point.point_id));
+
+        // Inbound links to removed points are kept, but skipped
+        for idx in (0..num_vectors as PointOffsetType).filter(|&idx| !removed(idx)) {
+            for level in 0..=graph_layers_builder.get_point_level(idx) {
+                graph_layers_builder.links_map(idx, level, |link| assert!(!removed(link)));
+            }
+        }
+
+        // Inbound links to removed points are dropped on save
+        let dir = tempfile::Builder::new()
+            .prefix("graph_dir")
+            .tempdir()
+            .unwrap();
+        let graph_path = GraphLayers::<GraphLinksRam>::get_path(dir.path());
+        let links_path = GraphLayers::<GraphLinksRam>::get_links_path(dir.path());
+        graph_layers_builder.flusher(graph_path.clone(), links_path.clone())().unwrap();
+        let graph = GraphLayers::<GraphLinksRam>::load(&graph_path, &links_path).unwrap();
+        assert_eq!(graph.num_points(), num_vectors);
+        for idx in 0..num_vectors as PointOffsetType {
+            for level in 0..=graph.point_level(idx) {
+                assert!(graph
+                    .links
+                    .links(idx, level)
+                    .iter()
+                    .all(|&link| !removed(link)));
+            }
+        }
+
+        let query = random_vector(&mut rng, dim);
+        let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in (0..num_vectors as PointOffsetType).filter(|&idx| !removed(idx)) {
+            let vec = &vector_holder.vectors.get(idx);
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: M::similarity(vec, &processed_query),
+            });
+        }
+
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let graph_search = graph_layers_builder.search(top, ef, scorer, None);
+
+        assert_eq!(reference_top.into_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_remove_many_points_recall() {
+        let num_vectors = 1000;
+        let dim = 8;
+        let top = 10;
+        let ef = 64;
+        let num_queries = 20;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, true, &mut rng);
+
+        // Remove half of the points in random order
+        let mut removed: Vec<_> = (0..num_vectors as PointOffsetType).collect();
+        removed.shuffle(&mut rng);
+        removed.truncate(num_vectors / 2);
+
+        let fake_filter_context = FakeFilterContext {};
+        for &idx in &removed {
+            let vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            assert!(graph_layers_builder.remove_point(idx, scorer));
+        }
+        let removed: HashSet<_> = removed.into_iter().collect();
+
+        let mut found = 0;
+        for _ in 0..num_queries {
+            let query = random_vector(&mut rng, dim);
+            let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
+            let mut reference_top = FixedLengthPriorityQueue::new(top);
+            for idx in (0..num_vectors as PointOffsetType).filter(|idx| !removed.contains(idx)) {
+                let vec = &vector_holder.vectors.get(idx);
+                reference_top.push(ScoredPointOffset {
+                    idx,
+                    score: M::similarity(vec, &processed_query),
+                });
+            }
+            let reference: HashSet<_> = reference_top.into_vec().iter().map(|p| p.idx).collect();
+
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+            let graph_search = graph_layers_builder.search(top, ef, scorer, None);
+            assert!(graph_search.iter().all(|p| !removed.contains(&p.idx)));
+            found += graph_search
+                .iter()
+                .filter(|p| reference.contains(&p.idx))
+                .count();
+        }
+
+        let recall = found as f64 / (num_queries * top) as f64;
+        assert!(recall >= 0.9, "recall after removing points is {recall}");
+    }
+
+    #[test]
+    fn test_remapped_graph_relinks_points() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers_builder) =
+            create_graph_layer::<M, _>(num_vectors, dim, true, &mut rng);
+        let graph: GraphLayers<GraphLinksRam> =
+            graph_layers_builder.into_graph_layers(None).unwrap();
+
+        // Drop all neighbours of a point, which is not an entry point
+        let point_id = (0..num_vectors as PointOffsetType)
+            .find(|&idx| graph.point_level(idx) == 0 && !graph.entry_points.is_entry_point(idx))
+            .unwrap();
+        let dropped = graph.links.links(point_id, 0).to_vec();
+        let old_to_new = |idx| (!dropped.contains(&idx)).then_some(idx);
+
+        let graph_layers_builder = GraphLayersBuilder::from_remapped_graph_layers(
+            &graph,
+            num_vectors,
+            10,
+            true,
+            old_to_new,
+        );
+
+        assert!(!graph_layers_builder.is_linked(point_id));
+        assert!(dropped
+            .iter()
+            .all(|&idx| !graph_layers_builder.is_linked(idx)));
+
+        // Point to re-link is detached from the graph
+        for idx in 0..num_vectors as PointOffsetType {
+            for level in 0..=graph_layers_builder.get_point_level(idx) {
+                graph_layers_builder.links_map(idx, level, |link| {
+                    assert_ne!(link, point_id);
+                    assert!(!dropped.contains(&link));
+                });
+            }
+        }
+
+        let fake_filter_context = FakeFilterContext {};
+        let vector = vector_holder.vectors.get(point_id).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(vector.clone()).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        graph_layers_builder.link_new_point(point_id, scorer);
+        graph_layers_builder
+            .ready_list
+            .write()
+            .set(point_id as usize, true);
+
+        graph_layers_builder.links_map(point_id, 0, |link| assert_ne!(link, point_id));
+
+        let raw_scorer = vector_holder.get_raw_scorer(vector).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let graph_search = graph_layers_builder.search(1, 64, scorer, None);
+        assert_eq!(graph_search[0].idx, point_id);
+    }
+
     #[test]
     #[ignore]
     fn test_hnsw_graph_properties() {
@@ -1,15 +1,18 @@
+use std::collections::BTreeSet;
 use std::fs::create_dir_all;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitSlice;
 #[cfg(target_os = "linux")]
 use common::cpu::linux_low_thread_priority;
 use common::cpu::CpuPermit;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use io::file_operations::{atomic_save_json, read_json};
 use log::debug;
 use memory::mmap_ops;
 use parking_lot::Mutex;
@@ -17,12 +20,12 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
-use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::graph_links::{GraphLinks, GraphLinksMmap, GraphLinksRam};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
-use crate::common::BYTES_IN_KB;
+use crate::common::{Flusher, BYTES_IN_KB};
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::{IdTracker, IdTrackerSS};
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
@@ -34,7 +37,7 @@ use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
@@ -44,11 +47,26 @@ use crate::types::{
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::discovery_query::DiscoveryQuery;
 use crate::vector_storage::{
-    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+    check_deleted_condition, new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage,
+    VectorStorageEnum,
 };
 
 const HNSW_USE_HEURISTIC: bool = true;
 
+/// Points of the appendable graph, which were re-linked with a new vector after the graph was saved
+pub const HNSW_RELINKED_POINTS_FILE: &str = "relinked_points.json";
+
+/// Appendable graph is saved on flush, once the number of in-place changes reaches
+/// `1 / APPENDABLE_GRAPH_FLUSH_RATIO` of its linked points. Saving copies the whole graph.
+///
+/// Changes, which are not saved, are recovered on load: points missing in the graph are linked and
+/// deleted points are unlinked. Points updated in place keep the links of their previous vector.
+const APPENDABLE_GRAPH_FLUSH_RATIO: usize = 10;
+
+/// Graph of another index is not reused, if more than this fraction of its points is dropped.
+/// Remaining points would need too many links repaired, building from scratch is better.
+const MAX_REUSED_GRAPH_DROPPED_RATIO: f64 = 0.3;
+
 /// Build first N points in HNSW graph using only a single thread, to avoid
 /// disconnected components in the graph.
 #[cfg(debug_assertions)]
@@ -64,6 +82,15 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    /// Index is updated in place on inserts and deletes
+    appendable: bool,
+    /// Graph of the appendable index, replaces `graph` once it is built
+    appendable_graph: Option<GraphLayersBuilder>,
+    /// Number of in-place changes of the appendable graph, which are not saved yet
+    appendable_graph_changes: Arc<AtomicUsize>,
+    /// Points re-linked in place with a new vector, since the appendable graph was saved.
+    /// Saved graph keeps links of their previous vectors, so they are re-linked on load.
+    relinked_points: Arc<Mutex<BTreeSet<PointOffsetType>>>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -84,11 +111,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
         payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
         hnsw_config: HnswConfig,
+        appendable: bool,
     ) -> OperationResult<Self> {
         create_dir_all(path)?;
 
         let config_path = HnswGraphConfig::get_config_path(path);
-        let config = if config_path.exists() {
+        let mut config = if config_path.exists() {
             HnswGraphConfig::load(&config_path)?
         } else {
             let vector_storage = vector_storage.borrow();
@@ -108,12 +136,37 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         let graph_path = GraphLayers::<TGraphLinks>::get_path(path);
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
-        let graph = if graph_path.exists() {
+        let mut graph = if graph_path.exists() {
             Some(GraphLayers::load(&graph_path, &graph_links_path)?)
         } else {
             None
         };
-        Ok(HNSWIndex {
+
+        // Appendable graph is kept in the builder. Catch up with updates, which were applied
+        // to the vector storage, but not persisted in the graph yet.
+        let mut appendable_graph = None;
+        let mut graph_changed = false;
+        if appendable {
+            if let Some(loaded_graph) = graph.take() {
+                let relinked_points_path = Self::get_relinked_points_path(path);
+                let relinked_points: BTreeSet<PointOffsetType> = if relinked_points_path.exists() {
+                    read_json(&relinked_points_path)?
+                } else {
+                    BTreeSet::new()
+                };
+                let (builder, linked, changed) = Self::appendable_graph(
+                    &loaded_graph,
+                    &id_tracker.borrow(),
+                    &vector_storage.borrow(),
+                    &relinked_points,
+                )?;
+                appendable_graph = Some(builder);
+                config.in_place_linked_count += linked;
+                graph_changed = changed;
+            }
+        }
+
+        let index = HNSWIndex {
             id_tracker,
             vector_storage,
             quantized_vectors,
@@ -121,6 +174,10 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            appendable,
+            appendable_graph,
+            appendable_graph_changes: Arc::new(AtomicUsize::new(0)),
+            relinked_points: Arc::new(Mutex::new(BTreeSet::new())),
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -129,7 +186,108 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
             },
-        })
+        };
+
+        if graph_changed {
+            index.save()?;
+        }
+
+        Ok(index)
+    }
+
+    pub fn get_relinked_points_path(path: &Path) -> PathBuf {
+        path.join(HNSW_RELINKED_POINTS_FILE)
+    }
+
+    /// Convert built graph into the builder, which can be updated in place
+    ///
+    /// Deleted points are unlinked, points, which are missing in the graph, are linked and
+    /// `relinked_points` are linked again with their current vectors.
+    /// Returns the number of linked points, and whether the graph was changed.
+    fn appendable_graph(
+        graph: &GraphLayers<TGraphLinks>,
+        id_tracker: &IdTrackerSS,
+        vector_storage: &VectorStorageEnum,
+        relinked_points: &BTreeSet<PointOffsetType>,
+    ) -> OperationResult<(GraphLayersBuilder, usize, bool)> {
+        let total_vector_count = vector_storage.total_vector_count();
+        let mut builder =
+            GraphLayersBuilder::from_graph_layers(graph, total_vector_count, HNSW_USE_HEURISTIC);
+        let mut linked = 0;
+        let mut changed = false;
+
+        for point_id in 0..total_vector_count as PointOffsetType {
+            let deleted = !check_deleted_condition(
+                point_id,
+                vector_storage.deleted_vector_bitslice(),
+                id_tracker.deleted_point_bitslice(),
+            );
+            if deleted {
+                changed |= Self::unlink_point(&builder, point_id, vector_storage, id_tracker)?;
+            } else if !builder.is_linked(point_id) || relinked_points.contains(&point_id) {
+                if let Some(vector) = vector_storage.get_vector_opt(point_id) {
+                    Self::link_point(
+                        &mut builder,
+                        point_id,
+                        vector.as_vec_ref(),
+                        vector_storage,
+                        id_tracker,
+                    )?;
+                    linked += 1;
+                    changed = true;
+                }
+            }
+        }
+
+        Ok((builder, linked, changed))
+    }
+
+    /// Link the point into the graph, or re-link it if the vector was changed
+    fn link_point(
+        graph: &mut GraphLayersBuilder,
+        point_id: PointOffsetType,
+        vector: VectorRef,
+        vector_storage: &VectorStorageEnum,
+        id_tracker: &IdTrackerSS,
+    ) -> OperationResult<()> {
+        let raw_scorer = new_raw_scorer(
+            vector.into(),
+            vector_storage,
+            id_tracker.deleted_point_bitslice(),
+        )?;
+
+        if graph.is_linked(point_id) {
+            // Keep the level of the point, links are selected again
+            graph.remove_point(point_id, FilteredScorer::new(raw_scorer.as_ref(), None));
+        } else {
+            let level = graph.get_random_layer(&mut thread_rng());
+            graph.set_levels(point_id, level);
+        }
+
+        graph.link_new_point(point_id, FilteredScorer::new(raw_scorer.as_ref(), None));
+        Ok(())
+    }
+
+    /// Remove the point from the graph
+    ///
+    /// Returns true, if the graph was changed.
+    fn unlink_point(
+        graph: &GraphLayersBuilder,
+        point_id: PointOffsetType,
+        vector_storage: &VectorStorageEnum,
+        id_tracker: &IdTrackerSS,
+    ) -> OperationResult<bool> {
+        if !graph.is_linked(point_id) {
+            return Ok(false);
+        }
+        // Neighbours are scored against each other, query vector is not used
+        let vector = vector_storage.get_vector(point_id);
+        let raw_scorer = new_raw_scorer(
+            vector.as_vec_ref().into(),
+            vector_storage,
+            id_tracker.deleted_point_bitslice(),
+        )?;
+        Ok(graph.remove_point(point_id, FilteredScorer::new(raw_scorer.as_ref(), None)))
     }
 
     #[cfg(test)]
@@ -137,10 +295,58 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         self.graph.as_ref()
     }
 
+    /// Number of vectors linked in place since the graph was built
+    ///
+    /// These vectors have no links of the payload index graphs, so filtered search degrades as
+    /// they accumulate. Zero, if the index has no payload index graphs.
+    pub fn in_place_linked_count(&self) -> usize {
+        let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+        if payload_m == 0 || self.payload_index.borrow().indexed_fields().is_empty() {
+            return 0;
+        }
+        self.config.in_place_linked_count
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
 
+    /// Copy the graph of this index to continue building the index of another segment
+    ///
+    /// Returns `None`, if there is no graph, it is built with different parameters or too many of
+    /// its points are dropped.
+    fn remapped_graph<F>(
+        &self,
+        config: &HnswGraphConfig,
+        num_vectors: usize,
+        entry_points_num: usize,
+        old_to_new: F,
+    ) -> OperationResult<Option<GraphLayersBuilder>>
+    where
+        F: Fn(PointOffsetType) -> Option<PointOffsetType>,
+    {
+        let same_params = self.config.m == config.m
+            && self.config.m0 == config.m0
+            && self.config.ef_construct == config.ef_construct
+            && self.config.payload_m == config.payload_m
+            && self.config.payload_m0 == config.payload_m0;
+        if !same_params {
+            return Ok(None);
+        }
+
+        let remapped = match (&self.appendable_graph, &self.graph) {
+            (Some(appendable_graph), _) => remap_graph(
+                &appendable_graph.to_graph_layers::<GraphLinksRam>(None)?,
+                num_vectors,
+                entry_points_num,
+                old_to_new,
+            ),
+            (None, Some(graph)) => remap_graph(graph, num_vectors, entry_points_num, old_to_new),
+            (None, None) => None,
+        };
+        Ok(remapped)
+    }
+
     fn save_config(&self) -> OperationResult<()> {
         let config_path = HnswGraphConfig::get_config_path(&self.path);
         self.config.save(&config_path)
@@ -148,7 +354,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
     fn save_graph(&self) -> OperationResult<()> {
         let graph_path = GraphLayers::<TGraphLinks>::get_path(&self.path);
-        if let Some(graph) = &self.graph {
+        if let Some(appendable_graph) = &self.appendable_graph {
+            let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
+            self.appendable_graph_changes.store(0, Ordering::Relaxed);
+            self.relinked_points.lock().clear();
+            appendable_graph.flusher(graph_path, graph_links_path)()?;
+            remove_relinked_points(&self.path)
+        } else if let Some(graph) = &self.graph {
             graph.save(&graph_path)
         } else {
             Ok(())
@@ -252,6 +464,264 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         Ok(())
     }
 
+    /// Build the index, reusing the graph of `old_index` for points, which it has in common with
+    /// this index
+    ///
+    /// `old_to_new` maps point offsets of the old index to point offsets of this index.
+    pub fn build_index_with_old_graph(
+        &mut self,
+        permit: Arc<CpuPermit>,
+        stopped: &AtomicBool,
+        old_index: &VectorIndexEnum,
+        old_to_new: &[Option<PointOffsetType>],
+    ) -> OperationResult<()> {
+        self.build_graph(permit, stopped, Some((old_index, old_to_new)))
+    }
+
+    fn build_graph(
+        &mut self,
+        permit: Arc<CpuPermit>,
+        stopped: &AtomicBool,
+        old_index: Option<(&VectorIndexEnum, &[Option<PointOffsetType>])>,
+    ) -> OperationResult<()> {
+        // Build main index graph
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+        let mut rng = thread_rng();
+
+        let total_vector_count = vector_storage.total_vector_count();
+        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+
+        debug!(
+            "building HNSW for {total_vector_count} vectors with {} CPUs",
+            permit.num_cpus,
+        );
+        let indexing_threshold = self.config.full_scan_threshold;
+        let entry_points_num = (total_vector_count
+            .checked_div(indexing_threshold)
+            .unwrap_or(0)
+            * 10)
+            .max(1);
+
+        // Reuse links of the old graph for points, which are not deleted in this index
+        let old_graph = match old_index {
+            Some((old_index, old_to_new)) => {
+                let point_deleted_bitslice = id_tracker.deleted_point_bitslice();
+                let old_to_new = |point_id| {
+                    remap_point(
+                        point_id,
+                        old_to_new,
+                        deleted_bitslice,
+                        point_deleted_bitslice,
+                    )
+                };
+                match old_index {
+                    VectorIndexEnum::HnswRam(old_index) => old_index.remapped_graph(
+                        &self.config,
+                        total_vector_count,
+                        entry_points_num,
+                        old_to_new,
+                    )?,
+                    VectorIndexEnum::HnswMmap(old_index) => old_index.remapped_graph(
+                        &self.config,
+                        total_vector_count,
+                        entry_points_num,
+                        old_to_new,
+                    )?,
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        let mut graph_layers_builder = old_graph.unwrap_or_else(|| {
+            GraphLayersBuilder::new(
+                total_vector_count,
+                self.config.m,
+                self.config.m0,
+                self.config.ef_construct,
+                entry_points_num,
+                HNSW_USE_HEURISTIC,
+            )
+        });
+        let reused_vectors = graph_layers_builder.linked_points_count();
+        debug!("reusing {reused_vectors} points of the old HNSW graph");
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-build-{idx}"))
+            .num_threads(permit.num_cpus as usize)
+            .spawn_handler(|thread| {
+                let mut b = thread::Builder::new();
+                if let Some(name) = thread.name() {
+                    b = b.name(name.to_owned());
+                }
+                if let Some(stack_size) = thread.stack_size() {
+                    b = b.stack_size(stack_size);
+                }
+                b.spawn(|| {
+                    // On Linux, use lower thread priority so we interfere less with serving traffic
+                    #[cfg(target_os = "linux")]
+                    if let Err(err) = linux_low_thread_priority() {
+                        log::debug!(
+                            "Failed to set low thread priority for HNSW building, ignoring: {err}"
+                        );
+                    }
+
+                    thread.run()
+                })?;
+                Ok(())
+            })
+            .build()?;
+
+        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
+            check_process_stopped(stopped)?;
+            if graph_layers_builder.is_linked(vector_id) {
+                continue;
+            }
+            let level = graph_layers_builder.get_random_layer(&mut rng);
+            graph_layers_builder.set_levels(vector_id, level);
+        }
+
+        let mut indexed_vectors = 0;
+
+        if self.config.m > 0 {
+            let mut ids_iterator = id_tracker
+                .iter_ids_excluding(deleted_bitslice)
+                .filter(|&vector_id| !graph_layers_builder.is_linked(vector_id));
+
+            let first_few_ids: Vec<_> = ids_iterator
+                .by_ref()
+                .take(SINGLE_THREADED_HNSW_BUILD_THRESHOLD)
+                .collect();
+            let ids: Vec<_> = ids_iterator.collect();
+
+            indexed_vectors = reused_vectors + ids.len() + first_few_ids.len();
+
+            let insert_point = |vector_id| {
+                check_process_stopped(stopped)?;
+                let vector = vector_storage.get_vector(vector_id);
+                let vector = vector.as_vec_ref().into();
+                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
+                    quantized_storage.raw_scorer(
+                        vector,
+                        id_tracker.deleted_point_bitslice(),
+                        vector_storage.deleted_vector_bitslice(),
+                        stopped,
+                    )
+                } else {
+                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
+                }?;
+                let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+
+                graph_layers_builder.link_new_point(vector_id, points_scorer);
+                Ok::<_, OperationError>(())
+            };
+
+            for vector_id in first_few_ids {
+                insert_point(vector_id)?;
+            }
+
+            if !ids.is_empty() {
+                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+            }
+
+            debug!("finish main graph");
+        } else {
+            debug!("skip building main HNSW graph");
+        }
+
+        let visited_pool = VisitedPool::new();
+        let mut block_filter_list = visited_pool.get(total_vector_count);
+        let visits_iteration = block_filter_list.get_current_iteration_id();
+
+        let payload_index = self.payload_index.borrow();
+        let payload_m = self.config.payload_m.unwrap_or(self.config.m);
+
+        if payload_m > 0 {
+            // Calculate true average number of links per vertex in the HNSW graph
+            // to better estimate percolation threshold
+            let average_links_per_0_level =
+                graph_layers_builder.get_average_connectivity_on_level(0);
+            let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
+
+            for (field, _) in payload_index.indexed_fields() {
+                debug!("building additional index for field {}", &field);
+
+                // It is expected, that graph will become disconnected less than
+                // $1/m$ points left.
+                // So blocks larger than $1/m$ are not needed.
+                // We add multiplier for the extra safety.
+                let percolation_multiplier = 4;
+                let max_block_size = if self.config.m > 0 {
+                    total_vector_count / average_links_per_0_level_int * percolation_multiplier
+                } else {
+                    usize::MAX
+                };
+                let min_block_size = indexing_threshold;
+
+                for payload_block in payload_index.payload_blocks(&field, min_block_size) {
+                    check_process_stopped(stopped)?;
+                    if payload_block.cardinality > max_block_size {
+                        continue;
+                    }
+                    // ToDo: reuse graph layer for same payload
+                    let mut additional_graph = GraphLayersBuilder::new_with_params(
+                        total_vector_count,
+                        payload_m,
+                        self.config.payload_m0.unwrap_or(self.config.m0),
+                        self.config.ef_construct,
+                        1,
+                        HNSW_USE_HEURISTIC,
+                        false,
+                    );
+                    self.build_filtered_graph(
+                        &pool,
+                        stopped,
+                        &mut additional_graph,
+                        payload_block.condition,
+                        &mut block_filter_list,
+                    )?;
+                    graph_layers_builder.merge_from_other(additional_graph);
+                }
+            }
+
+            let indexed_payload_vectors = block_filter_list.count_visits_since(visits_iteration);
+
+            debug_assert!(indexed_vectors >= indexed_payload_vectors || self.config.m == 0);
+            indexed_vectors = indexed_vectors.max(indexed_payload_vectors);
+            debug_assert!(indexed_payload_vectors <= total_vector_count);
+        } else {
+            debug!("skip building additional HNSW links");
+        }
+
+        self.config.indexed_vector_count.replace(indexed_vectors);
+
+        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
+        let graph: GraphLayers<TGraphLinks> =
+            graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
+
+        #[cfg(debug_assertions)]
+        {
+            for (idx, deleted) in deleted_bitslice.iter().enumerate() {
+                if *deleted {
+                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
+                }
+            }
+        }
+
+        if self.appendable {
+            let (appendable_graph, linked, _) =
+                Self::appendable_graph(&graph, &id_tracker, &vector_storage)?;
+            self.appendable_graph = Some(appendable_graph);
+            self.config.in_place_linked_count = linked;
+        } else {
+            self.graph = Some(graph);
+        }
+
+        debug!("finish additional payload field indexing");
+        self.save()
+    }
+
     fn search_with_graph(
         &self,
         vector: &QueryVector,
@@ -283,14 +753,16 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let filter_context = filter.map(|f| payload_index.filter_context(f));
         let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
 
-        match &self.graph {
-            Some(graph) => {
-                let search_result =
-                    graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, is_stopped)
+        let search_result = match (&self.appendable_graph, &self.graph) {
+            (Some(appendable_graph), _) => {
+                appendable_graph.search(oversampled_top, ef, points_scorer, custom_entry_points)
             }
-            None => Ok(Default::default()),
-        }
+            (None, Some(graph)) => {
+                graph.search(oversampled_top, ef, points_scorer, custom_entry_points)
+            }
+            (None, None) => return Ok(Default::default()),
+        };
+        self.postprocess_search_result(search_result, vector, params, top, is_stopped)
     }
 
     fn search_vectors_with_graph(
@@ -637,192 +1109,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn build_index(&mut self, permit: Arc<CpuPermit>, stopped: &AtomicBool) -> OperationResult<()> {
-        // Build main index graph
-        let id_tracker = self.id_tracker.borrow();
-        let vector_storage = self.vector_storage.borrow();
-        let quantized_vectors = self.quantized_vectors.borrow();
-        let mut rng = thread_rng();
-
-        let total_vector_count = vector_storage.total_vector_count();
-        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
-
-        debug!(
-            "building HNSW for {total_vector_count} vectors with {} CPUs",
-            permit.num_cpus,
-        );
-        let indexing_threshold = self.config.full_scan_threshold;
-        let mut graph_layers_builder = GraphLayersBuilder::new(
-            total_vector_count,
-            self.config.m,
-            self.config.m0,
-            self.config.ef_construct,
-            (total_vector_count
-                .checked_div(indexing_threshold)
-                .unwrap_or(0)
-                * 10)
-                .max(1),
-            HNSW_USE_HEURISTIC,
-        );
-
-        let pool = rayon::ThreadPoolBuilder::new()
-            .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(permit.num_cpus as usize)
-            .spawn_handler(|thread| {
-                let mut b = thread::Builder::new();
-                if let Some(name) = thread.name() {
-                    b = b.name(name.to_owned());
-                }
-                if let Some(stack_size) = thread.stack_size() {
-                    b = b.stack_size(stack_size);
-                }
-                b.spawn(|| {
-                    // On Linux, use lower thread priority so we interfere less with serving traffic
-                    #[cfg(target_os = "linux")]
-                    if let Err(err) = linux_low_thread_priority() {
-                        log::debug!(
-                            "Failed to set low thread priority for HNSW building, ignoring: {err}"
-                        );
-                    }
-
-                    thread.run()
-                })?;
-                Ok(())
-            })
-            .build()?;
-
-        for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
-            check_process_stopped(stopped)?;
-            let level = graph_layers_builder.get_random_layer(&mut rng);
-            graph_layers_builder.set_levels(vector_id, level);
-        }
-
-        let mut indexed_vectors = 0;
-
-        if self.config.m > 0 {
-            let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
-
-            let first_few_ids: Vec<_> = ids_iterator
-                .by_ref()
-                .take(SINGLE_THREADED_HNSW_BUILD_THRESHOLD)
-                .collect();
-            let ids: Vec<_> = ids_iterator.collect();
-
-            indexed_vectors = ids.len() + first_few_ids.len();
-
-            let insert_point = |vector_id| {
-                check_process_stopped(stopped)?;
-                let vector = vector_storage.get_vector(vector_id);
-                let vector = vector.as_vec_ref().into();
-                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
-                    quantized_storage.raw_scorer(
-                        vector,
-                        id_tracker.deleted_point_bitslice(),
-                        vector_storage.deleted_vector_bitslice(),
-                        stopped,
-                    )
-                } else {
-                    new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
-                }?;
-                let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
-
-                graph_layers_builder.link_new_point(vector_id, points_scorer);
-                Ok::<_, OperationError>(())
-            };
-
-            for vector_id in first_few_ids {
-                insert_point(vector_id)?;
-            }
-
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
-            }
-
-            debug!("finish main graph");
-        } else {
-            debug!("skip building main HNSW graph");
-        }
-
-        let visited_pool = VisitedPool::new();
-        let mut block_filter_list = visited_pool.get(total_vector_count);
-        let visits_iteration = block_filter_list.get_current_iteration_id();
-
-        let payload_index = self.payload_index.borrow();
-        let payload_m = self.config.payload_m.unwrap_or(self.config.m);
-
-        if payload_m > 0 {
-            // Calculate true average number of links per vertex in the HNSW graph
-            // to better estimate percolation threshold
-            let average_links_per_0_level =
-                graph_layers_builder.get_average_connectivity_on_level(0);
-            let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
-
-            for (field, _) in payload_index.indexed_fields() {
-                debug!("building additional index for field {}", &field);
-
-                // It is expected, that graph will become disconnected less than
-                // $1/m$ points left.
-                // So blocks larger than $1/m$ are not needed.
-                // We add multiplier for the extra safety.
-                let percolation_multiplier = 4;
-                let max_block_size = if self.config.m > 0 {
-                    total_vector_count / average_links_per_0_level_int * percolation_multiplier
-                } else {
-                    usize::MAX
-                };
-                let min_block_size = indexing_threshold;
-
-                for payload_block in payload_index.payload_blocks(&field, min_block_size) {
-                    check_process_stopped(stopped)?;
-                    if payload_block.cardinality > max_block_size {
-                        continue;
-                    }
-                    // ToDo: reuse graph layer for same payload
-                    let mut additional_graph = GraphLayersBuilder::new_with_params(
-                        total_vector_count,
-                        payload_m,
-                        self.config.payload_m0.unwrap_or(self.config.m0),
-                        self.config.ef_construct,
-                        1,
-                        HNSW_USE_HEURISTIC,
-                        false,
-                    );
-                    self.build_filtered_graph(
-                        &pool,
-                        stopped,
-                        &mut additional_graph,
-                        payload_block.condition,
-                        &mut block_filter_list,
-                    )?;
-                    graph_layers_builder.merge_from_other(additional_graph);
-                }
-            }
-
-            let indexed_payload_vectors = block_filter_list.count_visits_since(visits_iteration);
-
-            debug_assert!(indexed_vectors >= indexed_payload_vectors || self.config.m == 0);
-            indexed_vectors = indexed_vectors.max(indexed_payload_vectors);
-            debug_assert!(indexed_payload_vectors <= total_vector_count);
-        } else {
-            debug!("skip building additional HNSW links");
-        }
-
-        self.config.indexed_vector_count.replace(indexed_vectors);
-
-        let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
-        self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
-
-        #[cfg(debug_assertions)]
-        {
-            let graph = self.graph.as_ref().unwrap();
-            for (idx, deleted) in deleted_bitslice.iter().enumerate() {
-                if *deleted {
-                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
-                }
-            }
-        }
-
-        debug!("finish additional payload field indexing");
-        self.save()
+        self.build_graph(permit, stopped, None)
     }
 
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
@@ -842,17 +1129,25 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        if self.graph.is_some() {
-            vec![
+        if self.graph.is_some() || self.appendable_graph.is_some() {
+            let mut files = vec![
                 GraphLayers::<TGraphLinks>::get_path(&self.path),
                 GraphLayers::<TGraphLinks>::get_links_path(&self.path),
-            ]
+            ];
+            let relinked_points_path = Self::get_relinked_points_path(&self.path);
+            if relinked_points_path.exists() {
+                files.push(relinked_points_path);
+            }
+            files
         } else {
             vec![]
         }
     }
 
     fn indexed_vector_count(&self) -> usize {
+        if let Some(appendable_graph) = &self.appendable_graph {
+            return appendable_graph.linked_points_count();
+        }
         self.config
             .indexed_vector_count
             // If indexed vector count is unknown, fall back to number of points
@@ -860,7 +1155,150 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             .unwrap_or(0)
     }
 
-    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
-        Err(OperationError::service_error("Cannot update HNSW index"))
+    fn update_vector(&mut self, id: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        if !self.appendable {
+            return Err(OperationError::service_error("Cannot update HNSW index"));
+        }
+        // Point is linked, once the graph is built
+        let Some(appendable_graph) = &mut self.appendable_graph else {
+            return Ok(());
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        if vector_storage.is_deleted_vector(id) {
+            Self::unlink_point(appendable_graph, id, &vector_storage, &id_tracker)?;
+        } else {
+            if appendable_graph.is_linked(id) {
+                self.relinked_points.lock().insert(id);
+            }
+            Self::link_point(appendable_graph, id, vector, &vector_storage, &id_tracker)?;
+            self.config.in_place_linked_count += 1;
+        }
+        self.appendable_graph_changes
+            .fetch_add(1, Ordering::Relaxed);
+        Ok(())
     }
+
+    fn delete_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        let Some(appendable_graph) = &self.appendable_graph else {
+            // Deleted vectors are skipped on search
+            return Ok(());
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        if Self::unlink_point(appendable_graph, id, &vector_storage, &id_tracker)? {
+            self.appendable_graph_changes
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        // Graph of non-appendable index is saved once it is built
+        let Some(appendable_graph) = &self.appendable_graph else {
+            return Box::new(|| Ok(()));
+        };
+        // Saving copies the whole graph, so it is deferred until enough changes accumulate.
+        // Until then, only points re-linked in place are saved, to re-link them again on load.
+        let changes = self.appendable_graph_changes.load(Ordering::Relaxed);
+        if changes == 0
+            || changes * APPENDABLE_GRAPH_FLUSH_RATIO < appendable_graph.linked_points_count()
+        {
+            let relinked_points = self.relinked_points.lock().clone();
+            if relinked_points.is_empty() {
+                return Box::new(|| Ok(()));
+            }
+            let relinked_points_path = Self::get_relinked_points_path(&self.path);
+            return Box::new(move || {
+                Ok(atomic_save_json(&relinked_points_path, &relinked_points)?)
+            });
+        }
+        self.appendable_graph_changes
+            .fetch_sub(changes, Ordering::Relaxed);
+        let relinked_points = std::mem::take(&mut *self.relinked_points.lock());
+
+        let graph_flusher = appendable_graph.flusher(
+            GraphLayers::<TGraphLinks>::get_path(&self.path),
+            GraphLayers::<TGraphLinks>::get_links_path(&self.path),
+        );
+        let config = self.config;
+        let path = self.path.clone();
+        let appendable_graph_changes = self.appendable_graph_changes.clone();
+        let pending_relinked_points = self.relinked_points.clone();
+        Box::new(move || {
+            graph_flusher()
+                .and_then(|()| config.save(&HnswGraphConfig::get_config_path(&path)))
+                .and_then(|()| remove_relinked_points(&path))
+                .map_err(|err| {
+                    // Retry on the next flush
+                    appendable_graph_changes.fetch_add(changes, Ordering::Relaxed);
+                    pending_relinked_points.lock().extend(relinked_points);
+                    err
+                })
+        })
+    }
+}
+
+/// Remove the list of re-linked points, once the graph with their new links is saved
+fn remove_relinked_points(path: &Path) -> OperationResult<()> {
+    let relinked_points_path = path.join(HNSW_RELINKED_POINTS_FILE);
+    if relinked_points_path.exists() {
+        std::fs::remove_file(relinked_points_path)?;
+    }
+    Ok(())
+}
+
+/// Copy the graph with translated point offsets, unless too many of its points are dropped
+fn remap_graph<TGraphLinks, F>(
+    graph: &GraphLayers<TGraphLinks>,
+    num_vectors: usize,
+    entry_points_num: usize,
+    old_to_new: F,
+) -> Option<GraphLayersBuilder>
+where
+    TGraphLinks: GraphLinks,
+    F: Fn(PointOffsetType) -> Option<PointOffsetType>,
+{
+    let num_points = graph.num_points();
+    let dropped_points = (0..num_points as PointOffsetType)
+        .filter(|&point_id| old_to_new(point_id).is_none())
+        .count();
+    if dropped_points as f64 > num_points as f64 * MAX_REUSED_GRAPH_DROPPED_RATIO {
+        debug!(
+            "not reusing the old HNSW graph, {dropped_points} of {num_points} points are dropped"
+        );
+        return None;
+    }
+    Some(GraphLayersBuilder::from_remapped_graph_layers(
+        graph,
+        num_vectors,
+        entry_points_num,
+        HNSW_USE_HEURISTIC,
+        old_to_new,
+    ))
+}
+
+/// Translate point offset of the old index
+///
+/// Points, which are not mapped, or are deleted in the new index, either in the vector storage
+/// or in the id tracker, are skipped.
+fn remap_point(
+    old_point_id: PointOffsetType,
+    old_to_new: &[Option<PointOffsetType>],
+    vector_deleted_bitslice: &BitSlice,
+    point_deleted_bitslice: &BitSlice,
+) -> Option<PointOffsetType> {
+    old_to_new
+        .get(old_point_id as usize)
+        .copied()
+        .flatten()
+        .filter(|&new_point_id| {
+            check_deleted_condition(
+                new_point_id,
+                vector_deleted_bitslice,
+                point_deleted_bitslice,
+            )
+        })
 }
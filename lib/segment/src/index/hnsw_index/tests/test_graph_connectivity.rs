@@ -45,6 +45,8 @@ fn test_graph_connectivity() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -70,6 +72,7 @@ fn test_graph_connectivity() {
         max_indexing_threads: 4,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -84,6 +87,7 @@ fn test_graph_connectivity() {
         Default::default(),
         payload_index_ptr.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
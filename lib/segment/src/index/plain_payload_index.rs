@@ -317,6 +317,14 @@ impl VectorIndex for PlainIndex {
     fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
         Ok(())
     }
+
+    fn delete_vector(&mut self, _id: PointOffsetType) -> OperationResult<()> {
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new(|| Ok(()))
+    }
 }
 
 pub struct PlainFilterContext<'a> {
@@ -5,6 +5,7 @@ use serde_json::Value;
 
 use crate::common::utils::IndexesMap;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::map_index::MapIndex;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
@@ -15,8 +16,9 @@ use crate::payload_storage::query_checker::{
 };
 use crate::types::{
     AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchPhrase, MatchText,
+    MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface, UuidIntType,
+    ValueVariants,
 };
 
 pub fn condition_converter<'a>(
@@ -73,6 +75,14 @@ pub fn condition_converter<'a>(
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        Condition::CustomIdChecker(checker) => {
+            let segment_ids: HashSet<_> = id_tracker
+                .iter_external()
+                .filter(|&external_id| checker.check(external_id))
+                .filter_map(|external_id| id_tracker.internal_id(external_id))
+                .collect();
+            Box::new(move |point_id| segment_ids.contains(&point_id))
+        }
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
             // that nested condition can address fields without nested part.
@@ -274,6 +284,16 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                         .map_or(false, |values| values.iter().any(|k| k == &keyword))
                 }))
             }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidMapIndex(index)) => {
+                let uuid = MapIndex::<UuidIntType>::parse_uuid(&keyword);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    uuid.is_some_and(|uuid| {
+                        index
+                            .get_values(point_id)
+                            .map_or(false, |values| values.contains(&uuid))
+                    })
+                }))
+            }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index
@@ -301,6 +321,15 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
             }
             _ => None,
         },
+        Match::Phrase(MatchPhrase { phrase }) => match index {
+            FieldIndex::FullTextIndex(full_text_index) => {
+                let parsed_query = full_text_index.parse_phrase(&phrase);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index.check_match(&parsed_query, point_id)
+                }))
+            }
+            _ => None,
+        },
         Match::Any(MatchAny { any }) => match (any, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
@@ -315,6 +344,14 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     })
                 }))
             }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let uuids = MapIndex::<UuidIntType>::parse_uuids(&list);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |values| values.iter().any(|u| uuids.contains(u)))
+                }))
+            }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.get_values(point_id).map_or(false, |values| {
@@ -353,6 +390,14 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     })
                 }))
             }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let uuids = MapIndex::<UuidIntType>::parse_uuids(&list);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index
+                        .get_values(point_id)
+                        .map_or(false, |values| values.iter().any(|u| !uuids.contains(u)))
+                }))
+            }
             (_, index) => Some(Box::new(|point_id: PointOffsetType| {
                 // If there is any other value of any other index, then it's a match
                 index.values_count(point_id) > 0
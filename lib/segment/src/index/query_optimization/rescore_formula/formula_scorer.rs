@@ -0,0 +1,280 @@
+use std::str::FromStr;
+
+use common::types::ScoreType;
+use geo::prelude::HaversineDistance;
+use geo::Point;
+use serde_json::Value;
+
+use super::parsed_formula::{
+    ParsedExpression, ParsedFormula, PreciseScore, VariableId, DEFAULT_DECAY_TARGET,
+};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, GeoPoint, Payload, PayloadContainer};
+
+const MICROS_IN_SECOND: PreciseScore = 1_000_000.0;
+
+/// Evaluates the formula for a single point
+pub struct FormulaScorer<'a> {
+    formula: &'a ParsedFormula,
+    /// Score of the point in each prefetch, if it was found by it
+    scores: Vec<Option<ScoreType>>,
+    /// Payload of the point, only needs keys the formula depends on
+    payload: &'a Payload,
+    /// Whether the point satisfies each condition of the formula
+    conditions: Vec<bool>,
+}
+
+fn variable_error(variable: &VariableId, description: impl Into<String>) -> OperationError {
+    OperationError::VariableTypeError {
+        variable: variable.to_string(),
+        description: description.into(),
+    }
+}
+
+impl<'a> FormulaScorer<'a> {
+    pub fn new(
+        formula: &'a ParsedFormula,
+        scores: Vec<Option<ScoreType>>,
+        payload: &'a Payload,
+        conditions: Vec<bool>,
+    ) -> Self {
+        debug_assert_eq!(formula.conditions.len(), conditions.len());
+        Self {
+            formula,
+            scores,
+            payload,
+            conditions,
+        }
+    }
+
+    /// Score of the point, fails if a variable has no suitable value or the result is not finite
+    pub fn score(&self) -> OperationResult<ScoreType> {
+        let score = self.evaluate(&self.formula.formula)?;
+        let score = score as ScoreType;
+        if !score.is_finite() {
+            return Err(OperationError::NonFiniteNumber {
+                value: score as PreciseScore,
+            });
+        }
+        Ok(score)
+    }
+
+    /// Own value of the variable, or its default
+    fn value(&self, variable: &VariableId) -> OperationResult<Value> {
+        let own_value = match variable {
+            VariableId::Score(index) => self
+                .scores
+                .get(*index)
+                .copied()
+                .flatten()
+                .map(|score| Value::from(score as PreciseScore)),
+            VariableId::Payload(key) => self.payload.get_value(key).first().copied().cloned(),
+            VariableId::Condition(index) => self
+                .conditions
+                .get(*index)
+                .map(|satisfied| Value::from(if *satisfied { 1.0 } else { 0.0 })),
+        };
+        own_value
+            .or_else(|| self.formula.defaults.get(variable).cloned())
+            .ok_or_else(|| variable_error(variable, "no value found and no default is given"))
+    }
+
+    fn number(&self, variable: &VariableId) -> OperationResult<PreciseScore> {
+        let value = self.value(variable)?;
+        value
+            .as_f64()
+            .ok_or_else(|| variable_error(variable, format!("expected a number, got {value}")))
+    }
+
+    fn geo_point(&self, key: &JsonPath) -> OperationResult<GeoPoint> {
+        let variable = VariableId::Payload(key.clone());
+        let value = self.value(&variable)?;
+        serde_json::from_value(value.clone())
+            .map_err(|_| variable_error(&variable, format!("expected a geo point, got {value}")))
+    }
+
+    fn datetime(&self, key: &JsonPath) -> OperationResult<PreciseScore> {
+        let variable = VariableId::Payload(key.clone());
+        let value = self.value(&variable)?;
+        value
+            .as_str()
+            .and_then(|value| DateTimePayloadType::from_str(value).ok())
+            .map(|datetime| datetime.timestamp() as PreciseScore / MICROS_IN_SECOND)
+            .ok_or_else(|| variable_error(&variable, format!("expected a datetime, got {value}")))
+    }
+
+    fn evaluate(&self, expression: &ParsedExpression) -> OperationResult<PreciseScore> {
+        match expression {
+            ParsedExpression::Constant(value) => Ok(*value),
+            ParsedExpression::Variable(variable) => self.number(variable),
+            ParsedExpression::GeoDistance { origin, key } => {
+                let point = self.geo_point(key)?;
+                Ok(Point::new(origin.lon, origin.lat)
+                    .haversine_distance(&Point::new(point.lon, point.lat)))
+            }
+            ParsedExpression::DatetimeKey(key) => self.datetime(key),
+            ParsedExpression::Mult(expressions) => {
+                expressions.iter().try_fold(1.0, |product, expression| {
+                    Ok(product * self.evaluate(expression)?)
+                })
+            }
+            ParsedExpression::Sum(expressions) => expressions
+                .iter()
+                .try_fold(0.0, |sum, expression| Ok(sum + self.evaluate(expression)?)),
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                match by_zero_default {
+                    Some(default) if right == 0.0 => Ok(*default),
+                    _ => Ok(left / right),
+                }
+            }
+            ParsedExpression::Neg(expression) => Ok(-self.evaluate(expression)?),
+            ParsedExpression::Abs(expression) => Ok(self.evaluate(expression)?.abs()),
+            ParsedExpression::Sqrt(expression) => Ok(self.evaluate(expression)?.sqrt()),
+            ParsedExpression::Pow { base, exponent } => {
+                Ok(self.evaluate(base)?.powf(self.evaluate(exponent)?))
+            }
+            ParsedExpression::Exp(expression) => Ok(self.evaluate(expression)?.exp()),
+            ParsedExpression::Log10(expression) => Ok(self.evaluate(expression)?.log10()),
+            ParsedExpression::Ln(expression) => Ok(self.evaluate(expression)?.ln()),
+            ParsedExpression::Decay {
+                kind,
+                x,
+                target,
+                scale,
+                midpoint,
+            } => {
+                let x = self.evaluate(x)?;
+                let target = match target {
+                    Some(target) => self.evaluate(target)?,
+                    None => DEFAULT_DECAY_TARGET,
+                };
+                Ok(ParsedExpression::decay(
+                    *kind,
+                    x - target,
+                    *scale,
+                    *midpoint,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::index::query_optimization::rescore_formula::parsed_formula::DecayKind;
+
+    fn key(key: &str) -> JsonPath {
+        key.parse().unwrap()
+    }
+
+    fn formula(formula: ParsedExpression, defaults: HashMap<VariableId, Value>) -> ParsedFormula {
+        ParsedFormula {
+            payload_vars: HashSet::new(),
+            conditions: vec![],
+            defaults,
+            formula,
+        }
+    }
+
+    fn score(formula: &ParsedFormula, payload: &Payload) -> OperationResult<ScoreType> {
+        FormulaScorer::new(formula, vec![Some(0.5), None], payload, vec![]).score()
+    }
+
+    #[test]
+    fn test_evaluate_formula() {
+        let payload: Payload = json!({
+            "popularity": 100,
+            "published_at": "2024-01-01T00:00:10Z",
+            "location": { "lon": 0.0, "lat": 0.0 },
+            "tag": "news",
+        })
+        .into();
+
+        // similarity * 2 + log10(popularity)
+        let weighted = formula(
+            ParsedExpression::Sum(vec![
+                ParsedExpression::Mult(vec![
+                    ParsedExpression::Variable(VariableId::Score(0)),
+                    ParsedExpression::Constant(2.0),
+                ]),
+                ParsedExpression::Log10(Box::new(ParsedExpression::Variable(VariableId::Payload(
+                    key("popularity"),
+                )))),
+            ]),
+            HashMap::new(),
+        );
+        assert_eq!(score(&weighted, &payload).unwrap(), 3.0);
+
+        // Recency decays by half in 10 seconds
+        let recency = formula(
+            ParsedExpression::new_decay(
+                DecayKind::Exp,
+                ParsedExpression::DatetimeKey(key("published_at")),
+                Some(ParsedExpression::Constant(1704067200.0)),
+                Some(10.0),
+                Some(0.5),
+            )
+            .unwrap(),
+            HashMap::new(),
+        );
+        assert!((score(&recency, &payload).unwrap() - 0.5).abs() < 1e-6);
+
+        let distance = formula(
+            ParsedExpression::GeoDistance {
+                origin: GeoPoint { lon: 0.0, lat: 1.0 },
+                key: key("location"),
+            },
+            HashMap::new(),
+        );
+        let meters = score(&distance, &payload).unwrap();
+        assert!((meters - 111_195.0).abs() < 100.0, "{meters}");
+    }
+
+    #[test]
+    fn test_missing_and_invalid_values() {
+        let payload: Payload = json!({ "tag": "news" }).into();
+
+        // Score of the second prefetch is missing, payload value is not a number
+        for variable in [VariableId::Score(1), VariableId::Payload(key("tag"))] {
+            let expression = ParsedExpression::Variable(variable.clone());
+            let result = score(&formula(expression.clone(), HashMap::new()), &payload);
+            assert!(matches!(
+                result,
+                Err(OperationError::VariableTypeError { .. })
+            ));
+
+            // Default is only used if there is no value at all
+            let defaults = HashMap::from([(variable.clone(), json!(7))]);
+            let result = score(&formula(expression, defaults), &payload);
+            assert_eq!(result.is_ok(), variable == VariableId::Score(1));
+        }
+
+        let division = |by_zero_default| {
+            formula(
+                ParsedExpression::Div {
+                    left: Box::new(ParsedExpression::Constant(1.0)),
+                    right: Box::new(ParsedExpression::Constant(0.0)),
+                    by_zero_default,
+                },
+                HashMap::new(),
+            )
+        };
+        assert!(matches!(
+            score(&division(None), &payload),
+            Err(OperationError::NonFiniteNumber { .. })
+        ));
+        assert_eq!(score(&division(Some(3.0)), &payload).unwrap(), 3.0);
+    }
+}
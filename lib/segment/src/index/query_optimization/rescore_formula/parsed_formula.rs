@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::json_path::JsonPath;
+use crate::types::{Condition, GeoPoint};
+
+/// Precision of intermediate results of the formula
+pub type PreciseScore = f64;
+
+const SCORE_VARIABLE: &str = "$score";
+
+pub const DEFAULT_DECAY_TARGET: PreciseScore = 0.0;
+pub const DEFAULT_DECAY_SCALE: PreciseScore = 1.0;
+pub const DEFAULT_DECAY_MIDPOINT: PreciseScore = 0.5;
+
+/// Value, which the formula depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariableId {
+    /// Score of the point in the prefetch with this index
+    Score(usize),
+    /// Value of the payload field
+    Payload(JsonPath),
+    /// Whether the point satisfies the condition with this index
+    Condition(usize),
+}
+
+impl FromStr for VariableId {
+    type Err = String;
+
+    /// Parse `$score`, `$score[<prefetch index>]` or a payload key
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some(index) = value.strip_prefix(SCORE_VARIABLE) else {
+            return JsonPath::from_str(value)
+                .map(VariableId::Payload)
+                .map_err(|_| format!("Invalid payload key in formula: {value}"));
+        };
+
+        if index.is_empty() {
+            return Ok(VariableId::Score(0));
+        }
+        index
+            .strip_prefix('[')
+            .and_then(|index| index.strip_suffix(']'))
+            .and_then(|index| index.parse().ok())
+            .map(VariableId::Score)
+            .ok_or_else(|| format!("Invalid score reference in formula: {value}"))
+    }
+}
+
+impl Display for VariableId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableId::Score(index) => write!(f, "{SCORE_VARIABLE}[{index}]"),
+            VariableId::Payload(key) => write!(f, "{key}"),
+            VariableId::Condition(index) => write!(f, "$condition[{index}]"),
+        }
+    }
+}
+
+/// Shape of the decay function, all of them are `1` at the target and `midpoint` at `scale`
+/// distance from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayKind {
+    /// Linear decay, reaches zero at a finite distance
+    Lin,
+    /// Exponential decay
+    Exp,
+    /// Gaussian decay
+    Gauss,
+}
+
+/// Expression of the formula, ready to be evaluated for each point
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedExpression {
+    Constant(PreciseScore),
+    Variable(VariableId),
+    /// Distance in meters from the origin to the geo point in the payload field
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    /// Datetime from the payload field, as seconds since UNIX epoch
+    DatetimeKey(JsonPath),
+    Mult(Vec<ParsedExpression>),
+    Sum(Vec<ParsedExpression>),
+    Div {
+        left: Box<ParsedExpression>,
+        right: Box<ParsedExpression>,
+        /// Result of the division by zero, the formula fails to evaluate if missing
+        by_zero_default: Option<PreciseScore>,
+    },
+    Neg(Box<ParsedExpression>),
+    Abs(Box<ParsedExpression>),
+    Sqrt(Box<ParsedExpression>),
+    Pow {
+        base: Box<ParsedExpression>,
+        exponent: Box<ParsedExpression>,
+    },
+    Exp(Box<ParsedExpression>),
+    Log10(Box<ParsedExpression>),
+    Ln(Box<ParsedExpression>),
+    Decay {
+        kind: DecayKind,
+        x: Box<ParsedExpression>,
+        target: Option<Box<ParsedExpression>>,
+        scale: PreciseScore,
+        midpoint: PreciseScore,
+    },
+}
+
+impl ParsedExpression {
+    /// Decay of `x` with the distance from `target`, validating its parameters
+    pub fn new_decay(
+        kind: DecayKind,
+        x: ParsedExpression,
+        target: Option<ParsedExpression>,
+        scale: Option<PreciseScore>,
+        midpoint: Option<PreciseScore>,
+    ) -> Result<Self, String> {
+        let scale = scale.unwrap_or(DEFAULT_DECAY_SCALE);
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(format!("Decay scale must be positive, got {scale}"));
+        }
+        let midpoint = midpoint.unwrap_or(DEFAULT_DECAY_MIDPOINT);
+        if !(midpoint > 0.0 && midpoint < 1.0) {
+            return Err(format!(
+                "Decay midpoint must be between 0 and 1 exclusive, got {midpoint}"
+            ));
+        }
+
+        Ok(ParsedExpression::Decay {
+            kind,
+            x: Box::new(x),
+            target: target.map(Box::new),
+            scale,
+            midpoint,
+        })
+    }
+
+    /// Value of the decay function at the `distance` from the target
+    pub fn decay(
+        kind: DecayKind,
+        distance: PreciseScore,
+        scale: PreciseScore,
+        midpoint: PreciseScore,
+    ) -> PreciseScore {
+        match kind {
+            DecayKind::Lin => {
+                let lambda = (1.0 - midpoint) / scale;
+                (1.0 - lambda * distance.abs()).max(0.0)
+            }
+            DecayKind::Exp => {
+                let lambda = midpoint.ln() / scale;
+                (lambda * distance.abs()).exp()
+            }
+            DecayKind::Gauss => {
+                let lambda = midpoint.ln() / (scale * scale);
+                (lambda * distance * distance).exp()
+            }
+        }
+    }
+}
+
+/// Formula to score points with, together with everything it depends on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFormula {
+    /// Payload keys, which have to be read to evaluate the formula
+    pub payload_vars: HashSet<JsonPath>,
+    /// Conditions, referenced by [`VariableId::Condition`]
+    pub conditions: Vec<Condition>,
+    /// Values of variables, which are used if the point has no value of its own
+    pub defaults: HashMap<VariableId, Value>,
+    pub formula: ParsedExpression,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!("$score".parse(), Ok(VariableId::Score(0)));
+        assert_eq!("$score[2]".parse(), Ok(VariableId::Score(2)));
+        assert_eq!(
+            "a.b[0]".parse(),
+            Ok(VariableId::Payload("a.b[0]".parse().unwrap()))
+        );
+        assert!("$score[x]".parse::<VariableId>().is_err());
+        assert!("$score2".parse::<VariableId>().is_err());
+
+        let score = VariableId::Score(1);
+        assert_eq!(score.to_string().parse(), Ok(score));
+    }
+
+    #[test]
+    fn test_decay() {
+        for kind in [DecayKind::Lin, DecayKind::Exp, DecayKind::Gauss] {
+            assert_eq!(ParsedExpression::decay(kind, 0.0, 10.0, 0.3), 1.0);
+            let at_scale = ParsedExpression::decay(kind, -10.0, 10.0, 0.3);
+            assert!((at_scale - 0.3).abs() < 1e-9, "{kind:?}: {at_scale}");
+            assert!(ParsedExpression::decay(kind, 20.0, 10.0, 0.3) < at_scale);
+        }
+        assert_eq!(
+            ParsedExpression::decay(DecayKind::Lin, 100.0, 10.0, 0.3),
+            0.0
+        );
+
+        let x = ParsedExpression::Constant(1.0);
+        assert!(
+            ParsedExpression::new_decay(DecayKind::Exp, x.clone(), None, Some(0.0), None).is_err()
+        );
+        assert!(ParsedExpression::new_decay(DecayKind::Exp, x, None, None, Some(1.0)).is_err());
+    }
+}
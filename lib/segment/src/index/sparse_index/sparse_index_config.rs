@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_error::OperationResult;
+use crate::types::VectorStorageDatatype;
 
 pub const SPARSE_INDEX_CONFIG_FILE: &str = "sparse_index_config.json";
 
@@ -31,6 +32,14 @@ pub struct SparseIndexConfig {
     pub full_scan_threshold: Option<usize>,
     /// Type of sparse index
     pub index_type: SparseIndexType,
+    /// Datatype of the weights stored in the index. Applies to immutable indexes only.
+    /// Weights of reduced precision are scaled into the range of each posting list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
+    /// Keep an additional full-precision index on disk, to rescore results of the index
+    /// with reduced precision weights.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_precision_on_disk: Option<bool>,
 }
 
 impl Anonymize for SparseIndexConfig {
@@ -38,6 +47,8 @@ impl Anonymize for SparseIndexConfig {
         SparseIndexConfig {
             full_scan_threshold: self.full_scan_threshold,
             index_type: self.index_type,
+            datatype: self.datatype,
+            full_precision_on_disk: self.full_precision_on_disk,
         }
     }
 }
@@ -47,9 +58,27 @@ impl SparseIndexConfig {
         SparseIndexConfig {
             full_scan_threshold,
             index_type,
+            datatype: None,
+            full_precision_on_disk: None,
         }
     }
 
+    /// Datatype of the weights, which is actually used by the index
+    pub fn effective_datatype(&self) -> VectorStorageDatatype {
+        match self.index_type {
+            SparseIndexType::MutableRam => VectorStorageDatatype::Float32,
+            SparseIndexType::ImmutableRam | SparseIndexType::Mmap => {
+                self.datatype.unwrap_or_default()
+            }
+        }
+    }
+
+    /// Whether an additional full-precision index is kept for rescoring
+    pub fn has_full_precision_index(&self) -> bool {
+        self.effective_datatype() != VectorStorageDatatype::Float32
+            && self.full_precision_on_disk.unwrap_or_default()
+    }
+
     pub fn get_config_path(path: &Path) -> PathBuf {
         path.join(SPARSE_INDEX_CONFIG_FILE)
     }
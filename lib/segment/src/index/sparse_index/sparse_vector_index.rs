@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -8,17 +8,23 @@ use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use itertools::Itertools;
+use parking_lot::Mutex;
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
+use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use sparse::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
 use sparse::index::inverted_index::InvertedIndex;
+use sparse::index::posting_list_common::PostingListIter;
 use sparse::index::search_context::SearchContext;
 
 use super::indices_tracker::IndicesTracker;
 use super::sparse_index_config::SparseIndexType;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::ScopeDurationMeasurer;
+use crate::common::Flusher;
+use crate::data_types::sparse_statistics::SparseVectorStatistics;
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
@@ -28,11 +34,16 @@ use crate::index::sparse_index::sparse_search_telemetry::SparseSearchesTelemetry
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::telemetry::VectorIndexSearchesTelemetry;
-use crate::types::{Filter, SearchParams, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD};
+use crate::types::{
+    Filter, QuantizationSearchParams, SearchParams, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD,
+};
 use crate::vector_storage::{
     check_deleted_condition, new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum,
 };
 
+/// Directory of the full-precision index, relative to the index directory
+const FULL_PRECISION_INDEX_DIR: &str = "full_precision";
+
 pub struct SparseVectorIndex<TInvertedIndex: InvertedIndex> {
     pub config: SparseIndexConfig,
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
@@ -40,10 +51,25 @@ pub struct SparseVectorIndex<TInvertedIndex: InvertedIndex> {
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     path: PathBuf,
     pub inverted_index: TInvertedIndex,
+    /// Index with full-precision weights, used to rescore results of the quantized index
+    pub full_precision_index: Option<InvertedIndexMmap>,
     searches_telemetry: SparseSearchesTelemetry,
     is_appendable: bool,
     pub indices_tracker: IndicesTracker,
     scores_memory_pool: ScoresMemoryPool,
+    /// Number of updates of the appendable index, invalidates cached document frequencies
+    updates_count: usize,
+    /// Number of live vectors containing each dimension, counted on IDF requests
+    document_frequencies: Mutex<DocumentFrequencies>,
+}
+
+/// Document frequencies of dimensions, valid while the index and deleted vectors are unchanged
+#[derive(Default)]
+struct DocumentFrequencies {
+    /// Number of index updates, deleted vectors and available points, when the frequencies were
+    /// counted
+    version: (usize, usize, usize),
+    frequencies: HashMap<DimId, usize>,
 }
 
 impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
@@ -61,27 +87,59 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let is_appendable = config.index_type == SparseIndexType::MutableRam;
 
         let config_path = SparseIndexConfig::get_config_path(path);
-        let (config, inverted_index, indices_tracker) = if is_appendable {
+        let (config, inverted_index, full_precision_index, indices_tracker) = if is_appendable {
             // RAM mutable case - build inverted index from scratch and use provided config
-            let (inverted_index, indices_tracker) = Self::build_inverted_index(
-                id_tracker.clone(),
-                vector_storage.clone(),
-                path,
-                stopped,
-            )?;
-            (config, inverted_index, indices_tracker)
+            let (inverted_index, full_precision_index, indices_tracker) =
+                Self::build_inverted_index(
+                    id_tracker.clone(),
+                    vector_storage.clone(),
+                    path,
+                    false,
+                    stopped,
+                )?;
+            (
+                config,
+                inverted_index,
+                full_precision_index,
+                indices_tracker,
+            )
         } else if config_path.exists() {
             // Load inverted index and config
             let loaded_config = SparseIndexConfig::load(&config_path)?;
             let inverted_index = TInvertedIndex::open(path)?;
+            let full_precision_index = if loaded_config.has_full_precision_index() {
+                Some(InvertedIndexMmap::open(&Self::full_precision_path(path))?)
+            } else {
+                None
+            };
             let indices_tracker =
                 IndicesTracker::open(path, || inverted_index.max_index().unwrap_or_default())?;
-            (loaded_config, inverted_index, indices_tracker)
+            (
+                loaded_config,
+                inverted_index,
+                full_precision_index,
+                indices_tracker,
+            )
         } else {
             // Inverted index and config are not presented - initialize empty inverted index
             let inverted_index = TInvertedIndex::from_ram_index(InvertedIndexRam::empty(), path)?;
+            let full_precision_index = if config.has_full_precision_index() {
+                let full_precision_path = Self::full_precision_path(path);
+                create_dir_all(&full_precision_path)?;
+                Some(InvertedIndexMmap::from_ram_index(
+                    InvertedIndexRam::empty(),
+                    full_precision_path,
+                )?)
+            } else {
+                None
+            };
             let indices_tracker = Default::default();
-            (config, inverted_index, indices_tracker)
+            (
+                config,
+                inverted_index,
+                full_precision_index,
+                indices_tracker,
+            )
         };
 
         let searches_telemetry = SparseSearchesTelemetry::new();
@@ -94,10 +152,13 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             payload_index,
             path,
             inverted_index,
+            full_precision_index,
             searches_telemetry,
             is_appendable,
             indices_tracker,
             scores_memory_pool,
+            updates_count: 0,
+            document_frequencies: Mutex::new(DocumentFrequencies::default()),
         })
     }
 
@@ -106,12 +167,17 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         self.config.save(&config_path)
     }
 
+    fn full_precision_path(path: &Path) -> PathBuf {
+        path.join(FULL_PRECISION_INDEX_DIR)
+    }
+
     fn build_inverted_index(
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
         vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
         path: &Path,
+        with_full_precision: bool,
         stopped: &AtomicBool,
-    ) -> OperationResult<(TInvertedIndex, IndicesTracker)> {
+    ) -> OperationResult<(TInvertedIndex, Option<InvertedIndexMmap>, IndicesTracker)> {
         let borrowed_vector_storage = vector_storage.borrow();
         let borrowed_id_tracker = id_tracker.borrow();
         let deleted_bitslice = borrowed_vector_storage.deleted_vector_bitslice();
@@ -141,8 +207,20 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 }
             }
         }
+        let ram_index = ram_index_builder.build();
+        let full_precision_index = if with_full_precision {
+            let full_precision_path = Self::full_precision_path(path);
+            create_dir_all(&full_precision_path)?;
+            Some(InvertedIndexMmap::convert_and_save(
+                &ram_index,
+                full_precision_path,
+            )?)
+        } else {
+            None
+        };
         Ok((
-            TInvertedIndex::from_ram_index(ram_index_builder.build(), path)?,
+            TInvertedIndex::from_ram_index(ram_index, path)?,
+            full_precision_index,
             indices_tracker,
         ))
     }
@@ -153,16 +231,72 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let mut unique_record_ids = HashSet::new();
         for dim_id in query_vector.indices.iter() {
             if let Some(dim_id) = self.indices_tracker.remap_index(*dim_id) {
-                if let Some(posting_list) = self.inverted_index.get(&dim_id) {
-                    for element in posting_list.elements.iter() {
-                        unique_record_ids.insert(element.record_id);
-                    }
+                if let Some(mut posting_list) = self.inverted_index.get(&dim_id) {
+                    posting_list.for_each_till_id(
+                        PointOffsetType::MAX,
+                        &mut unique_record_ids,
+                        |unique_record_ids, record_id, _| {
+                            unique_record_ids.insert(record_id);
+                        },
+                    );
                 }
             }
         }
         unique_record_ids.len()
     }
 
+    /// Accumulate number of indexed vectors and document frequencies of the requested dimensions
+    ///
+    /// Immutable indices keep postings of deleted points, so only live points are counted.
+    /// Frequencies are cached until the index is updated or more vectors are deleted.
+    pub fn fill_statistics(&self, statistics: &mut SparseVectorStatistics) {
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let available_point_count = id_tracker.available_point_count();
+        statistics.indexed_vector_count += vector_storage
+            .available_vector_count()
+            .min(available_point_count);
+
+        let version = (
+            self.updates_count,
+            vector_storage.deleted_vector_count(),
+            available_point_count,
+        );
+        let mut document_frequencies = self.document_frequencies.lock();
+        if document_frequencies.version != version {
+            document_frequencies.version = version;
+            document_frequencies.frequencies.clear();
+        }
+
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let deleted_points = id_tracker.deleted_point_bitslice();
+        for (dim_id, frequency) in statistics.document_frequencies.iter_mut() {
+            *frequency += *document_frequencies
+                .frequencies
+                .entry(*dim_id)
+                .or_insert_with(|| {
+                    let Some(dim_id) = self.indices_tracker.remap_index(*dim_id) else {
+                        return 0;
+                    };
+                    let Some(mut posting_list) = self.inverted_index.get(&dim_id) else {
+                        return 0;
+                    };
+                    let mut live_count = 0;
+                    posting_list.for_each_till_id(
+                        PointOffsetType::MAX,
+                        &mut live_count,
+                        |live_count, record_id, _| {
+                            if check_deleted_condition(record_id, deleted_vectors, deleted_points) {
+                                *live_count += 1;
+                            }
+                        },
+                    );
+                    live_count
+                });
+        }
+    }
+
     fn get_query_cardinality(&self, filter: &Filter) -> CardinalityEstimation {
         let vector_storage = self.vector_storage.borrow();
         let id_tracker = self.id_tracker.borrow();
@@ -217,6 +351,25 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         top: usize,
         is_stopped: &AtomicBool,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        self.search_plain_in(
+            &self.inverted_index,
+            sparse_vector,
+            filter,
+            top,
+            is_stopped,
+            prefiltered_points,
+        )
+    }
+
+    fn search_plain_in(
+        &self,
+        inverted_index: &impl InvertedIndex,
+        sparse_vector: &SparseVector,
+        filter: &Filter,
+        top: usize,
+        is_stopped: &AtomicBool,
+        prefiltered_points: &mut Option<Vec<PointOffsetType>>,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let vector_storage = self.vector_storage.borrow();
         let id_tracker = self.id_tracker.borrow();
@@ -242,7 +395,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let mut search_context = SearchContext::new(
             sparse_vector,
             top,
-            &self.inverted_index,
+            inverted_index,
             memory_handle,
             is_stopped,
         );
@@ -252,6 +405,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
     // search using sparse vector inverted index
     fn search_sparse(
         &self,
+        inverted_index: &impl InvertedIndex,
         sparse_vector: &SparseVector,
         filter: Option<&Filter>,
         top: usize,
@@ -270,7 +424,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let mut search_context = SearchContext::new(
             sparse_vector,
             top,
-            &self.inverted_index,
+            inverted_index,
             memory_handle,
             is_stopped,
         );
@@ -293,6 +447,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         vector: &SparseVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -302,6 +457,77 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let mut vector = vector.clone();
         vector.sort_by_indices();
 
+        let Some(full_precision_index) = &self.full_precision_index else {
+            return self.search_nearest_query_in(
+                &self.inverted_index,
+                &vector,
+                filter,
+                top,
+                is_stopped,
+                prefiltered_points,
+            );
+        };
+
+        let QuantizationSearchParams {
+            ignore,
+            rescore,
+            oversampling,
+        } = params
+            .and_then(|params| params.quantization)
+            .unwrap_or_default();
+
+        if ignore {
+            return self.search_nearest_query_in(
+                full_precision_index,
+                &vector,
+                filter,
+                top,
+                is_stopped,
+                prefiltered_points,
+            );
+        }
+
+        if !rescore.unwrap_or(true) {
+            return self.search_nearest_query_in(
+                &self.inverted_index,
+                &vector,
+                filter,
+                top,
+                is_stopped,
+                prefiltered_points,
+            );
+        }
+
+        // Select candidates with the quantized index and rescore them with full-precision weights
+        let oversampled_top = oversampling.map_or(top, |oversampling| {
+            ((oversampling * top as f64).ceil() as usize).max(top)
+        });
+        let candidates = self.search_nearest_query_in(
+            &self.inverted_index,
+            &vector,
+            filter,
+            oversampled_top,
+            is_stopped,
+            prefiltered_points,
+        )?;
+        let candidate_ids = candidates.iter().map(|scored| scored.idx).collect_vec();
+
+        let vector = self.indices_tracker.remap_vector(vector);
+        let memory_handle = self.scores_memory_pool.get();
+        let mut search_context =
+            SearchContext::new(vector, top, full_precision_index, memory_handle, is_stopped);
+        Ok(search_context.plain_search(&candidate_ids))
+    }
+
+    fn search_nearest_query_in(
+        &self,
+        inverted_index: &impl InvertedIndex,
+        vector: &SparseVector,
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+        prefiltered_points: &mut Option<Vec<PointOffsetType>>,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
         match filter {
             Some(filter) => {
                 // if cardinality is small - use plain search
@@ -313,16 +539,23 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 if query_cardinality.max < threshold {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    self.search_plain(&vector, filter, top, is_stopped, prefiltered_points)
+                    self.search_plain_in(
+                        inverted_index,
+                        vector,
+                        filter,
+                        top,
+                        is_stopped,
+                        prefiltered_points,
+                    )
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse);
-                    self.search_sparse(&vector, Some(filter), top, is_stopped)
+                    self.search_sparse(inverted_index, vector, Some(filter), top, is_stopped)
                 }
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
-                self.search_sparse(&vector, filter, top, is_stopped)
+                self.search_sparse(inverted_index, vector, filter, top, is_stopped)
             }
         }
     }
@@ -332,6 +565,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         query_vector: &QueryVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -344,6 +578,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 vector.try_into()?,
                 filter,
                 top,
+                params,
                 is_stopped,
                 prefiltered_points,
             ),
@@ -365,15 +600,21 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
-        _params: Option<&SearchParams>,
+        params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let mut results = Vec::with_capacity(vectors.len());
         let mut prefiltered_points = None;
         for vector in vectors {
             check_process_stopped(is_stopped)?;
-            let search_results =
-                self.search_query(vector, filter, top, is_stopped, &mut prefiltered_points)?;
+            let search_results = self.search_query(
+                vector,
+                filter,
+                top,
+                params,
+                is_stopped,
+                &mut prefiltered_points,
+            )?;
             results.push(search_results);
         }
         Ok(results)
@@ -384,14 +625,16 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
         _permit: Arc<CpuPermit>,
         stopped: &AtomicBool,
     ) -> OperationResult<()> {
-        let (inverted_index, indices_tracker) = Self::build_inverted_index(
+        let (inverted_index, full_precision_index, indices_tracker) = Self::build_inverted_index(
             self.id_tracker.clone(),
             self.vector_storage.clone(),
             &self.path,
+            !self.is_appendable && self.config.has_full_precision_index(),
             stopped,
         )?;
 
         self.inverted_index = inverted_index;
+        self.full_precision_index = full_precision_index;
         self.indices_tracker = indices_tracker;
 
         // save inverted index
@@ -424,6 +667,11 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
 
         all_files.push(config_file);
         all_files.extend_from_slice(&TInvertedIndex::files(&self.path));
+        if self.full_precision_index.is_some() {
+            all_files.extend(InvertedIndexMmap::files(&Self::full_precision_path(
+                &self.path,
+            )));
+        }
         all_files
     }
 
@@ -445,6 +693,18 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
             let vector = self.indices_tracker.remap_vector(vector.to_owned());
             self.inverted_index.upsert(id, vector);
         }
+        self.updates_count += 1;
         Ok(())
     }
+
+    fn delete_vector(&mut self, _id: PointOffsetType) -> OperationResult<()> {
+        // Deleted vectors are skipped on search
+        self.updates_count += 1;
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        // Mutable index is rebuilt from the vector storage on load
+        Box::new(|| Ok(()))
+    }
 }
@@ -308,6 +308,9 @@ impl StructPayloadIndex {
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
+            Condition::CustomIdChecker(_) => {
+                CardinalityEstimation::unknown(self.available_point_count())
+            }
         }
     }
 
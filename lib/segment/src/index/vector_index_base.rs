@@ -4,7 +4,14 @@ use std::sync::Arc;
 
 use common::cpu::CpuPermit;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
-use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
+use half::f16;
+use sparse::common::types::QuantizedU8;
+use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
+use sparse::index::inverted_index::inverted_index_mmap::{
+    GenericInvertedIndexMmap, InvertedIndexMmap,
+};
+use sparse::index::inverted_index::inverted_index_quantized_ram::InvertedIndexQuantizedRam;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 
 use super::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
@@ -12,6 +19,7 @@ use super::hnsw_index::hnsw::HNSWIndex;
 use super::plain_payload_index::PlainIndex;
 use super::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::{Filter, SearchParams};
@@ -40,6 +48,12 @@ pub trait VectorIndex {
 
     /// Update index for a single vector
     fn update_vector(&mut self, id: PointOffsetType, vector: VectorRef) -> OperationResult<()>;
+
+    /// Remove a single vector from the index
+    fn delete_vector(&mut self, id: PointOffsetType) -> OperationResult<()>;
+
+    /// Persist in-place updates of the index
+    fn flusher(&self) -> Flusher;
 }
 
 pub enum VectorIndexEnum {
@@ -48,6 +62,18 @@ pub enum VectorIndexEnum {
     HnswMmap(HNSWIndex<GraphLinksMmap>),
     SparseRam(SparseVectorIndex<InvertedIndexRam>),
     SparseMmap(SparseVectorIndex<InvertedIndexMmap>),
+    SparseRamF16(SparseVectorIndex<InvertedIndexQuantizedRam<f16>>),
+    SparseRamU8(SparseVectorIndex<InvertedIndexQuantizedRam<QuantizedU8>>),
+    SparseMmapF16(SparseVectorIndex<GenericInvertedIndexMmap<f16>>),
+    SparseMmapU8(SparseVectorIndex<GenericInvertedIndexMmap<QuantizedU8>>),
+    SparseCompressedImmutableRamF32(SparseVectorIndex<InvertedIndexCompressedImmutableRam<f32>>),
+    SparseCompressedImmutableRamF16(SparseVectorIndex<InvertedIndexCompressedImmutableRam<f16>>),
+    SparseCompressedImmutableRamU8(
+        SparseVectorIndex<InvertedIndexCompressedImmutableRam<QuantizedU8>>,
+    ),
+    SparseCompressedMmapF32(SparseVectorIndex<InvertedIndexCompressedMmap<f32>>),
+    SparseCompressedMmapF16(SparseVectorIndex<InvertedIndexCompressedMmap<f16>>),
+    SparseCompressedMmapU8(SparseVectorIndex<InvertedIndexCompressedMmap<QuantizedU8>>),
 }
 
 impl VectorIndexEnum {
@@ -58,6 +84,37 @@ impl VectorIndexEnum {
             Self::HnswMmap(_) => true,
             Self::SparseRam(_) => true,
             Self::SparseMmap(_) => true,
+            Self::SparseRamF16(_) => true,
+            Self::SparseRamU8(_) => true,
+            Self::SparseMmapF16(_) => true,
+            Self::SparseMmapU8(_) => true,
+            Self::SparseCompressedImmutableRamF32(_) => true,
+            Self::SparseCompressedImmutableRamF16(_) => true,
+            Self::SparseCompressedImmutableRamU8(_) => true,
+            Self::SparseCompressedMmapF32(_) => true,
+            Self::SparseCompressedMmapF16(_) => true,
+            Self::SparseCompressedMmapU8(_) => true,
+        }
+    }
+
+    /// Number of vectors linked into the HNSW graph in place, which lack payload index links
+    pub fn in_place_linked_count(&self) -> usize {
+        match self {
+            Self::HnswRam(index) => index.in_place_linked_count(),
+            Self::HnswMmap(index) => index.in_place_linked_count(),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseRamF16(_)
+            | Self::SparseRamU8(_)
+            | Self::SparseMmapF16(_)
+            | Self::SparseMmapU8(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedImmutableRamU8(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_)
+            | Self::SparseCompressedMmapU8(_) => 0,
         }
     }
 }
@@ -85,6 +142,36 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseMmap(index) => {
                 index.search(vectors, filter, top, params, is_stopped)
             }
+            VectorIndexEnum::SparseRamF16(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseRamU8(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseMmapF16(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseMmapU8(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedMmapF16(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::SparseCompressedMmapU8(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
         }
     }
 
@@ -95,6 +182,22 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => index.build_index(permit, stopped),
             VectorIndexEnum::SparseRam(index) => index.build_index(permit, stopped),
             VectorIndexEnum::SparseMmap(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseRamF16(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseRamU8(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseMmapF16(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseMmapU8(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.build_index(permit, stopped)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.build_index(permit, stopped)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.build_index(permit, stopped)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.build_index(permit, stopped),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.build_index(permit, stopped),
         }
     }
 
@@ -105,6 +208,22 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseMmap(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseRamF16(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseRamU8(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseMmapF16(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseMmapU8(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.get_telemetry_data(detail)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.get_telemetry_data(detail),
         }
     }
 
@@ -115,6 +234,16 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => index.files(),
             VectorIndexEnum::SparseRam(index) => index.files(),
             VectorIndexEnum::SparseMmap(index) => index.files(),
+            VectorIndexEnum::SparseRamF16(index) => index.files(),
+            VectorIndexEnum::SparseRamU8(index) => index.files(),
+            VectorIndexEnum::SparseMmapF16(index) => index.files(),
+            VectorIndexEnum::SparseMmapU8(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => index.files(),
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.files(),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.files(),
         }
     }
 
@@ -125,6 +254,16 @@ impl VectorIndex for VectorIndexEnum {
             Self::HnswMmap(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.indexed_vector_count(),
             Self::SparseMmap(index) => index.indexed_vector_count(),
+            Self::SparseRamF16(index) => index.indexed_vector_count(),
+            Self::SparseRamU8(index) => index.indexed_vector_count(),
+            Self::SparseMmapF16(index) => index.indexed_vector_count(),
+            Self::SparseMmapU8(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamF32(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamF16(index) => index.indexed_vector_count(),
+            Self::SparseCompressedImmutableRamU8(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapF32(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapF16(index) => index.indexed_vector_count(),
+            Self::SparseCompressedMmapU8(index) => index.indexed_vector_count(),
         }
     }
 
@@ -135,6 +274,56 @@ impl VectorIndex for VectorIndexEnum {
             Self::HnswMmap(index) => index.update_vector(id, vector),
             Self::SparseRam(index) => index.update_vector(id, vector),
             Self::SparseMmap(index) => index.update_vector(id, vector),
+            Self::SparseRamF16(index) => index.update_vector(id, vector),
+            Self::SparseRamU8(index) => index.update_vector(id, vector),
+            Self::SparseMmapF16(index) => index.update_vector(id, vector),
+            Self::SparseMmapU8(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamF32(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamF16(index) => index.update_vector(id, vector),
+            Self::SparseCompressedImmutableRamU8(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapF32(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapF16(index) => index.update_vector(id, vector),
+            Self::SparseCompressedMmapU8(index) => index.update_vector(id, vector),
+        }
+    }
+
+    fn delete_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            Self::Plain(index) => index.delete_vector(id),
+            Self::HnswRam(index) => index.delete_vector(id),
+            Self::HnswMmap(index) => index.delete_vector(id),
+            Self::SparseRam(index) => index.delete_vector(id),
+            Self::SparseMmap(index) => index.delete_vector(id),
+            Self::SparseRamF16(index) => index.delete_vector(id),
+            Self::SparseRamU8(index) => index.delete_vector(id),
+            Self::SparseMmapF16(index) => index.delete_vector(id),
+            Self::SparseMmapU8(index) => index.delete_vector(id),
+            Self::SparseCompressedImmutableRamF32(index) => index.delete_vector(id),
+            Self::SparseCompressedImmutableRamF16(index) => index.delete_vector(id),
+            Self::SparseCompressedImmutableRamU8(index) => index.delete_vector(id),
+            Self::SparseCompressedMmapF32(index) => index.delete_vector(id),
+            Self::SparseCompressedMmapF16(index) => index.delete_vector(id),
+            Self::SparseCompressedMmapU8(index) => index.delete_vector(id),
+        }
+    }
+
+    fn flusher(&self) -> Flusher {
+        match self {
+            Self::Plain(index) => index.flusher(),
+            Self::HnswRam(index) => index.flusher(),
+            Self::HnswMmap(index) => index.flusher(),
+            Self::SparseRam(index) => index.flusher(),
+            Self::SparseMmap(index) => index.flusher(),
+            Self::SparseRamF16(index) => index.flusher(),
+            Self::SparseRamU8(index) => index.flusher(),
+            Self::SparseMmapF16(index) => index.flusher(),
+            Self::SparseMmapU8(index) => index.flusher(),
+            Self::SparseCompressedImmutableRamF32(index) => index.flusher(),
+            Self::SparseCompressedImmutableRamF16(index) => index.flusher(),
+            Self::SparseCompressedImmutableRamU8(index) => index.flusher(),
+            Self::SparseCompressedMmapF32(index) => index.flusher(),
+            Self::SparseCompressedMmapF16(index) => index.flusher(),
+            Self::SparseCompressedMmapU8(index) => index.flusher(),
         }
     }
 }
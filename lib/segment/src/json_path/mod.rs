@@ -11,7 +11,7 @@ mod string;
 mod v2;
 
 pub use string::JsonPathString;
-pub use v2::JsonPathV2;
+pub use v2::{JsonPathItem, JsonPathV2};
 
 pub type JsonPath = JsonPathV2;
 
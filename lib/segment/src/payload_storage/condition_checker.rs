@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
+    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchPhrase, MatchText, MatchValue, Range,
     RangeInterface, ValueVariants, ValuesCount,
 };
 
@@ -91,6 +91,10 @@ impl ValueChecker for Match {
                 Value::String(stored) => stored.contains(text),
                 _ => false,
             },
+            Match::Phrase(MatchPhrase { phrase }) => match payload {
+                Value::String(stored) => stored.contains(phrase),
+                _ => false,
+            },
             Match::Any(MatchAny { any }) => match (payload, any) {
                 (Value::String(stored), AnyVariants::Keywords(list)) => {
                     if list.len() < INDEXSET_ITER_THRESHOLD {
@@ -128,6 +128,9 @@ where
         Condition::HasId(has_id) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
+        Condition::CustomIdChecker(checker) => id_tracker
+            .and_then(|id_tracker| id_tracker.external_id(point_id))
+            .map_or(false, |id| checker.check(id)),
         Condition::Nested(nested) => {
             let nested_path = nested.array_key();
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
@@ -7,11 +7,13 @@ use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
 use atomic_refcell::AtomicRefCell;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
 use io::file_operations::{atomic_save_json, read_json};
 use itertools::Either;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use rocksdb::DB;
 use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
@@ -25,22 +27,29 @@ use crate::common::version::{StorageVersion, VERSION_FILE};
 use crate::common::{
     check_named_vectors, check_query_vectors, check_stopped, check_vector, check_vector_name,
 };
+use crate::data_types::facets::{FacetCounts, FacetParams};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{Direction, OrderBy, OrderingValue};
-use crate::data_types::vectors::{QueryVector, Vector};
+use crate::data_types::order_by::{Direction, MultiOrderBy, OrderedPoint, OrderingValue};
+use crate::data_types::sample::{self, SampledPoint};
+use crate::data_types::sparse_statistics::SparseVectorStatistics;
+use crate::data_types::vectors::{MultiDenseVector, QueryVector, Vector};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
+
 use crate::index::field_index::numeric_index::StreamRange;
-use crate::index::field_index::CardinalityEstimation;
+use crate::index::field_index::{CardinalityEstimation, OrderableFieldIndex};
+use crate::index::query_optimization::rescore_formula::formula_scorer::FormulaScorer;
+use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
-use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
+use crate::spaces::tools::peek_top_smallest_iterable;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    Condition, FieldCondition, Filter, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentState, SegmentType, SeqNumberType, VectorDataInfo,
+    WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
@@ -109,6 +118,8 @@ impl VectorData {
 
         let storage_task = match &*self.vector_storage.borrow() {
             VectorStorageEnum::DenseMemmap(storage) => storage.prefault_mmap_pages(),
+            VectorStorageEnum::DenseMemmapByte(storage) => storage.prefault_mmap_pages(),
+            VectorStorageEnum::DenseMemmapHalf(storage) => storage.prefault_mmap_pages(),
             _ => None,
         };
 
@@ -141,15 +152,21 @@ impl Segment {
             let vector = vectors.get(vector_name);
             match vector {
                 Some(vector) => {
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    vector_storage.insert_vector(internal_id, vector)?;
+                    vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .insert_vector(internal_id, vector)?;
                     let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(internal_id, vector)?;
                 }
                 None => {
                     // No vector provided, so we remove it
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    vector_storage.delete_vector(internal_id)?;
+                    vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .delete_vector(internal_id)?;
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
+                    vector_index.delete_vector(internal_id)?;
                 }
             }
         }
@@ -206,23 +223,36 @@ impl Segment {
         let new_index = self.id_tracker.borrow().total_point_count() as PointOffsetType;
         for (vector_name, vector_data) in self.vector_data.iter_mut() {
             let vector_opt = vectors.get(vector_name);
+            // Vector storage must be released before updating the index, as index may read it
             let mut vector_storage = vector_data.vector_storage.borrow_mut();
-            let mut vector_index = vector_data.vector_index.borrow_mut();
             match vector_opt {
                 None => {
                     let dim = vector_storage.vector_dim();
                     let vector: Vector = match *vector_storage {
                         VectorStorageEnum::DenseSimple(_)
+                        | VectorStorageEnum::DenseSimpleByte(_)
+                        | VectorStorageEnum::DenseSimpleHalf(_)
                         | VectorStorageEnum::DenseMemmap(_)
-                        | VectorStorageEnum::DenseAppendableMemmap(_) => vec![1.0; dim].into(),
+                        | VectorStorageEnum::DenseMemmapByte(_)
+                        | VectorStorageEnum::DenseMemmapHalf(_)
+                        | VectorStorageEnum::DenseAppendableMemmap(_)
+                        | VectorStorageEnum::DenseAppendableMemmapByte(_)
+                        | VectorStorageEnum::DenseAppendableMemmapHalf(_) => vec![1.0; dim].into(),
                         VectorStorageEnum::SparseSimple(_) => SparseVector::default().into(),
+                        VectorStorageEnum::MultiDenseSimple(_) => {
+                            MultiDenseVector::new(vec![1.0; dim], dim).into()
+                        }
                     };
                     vector_storage.insert_vector(new_index, vector.to_vec_ref())?;
                     vector_storage.delete_vector(new_index)?;
+                    drop(vector_storage);
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(new_index, vector.to_vec_ref())?;
                 }
                 Some(vec) => {
                     vector_storage.insert_vector(new_index, vec)?;
+                    drop(vector_storage);
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
                     vector_index.update_vector(new_index, vec)?;
                 }
             }
@@ -711,65 +741,6 @@ impl Segment {
         page
     }
 
-    pub fn filtered_read_by_index_ordered(
-        &self,
-        order_by: &OrderBy,
-        limit: Option<usize>,
-        condition: &Filter,
-    ) -> OperationResult<Vec<(OrderingValue, PointIdType)>> {
-        let payload_index = self.payload_index.borrow();
-        let id_tracker = self.id_tracker.borrow();
-
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::ValidationError { description: "There is no range index for the `order_by` key, please create one to use `order_by`".to_string() })?;
-
-        let start_from = order_by.start_from();
-
-        let values_ids_iterator = payload_index
-            .query_points(condition)
-            .into_iter()
-            .flat_map(|internal_id| {
-                // Repeat a point for as many values as it has
-                numeric_index
-                    .get_ordering_values(internal_id)
-                    // But only those which start from `start_from` 😛
-                    .filter(|value| match order_by.direction() {
-                        Direction::Asc => value >= &start_from,
-                        Direction::Desc => value <= &start_from,
-                    })
-                    .map(move |ordering_value| (ordering_value, internal_id))
-            })
-            .filter_map(|(value, internal_id)| {
-                id_tracker
-                    .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
-            });
-
-        let page = match order_by.direction() {
-            Direction::Asc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_smallest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_a.cmp(value_b));
-                page
-            }
-            Direction::Desc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_largest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
-                page
-            }
-        };
-
-        Ok(page)
-    }
-
     pub fn filtered_read_by_id_stream(
         &self,
         offset: Option<PointIdType>,
@@ -787,50 +758,277 @@ impl Segment {
             .collect()
     }
 
-    pub fn filtered_read_by_value_stream(
+    fn orderable_index<'a>(
+        payload_index: &'a StructPayloadIndex,
+        key: &JsonPath,
+    ) -> OperationResult<OrderableFieldIndex<'a>> {
+        payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_orderable()))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!("There is no range or keyword index for the `order_by` key `{key}`, please create one to use `order_by`"),
+            })
+    }
+
+    /// Read points ordered by all `order_by` keys.
+    ///
+    /// Candidates come from the index of the primary key: either as a stream sorted by the primary
+    /// value, or unsorted, if checking filtered points is cheaper or the key is not numeric.
+    /// A sorted stream is read until the page is full and all ties of the last primary value are
+    /// seen, so that secondary keys and point ids can break them.
+    pub fn filtered_read_ordered(
         &self,
-        order_by: &OrderBy,
+        order_by: &MultiOrderBy,
         limit: Option<usize>,
         filter: Option<&Filter>,
-    ) -> OperationResult<Vec<(OrderingValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
 
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::ValidationError { description: "There is no range index for the `order_by` key, please create one to use `order_by`".to_string() })?;
+        let primary = order_by.primary();
+        let direction = primary.direction();
+        let primary_index = Self::orderable_index(&payload_index, &primary.key)?;
+        let secondary_indexes = order_by
+            .secondary()
+            .iter()
+            .map(|key| Self::orderable_index(&payload_index, &key.key))
+            .collect::<OperationResult<Vec<_>>>()?;
 
-        let range_iter = numeric_index.stream_range(&order_by.as_range());
+        let start_from = order_by.start_from();
+        let limit = limit.unwrap_or(usize::MAX);
 
-        let directed_range_iter = match order_by.direction() {
-            Direction::Asc => Either::Left(range_iter),
-            Direction::Desc => Either::Right(range_iter.rev()),
+        let stream_numeric_index = match &primary_index {
+            OrderableFieldIndex::NumericIndex(numeric_index) => filter
+                .map_or(true, |filter| !self.should_pre_filter(filter, Some(limit)))
+                .then_some(numeric_index),
+            OrderableFieldIndex::KeywordIndex(_) => None,
+        };
+        let is_sorted = stream_numeric_index.is_some();
+
+        let candidates = match stream_numeric_index {
+            Some(numeric_index) => {
+                let range_iter =
+                    numeric_index.stream_range(&direction.as_numeric_range(start_from.as_ref()));
+                let directed_range_iter = match direction {
+                    Direction::Asc => Either::Left(range_iter),
+                    Direction::Desc => Either::Right(range_iter.rev()),
+                };
+                let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+                Either::Left(directed_range_iter.filter(move |(_, internal_id)| {
+                    filter_context
+                        .as_ref()
+                        .map_or(true, |filter_context| filter_context.check(*internal_id))
+                }))
+            }
+            None => {
+                let points = match filter {
+                    Some(filter) => Either::Left(payload_index.query_points(filter).into_iter()),
+                    None => Either::Right(id_tracker.iter_ids()),
+                };
+                // Repeat a point for as many values as it has
+                Either::Right(points.flat_map(|internal_id| {
+                    primary_index
+                        .get_ordering_values(internal_id)
+                        .map(move |value| (value, internal_id))
+                }))
+            }
         };
 
-        let id_tracker = self.id_tracker.borrow();
+        let mut page: Vec<OrderedPoint> = Vec::new();
+        let mut last_value: Option<OrderingValue> = None;
 
-        let filtered_iter = match filter {
-            None => Either::Left(directed_range_iter),
-            Some(filter) => {
-                let filter_context = payload_index.filter_context(filter);
+        for (value, internal_id) in candidates {
+            if let Some(start_from) = &start_from {
+                if !value.starts_from(start_from, direction) {
+                    continue;
+                }
+            }
 
-                Either::Right(
-                    directed_range_iter
-                        .filter(move |(_, internal_id)| filter_context.check(*internal_id)),
-                )
+            if is_sorted && page.len() >= limit && last_value.as_ref() != Some(&value) {
+                break;
             }
-        };
 
-        let reads = filtered_iter
-            .filter_map(|(value, internal_id)| {
-                id_tracker
-                    .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
-            })
-            .take(limit.unwrap_or(usize::MAX))
-            .collect();
-        Ok(reads)
+            let Some(id) = id_tracker.external_id(internal_id) else {
+                continue;
+            };
+
+            let values =
+                std::iter::once(Some(value.clone()))
+                    .chain(order_by.secondary().iter().zip(&secondary_indexes).map(
+                        |(key, index)| key.select_value(index.get_ordering_values(internal_id)),
+                    ))
+                    .collect();
+            let point = OrderedPoint { values, id };
+
+            if !order_by.is_after_cursor(&point) {
+                continue;
+            }
+
+            last_value = Some(value);
+            page.push(point);
+
+            // Keep only the best points, if there are many ties or candidates are not sorted
+            if page.len() >= limit.saturating_mul(2) {
+                page.sort_unstable_by(|a, b| order_by.compare(a, b));
+                page.truncate(limit);
+            }
+        }
+
+        page.sort_unstable_by(|a, b| order_by.compare(a, b));
+        page.truncate(limit);
+
+        Ok(page)
+    }
+
+    fn facet_counts(
+        &self,
+        params: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<FacetCounts> {
+        let payload_index = self.payload_index.borrow();
+        let field_indexes = payload_index.field_indexes.get(&params.key);
+
+        let mut counts = FacetCounts::default();
+
+        if !params.ranges.is_empty() {
+            let numeric_index = field_indexes
+                .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
+                .ok_or_else(|| OperationError::ValidationError { description: "There is no range index for the facet key, please create one to count points in ranges".to_string() })?;
+
+            if params.exact {
+                counts.ranges = vec![0; params.ranges.len()];
+                for internal_id in self.filtered_points(&payload_index, params.filter.as_ref()) {
+                    check_stopped(is_stopped)?;
+                    let values: Vec<_> = numeric_index
+                        .get_ordering_values(internal_id)
+                        .filter_map(|value| value.as_float())
+                        .collect();
+                    for (range, count) in params.ranges.iter().zip(counts.ranges.iter_mut()) {
+                        // Count the point once per range, even if multiple values fall into it
+                        if values.iter().any(|value| range.check_range(*value)) {
+                            *count += 1;
+                        }
+                    }
+                }
+            } else {
+                // Estimation of the range condition is based on the histogram of the numeric index
+                counts.ranges = params
+                    .ranges
+                    .iter()
+                    .map(|range| {
+                        let condition =
+                            FieldCondition::new_range(params.key.clone(), range.clone());
+                        Self::facet_estimate(&payload_index, params.filter.as_ref(), condition)
+                    })
+                    .collect();
+            }
+
+            return Ok(counts);
+        }
+
+        let facet_index = field_indexes
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_facet_index()))
+            .ok_or_else(|| OperationError::ValidationError {
+                description: "There is no keyword, integer, bool or uuid index for the facet key, \
+                              please create one to use facets"
+                    .to_string(),
+            })?;
+
+        match (&params.filter, params.exact) {
+            // Posting lists of the index are exact without a filter
+            (None, _) => counts.values.extend(facet_index.iter_values_counts()),
+            (Some(filter), true) => {
+                for internal_id in self.filtered_points(&payload_index, Some(filter)) {
+                    check_stopped(is_stopped)?;
+                    for value in facet_index.get_point_values(internal_id) {
+                        *counts.values.entry(value).or_default() += 1;
+                    }
+                }
+            }
+            (Some(filter), false) => {
+                for (value, _) in facet_index.iter_values_counts() {
+                    check_stopped(is_stopped)?;
+                    let condition =
+                        FieldCondition::new_match(params.key.clone(), value.clone().into());
+                    let count = Self::facet_estimate(&payload_index, Some(filter), condition);
+                    counts.values.insert(value, count);
+                }
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Internal ids of all available points which satisfy the filter
+    fn filtered_points<'a>(
+        &'a self,
+        payload_index: &'a StructPayloadIndex,
+        filter: Option<&Filter>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+        match filter {
+            None => {
+                let ids: Vec<_> = self.id_tracker.borrow().iter_ids().collect();
+                Box::new(ids.into_iter())
+            }
+            Some(filter) => Box::new(payload_index.query_points(filter).into_iter()),
+        }
+    }
+
+    /// Pick `limit` random points which satisfy the filter by probing random internal ids.
+    ///
+    /// Cheaper than reading all matching points if they make up a large enough part of the
+    /// segment, `expected_matches` is the estimation of their number. Returns `None` if probing
+    /// is not worth it or did not find enough points.
+    fn probe_random_points(
+        &self,
+        payload_index: &StructPayloadIndex,
+        filter: Option<&Filter>,
+        limit: usize,
+        expected_matches: usize,
+        rng: &mut impl Rng,
+    ) -> Option<Vec<PointOffsetType>> {
+        let id_tracker = self.id_tracker.borrow();
+        let total = id_tracker.total_point_count();
+        let expected_probes = limit.saturating_mul(total) / expected_matches.max(1);
+        if total == 0 || expected_matches == 0 || expected_probes > expected_matches {
+            return None;
+        }
+
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let mut probed = HashSet::new();
+        let mut points = Vec::with_capacity(limit);
+        // Estimation might be too optimistic, give up instead of probing for too long
+        for _ in 0..expected_probes.saturating_mul(4) {
+            let internal_id = rng.gen_range(0..total) as PointOffsetType;
+            if !probed.insert(internal_id) || id_tracker.is_deleted_point(internal_id) {
+                continue;
+            }
+            if filter_context
+                .as_ref()
+                .map_or(true, |context| context.check(internal_id))
+            {
+                points.push(internal_id);
+                if points.len() == limit {
+                    return Some(points);
+                }
+            }
+        }
+        None
+    }
+
+    /// Expected number of points which satisfy both the filter and the condition
+    fn facet_estimate(
+        payload_index: &StructPayloadIndex,
+        filter: Option<&Filter>,
+        condition: FieldCondition,
+    ) -> usize {
+        let condition_filter = Filter::new_must(Condition::Field(condition));
+        let filter = match filter {
+            None => condition_filter,
+            Some(filter) => condition_filter.merge(filter),
+        };
+        payload_index.estimate_cardinality(&filter).exp
     }
 
     /// Check consistency of the segment's data and repair it if possible.
@@ -982,7 +1180,12 @@ impl SegmentEntry for Segment {
     ) -> OperationResult<bool> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| {
+            (
+                self.segment_config.distance(name).unwrap(),
+                self.segment_config.vector_datatype(name),
+            )
+        });
         let stored_internal_point = self.id_tracker.borrow().internal_id(point_id);
         self.handle_version_and_failure(op_num, stored_internal_point, |segment| {
             if let Some(existing_internal_id) = stored_internal_point {
@@ -1010,6 +1213,12 @@ impl SegmentEntry for Segment {
                     segment.payload_index.borrow_mut().drop(internal_id)?;
                     segment.id_tracker.borrow_mut().drop(point_id)?;
 
+                    // Unlink point from vector indices, which are updated in place
+                    for vector_data in segment.vector_data.values() {
+                        let mut vector_index = vector_data.vector_index.borrow_mut();
+                        vector_index.delete_vector(internal_id)?;
+                    }
+
                     // Before, we propagated point deletions to also delete its vectors. This turns
                     // out to be problematic because this sometimes makes us loose vector data
                     // because we cannot control the order of segment flushes.
@@ -1035,7 +1244,12 @@ impl SegmentEntry for Segment {
         mut vectors: NamedVectors,
     ) -> OperationResult<bool> {
         check_named_vectors(&vectors, &self.segment_config)?;
-        vectors.preprocess(|name| self.segment_config.distance(name).unwrap());
+        vectors.preprocess(|name| {
+            (
+                self.segment_config.distance(name).unwrap(),
+                self.segment_config.vector_datatype(name),
+            )
+        });
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
             None => Err(OperationError::PointIdError {
@@ -1069,8 +1283,12 @@ impl SegmentEntry for Segment {
                             received_name: vector_name.to_string(),
                         },
                     )?;
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    let is_deleted = vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .delete_vector(internal_id)?;
+                    let mut vector_index = vector_data.vector_index.borrow_mut();
+                    vector_index.delete_vector(internal_id)?;
                     Ok((is_deleted, Some(internal_id)))
                 })
             }
@@ -1210,18 +1428,111 @@ impl SegmentEntry for Segment {
         &'a self,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-        order_by: &'a OrderBy,
-    ) -> OperationResult<Vec<(OrderingValue, PointIdType)>> {
-        match filter {
-            None => self.filtered_read_by_value_stream(order_by, limit, None),
-            Some(filter) => {
-                if self.should_pre_filter(filter, limit) {
-                    self.filtered_read_by_index_ordered(order_by, limit, filter)
-                } else {
-                    self.filtered_read_by_value_stream(order_by, limit, Some(filter))
-                }
+        order_by: &'a MultiOrderBy,
+    ) -> OperationResult<Vec<OrderedPoint>> {
+        self.filtered_read_ordered(order_by, limit, filter)
+    }
+
+    fn facet(&self, params: &FacetParams, is_stopped: &AtomicBool) -> OperationResult<FacetCounts> {
+        self.facet_counts(params, is_stopped)
+    }
+
+    fn read_random_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        seed: u64,
+    ) -> Vec<SampledPoint> {
+        if limit == 0 {
+            return vec![];
+        }
+        let mut rng = sample::source_rng(seed, &self.current_path);
+        let cardinality = self.estimate_point_count(filter);
+
+        let payload_index = self.payload_index.borrow();
+        let probed = self
+            .probe_random_points(&payload_index, filter, limit, cardinality.exp, &mut rng)
+            .map(|points| (points, cardinality.exp));
+        let (points, matched) = probed.unwrap_or_else(|| {
+            let mut candidates: Vec<_> = self.filtered_points(&payload_index, filter).collect();
+            let matched = candidates.len();
+            let (picked, _) = candidates.partial_shuffle(&mut rng, limit);
+            (picked.to_vec(), matched)
+        });
+
+        let keys = sample::sampling_keys(&mut rng, matched.max(points.len()), points.len());
+        let id_tracker = self.id_tracker.borrow();
+        points
+            .into_iter()
+            .zip(keys)
+            .filter_map(|(internal_id, key)| {
+                Some(SampledPoint {
+                    id: id_tracker.external_id(internal_id)?,
+                    version: id_tracker.internal_version(internal_id).unwrap_or(0),
+                    key,
+                })
+            })
+            .collect()
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_scores: &[HashMap<PointIdType, ScoreType>],
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+
+        let condition_filters: Vec<_> = formula
+            .conditions
+            .iter()
+            .map(|condition| Filter::new_must(condition.clone()))
+            .collect();
+        let condition_contexts: Vec<_> = condition_filters
+            .iter()
+            .map(|filter| payload_index.filter_context(filter))
+            .collect();
+
+        let candidates: HashSet<_> = prefetches_scores
+            .iter()
+            .flat_map(|scores| scores.keys().copied())
+            .collect();
+        let mut scored_points = Vec::with_capacity(candidates.len());
+        for point_id in candidates {
+            check_stopped(is_stopped)?;
+            let Some(internal_id) = id_tracker.internal_id(point_id) else {
+                continue;
+            };
+            if id_tracker.is_deleted_point(internal_id) {
+                continue;
             }
+
+            let payload = if formula.payload_vars.is_empty() {
+                Payload::default()
+            } else {
+                payload_index.payload(internal_id)?
+            };
+            let scores = prefetches_scores
+                .iter()
+                .map(|scores| scores.get(&point_id).copied())
+                .collect();
+            let conditions = condition_contexts
+                .iter()
+                .map(|context| context.check(internal_id))
+                .collect();
+            let score = FormulaScorer::new(formula, scores, &payload, conditions).score()?;
+
+            scored_points.push(ScoredPoint {
+                id: point_id,
+                version: id_tracker.internal_version(internal_id).unwrap_or(0),
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+            });
         }
+        Ok(scored_points)
     }
 
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
@@ -1362,6 +1673,11 @@ impl SegmentEntry for Segment {
             .values()
             .map(|v| v.vector_storage.borrow().flusher())
             .collect();
+        let vector_index_flushers: Vec<_> = self
+            .vector_data
+            .values()
+            .map(|v| v.vector_index.borrow().flusher())
+            .collect();
         let state = self.get_state();
         let current_path = self.current_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
@@ -1429,6 +1745,12 @@ impl SegmentEntry for Segment {
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
+            // Vector index is flushed after vectors, it is caught up with the vector storage on load
+            for vector_index_flusher in vector_index_flushers {
+                vector_index_flusher().map_err(|err| {
+                    OperationError::service_error(format!("Failed to flush vector_index: {err}"))
+                })?;
+            }
             // Id Tracker contains versions of points. We need to flush it after vector_storage and payload_index flush.
             // This is because vector_storage and payload_index flush are not atomic.
             // If payload or vector flush fails, we will be able to recover data from WAL.
@@ -1556,6 +1878,38 @@ impl SegmentEntry for Segment {
             .collect()
     }
 
+    fn fill_sparse_vector_statistics(
+        &self,
+        vector_name: &str,
+        statistics: &mut SparseVectorStatistics,
+    ) -> OperationResult<()> {
+        check_vector_name(vector_name, &self.segment_config)?;
+        match &*self.vector_data[vector_name].vector_index.borrow() {
+            VectorIndexEnum::SparseRam(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseMmap(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseRamF16(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseRamU8(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseMmapF16(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseMmapU8(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseCompressedImmutableRamF32(index) => {
+                index.fill_statistics(statistics)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamF16(index) => {
+                index.fill_statistics(statistics)
+            }
+            VectorIndexEnum::SparseCompressedImmutableRamU8(index) => {
+                index.fill_statistics(statistics)
+            }
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.fill_statistics(statistics),
+            VectorIndexEnum::Plain(_)
+            | VectorIndexEnum::HnswRam(_)
+            | VectorIndexEnum::HnswMmap(_) => return Err(OperationError::WrongSparse),
+        }
+        Ok(())
+    }
+
     fn take_snapshot(
         &self,
         temp_path: &Path,
@@ -1771,6 +2125,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1844,6 +2200,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1936,6 +2294,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2031,6 +2391,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2063,6 +2425,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2158,6 +2522,8 @@ mod tests {
                     storage_type: VectorStorageType::Memory,
                     index: Indexes::Plain {},
                     quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2211,6 +2577,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -2221,6 +2589,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
@@ -2317,6 +2687,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
                 (
@@ -2327,6 +2699,8 @@ mod tests {
                         storage_type: VectorStorageType::Memory,
                         index: Indexes::Plain {},
                         quantization_config: None,
+                        multivector_config: None,
+                        datatype: None,
                     },
                 ),
             ]),
@@ -4,14 +4,16 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 
 use super::get_vector_storage_path;
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::hnsw_index::num_rayon_threads;
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
 use crate::types::{Indexes, PayloadFieldSchema, PayloadKeyType, SegmentConfig};
@@ -24,6 +26,18 @@ pub struct SegmentBuilder {
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
     pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    /// Indices of the biggest source segment, their graphs are reused for the new segment
+    source_indices: Option<SourceIndices>,
+}
+
+/// Vector indices of a source segment
+///
+/// Source segments are not modified during optimization, so their indices can be read on build.
+struct SourceIndices {
+    available_point_count: usize,
+    vector_indices: HashMap<String, Arc<AtomicRefCell<VectorIndexEnum>>>,
+    /// Point offsets in the new segment, by point offsets in the source segment
+    old_to_new: Vec<Option<PointOffsetType>>,
 }
 
 impl SegmentBuilder {
@@ -42,6 +56,7 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            source_indices: None,
         })
     }
 
@@ -77,6 +92,19 @@ impl SegmentBuilder {
             .collect();
         let other_payload_index = other.payload_index.borrow();
 
+        // Graph of the biggest segment is reused, so only points of other segments are inserted
+        let other_available_point_count = other.available_point_count();
+        let is_biggest_source = self.source_indices.as_ref().map_or(true, |source| {
+            source.available_point_count < other_available_point_count
+        });
+        let has_graph = other.vector_data.values().any(|vector_data| {
+            matches!(
+                *vector_data.vector_index.borrow(),
+                VectorIndexEnum::HnswRam(_) | VectorIndexEnum::HnswMmap(_),
+            )
+        });
+        let mut old_to_new = Vec::new();
+
         let mut id_tracker = self_segment.id_tracker.borrow_mut();
         let mut vector_storages: HashMap<_, _> = self_segment
             .vector_data
@@ -143,6 +171,7 @@ impl SegmentBuilder {
                         // New point, just insert
                         id_tracker.set_link(external_id, new_internal_id)?;
                         id_tracker.set_internal_version(new_internal_id, other_version)?;
+                        set_mapping(&mut old_to_new, old_internal_id, new_internal_id);
                         let other_payload = other_payload_index.payload(old_internal_id)?;
                         // Propagate payload to new segment
                         if !other_payload.is_empty() {
@@ -159,6 +188,7 @@ impl SegmentBuilder {
                             id_tracker.drop(external_id)?;
                             id_tracker.set_link(external_id, new_internal_id)?;
                             id_tracker.set_internal_version(new_internal_id, other_version)?;
+                            set_mapping(&mut old_to_new, old_internal_id, new_internal_id);
                             payload_index.drop(existing_internal_id)?;
                             let other_payload = other_payload_index.payload(old_internal_id)?;
                             // Propagate payload to new segment
@@ -183,6 +213,20 @@ impl SegmentBuilder {
             self.indexed_fields.insert(field, payload_schema);
         }
 
+        if is_biggest_source && has_graph {
+            self.source_indices = Some(SourceIndices {
+                available_point_count: other_available_point_count,
+                vector_indices: other
+                    .vector_data
+                    .iter()
+                    .map(|(vector_name, vector_data)| {
+                        (vector_name.to_owned(), vector_data.vector_index.clone())
+                    })
+                    .collect(),
+                old_to_new,
+            });
+        }
+
         Ok(true)
     }
 
@@ -206,11 +250,30 @@ impl SegmentBuilder {
 
             Self::update_quantization(&mut segment, stopped)?;
 
-            for vector_data in segment.vector_data.values_mut() {
-                vector_data
-                    .vector_index
-                    .borrow_mut()
-                    .build_index(permit.clone(), stopped)?;
+            let source_indices = self.source_indices.take();
+            for (vector_name, vector_data) in &segment.vector_data {
+                let source_index = source_indices.as_ref().and_then(|source| {
+                    let source_index = source.vector_indices.get(vector_name)?;
+                    Some((source_index.borrow(), source.old_to_new.as_slice()))
+                });
+                let mut vector_index = vector_data.vector_index.borrow_mut();
+                match (&mut *vector_index, source_index) {
+                    (VectorIndexEnum::HnswRam(index), Some((old_index, old_to_new))) => index
+                        .build_index_with_old_graph(
+                            permit.clone(),
+                            stopped,
+                            &old_index,
+                            old_to_new,
+                        )?,
+                    (VectorIndexEnum::HnswMmap(index), Some((old_index, old_to_new))) => index
+                        .build_index_with_old_graph(
+                            permit.clone(),
+                            stopped,
+                            &old_index,
+                            old_to_new,
+                        )?,
+                    (vector_index, _) => vector_index.build_index(permit.clone(), stopped)?,
+                }
             }
 
             // We're done with CPU-intensive tasks, release CPU permit
@@ -275,3 +338,15 @@ impl SegmentBuilder {
         Ok(())
     }
 }
+
+fn set_mapping(
+    old_to_new: &mut Vec<Option<PointOffsetType>>,
+    old_internal_id: PointOffsetType,
+    new_internal_id: PointOffsetType,
+) {
+    let old_internal_id = old_internal_id as usize;
+    if old_to_new.len() <= old_internal_id {
+        old_to_new.resize(old_internal_id + 1, None);
+    }
+    old_to_new[old_internal_id] = Some(new_internal_id);
+}
@@ -10,6 +10,7 @@ use log::info;
 use parking_lot::Mutex;
 use semver::Version;
 use serde::Deserialize;
+use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use uuid::Uuid;
 
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
@@ -30,12 +31,20 @@ use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
-    VectorStorageType,
+    VectorStorageDatatype, VectorStorageType,
+};
+use crate::vector_storage::appendable_mmap_dense_vector_storage::{
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
+    open_appendable_memmap_vector_storage_half,
+};
+use crate::vector_storage::memmap_dense_vector_storage::{
+    open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
 };
-use crate::vector_storage::appendable_mmap_dense_vector_storage::open_appendable_memmap_vector_storage;
-use crate::vector_storage::memmap_dense_vector_storage::open_memmap_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::simple_dense_vector_storage::open_simple_vector_storage;
+use crate::vector_storage::simple_dense_vector_storage::{
+    open_simple_vector_storage, open_simple_vector_storage_byte, open_simple_vector_storage_half,
+};
+use crate::vector_storage::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
 use crate::vector_storage::VectorStorage;
 
@@ -119,11 +128,39 @@ fn create_segment(
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
         // Select suitable vector storage type based on configuration
-        let vector_storage = match vector_config.storage_type {
+        let vector_storage = match (
+            vector_config.multivector_config,
+            vector_config.storage_type,
+            vector_config.datatype.unwrap_or_default(),
+        ) {
+            // Multi-vectors are always kept in memory, regardless of the storage type
+            (Some(multivector_config), _, VectorStorageDatatype::Float32) => {
+                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                open_simple_multi_dense_vector_storage(
+                    database.clone(),
+                    &db_column_name,
+                    vector_config.size,
+                    vector_config.distance,
+                    multivector_config,
+                    stopped,
+                )?
+            }
+            (Some(_), _, datatype) => {
+                return Err(OperationError::ValidationError {
+                    description: format!(
+                        "Multi-vectors only support float32 datatype, got {datatype:?}",
+                    ),
+                });
+            }
             // In memory
-            VectorStorageType::Memory => {
+            (None, VectorStorageType::Memory, datatype) => {
                 let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-                open_simple_vector_storage(
+                let open_fn = match datatype {
+                    VectorStorageDatatype::Float32 => open_simple_vector_storage,
+                    VectorStorageDatatype::Float16 => open_simple_vector_storage_half,
+                    VectorStorageDatatype::Uint8 => open_simple_vector_storage_byte,
+                };
+                open_fn(
                     database.clone(),
                     &db_column_name,
                     vector_config.size,
@@ -132,18 +169,32 @@ fn create_segment(
                 )?
             }
             // Mmap on disk, not appendable
-            VectorStorageType::Mmap => open_memmap_vector_storage(
-                &vector_storage_path,
-                vector_config.size,
-                vector_config.distance,
-            )?,
+            (None, VectorStorageType::Mmap, datatype) => {
+                let open_fn = match datatype {
+                    VectorStorageDatatype::Float32 => open_memmap_vector_storage,
+                    VectorStorageDatatype::Float16 => open_memmap_vector_storage_half,
+                    VectorStorageDatatype::Uint8 => open_memmap_vector_storage_byte,
+                };
+                open_fn(
+                    &vector_storage_path,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
             // Chunked mmap on disk, appendable
-            VectorStorageType::ChunkedMmap => open_appendable_memmap_vector_storage(
-                &vector_storage_path,
-                vector_config.size,
-                vector_config.distance,
-                stopped,
-            )?,
+            (None, VectorStorageType::ChunkedMmap, datatype) => {
+                let open_fn = match datatype {
+                    VectorStorageDatatype::Float32 => open_appendable_memmap_vector_storage,
+                    VectorStorageDatatype::Float16 => open_appendable_memmap_vector_storage_half,
+                    VectorStorageDatatype::Uint8 => open_appendable_memmap_vector_storage_byte,
+                };
+                open_fn(
+                    &vector_storage_path,
+                    vector_config.size,
+                    vector_config.distance,
+                    stopped,
+                )?
+            }
         };
 
         // Warn when number of points between ID tracker and storage differs
@@ -183,6 +234,7 @@ fn create_segment(
                     quantized_vectors.clone(),
                     payload_index.clone(),
                     vector_hnsw_config.clone(),
+                    vector_config.is_appendable(),
                 )?)
             } else {
                 VectorIndexEnum::HnswRam(HNSWIndex::<GraphLinksRam>::open(
@@ -192,6 +244,7 @@ fn create_segment(
                     quantized_vectors.clone(),
                     payload_index.clone(),
                     vector_hnsw_config.clone(),
+                    vector_config.is_appendable(),
                 )?)
             }),
         };
@@ -226,17 +279,9 @@ fn create_segment(
             );
         }
 
-        let vector_index = match sparse_vector_config.index.index_type {
-            SparseIndexType::Mmap => sp(VectorIndexEnum::SparseMmap(SparseVectorIndex::open(
-                sparse_vector_config.index,
-                id_tracker.clone(),
-                vector_storage.clone(),
-                payload_index.clone(),
-                &vector_index_path,
-                stopped,
-            )?)),
-            SparseIndexType::MutableRam | SparseIndexType::ImmutableRam => {
-                sp(VectorIndexEnum::SparseRam(SparseVectorIndex::open(
+        macro_rules! open_sparse_index {
+            ($variant:ident) => {
+                sp(VectorIndexEnum::$variant(SparseVectorIndex::open(
                     sparse_vector_config.index,
                     id_tracker.clone(),
                     vector_storage.clone(),
@@ -244,6 +289,53 @@ fn create_segment(
                     &vector_index_path,
                     stopped,
                 )?))
+            };
+        }
+
+        // Immutable indexes built before the compressed format keep their original format
+        let is_legacy_format = sparse_vector_config.index.index_type != SparseIndexType::MutableRam
+            && InvertedIndexMmap::index_file_path(&vector_index_path).exists();
+
+        let vector_index = match (
+            sparse_vector_config.index.index_type,
+            sparse_vector_config.index.effective_datatype(),
+        ) {
+            (SparseIndexType::MutableRam, _) => open_sparse_index!(SparseRam),
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Float32) if is_legacy_format => {
+                open_sparse_index!(SparseRam)
+            }
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Float16) if is_legacy_format => {
+                open_sparse_index!(SparseRamF16)
+            }
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Uint8) if is_legacy_format => {
+                open_sparse_index!(SparseRamU8)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Float32) if is_legacy_format => {
+                open_sparse_index!(SparseMmap)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Float16) if is_legacy_format => {
+                open_sparse_index!(SparseMmapF16)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Uint8) if is_legacy_format => {
+                open_sparse_index!(SparseMmapU8)
+            }
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Float32) => {
+                open_sparse_index!(SparseCompressedImmutableRamF32)
+            }
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Float16) => {
+                open_sparse_index!(SparseCompressedImmutableRamF16)
+            }
+            (SparseIndexType::ImmutableRam, VectorStorageDatatype::Uint8) => {
+                open_sparse_index!(SparseCompressedImmutableRamU8)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Float32) => {
+                open_sparse_index!(SparseCompressedMmapF32)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Float16) => {
+                open_sparse_index!(SparseCompressedMmapF16)
+            }
+            (SparseIndexType::Mmap, VectorStorageDatatype::Uint8) => {
+                open_sparse_index!(SparseCompressedMmapU8)
             }
         };
 
@@ -0,0 +1,169 @@
+use common::types::ScoreType;
+
+use super::metric::Metric;
+use super::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+#[cfg(target_arch = "x86_64")]
+use super::simple_byte_avx::*;
+use crate::data_types::vectors::{DenseVector, VectorElementTypeByte};
+use crate::types::Distance;
+
+#[cfg(target_arch = "x86_64")]
+const MIN_DIM_SIZE_AVX: usize = 32;
+
+impl Metric<VectorElementTypeByte> for EuclidMetric {
+    fn distance() -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { euclid_similarity_bytes_avx2(v1, v2) };
+            }
+        }
+
+        euclid_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeByte> for ManhattanMetric {
+    fn distance() -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { manhattan_similarity_bytes_avx2(v1, v2) };
+            }
+        }
+
+        manhattan_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeByte> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { dot_similarity_bytes_avx2(v1, v2) };
+            }
+        }
+
+        dot_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeByte> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { cosine_similarity_bytes_avx2(v1, v2) };
+            }
+        }
+
+        cosine_similarity_bytes(v1, v2)
+    }
+
+    /// Byte vectors can't be normalized without losing precision,
+    /// so the norm is accounted for during scoring instead
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn euclid_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| u64::from(a.abs_diff(*b)).pow(2))
+        .sum::<u64>() as ScoreType)
+}
+
+pub fn manhattan_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| u64::from(a.abs_diff(*b)))
+        .sum::<u64>() as ScoreType)
+}
+
+pub fn dot_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    v1.iter()
+        .zip(v2)
+        .map(|(a, b)| u64::from(*a) * u64::from(*b))
+        .sum::<u64>() as ScoreType
+}
+
+pub fn cosine_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let mut dot: u64 = 0;
+    let mut norm1: u64 = 0;
+    let mut norm2: u64 = 0;
+    for (a, b) in v1.iter().zip(v2) {
+        let (a, b) = (u64::from(*a), u64::from(*b));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_from_parts(dot, norm1, norm2)
+}
+
+pub(crate) fn cosine_from_parts(dot: u64, norm1: u64, norm2: u64) -> ScoreType {
+    if norm1 == 0 || norm2 == 0 {
+        return 0.0;
+    }
+    (dot as f64 / ((norm1 as f64).sqrt() * (norm2 as f64).sqrt())) as ScoreType
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_similarity() {
+        let v1: Vec<u8> = vec![0, 1, 2, 255];
+        let v2: Vec<u8> = vec![3, 1, 0, 255];
+
+        assert_eq!(dot_similarity_bytes(&v1, &v2), (1 + 255 * 255) as ScoreType);
+        assert_eq!(euclid_similarity_bytes(&v1, &v2), -13.0);
+        assert_eq!(manhattan_similarity_bytes(&v1, &v2), -5.0);
+
+        let cosine = cosine_similarity_bytes(&v1, &v1);
+        assert!((cosine - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity_bytes(&v1, &[0, 0, 0, 0]), 0.0);
+    }
+}
@@ -0,0 +1,195 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use super::simple_byte::cosine_from_parts;
+use crate::data_types::vectors::VectorElementTypeByte;
+
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_epi32_avx2(x: __m256i) -> u64 {
+    let mut lanes = [0u32; 8];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, x);
+    lanes.iter().map(|&lane| u64::from(lane)).sum()
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_epi64_avx2(x: __m256i) -> u64 {
+    let mut lanes = [0u64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, x);
+    lanes.iter().sum()
+}
+
+/// Load 32 bytes and zero-extend them into two vectors of 16-bit integers
+#[target_feature(enable = "avx2")]
+unsafe fn load_bytes_epi16_avx2(ptr: *const VectorElementTypeByte) -> (__m256i, __m256i) {
+    let bytes = _mm256_loadu_si256(ptr as *const __m256i);
+    (
+        _mm256_cvtepu8_epi16(_mm256_castsi256_si128(bytes)),
+        _mm256_cvtepu8_epi16(_mm256_extracti128_si256(bytes, 1)),
+    )
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn euclid_similarity_bytes_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    // Each 32-bit lane accumulates at most 4 * 255^2 per iteration, which can't overflow
+    // for any supported vector dimension
+    let mut sum256: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let (a_lo, a_hi) = load_bytes_epi16_avx2(ptr1);
+        let (b_lo, b_hi) = load_bytes_epi16_avx2(ptr2);
+        let sub_lo = _mm256_sub_epi16(a_lo, b_lo);
+        let sub_hi = _mm256_sub_epi16(a_hi, b_hi);
+        sum256 = _mm256_add_epi32(sum256, _mm256_madd_epi16(sub_lo, sub_lo));
+        sum256 = _mm256_add_epi32(sum256, _mm256_madd_epi16(sub_hi, sub_hi));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi32_avx2(sum256);
+    for i in 0..n - m {
+        result += u64::from((*ptr1.add(i)).abs_diff(*ptr2.add(i))).pow(2);
+    }
+    -(result as ScoreType)
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn manhattan_similarity_bytes_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum256: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        // Sum of absolute differences of each 8 bytes into 64-bit lanes
+        let sad = _mm256_sad_epu8(
+            _mm256_loadu_si256(ptr1 as *const __m256i),
+            _mm256_loadu_si256(ptr2 as *const __m256i),
+        );
+        sum256 = _mm256_add_epi64(sum256, sad);
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi64_avx2(sum256);
+    for i in 0..n - m {
+        result += u64::from((*ptr1.add(i)).abs_diff(*ptr2.add(i)));
+    }
+    -(result as ScoreType)
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn dot_similarity_bytes_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut sum256: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let (a_lo, a_hi) = load_bytes_epi16_avx2(ptr1);
+        let (b_lo, b_hi) = load_bytes_epi16_avx2(ptr2);
+        sum256 = _mm256_add_epi32(sum256, _mm256_madd_epi16(a_lo, b_lo));
+        sum256 = _mm256_add_epi32(sum256, _mm256_madd_epi16(a_hi, b_hi));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut result = hsum256_epi32_avx2(sum256);
+    for i in 0..n - m {
+        result += u64::from(*ptr1.add(i)) * u64::from(*ptr2.add(i));
+    }
+    result as ScoreType
+}
+
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn cosine_similarity_bytes_avx2(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 32);
+    let mut ptr1: *const VectorElementTypeByte = v1.as_ptr();
+    let mut ptr2: *const VectorElementTypeByte = v2.as_ptr();
+    let mut dot256: __m256i = _mm256_setzero_si256();
+    let mut norm256_1: __m256i = _mm256_setzero_si256();
+    let mut norm256_2: __m256i = _mm256_setzero_si256();
+    let mut i: usize = 0;
+    while i < m {
+        let (a_lo, a_hi) = load_bytes_epi16_avx2(ptr1);
+        let (b_lo, b_hi) = load_bytes_epi16_avx2(ptr2);
+        dot256 = _mm256_add_epi32(dot256, _mm256_madd_epi16(a_lo, b_lo));
+        dot256 = _mm256_add_epi32(dot256, _mm256_madd_epi16(a_hi, b_hi));
+        norm256_1 = _mm256_add_epi32(norm256_1, _mm256_madd_epi16(a_lo, a_lo));
+        norm256_1 = _mm256_add_epi32(norm256_1, _mm256_madd_epi16(a_hi, a_hi));
+        norm256_2 = _mm256_add_epi32(norm256_2, _mm256_madd_epi16(b_lo, b_lo));
+        norm256_2 = _mm256_add_epi32(norm256_2, _mm256_madd_epi16(b_hi, b_hi));
+
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+        i += 32;
+    }
+
+    let mut dot = hsum256_epi32_avx2(dot256);
+    let mut norm1 = hsum256_epi32_avx2(norm256_1);
+    let mut norm2 = hsum256_epi32_avx2(norm256_2);
+    for i in 0..n - m {
+        let (a, b) = (u64::from(*ptr1.add(i)), u64::from(*ptr2.add(i)));
+        dot += a * b;
+        norm1 += a * a;
+        norm2 += b * b;
+    }
+    cosine_from_parts(dot, norm1, norm2)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_bytes_avx2() {
+        use super::*;
+        use crate::spaces::simple_byte::*;
+
+        if is_x86_feature_detected!("avx2") {
+            let v1: Vec<u8> = (0..70).map(|i| (i * 7 % 256) as u8).collect();
+            let v2: Vec<u8> = (0..70).map(|i| (255 - i * 3 % 256) as u8).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_bytes_avx2(&v1, &v2) };
+            let euclid = euclid_similarity_bytes(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_bytes_avx2(&v1, &v2) };
+            let manhattan = manhattan_similarity_bytes(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_bytes_avx2(&v1, &v2) };
+            let dot = dot_similarity_bytes(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_similarity_bytes_avx2(&v1, &v2) };
+            let cosine = cosine_similarity_bytes(&v1, &v2);
+            assert_eq!(cosine_simd, cosine);
+        } else {
+            println!("avx2 test skipped");
+        }
+    }
+}
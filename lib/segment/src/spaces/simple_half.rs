@@ -0,0 +1,155 @@
+use common::types::ScoreType;
+
+use super::metric::Metric;
+use super::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+#[cfg(target_arch = "x86_64")]
+use super::simple_half_avx::*;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorElementTypeHalf};
+use crate::types::Distance;
+
+#[cfg(target_arch = "x86_64")]
+const MIN_DIM_SIZE_AVX: usize = 32;
+
+#[cfg(target_arch = "x86_64")]
+fn is_half_avx_available() -> bool {
+    is_x86_feature_detected!("avx")
+        && is_x86_feature_detected!("fma")
+        && is_x86_feature_detected!("f16c")
+}
+
+impl Metric<VectorElementTypeHalf> for EuclidMetric {
+    fn distance() -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available() && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { euclid_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        euclid_similarity_half(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeHalf> for ManhattanMetric {
+    fn distance() -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available() && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { manhattan_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        manhattan_similarity_half(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeHalf> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_half_avx_available() && v1.len() >= MIN_DIM_SIZE_AVX {
+                return unsafe { dot_similarity_half_avx(v1, v2) };
+            }
+        }
+
+        dot_similarity_half(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl Metric<VectorElementTypeHalf> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        <DotProductMetric as Metric<VectorElementTypeHalf>>::similarity(v1, v2)
+    }
+
+    /// Normalization is done in full precision, before conversion into half-precision storage
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        <CosineMetric as Metric<VectorElementType>>::preprocess(vector)
+    }
+}
+
+pub fn euclid_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+        .sum::<ScoreType>()
+}
+
+pub fn manhattan_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a.to_f32() - b.to_f32()).abs())
+        .sum::<ScoreType>()
+}
+
+pub fn dot_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    v1.iter()
+        .zip(v2)
+        .map(|(a, b)| a.to_f32() * b.to_f32())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+
+    use super::*;
+    use crate::spaces::simple::{dot_similarity, euclid_similarity, manhattan_similarity};
+
+    #[test]
+    fn test_half_similarity_matches_float() {
+        let v1: Vec<f32> = (0..100).map(|i| (i as f32) / 8.0 - 4.0).collect();
+        let v2: Vec<f32> = (0..100).map(|i| 2.0 - (i as f32) / 16.0).collect();
+        let h1: Vec<f16> = v1.iter().copied().map(f16::from_f32).collect();
+        let h2: Vec<f16> = v2.iter().copied().map(f16::from_f32).collect();
+
+        assert_eq!(
+            <EuclidMetric as Metric<VectorElementTypeHalf>>::similarity(&h1, &h2),
+            euclid_similarity(&v1, &v2)
+        );
+        assert_eq!(
+            <ManhattanMetric as Metric<VectorElementTypeHalf>>::similarity(&h1, &h2),
+            manhattan_similarity(&v1, &v2)
+        );
+        assert_eq!(
+            <DotProductMetric as Metric<VectorElementTypeHalf>>::similarity(&h1, &h2),
+            dot_similarity(&v1, &v2)
+        );
+    }
+}
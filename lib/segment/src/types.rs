@@ -6,6 +6,7 @@ use std::mem::size_of;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
@@ -24,11 +25,12 @@ use validator::{Validate, ValidationError, ValidationErrors};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MultiValue};
 use crate::data_types::integer_index::IntegerIndexParams;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::text_index::TextIndexParams;
 use crate::data_types::vectors::{DenseVector, VectorElementType, VectorStruct};
 use crate::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use crate::json_path::{JsonPath, JsonPathInterface};
-use crate::spaces::metric::Metric;
+use crate::spaces::metric::{Metric, MetricPostProcessing};
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::vector_storage::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
 
@@ -43,6 +45,10 @@ pub type FloatPayloadType = f64;
 pub type IntPayloadType = i64;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
+/// Type of UUID point payload
+pub type UuidPayloadType = Uuid;
+/// Type of UUID point payload key, as stored in the index
+pub type UuidIntType = u128;
 
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
@@ -165,12 +171,19 @@ pub enum Distance {
 }
 
 impl Distance {
-    pub fn preprocess_vector(&self, vector: DenseVector) -> DenseVector {
+    /// Preprocess vector before storing it as vector of `T` elements
+    pub fn preprocess_vector<T: PrimitiveVectorElement>(&self, vector: DenseVector) -> DenseVector
+    where
+        CosineMetric: Metric<T>,
+        EuclidMetric: Metric<T>,
+        DotProductMetric: Metric<T>,
+        ManhattanMetric: Metric<T>,
+    {
         match self {
-            Distance::Cosine => CosineMetric::preprocess(vector),
-            Distance::Euclid => EuclidMetric::preprocess(vector),
-            Distance::Dot => DotProductMetric::preprocess(vector),
-            Distance::Manhattan => ManhattanMetric::preprocess(vector),
+            Distance::Cosine => <CosineMetric as Metric<T>>::preprocess(vector),
+            Distance::Euclid => <EuclidMetric as Metric<T>>::preprocess(vector),
+            Distance::Dot => <DotProductMetric as Metric<T>>::preprocess(vector),
+            Distance::Manhattan => <ManhattanMetric as Metric<T>>::preprocess(vector),
         }
     }
 
@@ -190,6 +203,28 @@ impl Distance {
         }
     }
 
+    /// Score of a multi-vector compared by max-sim, from raw maximal similarities of the query
+    /// vectors
+    ///
+    /// Similarities are post-processed before summing, so for Euclid the score is the sum of
+    /// distances, not the root of summed squares. Distances are negated, so larger scores are
+    /// better, the same as raw scores of single vectors.
+    pub fn max_sim_score(&self, similarities: impl Iterator<Item = ScoreType>) -> ScoreType {
+        let score = similarities
+            .map(|similarity| self.postprocess_score(similarity))
+            .sum();
+        // Negation is its own inverse
+        self.postprocess_max_sim_score(score)
+    }
+
+    /// Convert a max-sim score into the user-facing units of the distance
+    pub fn postprocess_max_sim_score(&self, score: ScoreType) -> ScoreType {
+        match self.distance_order() {
+            Order::LargeBetter => score,
+            Order::SmallBetter => -score,
+        }
+    }
+
     /// Checks if score satisfies threshold condition
     pub fn check_threshold(&self, score: ScoreType, threshold: ScoreType) -> bool {
         match self.distance_order() {
@@ -203,14 +238,17 @@ impl Distance {
     /// Warn: prefer compile-time generics with `Metric` trait
     pub fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
         match self {
-            Distance::Cosine => CosineMetric::similarity(v1, v2),
-            Distance::Euclid => EuclidMetric::similarity(v1, v2),
-            Distance::Dot => DotProductMetric::similarity(v1, v2),
-            Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::similarity(v1, v2),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::similarity(v1, v2),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::similarity(v1, v2),
+            Distance::Manhattan => {
+                <ManhattanMetric as Metric<VectorElementType>>::similarity(v1, v2)
+            }
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Order {
     LargeBetter,
     SmallBetter,
@@ -432,6 +470,11 @@ pub struct HnswConfig {
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Update HNSW index in place on inserts and deletes, instead of re-indexing new points in the background.
+    /// Indexed segments stay appendable, if their vectors are stored in RAM and not quantized, and the index is not on disk.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub appendable: Option<bool>,
 }
 
 impl HnswConfig {
@@ -452,6 +495,8 @@ impl HnswConfig {
             // this to just reload the collection rather than optimizing it again as a whole just
             // to flip this flag
             || self.on_disk != other.on_disk
+            // Appendable index changes the type of the segment
+            || self.appendable != other.appendable
     }
 }
 
@@ -614,6 +659,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            appendable: None,
         }
     }
 }
@@ -679,9 +725,19 @@ impl SegmentConfig {
     pub fn quantization_config(&self, vector_name: &str) -> Option<&QuantizationConfig> {
         self.vector_data
             .get(vector_name)
+            // Quantization is not supported for multi-vectors
+            .filter(|v| v.multivector_config.is_none())
             .and_then(|v| v.quantization_config.as_ref())
     }
 
+    /// Element type of the stored vector, defaults to `float32` for unknown and sparse vectors
+    pub fn vector_datatype(&self, vector_name: &str) -> VectorStorageDatatype {
+        self.vector_data
+            .get(vector_name)
+            .and_then(|config| config.datatype)
+            .unwrap_or_default()
+    }
+
     pub fn distance(&self, vector_name: &str) -> Option<Distance> {
         let distance = self
             .vector_data
@@ -772,6 +828,12 @@ pub struct VectorDataConfig {
     pub index: Indexes,
     /// Vector specific quantization config that overrides collection config
     pub quantization_config: Option<QuantizationConfig>,
+    /// If set, each point stores multiple vectors of `size` dimension, compared by this config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multivector_config: Option<MultiVectorConfig>,
+    /// Element type of stored vectors. Default is `float32`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datatype: Option<VectorStorageDatatype>,
 }
 
 impl VectorDataConfig {
@@ -779,9 +841,14 @@ impl VectorDataConfig {
     ///
     /// This requires an index and storage type that both support appending.
     pub fn is_appendable(&self) -> bool {
-        let is_index_appendable = match self.index {
+        let is_index_appendable = match &self.index {
             Indexes::Plain {} => true,
-            Indexes::Hnsw(_) => false,
+            // Graph is updated in place, it must be kept in RAM and linked using original vectors
+            Indexes::Hnsw(hnsw_config) => {
+                hnsw_config.appendable == Some(true)
+                    && hnsw_config.on_disk != Some(true)
+                    && self.quantization_config.is_none()
+            }
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -792,6 +859,38 @@ impl VectorDataConfig {
     }
 }
 
+/// Element type of stored dense vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorStorageDatatype {
+    /// Single-precision floating point, 4 bytes per dimension
+    #[default]
+    Float32,
+    /// Half-precision floating point, 2 bytes per dimension
+    Float16,
+    /// Unsigned 8-bit integer, 1 byte per dimension.
+    /// Stored values must be integers in the range `[0, 255]`, query values are rounded and clamped.
+    Uint8,
+}
+
+/// Configuration of multi-vector storage and comparison
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct MultiVectorConfig {
+    /// How to compare multi-vectors
+    pub comparator: MultiVectorComparator,
+}
+
+/// Comparator used to compute a single score between two multi-vectors
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorComparator {
+    /// Late interaction scoring: for every query vector take the maximal similarity against
+    /// all vectors of the point, and sum these maximums up
+    #[default]
+    MaxSim,
+}
+
 /// Config of single sparse vector data storage
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Validate)]
 #[serde(rename_all = "snake_case")]
@@ -1080,6 +1179,7 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    Uuid,
 }
 
 /// Payload type with parameters
@@ -1108,6 +1208,7 @@ impl PayloadFieldSchema {
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Text)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Geo)
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::Uuid)
             | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_)) => false,
 
             PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
@@ -1116,6 +1217,15 @@ impl PayloadFieldSchema {
             })) => *range,
         }
     }
+
+    /// Whether points can be ordered by the field with this index, see `order_by`
+    pub fn is_orderable(&self) -> bool {
+        self.has_range_index()
+            || matches!(
+                self,
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
+            )
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -1221,6 +1331,14 @@ impl From<String> for MatchText {
     }
 }
 
+/// Full-text match of the phrase: all its words must appear in the string in the same order,
+/// next to each other.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPhrase {
+    pub phrase: String,
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1241,6 +1359,7 @@ pub struct MatchExcept {
 pub enum MatchInterface {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1251,6 +1370,7 @@ pub enum MatchInterface {
 pub enum Match {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1264,6 +1384,12 @@ impl Match {
         Self::Text(MatchText { text: text.into() })
     }
 
+    pub fn new_phrase(phrase: &str) -> Self {
+        Self::Phrase(MatchPhrase {
+            phrase: phrase.into(),
+        })
+    }
+
     pub fn new_any(any: AnyVariants) -> Self {
         Self::Any(MatchAny { any })
     }
@@ -1284,6 +1410,9 @@ impl From<MatchInterface> for Match {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
             MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Phrase(phrase) => Self::Phrase(MatchPhrase {
+                phrase: phrase.phrase,
+            }),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
@@ -1839,6 +1968,31 @@ impl NestedCondition {
     }
 }
 
+/// Predicate over external point ids, which can not be expressed with regular conditions
+pub trait CustomIdChecker: std::fmt::Debug + Send + Sync {
+    fn check(&self, point_id: ExtendedPointId) -> bool;
+}
+
+/// Wrapper of the [`CustomIdChecker`], which is never serialized and compared by reference
+#[derive(Debug, Clone)]
+pub struct CustomIdCheckerCondition(pub Arc<dyn CustomIdChecker>);
+
+impl CustomIdCheckerCondition {
+    pub fn new(checker: impl CustomIdChecker + 'static) -> Self {
+        Self(Arc::new(checker))
+    }
+
+    pub fn check(&self, point_id: ExtendedPointId) -> bool {
+        self.0.check(point_id)
+    }
+}
+
+impl PartialEq for CustomIdCheckerCondition {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
@@ -1855,6 +2009,10 @@ pub enum Condition {
     Nested(NestedCondition),
     /// Nested filter
     Filter(Filter),
+    /// Check point ids with an in-memory predicate, only available for internal use
+    #[serde(skip)]
+    #[schemars(skip)]
+    CustomIdChecker(CustomIdCheckerCondition),
 }
 
 impl Condition {
@@ -1869,7 +2027,10 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::CustomIdChecker(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fs::create_dir_all;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
@@ -12,7 +13,10 @@ use super::DenseVectorStorage;
 use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
 use crate::types::Distance;
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dynamic_mmap_flags::DynamicMmapFlags;
@@ -21,8 +25,8 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
 
-pub struct AppendableMmapDenseVectorStorage {
-    vectors: ChunkedMmapVectors,
+pub struct AppendableMmapDenseVectorStorage<T: PrimitiveVectorElement> {
+    vectors: ChunkedMmapVectors<T>,
     deleted: DynamicMmapFlags,
     distance: Distance,
     deleted_count: usize,
@@ -34,12 +38,57 @@ pub fn open_appendable_memmap_vector_storage(
     distance: Distance,
     stopped: &AtomicBool,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl::<VectorElementType>(
+        path, dim, distance, stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseAppendableMemmap(Box::new(storage)),
+    )))
+}
+
+pub fn open_appendable_memmap_vector_storage_byte(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl::<VectorElementTypeByte>(
+        path, dim, distance, stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseAppendableMemmapByte(Box::new(storage)),
+    )))
+}
+
+pub fn open_appendable_memmap_vector_storage_half(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_appendable_memmap_vector_storage_impl::<VectorElementTypeHalf>(
+        path, dim, distance, stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseAppendableMemmapHalf(Box::new(storage)),
+    )))
+}
+
+fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<AppendableMmapDenseVectorStorage<T>> {
     create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_DIR_PATH);
     let deleted_path = path.join(DELETED_DIR_PATH);
 
-    let vectors: ChunkedMmapVectors = ChunkedMmapVectors::open(&vectors_path, dim)?;
+    let vectors: ChunkedMmapVectors<T> = ChunkedMmapVectors::open(&vectors_path, dim)?;
 
     let num_vectors = vectors.len();
 
@@ -54,19 +103,15 @@ pub fn open_appendable_memmap_vector_storage(
         check_process_stopped(stopped)?;
     }
 
-    let storage = AppendableMmapDenseVectorStorage {
+    Ok(AppendableMmapDenseVectorStorage {
         vectors,
         deleted,
         distance,
         deleted_count,
-    };
-
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::DenseAppendableMemmap(Box::new(storage)),
-    )))
+    })
 }
 
-impl AppendableMmapDenseVectorStorage {
+impl<T: PrimitiveVectorElement> AppendableMmapDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<bool> {
@@ -87,13 +132,13 @@ impl AppendableMmapDenseVectorStorage {
     }
 }
 
-impl DenseVectorStorage for AppendableMmapDenseVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for AppendableMmapDenseVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key)
     }
 }
 
-impl VectorStorage for AppendableMmapDenseVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.vectors.dim()
     }
@@ -111,11 +156,14 @@ impl VectorStorage for AppendableMmapDenseVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        let vector = self.get_dense(key);
+        CowVector::from(T::slice_to_float_cow(vector.into()))
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        self.vectors.insert(key, vector.try_into()?)?;
+        let vector: &[VectorElementType] = vector.try_into()?;
+        let vector = T::slice_from_float_cow(Cow::from(vector));
+        self.vectors.insert(key, vector.as_ref())?;
         self.set_deleted(key, false)?;
         Ok(())
     }
@@ -132,8 +180,9 @@ impl VectorStorage for AppendableMmapDenseVectorStorage {
             // Do not perform preprocessing - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
-            let new_id = self.vectors.push(other_vector)?;
+            let other_vector: &[VectorElementType] = other_vector.as_vec_ref().try_into()?;
+            let other_vector = T::slice_from_float_cow(Cow::from(other_vector));
+            let new_id = self.vectors.push(other_vector.as_ref())?;
             self.set_deleted(new_id, other_deleted)?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
@@ -22,7 +22,7 @@ use crate::vector_storage::{RawScorer, VectorStorage as _, DEFAULT_STOPPED};
 
 pub fn new<'a>(
     query: QueryVector,
-    storage: &'a MemmapDenseVectorStorage,
+    storage: &'a MemmapDenseVectorStorage<VectorElementType>,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
@@ -34,7 +34,7 @@ pub fn new<'a>(
 pub struct AsyncRawScorerImpl<'a, TQueryScorer: QueryScorer<[VectorElementType]>> {
     points_count: PointOffsetType,
     query_scorer: TQueryScorer,
-    storage: &'a MmapDenseVectors,
+    storage: &'a MmapDenseVectors<VectorElementType>,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     /// This flag indicates that the search process is stopped externally,
@@ -49,7 +49,7 @@ where
     fn new(
         points_count: PointOffsetType,
         query_scorer: TQueryScorer,
-        storage: &'a MmapDenseVectors,
+        storage: &'a MmapDenseVectors<VectorElementType>,
         point_deleted: &'a BitSlice,
         vec_deleted: &'a BitSlice,
         is_stopped: &'a AtomicBool,
@@ -209,7 +209,7 @@ where
 struct AsyncRawScorerBuilder<'a> {
     points_count: PointOffsetType,
     query: QueryVector,
-    storage: &'a MemmapDenseVectorStorage,
+    storage: &'a MemmapDenseVectorStorage<VectorElementType>,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     distance: Distance,
@@ -219,7 +219,7 @@ struct AsyncRawScorerBuilder<'a> {
 impl<'a> AsyncRawScorerBuilder<'a> {
     pub fn new(
         query: QueryVector,
-        storage: &'a MemmapDenseVectorStorage,
+        storage: &'a MemmapDenseVectorStorage<VectorElementType>,
         point_deleted: &'a BitSlice,
     ) -> OperationResult<Self> {
         let points_count = storage.total_vector_count() as _;
@@ -269,8 +269,10 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             QueryVector::Nearest(vector) => {
                 match vector {
                     Vector::Dense(dense_vector) => {
-                        let query_scorer =
-                            MetricQueryScorer::<TMetric, _>::new(dense_vector, storage);
+                        let query_scorer = MetricQueryScorer::<VectorElementType, TMetric, _>::new(
+                            dense_vector,
+                            storage,
+                        );
                         Ok(Box::new(AsyncRawScorerImpl::new(
                             points_count,
                             query_scorer,
@@ -283,11 +285,15 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                     Vector::Sparse(_sparse_vector) => Err(OperationError::service_error(
                         "sparse vectors are not supported for async scorer",
                     )), // TODO(sparse) add support?
+                    Vector::MultiDense(_multi_dense_vector) => Err(OperationError::service_error(
+                        "multi-dense vectors are not supported for async scorer",
+                    )),
                 }
             }
             QueryVector::Recommend(reco_query) => {
                 let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<TMetric, _, _>::new(reco_query, storage);
+                let query_scorer =
+                    CustomQueryScorer::<VectorElementType, TMetric, _, _>::new(reco_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
@@ -300,8 +306,10 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             QueryVector::Discovery(discovery_query) => {
                 let discovery_query: DiscoveryQuery<DenseVector> =
                     discovery_query.transform_into()?;
-                let query_scorer =
-                    CustomQueryScorer::<TMetric, _, _>::new(discovery_query, storage);
+                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _>::new(
+                    discovery_query,
+                    storage,
+                );
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
@@ -313,7 +321,10 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             }
             QueryVector::Context(context_query) => {
                 let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<TMetric, _, _>::new(context_query, storage);
+                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _>::new(
+                    context_query,
+                    storage,
+                );
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
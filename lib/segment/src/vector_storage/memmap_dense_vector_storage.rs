@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::{self, Write};
 use std::ops::Range;
@@ -14,7 +15,8 @@ use super::{DenseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, VectorRef};
 use crate::types::Distance;
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::mmap_dense_vectors::MmapDenseVectors;
@@ -29,10 +31,10 @@ const DELETED_PATH: &str = "deleted.dat";
 /// but possible to mark some vectors as removed
 ///
 /// Mem-mapped storage can only be constructed from another storage
-pub struct MemmapDenseVectorStorage {
+pub struct MemmapDenseVectorStorage<T: PrimitiveVectorElement> {
     vectors_path: PathBuf,
     deleted_path: PathBuf,
-    mmap_store: Option<MmapDenseVectors>,
+    mmap_store: Option<MmapDenseVectors<T>>,
     distance: Distance,
 }
 
@@ -44,29 +46,63 @@ pub fn open_memmap_vector_storage(
     open_memmap_vector_storage_with_async_io(path, dim, distance, get_async_scorer())
 }
 
+pub fn open_memmap_vector_storage_byte(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    // Async IO is only supported for full precision vectors
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, false)?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseMemmapByte(Box::new(storage)),
+    )))
+}
+
+pub fn open_memmap_vector_storage_half(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    // Async IO is only supported for full precision vectors
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, false)?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseMemmapHalf(Box::new(storage)),
+    )))
+}
+
 pub fn open_memmap_vector_storage_with_async_io(
     path: &Path,
     dim: usize,
     distance: Distance,
     with_async_io: bool,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_memmap_vector_storage_impl(path, dim, distance, with_async_io)?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseMemmap(Box::new(storage)),
+    )))
+}
+
+fn open_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    with_async_io: bool,
+) -> OperationResult<MemmapDenseVectorStorage<T>> {
     create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_PATH);
     let deleted_path = path.join(DELETED_PATH);
     let mmap_store = MmapDenseVectors::open(&vectors_path, &deleted_path, dim, with_async_io)?;
 
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::DenseMemmap(Box::new(MemmapDenseVectorStorage {
-            vectors_path,
-            deleted_path,
-            mmap_store: Some(mmap_store),
-            distance,
-        })),
-    )))
+    Ok(MemmapDenseVectorStorage {
+        vectors_path,
+        deleted_path,
+        mmap_store: Some(mmap_store),
+        distance,
+    })
 }
 
-impl MemmapDenseVectorStorage {
+impl<T: PrimitiveVectorElement> MemmapDenseVectorStorage<T> {
     pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
         Some(
             self.mmap_store
@@ -75,7 +111,7 @@ impl MemmapDenseVectorStorage {
         )
     }
 
-    pub fn get_mmap_vectors(&self) -> &MmapDenseVectors {
+    pub fn get_mmap_vectors(&self) -> &MmapDenseVectors<T> {
         self.mmap_store.as_ref().unwrap()
     }
 
@@ -87,13 +123,13 @@ impl MemmapDenseVectorStorage {
     }
 }
 
-impl DenseVectorStorage for MemmapDenseVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for MemmapDenseVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.mmap_store.as_ref().unwrap().get_vector(key)
     }
 }
 
-impl VectorStorage for MemmapDenseVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.mmap_store.as_ref().unwrap().dim
     }
@@ -111,7 +147,8 @@ impl VectorStorage for MemmapDenseVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        let vector = self.get_dense(key);
+        CowVector::from(T::slice_to_float_cow(vector.into()))
     }
 
     fn insert_vector(&mut self, _key: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
@@ -140,7 +177,8 @@ impl VectorStorage for MemmapDenseVectorStorage {
         for id in other_ids {
             check_process_stopped(stopped)?;
             let vector: DenseVector = other.get_vector(id).try_into()?;
-            let raw_bites = mmap_ops::transmute_to_u8_slice(&vector);
+            let vector = T::slice_from_float_cow(Cow::from(vector));
+            let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
             vectors_file.write_all(raw_bites)?;
             end_index += 1;
 
@@ -221,7 +259,7 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
-    use crate::data_types::vectors::QueryVector;
+    use crate::data_types::vectors::{QueryVector, VectorElementType};
     use crate::fixtures::payload_context_fixture::FixtureIdTracker;
     use crate::id_tracker::IdTracker;
     use crate::types::{PointIdType, QuantizationConfig, ScalarQuantizationConfig};
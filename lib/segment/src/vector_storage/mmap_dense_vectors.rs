@@ -1,5 +1,6 @@
 use std::fs::{File, OpenOptions};
 use std::io::Write;
+use std::marker::PhantomData;
 use std::mem::{self, size_of, transmute};
 use std::path::Path;
 use std::sync::Arc;
@@ -14,6 +15,7 @@ use crate::common::error_logging::LogError;
 use crate::common::mmap_type::MmapBitSlice;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::VectorElementType;
 #[cfg(target_os = "linux")]
 use crate::vector_storage::async_io::UringReader;
@@ -25,7 +27,7 @@ const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
 const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
 
 /// Mem-mapped file for dense vectors
-pub struct MmapDenseVectors {
+pub struct MmapDenseVectors<T: PrimitiveVectorElement> {
     pub dim: usize,
     pub num_vectors: usize,
     /// Memory mapped file for vector data
@@ -39,9 +41,10 @@ pub struct MmapDenseVectors {
     deleted: MmapBitSlice,
     /// Current number of deleted vectors.
     pub deleted_count: usize,
+    _phantom: PhantomData<T>,
 }
 
-impl MmapDenseVectors {
+impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
     pub fn open(
         vectors_path: &Path,
         deleted_path: &Path,
@@ -52,7 +55,7 @@ impl MmapDenseVectors {
         ensure_mmap_file_size(vectors_path, VECTORS_HEADER, None)
             .describe("Create mmap data file")?;
         let mmap = mmap_ops::open_read_mmap(vectors_path).describe("Open mmap for reading")?;
-        let num_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<VectorElementType>();
+        let num_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<T>();
 
         // Allocate/open deleted mmap
         let deleted_mmap_size = deleted_mmap_size(num_vectors);
@@ -74,7 +77,7 @@ impl MmapDenseVectors {
         let uring_reader = if with_async_io {
             // Keep file handle open for async IO
             let vectors_file = File::open(vectors_path)?;
-            let raw_size = dim * size_of::<VectorElementType>();
+            let raw_size = dim * size_of::<T>();
             Some(UringReader::new(vectors_file, raw_size, HEADER_SIZE)?)
         } else {
             None
@@ -87,6 +90,7 @@ impl MmapDenseVectors {
             uring_reader: Mutex::new(uring_reader),
             deleted,
             deleted_count,
+            _phantom: PhantomData,
         })
     }
 
@@ -99,7 +103,7 @@ impl MmapDenseVectors {
     }
 
     pub fn data_offset(&self, key: PointOffsetType) -> Option<usize> {
-        let vector_data_length = self.dim * size_of::<VectorElementType>();
+        let vector_data_length = self.dim * size_of::<T>();
         let offset = (key as usize) * vector_data_length + HEADER_SIZE;
         if key >= (self.num_vectors as PointOffsetType) {
             return None;
@@ -108,17 +112,17 @@ impl MmapDenseVectors {
     }
 
     pub fn raw_size(&self) -> usize {
-        self.dim * size_of::<VectorElementType>()
+        self.dim * size_of::<T>()
     }
 
-    pub fn raw_vector_offset(&self, offset: usize) -> &[VectorElementType] {
+    pub fn raw_vector_offset(&self, offset: usize) -> &[T] {
         let byte_slice = &self.mmap[offset..(offset + self.raw_size())];
-        let arr: &[VectorElementType] = unsafe { transmute(byte_slice) };
+        let arr: &[T] = unsafe { transmute(byte_slice) };
         &arr[0..self.dim]
     }
 
     /// Returns reference to vector data by key
-    pub fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
+    pub fn get_vector(&self, key: PointOffsetType) -> &[T] {
         let offset = self.data_offset(key).unwrap();
         self.raw_vector_offset(offset)
     }
@@ -150,7 +154,10 @@ impl MmapDenseVectors {
     pub fn prefault_mmap_pages(&self, path: &Path) -> mmap_ops::PrefaultMmapPages {
         mmap_ops::PrefaultMmapPages::new(self.mmap.clone(), Some(path))
     }
+}
 
+/// Async IO is only supported for full precision vectors
+impl MmapDenseVectors<VectorElementType> {
     #[cfg(target_os = "linux")]
     fn process_points_uring(
         &self,
@@ -40,7 +40,7 @@ where
         distance: Distance,
     ) -> Self {
         let original_query = raw_query
-            .transform(|v| Ok(distance.preprocess_vector(v)))
+            .transform(|v| Ok(distance.preprocess_vector::<VectorElementType>(v)))
             .unwrap();
         let query = original_query
             .clone()
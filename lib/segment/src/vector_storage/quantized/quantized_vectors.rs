@@ -9,8 +9,9 @@ use quantization::{EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8};
 use serde::{Deserialize, Serialize};
 
 use super::quantized_scorer_builder::QuantizedScorerBuilder;
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::types::{
     BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, ProductQuantization,
@@ -108,19 +109,46 @@ impl QuantizedVectors {
     ) -> OperationResult<Self> {
         match vector_storage {
             VectorStorageEnum::DenseSimple(v) => {
-                Self::create_impl(v, quantization_config, path, max_threads, stopped)
+                Self::create_full_precision(v, quantization_config, path, max_threads, stopped)
             }
-            VectorStorageEnum::DenseMemmap(v) => {
-                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            VectorStorageEnum::DenseSimpleByte(v) => {
+                Self::create_converted(v, quantization_config, path, max_threads, stopped)
             }
-            VectorStorageEnum::DenseAppendableMemmap(v) => {
-                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            VectorStorageEnum::DenseSimpleHalf(v) => {
+                Self::create_converted(v, quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseMemmap(v) => Self::create_full_precision(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseMemmapByte(v) => {
+                Self::create_converted(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseMemmapHalf(v) => {
+                Self::create_converted(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmap(v) => Self::create_full_precision(
+                v.as_ref(),
+                quantization_config,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => {
+                Self::create_converted(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
+                Self::create_converted(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
+            VectorStorageEnum::MultiDenseSimple(_) => Err(OperationError::WrongMulti),
         }
     }
 
-    fn create_impl<TVectorStorage: DenseVectorStorage + Send + Sync>(
+    fn create_full_precision<TVectorStorage: DenseVectorStorage + Send + Sync>(
         vector_storage: &TVectorStorage,
         quantization_config: &QuantizationConfig,
         path: &Path,
@@ -129,6 +157,58 @@ impl QuantizedVectors {
     ) -> OperationResult<Self> {
         let count = vector_storage.total_vector_count();
         let vectors = (0..count as PointOffsetType).map(|i| vector_storage.get_dense(i));
+        Self::create_impl(
+            vector_storage,
+            vectors,
+            quantization_config,
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    /// Quantization operates on full precision vectors,
+    /// so vectors of other element types are converted into a temporary in-memory buffer first
+    fn create_converted<
+        TElement: PrimitiveVectorElement,
+        TVectorStorage: DenseVectorStorage<TElement>,
+    >(
+        vector_storage: &TVectorStorage,
+        quantization_config: &QuantizationConfig,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let count = vector_storage.total_vector_count();
+        let distance = vector_storage.distance();
+        let mut converted_vectors =
+            ChunkedVectors::<VectorElementType>::new(vector_storage.vector_dim());
+        converted_vectors.try_set_capacity_exact(count)?;
+        for i in 0..count as PointOffsetType {
+            check_process_stopped(stopped)?;
+            let vector = TElement::quantization_preprocess(distance, vector_storage.get_dense(i));
+            converted_vectors.push(&vector)?;
+        }
+        let vectors = (0..count as PointOffsetType).map(|i| converted_vectors.get(i));
+        Self::create_impl(
+            vector_storage,
+            vectors,
+            quantization_config,
+            path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    fn create_impl<'a, TVectorStorage: VectorStorage>(
+        vector_storage: &TVectorStorage,
+        vectors: impl Iterator<Item = &'a [VectorElementType]> + Clone + Send,
+        quantization_config: &QuantizationConfig,
+        path: &Path,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let count = vector_storage.total_vector_count();
         let on_disk_vector_storage = vector_storage.is_on_disk();
         let distance = vector_storage.distance();
         let dim = vector_storage.vector_dim();
@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{MultiDenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::types::{MultiVectorComparator, MultiVectorConfig};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::MultiVectorStorage;
+
+pub struct MultiMetricQueryScorer<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: MultiDenseVector,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage>
+    MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: MultiDenseVector, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: preprocess_multi::<TMetric>(query),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage> QueryScorer<MultiDenseVector>
+    for MultiMetricQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        let stored = self.vector_storage.get_multi(idx);
+        self.score(stored)
+    }
+
+    #[inline]
+    fn score(&self, v2: &MultiDenseVector) -> ScoreType {
+        score_multi::<TMetric>(self.vector_storage.multi_vector_config(), &self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_multi(point_a);
+        let v2 = self.vector_storage.get_multi(point_b);
+        score_multi::<TMetric>(self.vector_storage.multi_vector_config(), v1, v2)
+    }
+}
+
+/// Apply metric preprocessing to each inner vector of a multi-vector
+pub fn preprocess_multi<TMetric: Metric>(vector: MultiDenseVector) -> MultiDenseVector {
+    let dim = vector.dim;
+    let flattened_vectors = vector
+        .multi_vectors()
+        .flat_map(|inner| TMetric::preprocess(inner.to_vec()))
+        .collect();
+    MultiDenseVector::new(flattened_vectors, dim)
+}
+
+/// Compare two multi-vectors with the comparator from the given config
+pub fn score_multi<TMetric: Metric>(
+    multi_vector_config: &MultiVectorConfig,
+    query: &MultiDenseVector,
+    stored: &MultiDenseVector,
+) -> ScoreType {
+    match multi_vector_config.comparator {
+        MultiVectorComparator::MaxSim => score_max_similarity::<TMetric>(query, stored),
+    }
+}
+
+/// Sum over all query vectors of the maximal similarity to any of the stored vectors
+///
+/// See [`Distance::max_sim_score`](crate::types::Distance::max_sim_score) for the units.
+fn score_max_similarity<TMetric: Metric>(
+    query: &MultiDenseVector,
+    stored: &MultiDenseVector,
+) -> ScoreType {
+    TMetric::distance().max_sim_score(
+        query
+            .multi_vectors()
+            .map(|query_vector| max_similarity::<TMetric>(query_vector, stored)),
+    )
+}
+
+#[inline]
+fn max_similarity<TMetric: Metric>(
+    query_vector: &[VectorElementType],
+    stored: &MultiDenseVector,
+) -> ScoreType {
+    stored
+        .multi_vectors()
+        .map(|stored_vector| TMetric::similarity(query_vector, stored_vector))
+        .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::simple::{DotProductMetric, EuclidMetric};
+
+    #[test]
+    fn test_max_sim_dot() {
+        let query =
+            MultiDenseVector::try_from_matrix(vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        let stored = MultiDenseVector::try_from_matrix(vec![
+            vec![0.5, 0.1],
+            vec![0.2, 0.3],
+            vec![0.9, -1.0],
+        ])
+        .unwrap();
+
+        // max(0.5, 0.2, 0.9) + max(0.1, 0.3, -1.0)
+        let score = score_max_similarity::<DotProductMetric>(&query, &stored);
+        assert!((score - 1.2).abs() < 1e-6);
+
+        // Comparing with itself picks the matching vectors
+        let score = score_max_similarity::<DotProductMetric>(&query, &query);
+        assert!((score - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_max_sim_euclid() {
+        let query =
+            MultiDenseVector::try_from_matrix(vec![vec![1.0, 1.0], vec![3.0, 3.0]]).unwrap();
+        let stored = MultiDenseVector::try_from_matrix(vec![vec![1.0, 1.0]]).unwrap();
+
+        // Exact match contributes nothing, the other vector is at distance sqrt(8)
+        let score = score_max_similarity::<EuclidMetric>(&query, &stored);
+        assert!((score + 8.0f32.sqrt()).abs() < 1e-6);
+
+        // Distances of the query vectors are summed, not their squares
+        let query =
+            MultiDenseVector::try_from_matrix(vec![vec![4.0, 1.0], vec![1.0, 5.0]]).unwrap();
+        let score = score_max_similarity::<EuclidMetric>(&query, &stored);
+        assert!((score + 7.0).abs() < 1e-6);
+    }
+}
@@ -9,10 +9,13 @@ use super::query::discovery_query::DiscoveryQuery;
 use super::query::reco_query::RecoQuery;
 use super::query::TransformInto;
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
+use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
+use super::query_scorer::multi_metric_query_scorer::MultiMetricQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
-use super::{DenseVectorStorage, SparseVectorStorage, VectorStorageEnum};
+use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::data_types::vectors::{DenseVector, QueryVector};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, QueryVector};
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::spaces::tools::peek_top_largest_iterable;
@@ -109,6 +112,12 @@ pub fn new_stoppable_raw_scorer<'a>(
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match vector_storage {
         VectorStorageEnum::DenseSimple(vs) => raw_scorer_impl(query, vs, point_deleted, is_stopped),
+        VectorStorageEnum::DenseSimpleByte(vs) => {
+            raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleHalf(vs) => {
+            raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseMemmap(vs) => {
             if vs.has_async_reader() {
@@ -128,13 +137,28 @@ pub fn new_stoppable_raw_scorer<'a>(
 
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseMemmapByte(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseMemmapHalf(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseAppendableMemmap(vs) => {
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseSimple(vs) => {
+            raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
     }
 }
 
@@ -189,32 +213,42 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
-pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
+pub fn raw_scorer_impl<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TVectorStorage: DenseVectorStorage<TElement>,
+>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
-) -> OperationResult<Box<dyn RawScorer + 'a>> {
+) -> OperationResult<Box<dyn RawScorer + 'a>>
+where
+    CosineMetric: Metric<TElement>,
+    EuclidMetric: Metric<TElement>,
+    DotProductMetric: Metric<TElement>,
+    ManhattanMetric: Metric<TElement>,
+{
     match vector_storage.distance() {
-        Distance::Cosine => new_scorer_with_metric::<CosineMetric, _>(
+        Distance::Cosine => new_scorer_with_metric::<TElement, CosineMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Euclid => new_scorer_with_metric::<EuclidMetric, _>(
+        Distance::Euclid => new_scorer_with_metric::<TElement, EuclidMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Dot => new_scorer_with_metric::<DotProductMetric, _>(
+        Distance::Dot => new_scorer_with_metric::<TElement, DotProductMetric, _>(
             query,
             vector_storage,
             point_deleted,
             is_stopped,
         ),
-        Distance::Manhattan => new_scorer_with_metric::<ManhattanMetric, _>(
+        Distance::Manhattan => new_scorer_with_metric::<TElement, ManhattanMetric, _>(
             query,
             vector_storage,
             point_deleted,
@@ -223,7 +257,12 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
     }
 }
 
-fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorStorage>(
+fn new_scorer_with_metric<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement> + 'a,
+    TVectorStorage: DenseVectorStorage<TElement>,
+>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
@@ -232,7 +271,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
     let vec_deleted = vector_storage.deleted_vector_bitslice();
     match query {
         QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
-            MetricQueryScorer::<TMetric, _>::new(vector.try_into()?, vector_storage),
+            MetricQueryScorer::<TElement, TMetric, _>::new(vector.try_into()?, vector_storage),
             point_deleted,
             vec_deleted,
             is_stopped,
@@ -240,7 +279,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Recommend(reco_query) => {
             let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(reco_query, vector_storage),
+                CustomQueryScorer::<TElement, TMetric, _, _>::new(reco_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -249,7 +288,7 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(discovery_query, vector_storage),
+                CustomQueryScorer::<TElement, TMetric, _, _>::new(discovery_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -258,7 +297,86 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorS
         QueryVector::Context(context_query) => {
             let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
             raw_scorer_from_query_scorer(
-                CustomQueryScorer::<TMetric, _, _>::new(context_query, vector_storage),
+                CustomQueryScorer::<TElement, TMetric, _, _>::new(context_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
+pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_multi_scorer_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_multi_scorer_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_multi_scorer_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_multi_scorer_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_multi_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: MultiVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MultiMetricQueryScorer::<TMetric, _>::new(vector.try_into()?, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<MultiDenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(reco_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<MultiDenseVector> =
+                discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(discovery_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<MultiDenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<TMetric, _, _>::new(context_query, vector_storage),
                 point_deleted,
                 vec_deleted,
                 is_stopped,
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::mem::size_of;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
@@ -18,17 +19,20 @@ use crate::common::operation_error::{check_process_stopped, OperationError, Oper
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    TypedDenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
 use crate::types::Distance;
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 
 /// In-memory vector storage with on-update persistence using `store`
-pub struct SimpleDenseVectorStorage {
+pub struct SimpleDenseVectorStorage<T: PrimitiveVectorElement> {
     dim: usize,
     distance: Distance,
-    vectors: ChunkedVectors<VectorElementType>,
+    vectors: ChunkedVectors<T>,
     db_wrapper: DatabaseColumnWrapper,
-    update_buffer: StoredRecord,
+    update_buffer: StoredRecord<T>,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
     /// Current number of deleted vectors.
@@ -36,9 +40,10 @@ pub struct SimpleDenseVectorStorage {
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
-struct StoredRecord {
+#[serde(bound = "")]
+struct StoredRecord<T: PrimitiveVectorElement> {
     pub deleted: bool,
-    pub vector: DenseVector,
+    pub vector: TypedDenseVector<T>,
 }
 
 pub fn open_simple_vector_storage(
@@ -48,6 +53,66 @@ pub fn open_simple_vector_storage(
     distance: Distance,
     stopped: &AtomicBool,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl::<VectorElementType>(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimple(storage),
+    )))
+}
+
+pub fn open_simple_vector_storage_byte(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl::<VectorElementTypeByte>(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimpleByte(storage),
+    )))
+}
+
+pub fn open_simple_vector_storage_half(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl::<VectorElementTypeHalf>(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimpleHalf(storage),
+    )))
+}
+
+fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<SimpleDenseVectorStorage<T>> {
     let mut vectors = ChunkedVectors::new(dim);
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
 
@@ -56,7 +121,7 @@ pub fn open_simple_vector_storage(
     for (key, value) in db_wrapper.lock_db().iter()? {
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
-        let stored_record: StoredRecord = bincode::deserialize(&value)
+        let stored_record: StoredRecord<T> = bincode::deserialize(&value)
             .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
 
         // Propagate deleted flag
@@ -72,26 +137,24 @@ pub fn open_simple_vector_storage(
     debug!("Segment vectors: {}", vectors.len());
     debug!(
         "Estimated segment size {} MB",
-        vectors.len() * dim * size_of::<VectorElementType>() / 1024 / 1024
+        vectors.len() * dim * size_of::<T>() / 1024 / 1024
     );
 
-    Ok(Arc::new(AtomicRefCell::new(
-        VectorStorageEnum::DenseSimple(SimpleDenseVectorStorage {
-            dim,
-            distance,
-            vectors,
-            db_wrapper,
-            update_buffer: StoredRecord {
-                deleted: false,
-                vector: vec![0.; dim],
-            },
-            deleted,
-            deleted_count,
-        }),
-    )))
+    Ok(SimpleDenseVectorStorage {
+        dim,
+        distance,
+        vectors,
+        db_wrapper,
+        update_buffer: StoredRecord {
+            deleted: false,
+            vector: vec![T::default(); dim],
+        },
+        deleted,
+        deleted_count,
+    })
 }
 
-impl SimpleDenseVectorStorage {
+impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -113,7 +176,7 @@ impl SimpleDenseVectorStorage {
         &mut self,
         key: PointOffsetType,
         deleted: bool,
-        vector: Option<&[VectorElementType]>,
+        vector: Option<&[T]>,
     ) -> OperationResult<()> {
         // Write vector state to buffer record
         let record = &mut self.update_buffer;
@@ -132,13 +195,13 @@ impl SimpleDenseVectorStorage {
     }
 }
 
-impl DenseVectorStorage for SimpleDenseVectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for SimpleDenseVectorStorage<T> {
+    fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key)
     }
 }
 
-impl VectorStorage for SimpleDenseVectorStorage {
+impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
     fn vector_dim(&self) -> usize {
         self.dim
     }
@@ -156,14 +219,16 @@ impl VectorStorage for SimpleDenseVectorStorage {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        self.get_dense(key).into()
+        let vector = self.get_dense(key);
+        CowVector::from(T::slice_to_float_cow(vector.into()))
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        let vector = vector.try_into()?;
-        self.vectors.insert(key, vector)?;
+        let vector: &[VectorElementType] = vector.try_into()?;
+        let vector = T::slice_from_float_cow(Cow::from(vector));
+        self.vectors.insert(key, vector.as_ref())?;
         self.set_deleted(key, false);
-        self.update_stored(key, false, Some(vector))?;
+        self.update_stored(key, false, Some(vector.as_ref()))?;
         Ok(())
     }
 
@@ -178,11 +243,12 @@ impl VectorStorage for SimpleDenseVectorStorage {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = other.get_vector(point_id);
-            let other_vector = other_vector.as_vec_ref().try_into()?;
+            let other_vector: &[VectorElementType] = other_vector.as_vec_ref().try_into()?;
+            let other_vector = T::slice_from_float_cow(Cow::from(other_vector));
             let other_deleted = other.is_deleted_vector(point_id);
-            let new_id = self.vectors.push(other_vector)?;
+            let new_id = self.vectors.push(other_vector.as_ref())?;
             self.set_deleted(new_id, other_deleted);
-            self.update_stored(new_id, other_deleted, Some(other_vector))?;
+            self.update_stored(new_id, other_deleted, Some(other_vector.as_ref()))?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
         Ok(start_index..end_index)
@@ -0,0 +1,269 @@
+use std::mem::size_of;
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use log::debug;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+
+use super::vector_storage_base::VectorStorage;
+use super::{MultiVectorStorage, VectorStorageEnum};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, MultiDenseVector, VectorElementType, VectorRef};
+use crate::types::{Distance, MultiVectorConfig};
+use crate::vector_storage::bitvec::bitvec_set_deleted;
+
+/// In-memory multi-vector storage with on-update persistence using `store`
+pub struct SimpleMultiDenseVectorStorage {
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+    /// Keep vectors in memory
+    vectors: Vec<MultiDenseVector>,
+    db_wrapper: DatabaseColumnWrapper,
+    update_buffer: StoredRecord,
+    /// BitVec for deleted flags. Grows dynamically upto last set flag.
+    deleted: BitVec,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct StoredRecord {
+    pub deleted: bool,
+    /// All inner vectors, flattened
+    pub vector: DenseVector,
+}
+
+pub fn open_simple_multi_dense_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let mut vectors: Vec<MultiDenseVector> = vec![];
+    let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
+
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+
+    for (key, value) in db_wrapper.lock_db().iter()? {
+        let point_id: PointOffsetType = bincode::deserialize(&key)
+            .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
+        let stored_record: StoredRecord = bincode::deserialize(&value)
+            .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
+
+        // Propagate deleted flag
+        if stored_record.deleted {
+            bitvec_set_deleted(&mut deleted, point_id, true);
+            deleted_count += 1;
+        }
+
+        let point_id_usize = point_id as usize;
+        if point_id_usize >= vectors.len() {
+            vectors.resize(point_id_usize + 1, MultiDenseVector::new(vec![], dim));
+        }
+        vectors[point_id_usize] = MultiDenseVector::new(stored_record.vector, dim);
+
+        check_process_stopped(stopped)?;
+    }
+
+    debug!("Segment vectors: {}", vectors.len());
+    debug!(
+        "Estimated segment size {} MB",
+        vectors
+            .iter()
+            .map(|v| v.flattened_vectors.len() * size_of::<VectorElementType>())
+            .sum::<usize>()
+            / 1024
+            / 1024
+    );
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::MultiDenseSimple(SimpleMultiDenseVectorStorage {
+            dim,
+            distance,
+            multi_vector_config,
+            vectors,
+            db_wrapper,
+            update_buffer: StoredRecord {
+                deleted: false,
+                vector: vec![],
+            },
+            deleted,
+            deleted_count,
+        }),
+    )))
+}
+
+impl SimpleMultiDenseVectorStorage {
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if key as usize >= self.vectors.len() {
+            return false;
+        }
+        let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count -= 1;
+            }
+        }
+        was_deleted
+    }
+
+    /// Put multi-vector at the given key, growing the storage if needed
+    fn insert_multi(&mut self, key: PointOffsetType, vector: MultiDenseVector) {
+        let key_usize = key as usize;
+        if key_usize >= self.vectors.len() {
+            self.vectors
+                .resize(key_usize + 1, MultiDenseVector::new(vec![], self.dim));
+        }
+        self.vectors[key_usize] = vector;
+    }
+
+    fn update_stored(
+        &mut self,
+        key: PointOffsetType,
+        deleted: bool,
+        vector: Option<&MultiDenseVector>,
+    ) -> OperationResult<()> {
+        // Write vector state to buffer record
+        let record = &mut self.update_buffer;
+        record.deleted = deleted;
+        if let Some(vector) = vector {
+            record.vector.clone_from(&vector.flattened_vectors);
+        }
+
+        // Store updated record
+        self.db_wrapper.put(
+            bincode::serialize(&key).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Convert a vector reference into a multi-vector of the storage dimension
+    ///
+    /// A single dense vector is accepted as a multi-vector with a single inner vector.
+    fn to_multi_dense(&self, vector: VectorRef) -> OperationResult<MultiDenseVector> {
+        let multi_vector = match vector {
+            VectorRef::Dense(v) => MultiDenseVector::new(v.to_vec(), v.len()),
+            VectorRef::Sparse(_) => return Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(v) => v.clone(),
+        };
+        if multi_vector.dim != self.dim {
+            return Err(OperationError::WrongVector {
+                expected_dim: self.dim,
+                received_dim: multi_vector.dim,
+            });
+        }
+        Ok(multi_vector)
+    }
+}
+
+impl MultiVectorStorage for SimpleMultiDenseVectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> &MultiDenseVector {
+        self.vectors.get(key as usize).expect("vector not found")
+    }
+
+    fn multi_vector_config(&self) -> &MultiVectorConfig {
+        &self.multi_vector_config
+    }
+}
+
+impl VectorStorage for SimpleMultiDenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        self.get_vector_opt(key).expect("vector not found")
+    }
+
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
+        self.vectors.get(key as usize).map(CowVector::from)
+    }
+
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let multi_vector = self.to_multi_dense(vector)?;
+        self.update_stored(key, false, Some(&multi_vector))?;
+        self.insert_multi(key, multi_vector);
+        self.set_deleted(key, false);
+        Ok(())
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector = other.get_vector(point_id);
+            let other_vector = self.to_multi_dense(other_vector.as_vec_ref())?;
+            let other_deleted = other.is_deleted_vector(point_id);
+            let new_id = self.vectors.len() as PointOffsetType;
+            self.update_stored(new_id, other_deleted, Some(&other_vector))?;
+            self.insert_multi(new_id, other_vector);
+            self.set_deleted(new_id, other_deleted);
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn files(&self) -> Vec<std::path::PathBuf> {
+        vec![]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let is_deleted = !self.set_deleted(key, true);
+        if is_deleted {
+            self.update_stored(key, true, None)?;
+        }
+        Ok(is_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).map(|b| *b).unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+}
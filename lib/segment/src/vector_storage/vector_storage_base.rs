@@ -11,9 +11,13 @@ use super::simple_dense_vector_storage::SimpleDenseVectorStorage;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
-use crate::types::Distance;
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{
+    MultiDenseVector, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+};
+use crate::types::{Distance, MultiVectorConfig};
 use crate::vector_storage::appendable_mmap_dense_vector_storage::AppendableMmapDenseVectorStorage;
+use crate::vector_storage::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage;
 use crate::vector_storage::simple_sparse_vector_storage::SimpleSparseVectorStorage;
 
 /// Trait for vector storage
@@ -96,82 +100,144 @@ pub trait VectorStorage {
     fn deleted_vector_bitslice(&self) -> &BitSlice;
 }
 
-pub trait DenseVectorStorage: VectorStorage {
-    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType];
+pub trait DenseVectorStorage<T: PrimitiveVectorElement = VectorElementType>: VectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[T];
 }
 
 pub trait SparseVectorStorage: VectorStorage {
     fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector>;
 }
 
+pub trait MultiVectorStorage: VectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> &MultiDenseVector;
+
+    fn multi_vector_config(&self) -> &MultiVectorConfig;
+}
+
 pub enum VectorStorageEnum {
-    DenseSimple(SimpleDenseVectorStorage),
-    DenseMemmap(Box<MemmapDenseVectorStorage>),
-    DenseAppendableMemmap(Box<AppendableMmapDenseVectorStorage>),
+    DenseSimple(SimpleDenseVectorStorage<VectorElementType>),
+    DenseSimpleByte(SimpleDenseVectorStorage<VectorElementTypeByte>),
+    DenseSimpleHalf(SimpleDenseVectorStorage<VectorElementTypeHalf>),
+    DenseMemmap(Box<MemmapDenseVectorStorage<VectorElementType>>),
+    DenseMemmapByte(Box<MemmapDenseVectorStorage<VectorElementTypeByte>>),
+    DenseMemmapHalf(Box<MemmapDenseVectorStorage<VectorElementTypeHalf>>),
+    DenseAppendableMemmap(Box<AppendableMmapDenseVectorStorage<VectorElementType>>),
+    DenseAppendableMemmapByte(Box<AppendableMmapDenseVectorStorage<VectorElementTypeByte>>),
+    DenseAppendableMemmapHalf(Box<AppendableMmapDenseVectorStorage<VectorElementTypeHalf>>),
     SparseSimple(SimpleSparseVectorStorage),
+    MultiDenseSimple(SimpleMultiDenseVectorStorage),
 }
 
 impl VectorStorage for VectorStorageEnum {
     fn vector_dim(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.vector_dim(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.vector_dim(),
             VectorStorageEnum::DenseMemmap(v) => v.vector_dim(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.vector_dim(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.vector_dim(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.vector_dim(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.vector_dim(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.vector_dim(),
             VectorStorageEnum::SparseSimple(v) => v.vector_dim(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.vector_dim(),
         }
     }
 
     fn distance(&self) -> Distance {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.distance(),
             VectorStorageEnum::DenseMemmap(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.distance(),
             VectorStorageEnum::SparseSimple(v) => v.distance(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
         }
     }
 
     fn is_on_disk(&self) -> bool {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmap(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_on_disk(),
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
         }
     }
 
     fn total_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.total_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
         }
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector(key),
+            VectorStorageEnum::DenseMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector(key),
         }
     }
 
     fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt(key),
         }
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector),
         }
     }
 
@@ -183,63 +249,116 @@ impl VectorStorage for VectorStorageEnum {
     ) -> OperationResult<Range<PointOffsetType>> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseMemmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
+                v.update_from(other, other_ids, stopped)
+            }
             VectorStorageEnum::SparseSimple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other, other_ids, stopped),
         }
     }
 
     fn flusher(&self) -> Flusher {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.flusher(),
             VectorStorageEnum::DenseMemmap(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.flusher(),
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
         }
     }
 
     fn files(&self) -> Vec<PathBuf> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.files(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.files(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.files(),
             VectorStorageEnum::DenseMemmap(v) => v.files(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.files(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.files(),
             VectorStorageEnum::SparseSimple(v) => v.files(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.files(),
         }
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.delete_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
         }
     }
 
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
         }
     }
 
     fn deleted_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
         }
     }
 
     fn deleted_vector_bitslice(&self) -> &BitSlice {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
         }
     }
 }
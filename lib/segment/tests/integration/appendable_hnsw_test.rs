@@ -0,0 +1,387 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
+use rand::{thread_rng, Rng};
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::id_tracker::IdTracker;
+use segment::index::hnsw_index::graph_layers::GraphLayers;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment::Segment;
+use segment::segment_constructor::{build_segment, get_vector_index_path, load_segment};
+use segment::types::{
+    Distance, HnswConfig, Indexes, PayloadSchemaType, PointIdType, SearchParams, SegmentConfig,
+    SeqNumberType, VectorDataConfig, VectorStorageType,
+};
+use tempfile::Builder;
+
+use crate::utils::path;
+
+fn open_hnsw_index(segment: &Segment, path: &std::path::Path) -> HNSWIndex<GraphLinksRam> {
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 16, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: Some(true),
+    };
+
+    HNSWIndex::<GraphLinksRam>::open(
+        path,
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+        true,
+    )
+    .unwrap()
+}
+
+fn internal_id(segment: &Segment, point_id: u64) -> PointOffsetType {
+    segment
+        .id_tracker
+        .borrow()
+        .internal_id(PointIdType::from(point_id))
+        .unwrap()
+}
+
+#[test]
+fn appendable_hnsw_test() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 1_000;
+    let num_new_vectors: u64 = 500;
+    let ef = 64;
+    let top = 5;
+    let attempts = 50;
+
+    let mut rnd = thread_rng();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let permit_cpu_count = num_rayon_threads(2);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let mut hnsw_index = open_hnsw_index(&segment, hnsw_dir.path());
+    hnsw_index.build_index(permit, &stopped).unwrap();
+    assert_eq!(hnsw_index.indexed_vector_count(), num_vectors as usize);
+
+    // Insert new points, they are linked into the graph in place
+    for n in num_vectors..num_vectors + num_new_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+        hnsw_index
+            .update_vector(internal_id(&segment, n), (&vector).into())
+            .unwrap();
+    }
+
+    // Delete some old and new points, they are unlinked from the graph
+    let mut deleted = HashSet::new();
+    for _ in 0..200 {
+        let n = rnd.gen_range(0..num_vectors + num_new_vectors);
+        if !deleted.insert(n) {
+            continue;
+        }
+        let offset = internal_id(&segment, n);
+        segment
+            .delete_point((num_vectors + num_new_vectors) as SeqNumberType, n.into())
+            .unwrap();
+        hnsw_index.delete_vector(offset).unwrap();
+    }
+
+    let available = (num_vectors + num_new_vectors) as usize - deleted.len();
+    assert_eq!(hnsw_index.indexed_vector_count(), available);
+
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim).into();
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], None, top, None, &false.into())
+            .unwrap();
+
+        for scored_point in &index_result[0] {
+            let point_id = segment
+                .id_tracker
+                .borrow()
+                .external_id(scored_point.idx)
+                .expect("Deleted point must not be found");
+            assert!(!deleted.contains(&point_id_to_num(point_id)));
+        }
+
+        hits += index_result[0]
+            .iter()
+            .filter(|point| plain_result[0].contains(point))
+            .count();
+    }
+
+    let recall = hits as f64 / (attempts * top) as f64;
+    assert!(recall > 0.9, "recall is too low: {recall}");
+
+    // Graph catches up with the vector storage on load
+    drop(hnsw_index);
+    let hnsw_index = open_hnsw_index(&segment, hnsw_dir.path());
+    assert_eq!(hnsw_index.indexed_vector_count(), available);
+}
+
+#[test]
+fn appendable_hnsw_flush_test() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 500;
+    let num_new_vectors: u64 = 100;
+    let top = 5;
+
+    let mut rnd = thread_rng();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Hnsw(HnswConfig {
+                    m: 8,
+                    ef_construct: 32,
+                    full_scan_threshold: 1, // KB
+                    max_indexing_threads: 2,
+                    on_disk: Some(false),
+                    payload_m: None,
+                    appendable: Some(true),
+                }),
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    // Payload index graphs are built for indexed fields
+    segment
+        .create_field_index(
+            num_vectors,
+            &path("color"),
+            Some(&PayloadSchemaType::Keyword.into()),
+        )
+        .unwrap();
+
+    let permit_cpu_count = num_rayon_threads(2);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+    segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow_mut()
+        .build_index(permit, &stopped)
+        .unwrap();
+
+    // Updates of the segment are applied to the graph in place
+    let mut new_vectors = HashMap::new();
+    for n in num_vectors..num_vectors + num_new_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+        new_vectors.insert(n, vector);
+    }
+    let deleted: HashSet<u64> = (0..num_vectors).step_by(10).collect();
+    for &n in &deleted {
+        segment
+            .delete_point((num_vectors + num_new_vectors) as SeqNumberType, n.into())
+            .unwrap();
+    }
+
+    // Graph is saved on flush
+    segment.flush(true).unwrap();
+
+    let index_path = get_vector_index_path(&segment.current_path, DEFAULT_VECTOR_NAME);
+    let graph = GraphLayers::<GraphLinksRam>::load(
+        &GraphLayers::<GraphLinksRam>::get_path(&index_path),
+        &GraphLayers::<GraphLinksRam>::get_links_path(&index_path),
+    )
+    .unwrap();
+    assert_eq!(graph.num_points(), (num_vectors + num_new_vectors) as usize);
+
+    let segment_path = segment.current_path.clone();
+    drop(segment);
+    let segment = load_segment(&segment_path, &stopped).unwrap().unwrap();
+
+    let available = (num_vectors + num_new_vectors) as usize - deleted.len();
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    assert_eq!(vector_index.indexed_vector_count(), available);
+    // Points linked in place have no payload index links, until the index is rebuilt
+    assert_eq!(
+        vector_index.in_place_linked_count(),
+        num_new_vectors as usize,
+    );
+
+    // New points are found in the reopened graph, deleted ones are not
+    for (n, vector) in &new_vectors {
+        let query = vector.clone().into();
+        let result = vector_index
+            .search(&[&query], None, top, None, &false.into())
+            .unwrap();
+        let found: Vec<_> = result[0]
+            .iter()
+            .map(|scored_point| {
+                let point_id = segment
+                    .id_tracker
+                    .borrow()
+                    .external_id(scored_point.idx)
+                    .expect("Deleted point must not be found");
+                point_id_to_num(point_id)
+            })
+            .collect();
+        assert!(found.iter().all(|n| !deleted.contains(n)));
+        assert_eq!(found[0], *n);
+    }
+    drop(vector_index);
+
+    // Few changes don't save the whole graph on flush
+    let mut segment = segment;
+    let n = num_vectors + num_new_vectors;
+    let vector = random_vector(&mut rnd, dim);
+    segment
+        .upsert_point(
+            n as SeqNumberType + 1,
+            n.into(),
+            only_default_vector(&vector),
+        )
+        .unwrap();
+    segment.flush(true).unwrap();
+
+    let graph = GraphLayers::<GraphLinksRam>::load(
+        &GraphLayers::<GraphLinksRam>::get_path(&index_path),
+        &GraphLayers::<GraphLinksRam>::get_links_path(&index_path),
+    )
+    .unwrap();
+    assert_eq!(graph.num_points(), n as usize);
+
+    // Point is linked again on load
+    drop(segment);
+    let segment = load_segment(&segment_path, &stopped).unwrap().unwrap();
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    assert_eq!(vector_index.indexed_vector_count(), available + 1);
+
+    let result = vector_index
+        .search(&[&vector.into()], None, 1, None, &false.into())
+        .unwrap();
+    let found = segment
+        .id_tracker
+        .borrow()
+        .external_id(result[0][0].idx)
+        .unwrap();
+    assert_eq!(point_id_to_num(found), n);
+    drop(vector_index);
+
+    // Point updated in place keeps its offset, it is re-linked with the new vector on load
+    let mut segment = segment;
+    let updated = 1;
+    let updated_offset = internal_id(&segment, updated);
+    let vector = random_vector(&mut rnd, dim);
+    segment
+        .upsert_point(
+            n as SeqNumberType + 2,
+            updated.into(),
+            only_default_vector(&vector),
+        )
+        .unwrap();
+    assert_eq!(internal_id(&segment, updated), updated_offset);
+    segment.flush(true).unwrap();
+
+    let relinked_points_path = HNSWIndex::<GraphLinksRam>::get_relinked_points_path(&index_path);
+    assert!(relinked_points_path.exists());
+
+    drop(segment);
+    let segment = load_segment(&segment_path, &stopped).unwrap().unwrap();
+    // Graph with the new links is saved on load
+    assert!(!relinked_points_path.exists());
+
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    let result = vector_index
+        .search(&[&vector.into()], None, 1, None, &false.into())
+        .unwrap();
+    assert_eq!(result[0][0].idx, updated_offset);
+}
+
+fn point_id_to_num(point_id: PointIdType) -> u64 {
+    match point_id {
+        PointIdType::NumId(num) => num,
+        PointIdType::Uuid(_) => unreachable!(),
+    }
+}
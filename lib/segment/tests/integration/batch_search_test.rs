@@ -43,6 +43,8 @@ fn test_batch_and_single_request_equivalency() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -143,6 +145,7 @@ fn test_batch_and_single_request_equivalency() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -157,6 +160,7 @@ fn test_batch_and_single_request_equivalency() {
         quantized_vectors.clone(),
         payload_index_ptr,
         hnsw_config,
+        false,
     )
     .unwrap();
 
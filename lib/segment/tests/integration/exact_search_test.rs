@@ -51,6 +51,8 @@ fn exact_search_test() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -85,6 +87,7 @@ fn exact_search_test() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -101,6 +104,7 @@ fn exact_search_test() {
             .clone(),
         payload_index_ptr.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::types::PayloadSchemaType::{Bool, Float, Integer, Keyword};
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, Indexes, Payload, Range, SegmentConfig,
+    VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::utils::path;
+
+const DIM: usize = 4;
+const NUM_POINTS: u64 = 1000;
+
+const COLORS: [&str; 5] = ["red", "green", "blue", "black", "white"];
+
+/// Payload values of a single point, to count facets by brute force
+struct PointValues {
+    colors: Vec<&'static str>,
+    size: i64,
+    flag: bool,
+    price: f64,
+}
+
+fn build_facet_segment(segment_path: &Path) -> (Segment, HashMap<u64, PointValues>) {
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(segment_path, &config, true).unwrap();
+
+    let mut opnum = 0;
+    segment
+        .create_field_index(opnum, &path("color"), Some(&Keyword.into()))
+        .unwrap();
+    segment
+        .create_field_index(opnum, &path("size"), Some(&Integer.into()))
+        .unwrap();
+    segment
+        .create_field_index(opnum, &path("flag"), Some(&Bool.into()))
+        .unwrap();
+    segment
+        .create_field_index(opnum, &path("price"), Some(&Float.into()))
+        .unwrap();
+
+    let mut points = HashMap::new();
+    for idx in 0..NUM_POINTS {
+        opnum += 1;
+        let colors_count = rnd.gen_range(1..=3);
+        // Duplicated values of a point must be counted once
+        let colors: Vec<_> = (0..colors_count)
+            .map(|_| *COLORS.choose(&mut rnd).unwrap())
+            .collect();
+        let values = PointValues {
+            colors,
+            size: rnd.gen_range(0..10),
+            flag: rnd.gen_bool(0.3),
+            price: rnd.gen_range(0.0..100.0),
+        };
+        let payload: Payload = json!({
+            "color": values.colors,
+            "size": values.size,
+            "flag": values.flag,
+            "price": values.price,
+        })
+        .into();
+
+        segment
+            .upsert_point(
+                opnum,
+                idx.into(),
+                only_default_vector(&random_vector(&mut rnd, DIM)),
+            )
+            .unwrap();
+        segment
+            .set_full_payload(opnum, idx.into(), &payload)
+            .unwrap();
+        points.insert(idx, values);
+    }
+
+    // Deleted points must not be counted
+    for idx in (0..NUM_POINTS).step_by(7) {
+        opnum += 1;
+        segment.delete_point(opnum, idx.into()).unwrap();
+        points.remove(&idx);
+    }
+
+    (segment, points)
+}
+
+fn flag_filter(flag: bool) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_match(
+        path("flag"),
+        flag.into(),
+    )))
+}
+
+#[test]
+fn test_facet_values() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let (segment, points) = build_facet_segment(dir.path());
+    let is_stopped = AtomicBool::new(false);
+
+    for (filter, exact) in [(None, true), (None, false), (Some(flag_filter(true)), true)] {
+        let params = FacetParams {
+            key: path("color"),
+            filter: filter.clone(),
+            exact,
+            ranges: vec![],
+        };
+        let counts = segment.facet(&params, &is_stopped).unwrap();
+
+        let only_flag = filter.is_some();
+        let mut expected: HashMap<FacetValue, usize> = HashMap::new();
+        for values in points.values().filter(|values| !only_flag || values.flag) {
+            let mut colors = values.colors.clone();
+            colors.sort_unstable();
+            colors.dedup();
+            for color in colors {
+                *expected
+                    .entry(FacetValue::Keyword(color.to_string()))
+                    .or_default() += 1;
+            }
+        }
+        assert_eq!(
+            counts.values, expected,
+            "filter: {filter:?}, exact: {exact}"
+        );
+    }
+
+    let params = FacetParams {
+        key: path("size"),
+        filter: Some(flag_filter(false)),
+        exact: true,
+        ranges: vec![],
+    };
+    let counts = segment.facet(&params, &is_stopped).unwrap();
+    let mut expected: HashMap<FacetValue, usize> = HashMap::new();
+    for values in points.values().filter(|values| !values.flag) {
+        *expected.entry(FacetValue::Int(values.size)).or_default() += 1;
+    }
+    assert_eq!(counts.values, expected);
+
+    let params = FacetParams {
+        key: path("flag"),
+        filter: None,
+        exact: false,
+        ranges: vec![],
+    };
+    let counts = segment.facet(&params, &is_stopped).unwrap();
+    let trues = points.values().filter(|values| values.flag).count();
+    assert_eq!(counts.values[&FacetValue::Bool(true)], trues);
+    assert_eq!(
+        counts.values[&FacetValue::Bool(false)],
+        points.len() - trues
+    );
+
+    // Facets require a suitable index
+    let params = FacetParams {
+        key: path("price"),
+        filter: None,
+        exact: true,
+        ranges: vec![],
+    };
+    assert!(segment.facet(&params, &is_stopped).is_err());
+}
+
+#[test]
+fn test_facet_ranges() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let (segment, points) = build_facet_segment(dir.path());
+    let is_stopped = AtomicBool::new(false);
+
+    let ranges = vec![
+        Range {
+            lt: Some(25.0),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+        Range {
+            lt: Some(75.0),
+            gt: None,
+            gte: Some(25.0),
+            lte: None,
+        },
+        Range {
+            lt: None,
+            gt: None,
+            gte: Some(75.0),
+            lte: None,
+        },
+    ];
+
+    let params = FacetParams {
+        key: path("price"),
+        filter: Some(flag_filter(true)),
+        exact: true,
+        ranges: ranges.clone(),
+    };
+    let counts = segment.facet(&params, &is_stopped).unwrap();
+    let expected: Vec<_> = ranges
+        .iter()
+        .map(|range| {
+            points
+                .values()
+                .filter(|values| values.flag && range.check_range(values.price))
+                .count()
+        })
+        .collect();
+    assert_eq!(counts.ranges, expected);
+    assert!(counts.values.is_empty());
+
+    // Estimation is based on the histogram, it is only expected to be close
+    let params = FacetParams {
+        exact: false,
+        filter: None,
+        ..params
+    };
+    let estimated = segment.facet(&params, &is_stopped).unwrap();
+    assert_eq!(estimated.ranges.len(), ranges.len());
+    for (range, estimated_count) in ranges.iter().zip(estimated.ranges) {
+        let exact_count = points
+            .values()
+            .filter(|values| range.check_range(values.price))
+            .count();
+        let error = (estimated_count as f64 - exact_count as f64).abs();
+        assert!(
+            error <= exact_count as f64 * 0.2 + 10.0,
+            "range: {range:?}, estimated: {estimated_count}, exact: {exact_count}"
+        );
+    }
+}
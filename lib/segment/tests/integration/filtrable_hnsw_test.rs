@@ -115,6 +115,8 @@ fn _test_filterable_hnsw(
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -148,6 +150,7 @@ fn _test_filterable_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -162,6 +165,7 @@ fn _test_filterable_hnsw(
         quantized_vectors.clone(),
         payload_index_ptr.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
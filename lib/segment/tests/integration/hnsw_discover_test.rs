@@ -77,6 +77,8 @@ fn hnsw_discover_precision() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -103,6 +105,7 @@ fn hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -117,6 +120,7 @@ fn hnsw_discover_precision() {
         quantized_vectors.clone(),
         payload_index_ptr.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
@@ -187,6 +191,8 @@ fn filtered_hnsw_discover_precision() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -220,6 +226,7 @@ fn filtered_hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -234,6 +241,7 @@ fn filtered_hnsw_discover_precision() {
         quantized_vectors.clone(),
         payload_index_ptr.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
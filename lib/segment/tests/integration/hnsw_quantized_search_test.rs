@@ -71,6 +71,8 @@ fn hnsw_quantized_search_test(
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -122,6 +124,7 @@ fn hnsw_quantized_search_test(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -138,6 +141,7 @@ fn hnsw_quantized_search_test(
             .clone(),
         segment.payload_index.clone(),
         hnsw_config,
+        false,
     )
     .unwrap();
 
@@ -418,6 +422,7 @@ fn test_build_hnsw_using_quantization() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     });
 
     let permit_cpu_count = num_rayon_threads(0);
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::ScoreType;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{MultiDenseVector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, MultiVectorConfig, SearchParams, SegmentConfig, SeqNumberType,
+    VectorDataConfig, VectorStorageType,
+};
+use tempfile::Builder;
+
+fn random_multi_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> MultiDenseVector {
+    let vectors_count = rnd.gen_range(1..=5);
+    MultiDenseVector::try_from_matrix(
+        (0..vectors_count)
+            .map(|_| random_vector(rnd, dim))
+            .collect(),
+    )
+    .unwrap()
+}
+
+/// Sum over query vectors of the best dot product against stored vectors
+fn max_sim_dot(query: &MultiDenseVector, stored: &MultiDenseVector) -> ScoreType {
+    query
+        .multi_vectors()
+        .map(|q| {
+            stored
+                .multi_vectors()
+                .map(|s| q.iter().zip(s).map(|(a, b)| a * b).sum::<ScoreType>())
+                .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+        })
+        .sum()
+}
+
+fn assert_scores_eq(actual: &[ScoreType], expected: &[ScoreType]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-4, "score {a} is not equal to {e}");
+    }
+}
+
+#[test]
+fn multivector_search_test() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 1_000;
+    let top = 5;
+    let multi_vector_config = MultiVectorConfig::default();
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: Some(multi_vector_config),
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut stored = Vec::new();
+    for n in 0..num_vectors {
+        let vector = random_multi_vector(&mut rnd, dim);
+        let mut named_vectors = NamedVectors::default();
+        named_vectors.insert(DEFAULT_VECTOR_NAME.to_owned(), vector.clone().into());
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), named_vectors)
+            .unwrap();
+        stored.push(vector);
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 16,
+        full_scan_threshold: 1,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+        false,
+    )
+    .unwrap();
+
+    hnsw_index.build_index(permit, &stopped).unwrap();
+
+    let attempts = 20;
+    for _ in 0..attempts {
+        let query = random_multi_vector(&mut rnd, dim);
+
+        // Brute force MaxSim over all stored multi-vectors
+        let expected_scores = stored
+            .iter()
+            .map(|vector| max_sim_dot(&query, vector))
+            .sorted_by(|a, b| b.total_cmp(a))
+            .take(top)
+            .collect_vec();
+
+        let query: QueryVector = query.into();
+
+        let plain_result = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &false.into(),
+                &false.into(),
+                None,
+                top,
+                None,
+                &stopped,
+            )
+            .unwrap();
+        let plain_scores = plain_result.iter().map(|p| p.score).collect_vec();
+        assert_scores_eq(&plain_scores, &expected_scores);
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                None,
+                top,
+                Some(&SearchParams {
+                    exact: true,
+                    ..Default::default()
+                }),
+                &false.into(),
+            )
+            .unwrap();
+        let index_scores = index_result[0].iter().map(|p| p.score).collect_vec();
+        assert_scores_eq(&index_scores, &expected_scores);
+    }
+}
+
+#[test]
+fn multivector_euclid_score_test() {
+    let stopped = AtomicBool::new(false);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: 2,
+                distance: Distance::Euclid,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: Some(MultiVectorConfig::default()),
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let stored = MultiDenseVector::try_from_matrix(vec![vec![1.0, 1.0]]).unwrap();
+    let mut named_vectors = NamedVectors::default();
+    named_vectors.insert(DEFAULT_VECTOR_NAME.to_owned(), stored.into());
+    segment.upsert_point(1, 1.into(), named_vectors).unwrap();
+
+    // Query vectors are at distances 3 and 4 from the stored one
+    let query: QueryVector =
+        MultiDenseVector::try_from_matrix(vec![vec![4.0, 1.0], vec![1.0, 5.0]])
+            .unwrap()
+            .into();
+    let result = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query,
+            &false.into(),
+            &false.into(),
+            None,
+            1,
+            None,
+            &stopped,
+        )
+        .unwrap();
+
+    // Sum of distances, negated so larger is better, the same as single-vector Euclid units
+    assert_scores_eq(&[result[0].score], &[-7.0]);
+}
@@ -54,6 +54,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -189,6 +191,8 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
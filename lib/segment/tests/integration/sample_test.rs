@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::types::PayloadSchemaType::Integer;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, Indexes, Payload, PointIdType, Range,
+    SegmentConfig, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::utils::path;
+
+const DIM: usize = 4;
+const NUM_POINTS: u64 = 1000;
+const NUM_GROUPS: u64 = 10;
+
+/// Segment, where `group` of the point is its id modulo [`NUM_GROUPS`] and every 7th point is deleted
+fn build_sample_segment(segment_path: &Path) -> Segment {
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(segment_path, &config, true).unwrap();
+
+    let mut opnum = 0;
+    segment
+        .create_field_index(opnum, &path("group"), Some(&Integer.into()))
+        .unwrap();
+
+    for idx in 0..NUM_POINTS {
+        opnum += 1;
+        let payload: Payload = json!({ "group": idx % NUM_GROUPS }).into();
+        segment
+            .upsert_point(
+                opnum,
+                idx.into(),
+                only_default_vector(&random_vector(&mut rnd, DIM)),
+            )
+            .unwrap();
+        segment
+            .set_full_payload(opnum, idx.into(), &payload)
+            .unwrap();
+    }
+
+    for idx in (0..NUM_POINTS).step_by(7) {
+        opnum += 1;
+        segment.delete_point(opnum, idx.into()).unwrap();
+    }
+
+    segment
+}
+
+fn groups_below(group: u64) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_range(
+        path("group"),
+        Range {
+            lt: Some(group as f64),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    )))
+}
+
+fn sampled_ids(
+    segment: &Segment,
+    limit: usize,
+    filter: Option<&Filter>,
+    seed: u64,
+) -> Vec<PointIdType> {
+    let sample = segment.read_random_filtered(limit, filter, seed);
+    assert!(
+        sample.windows(2).all(|pair| pair[0].key <= pair[1].key),
+        "sample must be ordered by keys",
+    );
+    sample.into_iter().map(|point| point.id).collect()
+}
+
+fn is_valid(id: PointIdType, groups: u64) -> bool {
+    let PointIdType::NumId(id) = id else {
+        return false;
+    };
+    id % 7 != 0 && id % NUM_GROUPS < groups
+}
+
+#[test]
+fn test_sample_points() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment = build_sample_segment(dir.path());
+
+    // Whole segment, broad filter and narrow filter are sampled in different ways
+    let cases = [
+        (None, NUM_GROUPS),
+        (Some(groups_below(8)), 8),
+        (Some(groups_below(1)), 1),
+    ];
+    for (filter, groups) in cases {
+        let sample = sampled_ids(&segment, 50, filter.as_ref(), 1);
+        assert_eq!(sample.len(), 50);
+        assert_eq!(sample.iter().collect::<HashSet<_>>().len(), 50);
+        assert!(sample.iter().all(|id| is_valid(*id, groups)));
+
+        // Same seed gives the same sample, other seed gives another one
+        assert_eq!(sampled_ids(&segment, 50, filter.as_ref(), 1), sample);
+        assert_ne!(sampled_ids(&segment, 50, filter.as_ref(), 2), sample);
+    }
+
+    // Can't sample more points than there are
+    let all_matching = (0..NUM_POINTS)
+        .filter(|id| is_valid(PointIdType::NumId(*id), 1))
+        .count();
+    let sample = sampled_ids(&segment, 1000, Some(&groups_below(1)), 1);
+    assert_eq!(sample.len(), all_matching);
+
+    assert!(sampled_ids(&segment, 0, None, 1).is_empty());
+}
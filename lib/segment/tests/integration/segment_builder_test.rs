@@ -6,12 +6,17 @@ use std::time::{Duration, Instant};
 use common::cpu::CpuPermit;
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
-use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
 use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, VectorDataConfig, VectorStorageType,
+};
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_2, empty_segment};
@@ -90,6 +95,8 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool)
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Hnsw(Default::default()),
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -194,3 +201,112 @@ fn test_building_cancellation() {
         was_cancelled_later,
     );
 }
+
+#[test]
+fn test_building_with_old_graph() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+    let mut rnd = rand::thread_rng();
+
+    let dim = 8;
+    let top = 5;
+    let attempts = 50;
+
+    let plain_config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+    let mut hnsw_config = plain_config.clone();
+    hnsw_config
+        .vector_data
+        .get_mut(DEFAULT_VECTOR_NAME)
+        .unwrap()
+        .index = Indexes::Hnsw(HnswConfig {
+        full_scan_threshold: 1, // KB
+        ..Default::default()
+    });
+
+    let build = |segments: &[&Segment]| {
+        let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &hnsw_config).unwrap();
+        for segment in segments {
+            builder.update_from(segment, &stopped).unwrap();
+        }
+        let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+        builder.build(permit, &stopped).unwrap()
+    };
+
+    let mut big_segment = build_segment(dir.path(), &plain_config, true).unwrap();
+    for idx in 0..1000 {
+        big_segment
+            .upsert_point(
+                1,
+                idx.into(),
+                only_default_vector(&random_vector(&mut rnd, dim)),
+            )
+            .unwrap();
+    }
+    let mut small_segment = build_segment(dir.path(), &plain_config, true).unwrap();
+    for idx in 900..1200 {
+        small_segment
+            .upsert_point(
+                2,
+                idx.into(),
+                only_default_vector(&random_vector(&mut rnd, dim)),
+            )
+            .unwrap();
+    }
+
+    let mut indexed_segment = build(&[&big_segment]);
+    for idx in 0..100 {
+        indexed_segment.delete_point(3, idx.into()).unwrap();
+    }
+
+    // Graph of the indexed segment is reused, points of the small segment are inserted
+    let merged_segment = build(&[&indexed_segment, &small_segment]);
+    assert_eq!(merged_segment.available_point_count(), 1100);
+
+    let vector_index = merged_segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    assert_eq!(vector_index.indexed_vector_count(), 1100);
+
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = QueryVector::from(random_vector(&mut rnd, dim));
+        let search = |exact| {
+            let params = SearchParams {
+                hnsw_ef: Some(64),
+                exact,
+                ..Default::default()
+            };
+            vector_index
+                .search(&[&query], None, top, Some(&params), &false.into())
+                .unwrap()
+                .pop()
+                .unwrap()
+        };
+
+        let exact_result = search(true);
+        hits += search(false)
+            .iter()
+            .filter(|point| exact_result.contains(point))
+            .count();
+    }
+
+    let recall = hits as f64 / (attempts * top) as f64;
+    assert!(recall > 0.9, "recall is too low: {recall}");
+}
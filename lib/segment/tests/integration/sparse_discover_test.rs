@@ -121,10 +121,10 @@ fn sparse_index_discover_test() {
         sparse_vector_data: HashMap::from([(
             SPARSE_VECTOR_NAME.to_owned(),
             SparseVectorDataConfig {
-                index: SparseIndexConfig {
-                    full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-                    index_type: SparseIndexType::MutableRam,
-                },
+                index: SparseIndexConfig::new(
+                    Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+                    SparseIndexType::MutableRam,
+                ),
             },
         )]),
         payload_storage_type: Default::default(),
@@ -138,6 +138,8 @@ fn sparse_index_discover_test() {
                 storage_type: VectorStorageType::Memory,
                 index: Indexes::Plain {},
                 quantization_config: None,
+                multivector_config: None,
+                datatype: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -166,10 +168,10 @@ fn sparse_index_discover_test() {
 
     let vector_storage = &sparse_segment.vector_data[SPARSE_VECTOR_NAME].vector_storage;
     let mut sparse_index = SparseVectorIndex::<InvertedIndexRam>::open(
-        SparseIndexConfig {
-            full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-            index_type: SparseIndexType::ImmutableRam,
-        },
+        SparseIndexConfig::new(
+            Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+            SparseIndexType::ImmutableRam,
+        ),
         sparse_segment.id_tracker.clone(),
         vector_storage.clone(),
         payload_index_ptr.clone(),
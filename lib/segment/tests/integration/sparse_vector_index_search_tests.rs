@@ -9,6 +9,7 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use segment::common::operation_error::OperationResult;
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::sparse_statistics::SparseVectorStatistics;
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::payload_fixtures::STR_KEY;
@@ -29,9 +30,11 @@ use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
 use sparse::common::sparse_vector_fixture::{random_full_sparse_vector, random_sparse_vector};
 use sparse::common::types::DimId;
+use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use sparse::index::inverted_index::InvertedIndex;
+use sparse::index::posting_list_common::PostingListIter;
 use tempfile::Builder;
 
 use crate::utils::path;
@@ -151,17 +154,19 @@ fn check_index_storage_consistency<T: InvertedIndex>(sparse_vector_index: &Spars
             .iter()
             .zip(remapped_vector.values.iter())
         {
-            let posting_list = sparse_vector_index.inverted_index.get(dim_id).unwrap();
+            let mut posting_list = sparse_vector_index.inverted_index.get(dim_id).unwrap();
+            let mut elements = Vec::new();
+            posting_list.for_each_till_id(
+                PointOffsetType::MAX,
+                &mut elements,
+                |elements, record_id, weight| elements.push((record_id, weight)),
+            );
             // assert posting list sorted by record id
-            assert!(posting_list
-                .elements
-                .windows(2)
-                .all(|w| w[0].record_id < w[1].record_id));
+            assert!(elements.windows(2).all(|w| w[0].0 < w[1].0));
             // assert posted list contains record id
-            assert!(posting_list
-                .elements
+            assert!(elements
                 .iter()
-                .any(|e| e.record_id == id && e.weight == *dim_value));
+                .any(|&(record_id, weight)| record_id == id && weight == *dim_value));
         }
         // check the vector can be found via search using large top
         let top = sparse_vector_index.max_result_count(vector);
@@ -212,7 +217,7 @@ fn sparse_vector_index_consistent_with_storage() {
 
     // build index
     sparse_vector_mmap_index
-        .build_index(permit, &stopped)
+        .build_index(permit.clone(), &stopped)
         .unwrap();
 
     assert_eq!(
@@ -223,6 +228,31 @@ fn sparse_vector_index_consistent_with_storage() {
     // check consistency with underlying mmap inverted index
     check_index_storage_consistency(&sparse_vector_mmap_index);
 
+    // create and check compressed mmap sparse vector index
+    let compressed_mmap_index_dir = Builder::new()
+        .prefix("compressed_mmap_index_dir")
+        .tempdir()
+        .unwrap();
+    let mut sparse_vector_compressed_mmap_index: SparseVectorIndex<
+        InvertedIndexCompressedMmap<f32>,
+    > = SparseVectorIndex::open(
+        sparse_index_config,
+        sparse_vector_ram_index.id_tracker.clone(),
+        sparse_vector_ram_index.vector_storage.clone(),
+        sparse_vector_ram_index.payload_index.clone(),
+        compressed_mmap_index_dir.path(),
+        &stopped,
+    )
+    .unwrap();
+    sparse_vector_compressed_mmap_index
+        .build_index(permit, &stopped)
+        .unwrap();
+    assert_eq!(
+        sparse_vector_compressed_mmap_index.indexed_vector_count(),
+        sparse_vector_ram_index.indexed_vector_count()
+    );
+    check_index_storage_consistency(&sparse_vector_compressed_mmap_index);
+
     // drop and reload index
     drop(sparse_vector_mmap_index);
 
@@ -361,6 +391,71 @@ fn sparse_vector_index_ram_deleted_points_search() {
         .all(|x| x.iter().all(|y| y.idx != deleted_idx)));
 }
 
+#[test]
+fn sparse_vector_index_statistics_exclude_deleted_points() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
+
+    let sparse_vector_index = fixture_sparse_index_ram(
+        &mut rnd,
+        NUM_VECTORS,
+        MAX_SPARSE_DIM,
+        LOW_FULL_SCAN_THRESHOLD,
+        data_dir.path(),
+        &stopped,
+    );
+
+    // Pick a point, which contributes to document frequencies
+    let (deleted_idx, deleted_vector) = {
+        let vector_storage = sparse_vector_index.vector_storage.borrow();
+        (0..NUM_VECTORS as PointOffsetType)
+            .map(|idx| {
+                let vector = vector_storage.get_vector(idx);
+                let vector: &SparseVector = vector.as_vec_ref().try_into().unwrap();
+                (idx, vector.clone())
+            })
+            .find(|(_, vector)| !vector.indices.is_empty())
+            .unwrap()
+    };
+
+    let collect_statistics = || {
+        let mut statistics =
+            SparseVectorStatistics::for_dimensions(deleted_vector.indices.iter().copied());
+        sparse_vector_index.fill_statistics(&mut statistics);
+        statistics
+    };
+
+    let before_deletion = collect_statistics();
+
+    // Immutable index keeps the posting of the deleted point until it is rebuilt
+    let deleted_external = sparse_vector_index
+        .id_tracker
+        .borrow()
+        .external_id(deleted_idx)
+        .unwrap();
+    sparse_vector_index
+        .id_tracker
+        .borrow_mut()
+        .drop(deleted_external)
+        .unwrap();
+
+    let after_deletion = collect_statistics();
+    // Cached frequencies are reused until the next change
+    assert_eq!(collect_statistics(), after_deletion);
+    assert_eq!(
+        after_deletion.indexed_vector_count,
+        before_deletion.indexed_vector_count - 1,
+    );
+    for dim in &deleted_vector.indices {
+        assert_eq!(
+            after_deletion.document_frequencies[dim],
+            before_deletion.document_frequencies[dim] - 1,
+        );
+    }
+}
+
 #[test]
 fn sparse_vector_index_ram_filtered_search() {
     let stopped = AtomicBool::new(false);
@@ -583,10 +678,10 @@ fn sparse_vector_index_persistence_test() {
         sparse_vector_data: HashMap::from([(
             SPARSE_VECTOR_NAME.to_owned(),
             SparseVectorDataConfig {
-                index: SparseIndexConfig {
-                    full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-                    index_type: SparseIndexType::MutableRam,
-                },
+                index: SparseIndexConfig::new(
+                    Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+                    SparseIndexType::MutableRam,
+                ),
             },
         )]),
         payload_storage_type: Default::default(),
@@ -654,10 +749,10 @@ fn sparse_vector_index_persistence_test() {
         .tempdir()
         .unwrap();
     let mut sparse_vector_index_ram: SparseVectorIndex<InvertedIndexRam> = SparseVectorIndex::open(
-        SparseIndexConfig {
-            full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-            index_type: SparseIndexType::ImmutableRam,
-        },
+        SparseIndexConfig::new(
+            Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+            SparseIndexType::ImmutableRam,
+        ),
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
             .vector_storage
@@ -675,10 +770,10 @@ fn sparse_vector_index_persistence_test() {
     // reload sparse index from file
     drop(sparse_vector_index_ram);
     let sparse_vector_index_ram: SparseVectorIndex<InvertedIndexRam> = SparseVectorIndex::open(
-        SparseIndexConfig {
-            full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-            index_type: SparseIndexType::ImmutableRam,
-        },
+        SparseIndexConfig::new(
+            Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+            SparseIndexType::ImmutableRam,
+        ),
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
             .vector_storage
@@ -714,10 +809,10 @@ fn sparse_vector_index_persistence_test() {
         .unwrap();
     let mut sparse_vector_index_mmap: SparseVectorIndex<InvertedIndexMmap> =
         SparseVectorIndex::open(
-            SparseIndexConfig {
-                full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-                index_type: SparseIndexType::Mmap,
-            },
+            SparseIndexConfig::new(
+                Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+                SparseIndexType::Mmap,
+            ),
             segment.id_tracker.clone(),
             segment.vector_data[SPARSE_VECTOR_NAME]
                 .vector_storage
@@ -736,10 +831,10 @@ fn sparse_vector_index_persistence_test() {
     // reload sparse index from file
     drop(sparse_vector_index_mmap);
     let sparse_vector_index_mmap: SparseVectorIndex<InvertedIndexMmap> = SparseVectorIndex::open(
-        SparseIndexConfig {
-            full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-            index_type: SparseIndexType::Mmap,
-        },
+        SparseIndexConfig::new(
+            Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+            SparseIndexType::Mmap,
+        ),
         segment.id_tracker.clone(),
         segment.vector_data[SPARSE_VECTOR_NAME]
             .vector_storage
@@ -854,10 +949,10 @@ fn sparse_vector_test_large_index() {
         sparse_vector_data: HashMap::from([(
             SPARSE_VECTOR_NAME.to_owned(),
             SparseVectorDataConfig {
-                index: SparseIndexConfig {
-                    full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
-                    index_type: SparseIndexType::MutableRam,
-                },
+                index: SparseIndexConfig::new(
+                    Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+                    SparseIndexType::MutableRam,
+                ),
             },
         )]),
         payload_storage_type: Default::default(),
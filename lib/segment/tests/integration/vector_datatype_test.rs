@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use common::types::ScoreType;
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rstest::rstest;
+use segment::data_types::vectors::{only_default_vector, DenseVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageDatatype,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+/// Vector with small integer components, which are exactly representable in every datatype
+fn random_integer_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> DenseVector {
+    (0..dim).map(|_| rnd.gen_range(0..16) as f32).collect()
+}
+
+#[rstest]
+#[case::float32_memory(VectorStorageDatatype::Float32, VectorStorageType::Memory)]
+#[case::float16_memory(VectorStorageDatatype::Float16, VectorStorageType::Memory)]
+#[case::uint8_memory(VectorStorageDatatype::Uint8, VectorStorageType::Memory)]
+#[case::float16_chunked_mmap(VectorStorageDatatype::Float16, VectorStorageType::ChunkedMmap)]
+#[case::uint8_chunked_mmap(VectorStorageDatatype::Uint8, VectorStorageType::ChunkedMmap)]
+fn vector_datatype_search_test(
+    #[case] datatype: VectorStorageDatatype,
+    #[case] storage_type: VectorStorageType,
+) {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 40;
+    let num_vectors: u64 = 200;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: Some(datatype),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut stored = Vec::new();
+    for n in 0..num_vectors {
+        let vector = random_integer_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+        stored.push(vector);
+    }
+
+    // Stored vectors are converted back to float32 without loss
+    let vector = segment.vector(DEFAULT_VECTOR_NAME, 0.into()).unwrap();
+    assert_eq!(vector, Some(stored[0].clone().into()));
+
+    for _ in 0..10 {
+        let query = random_integer_vector(&mut rnd, dim);
+
+        let expected_scores = stored
+            .iter()
+            .map(|vector| {
+                vector
+                    .iter()
+                    .zip(&query)
+                    .map(|(a, b)| a * b)
+                    .sum::<ScoreType>()
+            })
+            .sorted_by(|a, b| b.total_cmp(a))
+            .take(top)
+            .collect_vec();
+
+        let result = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query.into(),
+                &false.into(),
+                &false.into(),
+                None,
+                top,
+                None,
+                &stopped,
+            )
+            .unwrap();
+        let scores = result.iter().map(|p| p.score).collect_vec();
+        assert_eq!(scores, expected_scores);
+    }
+}
+
+#[test]
+fn multivector_requires_float32_test() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: 4,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: Some(Default::default()),
+                datatype: Some(VectorStorageDatatype::Uint8),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    assert!(build_segment(dir.path(), &config, true).is_err());
+}
+
+#[test]
+fn uint8_vector_elements_test() {
+    let stopped = AtomicBool::new(false);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: 4,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: Some(VectorStorageDatatype::Uint8),
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    // Values, which would be changed by the conversion to bytes, are rejected
+    let invalid_vectors = [
+        vec![0.9, 1.0, 2.0, 3.0],
+        vec![-1.0, 1.0, 2.0, 3.0],
+        vec![256.0, 1.0, 2.0, 3.0],
+        vec![f32::NAN, 1.0, 2.0, 3.0],
+    ];
+    for (n, vector) in invalid_vectors.iter().enumerate() {
+        let result =
+            segment.upsert_point(n as SeqNumberType, 1.into(), only_default_vector(vector));
+        assert!(result.is_err(), "vector {vector:?} must be rejected");
+    }
+
+    segment
+        .upsert_point(10, 1.into(), only_default_vector(&[1.0, 2.0, 3.0, 255.0]))
+        .unwrap();
+
+    // Query values are rounded to the nearest byte: 0.6 -> 1, 1.4 -> 1
+    let query: DenseVector = vec![0.6, 1.4, 0.0, 0.0];
+    let result = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query.into(),
+            &false.into(),
+            &false.into(),
+            None,
+            1,
+            None,
+            &stopped,
+        )
+        .unwrap();
+    assert_eq!(result[0].score, 3.0);
+}
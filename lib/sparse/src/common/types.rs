@@ -1,2 +1,119 @@
+use std::fmt::Debug;
+
+use half::f16;
+
 pub type DimId = u32;
 pub type DimWeight = f32;
+
+/// Type of weights stored in posting lists of the inverted index.
+///
+/// Weights may be stored with reduced precision to save memory. Quantization parameters are
+/// computed per posting list and are required to restore the original weight.
+pub trait Weight: Copy + PartialEq + Debug + Send + Sync + 'static {
+    type QuantizationParams: Copy + PartialEq + Debug + Default + Send + Sync + 'static;
+
+    /// Compute quantization parameters for the weights of a single posting list
+    fn quantization_params(weights: impl Iterator<Item = DimWeight>) -> Self::QuantizationParams;
+
+    fn from_f32(params: Self::QuantizationParams, value: DimWeight) -> Self;
+
+    fn to_f32(self, params: Self::QuantizationParams) -> DimWeight;
+}
+
+impl Weight for f32 {
+    type QuantizationParams = ();
+
+    fn quantization_params(_weights: impl Iterator<Item = DimWeight>) {}
+
+    fn from_f32(_params: (), value: DimWeight) -> Self {
+        value
+    }
+
+    fn to_f32(self, _params: ()) -> DimWeight {
+        self
+    }
+}
+
+impl Weight for f16 {
+    type QuantizationParams = ();
+
+    fn quantization_params(_weights: impl Iterator<Item = DimWeight>) {}
+
+    fn from_f32(_params: (), value: DimWeight) -> Self {
+        f16::from_f32(value)
+    }
+
+    fn to_f32(self, _params: ()) -> DimWeight {
+        f16::to_f32(self)
+    }
+}
+
+/// Weight, scaled into `[0, 255]` range of the posting list weights
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct QuantizedU8(u8);
+
+/// Linear mapping of quantized values `[0, 255]` to the range `[min, min + 255 * scale]`
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct QuantizedU8Params {
+    min: DimWeight,
+    scale: DimWeight,
+}
+
+impl Weight for QuantizedU8 {
+    type QuantizationParams = QuantizedU8Params;
+
+    fn quantization_params(weights: impl Iterator<Item = DimWeight>) -> QuantizedU8Params {
+        let (min, max) = weights
+            .filter(|weight| weight.is_finite())
+            .fold(None, |range: Option<(DimWeight, DimWeight)>, weight| {
+                Some(match range {
+                    None => (weight, weight),
+                    Some((min, max)) => (min.min(weight), max.max(weight)),
+                })
+            })
+            .unwrap_or_default();
+        QuantizedU8Params {
+            min,
+            scale: (max - min) / u8::MAX as DimWeight,
+        }
+    }
+
+    fn from_f32(params: QuantizedU8Params, value: DimWeight) -> Self {
+        if params.scale == 0.0 {
+            return QuantizedU8(0);
+        }
+        // Out of range values, like `-inf` for the missing `max_next_weight`, are clamped
+        let quantized = ((value - params.min) / params.scale).round();
+        QuantizedU8(quantized.clamp(0.0, u8::MAX as DimWeight) as u8)
+    }
+
+    fn to_f32(self, params: QuantizedU8Params) -> DimWeight {
+        params.min + self.0 as DimWeight * params.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantized_u8_roundtrip() {
+        let weights = [0.5, 1.0, 3.0, 10.5];
+        let params = QuantizedU8::quantization_params(weights.iter().copied());
+
+        for weight in weights {
+            let restored = QuantizedU8::from_f32(params, weight).to_f32(params);
+            assert!((restored - weight).abs() <= params.scale / 2.0 + f32::EPSILON);
+        }
+        assert_eq!(QuantizedU8::from_f32(params, 0.5), QuantizedU8(0));
+        assert_eq!(QuantizedU8::from_f32(params, 10.5), QuantizedU8(u8::MAX));
+        assert_eq!(
+            QuantizedU8::from_f32(params, f32::NEG_INFINITY),
+            QuantizedU8(0)
+        );
+
+        // single distinct value is restored exactly
+        let params = QuantizedU8::quantization_params([2.0, 2.0].into_iter());
+        assert_eq!(QuantizedU8::from_f32(params, 2.0).to_f32(params), 2.0);
+    }
+}
@@ -0,0 +1,525 @@
+use bitpacking::BitPacker;
+use common::types::PointOffsetType;
+
+use crate::common::types::{DimWeight, Weight};
+use crate::index::posting_list::{GenericPostingElement, PostingList};
+use crate::index::posting_list_common::{PostingListIter, RecordWeight};
+
+type BitPackerImpl = bitpacking::BitPacker4x;
+
+/// Number of elements in a compressed chunk
+pub const CHUNK_LEN: usize = BitPackerImpl::BLOCK_LEN;
+
+/// Posting list with record ids compressed in chunks of [`CHUNK_LEN`] elements.
+///
+/// Ids of each chunk are delta-encoded and bitpacked. Each chunk keeps the max weight of its
+/// elements, so the search can skip whole chunks which can't contribute to the top results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressedPostingList<W: Weight> {
+    /// Compressed ids of all chunks
+    id_data: Vec<u8>,
+    chunks: Vec<CompressedPostingChunk<W>>,
+    /// Last elements, which are not aligned with the chunk size, are not compressed
+    remainders: Vec<GenericPostingElement<W>>,
+    last_id: Option<PointOffsetType>,
+    params: W::QuantizationParams,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct CompressedPostingChunk<W> {
+    /// First id of the chunk, used for decompression
+    initial: PointOffsetType,
+    /// Offset of the compressed ids of the chunk in the id data
+    offset: u32,
+    /// Max weight of the chunk elements
+    max_weight: W,
+    /// Weights of the chunk elements
+    weights: [W; CHUNK_LEN],
+}
+
+/// Borrowed parts of a compressed posting list, either owned in RAM or stored in mmap
+#[derive(Debug, Clone, Copy)]
+pub struct CompressedPostingListView<'a, W: Weight> {
+    pub(crate) id_data: &'a [u8],
+    pub(crate) chunks: &'a [CompressedPostingChunk<W>],
+    pub(crate) remainders: &'a [GenericPostingElement<W>],
+    pub(crate) last_id: Option<PointOffsetType>,
+    pub(crate) params: W::QuantizationParams,
+}
+
+impl<W: Weight> CompressedPostingList<W> {
+    pub fn from_posting_list(posting_list: &PostingList) -> Self {
+        let elements = &posting_list.elements;
+        let params = W::quantization_params(elements.iter().map(|element| element.weight));
+        let ids: Vec<PointOffsetType> = elements.iter().map(|element| element.record_id).collect();
+
+        // fill chunks data
+        let bitpacker = BitPackerImpl::new();
+        let mut chunks = Vec::with_capacity(elements.len() / CHUNK_LEN);
+        let mut id_data_size = 0;
+        for (chunk_ids, chunk_elements) in ids
+            .chunks_exact(CHUNK_LEN)
+            .zip(elements.chunks_exact(CHUNK_LEN))
+        {
+            let initial = chunk_ids[0];
+            let chunk_bits = bitpacker.num_bits_sorted(initial, chunk_ids);
+            let max_weight = chunk_elements
+                .iter()
+                .map(|element| element.weight)
+                .fold(DimWeight::NEG_INFINITY, DimWeight::max);
+            chunks.push(CompressedPostingChunk {
+                initial,
+                offset: id_data_size as u32,
+                max_weight: W::from_f32(params, max_weight),
+                weights: std::array::from_fn(|i| W::from_f32(params, chunk_elements[i].weight)),
+            });
+            id_data_size += BitPackerImpl::compressed_block_size(chunk_bits);
+        }
+
+        // compress ids
+        let mut id_data = vec![0u8; id_data_size];
+        for (chunk_index, chunk_ids) in ids.chunks_exact(CHUNK_LEN).enumerate() {
+            let chunk = &chunks[chunk_index];
+            let chunk_size = chunk_size(&chunks, id_data.len(), chunk_index);
+            let chunk_bits = (chunk_size * 8) / CHUNK_LEN;
+            bitpacker.compress_sorted(
+                chunk.initial,
+                chunk_ids,
+                &mut id_data[chunk.offset as usize..chunk.offset as usize + chunk_size],
+                chunk_bits as u8,
+            );
+        }
+
+        let remainders = elements
+            .chunks_exact(CHUNK_LEN)
+            .remainder()
+            .iter()
+            .map(|element| GenericPostingElement {
+                record_id: element.record_id,
+                weight: W::from_f32(params, element.weight),
+                max_next_weight: W::from_f32(params, element.max_next_weight),
+            })
+            .collect();
+
+        Self {
+            id_data,
+            chunks,
+            remainders,
+            last_id: ids.last().copied(),
+            params,
+        }
+    }
+
+    pub fn view(&self) -> CompressedPostingListView<W> {
+        CompressedPostingListView {
+            id_data: &self.id_data,
+            chunks: &self.chunks,
+            remainders: &self.remainders,
+            last_id: self.last_id,
+            params: self.params,
+        }
+    }
+
+    pub fn iter(&self) -> CompressedPostingListIterator<W> {
+        self.view().iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.view().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a, W: Weight> CompressedPostingListView<'a, W> {
+    pub fn iter(&self) -> CompressedPostingListIterator<'a, W> {
+        CompressedPostingListIterator {
+            list: *self,
+            unpacked_chunk: None,
+            unpacked_ids: [0; CHUNK_LEN],
+            pos: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.len() * CHUNK_LEN + self.remainders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn to_owned(&self) -> CompressedPostingList<W> {
+        CompressedPostingList {
+            id_data: self.id_data.to_vec(),
+            chunks: self.chunks.to_vec(),
+            remainders: self.remainders.to_vec(),
+            last_id: self.last_id,
+            params: self.params,
+        }
+    }
+
+    fn decompress_chunk(
+        &self,
+        chunk_index: usize,
+        decompressed: &mut [PointOffsetType; CHUNK_LEN],
+    ) {
+        let chunk = &self.chunks[chunk_index];
+        let chunk_size = chunk_size(self.chunks, self.id_data.len(), chunk_index);
+        let chunk_bits = (chunk_size * 8) / CHUNK_LEN;
+        BitPackerImpl::new().decompress_sorted(
+            chunk.initial,
+            &self.id_data[chunk.offset as usize..chunk.offset as usize + chunk_size],
+            decompressed,
+            chunk_bits as u8,
+        );
+    }
+}
+
+fn chunk_size<W>(
+    chunks: &[CompressedPostingChunk<W>],
+    data_len: usize,
+    chunk_index: usize,
+) -> usize {
+    assert!(chunk_index < chunks.len());
+    if chunk_index + 1 < chunks.len() {
+        chunks[chunk_index + 1].offset as usize - chunks[chunk_index].offset as usize
+    } else {
+        data_len - chunks[chunk_index].offset as usize
+    }
+}
+
+/// Iterator over a compressed posting list, which decompresses one chunk at a time
+#[derive(Debug, Clone)]
+pub struct CompressedPostingListIterator<'a, W: Weight> {
+    list: CompressedPostingListView<'a, W>,
+    /// Index of the chunk, which ids are decompressed into `unpacked_ids`
+    unpacked_chunk: Option<usize>,
+    unpacked_ids: [PointOffsetType; CHUNK_LEN],
+    pos: usize,
+}
+
+impl<'a, W: Weight> CompressedPostingListIterator<'a, W> {
+    fn chunks_len(&self) -> usize {
+        self.list.chunks.len() * CHUNK_LEN
+    }
+
+    fn unpack_chunk(&mut self, chunk_index: usize) {
+        if self.unpacked_chunk != Some(chunk_index) {
+            self.list
+                .decompress_chunk(chunk_index, &mut self.unpacked_ids);
+            self.unpacked_chunk = Some(chunk_index);
+        }
+    }
+
+    fn element_at(&mut self, pos: usize) -> Option<RecordWeight> {
+        if pos < self.chunks_len() {
+            let chunk_index = pos / CHUNK_LEN;
+            self.unpack_chunk(chunk_index);
+            let chunk = &self.list.chunks[chunk_index];
+            Some(RecordWeight {
+                record_id: self.unpacked_ids[pos % CHUNK_LEN],
+                weight: chunk.weights[pos % CHUNK_LEN].to_f32(self.list.params),
+            })
+        } else {
+            self.list
+                .remainders
+                .get(pos - self.chunks_len())
+                .map(|element| RecordWeight {
+                    record_id: element.record_id,
+                    weight: element.weight.to_f32(self.list.params),
+                })
+        }
+    }
+
+    /// Advance within the uncompressed remainders, see [`PostingListIter::skip_to`]
+    fn skip_to_in_remainders(&mut self, record_id: PointOffsetType) -> Option<RecordWeight> {
+        let chunks_len = self.chunks_len();
+        let remainders = &self.list.remainders[self.pos - chunks_len..];
+        match remainders.binary_search_by(|element| element.record_id.cmp(&record_id)) {
+            Ok(found_offset) => {
+                self.pos += found_offset;
+                self.element_at(self.pos)
+            }
+            Err(insert_offset) => {
+                self.pos += insert_offset;
+                None
+            }
+        }
+    }
+}
+
+impl<'a, W: Weight> PostingListIter for CompressedPostingListIterator<'a, W> {
+    fn peek(&mut self) -> Option<RecordWeight> {
+        self.element_at(self.pos)
+    }
+
+    fn last_id(&self) -> Option<PointOffsetType> {
+        self.list.last_id
+    }
+
+    fn skip_to(&mut self, record_id: PointOffsetType) -> Option<RecordWeight> {
+        let chunks_len = self.chunks_len();
+        if self.pos < chunks_len {
+            let current_chunk = self.pos / CHUNK_LEN;
+            // last chunk starting at or before `record_id`
+            let chunk_index = match self.list.chunks[current_chunk..]
+                .binary_search_by(|chunk| chunk.initial.cmp(&record_id))
+            {
+                Ok(offset) => current_chunk + offset,
+                // current element is already past `record_id`
+                Err(0) => return None,
+                Err(offset) => current_chunk + offset - 1,
+            };
+
+            let chunk_start = chunk_index * CHUNK_LEN;
+            let search_from = self.pos.max(chunk_start) - chunk_start;
+            self.unpack_chunk(chunk_index);
+            match self.unpacked_ids[search_from..].binary_search(&record_id) {
+                Ok(offset) => {
+                    self.pos = chunk_start + search_from + offset;
+                    return self.element_at(self.pos);
+                }
+                Err(offset) => {
+                    self.pos = chunk_start + search_from + offset;
+                    if self.pos < chunks_len {
+                        // next element is either in this chunk, or in the next chunk,
+                        // which starts after `record_id`
+                        return None;
+                    }
+                    // the rest of the chunks is before `record_id`, continue with remainders
+                }
+            }
+        }
+
+        if self.pos >= self.list.len() {
+            return None;
+        }
+        self.skip_to_in_remainders(record_id)
+    }
+
+    fn skip_to_end(&mut self) {
+        self.pos = self.list.len();
+    }
+
+    fn len_to_end(&self) -> usize {
+        self.list.len() - self.pos
+    }
+
+    fn current_index(&self) -> usize {
+        self.pos
+    }
+
+    fn for_each_till_id<Ctx: ?Sized>(
+        &mut self,
+        id: PointOffsetType,
+        ctx: &mut Ctx,
+        mut f: impl FnMut(&mut Ctx, PointOffsetType, DimWeight),
+    ) {
+        let list = self.list;
+        let chunks_len = self.chunks_len();
+
+        while self.pos < chunks_len {
+            let chunk_index = self.pos / CHUNK_LEN;
+            let chunk = &list.chunks[chunk_index];
+            if chunk.initial > id {
+                return;
+            }
+            self.unpack_chunk(chunk_index);
+            for offset in self.pos % CHUNK_LEN..CHUNK_LEN {
+                let record_id = self.unpacked_ids[offset];
+                if record_id > id {
+                    self.pos = chunk_index * CHUNK_LEN + offset;
+                    return;
+                }
+                f(ctx, record_id, chunk.weights[offset].to_f32(list.params));
+            }
+            self.pos = (chunk_index + 1) * CHUNK_LEN;
+        }
+
+        for element in &list.remainders[self.pos - chunks_len..] {
+            if element.record_id > id {
+                return;
+            }
+            f(ctx, element.record_id, element.weight.to_f32(list.params));
+            self.pos += 1;
+        }
+    }
+
+    fn prune_until(&mut self, until: Option<PointOffsetType>, max_weight: DimWeight) -> bool {
+        let list = self.list;
+        let chunks_len = self.chunks_len();
+        let position_before_pruning = self.pos;
+
+        // skip whole chunks with low max weight
+        while self.pos < chunks_len {
+            let chunk_index = self.pos / CHUNK_LEN;
+            if list.chunks[chunk_index].max_weight.to_f32(list.params) > max_weight {
+                return position_before_pruning != self.pos;
+            }
+
+            let next_chunk_start = list
+                .chunks
+                .get(chunk_index + 1)
+                .map(|chunk| chunk.initial)
+                .or_else(|| list.remainders.first().map(|element| element.record_id));
+            if let Some(until) = until {
+                if next_chunk_start.map_or(true, |next_chunk_start| until <= next_chunk_start) {
+                    // `until` is within the current chunk
+                    self.skip_to(until);
+                    return position_before_pruning != self.pos;
+                }
+            }
+            self.pos = (chunk_index + 1) * CHUNK_LEN;
+        }
+
+        // `max_next_weight` of the remainders bounds all the following elements
+        if let Some(element) = list.remainders.get(self.pos - chunks_len) {
+            let max_weight_from_element = element
+                .weight
+                .to_f32(list.params)
+                .max(element.max_next_weight.to_f32(list.params));
+            if max_weight_from_element <= max_weight {
+                match until {
+                    Some(until) => {
+                        self.skip_to_in_remainders(until);
+                    }
+                    None => self.skip_to_end(),
+                }
+            }
+        }
+        position_before_pruning != self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+
+    use super::*;
+    use crate::index::posting_list::PostingBuilder;
+
+    fn build_posting_list(ids: impl Iterator<Item = PointOffsetType>) -> PostingList {
+        let mut builder = PostingBuilder::new();
+        for id in ids {
+            builder.add(id, (id % 100) as DimWeight);
+        }
+        builder.build()
+    }
+
+    fn collect<I: PostingListIter>(iter: &mut I) -> Vec<RecordWeight> {
+        let mut elements = Vec::new();
+        iter.for_each_till_id(
+            PointOffsetType::MAX,
+            &mut elements,
+            |elements, id, weight| {
+                elements.push(RecordWeight {
+                    record_id: id,
+                    weight,
+                })
+            },
+        );
+        elements
+    }
+
+    #[test]
+    fn test_compressed_posting_list() {
+        for len in [
+            0,
+            1,
+            CHUNK_LEN - 1,
+            CHUNK_LEN,
+            CHUNK_LEN + 1,
+            5 * CHUNK_LEN + 17,
+        ] {
+            let posting_list = build_posting_list((0..len as PointOffsetType).map(|i| i * 3 + 1));
+            let compressed = CompressedPostingList::<f32>::from_posting_list(&posting_list);
+            assert_eq!(compressed.len(), len);
+
+            let expected: Vec<_> = posting_list
+                .elements
+                .iter()
+                .map(|element| RecordWeight {
+                    record_id: element.record_id,
+                    weight: element.weight,
+                })
+                .collect();
+            assert_eq!(collect(&mut compressed.iter()), expected);
+            assert_eq!(
+                compressed.iter().last_id(),
+                expected.last().map(|e| e.record_id)
+            );
+
+            // skip to every present and missing id
+            for element in &expected {
+                let mut iter = compressed.iter();
+                assert_eq!(iter.skip_to(element.record_id), Some(*element));
+                assert_eq!(iter.peek(), Some(*element));
+
+                let mut iter = compressed.iter();
+                assert_eq!(iter.skip_to(element.record_id - 1), None);
+                assert_eq!(iter.peek(), Some(*element));
+            }
+
+            // skip forward through the whole list
+            let mut iter = compressed.iter();
+            for element in expected.iter().step_by(7) {
+                assert_eq!(iter.skip_to(element.record_id), Some(*element));
+            }
+            assert_eq!(iter.skip_to(PointOffsetType::MAX), None);
+            assert_eq!(iter.len_to_end(), 0);
+        }
+    }
+
+    #[test]
+    fn test_compressed_posting_list_quantized() {
+        let posting_list = build_posting_list(0..3 * CHUNK_LEN as PointOffsetType);
+        let compressed = CompressedPostingList::<f16>::from_posting_list(&posting_list);
+        let elements = collect(&mut compressed.iter());
+        assert_eq!(elements.len(), posting_list.elements.len());
+        for (element, expected) in elements.iter().zip(&posting_list.elements) {
+            assert_eq!(element.record_id, expected.record_id);
+            // integers below 2048 are exact in f16
+            assert_eq!(element.weight, expected.weight);
+        }
+    }
+
+    #[test]
+    fn test_compressed_posting_list_prune() {
+        // first chunk has low weights, second chunk has a high weight, remainders are low
+        let mut builder = PostingBuilder::new();
+        for id in 0..2 * CHUNK_LEN as PointOffsetType + 10 {
+            let weight = if id == CHUNK_LEN as PointOffsetType + 5 {
+                10.0
+            } else {
+                1.0
+            };
+            builder.add(id, weight);
+        }
+        let compressed = CompressedPostingList::<f32>::from_posting_list(&builder.build());
+
+        // first chunk is skipped, stops at the chunk with the high weight
+        let mut iter = compressed.iter();
+        assert!(iter.prune_until(None, 5.0));
+        assert_eq!(iter.current_index(), CHUNK_LEN);
+
+        // prune within the first chunk
+        let mut iter = compressed.iter();
+        assert!(iter.prune_until(Some(50), 5.0));
+        assert_eq!(iter.current_index(), 50);
+
+        // nothing is pruned if all weights may contribute
+        let mut iter = compressed.iter();
+        assert!(!iter.prune_until(None, 0.5));
+        assert_eq!(iter.current_index(), 0);
+
+        // remainders are pruned to the end
+        let mut iter = compressed.iter();
+        iter.skip_to(2 * CHUNK_LEN as PointOffsetType);
+        assert!(iter.prune_until(None, 5.0));
+        assert_eq!(iter.len_to_end(), 0);
+    }
+}
@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+
+use super::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
+use crate::common::sparse_vector::SparseVector;
+use crate::common::types::{DimId, Weight};
+use crate::index::compressed_posting_list::{CompressedPostingList, CompressedPostingListIterator};
+use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
+use crate::index::inverted_index::InvertedIndex;
+
+/// Immutable inverted index with compressed posting lists, loaded into RAM.
+///
+/// Persisted in the same format as [`InvertedIndexCompressedMmap`] with the same weight type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvertedIndexCompressedImmutableRam<W: Weight> {
+    /// Posting lists for each dimension flattened (dimension id -> posting list)
+    /// Gaps are filled with empty posting lists
+    pub postings: Vec<CompressedPostingList<W>>,
+    /// Number of unique indexed vectors
+    pub vector_count: usize,
+}
+
+impl<W: Weight> InvertedIndex for InvertedIndexCompressedImmutableRam<W> {
+    type Iter<'a> = CompressedPostingListIterator<'a, W>;
+
+    fn open(path: &Path) -> std::io::Result<Self> {
+        let mmap_inverted_index = InvertedIndexCompressedMmap::<W>::load(path)?;
+        let postings = (0..mmap_inverted_index.file_header.posting_count as DimId)
+            .map(|id| {
+                mmap_inverted_index
+                    .get(&id)
+                    .map(|posting_list| posting_list.to_owned())
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("Posting list {} not found", id),
+                        )
+                    })
+            })
+            .collect::<std::io::Result<_>>()?;
+        Ok(Self {
+            postings,
+            vector_count: mmap_inverted_index.file_header.vector_count,
+        })
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        InvertedIndexCompressedMmap::<W>::save_postings(&self.postings, self.vector_count, path)?;
+        Ok(())
+    }
+
+    fn get<'a>(&'a self, id: &DimId) -> Option<CompressedPostingListIterator<'a, W>> {
+        self.postings
+            .get(*id as usize)
+            .map(|posting_list| posting_list.iter())
+    }
+
+    fn posting_list_len(&self, id: &DimId) -> Option<usize> {
+        self.postings
+            .get(*id as usize)
+            .map(|posting_list| posting_list.len())
+    }
+
+    fn files(path: &Path) -> Vec<PathBuf> {
+        InvertedIndexCompressedMmap::<W>::files(path)
+    }
+
+    fn upsert(&mut self, _id: PointOffsetType, _vector: SparseVector) {
+        panic!("Cannot upsert into a read-only compressed inverted index")
+    }
+
+    fn from_ram_index<P: AsRef<Path>>(
+        ram_index: InvertedIndexRam,
+        _path: P,
+    ) -> std::io::Result<Self> {
+        Ok(Self {
+            postings: ram_index
+                .postings
+                .iter()
+                .map(CompressedPostingList::from_posting_list)
+                .collect(),
+            vector_count: ram_index.vector_count,
+        })
+    }
+
+    fn vector_count(&self) -> usize {
+        self.vector_count
+    }
+
+    fn max_index(&self) -> Option<DimId> {
+        match self.postings.len() {
+            0 => None,
+            len => Some(len as DimId - 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::types::QuantizedU8;
+    use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
+
+    fn check_save_and_open<W: Weight>(inverted_index_ram: &InvertedIndexRam) {
+        let tmp_dir_path = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        let compressed_index = InvertedIndexCompressedImmutableRam::<W>::from_ram_index(
+            inverted_index_ram.clone(),
+            &tmp_dir_path,
+        )
+        .unwrap();
+        compressed_index.save(tmp_dir_path.path()).unwrap();
+
+        let loaded_index =
+            InvertedIndexCompressedImmutableRam::<W>::open(tmp_dir_path.path()).unwrap();
+        assert_eq!(compressed_index, loaded_index);
+        assert_eq!(loaded_index.vector_count, inverted_index_ram.vector_count);
+        assert_eq!(
+            loaded_index.postings.len(),
+            inverted_index_ram.postings.len()
+        );
+    }
+
+    #[test]
+    fn test_inverted_index_compressed_immutable_ram() {
+        let mut builder = InvertedIndexBuilder::new();
+        builder.add(1, [(1, 10.0), (2, 10.0), (3, 10.0), (5, 10.0)].into());
+        builder.add(2, [(1, 20.0), (2, 20.0), (3, 20.0), (5, 20.0)].into());
+        builder.add(3, [(1, 30.0), (2, 30.0), (3, 30.0)].into());
+        builder.add(4, [(1, 1.0), (2, 1.0)].into());
+        builder.add(5, [(1, 2.0)].into());
+        builder.add(6, [(1, 3.0)].into());
+        let inverted_index_ram = builder.build();
+
+        check_save_and_open::<f32>(&inverted_index_ram);
+        check_save_and_open::<f16>(&inverted_index_ram);
+        check_save_and_open::<QuantizedU8>(&inverted_index_ram);
+    }
+}
@@ -0,0 +1,330 @@
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use memmap2::{Mmap, MmapMut};
+use memory::madvise;
+use memory::mmap_ops::{
+    create_and_ensure_length, open_read_mmap, open_write_mmap, transmute_from_u8,
+    transmute_from_u8_to_slice, transmute_to_u8, transmute_to_u8_slice,
+};
+
+use super::inverted_index_mmap::InvertedIndexFileHeader;
+use crate::common::sparse_vector::SparseVector;
+use crate::common::types::{DimId, Weight};
+use crate::index::compressed_posting_list::{
+    CompressedPostingChunk, CompressedPostingList, CompressedPostingListIterator,
+    CompressedPostingListView,
+};
+use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
+use crate::index::inverted_index::InvertedIndex;
+use crate::index::posting_list::GenericPostingElement;
+
+const INDEX_FILE_NAME: &str = "inverted_index_compressed.data";
+const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_compressed_config.json";
+
+/// Alignment of the posting list data in the file
+const POSTING_ALIGNMENT: usize = 8;
+
+/// Inverted flatten index from dimension id to compressed posting list
+///
+/// File layout: headers of all posting lists, followed by the data of each posting list:
+/// chunks, uncompressed remainders and compressed ids, padded to [`POSTING_ALIGNMENT`].
+pub struct InvertedIndexCompressedMmap<W: Weight> {
+    path: PathBuf,
+    mmap: Arc<Mmap>,
+    pub file_header: InvertedIndexFileHeader,
+    _phantom: PhantomData<W>,
+}
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+struct CompressedPostingListFileHeader<P> {
+    /// Offset of the posting list data in the file
+    pub offset: u64,
+    pub chunks_count: u32,
+    pub remainders_count: u32,
+    pub id_data_len: u32,
+    /// Id of the last element, valid for non-empty posting lists only
+    pub last_id: PointOffsetType,
+    pub params: P,
+}
+
+impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
+    type Iter<'a> = CompressedPostingListIterator<'a, W>;
+
+    fn open(path: &Path) -> std::io::Result<Self> {
+        Self::load(path)
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        debug_assert_eq!(path, self.path);
+        Ok(())
+    }
+
+    fn get<'a>(&'a self, id: &DimId) -> Option<CompressedPostingListIterator<'a, W>> {
+        self.get(id).map(|posting_list| posting_list.iter())
+    }
+
+    fn posting_list_len(&self, id: &DimId) -> Option<usize> {
+        self.get(id).map(|posting_list| posting_list.len())
+    }
+
+    fn files(path: &Path) -> Vec<PathBuf> {
+        vec![
+            Self::index_file_path(path),
+            Self::index_config_file_path(path),
+        ]
+    }
+
+    fn upsert(&mut self, _id: PointOffsetType, _vector: SparseVector) {
+        panic!("Cannot upsert into a read-only Mmap inverted index")
+    }
+
+    fn from_ram_index<P: AsRef<Path>>(
+        ram_index: InvertedIndexRam,
+        path: P,
+    ) -> std::io::Result<Self> {
+        Self::convert_and_save(&ram_index, path)
+    }
+
+    fn vector_count(&self) -> usize {
+        self.file_header.vector_count
+    }
+
+    fn max_index(&self) -> Option<DimId> {
+        match self.file_header.posting_count {
+            0 => None,
+            len => Some(len as DimId - 1),
+        }
+    }
+}
+
+impl<W: Weight> InvertedIndexCompressedMmap<W> {
+    const POSTING_HEADER_SIZE: usize =
+        size_of::<CompressedPostingListFileHeader<W::QuantizationParams>>();
+
+    pub fn index_file_path(path: &Path) -> PathBuf {
+        path.join(INDEX_FILE_NAME)
+    }
+
+    pub fn index_config_file_path(path: &Path) -> PathBuf {
+        path.join(INDEX_CONFIG_FILE_NAME)
+    }
+
+    pub fn get(&self, id: &DimId) -> Option<CompressedPostingListView<W>> {
+        // check that the id is not out of bounds (posting_count includes the empty zeroth entry)
+        if *id >= self.file_header.posting_count as DimId {
+            return None;
+        }
+        let header_start = *id as usize * Self::POSTING_HEADER_SIZE;
+        let header = transmute_from_u8::<CompressedPostingListFileHeader<W::QuantizationParams>>(
+            &self.mmap[header_start..header_start + Self::POSTING_HEADER_SIZE],
+        )
+        .clone();
+
+        let chunks_start = header.offset as usize;
+        let remainders_start = chunks_start + Self::chunks_size(header.chunks_count as usize);
+        let id_data_start =
+            remainders_start + Self::remainders_size(header.remainders_count as usize);
+        let id_data_end = id_data_start + header.id_data_len as usize;
+
+        let is_empty = header.chunks_count == 0 && header.remainders_count == 0;
+        Some(CompressedPostingListView {
+            id_data: &self.mmap[id_data_start..id_data_end],
+            chunks: transmute_from_u8_to_slice(&self.mmap[chunks_start..remainders_start]),
+            remainders: transmute_from_u8_to_slice(&self.mmap[remainders_start..id_data_start]),
+            last_id: (!is_empty).then_some(header.last_id),
+            params: header.params,
+        })
+    }
+
+    pub fn convert_and_save<P: AsRef<Path>>(
+        inverted_index_ram: &InvertedIndexRam,
+        path: P,
+    ) -> std::io::Result<Self> {
+        let postings: Vec<_> = inverted_index_ram
+            .postings
+            .iter()
+            .map(CompressedPostingList::<W>::from_posting_list)
+            .collect();
+        Self::save_postings(&postings, inverted_index_ram.vector_count(), path)
+    }
+
+    /// Save compressed posting lists into a new index
+    pub fn save_postings<P: AsRef<Path>>(
+        postings: &[CompressedPostingList<W>],
+        vector_count: usize,
+        path: P,
+    ) -> std::io::Result<Self> {
+        debug_assert_eq!(Self::POSTING_HEADER_SIZE % POSTING_ALIGNMENT, 0);
+        debug_assert!(align_of::<CompressedPostingChunk<W>>() <= POSTING_ALIGNMENT);
+
+        let total_posting_headers_size = postings.len() * Self::POSTING_HEADER_SIZE;
+        let total_posting_data_size: usize = postings
+            .iter()
+            .map(|posting| Self::posting_data_size(&posting.view()))
+            .sum();
+
+        let file_length = total_posting_headers_size + total_posting_data_size;
+        let file_path = Self::index_file_path(path.as_ref());
+        create_and_ensure_length(file_path.as_ref(), file_length)?;
+
+        let mut mmap = open_write_mmap(file_path.as_ref())?;
+        madvise::madvise(&mmap, madvise::Advice::Normal)?;
+
+        // file index data
+        Self::save_postings_data(&mut mmap, postings, total_posting_headers_size);
+        if file_length > 0 {
+            mmap.flush()?;
+        }
+
+        // finalize data with index file.
+        let file_header = InvertedIndexFileHeader {
+            posting_count: postings.len(),
+            vector_count,
+        };
+        let config_file_path = Self::index_config_file_path(path.as_ref());
+        atomic_save_json(&config_file_path, &file_header)?;
+
+        Ok(Self {
+            path: path.as_ref().to_owned(),
+            mmap: Arc::new(mmap.make_read_only()?),
+            file_header,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        // read index config file
+        let config_file_path = Self::index_config_file_path(path.as_ref());
+        // if the file header does not exist, the index is malformed
+        let file_header: InvertedIndexFileHeader = read_json(&config_file_path)?;
+        // read index data into mmap
+        let file_path = Self::index_file_path(path.as_ref());
+        let mmap = open_read_mmap(file_path.as_ref())?;
+        madvise::madvise(&mmap, madvise::Advice::Normal)?;
+        Ok(Self {
+            path: path.as_ref().to_owned(),
+            mmap: Arc::new(mmap),
+            file_header,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn chunks_size(chunks_count: usize) -> usize {
+        chunks_count * size_of::<CompressedPostingChunk<W>>()
+    }
+
+    fn remainders_size(remainders_count: usize) -> usize {
+        remainders_count * size_of::<GenericPostingElement<W>>()
+    }
+
+    fn posting_data_size(posting: &CompressedPostingListView<W>) -> usize {
+        let size = Self::chunks_size(posting.chunks.len())
+            + Self::remainders_size(posting.remainders.len())
+            + posting.id_data.len();
+        size.next_multiple_of(POSTING_ALIGNMENT)
+    }
+
+    fn save_postings_data(
+        mmap: &mut MmapMut,
+        postings: &[CompressedPostingList<W>],
+        total_posting_headers_size: usize,
+    ) {
+        let mut offset = total_posting_headers_size;
+        for (id, posting) in postings.iter().enumerate() {
+            let posting = posting.view();
+            let posting_header = CompressedPostingListFileHeader {
+                offset: offset as u64,
+                chunks_count: posting.chunks.len() as u32,
+                remainders_count: posting.remainders.len() as u32,
+                id_data_len: posting.id_data.len() as u32,
+                last_id: posting.last_id.unwrap_or_default(),
+                params: posting.params,
+            };
+
+            // save posting header
+            let posting_header_bytes = transmute_to_u8(&posting_header);
+            let start_posting_offset = id * Self::POSTING_HEADER_SIZE;
+            let end_posting_offset = (id + 1) * Self::POSTING_HEADER_SIZE;
+            mmap[start_posting_offset..end_posting_offset].copy_from_slice(posting_header_bytes);
+
+            // save posting data
+            let mut data_offset = offset;
+            for bytes in [
+                transmute_to_u8_slice(posting.chunks),
+                transmute_to_u8_slice(posting.remainders),
+                posting.id_data,
+            ] {
+                mmap[data_offset..data_offset + bytes.len()].copy_from_slice(bytes);
+                data_offset += bytes.len();
+            }
+            offset += Self::posting_data_size(&posting);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::types::QuantizedU8;
+    use crate::index::compressed_posting_list::CHUNK_LEN;
+    use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
+    use crate::index::posting_list_common::PostingListIter;
+
+    fn check_compressed_index<W: Weight>(inverted_index_ram: &InvertedIndexRam) {
+        let tmp_dir_path = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        {
+            InvertedIndexCompressedMmap::<W>::convert_and_save(inverted_index_ram, &tmp_dir_path)
+                .unwrap();
+        }
+        let inverted_index_mmap = InvertedIndexCompressedMmap::<W>::load(&tmp_dir_path).unwrap();
+        assert_eq!(
+            inverted_index_mmap.file_header.posting_count,
+            inverted_index_ram.postings.len()
+        );
+        assert_eq!(
+            inverted_index_mmap.vector_count(),
+            inverted_index_ram.vector_count()
+        );
+
+        for (id, posting_list) in inverted_index_ram.postings.iter().enumerate() {
+            let expected = CompressedPostingList::<W>::from_posting_list(posting_list);
+            let loaded = inverted_index_mmap.get(&(id as DimId)).unwrap();
+            assert_eq!(loaded.to_owned(), expected);
+            assert_eq!(
+                InvertedIndex::get(&inverted_index_mmap, &(id as DimId))
+                    .unwrap()
+                    .last_id(),
+                posting_list.elements.last().map(|e| e.record_id)
+            );
+        }
+        assert!(inverted_index_mmap
+            .get(&(inverted_index_ram.postings.len() as DimId))
+            .is_none());
+    }
+
+    #[test]
+    fn test_inverted_index_compressed_mmap() {
+        // skip 4th dimension, make the 1st dimension long enough for several chunks
+        let mut builder = InvertedIndexBuilder::new();
+        for id in 1..=(3 * CHUNK_LEN + 5) as PointOffsetType {
+            builder.add(id, [(1, id as f32)].into());
+        }
+        builder.add(10_000, [(1, 10.0), (2, 10.0), (3, 10.0), (5, 10.0)].into());
+        builder.add(10_001, [(2, 20.0), (3, 20.0), (5, 20.0)].into());
+        builder.add(10_002, [(2, 1.0), (3, 30.0)].into());
+        let inverted_index_ram = builder.build();
+
+        check_compressed_index::<f32>(&inverted_index_ram);
+        check_compressed_index::<f16>(&inverted_index_ram);
+        check_compressed_index::<QuantizedU8>(&inverted_index_ram);
+    }
+}
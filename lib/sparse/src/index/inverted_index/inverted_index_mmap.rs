@@ -1,3 +1,4 @@
+use std::marker::PhantomData;
 use std::mem::size_of;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -13,12 +14,13 @@ use memory::mmap_ops::{
 use serde::{Deserialize, Serialize};
 
 use crate::common::sparse_vector::SparseVector;
-use crate::common::types::DimId;
+use crate::common::types::{DimId, DimWeight, Weight};
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use crate::index::inverted_index::InvertedIndex;
-use crate::index::posting_list::{PostingElement, PostingListIterator};
+use crate::index::posting_list::{
+    GenericPostingElement, PostingListIterator, QuantizedPostingList,
+};
 
-const POSTING_HEADER_SIZE: usize = size_of::<PostingListFileHeader>();
 const INDEX_FILE_NAME: &str = "inverted_index.data";
 const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_config.json";
 
@@ -29,19 +31,30 @@ pub struct InvertedIndexFileHeader {
 }
 
 /// Inverted flatten index from dimension id to posting list
-pub struct InvertedIndexMmap {
+pub struct GenericInvertedIndexMmap<W: Weight> {
     path: PathBuf,
     mmap: Arc<Mmap>,
     pub file_header: InvertedIndexFileHeader,
+    _phantom: PhantomData<W>,
 }
 
-#[derive(Debug, Default, Clone)]
-struct PostingListFileHeader {
+/// Inverted index with full-precision weights
+pub type InvertedIndexMmap = GenericInvertedIndexMmap<DimWeight>;
+
+/// Header of a single posting list.
+///
+/// Quantization parameters are zero-sized for full-precision weights,
+/// so the layout of the full-precision index is not affected.
+#[derive(Debug, Clone)]
+struct PostingListFileHeader<P> {
     pub start_offset: u64,
     pub end_offset: u64,
+    pub params: P,
 }
 
-impl InvertedIndex for InvertedIndexMmap {
+impl<W: Weight> InvertedIndex for GenericInvertedIndexMmap<W> {
+    type Iter<'a> = PostingListIterator<'a, W>;
+
     fn open(path: &Path) -> std::io::Result<Self> {
         Self::load(path)
     }
@@ -51,8 +64,14 @@ impl InvertedIndex for InvertedIndexMmap {
         Ok(())
     }
 
-    fn get(&self, id: &DimId) -> Option<PostingListIterator> {
-        self.get(id).map(PostingListIterator::new)
+    fn get<'a>(&'a self, id: &DimId) -> Option<PostingListIterator<'a, W>> {
+        self.posting_header(id).map(|header| {
+            PostingListIterator::with_params(self.posting_elements(&header), header.params)
+        })
+    }
+
+    fn posting_list_len(&self, id: &DimId) -> Option<usize> {
+        self.get(id).map(|posting_list| posting_list.len())
     }
 
     fn files(path: &Path) -> Vec<PathBuf> {
@@ -85,7 +104,9 @@ impl InvertedIndex for InvertedIndexMmap {
     }
 }
 
-impl InvertedIndexMmap {
+impl<W: Weight> GenericInvertedIndexMmap<W> {
+    const POSTING_HEADER_SIZE: usize = size_of::<PostingListFileHeader<W::QuantizationParams>>();
+
     pub fn index_file_path(path: &Path) -> PathBuf {
         path.join(INDEX_FILE_NAME)
     }
@@ -94,18 +115,30 @@ impl InvertedIndexMmap {
         path.join(INDEX_CONFIG_FILE_NAME)
     }
 
-    pub fn get(&self, id: &DimId) -> Option<&[PostingElement]> {
+    pub fn get(&self, id: &DimId) -> Option<&[GenericPostingElement<W>]> {
+        self.posting_header(id)
+            .map(|header| self.posting_elements(&header))
+    }
+
+    fn posting_header(&self, id: &DimId) -> Option<PostingListFileHeader<W::QuantizationParams>> {
         // check that the id is not out of bounds (posting_count includes the empty zeroth entry)
         if *id >= self.file_header.posting_count as DimId {
             return None;
         }
-        let header_start = *id as usize * POSTING_HEADER_SIZE;
-        let header = transmute_from_u8::<PostingListFileHeader>(
-            &self.mmap[header_start..header_start + POSTING_HEADER_SIZE],
+        let header_start = *id as usize * Self::POSTING_HEADER_SIZE;
+        let header = transmute_from_u8::<PostingListFileHeader<W::QuantizationParams>>(
+            &self.mmap[header_start..header_start + Self::POSTING_HEADER_SIZE],
         )
         .clone();
+        Some(header)
+    }
+
+    fn posting_elements(
+        &self,
+        header: &PostingListFileHeader<W::QuantizationParams>,
+    ) -> &[GenericPostingElement<W>] {
         let elements_bytes = &self.mmap[header.start_offset as usize..header.end_offset as usize];
-        Some(transmute_from_u8_to_slice(elements_bytes))
+        transmute_from_u8_to_slice(elements_bytes)
     }
 
     pub fn convert_and_save<P: AsRef<Path>>(
@@ -123,8 +156,7 @@ impl InvertedIndexMmap {
         madvise::madvise(&mmap, madvise::Advice::Normal)?;
 
         // file index data
-        Self::save_posting_headers(&mut mmap, inverted_index_ram, total_posting_headers_size);
-        Self::save_posting_elements(&mut mmap, inverted_index_ram, total_posting_headers_size);
+        Self::save_postings(&mut mmap, inverted_index_ram, total_posting_headers_size);
         if file_length > 0 {
             mmap.flush()?;
         }
@@ -145,6 +177,7 @@ impl InvertedIndexMmap {
             path: path.as_ref().to_owned(),
             mmap: Arc::new(mmap.make_read_only()?),
             file_header,
+            _phantom: PhantomData,
         })
     }
 
@@ -161,56 +194,49 @@ impl InvertedIndexMmap {
             path: path.as_ref().to_owned(),
             mmap: Arc::new(mmap),
             file_header,
+            _phantom: PhantomData,
         })
     }
 
     fn total_posting_headers_size(inverted_index_ram: &InvertedIndexRam) -> usize {
-        inverted_index_ram.postings.len() * POSTING_HEADER_SIZE
+        inverted_index_ram.postings.len() * Self::POSTING_HEADER_SIZE
     }
 
     fn total_posting_elements_size(inverted_index_ram: &InvertedIndexRam) -> usize {
         let mut total_posting_elements_size = 0;
         for posting in &inverted_index_ram.postings {
-            total_posting_elements_size += posting.elements.len() * size_of::<PostingElement>();
+            total_posting_elements_size +=
+                posting.elements.len() * size_of::<GenericPostingElement<W>>();
         }
 
         total_posting_elements_size
     }
 
-    fn save_posting_headers(
+    fn save_postings(
         mmap: &mut MmapMut,
         inverted_index_ram: &InvertedIndexRam,
         total_posting_headers_size: usize,
     ) {
         let mut elements_offset: usize = total_posting_headers_size;
         for (id, posting) in inverted_index_ram.postings.iter().enumerate() {
-            let posting_elements_size = posting.elements.len() * size_of::<PostingElement>();
+            let posting = QuantizedPostingList::<W>::from_posting_list(posting);
+            let posting_elements_bytes = transmute_to_u8_slice(&posting.elements);
             let posting_header = PostingListFileHeader {
                 start_offset: elements_offset as u64,
-                end_offset: (elements_offset + posting_elements_size) as u64,
+                end_offset: (elements_offset + posting_elements_bytes.len()) as u64,
+                params: posting.params,
             };
-            elements_offset = posting_header.end_offset as usize;
 
             // save posting header
             let posting_header_bytes = transmute_to_u8(&posting_header);
-            let start_posting_offset = id * POSTING_HEADER_SIZE;
-            let end_posting_offset = (id + 1) * POSTING_HEADER_SIZE;
+            let start_posting_offset = id * Self::POSTING_HEADER_SIZE;
+            let end_posting_offset = (id + 1) * Self::POSTING_HEADER_SIZE;
             mmap[start_posting_offset..end_posting_offset].copy_from_slice(posting_header_bytes);
-        }
-    }
 
-    fn save_posting_elements(
-        mmap: &mut MmapMut,
-        inverted_index_ram: &InvertedIndexRam,
-        total_posting_headers_size: usize,
-    ) {
-        let mut offset = total_posting_headers_size;
-        for posting in &inverted_index_ram.postings {
-            // save posting element
-            let posting_elements_bytes = transmute_to_u8_slice(&posting.elements);
-            mmap[offset..offset + posting_elements_bytes.len()]
+            // save posting elements
+            mmap[elements_offset..posting_header.end_offset as usize]
                 .copy_from_slice(posting_elements_bytes);
-            offset += posting_elements_bytes.len();
+            elements_offset = posting_header.end_offset as usize;
         }
     }
 }
@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+
+use super::inverted_index_mmap::GenericInvertedIndexMmap;
+use crate::common::sparse_vector::SparseVector;
+use crate::common::types::{DimId, Weight};
+use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
+use crate::index::inverted_index::InvertedIndex;
+use crate::index::posting_list::{PostingListIterator, QuantizedPostingList};
+
+/// Immutable inverted index with weights of reduced precision, loaded into RAM.
+///
+/// Persisted in the same format as [`GenericInvertedIndexMmap`] with the same weight type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvertedIndexQuantizedRam<W: Weight> {
+    /// Posting lists for each dimension flattened (dimension id -> posting list)
+    /// Gaps are filled with empty posting lists
+    pub postings: Vec<QuantizedPostingList<W>>,
+    /// Number of unique indexed vectors
+    pub vector_count: usize,
+}
+
+impl<W: Weight> InvertedIndex for InvertedIndexQuantizedRam<W> {
+    type Iter<'a> = PostingListIterator<'a, W>;
+
+    fn open(path: &Path) -> std::io::Result<Self> {
+        let mmap_inverted_index = GenericInvertedIndexMmap::<W>::load(path)?;
+        Ok(Self::from_mmap_index(&mmap_inverted_index))
+    }
+
+    fn save(&self, _path: &Path) -> std::io::Result<()> {
+        // Index is persisted on creation, see `from_ram_index`
+        Ok(())
+    }
+
+    fn get<'a>(&'a self, id: &DimId) -> Option<PostingListIterator<'a, W>> {
+        self.postings
+            .get(*id as usize)
+            .map(|posting| posting.iter())
+    }
+
+    fn posting_list_len(&self, id: &DimId) -> Option<usize> {
+        self.postings
+            .get(*id as usize)
+            .map(|posting| posting.elements.len())
+    }
+
+    fn files(path: &Path) -> Vec<PathBuf> {
+        GenericInvertedIndexMmap::<W>::files(path)
+    }
+
+    fn upsert(&mut self, _id: PointOffsetType, _vector: SparseVector) {
+        panic!("Cannot upsert into a read-only quantized inverted index")
+    }
+
+    fn from_ram_index<P: AsRef<Path>>(
+        ram_index: InvertedIndexRam,
+        path: P,
+    ) -> std::io::Result<Self> {
+        let mmap_inverted_index =
+            GenericInvertedIndexMmap::<W>::convert_and_save(&ram_index, path)?;
+        Ok(Self::from_mmap_index(&mmap_inverted_index))
+    }
+
+    fn vector_count(&self) -> usize {
+        self.vector_count
+    }
+
+    fn max_index(&self) -> Option<DimId> {
+        match self.postings.len() {
+            0 => None,
+            len => Some(len as DimId - 1),
+        }
+    }
+}
+
+impl<W: Weight> InvertedIndexQuantizedRam<W> {
+    fn from_mmap_index(mmap_inverted_index: &GenericInvertedIndexMmap<W>) -> Self {
+        let postings = (0..mmap_inverted_index.file_header.posting_count as DimId)
+            .filter_map(|id| InvertedIndex::get(mmap_inverted_index, &id))
+            .map(|posting| QuantizedPostingList {
+                elements: posting.elements.to_vec(),
+                params: posting.params,
+            })
+            .collect();
+        Self {
+            postings,
+            vector_count: mmap_inverted_index.file_header.vector_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use half::f16;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::types::QuantizedU8;
+    use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
+
+    fn check_quantized_index<W: Weight>(inverted_index_ram: &InvertedIndexRam, tolerance: f32) {
+        let tmp_dir_path = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        let quantized_index = InvertedIndexQuantizedRam::<W>::from_ram_index(
+            inverted_index_ram.clone(),
+            &tmp_dir_path,
+        )
+        .unwrap();
+        let loaded_index = InvertedIndexQuantizedRam::<W>::open(tmp_dir_path.path()).unwrap();
+        assert_eq!(quantized_index, loaded_index);
+        assert_eq!(loaded_index.vector_count, inverted_index_ram.vector_count);
+
+        for (id, posting_list) in inverted_index_ram.postings.iter().enumerate() {
+            let iterator = loaded_index.get(&(id as DimId)).unwrap();
+            assert_eq!(iterator.elements.len(), posting_list.elements.len());
+            for (quantized, original) in iterator.elements.iter().zip(&posting_list.elements) {
+                assert_eq!(quantized.record_id, original.record_id);
+                assert!((iterator.weight(quantized) - original.weight).abs() <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverted_index_quantized_ram() {
+        let mut builder = InvertedIndexBuilder::new();
+        builder.add(1, [(1, 10.0), (2, 10.0), (3, 10.0), (5, 10.0)].into());
+        builder.add(2, [(1, 20.0), (2, 20.0), (3, 20.0), (5, 20.0)].into());
+        builder.add(3, [(1, 30.0), (2, 30.0), (3, 30.0)].into());
+        builder.add(4, [(1, 1.0), (2, 1.0)].into());
+        builder.add(5, [(1, 2.0)].into());
+        builder.add(6, [(1, 3.0)].into());
+        let inverted_index_ram = builder.build();
+
+        check_quantized_index::<f16>(&inverted_index_ram, 0.01);
+        // 29 / 255 / 2
+        check_quantized_index::<QuantizedU8>(&inverted_index_ram, 0.06);
+    }
+}
@@ -21,6 +21,8 @@ pub struct InvertedIndexRam {
 }
 
 impl InvertedIndex for InvertedIndexRam {
+    type Iter<'a> = PostingListIterator<'a>;
+
     fn open(path: &Path) -> std::io::Result<Self> {
         let mmap_inverted_index = InvertedIndexMmap::load(path)?;
         let mut inverted_index = InvertedIndexRam {
@@ -48,11 +50,15 @@ impl InvertedIndex for InvertedIndexRam {
         Ok(())
     }
 
-    fn get(&self, id: &DimId) -> Option<PostingListIterator> {
+    fn get<'a>(&'a self, id: &DimId) -> Option<PostingListIterator<'a>> {
         self.get(id)
             .map(|posting_list| PostingListIterator::new(&posting_list.elements))
     }
 
+    fn posting_list_len(&self, id: &DimId) -> Option<usize> {
+        self.get(id).map(|posting_list| posting_list.elements.len())
+    }
+
     fn files(path: &Path) -> Vec<PathBuf> {
         [
             InvertedIndexMmap::index_file_path(path),
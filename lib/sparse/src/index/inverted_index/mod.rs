@@ -5,13 +5,21 @@ use common::types::PointOffsetType;
 use crate::common::sparse_vector::SparseVector;
 use crate::common::types::DimId;
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
-use crate::index::posting_list::PostingListIterator;
+use crate::index::posting_list_common::PostingListIter;
 
+pub mod inverted_index_compressed_immutable_ram;
+pub mod inverted_index_compressed_mmap;
 pub mod inverted_index_mmap;
+pub mod inverted_index_quantized_ram;
 pub mod inverted_index_ram;
 pub mod inverted_index_ram_builder;
 
 pub trait InvertedIndex: Sized {
+    /// Iterator over the posting list of a single dimension
+    type Iter<'a>: PostingListIter
+    where
+        Self: 'a;
+
     /// Open existing index based on path
     fn open(path: &Path) -> std::io::Result<Self>;
 
@@ -19,7 +27,11 @@ pub trait InvertedIndex: Sized {
     fn save(&self, path: &Path) -> std::io::Result<()>;
 
     /// Get posting list for dimension id
-    fn get(&self, id: &DimId) -> Option<PostingListIterator>;
+    fn get<'a>(&'a self, id: &DimId) -> Option<Self::Iter<'a>>;
+
+    /// Get number of posting list elements for dimension id,
+    /// i.e. the number of indexed vectors containing this dimension
+    fn posting_list_len(&self, id: &DimId) -> Option<usize>;
 
     /// Files used by this index
     fn files(path: &Path) -> Vec<PathBuf>;
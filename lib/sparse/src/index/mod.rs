@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+pub mod compressed_posting_list;
 pub mod inverted_index;
 pub mod posting_list;
+pub mod posting_list_common;
 pub mod search_context;
@@ -3,18 +3,22 @@ use std::cmp::max;
 use common::types::PointOffsetType;
 use ordered_float::OrderedFloat;
 
-use crate::common::types::DimWeight;
+use crate::common::types::{DimWeight, Weight};
+use crate::index::posting_list_common::{PostingListIter, RecordWeight};
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct PostingElement {
+pub struct GenericPostingElement<W> {
     /// Record ID
     pub record_id: PointOffsetType,
     /// Weight of the record in the dimension
-    pub weight: DimWeight,
+    pub weight: W,
     /// Max weight of the next elements in the posting list.
-    pub max_next_weight: DimWeight,
+    pub max_next_weight: W,
 }
 
+/// Posting element with full-precision weights
+pub type PostingElement = GenericPostingElement<DimWeight>;
+
 const DEFAULT_MAX_NEXT_WEIGHT: DimWeight = f32::NEG_INFINITY;
 
 impl PostingElement {
@@ -120,6 +124,36 @@ impl PostingList {
     }
 }
 
+/// Immutable posting list with weights of reduced precision
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedPostingList<W: Weight> {
+    /// List of the posting elements ordered by id
+    pub elements: Vec<GenericPostingElement<W>>,
+    /// Parameters to restore the weights of the elements
+    pub params: W::QuantizationParams,
+}
+
+impl<W: Weight> QuantizedPostingList<W> {
+    pub fn from_posting_list(posting_list: &PostingList) -> Self {
+        let params =
+            W::quantization_params(posting_list.elements.iter().map(|element| element.weight));
+        let elements = posting_list
+            .elements
+            .iter()
+            .map(|element| GenericPostingElement {
+                record_id: element.record_id,
+                weight: W::from_f32(params, element.weight),
+                max_next_weight: W::from_f32(params, element.max_next_weight),
+            })
+            .collect();
+        QuantizedPostingList { elements, params }
+    }
+
+    pub fn iter(&self) -> PostingListIterator<W> {
+        PostingListIterator::with_params(&self.elements, self.params)
+    }
+}
+
 pub struct PostingBuilder {
     elements: Vec<PostingElement>,
 }
@@ -173,21 +207,46 @@ impl PostingBuilder {
 }
 
 /// Iterator over posting list elements offering skipping abilities to avoid full iteration.
-pub struct PostingListIterator<'a> {
-    pub elements: &'a [PostingElement],
+pub struct PostingListIterator<'a, W: Weight = DimWeight> {
+    pub elements: &'a [GenericPostingElement<W>],
+    /// Parameters to restore the weights of the elements
+    pub params: W::QuantizationParams,
     pub current_index: usize,
 }
 
 impl<'a> PostingListIterator<'a> {
     pub fn new(elements: &'a [PostingElement]) -> PostingListIterator<'a> {
+        Self::with_params(elements, ())
+    }
+}
+
+impl<'a, W: Weight> PostingListIterator<'a, W> {
+    pub fn with_params(
+        elements: &'a [GenericPostingElement<W>],
+        params: W::QuantizationParams,
+    ) -> PostingListIterator<'a, W> {
         PostingListIterator {
             elements,
+            params,
             current_index: 0,
         }
     }
 
+    /// Full-precision weight of the element
+    pub fn weight(&self, element: &GenericPostingElement<W>) -> DimWeight {
+        element.weight.to_f32(self.params)
+    }
+
+    /// Full-precision max weight of the element and the elements after it
+    pub fn max_weight_from(&self, element: &GenericPostingElement<W>) -> DimWeight {
+        element
+            .weight
+            .to_f32(self.params)
+            .max(element.max_next_weight.to_f32(self.params))
+    }
+
     /// Slice of the remaining elements.
-    pub fn remaining_elements(&self) -> &'a [PostingElement] {
+    pub fn remaining_elements(&self) -> &'a [GenericPostingElement<W>] {
         &self.elements[self.current_index..]
     }
 
@@ -204,7 +263,7 @@ impl<'a> PostingListIterator<'a> {
     }
 
     /// Returns the next element without advancing the iterator.
-    pub fn peek(&self) -> Option<&PostingElement> {
+    pub fn peek(&self) -> Option<&GenericPostingElement<W>> {
         self.elements.get(self.current_index)
     }
 
@@ -219,7 +278,7 @@ impl<'a> PostingListIterator<'a> {
     /// If the iterator is already at the end, None is returned.
     /// If the iterator skipped to the end, None is returned and current index is set to the length of the list.
     /// Uses binary search.
-    pub fn skip_to(&mut self, id: PointOffsetType) -> Option<&PostingElement> {
+    pub fn skip_to(&mut self, id: PointOffsetType) -> Option<&GenericPostingElement<W>> {
         // Check if we are already at the end
         if self.current_index >= self.elements.len() {
             return None;
@@ -242,10 +301,87 @@ impl<'a> PostingListIterator<'a> {
     }
 
     /// Skips to the end of the posting list and returns None.
-    pub fn skip_to_end(&mut self) -> Option<&PostingElement> {
+    pub fn skip_to_end(&mut self) -> Option<&GenericPostingElement<W>> {
         self.current_index = self.elements.len();
         None
     }
+
+    fn record_weight(&self, element: &GenericPostingElement<W>) -> RecordWeight {
+        RecordWeight {
+            record_id: element.record_id,
+            weight: self.weight(element),
+        }
+    }
+}
+
+impl<'a, W: Weight> PostingListIter for PostingListIterator<'a, W> {
+    fn peek(&mut self) -> Option<RecordWeight> {
+        self.elements
+            .get(self.current_index)
+            .map(|element| self.record_weight(element))
+    }
+
+    fn last_id(&self) -> Option<PointOffsetType> {
+        self.elements.last().map(|element| element.record_id)
+    }
+
+    fn skip_to(&mut self, record_id: PointOffsetType) -> Option<RecordWeight> {
+        let params = self.params;
+        PostingListIterator::skip_to(self, record_id).map(|element| RecordWeight {
+            record_id: element.record_id,
+            weight: element.weight.to_f32(params),
+        })
+    }
+
+    fn skip_to_end(&mut self) {
+        PostingListIterator::skip_to_end(self);
+    }
+
+    fn len_to_end(&self) -> usize {
+        PostingListIterator::len_to_end(self)
+    }
+
+    fn current_index(&self) -> usize {
+        self.current_index
+    }
+
+    fn for_each_till_id<Ctx: ?Sized>(
+        &mut self,
+        id: PointOffsetType,
+        ctx: &mut Ctx,
+        mut f: impl FnMut(&mut Ctx, PointOffsetType, DimWeight),
+    ) {
+        let remaining_elements = self.remaining_elements();
+        let mut count = 0;
+        for element in remaining_elements {
+            if element.record_id > id {
+                break;
+            }
+            f(ctx, element.record_id, element.weight.to_f32(self.params));
+            count += 1;
+        }
+        self.advance_by(count);
+    }
+
+    fn prune_until(&mut self, until: Option<PointOffsetType>, max_weight: DimWeight) -> bool {
+        let Some(element) = self.elements.get(self.current_index) else {
+            return false;
+        };
+        // `max_next_weight` bounds all the following elements, not only the ones below `until`
+        if self.max_weight_from(element) > max_weight {
+            return false;
+        }
+        let position_before_pruning = self.current_index;
+        match until {
+            Some(until) => {
+                PostingListIterator::skip_to(self, until);
+            }
+            None => {
+                PostingListIterator::skip_to_end(self);
+            }
+        }
+        position_before_pruning != self.current_index
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,49 @@
+use common::types::PointOffsetType;
+
+use crate::common::types::DimWeight;
+
+/// Posting list element with the weight restored to full precision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordWeight {
+    pub record_id: PointOffsetType,
+    pub weight: DimWeight,
+}
+
+/// Iterator over posting list elements, used by the search.
+///
+/// Elements are ordered by record id. Implemented for plain and compressed posting lists.
+pub trait PostingListIter {
+    /// Current element, without advancing the iterator
+    fn peek(&mut self) -> Option<RecordWeight>;
+
+    /// Id of the last element of the posting list
+    fn last_id(&self) -> Option<PointOffsetType>;
+
+    /// Advance to the first element with id greater or equal to `record_id`.
+    ///
+    /// Returns the element if its id is equal to `record_id`.
+    fn skip_to(&mut self, record_id: PointOffsetType) -> Option<RecordWeight>;
+
+    /// Advance past the last element
+    fn skip_to_end(&mut self);
+
+    /// Number of remaining elements
+    fn len_to_end(&self) -> usize;
+
+    /// Position of the current element in the posting list
+    fn current_index(&self) -> usize;
+
+    /// Call `f` for each remaining element with id less or equal to `id`, advancing past them
+    fn for_each_till_id<Ctx: ?Sized>(
+        &mut self,
+        id: PointOffsetType,
+        ctx: &mut Ctx,
+        f: impl FnMut(&mut Ctx, PointOffsetType, DimWeight),
+    );
+
+    /// Skip remaining elements with ids below `until` (all remaining elements if `None`),
+    /// as long as their weights are known not to exceed `max_weight`.
+    ///
+    /// Returns true if any element was skipped.
+    fn prune_until(&mut self, until: Option<PointOffsetType>, max_weight: DimWeight) -> bool;
+}
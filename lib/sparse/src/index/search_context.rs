@@ -10,10 +10,11 @@ use crate::common::sparse_vector::SparseVector;
 use crate::common::types::{DimId, DimWeight};
 use crate::index::inverted_index::InvertedIndex;
 use crate::index::posting_list::PostingListIterator;
+use crate::index::posting_list_common::PostingListIter;
 
 /// Iterator over posting lists with a reference to the corresponding query index and weight
-pub struct IndexedPostingListIterator<'a> {
-    posting_list_iterator: PostingListIterator<'a>,
+pub struct IndexedPostingListIterator<T: PostingListIter> {
+    posting_list_iterator: T,
     query_index: DimId,
     query_weight: DimWeight,
 }
@@ -21,8 +22,12 @@ pub struct IndexedPostingListIterator<'a> {
 /// Making this larger makes the search faster but uses more (pooled) memory
 const ADVANCE_BATCH_SIZE: usize = 10_000;
 
-pub struct SearchContext<'a, 'b> {
-    postings_iterators: Vec<IndexedPostingListIterator<'a>>,
+/// Search over the inverted index.
+///
+/// Generic over the posting list iterator, so plain and compressed posting lists share the same
+/// scoring and pruning logic. Weights of reduced precision are restored by the iterator.
+pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
+    postings_iterators: Vec<IndexedPostingListIterator<T>>,
     query: SparseVector,
     top: usize,
     is_stopped: &'a AtomicBool,
@@ -33,30 +38,29 @@ pub struct SearchContext<'a, 'b> {
     use_pruning: bool,
 }
 
-impl<'a, 'b> SearchContext<'a, 'b> {
-    pub fn new(
+impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
+    pub fn new<I: InvertedIndex<Iter<'a> = T>>(
         query: SparseVector,
         top: usize,
-        inverted_index: &'a impl InvertedIndex,
+        inverted_index: &'a I,
         pooled: PooledScoresHandle<'b>,
         is_stopped: &'a AtomicBool,
-    ) -> SearchContext<'a, 'b> {
+    ) -> SearchContext<'a, 'b, T> {
         let mut postings_iterators = Vec::new();
         // track min and max record ids across all posting lists
         let mut max_record_id = 0;
         let mut min_record_id = u32::MAX;
         // iterate over query indices
         for (query_weight_offset, id) in query.indices.iter().enumerate() {
-            if let Some(posting_list_iterator) = inverted_index.get(id) {
-                let posting_elements = posting_list_iterator.elements;
-                if !posting_elements.is_empty() {
+            if let Some(mut posting_list_iterator) = inverted_index.get(id) {
+                let first_element = posting_list_iterator.peek();
+                let last_id = posting_list_iterator.last_id();
+                if let (Some(first_element), Some(last_id)) = (first_element, last_id) {
                     // check if new min
-                    let min_record_id_posting = posting_elements[0].record_id;
-                    min_record_id = min(min_record_id, min_record_id_posting);
+                    min_record_id = min(min_record_id, first_element.record_id);
 
                     // check if new max
-                    let max_record_id_posting = posting_elements.last().unwrap().record_id;
-                    max_record_id = max(max_record_id, max_record_id_posting);
+                    max_record_id = max(max_record_id, last_id);
 
                     // capture query info
                     let query_index = *id;
@@ -139,36 +143,18 @@ impl<'a, 'b> SearchContext<'a, 'b> {
         self.pooled.scores.resize(batch_len as usize, 0.0);
 
         for posting in self.postings_iterators.iter_mut() {
-            // offset at which the posting list stops contributing to the batch (relative to the batch start)
-            let mut posting_stopped_at = None;
-            for (offset, element) in posting
-                .posting_list_iterator
-                .remaining_elements()
-                .iter()
-                .enumerate()
-            {
-                let element_id = element.record_id;
-                if element_id > batch_last_id {
-                    // reaching end of the batch
-                    posting_stopped_at = Some(offset);
-                    break;
-                }
-                let element_score = element.weight * posting.query_weight;
-                // update score for id
-                let local_id = (element_id - batch_start_id) as usize;
-                self.pooled.scores[local_id] += element_score;
-            }
-            // advance posting list iterator
-            match posting_stopped_at {
-                None => {
-                    // posting list is exhausted before reaching the end of the batch
-                    posting.posting_list_iterator.skip_to_end();
-                }
-                Some(stopped_at) => {
-                    // posting list is not exhausted - advance to last id
-                    posting.posting_list_iterator.advance_by(stopped_at)
-                }
-            };
+            let query_weight = posting.query_weight;
+            // advance posting list iterator up to the end of the batch
+            posting.posting_list_iterator.for_each_till_id(
+                batch_last_id,
+                self.pooled.scores.as_mut_slice(),
+                |scores, element_id, weight| {
+                    let element_score = weight * query_weight;
+                    // update score for id
+                    let local_id = (element_id - batch_start_id) as usize;
+                    scores[local_id] += element_score;
+                },
+            );
         }
 
         // publish only the non-zero scores above the current min
@@ -196,28 +182,32 @@ impl<'a, 'b> SearchContext<'a, 'b> {
     /// Compute scores for the last posting list quickly
     fn process_last_posting_list<F: Fn(PointOffsetType) -> bool>(&mut self, filter_condition: &F) {
         debug_assert_eq!(self.postings_iterators.len(), 1);
-        let posting = &self.postings_iterators[0];
-        for element in posting.posting_list_iterator.remaining_elements() {
-            // do not score if filter condition is not satisfied
-            if !filter_condition(element.record_id) {
-                continue;
-            }
-            let score = element.weight * posting.query_weight;
-            self.result_queue.push(ScoredPointOffset {
-                score,
-                idx: element.record_id,
-            });
-        }
+        let posting = &mut self.postings_iterators[0];
+        let query_weight = posting.query_weight;
+        posting.posting_list_iterator.for_each_till_id(
+            PointOffsetType::MAX,
+            &mut self.result_queue,
+            |result_queue, record_id, weight| {
+                // do not score if filter condition is not satisfied
+                if !filter_condition(record_id) {
+                    return;
+                }
+                result_queue.push(ScoredPointOffset {
+                    score: weight * query_weight,
+                    idx: record_id,
+                });
+            },
+        );
     }
 
     /// Returns the next min record id from all posting list iterators
     ///
     /// returns None if all posting list iterators are exhausted
-    fn next_min_id(to_inspect: &[IndexedPostingListIterator<'_>]) -> Option<PointOffsetType> {
+    fn next_min_id(to_inspect: &mut [IndexedPostingListIterator<T>]) -> Option<PointOffsetType> {
         let mut min_record_id = None;
 
         // Iterate to find min record id at the head of the posting lists
-        for posting_iterator in to_inspect.iter() {
+        for posting_iterator in to_inspect.iter_mut() {
             if let Some(next_element) = posting_iterator.posting_list_iterator.peek() {
                 match min_record_id {
                     None => min_record_id = Some(next_element.record_id), // first record with matching id
@@ -293,7 +283,7 @@ impl<'a, 'b> SearchContext<'a, 'b> {
             });
 
             // update min_record_id
-            self.min_record_id = Self::next_min_id(&self.postings_iterators);
+            self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
 
             // check if all posting lists are exhausted
             if self.postings_iterators.is_empty() {
@@ -323,7 +313,7 @@ impl<'a, 'b> SearchContext<'a, 'b> {
                 let pruned = self.prune_longest_posting_list(new_min_score);
                 if pruned {
                     // update min_record_id
-                    self.min_record_id = Self::next_min_id(&self.postings_iterators);
+                    self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
                 }
             }
         }
@@ -336,13 +326,16 @@ impl<'a, 'b> SearchContext<'a, 'b> {
     /// Assumes longest posting list is at the head of the posting list iterators
     /// Returns true if the longest posting list was pruned
     pub fn prune_longest_posting_list(&mut self, min_score: f32) -> bool {
-        if self.postings_iterators.is_empty() {
+        let Some((longest_posting_iterator, other_posting_iterators)) =
+            self.postings_iterators.split_first_mut()
+        else {
             return false;
-        }
+        };
+        // weight of the longest posting list elements which can't bring the score above `min_score`
+        let max_weight_to_prune = min_score / longest_posting_iterator.query_weight;
         // peek first element of longest posting list
-        let longest_posting_iterator = &self.postings_iterators[0];
         if let Some(element) = longest_posting_iterator.posting_list_iterator.peek() {
-            let next_min_id_in_others = Self::next_min_id(&self.postings_iterators[1..]);
+            let next_min_id_in_others = Self::next_min_id(other_posting_iterators);
             match next_min_id_in_others {
                 Some(next_min_id) => {
                     match next_min_id.cmp(&element.record_id) {
@@ -357,38 +350,21 @@ impl<'a, 'b> SearchContext<'a, 'b> {
                         }
                         Ordering::Greater => {
                             // next_min_id is > element.record_id there is a chance to prune up to `next_min_id`
-                            // check against the max possible score using the `max_next_weight`
+                            // check against the max possible score using the `max_next_weight` or the block max weights
                             // we can under prune as we should actually check the best score up to `next_min_id` - 1 only
                             // instead of the max possible score but it is not possible to know the best score up to `next_min_id` - 1
-                            let max_weight_from_list = element.weight.max(element.max_next_weight);
-                            let max_score_contribution =
-                                max_weight_from_list * longest_posting_iterator.query_weight;
-                            if max_score_contribution <= min_score {
-                                // prune to next_min_id
-                                let longest_posting_iterator =
-                                    &mut self.postings_iterators[0].posting_list_iterator;
-                                let position_before_pruning =
-                                    longest_posting_iterator.current_index;
-                                longest_posting_iterator.skip_to(next_min_id);
-                                let position_after_pruning = longest_posting_iterator.current_index;
-                                // check if pruning took place
-                                return position_before_pruning != position_after_pruning;
-                            }
+                            return longest_posting_iterator
+                                .posting_list_iterator
+                                .prune_until(Some(next_min_id), max_weight_to_prune);
                         }
                     }
                 }
                 None => {
                     // the current posting list is the only one left, we can potentially skip it to the end
-                    // check against the max possible score using the `max_next_weight`
-                    let max_weight_from_list = element.weight.max(element.max_next_weight);
-                    let max_score_contribution =
-                        max_weight_from_list * longest_posting_iterator.query_weight;
-                    if max_score_contribution <= min_score {
-                        // prune to the end!
-                        let longest_posting_iterator = &mut self.postings_iterators[0];
-                        longest_posting_iterator.posting_list_iterator.skip_to_end();
-                        return true;
-                    }
+                    // check against the max possible score using the `max_next_weight` or the block max weights
+                    return longest_posting_iterator
+                        .posting_list_iterator
+                        .prune_until(None, max_weight_to_prune);
                 }
             }
         }
@@ -401,12 +377,19 @@ impl<'a, 'b> SearchContext<'a, 'b> {
 mod tests {
     use std::sync::OnceLock;
 
-    use rand::Rng;
+    use half::f16;
+    use rand::{Rng, SeedableRng};
 
     use super::*;
     use crate::common::scores_memory_pool::ScoresMemoryPool;
     use crate::common::sparse_vector_fixture::random_sparse_vector;
-    use crate::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
+    use crate::common::types::QuantizedU8;
+    use crate::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+    use crate::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
+    use crate::index::inverted_index::inverted_index_mmap::{
+        GenericInvertedIndexMmap, InvertedIndexMmap,
+    };
+    use crate::index::inverted_index::inverted_index_quantized_ram::InvertedIndexQuantizedRam;
     use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
     use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
 
@@ -488,6 +471,77 @@ mod tests {
         let inverted_index_mmap =
             InvertedIndexMmap::convert_and_save(&inverted_index_ram, &tmp_dir_path).unwrap();
         _search_test(&inverted_index_mmap);
+
+        // test with f16 weights, which are exact for the test values
+        let tmp_dir_path = tempfile::Builder::new()
+            .prefix("test_index_dir")
+            .tempdir()
+            .unwrap();
+        let inverted_index_f16 =
+            GenericInvertedIndexMmap::<f16>::convert_and_save(&inverted_index_ram, &tmp_dir_path)
+                .unwrap();
+        _search_test(&inverted_index_f16);
+
+        // test with compressed ram index
+        let inverted_index_compressed = InvertedIndexCompressedImmutableRam::<f32>::from_ram_index(
+            inverted_index_ram.clone(),
+            &tmp_dir_path,
+        )
+        .unwrap();
+        _search_test(&inverted_index_compressed);
+
+        // test with compressed mmap index
+        let tmp_dir_path = tempfile::Builder::new()
+            .prefix("test_index_dir")
+            .tempdir()
+            .unwrap();
+        let inverted_index_compressed_mmap = InvertedIndexCompressedMmap::<f16>::convert_and_save(
+            &inverted_index_ram,
+            &tmp_dir_path,
+        )
+        .unwrap();
+        _search_test(&inverted_index_compressed_mmap);
+    }
+
+    #[test]
+    fn search_quantized_u8_test() {
+        let is_stopped = AtomicBool::new(false);
+        let mut builder = InvertedIndexBuilder::new();
+        builder.add(1, [(1, 10.0), (2, 10.0), (3, 10.0)].into());
+        builder.add(2, [(1, 20.0), (2, 20.0), (3, 20.0)].into());
+        builder.add(3, [(1, 30.0), (2, 30.0), (3, 30.0)].into());
+        builder.add(4, [(1, 1.0), (2, 5.0)].into());
+        let inverted_index_ram = builder.build();
+
+        let tmp_dir_path = tempfile::Builder::new()
+            .prefix("test_index_dir")
+            .tempdir()
+            .unwrap();
+        let inverted_index = InvertedIndexQuantizedRam::<QuantizedU8>::from_ram_index(
+            inverted_index_ram,
+            &tmp_dir_path,
+        )
+        .unwrap();
+
+        let mut search_context = SearchContext::new(
+            SparseVector {
+                indices: vec![1, 2, 3],
+                values: vec![1.0, 1.0, 1.0],
+            },
+            3,
+            &inverted_index,
+            get_pooled_scores(),
+            &is_stopped,
+        );
+        let results = search_context.search(&match_all);
+
+        let expected = [(3, 90.0), (2, 60.0), (1, 30.0)];
+        assert_eq!(results.len(), expected.len());
+        for (result, (idx, score)) in results.iter().zip(expected) {
+            assert_eq!(result.idx, idx);
+            // error of each weight is at most half of the quantization step (29 / 255)
+            assert!((result.score - score).abs() < 0.2, "{result:?}");
+        }
     }
 
     #[test]
@@ -657,6 +711,18 @@ mod tests {
         let inverted_index_mmap =
             InvertedIndexMmap::convert_and_save(&inverted_index_ram, &tmp_dir_path).unwrap();
         _search_with_hot_key_test(&inverted_index_mmap);
+
+        // test with compressed mmap index
+        let tmp_dir_path = tempfile::Builder::new()
+            .prefix("test_index_dir")
+            .tempdir()
+            .unwrap();
+        let inverted_index_compressed_mmap = InvertedIndexCompressedMmap::<f32>::convert_and_save(
+            &inverted_index_ram,
+            &tmp_dir_path,
+        )
+        .unwrap();
+        _search_with_hot_key_test(&inverted_index_compressed_mmap);
     }
 
     #[test]
@@ -777,6 +843,60 @@ mod tests {
         inverted_index_ram
     }
 
+    /// Generates a random vector over the first 10 dimensions, each present with probability 0.5
+    fn random_dense_vector<R: Rng + ?Sized>(rnd_gen: &mut R, max_weight: f32) -> SparseVector {
+        let mut tuples = Vec::new();
+        for dim in 1..=10 {
+            if rnd_gen.gen_bool(0.5) {
+                tuples.push((dim, rnd_gen.gen_range(0.0..max_weight)));
+            }
+        }
+        tuples.try_into().unwrap()
+    }
+
+    #[test]
+    fn compressed_search_matches_plain_search_test() {
+        let is_stopped = AtomicBool::new(false);
+        let mut rnd_gen = rand::rngs::StdRng::seed_from_u64(42);
+        // long posting lists to exercise pruning of whole chunks
+        let mut builder = InvertedIndexBuilder::new();
+        for id in 1..=5_000 {
+            builder.add(id, random_dense_vector(&mut rnd_gen, 10.0));
+        }
+        let inverted_index_ram = builder.build();
+        let inverted_index_compressed = InvertedIndexCompressedImmutableRam::<f32>::from_ram_index(
+            inverted_index_ram.clone(),
+            "",
+        )
+        .unwrap();
+
+        for _ in 0..20 {
+            let query = random_dense_vector(&mut rnd_gen, 1.0);
+            let mut search_context = SearchContext::new(
+                query.clone(),
+                10,
+                &inverted_index_ram,
+                get_pooled_scores(),
+                &is_stopped,
+            );
+            let expected = search_context.search(&match_all);
+
+            let mut search_context = SearchContext::new(
+                query,
+                10,
+                &inverted_index_compressed,
+                get_pooled_scores(),
+                &is_stopped,
+            );
+            let results = search_context.search(&match_all);
+
+            let scores = |results: &[ScoredPointOffset]| -> Vec<_> {
+                results.iter().map(|result| result.score).collect()
+            };
+            assert_eq!(scores(&results), scores(&expected));
+        }
+    }
+
     #[test]
     fn promote_longest_test() {
         let is_stopped = AtomicBool::new(false);
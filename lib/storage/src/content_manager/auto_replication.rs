@@ -0,0 +1,509 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use collection::shards::replica_set::{self, ReplicaState};
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::ShardTransfer;
+use collection::shards::CollectionId;
+use rbac::access::Access;
+use tokio::time::sleep;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, ShardTransferOperations, UpdateCollectionOperation,
+};
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::dispatcher::Dispatcher;
+
+/// Interval between checks of replica states
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Replicas of a single shard, as seen by the auto replicator
+#[derive(Debug, Clone)]
+pub struct ShardReplicas {
+    pub collection_name: CollectionId,
+    pub shard_id: ShardId,
+    pub replicas: HashMap<PeerId, ReplicaState>,
+    /// Replicas marked in consensus as created in place of a dead replica
+    pub replacements: HashSet<PeerId>,
+    pub replication_factor: usize,
+    /// Whether a shard transfer of the shard is running
+    pub has_transfer: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationAction {
+    /// Recreate a dead replica on another peer, from an active replica
+    Replicate {
+        collection_name: CollectionId,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+    },
+    /// Drop the replica, which replaced a dead replica that became active again
+    DropReplica {
+        collection_name: CollectionId,
+        shard_id: ShardId,
+        peer_id: PeerId,
+    },
+}
+
+/// Decides on recreating dead replicas on other peers
+///
+/// Tracks for how long replicas are dead. Replicas created in place of a dead one are marked in
+/// consensus, so a new leader drops the same replicas the previous one created.
+pub struct AutoReplicator {
+    grace_period: Duration,
+    dead_since: HashMap<(CollectionId, ShardId, PeerId), Instant>,
+}
+
+impl AutoReplicator {
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            dead_since: HashMap::new(),
+        }
+    }
+
+    /// Forget all tracked state, once this peer is not the leader anymore
+    pub fn reset(&mut self) {
+        self.dead_since.clear();
+    }
+
+    /// Decide which replicas to create or drop, given the current replicas of all shards
+    ///
+    /// A replica is recreated once it is dead for longer than the grace period, and the shard has
+    /// less live replicas than its replication factor. The new replica is placed on the healthy
+    /// peer with the least replicas. Unreachable peers and peers holding any dead replica are not
+    /// considered healthy.
+    ///
+    /// Once a shard has more replicas than its replication factor and all of them are active, the
+    /// replacement on the peer with the most replicas is dropped. Replicas not marked as
+    /// replacements are never dropped.
+    pub fn decide(
+        &mut self,
+        now: Instant,
+        peers: &[PeerId],
+        unreachable: &HashSet<PeerId>,
+        shards: &[ShardReplicas],
+    ) -> Vec<ReplicationAction> {
+        let dead: HashSet<_> = shards
+            .iter()
+            .flat_map(|shard| {
+                shard
+                    .replicas
+                    .iter()
+                    .filter(|(_, state)| **state == ReplicaState::Dead)
+                    .map(|(peer_id, _)| (shard.collection_name.clone(), shard.shard_id, *peer_id))
+            })
+            .collect();
+        self.dead_since.retain(|key, _| dead.contains(key));
+        for key in dead {
+            self.dead_since.entry(key).or_insert(now);
+        }
+
+        let unhealthy: HashSet<PeerId> = self
+            .dead_since
+            .keys()
+            .map(|(_, _, peer_id)| *peer_id)
+            .chain(unreachable.iter().copied())
+            .collect();
+
+        let mut replica_counts: HashMap<PeerId, usize> =
+            peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+        for shard in shards {
+            for peer_id in shard.replicas.keys() {
+                if let Some(count) = replica_counts.get_mut(peer_id) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut actions = Vec::new();
+
+        for shard in shards {
+            if shard.has_transfer {
+                continue;
+            }
+
+            let all_active = shard
+                .replicas
+                .values()
+                .all(|state| *state == ReplicaState::Active);
+            if shard.replicas.len() > shard.replication_factor && all_active {
+                let peer_id = shard
+                    .replacements
+                    .iter()
+                    .filter(|peer_id| shard.replicas.contains_key(peer_id))
+                    .max_by_key(|peer_id| {
+                        (replica_counts.get(peer_id).copied().unwrap_or(0), **peer_id)
+                    })
+                    .copied();
+                if let Some(peer_id) = peer_id {
+                    if let Some(count) = replica_counts.get_mut(&peer_id) {
+                        *count -= 1;
+                    }
+                    actions.push(ReplicationAction::DropReplica {
+                        collection_name: shard.collection_name.clone(),
+                        shard_id: shard.shard_id,
+                        peer_id,
+                    });
+                }
+                continue;
+            }
+
+            let live_replicas = shard
+                .replicas
+                .values()
+                .filter(|state| **state != ReplicaState::Dead)
+                .count();
+            if live_replicas >= shard.replication_factor {
+                continue;
+            }
+
+            let has_expired_dead = shard.replicas.iter().any(|(peer_id, state)| {
+                *state == ReplicaState::Dead
+                    && self
+                        .dead_since
+                        .get(&(shard.collection_name.clone(), shard.shard_id, *peer_id))
+                        .map_or(false, |since| {
+                            now.duration_since(*since) >= self.grace_period
+                        })
+            });
+            if !has_expired_dead {
+                continue;
+            }
+
+            let from = shard
+                .replicas
+                .iter()
+                .filter(|(peer_id, state)| {
+                    **state == ReplicaState::Active && !unreachable.contains(peer_id)
+                })
+                .map(|(peer_id, _)| *peer_id)
+                .min();
+            let Some(from) = from else {
+                continue;
+            };
+
+            let to = replica_counts
+                .iter()
+                .filter(|(peer_id, _)| {
+                    !shard.replicas.contains_key(peer_id) && !unhealthy.contains(peer_id)
+                })
+                .min_by_key(|(peer_id, count)| (**count, **peer_id))
+                .map(|(peer_id, _)| *peer_id);
+            let Some(to) = to else {
+                continue;
+            };
+
+            *replica_counts.get_mut(&to).unwrap() += 1;
+            actions.push(ReplicationAction::Replicate {
+                collection_name: shard.collection_name.clone(),
+                shard_id: shard.shard_id,
+                from_peer_id: from,
+                to_peer_id: to,
+            });
+        }
+
+        actions
+    }
+}
+
+/// Periodically recreate dead replicas on other peers, while this peer is the consensus leader
+///
+/// Never returns in distributed mode.
+pub async fn run_auto_replication(dispatcher: Dispatcher, grace_period: Duration) {
+    let Some(consensus_state) = dispatcher.consensus_state().cloned() else {
+        return;
+    };
+
+    let mut replicator = AutoReplicator::new(grace_period);
+
+    loop {
+        sleep(CHECK_INTERVAL).await;
+
+        if !consensus_state.is_leader() {
+            replicator.reset();
+            continue;
+        }
+
+        let peers: Vec<_> = consensus_state.peer_address_by_id().into_keys().collect();
+        let unreachable = consensus_state.unreachable_peers();
+        let shards = shard_replicas(dispatcher.toc()).await;
+
+        for action in replicator.decide(Instant::now(), &peers, &unreachable, &shards) {
+            log::info!("Applying automatic replication action: {action:?}");
+            if let Err(err) = apply_action(&dispatcher, &action).await {
+                log::warn!("Failed to apply automatic replication action {action:?}: {err}");
+            }
+        }
+    }
+}
+
+async fn shard_replicas(toc: &TableOfContent) -> Vec<ShardReplicas> {
+    let mut shards = Vec::new();
+
+    for collection_name in toc.all_collections().await {
+        // Collection may be removed concurrently
+        let Ok(collection) = toc.get_collection(&collection_name).await else {
+            continue;
+        };
+        let state = collection.state().await;
+        let replication_factor = state.config.params.replication_factor.get() as usize;
+
+        for (shard_id, shard_info) in state.shards {
+            let has_transfer = state.transfers.iter().any(|transfer| {
+                transfer.shard_id == shard_id || transfer.to_shard_id == Some(shard_id)
+            });
+            shards.push(ShardReplicas {
+                collection_name: collection_name.clone(),
+                shard_id,
+                replicas: shard_info.replicas,
+                replacements: shard_info.replacements,
+                replication_factor,
+                has_transfer,
+            });
+        }
+    }
+
+    shards
+}
+
+async fn apply_action(
+    dispatcher: &Dispatcher,
+    action: &ReplicationAction,
+) -> Result<(), StorageError> {
+    match action {
+        ReplicationAction::Replicate {
+            collection_name,
+            shard_id,
+            from_peer_id,
+            to_peer_id,
+        } => {
+            let transfer = CollectionMetaOperations::TransferShard(
+                collection_name.clone(),
+                ShardTransferOperations::Start(ShardTransfer {
+                    shard_id: *shard_id,
+                    to_shard_id: None,
+                    from: *from_peer_id,
+                    to: *to_peer_id,
+                    sync: true,
+                    method: None,
+                }),
+            );
+            submit(dispatcher, transfer).await?;
+
+            // The new replica exists once the transfer is started. If marking it fails, the
+            // replica is kept for good rather than dropping a replica not created by us.
+            let change = replica_set::Change::MarkReplacement(*shard_id, *to_peer_id);
+            submit(dispatcher, replica_changes(collection_name, change)).await
+        }
+        ReplicationAction::DropReplica {
+            collection_name,
+            shard_id,
+            peer_id,
+        } => {
+            let change = replica_set::Change::Remove(*shard_id, *peer_id);
+            submit(dispatcher, replica_changes(collection_name, change)).await
+        }
+    }
+}
+
+fn replica_changes(
+    collection_name: &CollectionId,
+    change: replica_set::Change,
+) -> CollectionMetaOperations {
+    let mut operation = UpdateCollectionOperation::new_empty(collection_name.clone());
+    operation.set_shard_replica_changes(vec![change]);
+    CollectionMetaOperations::UpdateCollection(operation)
+}
+
+async fn submit(
+    dispatcher: &Dispatcher,
+    operation: CollectionMetaOperations,
+) -> Result<(), StorageError> {
+    dispatcher
+        .submit_collection_meta_op(operation, &Access::full(), None)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+    fn shard(replicas: &[(PeerId, ReplicaState)]) -> ShardReplicas {
+        ShardReplicas {
+            collection_name: "test".to_string(),
+            shard_id: 0,
+            replicas: replicas.iter().copied().collect(),
+            replacements: HashSet::new(),
+            replication_factor: replicas.len(),
+            has_transfer: false,
+        }
+    }
+
+    #[test]
+    fn test_replicates_after_grace_period() {
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        let start = Instant::now();
+        let unreachable = HashSet::new();
+        let shards = vec![shard(&[(1, ReplicaState::Active), (2, ReplicaState::Dead)])];
+
+        assert!(replicator
+            .decide(start, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+        assert!(replicator
+            .decide(start + GRACE_PERIOD / 2, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+
+        let actions = replicator.decide(start + GRACE_PERIOD, &[1, 2, 3], &unreachable, &shards);
+        assert_eq!(
+            actions,
+            vec![ReplicationAction::Replicate {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                from_peer_id: 1,
+                to_peer_id: 3,
+            }],
+        );
+
+        // Replacement is already planned
+        assert!(replicator
+            .decide(start + GRACE_PERIOD * 2, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recovered_replica_resets_grace_period() {
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        let start = Instant::now();
+        let unreachable = HashSet::new();
+        let dead = vec![shard(&[(1, ReplicaState::Active), (2, ReplicaState::Dead)])];
+        let active = vec![shard(&[
+            (1, ReplicaState::Active),
+            (2, ReplicaState::Active),
+        ])];
+
+        assert!(replicator
+            .decide(start, &[1, 2, 3], &unreachable, &dead)
+            .is_empty());
+        assert!(replicator
+            .decide(start + GRACE_PERIOD / 2, &[1, 2, 3], &unreachable, &active)
+            .is_empty());
+        assert!(replicator
+            .decide(start + GRACE_PERIOD, &[1, 2, 3], &unreachable, &dead)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_drops_replacement_once_original_is_back() {
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        let start = Instant::now();
+        let unreachable = HashSet::new();
+        let mut shards = vec![shard(&[(1, ReplicaState::Active), (2, ReplicaState::Dead)])];
+
+        replicator.decide(start, &[1, 2, 3], &unreachable, &shards);
+        assert_eq!(
+            replicator
+                .decide(start + GRACE_PERIOD, &[1, 2, 3], &unreachable, &shards)
+                .len(),
+            1,
+        );
+
+        // Replacement is active, original is still dead
+        shards[0].replicas.insert(3, ReplicaState::Active);
+        shards[0].replacements.insert(3);
+        assert!(replicator
+            .decide(start + GRACE_PERIOD * 2, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+
+        // Original recovered
+        shards[0].replicas.insert(2, ReplicaState::Active);
+        let actions =
+            replicator.decide(start + GRACE_PERIOD * 3, &[1, 2, 3], &unreachable, &shards);
+        assert_eq!(
+            actions,
+            vec![ReplicationAction::DropReplica {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                peer_id: 3,
+            }],
+        );
+    }
+
+    #[test]
+    fn test_drops_only_marked_replicas() {
+        let start = Instant::now();
+        let unreachable = HashSet::new();
+        let mut shards = vec![shard(&[
+            (1, ReplicaState::Active),
+            (2, ReplicaState::Active),
+            (3, ReplicaState::Active),
+        ])];
+        shards[0].replication_factor = 2;
+
+        // Excess replicas, which did not replace anything, are kept
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        assert!(replicator
+            .decide(start, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+
+        // Marker is stored in consensus, so any leader drops the replacement
+        shards[0].replacements.insert(2);
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        let actions = replicator.decide(start, &[1, 2, 3], &unreachable, &shards);
+        assert_eq!(
+            actions,
+            vec![ReplicationAction::DropReplica {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                peer_id: 2,
+            }],
+        );
+
+        // Replacement is kept while any replica of the shard is not active
+        shards[0].replicas.insert(1, ReplicaState::Partial);
+        assert!(replicator
+            .decide(start, &[1, 2, 3], &unreachable, &shards)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_skips_unhealthy_peers_and_full_shards() {
+        let mut replicator = AutoReplicator::new(GRACE_PERIOD);
+        let start = Instant::now();
+        let peers = [1, 2, 3, 4, 5];
+        // Peer 5 does not respond to consensus messages
+        let unreachable = HashSet::from([5]);
+
+        let mut dead = shard(&[(1, ReplicaState::Active), (2, ReplicaState::Dead)]);
+        dead.shard_id = 0;
+        // Peer 3 holds a dead replica of another shard
+        let mut other = shard(&[(1, ReplicaState::Active), (3, ReplicaState::Dead)]);
+        other.shard_id = 1;
+        // Shard already has enough live replicas
+        let mut full = shard(&[
+            (1, ReplicaState::Active),
+            (4, ReplicaState::Active),
+            (2, ReplicaState::Dead),
+        ]);
+        full.shard_id = 2;
+        full.replication_factor = 2;
+
+        let shards = vec![dead, other, full];
+        replicator.decide(start, &peers, &unreachable, &shards);
+        let actions = replicator.decide(start + GRACE_PERIOD, &peers, &unreachable, &shards);
+
+        assert_eq!(actions.len(), 2);
+        assert!(actions
+            .iter()
+            .all(|action| matches!(action, ReplicationAction::Replicate { to_peer_id: 4, .. },)));
+        assert!(actions
+            .iter()
+            .all(|action| !matches!(action, ReplicationAction::Replicate { shard_id: 2, .. },)));
+    }
+}
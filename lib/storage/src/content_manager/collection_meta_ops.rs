@@ -5,13 +5,17 @@ use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use collection::operations::strict_mode::StrictModeConfig;
+use collection::operations::ttl::TtlConfig;
 use collection::operations::types::{
     SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
 };
 use collection::shards::replica_set::ReplicaState;
+use collection::shards::resharding::ReshardKey;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{replica_set, CollectionId};
+use rbac::access::{Access, AccessDenied, AccessLevel};
 use schemars::JsonSchema;
 use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
 use serde::{Deserialize, Serialize};
@@ -164,6 +168,14 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Expiration of points. If none - points never expire.
+    #[serde(default)]
+    #[validate]
+    pub ttl_config: Option<TtlConfig>,
+    /// Strict mode limits of requests. If none - requests are not limited.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -210,6 +222,7 @@ pub struct UpdateCollection {
     #[serde(alias = "optimizer_config")]
     pub optimizers_config: Option<OptimizersConfigDiff>, // TODO: Allow updates for other configuration params as well
     /// Collection base params. If none - it is left unchanged.
+    #[validate]
     pub params: Option<CollectionParamsDiff>,
     /// HNSW parameters to update for the collection index. If none - it is left unchanged.
     #[validate]
@@ -221,6 +234,10 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Strict mode limits to update. If none - they are left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -243,6 +260,7 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                strict_mode_config: None,
             },
             shard_replica_changes: None,
         }
@@ -315,6 +333,22 @@ pub enum ShardTransferOperations {
     },
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub enum ReshardingOperation {
+    /// Add or remove a shard and start migrating points between shards
+    Start(ReshardKey),
+    /// Called when all points are migrated, switches reads to the new hash ring
+    CommitRead(ReshardKey),
+    /// Called when all peers read by the new hash ring, switches writes to the new hash ring
+    CommitWrite(ReshardKey),
+    /// Called when outdated points are cleaned up, completes the resharding operation
+    Finish(ReshardKey),
+    Abort {
+        key: ReshardKey,
+        reason: String,
+    },
+}
+
 /// Sets the state of shard replica
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 pub struct SetShardReplicaState {
@@ -367,6 +401,7 @@ pub enum CollectionMetaOperations {
     DeleteCollection(DeleteCollectionOperation),
     ChangeAliases(ChangeAliasesOperation),
     TransferShard(CollectionId, ShardTransferOperations),
+    Resharding(CollectionId, ReshardingOperation),
     SetShardReplicaState(SetShardReplicaState),
     CreateShardKey(CreateShardKey),
     DropShardKey(DropShardKey),
@@ -375,6 +410,50 @@ pub enum CollectionMetaOperations {
     Nop { token: usize }, // Empty operation
 }
 
+impl CollectionMetaOperations {
+    /// Check that the operation is allowed with the given access rights.
+    ///
+    /// Collection management requires manage access to the whole collection.
+    /// Aliases are shared between collections, so changing them requires global manage access.
+    pub fn check_access(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            CollectionMetaOperations::CreateCollection(operation) => {
+                if let Some(init_from) = &operation.create_collection.init_from {
+                    access
+                        .check_whole_collection_access(&init_from.collection, AccessLevel::Read)?;
+                }
+                access
+                    .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage)
+            }
+            CollectionMetaOperations::UpdateCollection(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::DeleteCollection(operation) => {
+                access.check_whole_collection_access(&operation.0, AccessLevel::Manage)
+            }
+            CollectionMetaOperations::ChangeAliases(_) => {
+                access.check_global_access(AccessLevel::Manage)
+            }
+            CollectionMetaOperations::TransferShard(collection_name, _) => {
+                access.check_whole_collection_access(collection_name, AccessLevel::Manage)
+            }
+            CollectionMetaOperations::Resharding(collection_name, _) => {
+                access.check_whole_collection_access(collection_name, AccessLevel::Manage)
+            }
+            CollectionMetaOperations::SetShardReplicaState(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::CreateShardKey(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::DropShardKey(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::CreatePayloadIndex(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::DropPayloadIndex(operation) => access
+                .check_whole_collection_access(&operation.collection_name, AccessLevel::Manage),
+            CollectionMetaOperations::Nop { .. } => access.check_global_access(AccessLevel::Manage),
+        }
+    }
+}
+
 /// Use config of the existing collection to generate a create collection operation
 /// for the new collection
 impl From<CollectionConfig> for CreateCollection {
@@ -392,6 +471,8 @@ impl From<CollectionConfig> for CreateCollection {
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            ttl_config: value.params.ttl_config,
+            strict_mode_config: value.strict_mode_config,
         }
     }
 }
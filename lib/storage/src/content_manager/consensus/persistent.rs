@@ -14,6 +14,7 @@ use raft::RaftState;
 use serde::{Deserialize, Serialize};
 
 use crate::content_manager::consensus::entry_queue::{EntryApplyProgressQueue, EntryId};
+use crate::content_manager::rebalance::state::{RebalanceOperation, RebalanceStatus};
 use crate::types::{PeerAddressById, PeerMetadata, PeerMetadataById};
 use crate::StorageError;
 
@@ -41,6 +42,9 @@ pub struct Persistent {
     pub peer_address_by_id: Arc<RwLock<PeerAddressById>>,
     #[serde(default)]
     pub peer_metadata_by_id: Arc<RwLock<PeerMetadataById>>,
+    /// Last started rebalancing of the cluster
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebalance: Option<RebalanceStatus>,
     pub this_peer_id: PeerId,
     #[serde(skip)]
     pub path: PathBuf,
@@ -63,9 +67,11 @@ impl Persistent {
         meta: &SnapshotMetadata,
         address_by_id: PeerAddressById,
         metadata_by_id: PeerMetadataById,
+        rebalance: Option<RebalanceStatus>,
     ) -> Result<(), StorageError> {
         *self.peer_address_by_id.write() = address_by_id;
         *self.peer_metadata_by_id.write() = metadata_by_id;
+        self.rebalance = rebalance;
         self.state.conf_state = meta.get_conf_state().clone();
         self.state.hard_state.term = cmp::max(self.state.hard_state.term, meta.term);
         self.state.hard_state.commit = meta.index;
@@ -173,6 +179,18 @@ impl Persistent {
         self.save()
     }
 
+    pub fn apply_rebalance_operation(
+        &mut self,
+        operation: RebalanceOperation,
+    ) -> Result<(), StorageError> {
+        operation.apply(&mut self.rebalance)?;
+        self.save()
+    }
+
+    pub fn rebalance(&self) -> Option<RebalanceStatus> {
+        self.rebalance.clone()
+    }
+
     pub fn last_applied_entry(&self) -> Option<u64> {
         self.apply_progress_queue.get_last_applied()
     }
@@ -223,6 +241,7 @@ impl Persistent {
             apply_progress_queue: Default::default(),
             peer_address_by_id: Default::default(),
             peer_metadata_by_id: Default::default(),
+            rebalance: None,
             this_peer_id,
             path,
             latest_snapshot_meta: Default::default(),
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Display;
 use std::future::Future;
@@ -31,6 +31,7 @@ use crate::content_manager::consensus::consensus_wal::ConsensusOpWal;
 use crate::content_manager::consensus::entry_queue::EntryId;
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::consensus::persistent::Persistent;
+use crate::content_manager::rebalance::state::RebalanceStatus;
 use crate::types::{
     ClusterInfo, ClusterStatus, ConsensusThreadStatus, MessageSendErrors, PeerAddressById,
     PeerInfo, PeerMetadataById, RaftInfo,
@@ -49,6 +50,8 @@ pub struct SnapshotData {
     pub address_by_id: PeerAddressById,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata_by_id: PeerMetadataById,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebalance: Option<RebalanceStatus>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -177,6 +180,15 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().this_peer_id
     }
 
+    /// Whether this peer is the current consensus leader
+    pub fn is_leader(&self) -> bool {
+        let this_peer_id = self.this_peer_id();
+        self.soft_state
+            .read()
+            .as_ref()
+            .map_or(false, |state| state.leader_id == this_peer_id)
+    }
+
     pub fn first_voter(&self) -> PeerId {
         match self.first_voter.read().as_ref() {
             Some(id) => *id,
@@ -458,6 +470,13 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 Ok(true)
             }
 
+            ConsensusOperations::Rebalance(operation) => {
+                self.persistent
+                    .write()
+                    .apply_rebalance_operation(operation)?;
+                Ok(true)
+            }
+
             ConsensusOperations::RequestSnapshot | ConsensusOperations::ReportSnapshot { .. } => {
                 unreachable!()
             }
@@ -485,6 +504,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             meta,
             data.address_by_id,
             data.metadata_by_id,
+            data.rebalance,
         )?;
 
         Ok(Ok(()))
@@ -700,10 +720,27 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().peer_address_by_id()
     }
 
+    /// Peers, which consensus messages currently can not be sent to
+    ///
+    /// Only the leader sends messages to all peers, so this is complete on the leader only.
+    pub fn unreachable_peers(&self) -> HashSet<PeerId> {
+        let message_send_failures = self.message_send_failures.read();
+        self.peer_address_by_id()
+            .into_iter()
+            .filter(|(_, uri)| message_send_failures.contains_key(&uri.to_string()))
+            .map(|(peer_id, _)| peer_id)
+            .collect()
+    }
+
     pub fn peer_metadata_by_id(&self) -> PeerMetadataById {
         self.persistent.read().peer_metadata_by_id()
     }
 
+    /// Last started rebalancing of the cluster
+    pub fn rebalance_status(&self) -> Option<RebalanceStatus> {
+        self.persistent.read().rebalance()
+    }
+
     pub fn peer_count(&self) -> usize {
         self.persistent.read().peer_address_by_id.read().len()
     }
@@ -803,6 +840,7 @@ impl<C: CollectionContainer> Storage for ConsensusManager<C> {
                 collections_data,
                 address_by_id: persistent.peer_address_by_id(),
                 metadata_by_id: persistent.peer_metadata_by_id(),
+                rebalance: persistent.rebalance(),
             };
             Ok(raft::eraftpb::Snapshot {
                 data: serde_cbor::to_vec(&snapshot).map_err(raft_error_other)?,
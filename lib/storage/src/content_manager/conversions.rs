@@ -20,6 +20,8 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::Timeout { .. } => tonic::Code::DeadlineExceeded,
         StorageError::AlreadyExists { .. } => tonic::Code::AlreadyExists,
         StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
+        StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
+        StorageError::PositionTruncated { .. } => tonic::Code::OutOfRange,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
@@ -38,7 +40,14 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 },
                 sparse_vectors: value
                     .sparse_vectors_config
-                    .map(|config| config.map.into_iter().map(|(k, v)| (k, v.into())).collect()),
+                    .map(|config| {
+                        config
+                            .map
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                    })
+                    .transpose()?,
                 hnsw_config: value.hnsw_config.map(|v| v.into()),
                 wal_config: value.wal_config.map(|v| v.into()),
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
@@ -57,6 +66,8 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                ttl_config: value.ttl_config.map(TryInto::try_into).transpose()?,
+                strict_mode_config: value.strict_mode_config.map(Into::into),
             },
         )))
     }
@@ -81,11 +92,18 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                     .quantization_config
                     .map(TryInto::try_into)
                     .transpose()?,
-                sparse_vectors: value.sparse_vectors_config.map(|config| {
-                    SparseVectorsConfig(
-                        config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
-                    )
-                }),
+                sparse_vectors: value
+                    .sparse_vectors_config
+                    .map(|config| {
+                        config
+                            .map
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                            .map(SparseVectorsConfig)
+                    })
+                    .transpose()?,
+                strict_mode_config: value.strict_mode_config.map(Into::into),
             },
         )))
     }
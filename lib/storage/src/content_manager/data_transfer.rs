@@ -85,6 +85,7 @@ async fn replicate_shard_data(
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
             order_by: None,
+            cursor: None,
         };
 
         let collections_read = collections.read().await;
@@ -3,6 +3,7 @@ use std::io::Error as IoError;
 
 use collection::operations::types::CollectionError;
 use io::file_operations::FileStorageError;
+use rbac::access::AccessDenied;
 use tempfile::PersistError;
 use thiserror::Error;
 
@@ -28,6 +29,10 @@ pub enum StorageError {
     Timeout { description: String },
     #[error("Checksum mismatch: expected {expected}, actual {actual}")]
     ChecksumMismatch { expected: String, actual: String },
+    #[error("Forbidden: {description}")]
+    Forbidden { description: String },
+    #[error("Position truncated: {description}")]
+    PositionTruncated { description: String },
 }
 
 impl StorageError {
@@ -63,6 +68,12 @@ impl StorageError {
         }
     }
 
+    pub fn forbidden(description: impl Into<String>) -> StorageError {
+        StorageError::Forbidden {
+            description: description.into(),
+        }
+    }
+
     /// Used to override the `description` field of the resulting `StorageError`
     pub fn from_inconsistent_shard_failure(
         err: CollectionError,
@@ -108,6 +119,12 @@ impl StorageError {
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: overriding_description,
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: overriding_description,
+            },
+            CollectionError::PositionTruncated { .. } => StorageError::PositionTruncated {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -149,10 +166,22 @@ impl From<CollectionError> for StorageError {
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: format!("{err}"),
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: format!("{err}"),
+            },
+            CollectionError::PositionTruncated { description } => {
+                StorageError::PositionTruncated { description }
+            }
         }
     }
 }
 
+impl From<AccessDenied> for StorageError {
+    fn from(err: AccessDenied) -> Self {
+        StorageError::forbidden(err.0)
+    }
+}
+
 impl From<IoError> for StorageError {
     fn from(err: IoError) -> Self {
         StorageError::service_error(format!("{err}"))
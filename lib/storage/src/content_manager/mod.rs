@@ -5,6 +5,7 @@ use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
 
 pub mod alias_mapping;
+pub mod auto_replication;
 pub mod collection_meta_ops;
 mod collections_ops;
 pub mod consensus;
@@ -12,6 +13,7 @@ pub mod consensus_manager;
 pub mod conversions;
 mod data_transfer;
 pub mod errors;
+pub mod rebalance;
 pub mod shard_distribution;
 pub mod snapshots;
 pub mod toc;
@@ -29,6 +31,7 @@ pub mod consensus_ops {
         CollectionMetaOperations, SetShardReplicaState, ShardTransferOperations, UpdateCollection,
         UpdateCollectionOperation,
     };
+    use crate::content_manager::rebalance::state::RebalanceOperation;
     use crate::types::PeerMetadata;
 
     /// Operation that should pass consensus
@@ -44,6 +47,7 @@ pub mod consensus_ops {
             peer_id: PeerId,
             metadata: PeerMetadata,
         },
+        Rebalance(RebalanceOperation),
         RequestSnapshot,
         ReportSnapshot {
             peer_id: PeerId,
@@ -114,6 +118,7 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    strict_mode_config: None,
                 },
             );
             operation
@@ -0,0 +1,578 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+use collection::common::stoppable_task_async::{
+    spawn_async_cancellable, CancellableAsyncTaskHandle,
+};
+use collection::shards::replica_set::{self, ReplicaState};
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferMethod};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use rbac::access::Access;
+use tokio::time::sleep;
+
+use super::state::{RebalanceActionState, RebalanceOperation, RebalanceState, RebalanceStatus};
+use super::RebalanceAction;
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, ShardTransferOperations, UpdateCollectionOperation,
+};
+use crate::content_manager::errors::StorageError;
+use crate::dispatcher::Dispatcher;
+use crate::{ConsensusOperations, ConsensusStateRef};
+
+/// Interval between checks of the rebalancing state in consensus
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Interval between checks of a running shard transfer
+const TRANSFER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Apply the rebalancing stored in consensus, while this peer is the consensus leader
+///
+/// Once the leader changes, the executor on the previous leader is stopped without aborting its
+/// shard transfers, and the new leader resumes the rebalancing.
+///
+/// Never returns in distributed mode.
+pub async fn run_rebalancer(dispatcher: Dispatcher) {
+    let Some(consensus_state) = dispatcher.consensus_state().cloned() else {
+        return;
+    };
+
+    let mut rebalancer: Option<Rebalancer> = None;
+
+    loop {
+        sleep(CHECK_INTERVAL).await;
+
+        let is_leader = consensus_state.is_leader();
+
+        if let Some(current) = &rebalancer {
+            if current.is_running() {
+                // The executor ends by itself, once the rebalancing is aborted
+                if !is_leader {
+                    log::info!(
+                        "Stopping rebalancing {}, this peer is not the leader",
+                        current.id
+                    );
+                    rebalancer.take().unwrap().stop();
+                }
+                continue;
+            }
+        }
+        rebalancer = None;
+
+        if !is_leader {
+            continue;
+        }
+
+        let Some(status) = consensus_state
+            .rebalance_status()
+            .filter(RebalanceStatus::is_running)
+        else {
+            continue;
+        };
+
+        log::info!("Applying rebalancing {}", status.id);
+        rebalancer = Some(Rebalancer::start(
+            dispatcher.clone(),
+            consensus_state.clone(),
+            status,
+        ));
+    }
+}
+
+/// Background task applying a rebalancing on this peer
+struct Rebalancer {
+    id: u64,
+    task: CancellableAsyncTaskHandle<()>,
+}
+
+impl Rebalancer {
+    fn start(
+        dispatcher: Dispatcher,
+        consensus_state: ConsensusStateRef,
+        status: RebalanceStatus,
+    ) -> Self {
+        let id = status.id;
+
+        let task = spawn_async_cancellable(move |cancel| async move {
+            let executor = Executor {
+                dispatcher,
+                consensus_state,
+                id,
+                method: status.method,
+            };
+            tokio::select! {
+                () = cancel.cancelled() => {}
+                () = executor.run(status) => {}
+            }
+        });
+
+        Self { id, task }
+    }
+
+    fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stop applying the rebalancing on this peer, keeping the running shard transfers
+    fn stop(self) {
+        self.task.ask_to_cancel();
+    }
+}
+
+/// Decides which actions of a plan to run next
+///
+/// Actions of the same shard never run concurrently and run in the planned order.
+struct Schedule<'a> {
+    actions: &'a [RebalanceAction],
+    pending: VecDeque<usize>,
+    busy_shards: HashSet<(&'a str, ShardId)>,
+}
+
+impl<'a> Schedule<'a> {
+    fn new(actions: &'a [RebalanceAction], pending: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            actions,
+            pending: pending.into_iter().collect(),
+            busy_shards: HashSet::new(),
+        }
+    }
+
+    fn shard(&self, idx: usize) -> (&'a str, ShardId) {
+        let action = &self.actions[idx];
+        (action.collection_name(), action.shard_id())
+    }
+
+    /// Take the first pending action of a shard without running actions
+    fn next(&mut self) -> Option<usize> {
+        let pos = self
+            .pending
+            .iter()
+            .position(|idx| !self.busy_shards.contains(&self.shard(*idx)))?;
+        let idx = self.pending.remove(pos).unwrap();
+        self.busy_shards.insert(self.shard(idx));
+        Some(idx)
+    }
+
+    fn done(&mut self, idx: usize) {
+        self.busy_shards.remove(&self.shard(idx));
+    }
+
+    /// Mark the action as failed, and return the skipped pending actions of its shard
+    ///
+    /// Remaining actions of the shard were planned for the placement after the failed action,
+    /// they are not applied on top of an unexpected placement.
+    fn failed(&mut self, idx: usize) -> Vec<usize> {
+        let shard = self.shard(idx);
+        self.busy_shards.remove(&shard);
+
+        let (skipped, pending): (Vec<_>, Vec<_>) = self
+            .pending
+            .iter()
+            .copied()
+            .partition(|pending_idx| self.shard(*pending_idx) == shard);
+        self.pending = pending.into();
+        skipped
+    }
+}
+
+struct Executor {
+    dispatcher: Dispatcher,
+    consensus_state: ConsensusStateRef,
+    id: u64,
+    method: Option<ShardTransferMethod>,
+}
+
+impl Executor {
+    /// Apply all unfinished actions of the rebalancing
+    ///
+    /// Actions left running by a previous leader are applied again. Their outcome is checked
+    /// first, so already applied actions are not repeated.
+    async fn run(&self, status: RebalanceStatus) {
+        let actions: Vec<_> = status
+            .actions
+            .iter()
+            .map(|status| status.action.clone())
+            .collect();
+        let unfinished = status
+            .actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| {
+                matches!(
+                    action.status,
+                    RebalanceActionState::Pending | RebalanceActionState::Running,
+                )
+            })
+            .map(|(idx, _)| idx);
+        let mut has_failed = status
+            .actions
+            .iter()
+            .any(|action| action.status == RebalanceActionState::Failed);
+
+        let mut schedule = Schedule::new(&actions, unfinished);
+        let mut running = FuturesUnordered::new();
+        let max_concurrent_transfers = status.max_concurrent_transfers.max(1);
+
+        loop {
+            while running.len() < max_concurrent_transfers && self.is_current() {
+                let Some(idx) = schedule.next() else {
+                    break;
+                };
+                self.set_action_state(idx, RebalanceActionState::Running, None)
+                    .await;
+                let action = &actions[idx];
+                running.push(async move { (idx, self.execute(action).await) });
+            }
+
+            let Some((idx, result)) = running.next().await else {
+                break;
+            };
+
+            let action = &actions[idx];
+            match result {
+                Ok(()) => {
+                    schedule.done(idx);
+                    self.set_action_state(idx, RebalanceActionState::Done, None)
+                        .await;
+                }
+                Err(err) => {
+                    log::warn!("Failed to apply rebalance action {action:?}: {err}");
+                    has_failed = true;
+                    self.set_action_state(idx, RebalanceActionState::Failed, Some(err.to_string()))
+                        .await;
+
+                    let skipped = schedule.failed(idx);
+                    if !self.is_current() {
+                        continue;
+                    }
+                    for skipped_idx in skipped {
+                        let error = format!(
+                            "Skipped, previous action on shard {}:{} failed",
+                            action.collection_name(),
+                            action.shard_id(),
+                        );
+                        self.set_action_state(
+                            skipped_idx,
+                            RebalanceActionState::Failed,
+                            Some(error),
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
+
+        if !self.is_current() {
+            log::info!("Rebalancing {} was aborted", self.id);
+            return;
+        }
+
+        let state = if has_failed {
+            RebalanceState::Failed
+        } else {
+            RebalanceState::Finished
+        };
+        let operation = RebalanceOperation::Finish {
+            id: self.id,
+            status: state,
+        };
+        match self.propose(operation).await {
+            Ok(()) => log::info!("Rebalancing {} is over: {state:?}", self.id),
+            Err(err) => log::warn!("Failed to finish rebalancing {}: {err}", self.id),
+        }
+    }
+
+    /// Whether this rebalancing is still running according to consensus
+    fn is_current(&self) -> bool {
+        self.consensus_state
+            .rebalance_status()
+            .map_or(false, |status| status.id == self.id && status.is_running())
+    }
+
+    async fn propose(&self, operation: RebalanceOperation) -> Result<(), StorageError> {
+        self.consensus_state
+            .propose_consensus_op_with_await(ConsensusOperations::Rebalance(operation), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_action_state(
+        &self,
+        idx: usize,
+        state: RebalanceActionState,
+        error: Option<String>,
+    ) {
+        let operation = RebalanceOperation::UpdateAction {
+            id: self.id,
+            action: idx,
+            status: state,
+            error,
+        };
+        if let Err(err) = self.propose(operation).await {
+            log::warn!("Failed to update state of rebalance action {idx}: {err}");
+        }
+    }
+
+    async fn execute(&self, action: &RebalanceAction) -> Result<(), StorageError> {
+        match action {
+            RebalanceAction::ReplicateShard {
+                collection_name,
+                shard_id,
+                from_peer_id,
+                to_peer_id,
+            } => {
+                self.transfer_shard(collection_name, *shard_id, *from_peer_id, *to_peer_id, true)
+                    .await
+            }
+            RebalanceAction::MoveShard {
+                collection_name,
+                shard_id,
+                from_peer_id,
+                to_peer_id,
+            } => {
+                self.transfer_shard(
+                    collection_name,
+                    *shard_id,
+                    *from_peer_id,
+                    *to_peer_id,
+                    false,
+                )
+                .await
+            }
+            RebalanceAction::DropReplica {
+                collection_name,
+                shard_id,
+                peer_id,
+            } => {
+                self.drop_replica(collection_name, *shard_id, *peer_id)
+                    .await
+            }
+        }
+    }
+
+    /// Transfer the shard through consensus, and wait for the transfer to end
+    ///
+    /// If the transfer is already running, e.g. started by a previous leader, only waits for it.
+    async fn transfer_shard(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        from: PeerId,
+        to: PeerId,
+        sync: bool,
+    ) -> Result<(), StorageError> {
+        let transfer = ShardTransfer {
+            shard_id,
+            to_shard_id: None,
+            from,
+            to,
+            sync,
+            method: self.method,
+        };
+        let transfer_key = transfer.key();
+
+        let collection = self.dispatcher.get_collection(collection_name).await?;
+        if !collection.check_transfer_exists(&transfer_key).await {
+            let state = collection.state().await;
+            let replicas = state.shards.get(&shard_id).map(|shard| &shard.replicas);
+
+            let is_applied = replicas.map_or(false, |replicas| {
+                replicas.get(&to) == Some(&ReplicaState::Active)
+                    && (sync || !replicas.contains_key(&from))
+            });
+            if is_applied {
+                return Ok(());
+            }
+
+            let is_valid = replicas.map_or(false, |replicas| {
+                replicas.get(&from) == Some(&ReplicaState::Active) && !replicas.contains_key(&to)
+            }) && !state
+                .transfers
+                .iter()
+                .any(|transfer| transfer.shard_id == shard_id);
+            if !is_valid {
+                return Err(StorageError::bad_request(format!(
+                    "Placement of shard {collection_name}:{shard_id} changed since planning",
+                )));
+            }
+
+            self.dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::TransferShard(
+                        collection_name.to_string(),
+                        ShardTransferOperations::Start(transfer),
+                    ),
+                    &Access::full(),
+                    None,
+                )
+                .await?;
+        }
+
+        loop {
+            sleep(TRANSFER_POLL_INTERVAL).await;
+
+            if !self.is_current() {
+                self.abort_transfer(collection_name, transfer_key).await;
+                return Err(StorageError::service_error("Rebalancing aborted"));
+            }
+
+            let collection = self.dispatcher.get_collection(collection_name).await?;
+            if collection.check_transfer_exists(&transfer_key).await {
+                continue;
+            }
+
+            let to_state = collection
+                .state()
+                .await
+                .shards
+                .get(&shard_id)
+                .and_then(|shard| shard.replicas.get(&to).copied());
+            return match to_state {
+                Some(ReplicaState::Active) => Ok(()),
+                _ => Err(StorageError::service_error(format!(
+                    "Transfer of shard {collection_name}:{shard_id} from {from} to {to} failed",
+                ))),
+            };
+        }
+    }
+
+    async fn abort_transfer(&self, collection_name: &str, transfer_key: ShardTransferKey) {
+        let result = self
+            .dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::TransferShard(
+                    collection_name.to_string(),
+                    ShardTransferOperations::Abort {
+                        transfer: transfer_key,
+                        reason: "rebalancing aborted".to_string(),
+                    },
+                ),
+                &Access::full(),
+                None,
+            )
+            .await;
+
+        if let Err(err) = result {
+            log::warn!("Failed to abort shard transfer of aborted rebalancing: {err}");
+        }
+    }
+
+    async fn drop_replica(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        peer_id: PeerId,
+    ) -> Result<(), StorageError> {
+        {
+            let collection = self.dispatcher.get_collection(collection_name).await?;
+            let state = collection.state().await;
+            let Some(replicas) = state.shards.get(&shard_id).map(|shard| &shard.replicas) else {
+                return Err(StorageError::bad_request(format!(
+                    "Placement of shard {collection_name}:{shard_id} changed since planning",
+                )));
+            };
+
+            // Already dropped, e.g. before the leader changed
+            if !replicas.contains_key(&peer_id) {
+                return Ok(());
+            }
+
+            let has_other_active = replicas.iter().any(|(replica_peer_id, state)| {
+                *replica_peer_id != peer_id && *state == ReplicaState::Active
+            });
+            if !has_other_active {
+                return Err(StorageError::bad_request(format!(
+                    "Placement of shard {collection_name}:{shard_id} changed since planning",
+                )));
+            }
+        }
+
+        let mut operation = UpdateCollectionOperation::new_empty(collection_name.to_string());
+        operation.set_shard_replica_changes(vec![replica_set::Change::Remove(shard_id, peer_id)]);
+
+        self.dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::UpdateCollection(operation),
+                &Access::full(),
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop_replica(collection_name: &str, shard_id: ShardId, peer_id: PeerId) -> RebalanceAction {
+        RebalanceAction::DropReplica {
+            collection_name: collection_name.to_string(),
+            shard_id,
+            peer_id,
+        }
+    }
+
+    #[test]
+    fn test_schedule_runs_shard_actions_in_order() {
+        let actions = vec![
+            drop_replica("a", 0, 1),
+            drop_replica("a", 0, 2),
+            drop_replica("a", 1, 1),
+            drop_replica("b", 0, 1),
+        ];
+        let mut schedule = Schedule::new(&actions, 0..actions.len());
+
+        // Second action of shard a:0 waits for the first one
+        assert_eq!(schedule.next(), Some(0));
+        assert_eq!(schedule.next(), Some(2));
+        assert_eq!(schedule.next(), Some(3));
+        assert_eq!(schedule.next(), None);
+
+        schedule.done(2);
+        assert_eq!(schedule.next(), None);
+
+        schedule.done(0);
+        assert_eq!(schedule.next(), Some(1));
+        assert_eq!(schedule.next(), None);
+    }
+
+    #[test]
+    fn test_schedule_skips_shard_actions_after_failure() {
+        let actions = vec![
+            drop_replica("a", 0, 1),
+            drop_replica("a", 1, 1),
+            drop_replica("a", 0, 2),
+            drop_replica("a", 0, 3),
+            drop_replica("a", 1, 2),
+        ];
+        let mut schedule = Schedule::new(&actions, 0..actions.len());
+
+        assert_eq!(schedule.next(), Some(0));
+        assert_eq!(schedule.next(), Some(1));
+
+        assert_eq!(schedule.failed(0), vec![2, 3]);
+        assert_eq!(schedule.next(), None);
+
+        schedule.done(1);
+        assert_eq!(schedule.next(), Some(4));
+        assert_eq!(schedule.next(), None);
+    }
+
+    #[test]
+    fn test_schedule_resumes_unfinished_actions() {
+        let actions = vec![
+            drop_replica("a", 0, 1),
+            drop_replica("a", 0, 2),
+            drop_replica("a", 1, 1),
+        ];
+        // First action is done already
+        let mut schedule = Schedule::new(&actions, [1, 2]);
+
+        assert_eq!(schedule.next(), Some(1));
+        assert_eq!(schedule.next(), Some(2));
+        assert_eq!(schedule.next(), None);
+    }
+}
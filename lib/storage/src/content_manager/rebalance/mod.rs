@@ -0,0 +1,419 @@
+pub mod executor;
+pub mod planner;
+pub mod state;
+
+use std::mem::{size_of, size_of_val};
+
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, ShardingMethod};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{
+    CountRequestInternal, Record, SampleRequestInternal, VectorsConfig,
+};
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::ShardTransferMethod;
+use schemars::JsonSchema;
+use segment::data_types::vectors::{Vector, VectorElementType, VectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{VectorStorageDatatype, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use self::planner::{plan_rebalance, ShardPlacement};
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+
+/// Default limit of shard transfers run by the rebalancer at once
+pub const DEFAULT_MAX_CONCURRENT_TRANSFERS: usize = 2;
+
+/// Number of points sampled from a collection to estimate the size of its points
+const POINT_SIZE_SAMPLE: usize = 64;
+
+/// Approximate size of the point id, version and id mapping, stored for every point
+const POINT_OVERHEAD: usize = 32;
+
+/// Default allowed load difference of two peers, relative to the average peer load
+pub const DEFAULT_TOLERANCE: f64 = 0.1;
+
+/// Load metric, which is evened out across peers
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceStrategy {
+    /// Number of shard replicas on each peer
+    #[default]
+    ShardCount,
+    /// Number of points in shard replicas on each peer
+    PointCount,
+    /// Estimated disk usage of shard replicas on each peer, based on the number of points and
+    /// the average size of a sample of points, including vectors and payload
+    DiskUsage,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RebalanceRequest {
+    /// Load metric to balance across peers. Default: shard_count
+    #[serde(default)]
+    pub strategy: RebalanceStrategy,
+    /// Collections to rebalance. If not specified, all collections are rebalanced.
+    /// Load of other collections is not taken into account.
+    pub collections: Option<Vec<String>>,
+    /// Maximum number of shard transfers running at once. Default: 2
+    #[validate(range(min = 1))]
+    pub max_concurrent_transfers: Option<usize>,
+    /// Allowed load difference of the most and the least loaded peer, relative to the average
+    /// peer load. Default: 0.1
+    #[validate(range(min = 0.0))]
+    pub tolerance: Option<f64>,
+    /// Method for transferring shards
+    pub method: Option<ShardTransferMethod>,
+}
+
+/// Single step of a rebalance plan
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceAction {
+    /// Create a new replica of the shard, to reach the replication factor
+    ReplicateShard {
+        collection_name: String,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+    },
+    /// Move the replica of the shard to a less loaded peer
+    MoveShard {
+        collection_name: String,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+    },
+    /// Drop a surplus replica of the shard
+    DropReplica {
+        collection_name: String,
+        shard_id: ShardId,
+        peer_id: PeerId,
+    },
+}
+
+impl RebalanceAction {
+    pub fn collection_name(&self) -> &str {
+        match self {
+            RebalanceAction::ReplicateShard {
+                collection_name, ..
+            }
+            | RebalanceAction::MoveShard {
+                collection_name, ..
+            }
+            | RebalanceAction::DropReplica {
+                collection_name, ..
+            } => collection_name,
+        }
+    }
+
+    pub fn shard_id(&self) -> ShardId {
+        match self {
+            RebalanceAction::ReplicateShard { shard_id, .. }
+            | RebalanceAction::MoveShard { shard_id, .. }
+            | RebalanceAction::DropReplica { shard_id, .. } => *shard_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+pub struct PeerLoad {
+    pub peer_id: PeerId,
+    /// Current load of the peer, measured by the rebalance strategy
+    pub load: u64,
+    /// Load of the peer, once the plan is applied
+    pub planned_load: u64,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct RebalancePlan {
+    pub strategy: RebalanceStrategy,
+    pub peers: Vec<PeerLoad>,
+    /// Actions to apply, in order
+    pub actions: Vec<RebalanceAction>,
+}
+
+/// Plan rebalancing of the shards of the given peers
+pub async fn plan(
+    toc: &TableOfContent,
+    peers: &[PeerId],
+    request: &RebalanceRequest,
+) -> Result<RebalancePlan, StorageError> {
+    let collection_names = match &request.collections {
+        Some(collections) => collections.clone(),
+        None => toc.all_collections().await,
+    };
+
+    let mut placements = Vec::new();
+    for collection_name in collection_names {
+        let collection = toc.get_collection(&collection_name).await?;
+        // Resolve aliases
+        let collection_name = collection.name();
+        let state = collection.state().await;
+
+        let replication_factor = match state.config.params.sharding_method.unwrap_or_default() {
+            ShardingMethod::Auto => Some(state.config.params.replication_factor.get() as usize),
+            // Shard keys may have been created with their own replication factor
+            ShardingMethod::Custom => None,
+        };
+        let point_size = match request.strategy {
+            RebalanceStrategy::DiskUsage => estimate_point_size(&collection, &state.config).await?,
+            RebalanceStrategy::ShardCount | RebalanceStrategy::PointCount => 1,
+        };
+
+        for (shard_id, shard_info) in state.shards {
+            let weight = match request.strategy {
+                RebalanceStrategy::ShardCount => 1,
+                RebalanceStrategy::PointCount | RebalanceStrategy::DiskUsage => {
+                    let count_request = CountRequestInternal {
+                        filter: None,
+                        exact: false,
+                    };
+                    let count = collection
+                        .count(
+                            count_request,
+                            None,
+                            &ShardSelectorInternal::ShardId(shard_id),
+                        )
+                        .await?
+                        .count as u64;
+                    match request.strategy {
+                        RebalanceStrategy::DiskUsage => count * point_size,
+                        _ => count,
+                    }
+                }
+            };
+
+            let movable = state.resharding.is_none()
+                && !state.transfers.iter().any(|transfer| {
+                    transfer.shard_id == shard_id || transfer.to_shard_id == Some(shard_id)
+                })
+                && shard_info
+                    .replicas
+                    .values()
+                    .all(|state| *state == ReplicaState::Active);
+
+            let mut replicas: Vec<_> = shard_info.replicas.into_keys().collect();
+            replicas.sort_unstable();
+
+            placements.push(ShardPlacement {
+                collection_name: collection_name.clone(),
+                shard_id,
+                replicas,
+                weight,
+                replication_factor,
+                movable,
+            });
+        }
+    }
+    placements.sort_unstable_by(|a, b| {
+        (&a.collection_name, a.shard_id).cmp(&(&b.collection_name, b.shard_id))
+    });
+
+    let tolerance = request.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+    let (peers, actions) = plan_rebalance(peers, placements, tolerance);
+
+    Ok(RebalancePlan {
+        strategy: request.strategy,
+        peers,
+        actions,
+    })
+}
+
+/// Estimate the average size of a point of the collection in bytes
+///
+/// Sizes are measured on a sample of stored points, so multi-vectors, sparse vectors and payload
+/// are accounted for. Falls back to the size of the configured dense vectors, if the collection
+/// is empty.
+async fn estimate_point_size(
+    collection: &Collection,
+    config: &CollectionConfig,
+) -> Result<u64, StorageError> {
+    let sample_request = SampleRequestInternal {
+        filter: None,
+        limit: POINT_SIZE_SAMPLE,
+        with_payload: WithPayloadInterface::Bool(true),
+        with_vector: WithVector::Bool(true),
+    };
+    let sample = collection
+        .sample_points(sample_request, &ShardSelectorInternal::All)
+        .await?;
+
+    let vectors_config = &config.params.vectors;
+    if sample.is_empty() {
+        return Ok(configured_vectors_size(vectors_config).max(1));
+    }
+
+    let total_size: u64 = sample
+        .iter()
+        .map(|sampled| record_size(&sampled.record, vectors_config) as u64)
+        .sum();
+    Ok((total_size / sample.len() as u64).max(1))
+}
+
+/// Size of all configured dense vectors of a single point
+fn configured_vectors_size(vectors_config: &VectorsConfig) -> u64 {
+    vectors_config
+        .params_iter()
+        .map(|(_, params)| params.size.get() * datatype_size(params.datatype) as u64)
+        .sum()
+}
+
+/// Approximate storage size of a single point, including its id, vectors and payload
+fn record_size(record: &Record, vectors_config: &VectorsConfig) -> usize {
+    let vectors_size = match &record.vector {
+        None => 0,
+        Some(VectorStruct::Single(vector)) => {
+            let datatype = vectors_config
+                .get_params(DEFAULT_VECTOR_NAME)
+                .and_then(|params| params.datatype);
+            vector.len() * datatype_size(datatype)
+        }
+        Some(VectorStruct::Multi(vectors)) => vectors
+            .iter()
+            .map(|(name, vector)| match vector {
+                Vector::Dense(vector) => {
+                    let datatype = vectors_config
+                        .get_params(name)
+                        .and_then(|params| params.datatype);
+                    vector.len() * datatype_size(datatype)
+                }
+                // Multi-vectors are always stored as `float32`
+                Vector::MultiDense(vector) => {
+                    vector.flattened_vectors.len() * size_of::<VectorElementType>()
+                }
+                Vector::Sparse(vector) => {
+                    size_of_val(vector.indices.as_slice()) + size_of_val(vector.values.as_slice())
+                }
+            })
+            .sum(),
+    };
+
+    let payload_size = record.payload.as_ref().map_or(0, |payload| {
+        serde_json::to_vec(payload).map_or(0, |bytes| bytes.len())
+    });
+
+    POINT_OVERHEAD + vectors_size + payload_size
+}
+
+/// Size of a single vector element in bytes
+fn datatype_size(datatype: Option<VectorStorageDatatype>) -> usize {
+    match datatype.unwrap_or_default() {
+        VectorStorageDatatype::Float32 => size_of::<f32>(),
+        VectorStorageDatatype::Float16 => size_of::<u16>(),
+        VectorStorageDatatype::Uint8 => size_of::<u8>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::num::NonZeroU64;
+
+    use collection::operations::types::VectorParams;
+    use segment::data_types::vectors::MultiDenseVector;
+    use segment::types::{Distance, Payload};
+    use serde_json::json;
+
+    use super::*;
+
+    fn vector_params(size: u64, datatype: Option<VectorStorageDatatype>) -> VectorParams {
+        VectorParams {
+            size: NonZeroU64::new(size).unwrap(),
+            distance: Distance::Dot,
+            hnsw_config: None,
+            quantization_config: None,
+            on_disk: None,
+            multivector_config: None,
+            datatype,
+        }
+    }
+
+    fn record(vector: Option<VectorStruct>, payload: Option<Payload>) -> Record {
+        Record {
+            id: 1.into(),
+            payload,
+            vector,
+            shard_key: None,
+            order_values: None,
+        }
+    }
+
+    #[test]
+    fn test_record_size() {
+        let single = VectorsConfig::Single(vector_params(4, None));
+
+        assert_eq!(record_size(&record(None, None), &single), POINT_OVERHEAD);
+
+        let dense = record(Some(VectorStruct::Single(vec![0.0; 4])), None);
+        assert_eq!(record_size(&dense, &single), POINT_OVERHEAD + 4 * 4);
+
+        let payload = json!({"a": 1}).as_object().unwrap().clone();
+        let with_payload = record(
+            Some(VectorStruct::Single(vec![0.0; 4])),
+            Some(Payload::from(payload)),
+        );
+        assert_eq!(
+            record_size(&with_payload, &single),
+            POINT_OVERHEAD + 4 * 4 + r#"{"a":1}"#.len(),
+        );
+
+        // Element size depends on the configured datatype of each named vector
+        let multi = VectorsConfig::Multi(BTreeMap::from([
+            (
+                "byte".to_string(),
+                vector_params(8, Some(VectorStorageDatatype::Uint8)),
+            ),
+            (
+                "half".to_string(),
+                vector_params(8, Some(VectorStorageDatatype::Float16)),
+            ),
+            ("multi".to_string(), vector_params(2, None)),
+        ]));
+        let named = record(
+            Some(VectorStruct::Multi(HashMap::from([
+                ("byte".to_string(), Vector::Dense(vec![0.0; 8])),
+                ("half".to_string(), Vector::Dense(vec![0.0; 8])),
+                (
+                    "multi".to_string(),
+                    Vector::MultiDense(MultiDenseVector {
+                        flattened_vectors: vec![0.0; 6],
+                        dim: 2,
+                    }),
+                ),
+            ]))),
+            None,
+        );
+        assert_eq!(
+            record_size(&named, &multi),
+            POINT_OVERHEAD + 8 + 8 * 2 + 6 * 4,
+        );
+    }
+
+    #[test]
+    fn test_configured_vectors_size() {
+        assert_eq!(
+            configured_vectors_size(&VectorsConfig::Single(vector_params(128, None))),
+            128 * 4,
+        );
+
+        let multi = VectorsConfig::Multi(BTreeMap::from([
+            (
+                "byte".to_string(),
+                vector_params(100, Some(VectorStorageDatatype::Uint8)),
+            ),
+            (
+                "half".to_string(),
+                vector_params(100, Some(VectorStorageDatatype::Float16)),
+            ),
+            (
+                "float".to_string(),
+                vector_params(100, Some(VectorStorageDatatype::Float32)),
+            ),
+        ]));
+        assert_eq!(configured_vectors_size(&multi), 100 + 200 + 400);
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+
+use collection::shards::shard::{PeerId, ShardId};
+
+use super::{PeerLoad, RebalanceAction};
+
+/// Upper bound of actions in a single plan, to keep planning time bounded on large clusters
+const MAX_PLAN_ACTIONS: usize = 1000;
+
+/// Placement of a single shard, as seen by the planner
+#[derive(Debug, Clone)]
+pub struct ShardPlacement {
+    pub collection_name: String,
+    pub shard_id: ShardId,
+    /// Peers holding a replica of the shard
+    pub replicas: Vec<PeerId>,
+    /// Load of a single replica of the shard
+    pub weight: u64,
+    /// Desired number of replicas, if the replication factor is managed by the collection config
+    pub replication_factor: Option<usize>,
+    /// Whether the planner may change replicas of the shard
+    ///
+    /// Shards which are not fully active, involved in a transfer or being resharded only
+    /// contribute to the load of their peers.
+    pub movable: bool,
+}
+
+/// Plan a minimal sequence of actions, which evens out the load of the given peers
+///
+/// First, surplus replicas are dropped from the most loaded peers and missing replicas are
+/// created on the least loaded peers. Then replicas are moved from the most to the least loaded
+/// peers, until the load difference of any two peers is within `tolerance`. The tolerance is
+/// relative to the average peer load.
+pub fn plan_rebalance(
+    peers: &[PeerId],
+    mut shards: Vec<ShardPlacement>,
+    tolerance: f64,
+) -> (Vec<PeerLoad>, Vec<RebalanceAction>) {
+    let mut loads: BTreeMap<PeerId, u64> = peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+    for shard in &shards {
+        for peer_id in &shard.replicas {
+            *loads.entry(*peer_id).or_default() += shard.weight;
+        }
+    }
+    let current_loads = loads.clone();
+
+    let mut actions = Vec::new();
+
+    fix_replication_factor(&mut shards, &mut loads, &mut actions);
+
+    let total_load: u64 = loads.values().sum();
+    let allowed_gap = (total_load as f64 / loads.len().max(1) as f64 * tolerance).max(0.0);
+
+    while actions.len() < MAX_PLAN_ACTIONS {
+        let Some((shard_idx, from, to)) = best_move(&shards, &loads, allowed_gap) else {
+            break;
+        };
+
+        let shard = &mut shards[shard_idx];
+        for replica in shard
+            .replicas
+            .iter_mut()
+            .filter(|peer_id| **peer_id == from)
+        {
+            *replica = to;
+        }
+        *loads.get_mut(&from).unwrap() -= shard.weight;
+        *loads.entry(to).or_default() += shard.weight;
+
+        push_move(
+            &mut actions,
+            &shard.collection_name,
+            shard.shard_id,
+            from,
+            to,
+        );
+    }
+
+    let peer_loads = current_loads
+        .into_iter()
+        .map(|(peer_id, load)| PeerLoad {
+            peer_id,
+            load,
+            planned_load: loads.get(&peer_id).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    (peer_loads, actions)
+}
+
+/// Drop surplus replicas and create missing ones, to match the replication factor of the shards
+fn fix_replication_factor(
+    shards: &mut [ShardPlacement],
+    loads: &mut BTreeMap<PeerId, u64>,
+    actions: &mut Vec<RebalanceAction>,
+) {
+    let peer_count = loads.len();
+
+    for shard in shards.iter_mut().filter(|shard| shard.movable) {
+        let Some(replication_factor) = shard.replication_factor else {
+            continue;
+        };
+        let target = replication_factor.clamp(1, peer_count.max(1));
+
+        while shard.replicas.len() > target {
+            let Some(peer_id) = shard
+                .replicas
+                .iter()
+                .copied()
+                .max_by_key(|peer_id| (loads[peer_id], *peer_id))
+            else {
+                break;
+            };
+
+            shard.replicas.retain(|replica| *replica != peer_id);
+            *loads.get_mut(&peer_id).unwrap() -= shard.weight;
+            actions.push(RebalanceAction::DropReplica {
+                collection_name: shard.collection_name.clone(),
+                shard_id: shard.shard_id,
+                peer_id,
+            });
+        }
+
+        while !shard.replicas.is_empty() && shard.replicas.len() < target {
+            let from = shard
+                .replicas
+                .iter()
+                .copied()
+                .min_by_key(|peer_id| (loads[peer_id], *peer_id))
+                .unwrap();
+            let Some(to) = loads
+                .iter()
+                .filter(|(peer_id, _)| !shard.replicas.contains(peer_id))
+                .min_by_key(|(peer_id, load)| (**load, **peer_id))
+                .map(|(peer_id, _)| *peer_id)
+            else {
+                break;
+            };
+
+            shard.replicas.push(to);
+            *loads.get_mut(&to).unwrap() += shard.weight;
+            actions.push(RebalanceAction::ReplicateShard {
+                collection_name: shard.collection_name.clone(),
+                shard_id: shard.shard_id,
+                from_peer_id: from,
+                to_peer_id: to,
+            });
+        }
+    }
+}
+
+/// Find the move reducing the load difference of two peers the most
+///
+/// Peers are tried from the largest load difference down. A replica only moves if its weight is
+/// smaller than the load difference, so that every move strictly evens out the loads.
+fn best_move(
+    shards: &[ShardPlacement],
+    loads: &BTreeMap<PeerId, u64>,
+    allowed_gap: f64,
+) -> Option<(usize, PeerId, PeerId)> {
+    let mut by_load: Vec<_> = loads
+        .iter()
+        .map(|(peer_id, load)| (*load, *peer_id))
+        .collect();
+    by_load.sort_unstable();
+
+    for &(from_load, from) in by_load.iter().rev() {
+        for &(to_load, to) in &by_load {
+            if from_load <= to_load || ((from_load - to_load) as f64) <= allowed_gap {
+                break;
+            }
+            let gap = from_load - to_load;
+
+            let candidate = shards
+                .iter()
+                .enumerate()
+                .filter(|(_, shard)| {
+                    shard.movable
+                        && shard.weight > 0
+                        && shard.weight < gap
+                        && shard.replicas.contains(&from)
+                        && !shard.replicas.contains(&to)
+                })
+                // Best move leaves both peers with an equal load
+                .min_by_key(|(_, shard)| (gap.abs_diff(2 * shard.weight), shard.shard_id));
+
+            if let Some((shard_idx, _)) = candidate {
+                return Some((shard_idx, from, to));
+            }
+        }
+    }
+
+    None
+}
+
+/// Add a move to the plan, merging it with earlier actions of the same replica
+///
+/// Moving a replica twice, or moving a newly created replica, is replaced by a single action.
+fn push_move(
+    actions: &mut Vec<RebalanceAction>,
+    collection: &str,
+    shard: ShardId,
+    from: PeerId,
+    to: PeerId,
+) {
+    let earlier = actions.iter().position(|action| match action {
+        RebalanceAction::MoveShard {
+            collection_name,
+            shard_id,
+            to_peer_id,
+            ..
+        }
+        | RebalanceAction::ReplicateShard {
+            collection_name,
+            shard_id,
+            to_peer_id,
+            ..
+        } => collection_name == collection && *shard_id == shard && *to_peer_id == from,
+        RebalanceAction::DropReplica { .. } => false,
+    });
+
+    let Some(idx) = earlier else {
+        actions.push(RebalanceAction::MoveShard {
+            collection_name: collection.to_string(),
+            shard_id: shard,
+            from_peer_id: from,
+            to_peer_id: to,
+        });
+        return;
+    };
+
+    match &mut actions[idx] {
+        RebalanceAction::MoveShard {
+            from_peer_id,
+            to_peer_id,
+            ..
+        } => {
+            if *from_peer_id == to {
+                actions.remove(idx);
+            } else {
+                *to_peer_id = to;
+            }
+        }
+        RebalanceAction::ReplicateShard { to_peer_id, .. } => *to_peer_id = to,
+        RebalanceAction::DropReplica { .. } => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(shard_id: ShardId, replicas: &[PeerId], weight: u64) -> ShardPlacement {
+        ShardPlacement {
+            collection_name: "test".to_string(),
+            shard_id,
+            replicas: replicas.to_vec(),
+            weight,
+            replication_factor: Some(replicas.len()),
+            movable: true,
+        }
+    }
+
+    fn planned_loads(peer_loads: &[PeerLoad]) -> Vec<u64> {
+        peer_loads.iter().map(|load| load.planned_load).collect()
+    }
+
+    #[test]
+    fn test_moves_shards_to_new_peer() {
+        let shards = (0..6).map(|shard_id| shard(shard_id, &[1 + shard_id as PeerId % 2], 1));
+
+        let (peer_loads, actions) = plan_rebalance(&[1, 2, 3], shards.collect(), 0.0);
+
+        assert_eq!(planned_loads(&peer_loads), vec![2, 2, 2]);
+        assert_eq!(actions.len(), 2);
+        assert!(actions
+            .iter()
+            .all(|action| matches!(action, RebalanceAction::MoveShard { to_peer_id: 3, .. },)));
+    }
+
+    #[test]
+    fn test_balanced_cluster_is_untouched() {
+        let shards = vec![
+            shard(0, &[1, 2], 10),
+            shard(1, &[2, 3], 10),
+            shard(2, &[3, 1], 10),
+        ];
+
+        let (peer_loads, actions) = plan_rebalance(&[1, 2, 3], shards, 0.0);
+
+        assert_eq!(planned_loads(&peer_loads), vec![20, 20, 20]);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_balances_by_weight() {
+        let shards = vec![
+            shard(0, &[1], 100),
+            shard(1, &[1], 50),
+            shard(2, &[1], 50),
+            shard(3, &[2], 10),
+        ];
+
+        let (peer_loads, actions) = plan_rebalance(&[1, 2], shards, 0.0);
+
+        assert_eq!(planned_loads(&peer_loads), vec![100, 110]);
+        assert_eq!(
+            actions,
+            vec![RebalanceAction::MoveShard {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                from_peer_id: 1,
+                to_peer_id: 2,
+            }],
+        );
+    }
+
+    #[test]
+    fn test_tolerance_skips_small_imbalance() {
+        let shards = vec![shard(0, &[1], 10), shard(1, &[1], 1), shard(2, &[2], 10)];
+
+        let (_, actions) = plan_rebalance(&[1, 2], shards.clone(), 0.1);
+        assert!(actions.is_empty());
+
+        let (_, actions) = plan_rebalance(&[1, 2], shards, 0.0);
+        assert!(
+            actions.is_empty(),
+            "moving the only candidate would not help"
+        );
+    }
+
+    #[test]
+    fn test_fixes_replication_factor() {
+        let mut under_replicated = shard(0, &[1], 1);
+        under_replicated.replication_factor = Some(2);
+        let mut over_replicated = shard(1, &[1, 2, 3], 1);
+        over_replicated.replication_factor = Some(2);
+
+        let (peer_loads, actions) =
+            plan_rebalance(&[1, 2, 3], vec![under_replicated, over_replicated], 0.0);
+
+        assert_eq!(planned_loads(&peer_loads).iter().sum::<u64>(), 4);
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, RebalanceAction::DropReplica { shard_id: 1, .. })));
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, RebalanceAction::ReplicateShard { shard_id: 0, .. },)));
+    }
+
+    #[test]
+    fn test_immovable_shards_only_add_load() {
+        let mut pinned = shard(0, &[1], 10);
+        pinned.movable = false;
+        let shards = vec![pinned, shard(1, &[1], 1), shard(2, &[1], 1)];
+
+        let (peer_loads, actions) = plan_rebalance(&[1, 2], shards, 0.0);
+
+        assert_eq!(planned_loads(&peer_loads), vec![10, 2]);
+        assert_eq!(actions.len(), 2);
+    }
+
+    #[test]
+    fn test_repeated_moves_are_merged() {
+        let mut actions = Vec::new();
+        push_move(&mut actions, "test", 0, 1, 2);
+        push_move(&mut actions, "test", 0, 2, 3);
+        assert_eq!(
+            actions,
+            vec![RebalanceAction::MoveShard {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                from_peer_id: 1,
+                to_peer_id: 3,
+            }],
+        );
+
+        push_move(&mut actions, "test", 0, 3, 1);
+        assert!(actions.is_empty());
+    }
+}
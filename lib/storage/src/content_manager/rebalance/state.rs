@@ -0,0 +1,242 @@
+use collection::shards::transfer::ShardTransferMethod;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{PeerLoad, RebalanceAction, RebalancePlan, RebalanceStrategy};
+use crate::content_manager::errors::StorageError;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceState {
+    /// Actions of the plan are being applied
+    Running,
+    /// All actions of the plan are applied
+    Finished,
+    /// Some actions of the plan failed
+    Failed,
+    /// Rebalancing was aborted on request
+    Aborted,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceActionState {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+pub struct RebalanceActionStatus {
+    pub action: RebalanceAction,
+    pub status: RebalanceActionState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Rebalancing of the cluster, as stored in consensus
+///
+/// The plan is applied by the consensus leader. If the leader changes, the new leader resumes the
+/// rebalancing from the stored state of its actions.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+pub struct RebalanceStatus {
+    /// Unique id of the rebalancing
+    pub id: u64,
+    pub status: RebalanceState,
+    pub strategy: RebalanceStrategy,
+    /// Maximum number of shard transfers running at once
+    pub max_concurrent_transfers: usize,
+    /// Method for transferring shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<ShardTransferMethod>,
+    pub peers: Vec<PeerLoad>,
+    pub actions: Vec<RebalanceActionStatus>,
+}
+
+impl RebalanceStatus {
+    pub fn new(
+        id: u64,
+        plan: RebalancePlan,
+        max_concurrent_transfers: usize,
+        method: Option<ShardTransferMethod>,
+    ) -> Self {
+        let RebalancePlan {
+            strategy,
+            peers,
+            actions,
+        } = plan;
+
+        Self {
+            id,
+            status: RebalanceState::Running,
+            strategy,
+            max_concurrent_transfers,
+            method,
+            peers,
+            actions: actions
+                .into_iter()
+                .map(|action| RebalanceActionStatus {
+                    action,
+                    status: RebalanceActionState::Pending,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == RebalanceState::Running
+    }
+}
+
+/// Change of the rebalancing state, applied through consensus
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub enum RebalanceOperation {
+    /// Start a new rebalancing, replacing the state of the previous one
+    Start(RebalanceStatus),
+    /// Set the state of a single action of the rebalancing
+    UpdateAction {
+        id: u64,
+        action: usize,
+        status: RebalanceActionState,
+        error: Option<String>,
+    },
+    /// End the running rebalancing
+    Finish { id: u64, status: RebalanceState },
+}
+
+impl RebalanceOperation {
+    /// Apply the operation to the current rebalancing state
+    ///
+    /// Operations of a rebalancing other than the current one are rejected, so a previous leader
+    /// can not change the state of a newer rebalancing.
+    pub fn apply(self, current: &mut Option<RebalanceStatus>) -> Result<(), StorageError> {
+        match self {
+            RebalanceOperation::Start(status) => {
+                if current.as_ref().map_or(false, RebalanceStatus::is_running) {
+                    return Err(StorageError::bad_request("Rebalancing is already running"));
+                }
+                *current = Some(status);
+            }
+            RebalanceOperation::UpdateAction {
+                id,
+                action,
+                status,
+                error,
+            } => {
+                let current = current_rebalance(current, id)?;
+                let Some(action) = current.actions.get_mut(action) else {
+                    return Err(StorageError::bad_request(format!(
+                        "Rebalancing {id} has no action {action}",
+                    )));
+                };
+                action.status = status;
+                action.error = error;
+            }
+            RebalanceOperation::Finish { id, status } => {
+                let current = current_rebalance(current, id)?;
+                if !current.is_running() {
+                    return Err(StorageError::bad_request(format!(
+                        "Rebalancing {id} is not running",
+                    )));
+                }
+                current.status = status;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn current_rebalance(
+    current: &mut Option<RebalanceStatus>,
+    id: u64,
+) -> Result<&mut RebalanceStatus, StorageError> {
+    match current {
+        Some(current) if current.id == id => Ok(current),
+        _ => Err(StorageError::bad_request(format!(
+            "Rebalancing {id} is not the current one",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: u64) -> RebalanceStatus {
+        let plan = RebalancePlan {
+            strategy: RebalanceStrategy::ShardCount,
+            peers: vec![],
+            actions: vec![RebalanceAction::DropReplica {
+                collection_name: "test".to_string(),
+                shard_id: 0,
+                peer_id: 1,
+            }],
+        };
+        RebalanceStatus::new(id, plan, 1, None)
+    }
+
+    #[test]
+    fn test_rebalance_operations() {
+        let mut current = None;
+
+        RebalanceOperation::Start(status(1))
+            .apply(&mut current)
+            .unwrap();
+        // Only one rebalancing can run at once
+        assert!(RebalanceOperation::Start(status(2))
+            .apply(&mut current)
+            .is_err());
+
+        RebalanceOperation::UpdateAction {
+            id: 1,
+            action: 0,
+            status: RebalanceActionState::Done,
+            error: None,
+        }
+        .apply(&mut current)
+        .unwrap();
+        assert!(RebalanceOperation::UpdateAction {
+            id: 1,
+            action: 1,
+            status: RebalanceActionState::Done,
+            error: None,
+        }
+        .apply(&mut current)
+        .is_err());
+
+        RebalanceOperation::Finish {
+            id: 1,
+            status: RebalanceState::Aborted,
+        }
+        .apply(&mut current)
+        .unwrap();
+        // Already finished
+        assert!(RebalanceOperation::Finish {
+            id: 1,
+            status: RebalanceState::Finished,
+        }
+        .apply(&mut current)
+        .is_err());
+
+        RebalanceOperation::Start(status(2))
+            .apply(&mut current)
+            .unwrap();
+
+        // Operations of the previous rebalancing are rejected
+        assert!(RebalanceOperation::UpdateAction {
+            id: 1,
+            action: 0,
+            status: RebalanceActionState::Failed,
+            error: None,
+        }
+        .apply(&mut current)
+        .is_err());
+
+        let current = current.unwrap();
+        assert_eq!(current.id, 2);
+        assert!(current.is_running());
+        assert_eq!(current.actions[0].status, RebalanceActionState::Pending);
+    }
+}
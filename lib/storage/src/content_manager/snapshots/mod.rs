@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use collection::operations::snapshot_ops::SnapshotDescription;
+use rbac::access::{Access, AccessLevel};
 use serde::{Deserialize, Serialize};
 use tar::Builder as TarBuilder;
 use tempfile::TempPath;
@@ -38,9 +39,11 @@ pub async fn get_full_snapshot_path(
 
 pub async fn do_delete_full_snapshot(
     dispatcher: &Dispatcher,
+    access: &Access,
     snapshot_name: &str,
     wait: bool,
 ) -> Result<bool, StorageError> {
+    access.check_global_access(AccessLevel::Manage)?;
     let dispatcher = dispatcher.clone();
     let snapshot_manager = dispatcher.clone().toc().get_snapshots_storage_manager();
     let snapshot_dir = get_full_snapshot_path(dispatcher.toc(), snapshot_name).await?;
@@ -56,10 +59,12 @@ pub async fn do_delete_full_snapshot(
 
 pub async fn do_delete_collection_snapshot(
     dispatcher: &Dispatcher,
+    access: &Access,
     collection_name: &str,
     snapshot_name: &str,
     wait: bool,
 ) -> Result<bool, StorageError> {
+    access.check_whole_collection_access(collection_name, AccessLevel::Manage)?;
     let collection_name = collection_name.to_string();
     let snapshot_name = snapshot_name.to_string();
     let collection = dispatcher.get_collection(&collection_name).await?;
@@ -78,7 +83,9 @@ pub async fn do_delete_collection_snapshot(
 
 pub async fn do_list_full_snapshots(
     toc: &TableOfContent,
+    access: &Access,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
+    access.check_global_access(AccessLevel::Manage)?;
     let snapshots_manager = toc.get_snapshots_storage_manager();
     let snapshots_path = Path::new(toc.snapshots_path());
     Ok(snapshots_manager.list_snapshots(snapshots_path).await?)
@@ -86,8 +93,10 @@ pub async fn do_list_full_snapshots(
 
 pub async fn do_create_full_snapshot(
     dispatcher: &Dispatcher,
+    access: &Access,
     wait: bool,
 ) -> Result<Option<SnapshotDescription>, StorageError> {
+    access.check_global_access(AccessLevel::Manage)?;
     let dispatcher = dispatcher.clone();
     let task = tokio::spawn(async move { _do_create_full_snapshot(&dispatcher).await });
     if wait {
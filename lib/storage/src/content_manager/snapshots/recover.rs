@@ -6,6 +6,7 @@ use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::shard_config::ShardType;
 use collection::shards::shard_versioning::latest_shard_paths;
+use rbac::access::{Access, AccessLevel};
 
 use crate::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollectionOperation,
@@ -48,11 +49,18 @@ pub async fn activate_shard(
 
 pub async fn do_recover_from_snapshot(
     dispatcher: &Dispatcher,
+    access: &Access,
     collection_name: &str,
     source: SnapshotRecover,
     wait: bool,
     client: reqwest::Client,
 ) -> Result<bool, StorageError> {
+    access.check_whole_collection_access(collection_name, AccessLevel::Manage)?;
+    // Local files are not bound to the collection
+    if source.location.scheme() == "file" {
+        access.check_global_access(AccessLevel::Manage)?;
+    }
+
     let dispatch = dispatcher.clone();
     let collection_name = collection_name.to_string();
     let recovery = tokio::spawn(async move {
@@ -144,7 +152,7 @@ async fn _do_recover_from_snapshot(
                     snapshot_config.clone().into(),
                 ));
             dispatcher
-                .submit_collection_meta_op(operation, None)
+                .submit_collection_meta_op(operation, &Access::full(), None)
                 .await?;
             toc.get_collection(collection_name).await?
         }
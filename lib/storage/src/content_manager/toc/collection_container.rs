@@ -103,6 +103,14 @@ impl CollectionContainer for TableOfContent {
                         convert_from_listener_to_active_callback,
                     )
                     .await?;
+
+                // Continue driving resharding, e.g. after restart
+                let shard_consensus = self.shard_transfer_dispatcher.lock().clone();
+                if let Some(consensus) = shard_consensus {
+                    collection
+                        .resume_resharding_driver(Box::new(consensus))
+                        .await;
+                }
             }
             Ok(())
         })
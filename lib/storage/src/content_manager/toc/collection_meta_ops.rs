@@ -73,6 +73,13 @@ impl TableOfContent {
                     .await
                     .map(|()| true)
             }
+            CollectionMetaOperations::Resharding(collection, operation) => {
+                log::debug!("Resharding {:?} of {}", operation, collection);
+
+                self.handle_resharding(collection, operation)
+                    .await
+                    .map(|()| true)
+            }
             CollectionMetaOperations::SetShardReplicaState(operation) => {
                 log::debug!("Set shard replica state {:?}", operation);
                 self.set_shard_replica_state(operation).await.map(|()| true)
@@ -113,6 +120,7 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
         } = operation.update_collection;
         let collection = self.get_collection(&operation.collection_name).await?;
         let mut recreate_optimizers = false;
@@ -143,6 +151,9 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diff) = strict_mode_config {
+            collection.update_strict_mode_config(diff).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -265,6 +276,7 @@ impl TableOfContent {
                     transfers,
                     shards_key_mapping: _,
                     payload_index_schema: _,
+                    resharding: _,
                 } = collection.state().await;
                 let all_peers: HashSet<_> = self
                     .channel_service
@@ -375,6 +387,7 @@ impl TableOfContent {
 
                 let new_transfer = ShardTransfer {
                     shard_id: transfer_restart.shard_id,
+                    to_shard_id: old_transfer.to_shard_id,
                     from: transfer_restart.from,
                     to: transfer_restart.to,
                     sync: old_transfer.sync, // Preserve sync flag from the old transfer
@@ -453,12 +466,61 @@ impl TableOfContent {
                     &collection.state().await.transfers,
                 )?;
                 log::warn!("Aborting shard transfer: {reason}");
+                let is_resharding = transfer.to_shard_id.is_some();
                 collection.abort_shard_transfer(transfer).await?;
+
+                // Points can't be migrated, the resharding operation can't go on
+                if is_resharding {
+                    if let Some(state) = collection.resharding_state().await {
+                        collection
+                            .abort_resharding(&state.key(), &format!("migration failed: {reason}"))
+                            .await?;
+                    }
+                }
             }
         };
         Ok(())
     }
 
+    async fn handle_resharding(
+        &self,
+        collection_id: CollectionId,
+        resharding_operation: ReshardingOperation,
+    ) -> Result<(), StorageError> {
+        let collection = self.get_collection(&collection_id).await?;
+
+        match resharding_operation {
+            ReshardingOperation::Start(reshard_key) => {
+                let shard_consensus = match self.shard_transfer_dispatcher.lock().as_ref() {
+                    Some(consensus) => Box::new(consensus.clone()),
+                    None => {
+                        return Err(StorageError::service_error(
+                            "Can't handle resharding, this is a single node deployment",
+                        ))
+                    }
+                };
+
+                collection
+                    .start_resharding(reshard_key, shard_consensus)
+                    .await?;
+            }
+            ReshardingOperation::CommitRead(reshard_key) => {
+                collection.commit_read_hashring(&reshard_key).await?;
+            }
+            ReshardingOperation::CommitWrite(reshard_key) => {
+                collection.commit_write_hashring(&reshard_key).await?;
+            }
+            ReshardingOperation::Finish(reshard_key) => {
+                collection.finish_resharding(&reshard_key).await?;
+            }
+            ReshardingOperation::Abort { key, reason } => {
+                log::warn!("Aborting resharding: {reason}");
+                collection.abort_resharding(&key, &reason).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn set_shard_replica_state(
         &self,
         operation: SetShardReplicaState,
@@ -46,6 +46,8 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            ttl_config,
+            strict_mode_config,
         } = operation;
 
         self.collections
@@ -125,6 +127,7 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            ttl_config,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -157,6 +160,7 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            strict_mode_config,
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -187,6 +191,8 @@ impl TableOfContent {
         )
         .await?;
 
+        collection.ensure_expiration_index().await?;
+
         let local_shards = collection.get_local_shards().await;
 
         {
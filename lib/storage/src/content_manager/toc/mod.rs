@@ -4,6 +4,7 @@ mod collection_meta_ops;
 mod create_collection;
 mod locks;
 mod point_ops;
+mod request_access;
 mod snapshots;
 mod temp_directories;
 pub mod transfer;
@@ -1,19 +1,24 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
+use collection::operations::changes::{ChangesBatch, ChangesOffset, ChangesRequest, PointChange};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::point_ops::WriteOrdering;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
+use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::shard::ShardId;
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
-use segment::types::{ScoredPoint, ShardKey};
+use rbac::access::{Access, AccessLevel};
+use segment::types::{ScoredPoint, ShardKey, WithPayloadInterface, WithVector};
 
-use super::TableOfContent;
+use super::{request_access, TableOfContent};
 use crate::content_manager::errors::StorageError;
 
 impl TableOfContent {
@@ -30,12 +35,16 @@ impl TableOfContent {
     pub async fn recommend(
         &self,
         collection_name: &str,
-        request: RecommendRequestInternal,
+        mut request: RecommendRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+        request_access::check_recommend_access(&mut request, collection_name, access)?;
         recommendations::recommend_by(
             request,
             &collection,
@@ -61,11 +70,19 @@ impl TableOfContent {
     pub async fn recommend_batch(
         &self,
         collection_name: &str,
-        requests: Vec<(RecommendRequestInternal, ShardSelectorInternal)>,
+        mut requests: Vec<(RecommendRequestInternal, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        collection
+            .check_strict_mode_batch(requests.iter().map(|(request, _)| request), timeout)
+            .await?;
+        for (request, _) in &mut requests {
+            request_access::check_recommend_access(request, collection_name, access)?;
+        }
         recommendations::recommend_batch_by(
             requests,
             &collection,
@@ -94,18 +111,85 @@ impl TableOfContent {
     pub async fn core_search_batch(
         &self,
         collection_name: &str,
-        request: CoreSearchRequestBatch,
+        mut request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        if !shard_selection.is_shard_id() {
+            collection
+                .check_strict_mode_batch(request.searches.iter(), timeout)
+                .await?;
+        }
+        request_access::check_core_search_access(&mut request.searches, collection_name, access)?;
         collection
             .core_search_batch(request, read_consistency, shard_selection, timeout)
             .await
             .map_err(|err| err.into())
     }
 
+    /// Query points using the universal query API: multi-stage prefetches, re-scoring and fusion
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we query
+    /// * `request` - [`ShardQueryRequest`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    /// * `read_consistency` - consistency level
+    ///
+    /// # Result
+    ///
+    /// Points with final score
+    pub async fn query(
+        &self,
+        collection_name: &str,
+        mut request: ShardQueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access: &Access,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
+        let collection = self.get_collection(collection_name).await?;
+        if !shard_selection.is_shard_id() {
+            collection.check_strict_mode(&request, timeout).await?;
+        }
+        request_access::check_query_access(&mut request, collection_name, access)?;
+        collection
+            .query(request, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Perform the universal query on selected shards, without merging their results
+    ///
+    /// # Result
+    ///
+    /// Response of each selected shard
+    pub async fn query_shards(
+        &self,
+        collection_name: &str,
+        request: ShardQueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<ShardQueryResponse>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .query_shards(
+                Arc::new(request),
+                read_consistency,
+                &shard_selection,
+                timeout,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -121,17 +205,126 @@ impl TableOfContent {
     pub async fn count(
         &self,
         collection_name: &str,
-        request: CountRequestInternal,
+        mut request: CountRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access: &Access,
     ) -> Result<CountResult, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        if !shard_selection.is_shard_id() {
+            collection.check_strict_mode(&request, None).await?;
+        }
+        request_access::check_count_access(&mut request, collection_name, access)?;
         collection
             .count(request, read_consistency, &shard_selection)
             .await
             .map_err(|err| err.into())
     }
 
+    /// Count points per value of a payload field.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Values with the most points, and number of points in each of the requested ranges.
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        mut request: FacetRequestInternal,
+        shard_selection: ShardSelectorInternal,
+        access: &Access,
+    ) -> Result<FacetResponse, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
+        let collection = self.get_collection(collection_name).await?;
+        if !shard_selection.is_shard_id() {
+            collection.check_strict_mode(&request, None).await?;
+        }
+        request_access::check_facet_access(&mut request, collection_name, access)?;
+        collection
+            .facet(request, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Find the nearest neighbours of sampled points among the same sample
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchMatrixRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    /// * `read_consistency` - consistency level
+    ///
+    /// # Result
+    ///
+    /// Sampled point ids and the nearest neighbours of each of them
+    ///
+    pub async fn search_points_matrix(
+        &self,
+        collection_name: &str,
+        mut request: SearchMatrixRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access: &Access,
+    ) -> Result<CollectionSearchMatrixResponse, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
+        let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+        request_access::check_search_matrix_access(&mut request, collection_name, access)?;
+        collection
+            .search_points_matrix(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Collect statistics of sparse vectors, required to apply modifiers like IDF.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we collect statistics
+    /// * `request` - [`SparseStatisticsRequest`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Statistics of each requested sparse vector, aggregated across selected shards.
+    ///
+    pub async fn sparse_statistics(
+        &self,
+        collection_name: &str,
+        request: SparseStatisticsRequest,
+        shard_selection: ShardSelectorInternal,
+    ) -> Result<SparseStatistics, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .sparse_statistics(request, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Pick random points which satisfy the filter, used to build a distance matrix on remote shards
+    pub async fn sample_points(
+        &self,
+        collection_name: &str,
+        request: SampleRequestInternal,
+        shard_selection: ShardSelectorInternal,
+    ) -> Result<Vec<SampledRecord>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .sample_points(request, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -149,7 +342,9 @@ impl TableOfContent {
         request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access: &Access,
     ) -> Result<Vec<Record>, StorageError> {
+        access.check_whole_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
         collection
             .retrieve(request, read_consistency, &shard_selection)
@@ -157,15 +352,61 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Read the next batch of changes of each of the requested shards of the collection
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - which collection to read changes of
+    /// * `request` - [`ChangesRequest`], positions to read the shards from
+    ///
+    /// # Result
+    ///
+    /// Changes of each shard, reduced to their final effect, with positions to continue from
+    ///
+    pub async fn read_changes(
+        &self,
+        collection_name: &str,
+        request: &ChangesRequest,
+        access: &Access,
+    ) -> Result<Vec<ChangesBatch>, StorageError> {
+        access.check_whole_collection_access(collection_name, AccessLevel::Read)?;
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .read_changes(request)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Read changes of the local replica of a shard, used to read changes through other peers
+    pub async fn read_local_changes(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        offset: Option<&ChangesOffset>,
+        limit: usize,
+        with_payload: &WithPayloadInterface,
+        with_vector: &WithVector,
+    ) -> Result<(Vec<PointChange>, ChangesOffset), StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .read_local_changes(shard_id, offset, limit, with_payload, with_vector)
+            .await
+            .map_err(|err| err.into())
+    }
+
     pub async fn group(
         &self,
         collection_name: &str,
-        request: GroupRequest,
+        mut request: GroupRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<GroupsResult, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+        request_access::check_group_access(&mut request, collection_name, access)?;
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -184,12 +425,16 @@ impl TableOfContent {
     pub async fn discover(
         &self,
         collection_name: &str,
-        request: DiscoverRequestInternal,
+        mut request: DiscoverRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        collection.check_strict_mode(&request, timeout).await?;
+        request_access::check_discover_access(&mut request, collection_name, access)?;
         discovery::discover(
             request,
             &collection,
@@ -205,11 +450,19 @@ impl TableOfContent {
     pub async fn discover_batch(
         &self,
         collection_name: &str,
-        requests: Vec<(DiscoverRequestInternal, ShardSelectorInternal)>,
+        mut requests: Vec<(DiscoverRequestInternal, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
+        access: &Access,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        collection
+            .check_strict_mode_batch(requests.iter().map(|(request, _)| request), timeout)
+            .await?;
+        for (request, _) in &mut requests {
+            request_access::check_discover_access(request, collection_name, access)?;
+        }
 
         discovery::discover_batch(
             requests,
@@ -236,11 +489,17 @@ impl TableOfContent {
     pub async fn scroll(
         &self,
         collection_name: &str,
-        request: ScrollRequestInternal,
+        mut request: ScrollRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        access: &Access,
     ) -> Result<ScrollResult, StorageError> {
+        access.check_collection_access(collection_name, AccessLevel::Read)?;
         let collection = self.get_collection(collection_name).await?;
+        if !shard_selection.is_shard_id() {
+            collection.check_strict_mode(&request, None).await?;
+        }
+        request_access::check_scroll_access(&mut request, collection_name, access)?;
         collection
             .scroll_by(request, read_consistency, &shard_selection)
             .await
@@ -284,15 +543,24 @@ impl TableOfContent {
     pub async fn update(
         &self,
         collection_name: &str,
-        operation: OperationWithClockTag,
+        mut operation: OperationWithClockTag,
         wait: bool,
         ordering: WriteOrdering,
         shard_selector: ShardSelectorInternal,
+        access: &Access,
     ) -> Result<UpdateResult, StorageError> {
         // `TableOfContent::_update_shard_keys` and `Collection::update_from_*` are cancel safe,
         // so this method is cancel safe.
 
+        access.check_collection_access(collection_name, AccessLevel::ReadWrite)?;
+
         let collection = self.get_collection(collection_name).await?;
+        if !shard_selector.is_shard_id() {
+            collection
+                .check_strict_mode(&operation.operation, None)
+                .await?;
+        }
+        request_access::check_update_access(&mut operation.operation, collection_name, access)?;
 
         // Ordered operation flow:
         //
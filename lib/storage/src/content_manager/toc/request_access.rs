@@ -0,0 +1,367 @@
+//! Access checks of point requests.
+//!
+//! If the access to a collection is limited by a payload filter, the filter is added to every
+//! request, so only matching points are read or deleted. Requests, which can't be limited
+//! this way, are denied.
+//!
+//! Requests are checked against strict mode limits before the filter is added, so the
+//! conditions of the access don't count toward the limits of the user.
+
+use std::collections::HashSet;
+
+use collection::grouping::group_by::{GroupRequest, SourceRequest};
+use collection::operations::point_ops::PointOperations;
+use collection::operations::types::{
+    CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal, FacetRequestInternal,
+    RecommendExample, RecommendRequestInternal, ScrollRequestInternal, SearchMatrixRequestInternal,
+};
+use collection::operations::universal_query::shard_query::{ShardPrefetch, ShardQueryRequest};
+use collection::operations::CollectionUpdateOperations;
+use rbac::access::{Access, AccessLevel};
+use segment::types::{Condition, Filter, HasIdCondition};
+
+use crate::content_manager::errors::StorageError;
+
+/// Add the mandatory filter of the access to the filter of the request
+fn restrict_filter(filter: &mut Option<Filter>, restriction: &Filter) {
+    *filter = Some(match filter.take() {
+        Some(filter) => filter.merge(restriction),
+        None => restriction.clone(),
+    });
+}
+
+/// Point examples can't be limited by a filter, as they are read directly by IDs
+fn check_no_point_examples<'a>(
+    mut examples: impl Iterator<Item = &'a RecommendExample>,
+    collection_name: &str,
+) -> Result<(), StorageError> {
+    if examples.any(|example| matches!(example, RecommendExample::PointId(_))) {
+        return Err(StorageError::forbidden(format!(
+            "Access to collection {collection_name} is limited by a payload filter, \
+             examples by point ID are denied"
+        )));
+    }
+    Ok(())
+}
+
+pub fn check_recommend_access(
+    request: &mut RecommendRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let restriction = access.check_collection_access(collection_name, AccessLevel::Read)?;
+
+    match &request.lookup_from {
+        Some(lookup) => {
+            access.check_whole_collection_access(&lookup.collection, AccessLevel::Read)?;
+        }
+        None if restriction.is_some() => check_no_point_examples(
+            request.positive.iter().chain(&request.negative),
+            collection_name,
+        )?,
+        None => {}
+    }
+
+    if let Some(restriction) = restriction {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_discover_access(
+    request: &mut DiscoverRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let restriction = access.check_collection_access(collection_name, AccessLevel::Read)?;
+
+    match &request.lookup_from {
+        Some(lookup) => {
+            access.check_whole_collection_access(&lookup.collection, AccessLevel::Read)?;
+        }
+        None if restriction.is_some() => {
+            let context = request.context.iter().flatten();
+            check_no_point_examples(
+                request
+                    .target
+                    .iter()
+                    .chain(context.flat_map(|pair| [&pair.positive, &pair.negative])),
+                collection_name,
+            )?
+        }
+        None => {}
+    }
+
+    if let Some(restriction) = restriction {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_core_search_access(
+    requests: &mut [CoreSearchRequest],
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        for request in requests {
+            restrict_filter(&mut request.filter, restriction);
+        }
+    }
+    Ok(())
+}
+
+pub fn check_query_access(
+    request: &mut ShardQueryRequest,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    fn restrict_prefetches(prefetches: &mut [ShardPrefetch], restriction: &Filter) {
+        for prefetch in prefetches {
+            restrict_filter(&mut prefetch.filter, restriction);
+            restrict_prefetches(&mut prefetch.prefetches, restriction);
+        }
+    }
+
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        restrict_filter(&mut request.filter, restriction);
+        restrict_prefetches(&mut request.prefetches, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_group_access(
+    request: &mut GroupRequest,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(with_lookup) = &request.with_lookup {
+        access.check_whole_collection_access(&with_lookup.collection_name, AccessLevel::Read)?;
+    }
+
+    match &mut request.source {
+        SourceRequest::Search(search) => {
+            if let Some(restriction) =
+                access.check_collection_access(collection_name, AccessLevel::Read)?
+            {
+                restrict_filter(&mut search.filter, restriction);
+            }
+            Ok(())
+        }
+        SourceRequest::Recommend(recommend) => {
+            check_recommend_access(recommend, collection_name, access)
+        }
+    }
+}
+
+pub fn check_count_access(
+    request: &mut CountRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_facet_access(
+    request: &mut FacetRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_search_matrix_access(
+    request: &mut SearchMatrixRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+pub fn check_scroll_access(
+    request: &mut ScrollRequestInternal,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let Some(restriction) = access.check_collection_access(collection_name, AccessLevel::Read)? {
+        restrict_filter(&mut request.filter, restriction);
+    }
+    Ok(())
+}
+
+/// Check access of the update operation.
+///
+/// If the access is limited by a payload filter, only deletions are allowed, and they are
+/// turned into deletions by filter, so points outside of the filter are kept.
+pub fn check_update_access(
+    operation: &mut CollectionUpdateOperations,
+    collection_name: &str,
+    access: &Access,
+) -> Result<(), StorageError> {
+    if let CollectionUpdateOperations::FieldIndexOperation(_) = operation {
+        access.check_whole_collection_access(collection_name, AccessLevel::Manage)?;
+        return Ok(());
+    }
+
+    let Some(restriction) =
+        access.check_collection_access(collection_name, AccessLevel::ReadWrite)?
+    else {
+        return Ok(());
+    };
+
+    match operation {
+        // Preconditions can't be checked for the deletion by filter
+        CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids,
+            preconditions,
+        }) if preconditions.is_empty() => {
+            let has_id = HasIdCondition::from(ids.drain(..).collect::<HashSet<_>>());
+            let filter = Filter::new_must(Condition::HasId(has_id)).merge(restriction);
+            *operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::DeletePointsByFilter(filter),
+            );
+            Ok(())
+        }
+        CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+            filter,
+        )) => {
+            *filter = filter.merge(restriction);
+            Ok(())
+        }
+        _ => Err(StorageError::forbidden(format!(
+            "Access to collection {collection_name} is limited by a payload filter, \
+             only deletion of points is allowed"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::operations::point_ops::PointInsertOperationsInternal;
+    use rbac::access::CollectionAccess;
+    use segment::types::FieldCondition;
+
+    use super::*;
+
+    fn restricted_access() -> Access {
+        Access::Collections(vec![CollectionAccess {
+            collection: "tenants".to_string(),
+            access: AccessLevel::ReadWrite,
+            payload: Some(Filter::new_must(Condition::Field(
+                FieldCondition::new_match("tenant".parse().unwrap(), "a".to_string().into()),
+            ))),
+        }])
+    }
+
+    #[test]
+    fn test_update_access() {
+        let access = restricted_access();
+
+        let mut operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                ids: vec![1.into(), 2.into()],
+                preconditions: vec![],
+            });
+        check_update_access(&mut operation, "tenants", &access).unwrap();
+
+        let CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+            filter,
+        )) = operation
+        else {
+            panic!("deletion by IDs must be limited by the filter");
+        };
+        let must = filter.must.unwrap();
+        assert_eq!(must.len(), 2);
+        assert!(matches!(must[0], Condition::HasId(_)));
+        assert!(matches!(must[1], Condition::Field(_)));
+
+        let mut operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(vec![])),
+        );
+        assert!(matches!(
+            check_update_access(&mut operation, "tenants", &access),
+            Err(StorageError::Forbidden { .. }),
+        ));
+        assert!(matches!(
+            check_update_access(&mut operation, "other", &access),
+            Err(StorageError::Forbidden { .. }),
+        ));
+    }
+
+    #[test]
+    fn test_count_access() {
+        let access = restricted_access();
+
+        let mut request = CountRequestInternal {
+            filter: None,
+            exact: true,
+        };
+        check_count_access(&mut request, "tenants", &access).unwrap();
+        assert_eq!(
+            request.filter.as_ref(),
+            access
+                .check_collection_access("tenants", AccessLevel::Read)
+                .unwrap()
+        );
+
+        let mut request = CountRequestInternal {
+            filter: None,
+            exact: true,
+        };
+        check_count_access(&mut request, "any", &Access::full()).unwrap();
+        assert_eq!(request.filter, None);
+    }
+
+    #[test]
+    fn test_facet_access() {
+        let access = restricted_access();
+
+        let mut request = FacetRequestInternal {
+            key: "city".parse().unwrap(),
+            limit: None,
+            filter: None,
+            exact: false,
+            ranges: None,
+        };
+        check_facet_access(&mut request, "tenants", &access).unwrap();
+        assert_eq!(
+            request.filter.as_ref(),
+            access
+                .check_collection_access("tenants", AccessLevel::Read)
+                .unwrap()
+        );
+
+        assert!(matches!(
+            check_facet_access(&mut request, "other", &access),
+            Err(StorageError::Forbidden { .. }),
+        ));
+    }
+
+    #[test]
+    fn test_search_matrix_access() {
+        let access = restricted_access();
+
+        let mut request = SearchMatrixRequestInternal::default();
+        check_search_matrix_access(&mut request, "tenants", &access).unwrap();
+        assert_eq!(
+            request.filter.as_ref(),
+            access
+                .check_collection_access("tenants", AccessLevel::Read)
+                .unwrap()
+        );
+
+        assert!(matches!(
+            check_search_matrix_access(&mut request, "other", &access),
+            Err(StorageError::Forbidden { .. }),
+        ));
+    }
+}
@@ -1,12 +1,13 @@
 use std::sync::Weak;
 
 use collection::operations::types::{CollectionError, CollectionResult};
+use collection::shards::resharding::ReshardKey;
 use collection::shards::transfer::{ShardTransfer, ShardTransferConsensus};
 use collection::shards::CollectionId;
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, ShardTransferOperations,
+    CollectionMetaOperations, ReshardingOperation, ShardTransferOperations,
 };
 use crate::content_manager::consensus_manager::ConsensusStateRef;
 use crate::content_manager::consensus_ops::ConsensusOperations;
@@ -29,6 +30,28 @@ impl ShardTransferDispatcher {
             consensus_state,
         }
     }
+
+    /// Submit the given collection meta operation as consensus proposal
+    fn propose(&self, operation: CollectionMetaOperations) -> CollectionResult<()> {
+        let Some(toc) = self.toc.upgrade() else {
+            return Err(CollectionError::service_error(
+                "Table of contents is dropped",
+            ));
+        };
+        let Some(proposal_sender) = toc.consensus_proposal_sender.as_ref() else {
+            return Err(CollectionError::service_error(
+                "Can't submit proposal, this is a single node deployment",
+            ));
+        };
+
+        proposal_sender
+            .send(ConsensusOperations::CollectionMeta(Box::new(operation)))
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Failed to submit consensus proposal: {err}"
+                ))
+            })
+    }
 }
 
 impl ShardTransferConsensus for ShardTransferDispatcher {
@@ -65,4 +88,63 @@ impl ShardTransferConsensus for ShardTransferDispatcher {
 
         Ok(())
     }
+
+    fn start_shard_transfer(
+        &self,
+        transfer_config: ShardTransfer,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        self.propose(CollectionMetaOperations::TransferShard(
+            collection_name,
+            ShardTransferOperations::Start(transfer_config),
+        ))
+    }
+
+    fn commit_read_hashring(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        self.propose(CollectionMetaOperations::Resharding(
+            collection_name,
+            ReshardingOperation::CommitRead(reshard_key),
+        ))
+    }
+
+    fn commit_write_hashring(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        self.propose(CollectionMetaOperations::Resharding(
+            collection_name,
+            ReshardingOperation::CommitWrite(reshard_key),
+        ))
+    }
+
+    fn finish_resharding(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+    ) -> CollectionResult<()> {
+        self.propose(CollectionMetaOperations::Resharding(
+            collection_name,
+            ReshardingOperation::Finish(reshard_key),
+        ))
+    }
+
+    fn abort_resharding(
+        &self,
+        reshard_key: ReshardKey,
+        collection_name: CollectionId,
+        reason: &str,
+    ) -> CollectionResult<()> {
+        self.propose(CollectionMetaOperations::Resharding(
+            collection_name,
+            ReshardingOperation::Abort {
+                key: reshard_key,
+                reason: reason.to_string(),
+            },
+        ))
+    }
 }
@@ -5,8 +5,15 @@ use std::time::{Duration, Instant};
 
 use collection::config::ShardingMethod;
 use common::defaults::CONSENSUS_META_OP_WAIT;
+use rbac::access::{Access, AccessLevel};
 
-use crate::content_manager::collection_meta_ops::AliasOperations;
+use crate::content_manager::collection_meta_ops::{AliasOperations, ReshardingOperation};
+use crate::content_manager::rebalance::state::{
+    RebalanceOperation, RebalanceState, RebalanceStatus,
+};
+use crate::content_manager::rebalance::{
+    self, RebalancePlan, RebalanceRequest, DEFAULT_MAX_CONCURRENT_TRANSFERS,
+};
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 use crate::{
     ClusterStatus, CollectionMetaOperations, ConsensusOperations, ConsensusStateRef, StorageError,
@@ -47,8 +54,11 @@ impl Dispatcher {
     pub async fn submit_collection_meta_op(
         &self,
         operation: CollectionMetaOperations,
+        access: &Access,
         wait_timeout: Option<Duration>,
     ) -> Result<bool, StorageError> {
+        operation.check_access(access)?;
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             let start = Instant::now();
@@ -116,9 +126,10 @@ impl Dispatcher {
                 };
 
             let do_sync_nodes = match &op {
-                // Sync nodes after collection or shard key creation
+                // Sync nodes after collection, shard key or resharded shard creation
                 CollectionMetaOperations::CreateCollection(_)
-                | CollectionMetaOperations::CreateShardKey(_) => true,
+                | CollectionMetaOperations::CreateShardKey(_)
+                | CollectionMetaOperations::Resharding(_, ReshardingOperation::Start(_)) => true,
                 // Sync nodes when creating or renaming collection aliases
                 CollectionMetaOperations::ChangeAliases(changes) => {
                     changes.actions.iter().any(|change| match change {
@@ -130,6 +141,7 @@ impl Dispatcher {
                 CollectionMetaOperations::UpdateCollection(_)
                 | CollectionMetaOperations::DeleteCollection(_)
                 | CollectionMetaOperations::TransferShard(_, _)
+                | CollectionMetaOperations::Resharding(_, _)
                 | CollectionMetaOperations::SetShardReplicaState(_)
                 | CollectionMetaOperations::DropShardKey(_)
                 | CollectionMetaOperations::CreatePayloadIndex(_)
@@ -210,6 +222,103 @@ impl Dispatcher {
     }
 }
 
+impl Dispatcher {
+    /// Plan rebalancing of shards across all peers of the cluster, without applying it
+    pub async fn plan_rebalance(
+        &self,
+        request: &RebalanceRequest,
+        access: &Access,
+    ) -> Result<RebalancePlan, StorageError> {
+        access.check_global_access(AccessLevel::Manage)?;
+
+        let state = self.rebalance_consensus_state()?;
+        let mut peers: Vec<_> = state.peer_address_by_id().into_keys().collect();
+        peers.sort_unstable();
+
+        rebalance::plan(&self.toc, &peers, request).await
+    }
+
+    /// Plan rebalancing of shards across all peers of the cluster, and start applying it
+    ///
+    /// The plan is stored in consensus and applied in the background by the consensus leader.
+    /// Only one rebalancing can run at once.
+    pub async fn start_rebalance(
+        &self,
+        request: RebalanceRequest,
+        access: Access,
+    ) -> Result<RebalanceStatus, StorageError> {
+        let plan = self.plan_rebalance(&request, &access).await?;
+        let state = self.rebalance_consensus_state()?;
+
+        if state
+            .rebalance_status()
+            .as_ref()
+            .map_or(false, RebalanceStatus::is_running)
+        {
+            return Err(StorageError::bad_request("Rebalancing is already running"));
+        }
+
+        // Do not generate too big id, to avoid problems with json serialization
+        let id = rand::random::<u64>() % (1 << 53);
+        let status = RebalanceStatus::new(
+            id,
+            plan,
+            request
+                .max_concurrent_transfers
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_TRANSFERS),
+            request.method,
+        );
+        state
+            .propose_consensus_op_with_await(
+                ConsensusOperations::Rebalance(RebalanceOperation::Start(status.clone())),
+                None,
+            )
+            .await?;
+        Ok(status)
+    }
+
+    /// Status of the last rebalancing started in the cluster
+    pub fn rebalance_status(&self, access: &Access) -> Result<RebalanceStatus, StorageError> {
+        access.check_global_access(AccessLevel::Read)?;
+
+        self.rebalance_consensus_state()?
+            .rebalance_status()
+            .ok_or_else(|| StorageError::NotFound {
+                description: "Rebalancing was never started".to_string(),
+            })
+    }
+
+    /// Abort the running rebalancing
+    ///
+    /// The leader stops applying the plan and aborts its running shard transfers.
+    pub async fn abort_rebalance(&self, access: &Access) -> Result<bool, StorageError> {
+        access.check_global_access(AccessLevel::Manage)?;
+
+        let state = self.rebalance_consensus_state()?;
+        let Some(status) = state.rebalance_status().filter(RebalanceStatus::is_running) else {
+            return Err(StorageError::NotFound {
+                description: "Rebalancing is not running".to_string(),
+            });
+        };
+
+        state
+            .propose_consensus_op_with_await(
+                ConsensusOperations::Rebalance(RebalanceOperation::Finish {
+                    id: status.id,
+                    status: RebalanceState::Aborted,
+                }),
+                None,
+            )
+            .await
+    }
+
+    fn rebalance_consensus_state(&self) -> Result<&ConsensusStateRef, StorageError> {
+        self.consensus_state
+            .as_ref()
+            .ok_or_else(|| StorageError::bad_request("Distributed mode disabled"))
+    }
+}
+
 impl Deref for Dispatcher {
     type Target = TableOfContent;
 
@@ -94,6 +94,10 @@ pub struct StorageConfig {
     /// Default method used for transferring shards.
     #[serde(default)]
     pub shard_transfer_method: Option<ShardTransferMethod>,
+    /// Recreation of dead replicas on other peers.
+    #[serde(default)]
+    #[validate]
+    pub auto_replication: AutoReplicationConfig,
 }
 
 impl StorageConfig {
@@ -117,6 +121,31 @@ impl StorageConfig {
     }
 }
 
+/// Policy of recreating replicas, which stay dead for too long, on other peers
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct AutoReplicationConfig {
+    /// If true - the consensus leader recreates dead replicas on healthy peers.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long a replica must stay dead, before it is recreated on another peer.
+    #[serde(default = "default_auto_replication_grace_period_sec")]
+    #[validate(range(min = 1))]
+    pub grace_period_sec: u64,
+}
+
+impl Default for AutoReplicationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            grace_period_sec: default_auto_replication_grace_period_sec(),
+        }
+    }
+}
+
+const fn default_auto_replication_grace_period_sec() -> u64 {
+    300
+}
+
 fn default_snapshots_path() -> String {
     DEFAULT_SNAPSHOTS_PATH.to_string()
 }
@@ -6,6 +6,7 @@ use collection::optimizers_builder::OptimizersConfig;
 use collection::shards::channel_service::ChannelService;
 use common::cpu::CpuBudget;
 use memory::madvise;
+use rbac::access::Access;
 use segment::types::Distance;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateAlias, CreateCollection,
@@ -64,6 +65,7 @@ fn test_alias_operation() {
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
         shard_transfer_method: None,
+        auto_replication: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -100,6 +102,8 @@ fn test_alias_operation() {
                             hnsw_config: None,
                             quantization_config: None,
                             on_disk: None,
+                            multivector_config: None,
+                            datatype: None,
                         }
                         .into(),
                         sparse_vectors: None,
@@ -113,8 +117,11 @@ fn test_alias_operation() {
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        ttl_config: None,
+                        strict_mode_config: None,
                     },
                 )),
+                &Access::full(),
                 None,
             ),
         )
@@ -129,6 +136,7 @@ fn test_alias_operation() {
                     }
                     .into()],
             }),
+            &Access::full(),
             None,
         ))
         .unwrap();
@@ -153,6 +161,7 @@ fn test_alias_operation() {
                         .into(),
                     ],
             }),
+            &Access::full(),
             None,
         ))
         .unwrap();
@@ -0,0 +1,58 @@
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::web::Bytes;
+use actix_web::{post, web, HttpResponse, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::changes::ChangesRequest;
+use futures::StreamExt as _;
+use storage::content_manager::toc::TableOfContent;
+use tokio::time::Instant;
+
+use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+use crate::common::points::do_stream_changes;
+
+/// Stream changes of the collection as server-sent events, each event holds a batch of changes
+///
+/// An error, happened during streaming, is sent as an `error` event and ends the stream.
+#[post("/collections/{name}/changes")]
+async fn stream_changes(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<ChangesRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let changes = match do_stream_changes(
+        toc.into_inner(),
+        collection.into_inner().name,
+        request.into_inner(),
+        access,
+    )
+    .await
+    {
+        Ok(changes) => changes,
+        Err(err) => return process_response::<()>(Err(err), timing),
+    };
+
+    let events = changes.map(|batch| {
+        let event = match batch {
+            Ok(batch) => format!("data: {}\n\n", serde_json::to_string(&batch)?),
+            Err(err) => format!(
+                "event: error\ndata: {}\n\n",
+                serde_json::json!({ "status": { "error": err.to_string() } }),
+            ),
+        };
+        Ok::<_, serde_json::Error>(Bytes::from(event))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .streaming(events)
+}
+
+pub fn config_changes_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(stream_changes);
+}
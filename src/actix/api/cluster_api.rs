@@ -1,13 +1,16 @@
 use actix_web::rt::time::Instant;
 use actix_web::{delete, get, post, web, Responder};
-use actix_web_validator::Query;
+use actix_web_validator::{Json, Query};
+use rbac::access::AccessLevel;
 use serde::Deserialize;
 use storage::content_manager::consensus_ops::ConsensusOperations;
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::rebalance::RebalanceRequest;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 
 #[derive(Debug, Deserialize, Validate)]
@@ -20,15 +23,27 @@ struct QueryParams {
 }
 
 #[get("/cluster")]
-async fn cluster_status(dispatcher: web::Data<Dispatcher>) -> impl Responder {
+async fn cluster_status(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Read) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let response = dispatcher.cluster_status();
     process_response(Ok(response), timing)
 }
 
 #[post("/cluster/recover")]
-async fn recover_current_peer(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn recover_current_peer(
+    toc: web::Data<TableOfContent>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Manage) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     process_response(toc.request_snapshot().map(|_| true), timing)
 }
 
@@ -37,8 +52,12 @@ async fn remove_peer(
     dispatcher: web::Data<Dispatcher>,
     peer_id: web::Path<u64>,
     Query(params): Query<QueryParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Manage) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let dispatcher = dispatcher.into_inner();
     let peer_id = peer_id.into_inner();
 
@@ -68,9 +87,55 @@ async fn remove_peer(
     process_response(response, timing)
 }
 
+#[post("/cluster/rebalance/plan")]
+async fn plan_rebalance(
+    dispatcher: web::Data<Dispatcher>,
+    request: Json<RebalanceRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher.plan_rebalance(&request, &access).await;
+    process_response(response, timing)
+}
+
+#[post("/cluster/rebalance")]
+async fn start_rebalance(
+    dispatcher: web::Data<Dispatcher>,
+    request: Json<RebalanceRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .start_rebalance(request.into_inner(), access)
+        .await;
+    process_response(response, timing)
+}
+
+#[get("/cluster/rebalance")]
+async fn rebalance_status(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    process_response(dispatcher.rebalance_status(&access), timing)
+}
+
+#[delete("/cluster/rebalance")]
+async fn abort_rebalance(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    process_response(dispatcher.abort_rebalance(&access).await, timing)
+}
+
 // Configure services
 pub fn config_cluster_api(cfg: &mut web::ServiceConfig) {
     cfg.service(cluster_status)
         .service(remove_peer)
-        .service(recover_current_peer);
+        .service(recover_current_peer)
+        .service(plan_rebalance)
+        .service(start_rebalance)
+        .service(rebalance_status)
+        .service(abort_rebalance);
 }
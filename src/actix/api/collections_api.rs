@@ -15,6 +15,7 @@ use validator::Validate;
 
 use super::CollectionPath;
 use crate::actix::api::StrictCollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::collections::*;
 
@@ -31,16 +32,22 @@ impl WaitTimeout {
 }
 
 #[get("/collections")]
-async fn get_collections(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn get_collections(
+    toc: web::Data<TableOfContent>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
-    let response = Ok(do_list_collections(toc.get_ref()).await);
+    let response = Ok(do_list_collections(toc.get_ref(), access).await);
     process_response(response, timing)
 }
 
 #[get("/aliases")]
-async fn get_aliases(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn get_aliases(
+    toc: web::Data<TableOfContent>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_aliases(toc.get_ref()).await;
+    let response = do_list_aliases(toc.get_ref(), access).await;
     process_response(response, timing)
 }
 
@@ -48,9 +55,10 @@ async fn get_aliases(toc: web::Data<TableOfContent>) -> impl Responder {
 async fn get_collection(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_get_collection(toc.get_ref(), &collection.name, None).await;
+    let response = do_get_collection(toc.get_ref(), access, &collection.name, None).await;
     process_response(response, timing)
 }
 
@@ -58,9 +66,10 @@ async fn get_collection(
 async fn get_collection_existence(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_collection_exists(toc.get_ref(), &collection.name).await;
+    let response = do_collection_exists(toc.get_ref(), access, &collection.name).await;
     process_response(response, timing)
 }
 
@@ -68,9 +77,10 @@ async fn get_collection_existence(
 async fn get_collection_aliases(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_collection_aliases(toc.get_ref(), &collection.name).await;
+    let response = do_list_collection_aliases(toc.get_ref(), access, &collection.name).await;
     process_response(response, timing)
 }
 
@@ -80,6 +90,7 @@ async fn create_collection(
     collection: Path<StrictCollectionPath>,
     operation: Json<CreateCollection>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let response = dispatcher
@@ -88,6 +99,7 @@ async fn create_collection(
                 collection.name.clone(),
                 operation.into_inner(),
             )),
+            &access,
             query.timeout(),
         )
         .await;
@@ -100,6 +112,7 @@ async fn update_collection(
     collection: Path<CollectionPath>,
     operation: Json<UpdateCollection>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let name = collection.name.clone();
@@ -109,6 +122,7 @@ async fn update_collection(
                 name,
                 operation.into_inner(),
             )),
+            &access,
             query.timeout(),
         )
         .await;
@@ -120,6 +134,7 @@ async fn delete_collection(
     dispatcher: web::Data<Dispatcher>,
     collection: Path<CollectionPath>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let response = dispatcher
@@ -127,6 +142,7 @@ async fn delete_collection(
             CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
                 collection.name.clone(),
             )),
+            &access,
             query.timeout(),
         )
         .await;
@@ -138,11 +154,13 @@ async fn update_aliases(
     dispatcher: web::Data<Dispatcher>,
     operation: Json<ChangeAliasesOperation>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let response = dispatcher
         .submit_collection_meta_op(
             CollectionMetaOperations::ChangeAliases(operation.0),
+            &access,
             query.timeout(),
         )
         .await;
@@ -153,9 +171,10 @@ async fn update_aliases(
 async fn get_cluster_info(
     toc: web::Data<TableOfContent>,
     collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let response = do_get_collection_cluster(toc.get_ref(), &collection.name).await;
+    let response = do_get_collection_cluster(toc.get_ref(), access, &collection.name).await;
     process_response(response, timing)
 }
 
@@ -165,11 +184,13 @@ async fn update_collection_cluster(
     collection: Path<CollectionPath>,
     operation: Json<ClusterOperations>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait_timeout = query.timeout();
     let response = do_update_collection_cluster(
         &dispatcher.into_inner(),
+        access,
         collection.name.clone(),
         operation.0,
         wait_timeout,
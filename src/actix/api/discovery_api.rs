@@ -7,6 +7,7 @@ use tokio::time::Instant;
 
 use crate::actix::api::read_params::ReadParams;
 use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::points::do_discover_batch_points;
 
@@ -16,6 +17,7 @@ async fn discover_points(
     collection: Path<CollectionPath>,
     request: Json<DiscoverRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -36,6 +38,7 @@ async fn discover_points(
             params.consistency,
             shard_selection,
             params.timeout(),
+            &access,
         )
         .await;
 
@@ -48,6 +51,7 @@ async fn discover_batch_points(
     collection: Path<CollectionPath>,
     request: Json<DiscoverRequestBatch>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -57,6 +61,7 @@ async fn discover_batch_points(
         request.into_inner(),
         params.consistency,
         params.timeout(),
+        access,
     )
     .await;
 
@@ -0,0 +1,46 @@
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::FacetRequest;
+use storage::content_manager::toc::TableOfContent;
+use tokio::time::Instant;
+
+use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+use crate::common::points::do_facet;
+
+#[post("/collections/{name}/facet")]
+async fn facet(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<FacetRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let FacetRequest {
+        facet_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_facet(
+        toc.get_ref(),
+        &collection.name,
+        facet_request,
+        shard_selection,
+        access,
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(facet);
+}
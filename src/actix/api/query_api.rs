@@ -0,0 +1,50 @@
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::universal_query::collection_query::QueryRequest;
+use storage::content_manager::toc::TableOfContent;
+use tokio::time::Instant;
+
+use crate::actix::api::read_params::ReadParams;
+use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+use crate::common::points::do_query_points;
+
+#[post("/collections/{name}/points/query")]
+async fn query_points(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let QueryRequest {
+        internal,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_query_points(
+        toc.get_ref(),
+        &collection.name,
+        internal,
+        params.consistency,
+        shard_selection,
+        params.timeout(),
+        access,
+    )
+    .await;
+
+    process_response(response, timing)
+}
+
+pub fn config_query_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(query_points);
+}
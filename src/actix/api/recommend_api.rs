@@ -8,12 +8,14 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
 };
+use rbac::access::Access;
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 
 #[post("/collections/{name}/points/recommend")]
@@ -22,6 +24,7 @@ async fn recommend_points(
     collection: Path<CollectionPath>,
     request: Json<RecommendRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -42,6 +45,7 @@ async fn recommend_points(
             params.consistency,
             shard_selection,
             params.timeout(),
+            &access,
         )
         .await;
 
@@ -54,6 +58,7 @@ async fn do_recommend_batch_points(
     request: RecommendRequestBatch,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -68,8 +73,14 @@ async fn do_recommend_batch_points(
         })
         .collect();
 
-    toc.recommend_batch(collection_name, requests, read_consistency, timeout)
-        .await
+    toc.recommend_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        timeout,
+        &access,
+    )
+    .await
 }
 
 #[post("/collections/{name}/points/recommend/batch")]
@@ -78,6 +89,7 @@ async fn recommend_batch_points(
     collection: Path<CollectionPath>,
     request: Json<RecommendRequestBatch>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -87,6 +99,7 @@ async fn recommend_batch_points(
         request.into_inner(),
         params.consistency,
         params.timeout(),
+        access,
     )
     .await;
 
@@ -99,6 +112,7 @@ async fn recommend_point_groups(
     collection: Path<CollectionPath>,
     request: Json<RecommendGroupsRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -119,6 +133,7 @@ async fn recommend_point_groups(
         params.consistency,
         shard_selection,
         params.timeout(),
+        access,
     )
     .await;
 
@@ -4,6 +4,7 @@ use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
+use rbac::access::Access;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
 use storage::content_manager::errors::StorageError;
@@ -12,6 +13,7 @@ use validator::Validate;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::points::do_get_points;
 
@@ -27,6 +29,7 @@ async fn do_get_point(
     collection_name: &str,
     point_id: PointIdType,
     read_consistency: Option<ReadConsistency>,
+    access: Access,
 ) -> Result<Option<Record>, StorageError> {
     let request = PointRequestInternal {
         ids: vec![point_id],
@@ -36,9 +39,15 @@ async fn do_get_point(
 
     let shard_selection = ShardSelectorInternal::All;
 
-    toc.retrieve(collection_name, request, read_consistency, shard_selection)
-        .await
-        .map(|points| points.into_iter().next())
+    toc.retrieve(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        &access,
+    )
+    .await
+    .map(|points| points.into_iter().next())
 }
 
 #[get("/collections/{name}/points/{id}")]
@@ -47,6 +56,7 @@ async fn get_point(
     collection: Path<CollectionPath>,
     point: Path<PointPath>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -68,6 +78,7 @@ async fn get_point(
         &collection.name,
         point_id,
         params.consistency,
+        access,
     )
     .await;
 
@@ -89,6 +100,7 @@ async fn get_points(
     collection: Path<CollectionPath>,
     request: Json<PointRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -108,6 +120,7 @@ async fn get_points(
         point_request,
         params.consistency,
         shard_selection,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -119,6 +132,7 @@ async fn scroll_points(
     collection: Path<CollectionPath>,
     request: Json<ScrollRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -139,6 +153,7 @@ async fn scroll_points(
             params.consistency,
             // TODO: handle params.timeout
             shard_selection,
+            &access,
         )
         .await;
 
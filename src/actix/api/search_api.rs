@@ -3,15 +3,17 @@ use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CoreSearchRequest, SearchGroupsRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest, SearchRequest, SearchRequestBatch,
 };
 use storage::content_manager::toc::TableOfContent;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_search_batch_points, do_search_point_groups, do_search_points_matrix,
 };
 
 #[post("/collections/{name}/points/search")]
@@ -20,6 +22,7 @@ async fn search_points(
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -40,6 +43,7 @@ async fn search_points(
         params.consistency,
         shard_selection,
         params.timeout(),
+        access,
     )
     .await;
 
@@ -52,6 +56,7 @@ async fn batch_search_points(
     collection: Path<CollectionPath>,
     request: Json<SearchRequestBatch>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -80,6 +85,7 @@ async fn batch_search_points(
         requests,
         params.consistency,
         params.timeout(),
+        access,
     )
     .await;
 
@@ -92,6 +98,7 @@ async fn search_point_groups(
     collection: Path<CollectionPath>,
     request: Json<SearchGroupsRequest>,
     params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
 
@@ -112,15 +119,88 @@ async fn search_point_groups(
         params.consistency,
         shard_selection,
         params.timeout(),
+        access,
     )
     .await;
 
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/search/matrix/pairs")]
+async fn search_points_matrix_pairs(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_search_points_matrix(
+        toc.get_ref(),
+        &collection.name,
+        search_request,
+        params.consistency,
+        shard_selection,
+        params.timeout(),
+        access,
+    )
+    .await
+    .map(SearchMatrixPairsResponse::from);
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/matrix/offsets")]
+async fn search_points_matrix_offsets(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_search_points_matrix(
+        toc.get_ref(),
+        &collection.name,
+        search_request,
+        params.consistency,
+        shard_selection,
+        params.timeout(),
+        access,
+    )
+    .await
+    .map(SearchMatrixOffsetsResponse::from);
+
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(search_points_matrix_pairs)
+        .service(search_points_matrix_offsets);
 }
@@ -7,12 +7,14 @@ use actix_web::web::Query;
 use actix_web::{get, post, web, HttpResponse, Responder};
 use actix_web_validator::Json;
 use common::types::{DetailsLevel, TelemetryDetail};
+use rbac::access::AccessLevel;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::{Deserialize, Serialize};
 use storage::content_manager::toc::TableOfContent;
 use tokio::sync::Mutex;
 
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::health;
 use crate::common::helpers::LocksOption;
@@ -30,8 +32,12 @@ pub struct TelemetryParam {
 async fn telemetry(
     telemetry_collector: web::Data<Mutex<TelemetryCollector>>,
     params: Query<TelemetryParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Read) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let anonymize = params.anonymize.unwrap_or(false);
     let details_level = params
         .details_level
@@ -59,7 +65,11 @@ pub struct MetricsParam {
 async fn metrics(
     telemetry_collector: web::Data<Mutex<TelemetryCollector>>,
     params: Query<MetricsParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
+    if let Err(err) = access.check_global_access(AccessLevel::Read) {
+        return process_response::<()>(Err(err.into()), Instant::now());
+    }
     let anonymize = params.anonymize.unwrap_or(false);
     let telemetry_collector = telemetry_collector.lock().await;
     let telemetry_data = telemetry_collector
@@ -83,8 +93,12 @@ async fn metrics(
 async fn put_locks(
     toc: web::Data<TableOfContent>,
     locks_option: Json<LocksOption>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Manage) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let result = LocksOption {
         write: toc.get_ref().is_write_locked(),
         error_message: toc.get_ref().get_lock_error_message(),
@@ -95,8 +109,14 @@ async fn put_locks(
 }
 
 #[get("/locks")]
-async fn get_locks(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn get_locks(
+    toc: web::Data<TableOfContent>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Read) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let result = LocksOption {
         write: toc.get_ref().is_write_locked(),
         error_message: toc.get_ref().get_lock_error_message(),
@@ -105,8 +125,11 @@ async fn get_locks(toc: web::Data<TableOfContent>) -> impl Responder {
 }
 
 #[get("/stacktrace")]
-async fn get_stacktrace() -> impl Responder {
+async fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Responder {
     let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessLevel::Read) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
     let result = get_stack_trace();
     process_response(Ok(result), timing)
 }
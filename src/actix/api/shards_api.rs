@@ -9,6 +9,7 @@ use tokio::time::Instant;
 
 use crate::actix::api::collections_api::WaitTimeout;
 use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::collections::do_update_collection_cluster;
 
@@ -20,6 +21,7 @@ async fn create_shard_key(
     collection: Path<CollectionPath>,
     request: Json<CreateShardingKey>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait_timeout = query.timeout();
@@ -33,6 +35,7 @@ async fn create_shard_key(
 
     let response = do_update_collection_cluster(
         &dispatcher,
+        access,
         collection.name.clone(),
         operation,
         wait_timeout,
@@ -48,6 +51,7 @@ async fn delete_shard_key(
     collection: Path<CollectionPath>,
     request: Json<DropShardingKey>,
     Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait_timeout = query.timeout();
@@ -61,6 +65,7 @@ async fn delete_shard_key(
 
     let response = do_update_collection_cluster(
         &dispatcher,
+        access,
         collection.name.clone(),
         operation,
         wait_timeout,
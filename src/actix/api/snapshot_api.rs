@@ -13,6 +13,7 @@ use collection::operations::snapshot_ops::{
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
+use rbac::access::{Access, AccessLevel};
 use reqwest::Url;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -28,6 +29,7 @@ use uuid::Uuid;
 use validator::Validate;
 
 use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers;
 use crate::actix::helpers::{
     accepted_response, collection_into_actix_error, process_response, storage_into_actix_error,
@@ -65,7 +67,15 @@ pub struct SnapshottingForm {
 }
 
 // Actix specific code
-pub async fn do_get_full_snapshot(toc: &TableOfContent, snapshot_name: &str) -> Result<NamedFile> {
+pub async fn do_get_full_snapshot(
+    toc: &TableOfContent,
+    access: Access,
+    snapshot_name: &str,
+) -> Result<NamedFile> {
+    access
+        .check_global_access(AccessLevel::Manage)
+        .map_err(|err| storage_into_actix_error(err.into()))?;
+
     let file_name = get_full_snapshot_path(toc, snapshot_name)
         .await
         .map_err(storage_into_actix_error)?;
@@ -118,9 +128,14 @@ pub async fn do_save_uploaded_snapshot(
 // Actix specific code
 pub async fn do_get_snapshot(
     toc: &TableOfContent,
+    access: Access,
     collection_name: &str,
     snapshot_name: &str,
 ) -> Result<NamedFile> {
+    access
+        .check_whole_collection_access(collection_name, AccessLevel::Manage)
+        .map_err(|err| storage_into_actix_error(err.into()))?;
+
     let collection = toc
         .get_collection(collection_name)
         .await
@@ -135,11 +150,15 @@ pub async fn do_get_snapshot(
 }
 
 #[get("/collections/{name}/snapshots")]
-async fn list_snapshots(toc: web::Data<TableOfContent>, path: web::Path<String>) -> impl Responder {
+async fn list_snapshots(
+    toc: web::Data<TableOfContent>,
+    path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let collection_name = path.into_inner();
     let timing = Instant::now();
 
-    let response = do_list_snapshots(&toc, &collection_name).await;
+    let response = do_list_snapshots(&toc, access, &collection_name).await;
     process_response(response, timing)
 }
 
@@ -148,12 +167,13 @@ async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
     params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let wait = params.wait.unwrap_or(true);
 
     let timing = Instant::now();
-    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, wait).await;
+    let response = do_create_snapshot(dispatcher.get_ref(), access, &collection_name, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -168,11 +188,16 @@ async fn upload_snapshot(
     collection: valid::Path<CollectionPath>,
     MultipartForm(form): MultipartForm<SnapshottingForm>,
     params: valid::Query<SnapshotUploadingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let snapshot = form.snapshot;
     let wait = params.wait.unwrap_or(true);
 
+    if let Err(err) = access.check_whole_collection_access(&collection.name, AccessLevel::Manage) {
+        return process_response::<()>(Err(err.into()), timing);
+    }
+
     if let Some(checksum) = &params.checksum {
         let snapshot_checksum = match hash_file(snapshot.file.path()).await {
             Ok(checksum) => checksum,
@@ -203,8 +228,10 @@ async fn upload_snapshot(
         checksum: None,
     };
 
+    // Access is checked above, the uploaded snapshot is stored locally by the server itself
     let response = do_recover_from_snapshot(
         dispatcher.get_ref(),
+        &Access::full(),
         &collection.name,
         snapshot_recover,
         wait,
@@ -226,6 +253,7 @@ async fn recover_from_snapshot(
     collection: valid::Path<CollectionPath>,
     request: valid::Json<SnapshotRecover>,
     params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let snapshot_recover = request.into_inner();
@@ -238,6 +266,7 @@ async fn recover_from_snapshot(
 
     let response = do_recover_from_snapshot(
         dispatcher.get_ref(),
+        &access,
         &collection.name,
         snapshot_recover,
         wait,
@@ -256,15 +285,19 @@ async fn recover_from_snapshot(
 async fn get_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, String)>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection_name, snapshot_name) = path.into_inner();
-    do_get_snapshot(&toc, &collection_name, &snapshot_name).await
+    do_get_snapshot(&toc, access, &collection_name, &snapshot_name).await
 }
 
 #[get("/snapshots")]
-async fn list_full_snapshots(toc: web::Data<TableOfContent>) -> impl Responder {
+async fn list_full_snapshots(
+    toc: web::Data<TableOfContent>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
     let timing = Instant::now();
-    let response = do_list_full_snapshots(toc.get_ref()).await;
+    let response = do_list_full_snapshots(toc.get_ref(), &access).await;
     process_response(response, timing)
 }
 
@@ -272,10 +305,11 @@ async fn list_full_snapshots(toc: web::Data<TableOfContent>) -> impl Responder {
 async fn create_full_snapshot(
     dispatcher: web::Data<Dispatcher>,
     params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response = do_create_full_snapshot(dispatcher.get_ref(), wait).await;
+    let response = do_create_full_snapshot(dispatcher.get_ref(), &access, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -287,9 +321,10 @@ async fn create_full_snapshot(
 async fn get_full_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let snapshot_name = path.into_inner();
-    do_get_full_snapshot(&toc, &snapshot_name).await
+    do_get_full_snapshot(&toc, access, &snapshot_name).await
 }
 
 #[delete("/snapshots/{snapshot_name}")]
@@ -297,11 +332,13 @@ async fn delete_full_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
     params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let snapshot_name = path.into_inner();
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response = do_delete_full_snapshot(dispatcher.get_ref(), &snapshot_name, wait).await;
+    let response =
+        do_delete_full_snapshot(dispatcher.get_ref(), &access, &snapshot_name, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -314,13 +351,19 @@ async fn delete_collection_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<(String, String)>,
     params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection_name, snapshot_name) = path.into_inner();
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response =
-        do_delete_collection_snapshot(dispatcher.get_ref(), &collection_name, &snapshot_name, wait)
-            .await;
+    let response = do_delete_collection_snapshot(
+        dispatcher.get_ref(),
+        &access,
+        &collection_name,
+        &snapshot_name,
+        wait,
+    )
+    .await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -332,10 +375,12 @@ async fn delete_collection_snapshot(
 async fn list_shard_snapshots(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, ShardId)>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection, shard) = path.into_inner();
-    let future = common::snapshots::list_shard_snapshots(toc.into_inner(), collection, shard)
-        .map_err(Into::into);
+    let future =
+        common::snapshots::list_shard_snapshots(toc.into_inner(), access, collection, shard)
+            .map_err(Into::into);
 
     helpers::time(future).await
 }
@@ -345,10 +390,12 @@ async fn create_shard_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, ShardId)>,
     query: web::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection, shard) = path.into_inner();
-    let future = common::snapshots::create_shard_snapshot(toc.into_inner(), collection, shard)
-        .map_err(Into::into);
+    let future =
+        common::snapshots::create_shard_snapshot(toc.into_inner(), access, collection, shard)
+            .map_err(Into::into);
 
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
@@ -361,12 +408,14 @@ async fn recover_shard_snapshot(
     path: web::Path<(String, ShardId)>,
     query: web::Query<SnapshottingParam>,
     web::Json(request): web::Json<ShardSnapshotRecover>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let future = async move {
         let (collection, shard) = path.into_inner();
 
         common::snapshots::recover_shard_snapshot(
             toc.into_inner(),
+            access,
             collection,
             shard,
             request.location,
@@ -389,6 +438,7 @@ async fn upload_shard_snapshot(
     path: web::Path<(String, ShardId)>,
     query: web::Query<SnapshotUploadingParam>,
     MultipartForm(form): MultipartForm<SnapshottingForm>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection, shard) = path.into_inner();
     let SnapshotUploadingParam {
@@ -401,6 +451,10 @@ async fn upload_shard_snapshot(
     //   - but the task is *spawned* on the runtime and won't be cancelled, if request is cancelled
 
     let future = cancel::future::spawn_cancel_on_drop(move |cancel| async move {
+        access
+            .check_whole_collection_access(&collection, AccessLevel::Manage)
+            .map_err(StorageError::from)?;
+
         if let Some(checksum) = checksum {
             let snapshot_checksum = hash_file(form.snapshot.file.path()).await?;
             if !hashes_equal(snapshot_checksum.as_str(), checksum.as_str()) {
@@ -441,8 +495,12 @@ async fn upload_shard_snapshot(
 async fn download_shard_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, ShardId, String)>,
+    ActixAccess(access): ActixAccess,
 ) -> Result<impl Responder, helpers::HttpError> {
     let (collection, shard, snapshot) = path.into_inner();
+    access
+        .check_whole_collection_access(&collection, AccessLevel::Manage)
+        .map_err(StorageError::from)?;
     let collection = toc.get_collection(&collection).await?;
     let snapshot_path = collection.get_shard_snapshot_path(shard, &snapshot).await?;
 
@@ -454,12 +512,18 @@ async fn delete_shard_snapshot(
     toc: web::Data<TableOfContent>,
     path: web::Path<(String, ShardId, String)>,
     query: web::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let (collection, shard, snapshot) = path.into_inner();
-    let future =
-        common::snapshots::delete_shard_snapshot(toc.into_inner(), collection, shard, snapshot)
-            .map_ok(|_| true)
-            .map_err(Into::into);
+    let future = common::snapshots::delete_shard_snapshot(
+        toc.into_inner(),
+        access,
+        collection,
+        shard,
+        snapshot,
+    )
+    .map_ok(|_| true)
+    .map_err(Into::into);
 
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
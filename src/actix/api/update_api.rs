@@ -1,7 +1,7 @@
 use actix_web::rt::time::Instant;
 use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, SetPayload, UpdatePayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
@@ -12,11 +12,12 @@ use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
 use super::CollectionPath;
+use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
     do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
-    do_upsert_points, CreateFieldIndex, UpdateOperations,
+    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_payload,
+    do_update_vectors, do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
 #[derive(Deserialize, Validate)]
@@ -38,6 +39,7 @@ async fn upsert_points(
     collection: Path<CollectionPath>,
     operation: Json<PointInsertOperations>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -52,6 +54,7 @@ async fn upsert_points(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -63,6 +66,7 @@ async fn delete_points(
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -77,6 +81,7 @@ async fn delete_points(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -88,6 +93,7 @@ async fn update_vectors(
     collection: Path<CollectionPath>,
     operation: Json<UpdateVectors>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -102,6 +108,7 @@ async fn update_vectors(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -113,6 +120,7 @@ async fn delete_vectors(
     collection: Path<CollectionPath>,
     operation: Json<DeleteVectors>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -127,6 +135,7 @@ async fn delete_vectors(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -138,6 +147,7 @@ async fn set_payload(
     collection: Path<CollectionPath>,
     operation: Json<SetPayload>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -152,6 +162,7 @@ async fn set_payload(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -163,6 +174,7 @@ async fn overwrite_payload(
     collection: Path<CollectionPath>,
     operation: Json<SetPayload>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -177,6 +189,7 @@ async fn overwrite_payload(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -188,6 +201,7 @@ async fn delete_payload(
     collection: Path<CollectionPath>,
     operation: Json<DeletePayload>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -202,6 +216,34 @@ async fn delete_payload(
         None,
         wait,
         ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/payload/update")]
+async fn update_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<UpdatePayload>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_update_payload(
+        toc.into_inner(),
+        collection.into_inner().name,
+        operation,
+        None,
+        None,
+        wait,
+        ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -213,6 +255,7 @@ async fn clear_payload(
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -227,6 +270,7 @@ async fn clear_payload(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -238,6 +282,7 @@ async fn update_batch(
     collection: Path<CollectionPath>,
     operations: Json<UpdateOperations>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operations = operations.into_inner();
@@ -252,6 +297,7 @@ async fn update_batch(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -262,6 +308,7 @@ async fn create_field_index(
     collection: Path<CollectionPath>,
     operation: Json<CreateFieldIndex>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let operation = operation.into_inner();
@@ -276,6 +323,7 @@ async fn create_field_index(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -287,6 +335,7 @@ async fn delete_field_index(
     collection: Path<CollectionPath>,
     field: Path<FieldPath>,
     params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(false);
@@ -300,6 +349,7 @@ async fn delete_field_index(
         None,
         wait,
         ordering,
+        access,
     )
     .await;
     process_response(response, timing)
@@ -314,6 +364,7 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(set_payload)
         .service(overwrite_payload)
         .service(delete_payload)
+        .service(update_payload)
         .service(clear_payload)
         .service(create_field_index)
         .service(delete_field_index)
@@ -6,16 +6,17 @@ use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Tr
 use actix_web::http::Method;
 use actix_web::{Error, FromRequest, HttpMessage as _, HttpResponse};
 use futures_util::future::LocalBoxFuture;
-use rbac::jwt::Claims;
+use rbac::access::Access;
 
 use crate::common::auth::AuthKeys;
 
 /// List of read-only POST request paths. List MUST be sorted.
-const READ_ONLY_POST_PATTERNS: [&str; 11] = [
+const READ_ONLY_POST_PATTERNS: [&str; 12] = [
     "/collections/{name}/points",
     "/collections/{name}/points/count",
     "/collections/{name}/points/discover",
     "/collections/{name}/points/discover/batch",
+    "/collections/{name}/points/query",
     "/collections/{name}/points/recommend",
     "/collections/{name}/points/recommend/batch",
     "/collections/{name}/points/recommend/groups",
@@ -130,14 +131,12 @@ where
             |key| req.headers().get(key).and_then(|val| val.to_str().ok()),
             is_read_only(&req),
         ) {
-            Ok(claims) => {
-                if let Some(claims) = claims {
-                    let _previous = req.extensions_mut().insert::<Claims>(claims);
-                    debug_assert!(
-                        _previous.is_none(),
-                        "Previous claims should not exist in the request"
-                    );
-                }
+            Ok(access) => {
+                let _previous = req.extensions_mut().insert::<Access>(access);
+                debug_assert!(
+                    _previous.is_none(),
+                    "Previous access object should not exist in the request"
+                );
                 Box::pin(self.service.call(req))
             }
             Err(e) => Box::pin(async move {
@@ -149,9 +148,12 @@ where
     }
 }
 
-pub struct ActixClaims(pub Option<Claims>);
+/// Access rights of the request.
+///
+/// Full access is granted if authentication is disabled or the path is whitelisted.
+pub struct ActixAccess(pub Access);
 
-impl FromRequest for ActixClaims {
+impl FromRequest for ActixAccess {
     type Error = Infallible;
     type Future = Ready<Result<Self, Self::Error>>;
 
@@ -159,7 +161,11 @@ impl FromRequest for ActixClaims {
         req: &actix_web::HttpRequest,
         _payload: &mut actix_web::dev::Payload,
     ) -> Self::Future {
-        ready(Ok(ActixClaims(req.extensions_mut().remove::<Claims>())))
+        let access = req
+            .extensions_mut()
+            .remove::<Access>()
+            .unwrap_or_else(Access::full);
+        ready(Ok(ActixAccess(access)))
     }
 }
 
@@ -26,6 +26,8 @@ pub fn storage_into_actix_error(err: StorageError) -> Error {
         StorageError::Timeout { .. } => error::ErrorRequestTimeout(format!("{err}")),
         StorageError::AlreadyExists { .. } => error::ErrorConflict(format!("{err}")),
         StorageError::ChecksumMismatch { .. } => error::ErrorBadRequest(format!("{err}")),
+        StorageError::Forbidden { .. } => error::ErrorForbidden(format!("{err}")),
+        StorageError::PositionTruncated { .. } => error::ErrorGone(format!("{err}")),
     }
 }
 
@@ -68,6 +70,8 @@ where
                 StorageError::Timeout { .. } => HttpResponse::RequestTimeout(),
                 StorageError::AlreadyExists { .. } => HttpResponse::Conflict(),
                 StorageError::ChecksumMismatch { .. } => HttpResponse::BadRequest(),
+                StorageError::Forbidden { .. } => HttpResponse::Forbidden(),
+                StorageError::PositionTruncated { .. } => HttpResponse::Gone(),
             };
 
             resp.json(ApiResponse::<()> {
@@ -200,6 +204,10 @@ impl From<StorageError> for HttpError {
             StorageError::ChecksumMismatch { .. } => {
                 (http::StatusCode::BAD_REQUEST, err.to_string())
             }
+            StorageError::Forbidden { description } => (http::StatusCode::FORBIDDEN, description),
+            StorageError::PositionTruncated { description } => {
+                (http::StatusCode::GONE, description)
+            }
         };
 
         Self {
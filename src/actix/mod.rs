@@ -20,10 +20,13 @@ use actix_web_extras::middleware::Condition as ConditionEx;
 use collection::operations::validation;
 use storage::dispatcher::Dispatcher;
 
+use crate::actix::api::changes_api::config_changes_api;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::config_facet_api;
+use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
 use crate::actix::api::search_api::config_search_api;
@@ -156,6 +159,9 @@ pub fn init(
                 .configure(config_search_api)
                 .configure(config_recommend_api)
                 .configure(config_discovery_api)
+                .configure(config_query_api)
+                .configure(config_facet_api)
+                .configure(config_changes_api)
                 .configure(config_shards_api)
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
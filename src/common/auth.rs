@@ -1,4 +1,4 @@
-use rbac::jwt::Claims;
+use rbac::access::{Access, AccessLevel};
 use rbac::JwtParser;
 
 use super::strings::ct_eq;
@@ -40,35 +40,41 @@ impl AuthKeys {
 
     /// Validate that the specified request is allowed for given keys.
     ///
+    /// Tokens limited to specific collections pass this validation regardless of `is_read_only`,
+    /// their access is checked by each operation.
+    ///
     /// # Returns
     ///
-    /// - `Ok(None)` if the request is allowed through the API key.
-    /// - `Ok(Some(claims))` if the request is allowed through the JWT token.
+    /// - `Ok(access)` with access rights granted by the API key or the JWT token.
     /// - `Err(description)` if the request is not allowed.
     pub fn validate_request<'a>(
         &self,
         get_header: impl Fn(&'a str) -> Option<&'a str>,
         is_read_only: bool,
-    ) -> Result<Option<Claims>, &'static str> {
+    ) -> Result<Access, &'static str> {
         let Some(key) = get_header("api-key")
             .or_else(|| get_header("authorization").and_then(|v| v.strip_prefix("Bearer ")))
         else {
             return Err("Must provide an API key or an Authorization bearer token");
         };
 
-        if self.can_write(key) || (is_read_only && self.can_read(key)) {
-            return Ok(None);
+        if self.can_write(key) {
+            return Ok(Access::Global(AccessLevel::Manage));
         }
 
-        if !is_read_only && self.can_read(key) {
-            return Err("Write access denied");
+        if self.can_read(key) {
+            if !is_read_only {
+                return Err("Write access denied");
+            }
+            return Ok(Access::Global(AccessLevel::Read));
         }
 
         if let Some(claims) = self.jwt_parser.as_ref().and_then(|p| p.decode(key).ok()) {
-            if !claims.w.unwrap_or(false) && !is_read_only {
+            let access = claims.to_access();
+            if access == Access::Global(AccessLevel::Read) && !is_read_only {
                 return Err("Write access denied");
             }
-            return Ok(Some(claims));
+            return Ok(access);
         }
 
         Err("Invalid API key or JWT token")
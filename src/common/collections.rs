@@ -5,7 +5,8 @@ use api::grpc::qdrant::CollectionExists;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
-    ReplicateShardOperation, RestartTransfer, RestartTransferOperation,
+    ReplicateShardOperation, RestartTransfer, RestartTransferOperation, StartResharding,
+    StartReshardingOperation,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::snapshot_ops::SnapshotDescription;
@@ -14,13 +15,16 @@ use collection::operations::types::{
 };
 use collection::shards::replica_set;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
-use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
+use collection::shards::transfer::{
+    ShardTransfer, ShardTransferKey, ShardTransferMethod, ShardTransferRestart,
+};
 use itertools::Itertools;
 use rand::prelude::SliceRandom;
+use rbac::access::{Access, AccessLevel};
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, CreateShardKey, DropShardKey, ShardTransferOperations,
-    UpdateCollectionOperation,
+    CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
+    ShardTransferOperations, UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -28,8 +32,11 @@ use storage::dispatcher::Dispatcher;
 
 pub async fn do_collection_exists(
     toc: &TableOfContent,
+    access: Access,
     name: &str,
 ) -> Result<CollectionExists, StorageError> {
+    access.check_collection_access(name, AccessLevel::Read)?;
+
     // if this returns Ok, it means the collection exists.
     // if not, we check that the error is NotFound
     let Err(error) = toc.get_collection(name).await else {
@@ -43,9 +50,12 @@ pub async fn do_collection_exists(
 
 pub async fn do_get_collection(
     toc: &TableOfContent,
+    access: Access,
     name: &str,
     shard_selection: Option<ShardId>,
 ) -> Result<CollectionInfo, StorageError> {
+    access.check_collection_access(name, AccessLevel::Read)?;
+
     let collection = toc.get_collection(name).await?;
 
     let shard_selection = match shard_selection {
@@ -56,11 +66,12 @@ pub async fn do_get_collection(
     Ok(collection.info(&shard_selection).await?)
 }
 
-pub async fn do_list_collections(toc: &TableOfContent) -> CollectionsResponse {
+pub async fn do_list_collections(toc: &TableOfContent, access: Access) -> CollectionsResponse {
     let collections = toc
         .all_collections()
         .await
         .into_iter()
+        .filter(|name| access.is_collection_visible(name))
         .map(|name| CollectionDescription { name })
         .collect_vec();
 
@@ -108,8 +119,11 @@ fn generate_even_placement(
 
 pub async fn do_list_collection_aliases(
     toc: &TableOfContent,
+    access: Access,
     collection_name: &str,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
+    access.check_collection_access(collection_name, AccessLevel::Read)?;
+
     let mut aliases: Vec<AliasDescription> = Default::default();
     for alias in toc.collection_aliases(collection_name).await? {
         aliases.push(AliasDescription {
@@ -122,15 +136,24 @@ pub async fn do_list_collection_aliases(
 
 pub async fn do_list_aliases(
     toc: &TableOfContent,
+    access: Access,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
-    let aliases = toc.list_aliases().await?;
+    let aliases = toc
+        .list_aliases()
+        .await?
+        .into_iter()
+        .filter(|alias| access.is_collection_visible(&alias.collection_name))
+        .collect();
     Ok(CollectionsAliasesResponse { aliases })
 }
 
 pub async fn do_list_snapshots(
     toc: &TableOfContent,
+    access: Access,
     collection_name: &str,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
+    access.check_whole_collection_access(collection_name, AccessLevel::Manage)?;
+
     Ok(toc
         .get_collection(collection_name)
         .await?
@@ -140,9 +163,12 @@ pub async fn do_list_snapshots(
 
 pub async fn do_create_snapshot(
     dispatcher: &Dispatcher,
+    access: Access,
     collection_name: &str,
     wait: bool,
 ) -> Result<SnapshotDescription, StorageError> {
+    access.check_whole_collection_access(collection_name, AccessLevel::Manage)?;
+
     let collection = collection_name.to_string();
     let dispatcher = dispatcher.clone();
     let snapshot = tokio::spawn(async move { dispatcher.create_snapshot(&collection).await });
@@ -160,18 +186,24 @@ pub async fn do_create_snapshot(
 
 pub async fn do_get_collection_cluster(
     toc: &TableOfContent,
+    access: Access,
     name: &str,
 ) -> Result<CollectionClusterInfo, StorageError> {
+    access.check_collection_access(name, AccessLevel::Read)?;
+
     let collection = toc.get_collection(name).await?;
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
+    access: Access,
     collection_name: String,
     operation: ClusterOperations,
     wait_timeout: Option<Duration>,
 ) -> Result<bool, StorageError> {
+    access.check_whole_collection_access(&collection_name, AccessLevel::Manage)?;
+
     if dispatcher.consensus_state().is_none() {
         return Err(StorageError::BadRequest {
             description: "Distributed mode disabled".to_string(),
@@ -205,6 +237,16 @@ pub async fn do_update_collection_cluster(
         Ok(())
     };
 
+    // Resharding transfers are only started by the resharding operation itself
+    let validate_transfer_method = |method: Option<ShardTransferMethod>| {
+        if method == Some(ShardTransferMethod::ReshardingStreamRecords) {
+            return Err(StorageError::bad_request(
+                "Resharding transfer method can't be used explicitly",
+            ));
+        }
+        Ok(())
+    };
+
     let collection = dispatcher.get_collection(&collection_name).await?;
 
     match operation {
@@ -223,6 +265,8 @@ pub async fn do_update_collection_cluster(
             validate_peer_exists(move_shard.to_peer_id)?;
             validate_peer_exists(move_shard.from_peer_id)?;
 
+            validate_transfer_method(move_shard.method)?;
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
@@ -230,12 +274,14 @@ pub async fn do_update_collection_cluster(
                         collection_name,
                         Start(ShardTransfer {
                             shard_id: move_shard.shard_id,
+                            to_shard_id: None,
                             to: move_shard.to_peer_id,
                             from: move_shard.from_peer_id,
                             sync: false,
                             method: move_shard.method,
                         }),
                     ),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -257,6 +303,8 @@ pub async fn do_update_collection_cluster(
             // validate source peer exists
             validate_peer_exists(replicate_shard.from_peer_id)?;
 
+            validate_transfer_method(replicate_shard.method)?;
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
@@ -264,12 +312,14 @@ pub async fn do_update_collection_cluster(
                         collection_name,
                         Start(ShardTransfer {
                             shard_id: replicate_shard.shard_id,
+                            to_shard_id: None,
                             to: replicate_shard.to_peer_id,
                             from: replicate_shard.from_peer_id,
                             sync: true,
                             method: replicate_shard.method,
                         }),
                     ),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -277,6 +327,7 @@ pub async fn do_update_collection_cluster(
         ClusterOperations::AbortTransfer(AbortTransferOperation { abort_transfer }) => {
             let transfer = ShardTransferKey {
                 shard_id: abort_transfer.shard_id,
+                to_shard_id: None,
                 to: abort_transfer.to_peer_id,
                 from: abort_transfer.from_peer_id,
             };
@@ -299,6 +350,7 @@ pub async fn do_update_collection_cluster(
                             reason: "user request".to_string(),
                         },
                     ),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -325,6 +377,7 @@ pub async fn do_update_collection_cluster(
             dispatcher
                 .submit_collection_meta_op(
                     CollectionMetaOperations::UpdateCollection(update_operation),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -397,6 +450,7 @@ pub async fn do_update_collection_cluster(
                         shard_key: create_sharding_key.shard_key,
                         placement: exact_placement,
                     }),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -434,6 +488,7 @@ pub async fn do_update_collection_cluster(
                         collection_name,
                         shard_key: drop_sharding_key.shard_key,
                     }),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -446,8 +501,11 @@ pub async fn do_update_collection_cluster(
                 method,
             } = restart_transfer;
 
+            validate_transfer_method(Some(method))?;
+
             let transfer_key = ShardTransferKey {
                 shard_id,
+                to_shard_id: None,
                 to: to_peer_id,
                 from: from_peer_id,
             };
@@ -467,11 +525,62 @@ pub async fn do_update_collection_cluster(
                         collection_name,
                         ShardTransferOperations::Restart(ShardTransferRestart {
                             shard_id,
+                            to_shard_id: None,
                             to: to_peer_id,
                             from: from_peer_id,
                             method,
                         }),
                     ),
+                    &access,
+                    wait_timeout,
+                )
+                .await
+        }
+        ClusterOperations::StartResharding(StartReshardingOperation { start_resharding }) => {
+            let StartResharding {
+                direction,
+                peer_id,
+                shard_key,
+            } = start_resharding;
+
+            if let Some(peer_id) = peer_id {
+                validate_peer_exists(peer_id)?;
+            }
+
+            let reshard_key = collection
+                .reshard_key(direction, peer_id, shard_key)
+                .await?;
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::Resharding(
+                        collection_name,
+                        ReshardingOperation::Start(reshard_key),
+                    ),
+                    &access,
+                    wait_timeout,
+                )
+                .await
+        }
+        ClusterOperations::AbortResharding(_) => {
+            let Some(state) = collection.resharding_state().await else {
+                return Err(StorageError::NotFound {
+                    description: format!(
+                        "Resharding of collection {collection_name} is not in progress",
+                    ),
+                });
+            };
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::Resharding(
+                        collection_name,
+                        ReshardingOperation::Abort {
+                            key: state.key(),
+                            reason: "user request".to_string(),
+                        },
+                    ),
+                    &access,
                     wait_timeout,
                 )
                 .await
@@ -1,23 +1,29 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use collection::common::batching::batch_requests;
+use collection::operations::changes::{ChangesBatch, ChangesOffset, ChangesRequest};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{
-    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
+    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp, UpdatePayload,
+    UpdatePayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    ConditionalInsertOperationInternal, FilterSelector, PointIdsList, PointInsertOperations,
+    PointOperations, PointsSelector, WriteOrdering,
 };
 use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
+    CollectionSearchMatrixResponse, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, DiscoverRequestBatch, DiscoverRequestInternal,
+    FacetRequestInternal, FacetResponse, GroupsResult, PointRequestInternal,
     RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult,
-    SearchGroupsRequestInternal, UpdateResult,
+    SearchGroupsRequestInternal, SearchMatrixRequestInternal, UpdateResult,
 };
+use collection::operations::universal_query::collection_query::QueryRequestInternal;
+use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
 };
@@ -25,6 +31,8 @@ use collection::operations::{
     ClockTag, CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
 use collection::shards::shard::ShardId;
+use futures::{stream, Stream, TryStreamExt as _};
+use rbac::access::Access;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
 use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
@@ -157,6 +165,7 @@ fn get_shard_selector_for_update(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_upsert_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -165,10 +174,18 @@ pub async fn do_upsert_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let (shard_key, operation, preconditions) = operation.decompose();
+    let point_operation = if preconditions.is_empty() {
+        PointOperations::UpsertPoints(operation)
+    } else {
+        PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+            points_op: operation,
+            preconditions,
+        })
+    };
+    let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
@@ -178,10 +195,12 @@ pub async fn do_upsert_points(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_delete_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -190,11 +209,20 @@ pub async fn do_delete_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
-            (PointOperations::DeletePoints { ids: points }, shard_key)
-        }
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            preconditions,
+        }) => (
+            PointOperations::DeletePoints {
+                ids: points,
+                preconditions,
+            },
+            shard_key,
+        ),
         PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
             (PointOperations::DeletePointsByFilter(filter), shard_key)
         }
@@ -208,10 +236,12 @@ pub async fn do_delete_points(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -220,6 +250,7 @@ pub async fn do_update_vectors(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let UpdateVectors { points, shard_key } = operation;
 
@@ -235,10 +266,12 @@ pub async fn do_update_vectors(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_delete_vectors(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -247,6 +280,7 @@ pub async fn do_delete_vectors(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     // TODO: Is this cancel safe!?
 
@@ -275,6 +309,7 @@ pub async fn do_delete_vectors(
                 wait,
                 ordering,
                 shard_selector.clone(),
+                &access,
             )
             .await?,
         );
@@ -290,6 +325,7 @@ pub async fn do_delete_vectors(
                 wait,
                 ordering,
                 shard_selector,
+                &access,
             )
             .await?,
         );
@@ -298,6 +334,7 @@ pub async fn do_delete_vectors(
     result.ok_or_else(|| StorageError::bad_request("No filter or points provided"))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_set_payload(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -306,6 +343,7 @@ pub async fn do_set_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
         points,
@@ -313,6 +351,7 @@ pub async fn do_set_payload(
         filter,
         shard_key,
         key,
+        preconditions,
     } = operation;
 
     let collection_operation =
@@ -321,6 +360,7 @@ pub async fn do_set_payload(
             points,
             filter,
             key,
+            preconditions,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -331,10 +371,12 @@ pub async fn do_set_payload(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_overwrite_payload(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -343,12 +385,14 @@ pub async fn do_overwrite_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
         points,
         payload,
         filter,
         shard_key,
+        preconditions,
         ..
     } = operation;
 
@@ -359,6 +403,7 @@ pub async fn do_overwrite_payload(
             filter,
             // overwrite operation doesn't support payload selector
             key: None,
+            preconditions,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -369,10 +414,12 @@ pub async fn do_overwrite_payload(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_delete_payload(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -381,12 +428,14 @@ pub async fn do_delete_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let DeletePayload {
         keys,
         points,
         filter,
         shard_key,
+        preconditions,
     } = operation;
 
     let collection_operation =
@@ -394,6 +443,49 @@ pub async fn do_delete_payload(
             keys,
             points,
             filter,
+            preconditions,
+        }));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag),
+        wait,
+        ordering,
+        shard_selector,
+        &access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_update_payload(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: UpdatePayload,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let UpdatePayload {
+        updates,
+        points,
+        filter,
+        condition,
+        shard_key,
+        preconditions,
+    } = operation;
+
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::UpdatePayload(UpdatePayloadOp {
+            updates,
+            points,
+            filter,
+            condition,
+            preconditions,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -404,10 +496,12 @@ pub async fn do_delete_payload(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_clear_payload(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -416,11 +510,20 @@ pub async fn do_clear_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
-            (PayloadOps::ClearPayload { points }, shard_key)
-        }
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            preconditions,
+        }) => (
+            PayloadOps::ClearPayload {
+                points,
+                preconditions,
+            },
+            shard_key,
+        ),
         PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
             (PayloadOps::ClearPayloadByFilter(filter), shard_key)
         }
@@ -436,10 +539,12 @@ pub async fn do_clear_payload(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_batch_update_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -448,6 +553,7 @@ pub async fn do_batch_update_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<Vec<UpdateResult>, StorageError> {
     let mut results = Vec::with_capacity(operations.len());
     for operation in operations {
@@ -461,6 +567,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -473,6 +580,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -485,6 +593,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -497,6 +606,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -509,6 +619,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -521,6 +632,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -533,6 +645,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -545,6 +658,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    access.clone(),
                 )
                 .await
             }
@@ -564,6 +678,7 @@ pub async fn do_create_index_internal(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::CreateIndex(CreateIndex {
@@ -584,10 +699,12 @@ pub async fn do_create_index_internal(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_create_index(
     dispatcher: Arc<Dispatcher>,
     collection_name: String,
@@ -596,6 +713,7 @@ pub async fn do_create_index(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     // TODO: Is this cancel safe!?
 
@@ -616,7 +734,7 @@ pub async fn do_create_index(
 
     // TODO: Is `submit_collection_meta_op` cancel-safe!? Should be, I think?.. 🤔
     dispatcher
-        .submit_collection_meta_op(consensus_op, wait_timeout)
+        .submit_collection_meta_op(consensus_op, &access, wait_timeout)
         .await?;
 
     // This function is required as long as we want to maintain interface compatibility
@@ -632,10 +750,12 @@ pub async fn do_create_index(
         shard_selection,
         wait,
         ordering,
+        access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_delete_index_internal(
     toc: Arc<TableOfContent>,
     collection_name: String,
@@ -644,6 +764,7 @@ pub async fn do_delete_index_internal(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let collection_operation = CollectionUpdateOperations::FieldIndexOperation(
         FieldIndexOperations::DeleteIndex(index_name),
@@ -661,10 +782,12 @@ pub async fn do_delete_index_internal(
         wait,
         ordering,
         shard_selector,
+        &access,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn do_delete_index(
     dispatcher: Arc<Dispatcher>,
     collection_name: String,
@@ -673,6 +796,7 @@ pub async fn do_delete_index(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    access: Access,
 ) -> Result<UpdateResult, StorageError> {
     // TODO: Is this cancel safe!?
 
@@ -686,7 +810,7 @@ pub async fn do_delete_index(
 
     // TODO: Is `submit_collection_meta_op` cancel-safe!? Should be, I think?.. 🤔
     dispatcher
-        .submit_collection_meta_op(consensus_op, wait_timeout)
+        .submit_collection_meta_op(consensus_op, &access, wait_timeout)
         .await?;
 
     do_delete_index_internal(
@@ -697,6 +821,7 @@ pub async fn do_delete_index(
         shard_selection,
         wait,
         ordering,
+        access,
     )
     .await
 }
@@ -708,6 +833,7 @@ pub async fn do_core_search_points(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     let batch_res = do_core_search_batch_points(
         toc,
@@ -718,6 +844,7 @@ pub async fn do_core_search_points(
         read_consistency,
         shard_selection,
         timeout,
+        access,
     )
     .await?;
     batch_res
@@ -732,6 +859,7 @@ pub async fn do_search_batch_points(
     requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
@@ -760,6 +888,7 @@ pub async fn do_search_batch_points(
                 read_consistency,
                 shard_selector,
                 timeout,
+                &access,
             );
             res.push(req);
             Ok(())
@@ -778,6 +907,7 @@ pub async fn do_core_search_batch_points(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     toc.core_search_batch(
         collection_name,
@@ -785,6 +915,7 @@ pub async fn do_core_search_batch_points(
         read_consistency,
         shard_selection,
         timeout,
+        &access,
     )
     .await
 }
@@ -796,6 +927,7 @@ pub async fn do_search_point_groups(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -803,6 +935,7 @@ pub async fn do_search_point_groups(
         read_consistency,
         shard_selection,
         timeout,
+        &access,
     )
     .await
 }
@@ -814,6 +947,7 @@ pub async fn do_recommend_point_groups(
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
@@ -821,6 +955,7 @@ pub async fn do_recommend_point_groups(
         read_consistency,
         shard_selection,
         timeout,
+        &access,
     )
     .await
 }
@@ -832,6 +967,7 @@ pub async fn do_discover_points(
     read_consistency: Option<ReadConsistency>,
     shard_selector: ShardSelectorInternal,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     toc.discover(
         collection_name,
@@ -839,6 +975,7 @@ pub async fn do_discover_points(
         read_consistency,
         shard_selector,
         timeout,
+        &access,
     )
     .await
 }
@@ -849,6 +986,7 @@ pub async fn do_discover_batch_points(
     request: DiscoverRequestBatch,
     read_consistency: Option<ReadConsistency>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     let requests = request
         .searches
@@ -863,8 +1001,35 @@ pub async fn do_discover_batch_points(
         })
         .collect();
 
-    toc.discover_batch(collection_name, requests, read_consistency, timeout)
-        .await
+    toc.discover_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        timeout,
+        &access,
+    )
+    .await
+}
+
+pub async fn do_query_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: QueryRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access: Access,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    let request = ShardQueryRequest::try_from(request)?;
+    toc.query(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        timeout,
+        &access,
+    )
+    .await
 }
 
 pub async fn do_count_points(
@@ -873,20 +1038,65 @@ pub async fn do_count_points(
     request: CountRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access: Access,
 ) -> Result<CountResult, StorageError> {
-    toc.count(collection_name, request, read_consistency, shard_selection)
+    toc.count(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        &access,
+    )
+    .await
+}
+
+pub async fn do_facet(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FacetRequestInternal,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<FacetResponse, StorageError> {
+    toc.facet(collection_name, request, shard_selection, &access)
         .await
 }
 
+pub async fn do_search_points_matrix(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchMatrixRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, StorageError> {
+    toc.search_points_matrix(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        timeout,
+        &access,
+    )
+    .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: PointRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access: Access,
 ) -> Result<Vec<Record>, StorageError> {
-    toc.retrieve(collection_name, request, read_consistency, shard_selection)
-        .await
+    toc.retrieve(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        &access,
+    )
+    .await
 }
 
 pub async fn do_scroll_points(
@@ -895,7 +1105,78 @@ pub async fn do_scroll_points(
     request: ScrollRequestInternal,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
+    access: Access,
 ) -> Result<ScrollResult, StorageError> {
-    toc.scroll(collection_name, request, read_consistency, shard_selection)
-        .await
+    toc.scroll(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        &access,
+    )
+    .await
+}
+
+/// Interval between reads of the changes, once all the available changes are streamed
+const CHANGES_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Stream changes of the collection as they happen, see [`TableOfContent::read_changes`]
+///
+/// The first batches are read right away, so errors in the request are reported before streaming.
+/// Only batches, which advance the position of a shard, are streamed.
+/// The stream ends after the first error.
+pub async fn do_stream_changes(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    mut request: ChangesRequest,
+    access: Access,
+) -> Result<impl Stream<Item = Result<ChangesBatch, StorageError>>, StorageError> {
+    let first_batches = toc
+        .read_changes(&collection_name, &request, &access)
+        .await?;
+    let first_batches = advance_changes_offsets(&mut request.offsets, first_batches);
+
+    let batches = stream::try_unfold(
+        (request, Some(first_batches)),
+        move |(mut request, first_batches)| {
+            let toc = toc.clone();
+            let collection_name = collection_name.clone();
+            let access = access.clone();
+            async move {
+                if let Some(batches) = first_batches.filter(|batches| !batches.is_empty()) {
+                    return Ok::<_, StorageError>(Some((batches, (request, None))));
+                }
+                loop {
+                    tokio::time::sleep(CHANGES_POLL_INTERVAL).await;
+                    let batches = toc
+                        .read_changes(&collection_name, &request, &access)
+                        .await?;
+                    let batches = advance_changes_offsets(&mut request.offsets, batches);
+                    if !batches.is_empty() {
+                        return Ok(Some((batches, (request, None))));
+                    }
+                }
+            }
+        },
+    );
+
+    Ok(batches
+        .map_ok(|batches| stream::iter(batches.into_iter().map(Ok)))
+        .try_flatten())
+}
+
+/// Remember the next offsets of the shards, keep only the batches, which advance them
+fn advance_changes_offsets(
+    offsets: &mut HashMap<ShardId, ChangesOffset>,
+    batches: Vec<ChangesBatch>,
+) -> Vec<ChangesBatch> {
+    batches
+        .into_iter()
+        .filter(|batch| {
+            offsets
+                .insert(batch.shard_id, batch.next_offset.clone())
+                .as_ref()
+                != Some(&batch.next_offset)
+        })
+        .collect()
 }
@@ -9,6 +9,7 @@ use collection::operations::snapshot_ops::{
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::ShardId;
+use rbac::access::{Access, AccessLevel};
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots;
 use storage::content_manager::toc::TableOfContent;
@@ -20,9 +21,12 @@ use super::http_client::HttpClient;
 /// This function is cancel safe.
 pub async fn create_shard_snapshot(
     toc: Arc<TableOfContent>,
+    access: Access,
     collection_name: String,
     shard_id: ShardId,
 ) -> Result<SnapshotDescription, StorageError> {
+    access.check_whole_collection_access(&collection_name, AccessLevel::Manage)?;
+
     let collection = toc.get_collection(&collection_name).await?;
 
     let snapshot = collection
@@ -37,9 +41,12 @@ pub async fn create_shard_snapshot(
 /// This function is cancel safe.
 pub async fn list_shard_snapshots(
     toc: Arc<TableOfContent>,
+    access: Access,
     collection_name: String,
     shard_id: ShardId,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
+    access.check_whole_collection_access(&collection_name, AccessLevel::Manage)?;
+
     let collection = toc.get_collection(&collection_name).await?;
     let snapshots = collection.list_shard_snapshots(shard_id).await?;
     Ok(snapshots)
@@ -50,10 +57,13 @@ pub async fn list_shard_snapshots(
 /// This function is cancel safe.
 pub async fn delete_shard_snapshot(
     toc: Arc<TableOfContent>,
+    access: Access,
     collection_name: String,
     shard_id: ShardId,
     snapshot_name: String,
 ) -> Result<(), StorageError> {
+    access.check_whole_collection_access(&collection_name, AccessLevel::Manage)?;
+
     let collection = toc.get_collection(&collection_name).await?;
     let snapshot_path = collection
         .get_shard_snapshot_path(shard_id, &snapshot_name)
@@ -69,8 +79,10 @@ pub async fn delete_shard_snapshot(
 /// # Cancel safety
 ///
 /// This function is cancel safe.
+#[allow(clippy::too_many_arguments)]
 pub async fn recover_shard_snapshot(
     toc: Arc<TableOfContent>,
+    access: Access,
     collection_name: String,
     shard_id: ShardId,
     snapshot_location: ShardSnapshotLocation,
@@ -78,6 +90,8 @@ pub async fn recover_shard_snapshot(
     checksum: Option<String>,
     client: HttpClient,
 ) -> Result<(), StorageError> {
+    access.check_whole_collection_access(&collection_name, AccessLevel::Manage)?;
+
     // - `download_dir` handled by `tempfile` and would be deleted, if request is cancelled
     //   - remote snapshot is downloaded into `download_dir` and would be deleted with it
     // - `recover_shard_snapshot_impl` is *not* cancel safe
@@ -1141,6 +1141,7 @@ mod tests {
     use collection::operations::types::VectorParams;
     use collection::shards::channel_service::ChannelService;
     use common::cpu::CpuBudget;
+    use rbac::access::Access;
     use segment::types::Distance;
     use slog::Drain;
     use storage::content_manager::collection_meta_ops::{
@@ -1246,6 +1247,8 @@ mod tests {
                                 hnsw_config: None,
                                 quantization_config: None,
                                 on_disk: None,
+                                multivector_config: None,
+                                datatype: None,
                             }
                             .into(),
                             sparse_vectors: None,
@@ -1259,8 +1262,11 @@ mod tests {
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            ttl_config: None,
+                            strict_mode_config: None,
                         },
                     )),
+                    &Access::full(),
                     None,
                 ),
             )
@@ -26,9 +26,11 @@ use collection::shards::channel_service::ChannelService;
 use consensus::Consensus;
 use slog::Drain;
 use startup::setup_panic_hook;
+use storage::content_manager::auto_replication::run_auto_replication;
 use storage::content_manager::consensus::operation_sender::OperationSender;
 use storage::content_manager::consensus::persistent::Persistent;
 use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
+use storage::content_manager::rebalance::executor::run_rebalancer;
 use storage::content_manager::toc::transfer::ShardTransferDispatcher;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
@@ -334,6 +336,17 @@ fn main() -> anyhow::Result<()> {
             }
         });
 
+        let auto_replication = &settings.storage.auto_replication;
+        if auto_replication.enabled {
+            let _auto_replication_handle = runtime_handle.spawn(run_auto_replication(
+                dispatcher_arc.as_ref().clone(),
+                Duration::from_secs(auto_replication.grace_period_sec),
+            ));
+        }
+
+        let _rebalancer_handle =
+            runtime_handle.spawn(run_rebalancer(dispatcher_arc.as_ref().clone()));
+
         let collections_to_recover_in_consensus = if is_new_deployment {
             let existing_collections = runtime_handle.block_on(toc_arc.all_collections());
             existing_collections
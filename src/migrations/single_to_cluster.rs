@@ -3,6 +3,7 @@ use std::sync::Arc;
 use collection::config::ShardingMethod;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::PeerId;
+use rbac::access::Access;
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollection, CreateCollectionOperation, CreateShardKey,
     SetShardReplicaState,
@@ -54,6 +55,8 @@ pub async fn handle_existing_collections(
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                ttl_config: collection_state.config.params.ttl_config,
+                strict_mode_config: collection_state.config.strict_mode_config,
             },
         );
 
@@ -107,7 +110,7 @@ pub async fn handle_existing_collections(
 
         for operation in consensus_operations {
             let _res = dispatcher_arc
-                .submit_collection_meta_op(operation, None)
+                .submit_collection_meta_op(operation, &Access::full(), None)
                 .await;
         }
 
@@ -122,6 +125,7 @@ pub async fn handle_existing_collections(
                             state: ReplicaState::Active,
                             from_state: None,
                         }),
+                        &Access::full(),
                         None,
                     )
                     .await;
@@ -1,7 +1,8 @@
 use api::grpc::models::{CollectionsResponse, VersionInfo};
+use collection::operations::changes::{ChangesBatch, ChangesRequest};
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, SetPayload, UpdatePayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::snapshot_ops::{
     ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
@@ -9,10 +10,12 @@ use collection::operations::snapshot_ops::{
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    FacetRequest, FacetResponse, GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest,
+    RecommendRequest, RecommendRequestBatch, Record, ScrollRequest, ScrollResult,
+    SearchGroupsRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest, SearchRequest, SearchRequestBatch, UpdateResult,
 };
+use collection::operations::universal_query::collection_query::QueryRequest;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
@@ -21,6 +24,8 @@ use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
 };
+use storage::content_manager::rebalance::state::RebalanceStatus;
+use storage::content_manager::rebalance::{RebalancePlan, RebalanceRequest};
 use storage::types::ClusterStatus;
 
 use crate::common::helpers::LocksOption;
@@ -80,6 +85,18 @@ struct AllDefinitions {
     bb: DiscoverRequestBatch,
     bc: VersionInfo,
     bd: CollectionExistence,
+    be: QueryRequest,
+    bf: FacetRequest,
+    bg: FacetResponse,
+    bh: SearchMatrixRequest,
+    bi: SearchMatrixOffsetsResponse,
+    bj: SearchMatrixPairsResponse,
+    bk: UpdatePayload,
+    bl: ChangesRequest,
+    bm: ChangesBatch,
+    bn: RebalanceRequest,
+    bo: RebalancePlan,
+    bp: RebalanceStatus,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -14,6 +14,7 @@ use api::grpc::qdrant::{
 use collection::operations::cluster_ops::{
     ClusterOperations, CreateShardingKeyOperation, DropShardingKeyOperation,
 };
+use rbac::access::AccessLevel;
 use storage::content_manager::conversions::error_to_status;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
@@ -21,6 +22,7 @@ use tonic::{Request, Response, Status};
 use super::validate;
 use crate::common::collections::*;
 use crate::tonic::api::collections_common::get;
+use crate::tonic::auth::extract_access;
 
 pub struct CollectionsService {
     dispatcher: Arc<Dispatcher>,
@@ -33,7 +35,7 @@ impl CollectionsService {
 
     async fn perform_operation<O>(
         &self,
-        request: Request<O>,
+        mut request: Request<O>,
     ) -> Result<Response<CollectionOperationResponse>, Status>
     where
         O: WithTimeout
@@ -43,11 +45,12 @@ impl CollectionsService {
             >,
     {
         let timing = Instant::now();
+        let access = extract_access(&mut request);
         let operation = request.into_inner();
         let wait_timeout = operation.wait_timeout();
         let result = self
             .dispatcher
-            .submit_collection_meta_op(operation.try_into()?, wait_timeout)
+            .submit_collection_meta_op(operation.try_into()?, &access, wait_timeout)
             .await
             .map_err(error_to_status)?;
 
@@ -57,15 +60,22 @@ impl CollectionsService {
 
     async fn list_aliases(
         &self,
-        _request: Request<ListAliasesRequest>,
+        mut request: Request<ListAliasesRequest>,
     ) -> Result<Response<ListAliasesResponse>, Status> {
         let timing = Instant::now();
+        let access = extract_access(&mut request);
         let aliases = self
             .dispatcher
             .toc()
             .list_aliases()
             .await
-            .map(|aliases| aliases.into_iter().map(|alias| alias.into()).collect())
+            .map(|aliases| {
+                aliases
+                    .into_iter()
+                    .filter(|alias| access.is_collection_visible(&alias.collection_name))
+                    .map(|alias| alias.into())
+                    .collect()
+            })
             .map_err(error_to_status)?;
         let response = ListAliasesResponse {
             aliases,
@@ -76,10 +86,14 @@ impl CollectionsService {
 
     async fn list_collection_aliases(
         &self,
-        request: Request<ListCollectionAliasesRequest>,
+        mut request: Request<ListCollectionAliasesRequest>,
     ) -> Result<Response<ListAliasesResponse>, Status> {
         let timing = Instant::now();
+        let access = extract_access(&mut request);
         let ListCollectionAliasesRequest { collection_name } = request.into_inner();
+        access
+            .check_collection_access(&collection_name, AccessLevel::Read)
+            .map_err(|err| error_to_status(err.into()))?;
         let aliases = self
             .dispatcher
             .toc()
@@ -107,19 +121,21 @@ impl CollectionsService {
 impl Collections for CollectionsService {
     async fn get(
         &self,
-        request: Request<GetCollectionInfoRequest>,
+        mut request: Request<GetCollectionInfoRequest>,
     ) -> Result<Response<GetCollectionInfoResponse>, Status> {
         validate(request.get_ref())?;
-        get(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        get(self.dispatcher.as_ref(), request.into_inner(), None, access).await
     }
 
     async fn list(
         &self,
-        request: Request<ListCollectionsRequest>,
+        mut request: Request<ListCollectionsRequest>,
     ) -> Result<Response<ListCollectionsResponse>, Status> {
         validate(request.get_ref())?;
         let timing = Instant::now();
-        let result = do_list_collections(&self.dispatcher).await;
+        let access = extract_access(&mut request);
+        let result = do_list_collections(&self.dispatcher, access).await;
 
         let response = ListCollectionsResponse::from((timing, result));
         Ok(Response::new(response))
@@ -175,12 +191,13 @@ impl Collections for CollectionsService {
 
     async fn collection_exists(
         &self,
-        request: Request<CollectionExistsRequest>,
+        mut request: Request<CollectionExistsRequest>,
     ) -> Result<Response<CollectionExistsResponse>, Status> {
         let timing = Instant::now();
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let CollectionExistsRequest { collection_name } = request.into_inner();
-        let result = do_collection_exists(self.dispatcher.toc(), &collection_name)
+        let result = do_collection_exists(self.dispatcher.toc(), access, &collection_name)
             .await
             .map_err(error_to_status)?;
         let response = CollectionExistsResponse {
@@ -193,11 +210,13 @@ impl Collections for CollectionsService {
 
     async fn collection_cluster_info(
         &self,
-        request: Request<CollectionClusterInfoRequest>,
+        mut request: Request<CollectionClusterInfoRequest>,
     ) -> Result<Response<CollectionClusterInfoResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let response = do_get_collection_cluster(
             self.dispatcher.toc(),
+            access,
             request.into_inner().collection_name.as_str(),
         )
         .await
@@ -209,9 +228,10 @@ impl Collections for CollectionsService {
 
     async fn update_collection_cluster_setup(
         &self,
-        request: Request<UpdateCollectionClusterSetupRequest>,
+        mut request: Request<UpdateCollectionClusterSetupRequest>,
     ) -> Result<Response<UpdateCollectionClusterSetupResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let UpdateCollectionClusterSetupRequest {
             collection_name,
             operation,
@@ -220,6 +240,7 @@ impl Collections for CollectionsService {
         } = request.into_inner();
         let result = do_update_collection_cluster(
             self.dispatcher.as_ref(),
+            access,
             collection_name,
             operation
                 .ok_or(Status::new(tonic::Code::InvalidArgument, "empty operation"))?
@@ -235,8 +256,9 @@ impl Collections for CollectionsService {
 
     async fn create_shard_key(
         &self,
-        request: Request<CreateShardKeyRequest>,
+        mut request: Request<CreateShardKeyRequest>,
     ) -> Result<Response<CreateShardKeyResponse>, Status> {
+        let access = extract_access(&mut request);
         let CreateShardKeyRequest {
             collection_name,
             request,
@@ -255,6 +277,7 @@ impl Collections for CollectionsService {
 
         let result = do_update_collection_cluster(
             self.dispatcher.as_ref(),
+            access,
             collection_name,
             operation,
             timeout,
@@ -267,8 +290,9 @@ impl Collections for CollectionsService {
 
     async fn delete_shard_key(
         &self,
-        request: Request<DeleteShardKeyRequest>,
+        mut request: Request<DeleteShardKeyRequest>,
     ) -> Result<Response<DeleteShardKeyResponse>, Status> {
+        let access = extract_access(&mut request);
         let DeleteShardKeyRequest {
             collection_name,
             request,
@@ -287,6 +311,7 @@ impl Collections for CollectionsService {
 
         let result = do_update_collection_cluster(
             self.dispatcher.as_ref(),
+            access,
             collection_name,
             operation,
             timeout,
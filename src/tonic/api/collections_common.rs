@@ -2,6 +2,7 @@ use std::time::Instant;
 
 use api::grpc::qdrant::{GetCollectionInfoRequest, GetCollectionInfoResponse};
 use collection::shards::shard::ShardId;
+use rbac::access::Access;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Response, Status};
@@ -12,10 +13,11 @@ pub async fn get(
     toc: &TableOfContent,
     get_collection_info_request: GetCollectionInfoRequest,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<GetCollectionInfoResponse>, Status> {
     let timing = Instant::now();
     let collection_name = get_collection_info_request.collection_name;
-    let result = do_get_collection(toc, &collection_name, shard_selection)
+    let result = do_get_collection(toc, access, &collection_name, shard_selection)
         .await
         .map_err(error_to_status)?;
     let response = GetCollectionInfoResponse {
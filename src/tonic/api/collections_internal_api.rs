@@ -7,6 +7,7 @@ use api::grpc::qdrant::{
     GetShardRecoveryPointRequest, GetShardRecoveryPointResponse, InitiateShardTransferRequest,
     UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
+use rbac::access::Access;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
@@ -43,6 +44,7 @@ impl CollectionsInternal for CollectionsInternalService {
             self.toc.as_ref(),
             get_collection_info_request,
             Some(shard_id),
+            Access::full(),
         )
         .await
     }
@@ -5,27 +5,31 @@ use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
+    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryPoints, QueryResponse,
+    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
+    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
+    SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse,
+    SetPayloadPoints, StreamChangesRequest, UpdateBatchPoints, UpdateBatchResponse,
+    UpdatePayloadPoints, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, recommend_groups, search_groups, update_batch,
-    update_vectors,
+    delete_vectors, discover, discover_batch, facet, query, recommend_groups, search_groups,
+    search_points_matrix_offsets, search_points_matrix_pairs, stream_changes, update_batch,
+    update_vectors, ChangesStream,
 };
 use super::validate;
 use crate::tonic::api::points_common::{
     clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
     delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
-    scroll, search, set_payload, upsert,
+    scroll, search, set_payload, update_payload, upsert,
 };
+use crate::tonic::auth::extract_access;
 
 pub struct PointsService {
     dispatcher: Arc<Dispatcher>,
@@ -41,14 +45,16 @@ impl PointsService {
 impl Points for PointsService {
     async fn upsert(
         &self,
-        request: Request<UpsertPoints>,
+        mut request: Request<UpsertPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         upsert(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -56,34 +62,39 @@ impl Points for PointsService {
 
     async fn delete(
         &self,
-        request: Request<DeletePoints>,
+        mut request: Request<DeletePoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         delete(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
     }
 
-    async fn get(&self, request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
+    async fn get(&self, mut request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
         validate(request.get_ref())?;
-        get(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        get(self.dispatcher.as_ref(), request.into_inner(), None, access).await
     }
 
     async fn update_vectors(
         &self,
-        request: Request<UpdatePointVectors>,
+        mut request: Request<UpdatePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         update_vectors(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -91,14 +102,16 @@ impl Points for PointsService {
 
     async fn delete_vectors(
         &self,
-        request: Request<DeletePointVectors>,
+        mut request: Request<DeletePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         delete_vectors(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -106,14 +119,16 @@ impl Points for PointsService {
 
     async fn set_payload(
         &self,
-        request: Request<SetPayloadPoints>,
+        mut request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         set_payload(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -121,14 +136,16 @@ impl Points for PointsService {
 
     async fn overwrite_payload(
         &self,
-        request: Request<SetPayloadPoints>,
+        mut request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         overwrite_payload(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -136,14 +153,16 @@ impl Points for PointsService {
 
     async fn delete_payload(
         &self,
-        request: Request<DeletePayloadPoints>,
+        mut request: Request<DeletePayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         delete_payload(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -151,14 +170,33 @@ impl Points for PointsService {
 
     async fn clear_payload(
         &self,
-        request: Request<ClearPayloadPoints>,
+        mut request: Request<ClearPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         clear_payload(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
+    }
+
+    async fn update_payload(
+        &self,
+        mut request: Request<UpdatePayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        update_payload(
+            self.dispatcher.toc().clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
         )
         .await
         .map(|resp| resp.map(Into::into))
@@ -166,51 +204,69 @@ impl Points for PointsService {
 
     async fn update_batch(
         &self,
-        request: Request<UpdateBatchPoints>,
+        mut request: Request<UpdateBatchPoints>,
     ) -> Result<Response<UpdateBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         update_batch(
             self.dispatcher.toc().clone(),
             request.into_inner(),
             None,
             None,
+            access,
         )
         .await
     }
 
     async fn create_field_index(
         &self,
-        request: Request<CreateFieldIndexCollection>,
+        mut request: Request<CreateFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
-        create_field_index(self.dispatcher.clone(), request.into_inner(), None, None)
-            .await
-            .map(|resp| resp.map(Into::into))
+        let access = extract_access(&mut request);
+        create_field_index(
+            self.dispatcher.clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
     }
 
     async fn delete_field_index(
         &self,
-        request: Request<DeleteFieldIndexCollection>,
+        mut request: Request<DeleteFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
-        delete_field_index(self.dispatcher.clone(), request.into_inner(), None, None)
-            .await
-            .map(|resp| resp.map(Into::into))
+        let access = extract_access(&mut request);
+        delete_field_index(
+            self.dispatcher.clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
     }
 
     async fn search(
         &self,
-        request: Request<SearchPoints>,
+        mut request: Request<SearchPoints>,
     ) -> Result<Response<SearchResponse>, Status> {
         validate(request.get_ref())?;
-        search(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        search(self.dispatcher.as_ref(), request.into_inner(), None, access).await
     }
 
     async fn search_batch(
         &self,
-        request: Request<SearchBatchPoints>,
+        mut request: Request<SearchBatchPoints>,
     ) -> Result<Response<SearchBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let SearchBatchPoints {
             collection_name,
             search_points,
@@ -237,39 +293,44 @@ impl Points for PointsService {
             requests,
             read_consistency,
             timeout,
+            access,
         )
         .await
     }
 
     async fn search_groups(
         &self,
-        request: Request<SearchPointGroups>,
+        mut request: Request<SearchPointGroups>,
     ) -> Result<Response<SearchGroupsResponse>, Status> {
         validate(request.get_ref())?;
-        search_groups(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        search_groups(self.dispatcher.as_ref(), request.into_inner(), None, access).await
     }
 
     async fn scroll(
         &self,
-        request: Request<ScrollPoints>,
+        mut request: Request<ScrollPoints>,
     ) -> Result<Response<ScrollResponse>, Status> {
         validate(request.get_ref())?;
-        scroll(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        scroll(self.dispatcher.as_ref(), request.into_inner(), None, access).await
     }
 
     async fn recommend(
         &self,
-        request: Request<RecommendPoints>,
+        mut request: Request<RecommendPoints>,
     ) -> Result<Response<RecommendResponse>, Status> {
         validate(request.get_ref())?;
-        recommend(self.dispatcher.as_ref(), request.into_inner()).await
+        let access = extract_access(&mut request);
+        recommend(self.dispatcher.as_ref(), request.into_inner(), access).await
     }
 
     async fn recommend_batch(
         &self,
-        request: Request<RecommendBatchPoints>,
+        mut request: Request<RecommendBatchPoints>,
     ) -> Result<Response<RecommendBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let RecommendBatchPoints {
             collection_name,
             recommend_points,
@@ -282,31 +343,35 @@ impl Points for PointsService {
             recommend_points,
             read_consistency,
             timeout.map(Duration::from_secs),
+            access,
         )
         .await
     }
 
     async fn recommend_groups(
         &self,
-        request: Request<RecommendPointGroups>,
+        mut request: Request<RecommendPointGroups>,
     ) -> Result<Response<RecommendGroupsResponse>, Status> {
         validate(request.get_ref())?;
-        recommend_groups(self.dispatcher.as_ref(), request.into_inner()).await
+        let access = extract_access(&mut request);
+        recommend_groups(self.dispatcher.as_ref(), request.into_inner(), access).await
     }
 
     async fn discover(
         &self,
-        request: Request<DiscoverPoints>,
+        mut request: Request<DiscoverPoints>,
     ) -> Result<Response<DiscoverResponse>, Status> {
         validate(request.get_ref())?;
-        discover(self.dispatcher.as_ref(), request.into_inner()).await
+        let access = extract_access(&mut request);
+        discover(self.dispatcher.as_ref(), request.into_inner(), access).await
     }
 
     async fn discover_batch(
         &self,
-        request: Request<DiscoverBatchPoints>,
+        mut request: Request<DiscoverBatchPoints>,
     ) -> Result<Response<DiscoverBatchResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let DiscoverBatchPoints {
             collection_name,
             discover_points,
@@ -319,15 +384,64 @@ impl Points for PointsService {
             discover_points,
             read_consistency,
             timeout.map(Duration::from_secs),
+            access,
         )
         .await
     }
 
+    async fn query(
+        &self,
+        mut request: Request<QueryPoints>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        query(self.dispatcher.as_ref(), request.into_inner(), access).await
+    }
+
     async fn count(
         &self,
-        request: Request<CountPoints>,
+        mut request: Request<CountPoints>,
     ) -> Result<Response<CountResponse>, Status> {
         validate(request.get_ref())?;
-        count(self.dispatcher.as_ref(), request.into_inner(), None).await
+        let access = extract_access(&mut request);
+        count(self.dispatcher.as_ref(), request.into_inner(), None, access).await
+    }
+
+    async fn facet(
+        &self,
+        mut request: Request<FacetCounts>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        facet(self.dispatcher.as_ref(), request.into_inner(), None, access).await
+    }
+
+    async fn search_matrix_pairs(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        search_points_matrix_pairs(self.dispatcher.as_ref(), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_offsets(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        search_points_matrix_offsets(self.dispatcher.as_ref(), request.into_inner(), access).await
+    }
+
+    type StreamChangesStream = ChangesStream;
+
+    async fn stream_changes(
+        &self,
+        mut request: Request<StreamChangesRequest>,
+    ) -> Result<Response<Self::StreamChangesStream>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        stream_changes(self.dispatcher.toc().clone(), request.into_inner(), access).await
     }
 }
@@ -1,3 +1,4 @@
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -5,34 +6,42 @@ use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
+    points_update_operation, BatchResult, ChangesBatch, ClearPayloadPoints, CoreSearchPoints,
+    CountPoints, CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection,
+    DeletePayloadPoints, DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints,
+    DiscoverResponse, FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse,
+    PayloadIndexParams, PointsOperationResponseInternal, PointsSelector, QueryPoints,
+    QueryResponse, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
     RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
+    SearchPoints, SearchResponse, SetPayloadPoints, StreamChangesRequest, SyncPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePayloadPoints, UpdatePointVectors, UpsertPoints,
 };
+use collection::operations::changes::ChangesRequest;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    try_discover_request_from_grpc, try_order_by_from_grpc, try_points_selector_from_grpc,
+    write_ordering_from_proto,
 };
-use collection::operations::payload_ops::DeletePayload;
+use collection::operations::payload_ops::{DeletePayload, UpdatePayload};
 use collection::operations::point_ops::{
     self, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
 };
 use collection::operations::shard_key_selector::ShardKeySelector;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, OrderByInterface,
-    PointRequestInternal, QueryEnum, RecommendExample, ScrollRequestInternal,
+    default_exact_count, CollectionSearchMatrixResponse, CoreSearchRequest, CoreSearchRequestBatch,
+    FacetRequestInternal, PointRequestInternal, QueryEnum, RecommendExample, ScrollRequestInternal,
+    SearchMatrixRequestInternal,
 };
+use collection::operations::universal_query::collection_query::QueryRequestInternal;
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
 use collection::shards::shard::ShardId;
+use futures::{Stream, TryStreamExt as _};
 use itertools::Itertools;
+use rbac::access::Access;
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
@@ -44,15 +53,17 @@ use tonic::{Response, Status};
 use crate::common::points::{
     do_clear_payload, do_core_search_points, do_count_points, do_create_index,
     do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload, do_scroll_points,
-    do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_delete_points, do_delete_vectors, do_facet, do_get_points, do_overwrite_payload,
+    do_query_points, do_scroll_points, do_search_batch_points, do_search_points_matrix,
+    do_set_payload, do_stream_changes, do_update_payload, do_update_vectors, do_upsert_points,
+    CreateFieldIndex,
 };
 
 fn extract_points_selector(
     points_selector: Option<PointsSelector>,
 ) -> Result<(Option<Vec<ExtendedPointId>>, Option<Filter>), Status> {
     let (points, filter) = if let Some(points_selector) = points_selector {
-        let points_selector = try_points_selector_from_grpc(points_selector, None)?;
+        let points_selector = try_points_selector_from_grpc(points_selector, None, vec![])?;
         match points_selector {
             point_ops::PointsSelector::PointIdsSelector(points) => (Some(points.points), None),
             point_ops::PointsSelector::FilterSelector(filter) => (None, Some(filter.filter)),
@@ -96,6 +107,7 @@ pub async fn upsert(
     upsert_points: UpsertPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let UpsertPoints {
         collection_name,
@@ -103,6 +115,7 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        preconditions,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -111,6 +124,10 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        preconditions: preconditions
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -121,6 +138,7 @@ pub async fn upsert(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -173,6 +191,7 @@ pub async fn sync(
             wait.unwrap_or(false),
             write_ordering_from_proto(ordering)?,
             shard_selector,
+            &Access::full(),
         )
         .await
         .map_err(error_to_status)?;
@@ -186,6 +205,7 @@ pub async fn delete(
     delete_points: DeletePoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let DeletePoints {
         collection_name,
@@ -193,11 +213,12 @@ pub async fn delete(
         points,
         ordering,
         shard_key_selector,
+        preconditions,
     } = delete_points;
 
     let points_selector = match points {
         None => return Err(Status::invalid_argument("PointSelector is missing")),
-        Some(p) => try_points_selector_from_grpc(p, shard_key_selector)?,
+        Some(p) => try_points_selector_from_grpc(p, shard_key_selector, preconditions)?,
     };
 
     let timing = Instant::now();
@@ -209,6 +230,7 @@ pub async fn delete(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -222,6 +244,7 @@ pub async fn update_vectors(
     update_point_vectors: UpdatePointVectors,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let UpdatePointVectors {
         collection_name,
@@ -259,6 +282,7 @@ pub async fn update_vectors(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -272,6 +296,7 @@ pub async fn delete_vectors(
     delete_point_vectors: DeletePointVectors,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let DeletePointVectors {
         collection_name,
@@ -304,6 +329,7 @@ pub async fn delete_vectors(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -317,6 +343,7 @@ pub async fn set_payload(
     set_payload_points: SetPayloadPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let SetPayloadPoints {
         collection_name,
@@ -326,6 +353,7 @@ pub async fn set_payload(
         ordering,
         shard_key_selector,
         key,
+        preconditions,
     } = set_payload_points;
     let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
 
@@ -336,6 +364,10 @@ pub async fn set_payload(
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         key,
+        preconditions: preconditions
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()?,
     };
 
     let timing = Instant::now();
@@ -347,6 +379,7 @@ pub async fn set_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -360,6 +393,7 @@ pub async fn overwrite_payload(
     set_payload_points: SetPayloadPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let SetPayloadPoints {
         collection_name,
@@ -368,6 +402,7 @@ pub async fn overwrite_payload(
         points_selector,
         ordering,
         shard_key_selector,
+        preconditions,
         ..
     } = set_payload_points;
 
@@ -379,6 +414,10 @@ pub async fn overwrite_payload(
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         // overwrite operation don't support indicate path of property
         key: None,
+        preconditions: preconditions
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()?,
     };
 
     let timing = Instant::now();
@@ -390,6 +429,7 @@ pub async fn overwrite_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -403,6 +443,7 @@ pub async fn delete_payload(
     delete_payload_points: DeletePayloadPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let DeletePayloadPoints {
         collection_name,
@@ -411,6 +452,7 @@ pub async fn delete_payload(
         points_selector,
         ordering,
         shard_key_selector,
+        preconditions,
     } = delete_payload_points;
     let keys = keys.iter().map(|k| json_path_from_proto(k)).try_collect()?;
 
@@ -420,6 +462,10 @@ pub async fn delete_payload(
         points,
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        preconditions: preconditions
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()?,
     };
 
     let timing = Instant::now();
@@ -431,6 +477,56 @@ pub async fn delete_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn update_payload(
+    toc: Arc<TableOfContent>,
+    update_payload_points: UpdatePayloadPoints,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let UpdatePayloadPoints {
+        collection_name,
+        wait,
+        updates,
+        points_selector,
+        condition,
+        ordering,
+        shard_key_selector,
+        preconditions,
+    } = update_payload_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = UpdatePayload {
+        updates: updates.into_iter().map(TryInto::try_into).try_collect()?,
+        points,
+        filter,
+        condition: condition.map(TryInto::try_into).transpose()?,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        preconditions: preconditions
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()?,
+    };
+
+    let timing = Instant::now();
+    let result = do_update_payload(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -444,6 +540,7 @@ pub async fn clear_payload(
     clear_payload_points: ClearPayloadPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let ClearPayloadPoints {
         collection_name,
@@ -451,11 +548,12 @@ pub async fn clear_payload(
         points,
         ordering,
         shard_key_selector,
+        preconditions,
     } = clear_payload_points;
 
     let points_selector = match points {
         None => return Err(Status::invalid_argument("PointSelector is missing")),
-        Some(p) => try_points_selector_from_grpc(p, shard_key_selector)?,
+        Some(p) => try_points_selector_from_grpc(p, shard_key_selector, preconditions)?,
     };
 
     let timing = Instant::now();
@@ -467,6 +565,7 @@ pub async fn clear_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -480,6 +579,7 @@ pub async fn update_batch(
     update_batch_points: UpdateBatchPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<UpdateBatchResponse>, Status> {
     let UpdateBatchPoints {
         collection_name,
@@ -509,9 +609,11 @@ pub async fn update_batch(
                         wait,
                         ordering,
                         shard_key_selector,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -524,9 +626,11 @@ pub async fn update_batch(
                         points: Some(points),
                         ordering,
                         shard_key_selector: None,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -548,9 +652,11 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         key,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -573,9 +679,11 @@ pub async fn update_batch(
                         shard_key_selector,
                         // overwrite operation don't support it
                         key: None,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -595,9 +703,11 @@ pub async fn update_batch(
                         points_selector,
                         ordering,
                         shard_key_selector,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -613,9 +723,11 @@ pub async fn update_batch(
                         points,
                         ordering,
                         shard_key_selector,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -636,6 +748,7 @@ pub async fn update_batch(
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -658,6 +771,7 @@ pub async fn update_batch(
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -670,9 +784,11 @@ pub async fn update_batch(
                         points: Some(selector),
                         ordering,
                         shard_key_selector: None,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -688,9 +804,11 @@ pub async fn update_batch(
                         points,
                         ordering,
                         shard_key_selector,
+                        preconditions: vec![],
                     },
                     clock_tag,
                     shard_selection,
+                    access.clone(),
                 )
                 .await
             }
@@ -742,6 +860,7 @@ fn convert_field_type(
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::Uuid => Some(PayloadSchemaType::Uuid.into()),
         },
         // Parameterized index with mismatching types
         (
@@ -766,6 +885,7 @@ pub async fn create_field_index(
     create_field_index_collection: CreateFieldIndexCollection,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let CreateFieldIndexCollection {
         collection_name,
@@ -793,6 +913,7 @@ pub async fn create_field_index(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -806,6 +927,7 @@ pub async fn create_field_index_internal(
     create_field_index_collection: CreateFieldIndexCollection,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let CreateFieldIndexCollection {
         collection_name,
@@ -829,6 +951,7 @@ pub async fn create_field_index_internal(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -842,6 +965,7 @@ pub async fn delete_field_index(
     delete_field_index_collection: DeleteFieldIndexCollection,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let DeleteFieldIndexCollection {
         collection_name,
@@ -861,6 +985,7 @@ pub async fn delete_field_index(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -874,6 +999,7 @@ pub async fn delete_field_index_internal(
     delete_field_index_collection: DeleteFieldIndexCollection,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let DeleteFieldIndexCollection {
         collection_name,
@@ -893,6 +1019,7 @@ pub async fn delete_field_index_internal(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -905,6 +1032,7 @@ pub async fn search(
     toc: &TableOfContent,
     search_points: SearchPoints,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<SearchResponse>, Status> {
     let SearchPoints {
         collection_name,
@@ -921,10 +1049,16 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        vectors_count,
+        mmr,
     } = search_points;
 
-    let vector_struct =
-        api::grpc::conversions::into_named_vector_struct(vector_name, vector, sparse_indices)?;
+    let vector_struct = api::grpc::conversions::into_named_vector_struct(
+        vector_name,
+        vector,
+        sparse_indices,
+        vectors_count,
+    )?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
@@ -941,6 +1075,7 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        mmr: mmr.map(Into::into),
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -953,6 +1088,7 @@ pub async fn search(
         read_consistency,
         shard_selector,
         timeout.map(Duration::from_secs),
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -974,15 +1110,22 @@ pub async fn core_search_batch(
     requests: Vec<(CoreSearchRequest, ShardSelectorInternal)>,
     read_consistency: Option<ReadConsistencyGrpc>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
     let timing = Instant::now();
 
-    let scored_points =
-        do_search_batch_points(toc, &collection_name, requests, read_consistency, timeout)
-            .await
-            .map_err(error_to_status)?;
+    let scored_points = do_search_batch_points(
+        toc,
+        &collection_name,
+        requests,
+        read_consistency,
+        timeout,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
 
     let response = SearchBatchResponse {
         result: scored_points
@@ -1033,6 +1176,7 @@ pub async fn core_search_list(
             read_consistency,
             shard_selection,
             timeout,
+            &Access::full(),
         )
         .await
         .map_err(error_to_status)?;
@@ -1054,6 +1198,7 @@ pub async fn search_groups(
     toc: &TableOfContent,
     search_point_groups: SearchPointGroups,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<SearchGroupsResponse>, Status> {
     let search_groups_request = search_point_groups.clone().try_into()?;
 
@@ -1077,6 +1222,7 @@ pub async fn search_groups(
         read_consistency,
         shard_selector,
         timeout.map(Duration::from_secs),
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -1092,6 +1238,7 @@ pub async fn search_groups(
 pub async fn recommend(
     toc: &TableOfContent,
     recommend_points: RecommendPoints,
+    access: Access,
 ) -> Result<Response<RecommendResponse>, Status> {
     // TODO(luis): check if we can make this into a From impl
     let RecommendPoints {
@@ -1113,6 +1260,7 @@ pub async fn recommend(
         read_consistency,
         timeout,
         shard_key_selector,
+        mmr,
     } = recommend_points;
 
     let timeout = timeout.map(Duration::from_secs);
@@ -1154,6 +1302,7 @@ pub async fn recommend(
         score_threshold,
         using: using.map(|u| u.into()),
         lookup_from: lookup_from.map(|l| l.into()),
+        mmr: mmr.map(Into::into),
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1168,6 +1317,7 @@ pub async fn recommend(
             read_consistency,
             shard_selector,
             timeout,
+            &access,
         )
         .await
         .map_err(error_to_status)?;
@@ -1189,6 +1339,7 @@ pub async fn recommend_batch(
     recommend_points: Vec<RecommendPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Response<RecommendBatchResponse>, Status> {
     let mut requests = Vec::with_capacity(recommend_points.len());
 
@@ -1204,7 +1355,13 @@ pub async fn recommend_batch(
 
     let timing = Instant::now();
     let scored_points = toc
-        .recommend_batch(&collection_name, requests, read_consistency, timeout)
+        .recommend_batch(
+            &collection_name,
+            requests,
+            read_consistency,
+            timeout,
+            &access,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -1224,6 +1381,7 @@ pub async fn recommend_batch(
 pub async fn recommend_groups(
     toc: &TableOfContent,
     recommend_point_groups: RecommendPointGroups,
+    access: Access,
 ) -> Result<Response<RecommendGroupsResponse>, Status> {
     let recommend_groups_request = recommend_point_groups.clone().try_into()?;
 
@@ -1247,6 +1405,7 @@ pub async fn recommend_groups(
         read_consistency,
         shard_selector,
         timeout.map(Duration::from_secs),
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -1262,6 +1421,7 @@ pub async fn recommend_groups(
 pub async fn discover(
     toc: &TableOfContent,
     discover_points: DiscoverPoints,
+    access: Access,
 ) -> Result<Response<DiscoverResponse>, Status> {
     let (request, collection_name, read_consistency, timeout, shard_key_selector) =
         try_discover_request_from_grpc(discover_points)?;
@@ -1277,6 +1437,7 @@ pub async fn discover(
             read_consistency,
             shard_selector,
             timeout,
+            &access,
         )
         .await
         .map_err(error_to_status)?;
@@ -1292,12 +1453,51 @@ pub async fn discover(
     Ok(Response::new(response))
 }
 
+pub async fn query(
+    toc: &TableOfContent,
+    query_points: QueryPoints,
+    access: Access,
+) -> Result<Response<QueryResponse>, Status> {
+    let collection_name = query_points.collection_name.clone();
+    let read_consistency =
+        ReadConsistency::try_from_optional(query_points.read_consistency.clone())?;
+    let shard_key_selector = query_points.shard_key_selector.clone();
+    let timeout = query_points.timeout.map(Duration::from_secs);
+    let request = QueryRequestInternal::try_from(query_points)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timing = Instant::now();
+    let scored_points = do_query_points(
+        toc,
+        &collection_name,
+        request,
+        read_consistency,
+        shard_selector,
+        timeout,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = QueryResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn discover_batch(
     toc: &TableOfContent,
     collection_name: String,
     discover_points: Vec<DiscoverPoints>,
     read_consistency: Option<ReadConsistencyGrpc>,
     timeout: Option<Duration>,
+    access: Access,
 ) -> Result<Response<DiscoverBatchResponse>, Status> {
     let mut requests = Vec::with_capacity(discover_points.len());
 
@@ -1312,7 +1512,13 @@ pub async fn discover_batch(
 
     let timing = Instant::now();
     let scored_points = toc
-        .discover_batch(&collection_name, requests, read_consistency, timeout)
+        .discover_batch(
+            &collection_name,
+            requests,
+            read_consistency,
+            timeout,
+            &access,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -1332,6 +1538,7 @@ pub async fn scroll(
     toc: &TableOfContent,
     scroll_points: ScrollPoints,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<ScrollResponse>, Status> {
     let ScrollPoints {
         collection_name,
@@ -1343,6 +1550,8 @@ pub async fn scroll(
         read_consistency,
         shard_key_selector,
         order_by,
+        then_by,
+        cursor,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
@@ -1353,7 +1562,8 @@ pub async fn scroll(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
-        order_by: order_by.map(OrderByInterface::try_from).transpose()?,
+        order_by: try_order_by_from_grpc(order_by, then_by)?,
+        cursor,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1367,12 +1577,14 @@ pub async fn scroll(
         scroll_request,
         read_consistency,
         shard_selector,
+        access,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = ScrollResponse {
         next_page_offset: scrolled_points.next_page_offset.map(|n| n.into()),
+        next_page_cursor: scrolled_points.next_page_cursor,
         result: scrolled_points
             .points
             .into_iter()
@@ -1388,6 +1600,7 @@ pub async fn count(
     toc: &TableOfContent,
     count_points: CountPoints,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<CountResponse>, Status> {
     let CountPoints {
         collection_name,
@@ -1413,6 +1626,7 @@ pub async fn count(
         count_request,
         read_consistency,
         shard_selector,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -1425,10 +1639,121 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<FacetResponse>, Status> {
+    let collection_name = facet_counts.collection_name.clone();
+    let shard_key_selector = facet_counts.shard_key_selector.clone();
+
+    let facet_request = FacetRequestInternal::try_from(facet_counts)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timing = Instant::now();
+    let facet_response = do_facet(toc, &collection_name, facet_request, shard_selector, access)
+        .await
+        .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_response.hits.into_iter().map(Into::into).collect(),
+        ranges: facet_response.ranges.into_iter().map(Into::into).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub type ChangesStream = Pin<Box<dyn Stream<Item = Result<ChangesBatch, Status>> + Send>>;
+
+pub async fn stream_changes(
+    toc: Arc<TableOfContent>,
+    stream_changes_request: StreamChangesRequest,
+    access: Access,
+) -> Result<Response<ChangesStream>, Status> {
+    let collection_name = stream_changes_request.collection_name.clone();
+    let request = ChangesRequest::try_from(stream_changes_request)?;
+
+    let changes = do_stream_changes(toc, collection_name, request, access)
+        .await
+        .map_err(error_to_status)?
+        .map_ok(ChangesBatch::from)
+        .map_err(error_to_status);
+
+    Ok(Response::new(Box::pin(changes)))
+}
+
+async fn search_points_matrix(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, Status> {
+    let collection_name = search_matrix_points.collection_name.clone();
+    let read_consistency = search_matrix_points.read_consistency.clone();
+    let shard_key_selector = search_matrix_points.shard_key_selector.clone();
+    let timeout = search_matrix_points.timeout.map(Duration::from_secs);
+
+    let search_matrix_request = SearchMatrixRequestInternal::try_from(search_matrix_points)?;
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    do_search_points_matrix(
+        toc,
+        &collection_name,
+        search_matrix_request,
+        read_consistency,
+        shard_selector,
+        timeout,
+        access,
+    )
+    .await
+    .map_err(error_to_status)
+}
+
+pub async fn search_points_matrix_pairs(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_response = search_points_matrix(toc, search_matrix_points, access).await?;
+
+    let pairs_response =
+        collection::operations::types::SearchMatrixPairsResponse::from(search_matrix_response);
+    let response = SearchMatrixPairsResponse {
+        result: Some(pairs_response.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_points_matrix_offsets(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_response = search_points_matrix(toc, search_matrix_points, access).await?;
+
+    let offsets_response =
+        collection::operations::types::SearchMatrixOffsetsResponse::from(search_matrix_response);
+    let response = SearchMatrixOffsetsResponse {
+        result: Some(offsets_response.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
     shard_selection: Option<ShardId>,
+    access: Access,
 ) -> Result<Response<GetResponse>, Status> {
     let GetPoints {
         collection_name,
@@ -1462,6 +1787,7 @@ pub async fn get(
         point_request,
         read_consistency,
         shard_selector,
+        access,
     )
     .await
     .map_err(error_to_status)?;
@@ -1,25 +1,34 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, PointsOperationResponseInternal, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse,
-    SearchPointsInternal, SearchResponse, SetPayloadPointsInternal, SyncPointsInternal,
-    UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    FacetResponse, GetPointsInternal, GetResponse, IntermediateResult,
+    PointsOperationResponseInternal, QueryPointsInternal, QueryResponseInternal,
+    ReadChangesInternal, ReadChangesResponseInternal, RecommendPointsInternal, RecommendResponse,
+    SamplePointsInternal, SamplePointsResponse, ScrollPointsInternal, ScrollResponse,
+    SearchBatchPointsInternal, SearchBatchResponse, SearchPointsInternal, SearchResponse,
+    SetPayloadPointsInternal, SparseStatisticsInternal, SparseStatisticsResponse,
+    SyncPointsInternal, UpdatePayloadPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
+use collection::operations::changes::ChangesOffset;
+use collection::operations::types::{SampleRequestInternal, SparseStatisticsRequest};
+use collection::operations::universal_query::shard_query::ShardQueryRequest;
+use rbac::access::Access;
+use segment::types::WithPayloadInterface;
+use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
 use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    clear_payload, convert_shard_selector_for_read, count, create_field_index_internal, delete,
+    delete_field_index_internal, delete_payload, delete_vectors, facet, get, overwrite_payload,
+    recommend, scroll, set_payload, sync, update_payload, update_vectors, upsert,
 };
 
 /// This API is intended for P2P communication within a distributed deployment.
@@ -54,6 +63,7 @@ impl PointsInternal for PointsInternalService {
             upsert_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -77,6 +87,7 @@ impl PointsInternal for PointsInternalService {
             delete_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -100,6 +111,7 @@ impl PointsInternal for PointsInternalService {
             update_point_vectors,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -123,6 +135,7 @@ impl PointsInternal for PointsInternalService {
             delete_point_vectors,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -146,6 +159,7 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -169,6 +183,7 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -192,6 +207,7 @@ impl PointsInternal for PointsInternalService {
             delete_payload_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -215,6 +231,31 @@ impl PointsInternal for PointsInternalService {
             clear_payload_points,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
+        )
+        .await
+    }
+
+    async fn update_payload(
+        &self,
+        request: Request<UpdatePayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+        let UpdatePayloadPointsInternal {
+            update_payload_points,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let update_payload_points = update_payload_points
+            .ok_or_else(|| Status::invalid_argument("UpdatePayloadPoints is missing"))?;
+
+        update_payload(
+            self.toc.clone(),
+            update_payload_points,
+            clock_tag.map(Into::into),
+            shard_id,
+            Access::full(),
         )
         .await
     }
@@ -238,6 +279,7 @@ impl PointsInternal for PointsInternalService {
             create_field_index_collection,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -261,6 +303,7 @@ impl PointsInternal for PointsInternalService {
             delete_field_index_collection,
             clock_tag.map(Into::into),
             shard_id,
+            Access::full(),
         )
         .await
     }
@@ -332,7 +375,7 @@ impl PointsInternal for PointsInternalService {
 
         recommend_points.read_consistency = None; // *Have* to be `None`!
 
-        recommend(self.toc.as_ref(), recommend_points).await
+        recommend(self.toc.as_ref(), recommend_points, Access::full()).await
     }
 
     async fn scroll(
@@ -350,7 +393,7 @@ impl PointsInternal for PointsInternalService {
 
         scroll_points.read_consistency = None; // *Have* to be `None`!
 
-        scroll(self.toc.as_ref(), scroll_points, shard_id).await
+        scroll(self.toc.as_ref(), scroll_points, shard_id, Access::full()).await
     }
 
     async fn get(
@@ -368,7 +411,7 @@ impl PointsInternal for PointsInternalService {
 
         get_points.read_consistency = None; // *Have* to be `None`!
 
-        get(self.toc.as_ref(), get_points, shard_id).await
+        get(self.toc.as_ref(), get_points, shard_id, Access::full()).await
     }
 
     async fn count(
@@ -383,7 +426,199 @@ impl PointsInternal for PointsInternalService {
 
         let count_points =
             count_points.ok_or_else(|| Status::invalid_argument("CountPoints is missing"))?;
-        count(self.toc.as_ref(), count_points, shard_id).await
+        count(self.toc.as_ref(), count_points, shard_id, Access::full()).await
+    }
+
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let FacetCountsInternal {
+            facet_counts,
+            shard_id,
+        } = request.into_inner();
+
+        let facet_counts =
+            facet_counts.ok_or_else(|| Status::invalid_argument("FacetCounts is missing"))?;
+        facet(self.toc.as_ref(), facet_counts, shard_id, Access::full()).await
+    }
+
+    async fn sparse_statistics(
+        &self,
+        request: Request<SparseStatisticsInternal>,
+    ) -> Result<Response<SparseStatisticsResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let SparseStatisticsInternal {
+            collection_name,
+            vectors,
+            shard_id,
+        } = request.into_inner();
+
+        let request = SparseStatisticsRequest {
+            vectors: vectors
+                .into_iter()
+                .map(|(vector_name, indices)| (vector_name, indices.data))
+                .collect(),
+        };
+
+        let shard_selector = convert_shard_selector_for_read(shard_id, None);
+
+        let timing = Instant::now();
+        let statistics = self
+            .toc
+            .sparse_statistics(&collection_name, request, shard_selector)
+            .await
+            .map_err(error_to_status)?;
+
+        let response = SparseStatisticsResponse {
+            statistics: statistics
+                .into_iter()
+                .map(|(vector_name, statistics)| (vector_name, statistics.into()))
+                .collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn sample(
+        &self,
+        request: Request<SamplePointsInternal>,
+    ) -> Result<Response<SamplePointsResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let SamplePointsInternal {
+            collection_name,
+            filter,
+            limit,
+            with_payload,
+            with_vectors,
+            shard_id,
+        } = request.into_inner();
+
+        let request = SampleRequestInternal {
+            filter: filter.map(|f| f.try_into()).transpose()?,
+            limit: limit as usize,
+            with_payload: with_payload
+                .map(|wp| wp.try_into())
+                .transpose()?
+                .unwrap_or(WithPayloadInterface::Bool(false)),
+            with_vector: with_vectors
+                .map(|selector| selector.into())
+                .unwrap_or_default(),
+        };
+
+        let shard_selector = convert_shard_selector_for_read(shard_id, None);
+
+        let timing = Instant::now();
+        let sampled = self
+            .toc
+            .sample_points(&collection_name, request, shard_selector)
+            .await
+            .map_err(error_to_status)?;
+
+        let (result, keys) = sampled
+            .into_iter()
+            .map(|point| (point.record.into(), point.key))
+            .unzip();
+        let response = SamplePointsResponse {
+            result,
+            keys,
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn read_changes(
+        &self,
+        request: Request<ReadChangesInternal>,
+    ) -> Result<Response<ReadChangesResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+        let ReadChangesInternal {
+            collection_name,
+            shard_id,
+            offset,
+            limit,
+            with_payload,
+            with_vectors,
+        } = request.into_inner();
+
+        let offset = offset.map(ChangesOffset::from);
+        let with_payload = with_payload
+            .map(|wp| wp.try_into())
+            .transpose()?
+            .unwrap_or(WithPayloadInterface::Bool(false));
+        let with_vector = with_vectors
+            .map(|selector| selector.into())
+            .unwrap_or_default();
+
+        let timing = Instant::now();
+        let (changes, next_offset) = self
+            .toc
+            .read_local_changes(
+                &collection_name,
+                shard_id,
+                offset.as_ref(),
+                limit as usize,
+                &with_payload,
+                &with_vector,
+            )
+            .await
+            .map_err(error_to_status)?;
+
+        let response = ReadChangesResponseInternal {
+            changes: changes.into_iter().map(Into::into).collect(),
+            next_offset: Some(next_offset.into()),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn query(
+        &self,
+        request: Request<QueryPointsInternal>,
+    ) -> Result<Response<QueryResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+        let QueryPointsInternal {
+            collection_name,
+            query_points,
+            shard_id,
+            timeout,
+        } = request.into_inner();
+
+        let query_points =
+            query_points.ok_or_else(|| Status::invalid_argument("QueryShardPoints is missing"))?;
+        let request = ShardQueryRequest::try_from(query_points)?;
+        let timeout = timeout.map(Duration::from_secs);
+
+        let shard_selector = convert_shard_selector_for_read(shard_id, None);
+
+        let timing = Instant::now();
+        let shard_responses = self
+            .toc
+            .query_shards(&collection_name, request, None, shard_selector, timeout)
+            .await
+            .map_err(error_to_status)?;
+
+        // Concatenate results of the same root prefetch across selected shards
+        let mut intermediate_results: Vec<IntermediateResult> = Vec::new();
+        for shard_response in shard_responses {
+            intermediate_results.resize_with(shard_response.len(), Default::default);
+            for (intermediate, points) in intermediate_results.iter_mut().zip(shard_response) {
+                intermediate
+                    .result
+                    .extend(points.into_iter().map(|point| point.into()));
+            }
+        }
+
+        let response = QueryResponseInternal {
+            intermediate_results,
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
     }
 
     async fn sync(
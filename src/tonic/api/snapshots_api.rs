@@ -23,6 +23,7 @@ use super::{validate, validate_and_log};
 use crate::common;
 use crate::common::collections::{do_create_snapshot, do_list_snapshots};
 use crate::common::http_client::HttpClient;
+use crate::tonic::auth::extract_access;
 
 pub struct SnapshotsService {
     dispatcher: Arc<Dispatcher>,
@@ -38,13 +39,14 @@ impl SnapshotsService {
 impl Snapshots for SnapshotsService {
     async fn create(
         &self,
-        request: Request<CreateSnapshotRequest>,
+        mut request: Request<CreateSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let collection_name = request.into_inner().collection_name;
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
-        let response = do_create_snapshot(&dispatcher, &collection_name, true)
+        let response = do_create_snapshot(&dispatcher, access, &collection_name, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
@@ -55,13 +57,14 @@ impl Snapshots for SnapshotsService {
 
     async fn list(
         &self,
-        request: Request<ListSnapshotsRequest>,
+        mut request: Request<ListSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let collection_name = request.into_inner().collection_name;
 
         let timing = Instant::now();
-        let snapshots = do_list_snapshots(&self.dispatcher, &collection_name)
+        let snapshots = do_list_snapshots(&self.dispatcher, access, &collection_name)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(ListSnapshotsResponse {
@@ -72,18 +75,24 @@ impl Snapshots for SnapshotsService {
 
     async fn delete(
         &self,
-        request: Request<DeleteSnapshotRequest>,
+        mut request: Request<DeleteSnapshotRequest>,
     ) -> Result<Response<DeleteSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let DeleteSnapshotRequest {
             collection_name,
             snapshot_name,
         } = request.into_inner();
         let timing = Instant::now();
-        let _response =
-            do_delete_collection_snapshot(&self.dispatcher, &collection_name, &snapshot_name, true)
-                .await
-                .map_err(error_to_status)?;
+        let _response = do_delete_collection_snapshot(
+            &self.dispatcher,
+            &access,
+            &collection_name,
+            &snapshot_name,
+            true,
+        )
+        .await
+        .map_err(error_to_status)?;
         Ok(Response::new(DeleteSnapshotResponse {
             time: timing.elapsed().as_secs_f64(),
         }))
@@ -91,11 +100,12 @@ impl Snapshots for SnapshotsService {
 
     async fn create_full(
         &self,
-        request: Request<CreateFullSnapshotRequest>,
+        mut request: Request<CreateFullSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let timing = Instant::now();
-        let response = do_create_full_snapshot(&self.dispatcher, true)
+        let response = do_create_full_snapshot(&self.dispatcher, &access, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
@@ -106,11 +116,12 @@ impl Snapshots for SnapshotsService {
 
     async fn list_full(
         &self,
-        request: Request<ListFullSnapshotsRequest>,
+        mut request: Request<ListFullSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let timing = Instant::now();
-        let snapshots = do_list_full_snapshots(&self.dispatcher)
+        let snapshots = do_list_full_snapshots(&self.dispatcher, &access)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(ListSnapshotsResponse {
@@ -121,12 +132,13 @@ impl Snapshots for SnapshotsService {
 
     async fn delete_full(
         &self,
-        request: Request<DeleteFullSnapshotRequest>,
+        mut request: Request<DeleteFullSnapshotRequest>,
     ) -> Result<Response<DeleteSnapshotResponse>, Status> {
         validate(request.get_ref())?;
+        let access = extract_access(&mut request);
         let snapshot_name = request.into_inner().snapshot_name;
         let timing = Instant::now();
-        let _response = do_delete_full_snapshot(&self.dispatcher, &snapshot_name, true)
+        let _response = do_delete_full_snapshot(&self.dispatcher, &access, &snapshot_name, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(DeleteSnapshotResponse {
@@ -150,8 +162,9 @@ impl ShardSnapshotsService {
 impl ShardSnapshots for ShardSnapshotsService {
     async fn create(
         &self,
-        request: Request<CreateShardSnapshotRequest>,
+        mut request: Request<CreateShardSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
+        let access = extract_access(&mut request);
         let request = request.into_inner();
         validate_and_log(&request);
 
@@ -159,6 +172,7 @@ impl ShardSnapshots for ShardSnapshotsService {
 
         let snapshot_description = common::snapshots::create_shard_snapshot(
             self.toc.clone(),
+            access,
             request.collection_name,
             request.shard_id,
         )
@@ -173,8 +187,9 @@ impl ShardSnapshots for ShardSnapshotsService {
 
     async fn list(
         &self,
-        request: Request<ListShardSnapshotsRequest>,
+        mut request: Request<ListShardSnapshotsRequest>,
     ) -> Result<Response<ListSnapshotsResponse>, Status> {
+        let access = extract_access(&mut request);
         let request = request.into_inner();
         validate_and_log(&request);
 
@@ -182,6 +197,7 @@ impl ShardSnapshots for ShardSnapshotsService {
 
         let snapshot_descriptions = common::snapshots::list_shard_snapshots(
             self.toc.clone(),
+            access,
             request.collection_name,
             request.shard_id,
         )
@@ -196,8 +212,9 @@ impl ShardSnapshots for ShardSnapshotsService {
 
     async fn delete(
         &self,
-        request: Request<DeleteShardSnapshotRequest>,
+        mut request: Request<DeleteShardSnapshotRequest>,
     ) -> Result<Response<DeleteSnapshotResponse>, Status> {
+        let access = extract_access(&mut request);
         let request = request.into_inner();
         validate_and_log(&request);
 
@@ -205,6 +222,7 @@ impl ShardSnapshots for ShardSnapshotsService {
 
         common::snapshots::delete_shard_snapshot(
             self.toc.clone(),
+            access,
             request.collection_name,
             request.shard_id,
             request.snapshot_name,
@@ -219,8 +237,9 @@ impl ShardSnapshots for ShardSnapshotsService {
 
     async fn recover(
         &self,
-        request: Request<RecoverShardSnapshotRequest>,
+        mut request: Request<RecoverShardSnapshotRequest>,
     ) -> Result<Response<RecoverSnapshotResponse>, Status> {
+        let access = extract_access(&mut request);
         let request = request.into_inner();
         validate_and_log(&request);
 
@@ -228,6 +247,7 @@ impl ShardSnapshots for ShardSnapshotsService {
 
         common::snapshots::recover_shard_snapshot(
             self.toc.clone(),
+            access,
             request.collection_name,
             request.shard_id,
             request.snapshot_location.try_into()?,
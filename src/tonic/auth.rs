@@ -1,4 +1,4 @@
-use rbac::jwt::Claims;
+use rbac::access::Access;
 use tonic::Status;
 use tower::filter::{FilterLayer, Predicate};
 
@@ -7,7 +7,7 @@ use crate::common::strings::ct_eq;
 
 type Request = tonic::codegen::http::Request<tonic::transport::Body>;
 
-const READ_ONLY_RPC_PATHS: [&str; 14] = [
+const READ_ONLY_RPC_PATHS: [&str; 15] = [
     "/qdrant.Collections/CollectionExists",
     "/qdrant.Collections/List",
     "/qdrant.Collections/Get",
@@ -22,6 +22,7 @@ const READ_ONLY_RPC_PATHS: [&str; 14] = [
     "/qdrant.Points/RecommendBatch",
     "/qdrant.Points/Discover",
     "/qdrant.Points/DiscoverBatch",
+    "/qdrant.Points/Query",
 ];
 
 #[derive(Clone)]
@@ -43,14 +44,12 @@ impl Predicate<Request> for AuthMiddleware {
             |key| req.headers().get(key).and_then(|val| val.to_str().ok()),
             is_read_only(&req),
         ) {
-            Ok(claims) => {
-                if let Some(claims) = claims {
-                    let _previous = req.extensions_mut().insert::<Claims>(claims);
-                    debug_assert!(
-                        _previous.is_none(),
-                        "Previous claims should not exist in the request"
-                    );
-                }
+            Ok(access) => {
+                let _previous = req.extensions_mut().insert::<Access>(access);
+                debug_assert!(
+                    _previous.is_none(),
+                    "Previous access object should not exist in the request"
+                );
                 Ok(req)
             }
             Err(e) => Err(Box::new(Status::permission_denied(e))),
@@ -58,9 +57,11 @@ impl Predicate<Request> for AuthMiddleware {
     }
 }
 
-#[allow(dead_code)] // TODO(RBAC): will be used later
-pub fn extract_claims<R>(req: &mut tonic::Request<R>) -> Option<Claims> {
-    req.extensions_mut().remove::<Claims>()
+/// Access rights of the request, full access is granted if authentication is disabled
+pub fn extract_access<R>(req: &mut tonic::Request<R>) -> Access {
+    req.extensions_mut()
+        .remove::<Access>()
+        .unwrap_or_else(Access::full)
 }
 
 fn is_read_only<R>(req: &tonic::codegen::http::Request<R>) -> bool {
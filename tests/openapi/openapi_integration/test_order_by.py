@@ -335,3 +335,91 @@ def test_cannot_use_offset_with_order_by():
     )
     assert not response.ok
     assert response.status_code == 400
+
+
+def paginate_with_cursor(order_by, limit=23):
+    points = []
+    cursor = None
+
+    while True:
+        body = {"order_by": order_by, "limit": limit}
+        if cursor is not None:
+            body["cursor"] = cursor
+
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/scroll",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=body,
+        )
+        assert response.ok, response.json()
+
+        result = response.json()["result"]
+        assert len(result["points"]) <= limit
+        points.extend(result["points"])
+
+        cursor = result.get("next_page_cursor")
+        if cursor is None:
+            break
+
+    return points
+
+
+@pytest.mark.parametrize("direction", ["asc", "desc"])
+@pytest.mark.timeout(60)  # possibly break of an infinite loop
+def test_paginate_with_cursor(direction):
+    points = paginate_with_cursor({"key": "maybe_repeated_float", "direction": direction})
+
+    ids = [point["id"] for point in points]
+    assert len(ids) == total_points
+    assert len(set(ids)) == total_points
+
+    # Ties are broken by point id
+    keys = [(point["payload"]["maybe_repeated_float"], point["id"]) for point in points]
+    if direction == "asc":
+        assert keys == sorted(keys)
+    else:
+        assert keys == sorted(keys, key=lambda key: (-key[0], key[1]))
+
+
+@pytest.mark.timeout(60)  # possibly break of an infinite loop
+def test_paginate_multiple_keys_with_cursor():
+    points = paginate_with_cursor(
+        [
+            {"key": "city", "direction": "desc"},
+            {"key": "maybe_repeated_float", "direction": "asc"},
+        ]
+    )
+
+    ids = [point["id"] for point in points]
+    assert len(ids) == total_points
+    assert len(set(ids)) == total_points
+
+    for point in points:
+        assert point["order_values"] == [
+            point["payload"]["city"],
+            point["payload"]["maybe_repeated_float"],
+        ]
+
+    cities = [point["payload"]["city"] for point in points]
+    assert cities == sorted(cities, reverse=True)
+
+    keys = [
+        (point["payload"]["city"], point["payload"]["maybe_repeated_float"], point["id"])
+        for point in points
+    ]
+    assert keys == sorted(keys, key=lambda key: (key[0] == "London", key[1], key[2]))
+
+
+def test_cannot_use_cursor_without_order_by():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "cursor": "abc",
+            "limit": 10,
+        },
+    )
+    assert not response.ok
+    assert response.status_code == 400
@@ -230,6 +230,57 @@ def test_datetime_indexing():
         assert not response.ok
 
 
+def test_uuid_indexing():
+    uuid_key = "uuid_payload"
+    # create payload
+    set_payload({uuid_key: "f47ac10b-58cc-4372-a567-0e02b2c3d479"}, [1, 2])
+    set_payload({uuid_key: ["6ba7b810-9dad-11d1-80b4-00c04fd430c8", "F47AC10B58CC4372A5670E02B2C3D479"]}, [3])
+    set_payload({uuid_key: "not-a-uuid"}, [4])
+
+    # Create index
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": uuid_key,
+            "field_schema": "uuid"
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()[
+        'result']['payload_schema'][uuid_key]['data_type'] == "uuid"
+    assert response.json()['result']['payload_schema'][uuid_key]['points'] == 3
+
+    data = [
+        ({"value": "f47ac10b-58cc-4372-a567-0e02b2c3d479"}, [1, 2, 3]),
+        ({"any": ["6ba7b810-9dad-11d1-80b4-00c04fd430c8", "not-a-uuid"]}, [3]),
+        ({"except": ["f47ac10b-58cc-4372-a567-0e02b2c3d479"]}, [3]),
+    ]
+    for match, expected_ids in data:
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/scroll",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "with_vector": False,
+                "filter": {"must": [{"key": uuid_key, "match": match}]},
+            },
+        )
+        assert response.ok, response.json()
+
+        point_ids = [p["id"] for p in response.json()["result"]["points"]]
+        assert sorted(point_ids) == expected_ids
+
+
 def test_update_payload_on_indexed_field():
     keyword_field = "city"
 
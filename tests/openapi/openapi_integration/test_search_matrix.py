@@ -0,0 +1,63 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_matrix'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_matrix_pairs():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 5,
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.text
+
+    pairs = response.json()['result']['pairs']
+    assert len(pairs) == 10
+    sampled = {pair['a'] for pair in pairs}
+    assert len(sampled) == 5
+    for pair in pairs:
+        assert pair['a'] != pair['b']
+        assert pair['b'] in sampled
+
+
+def test_search_matrix_offsets():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/offsets',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "ids": [1, 2, 3, 4],
+            "filter": {
+                "must_not": [
+                    {
+                        "has_id": [4]
+                    }
+                ]
+            },
+        }
+    )
+    assert response.ok, response.text
+
+    result = response.json()['result']
+    assert sorted(result['ids']) == [1, 2, 3]
+    # Full pairwise matrix without the diagonal
+    assert len(result['offsets_row']) == 6
+    assert len(result['offsets_col']) == 6
+    assert len(result['scores']) == 6
+    for row, col in zip(result['offsets_row'], result['offsets_col']):
+        assert row != col
+
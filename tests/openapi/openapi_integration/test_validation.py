@@ -72,3 +72,58 @@ def test_validation_query_param():
     assert not response.ok
     assert 'Validation error' in response.json()["status"]["error"]
     assert 'timeout: value 0 invalid' in response.json()["status"]["error"]
+
+
+def test_validation_multivector_params():
+    multivector_collection_name = 'test_multivector_validation'
+
+    # Multi-vectors are always kept in RAM and are not quantized
+    for params in [
+        {"on_disk": True},
+        {"quantization_config": {"scalar": {"type": "int8"}}},
+        {"datatype": "uint8"},
+    ]:
+        response = request_with_validation(
+            api='/collections/{collection_name}',
+            method="PUT",
+            path_params={'collection_name': multivector_collection_name},
+            body={
+                "vectors": {
+                    "size": 4,
+                    "distance": "Dot",
+                    "multivector_config": {"comparator": "max_sim"},
+                    **params,
+                },
+            }
+        )
+        assert not response.ok
+        assert 'Validation error' in response.json()["status"]["error"]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': multivector_collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "multivector_config": {"comparator": "max_sim"},
+            },
+        }
+    )
+    assert response.ok
+
+    for params in [
+        {"on_disk": True},
+        {"quantization_config": {"scalar": {"type": "int8"}}},
+    ]:
+        response = request_with_validation(
+            api='/collections/{collection_name}',
+            method="PATCH",
+            path_params={'collection_name': multivector_collection_name},
+            body={"vectors": {"": params}},
+        )
+        assert not response.ok
+        assert 'Multi-vector' in response.json()["status"]["error"]
+
+    drop_collection(collection_name=multivector_collection_name)